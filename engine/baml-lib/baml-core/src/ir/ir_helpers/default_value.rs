@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use baml_types::{BamlValue, FieldType, LiteralValue, TypeValue};
+
+use crate::ir::{jinja_helpers::render_expression, Expression, Identifier};
+
+/// Evaluates the argument of `@default(...)` into a value of `field_type`.
+///
+/// Bare identifiers name enum values, and jinja expressions are evaluated
+/// without any context. `has_enum_value(enum, value)` tells whether an enum
+/// has the given value, so this can be used both before and after the IR is
+/// built.
+pub fn evaluate_default_value(
+    expr: &Expression,
+    field_type: &FieldType,
+    has_enum_value: &dyn Fn(&str, &str) -> bool,
+) -> Result<BamlValue> {
+    let value = match expr {
+        Expression::JinjaExpression(expr) => {
+            let rendered = render_expression(expr, &HashMap::new())?;
+            serde_json::from_str(&rendered).unwrap_or(BamlValue::String(rendered))
+        }
+        Expression::Identifier(Identifier::ENV(_)) => {
+            anyhow::bail!("Environment variables can't be used as default values")
+        }
+        _ => expr.normalize(&HashMap::new())?,
+    };
+    coerce_default_value(field_type, value, has_enum_value)
+}
+
+fn coerce_default_value(
+    field_type: &FieldType,
+    value: BamlValue,
+    has_enum_value: &dyn Fn(&str, &str) -> bool,
+) -> Result<BamlValue> {
+    match (field_type, value) {
+        (FieldType::Primitive(TypeValue::String), value @ BamlValue::String(_))
+        | (FieldType::Primitive(TypeValue::Int), value @ BamlValue::Int(_))
+        | (FieldType::Primitive(TypeValue::Float), value @ BamlValue::Float(_))
+        | (FieldType::Primitive(TypeValue::Bool), value @ BamlValue::Bool(_))
+        | (FieldType::Primitive(TypeValue::Null), value @ BamlValue::Null)
+        | (FieldType::Optional(_), value @ BamlValue::Null) => Ok(value),
+        (FieldType::Primitive(TypeValue::Float), BamlValue::Int(i)) => {
+            Ok(BamlValue::Float(i as f64))
+        }
+        (FieldType::Literal(literal), value) => match (literal, &value) {
+            (LiteralValue::String(l), BamlValue::String(v)) if l == v => Ok(value),
+            (LiteralValue::Int(l), BamlValue::Int(v)) if l == v => Ok(value),
+            (LiteralValue::Bool(l), BamlValue::Bool(v)) if l == v => Ok(value),
+            _ => anyhow::bail!("Expected {}, got {}", literal, describe(&value)),
+        },
+        (FieldType::Enum(name), BamlValue::String(v)) if has_enum_value(name, &v) => {
+            Ok(BamlValue::Enum(name.clone(), v))
+        }
+        (FieldType::List(item), BamlValue::List(values)) => Ok(BamlValue::List(
+            values
+                .into_iter()
+                .map(|v| coerce_default_value(item, v, has_enum_value))
+                .collect::<Result<_>>()?,
+        )),
+        (FieldType::Map(_, item), BamlValue::Map(entries)) => Ok(BamlValue::Map(
+            entries
+                .into_iter()
+                .map(|(k, v)| Ok((k, coerce_default_value(item, v, has_enum_value)?)))
+                .collect::<Result<_>>()?,
+        )),
+        (FieldType::Optional(inner), value) => coerce_default_value(inner, value, has_enum_value),
+        (FieldType::Constrained { base, .. }, value) => {
+            coerce_default_value(base, value, has_enum_value)
+        }
+        (FieldType::Union(options), value) => options
+            .iter()
+            .find_map(|option| coerce_default_value(option, value.clone(), has_enum_value).ok())
+            .ok_or_else(|| anyhow::anyhow!("Expected {}, got {}", field_type, describe(&value))),
+        (_, value) => anyhow::bail!("Expected {}, got {}", field_type, describe(&value)),
+    }
+}
+
+fn describe(value: &BamlValue) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| value.r#type())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use baml_types::JinjaExpression;
+
+    fn no_enums(_: &str, _: &str) -> bool {
+        false
+    }
+
+    #[test]
+    fn evaluates_literals() {
+        let int = FieldType::Primitive(TypeValue::Int);
+        assert_eq!(
+            evaluate_default_value(&Expression::Numeric("3".into()), &int, &no_enums).unwrap(),
+            BamlValue::Int(3)
+        );
+
+        let float = FieldType::Primitive(TypeValue::Float);
+        assert_eq!(
+            evaluate_default_value(&Expression::Numeric("3".into()), &float, &no_enums).unwrap(),
+            BamlValue::Float(3.0)
+        );
+
+        let optional = FieldType::Optional(Box::new(int.clone()));
+        assert_eq!(
+            evaluate_default_value(
+                &Expression::Identifier(Identifier::Local("null".into())),
+                &optional,
+                &no_enums
+            )
+            .unwrap(),
+            BamlValue::Null
+        );
+
+        assert!(
+            evaluate_default_value(&Expression::String("three".into()), &int, &no_enums).is_err()
+        );
+    }
+
+    #[test]
+    fn evaluates_enum_values() {
+        let has_enum_value = |name: &str, value: &str| name == "Color" && value == "Red";
+        let color = FieldType::Enum("Color".into());
+        assert_eq!(
+            evaluate_default_value(
+                &Expression::Identifier(Identifier::Local("Red".into())),
+                &color,
+                &has_enum_value
+            )
+            .unwrap(),
+            BamlValue::Enum("Color".into(), "Red".into())
+        );
+        assert!(evaluate_default_value(
+            &Expression::Identifier(Identifier::Local("Blue".into())),
+            &color,
+            &has_enum_value
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn evaluates_jinja_expressions() {
+        let list = FieldType::List(Box::new(FieldType::Primitive(TypeValue::Int)));
+        assert_eq!(
+            evaluate_default_value(
+                &Expression::JinjaExpression(JinjaExpression("[1, 1 + 1]".into())),
+                &list,
+                &no_enums
+            )
+            .unwrap(),
+            BamlValue::List(vec![BamlValue::Int(1), BamlValue::Int(2)])
+        );
+    }
+}
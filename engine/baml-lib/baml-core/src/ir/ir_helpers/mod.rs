@@ -1,3 +1,4 @@
+mod default_value;
 mod error_utils;
 pub mod scope_diagnostics;
 mod to_baml_arg;
@@ -17,6 +18,7 @@ use baml_types::{
     BamlMap, BamlValue, BamlValueWithMeta, Constraint, ConstraintLevel, FieldType, LiteralValue,
     TypeValue,
 };
+pub use default_value::evaluate_default_value;
 pub use to_baml_arg::ArgCoercer;
 
 use super::repr;
@@ -212,25 +214,33 @@ impl IRHelper for IntermediateRepr {
         value: BamlValue,
         field_type: FieldType,
     ) -> anyhow::Result<BamlValueWithMeta<FieldType>> {
+        if let FieldType::RecursiveTypeAlias(name) = &field_type {
+            let target = self.find_recursive_alias_target(name)?.clone();
+            return self.distribute_type(value, target);
+        }
+
         match value {
             BamlValue::String(s) => {
                 let literal_type = FieldType::Literal(LiteralValue::String(s.clone()));
                 let primitive_type = FieldType::Primitive(TypeValue::String);
 
-                if literal_type.is_subtype_of(&field_type)
-                    || primitive_type.is_subtype_of(&field_type)
+                if self.is_subtype(&literal_type, &field_type)
+                    || self.is_subtype(&primitive_type, &field_type)
                 {
                     return Ok(BamlValueWithMeta::String(s, field_type));
                 }
                 anyhow::bail!("Could not unify String with {:?}", field_type)
             }
             BamlValue::Int(i)
-                if FieldType::Literal(LiteralValue::Int(i.clone())).is_subtype_of(&field_type) =>
+                if self.is_subtype(
+                    &FieldType::Literal(LiteralValue::Int(i.clone())),
+                    &field_type,
+                ) =>
             {
                 Ok(BamlValueWithMeta::Int(i, field_type))
             }
             BamlValue::Int(i)
-                if FieldType::Primitive(TypeValue::Int).is_subtype_of(&field_type) =>
+                if self.is_subtype(&FieldType::Primitive(TypeValue::Int), &field_type) =>
             {
                 Ok(BamlValueWithMeta::Int(i, field_type))
             }
@@ -239,7 +249,7 @@ impl IRHelper for IntermediateRepr {
             }
 
             BamlValue::Float(f)
-                if FieldType::Primitive(TypeValue::Float).is_subtype_of(&field_type) =>
+                if self.is_subtype(&FieldType::Primitive(TypeValue::Float), &field_type) =>
             {
                 Ok(BamlValueWithMeta::Float(f, field_type))
             }
@@ -249,16 +259,18 @@ impl IRHelper for IntermediateRepr {
                 let literal_type = FieldType::Literal(LiteralValue::Bool(b));
                 let primitive_type = FieldType::Primitive(TypeValue::Bool);
 
-                if literal_type.is_subtype_of(&field_type) {
+                if self.is_subtype(&literal_type, &field_type) {
                     Ok(BamlValueWithMeta::Bool(b, field_type))
-                } else if primitive_type.is_subtype_of(&field_type) {
+                } else if self.is_subtype(&primitive_type, &field_type) {
                     Ok(BamlValueWithMeta::Bool(b, field_type))
                 } else {
                     anyhow::bail!("Could not unify Bool with {:?}", field_type)
                 }
             }
 
-            BamlValue::Null if FieldType::Primitive(TypeValue::Null).is_subtype_of(&field_type) => {
+            BamlValue::Null
+                if self.is_subtype(&FieldType::Primitive(TypeValue::Null), &field_type) =>
+            {
                 Ok(BamlValueWithMeta::Null(field_type))
             }
             BamlValue::Null => anyhow::bail!("Could not unify Null with {:?}", field_type),
@@ -282,7 +294,7 @@ impl IRHelper for IntermediateRepr {
                             Box::new(item_type.clone()),
                         );
 
-                        if !map_type.is_subtype_of(&field_type) {
+                        if !self.is_subtype(&map_type, &field_type) {
                             anyhow::bail!("Could not unify {:?} with {:?}", map_type, field_type);
                         } else {
                             let mapped_fields: BamlMap<String, BamlValueWithMeta<FieldType>> =
@@ -316,7 +328,7 @@ impl IRHelper for IntermediateRepr {
                     Some(item_type) => {
                         let list_type = FieldType::List(Box::new(item_type.clone()));
 
-                        if !list_type.is_subtype_of(&field_type) {
+                        if !self.is_subtype(&list_type, &field_type) {
                             anyhow::bail!("Could not unify {:?} with {:?}", list_type, field_type);
                         } else {
                             let mapped_items: Vec<BamlValueWithMeta<FieldType>> = items
@@ -330,15 +342,17 @@ impl IRHelper for IntermediateRepr {
             }
 
             BamlValue::Media(m)
-                if FieldType::Primitive(TypeValue::Media(m.media_type))
-                    .is_subtype_of(&field_type) =>
+                if self.is_subtype(
+                    &FieldType::Primitive(TypeValue::Media(m.media_type)),
+                    &field_type,
+                ) =>
             {
                 Ok(BamlValueWithMeta::Media(m, field_type))
             }
             BamlValue::Media(_) => anyhow::bail!("Could not unify Media with {:?}", field_type),
 
             BamlValue::Enum(name, val) => {
-                if FieldType::Enum(name.clone()).is_subtype_of(&field_type) {
+                if self.is_subtype(&FieldType::Enum(name.clone()), &field_type) {
                     Ok(BamlValueWithMeta::Enum(name, val, field_type))
                 } else {
                     anyhow::bail!("Could not unify Enum {} with {:?}", name, field_type)
@@ -346,7 +360,7 @@ impl IRHelper for IntermediateRepr {
             }
 
             BamlValue::Class(name, fields) => {
-                if !FieldType::Class(name.clone()).is_subtype_of(&field_type) {
+                if !self.is_subtype(&FieldType::Class(name.clone()), &field_type) {
                     anyhow::bail!("Could not unify Class {} with {:?}", name, field_type);
                 } else {
                     let class_type = &self.find_class(&name)?.item.elem;
@@ -402,6 +416,10 @@ impl IRHelper for IntermediateRepr {
                 Err(_) => (field_type, Vec::new()),
                 Ok(enum_node) => (field_type, enum_node.item.attributes.constraints.clone()),
             },
+            FieldType::RecursiveTypeAlias(name) => match self.find_recursive_alias_target(name) {
+                Err(_) => (field_type, Vec::new()),
+                Ok(target) => self.distribute_constraints(target),
+            },
             // Check the first level to see if it's constrained.
             FieldType::Constrained { base, constraints } => {
                 match base.as_ref() {
@@ -606,6 +624,34 @@ mod tests {
         assert_eq!(value.meta(), &field_type);
     }
 
+    #[test]
+    fn distribute_recursive_alias() {
+        let ir = make_test_ir(
+            r#"
+          type JsonValue = int | string | JsonValue[] | map<string, JsonValue>
+        "#,
+        )
+        .unwrap();
+        let json_value = FieldType::RecursiveTypeAlias("JsonValue".to_string());
+
+        let value = BamlValue::List(vec![
+            mk_int(1),
+            BamlValue::List(vec![BamlValue::String("a".to_string())]),
+        ]);
+        assert!(ir.distribute_type(value, json_value.clone()).is_ok());
+
+        let image = BamlValue::Media(BamlMedia {
+            media_type: BamlMediaType::Image,
+            mime_type: None,
+            content: BamlMediaContent::Base64(MediaBase64 {
+                base64: "abcd1234=".to_string(),
+            }),
+        });
+        assert!(ir
+            .distribute_type(BamlValue::List(vec![image]), json_value)
+            .is_err());
+    }
+
     #[test]
     fn distribute_list_of_maps() {
         let ir = mk_ir();
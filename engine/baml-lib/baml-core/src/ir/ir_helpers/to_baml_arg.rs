@@ -338,6 +338,12 @@ impl ArgCoercer {
                     }
                 }
             }
+            // Only reachable if the alias is missing from the IR, otherwise
+            // distribute_constraints resolves it to its target.
+            (FieldType::RecursiveTypeAlias(name), _) => {
+                scope.push_error(format!("Type alias {} not found", name));
+                Err(())
+            }
             (FieldType::Constrained { .. }, _) => {
                 unreachable!("The return value of distribute_constraints can never be FieldType::Constrainted");
             }
@@ -20,6 +20,11 @@ impl WithJsonSchema for IntermediateRepr {
         let classes = self
             .walk_classes()
             .map(|c| (c.elem().name.clone(), c.json_schema()));
+        let recursive_aliases = self
+            .structural_recursive_alias_cycles()
+            .iter()
+            .flatten()
+            .map(|(name, target)| (name.clone(), target.json_schema()));
         let function_inputs = self
             .walk_functions()
             .map(|f| (format!("{}_input", f.name()), (f.item, true).json_schema()));
@@ -33,6 +38,7 @@ impl WithJsonSchema for IntermediateRepr {
         // Combine all the definitions into one object of key-value pairs
         let definitions = enums
             .chain(classes)
+            .chain(recursive_aliases)
             .chain(function_inputs)
             .chain(function_outputs)
             .collect::<serde_json::Map<_, _>>();
@@ -156,7 +162,9 @@ impl WithJsonSchema for Walker<'_, &Class> {
 impl<'db> WithJsonSchema for FieldType {
     fn json_schema(&self) -> serde_json::Value {
         match self {
-            FieldType::Class(name) | FieldType::Enum(name) => json!({
+            FieldType::Class(name)
+            | FieldType::Enum(name)
+            | FieldType::RecursiveTypeAlias(name) => json!({
                 "$ref": format!("#/definitions/{}", name),
             }),
             FieldType::Literal(v) => json!({
@@ -1,20 +1,21 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, Result};
-use baml_types::{Constraint, ConstraintLevel, FieldType};
-use either::Either;
+use baml_types::{Constraint, ConstraintLevel, FieldType, StreamingBehavior};
 use indexmap::{IndexMap, IndexSet};
 use internal_baml_parser_database::{
     walkers::{
         ClassWalker, ClientSpec as AstClientSpec, ClientWalker, ConfigurationWalker,
         EnumValueWalker, EnumWalker, FieldWalker, FunctionWalker, TemplateStringWalker,
+        TypeAliasWalker, TypeWalker,
     },
-    Attributes, ParserDatabase, PromptAst, RetryPolicyStrategy,
+    Attributes, ExpectedOutput as ParserExpectedOutput, ParserDatabase, PromptAst,
+    RetryPolicyStrategy,
 };
 use internal_baml_schema_ast::ast::SubType;
 
 use baml_types::JinjaExpression;
-use internal_baml_schema_ast::ast::{self, FieldArity, WithName, WithSpan};
+use internal_baml_schema_ast::ast::{self, FieldArity, WithDocumentation, WithName, WithSpan};
 use serde::Serialize;
 
 use crate::Configuration;
@@ -29,6 +30,12 @@ pub struct IntermediateRepr {
     classes: Vec<Node<Class>>,
     /// Strongly connected components of the dependency graph (finite cycles).
     finite_recursive_cycles: Vec<IndexSet<String>>,
+    /// Type aliases that point to themselves through a list or a map, grouped
+    /// by cycle and resolved to the type they point to.
+    structural_recursive_alias_cycles: Vec<IndexMap<String, FieldType>>,
+    /// The other type aliases, resolved to the type they point to. Types that
+    /// use them are resolved in place, so only the names are kept here.
+    type_aliases: IndexMap<String, FieldType>,
     functions: Vec<Node<Function>>,
     clients: Vec<Node<Client>>,
     retry_policies: Vec<Node<RetryPolicy>>,
@@ -53,6 +60,8 @@ impl IntermediateRepr {
             enums: vec![],
             classes: vec![],
             finite_recursive_cycles: vec![],
+            structural_recursive_alias_cycles: vec![],
+            type_aliases: IndexMap::new(),
             functions: vec![],
             clients: vec![],
             retry_policies: vec![],
@@ -98,6 +107,33 @@ impl IntermediateRepr {
         &self.finite_recursive_cycles
     }
 
+    /// Returns a list of all the recursive type alias cycles in the IR.
+    ///
+    /// Each cycle maps the alias name to the type it resolves to.
+    pub fn structural_recursive_alias_cycles(&self) -> &[IndexMap<String, FieldType>] {
+        &self.structural_recursive_alias_cycles
+    }
+
+    /// The type aliases that aren't recursive, mapped to the type they resolve
+    /// to.
+    pub fn type_aliases(&self) -> &IndexMap<String, FieldType> {
+        &self.type_aliases
+    }
+
+    /// Finds the type a recursive alias points to.
+    pub fn find_recursive_alias_target(&self, name: &str) -> Result<&FieldType> {
+        self.structural_recursive_alias_cycles
+            .iter()
+            .find_map(|cycle| cycle.get(name))
+            .ok_or_else(|| anyhow!("Type alias {} not found", name))
+    }
+
+    /// Whether `sub` is a subtype of `sup`, expanding the recursive aliases
+    /// in either of them.
+    pub fn is_subtype(&self, sub: &FieldType, sup: &FieldType) -> bool {
+        sub.is_subtype_of_with_aliases(sup, &|name| self.find_recursive_alias_target(name).ok())
+    }
+
     pub fn walk_enums<'a>(&'a self) -> impl ExactSizeIterator<Item = Walker<'a, &'a Node<Enum>>> {
         self.enums.iter().map(|e| Walker { db: self, item: e })
     }
@@ -174,6 +210,23 @@ impl IntermediateRepr {
                         .collect()
                 })
                 .collect(),
+            structural_recursive_alias_cycles: db
+                .structural_recursive_alias_cycles()
+                .iter()
+                .map(|ids| {
+                    ids.iter()
+                        .map(|id| {
+                            let alias = db.walk(*id);
+                            Ok((alias.name().to_string(), alias.repr(db)?))
+                        })
+                        .collect::<Result<IndexMap<_, _>>>()
+                })
+                .collect::<Result<Vec<_>>>()?,
+            type_aliases: db
+                .walk_type_aliases()
+                .filter(|alias| !alias.is_recursive())
+                .map(|alias| Ok((alias.name().to_string(), alias.repr(db)?)))
+                .collect::<Result<IndexMap<_, _>>>()?,
             functions: db
                 .walk_functions()
                 .map(|e| e.node(db))
@@ -230,6 +283,9 @@ pub struct NodeAttributes {
     ///
     ///   - @skip becomes ("skip", bool)
     ///   - @alias(...) becomes ("alias", ...)
+    ///   - @stream.done becomes ("stream.done", bool), and likewise for the
+    ///     other @stream.* attributes
+    ///   - @default(...) becomes ("default", ...)
     #[serde(with = "indexmap::map::serde_seq")]
     meta: IndexMap<String, Expression>,
 
@@ -244,6 +300,16 @@ impl NodeAttributes {
     pub fn get(&self, key: &str) -> Option<&Expression> {
         self.meta.get(key)
     }
+
+    /// The @stream.* attributes on the node.
+    pub fn streaming(&self) -> StreamingBehavior {
+        let is_set = |key| matches!(self.get(key), Some(Expression::Bool(true)));
+        StreamingBehavior {
+            done: is_set("stream.done"),
+            not_null: is_set("stream.not_null"),
+            with_state: is_set("stream.with_state"),
+        }
+    }
 }
 
 impl Default for NodeAttributes {
@@ -268,6 +334,8 @@ fn to_ir_attributes(
             dynamic_type,
             skip,
             constraints,
+            streaming,
+            default_value,
         } = attributes;
         let description = description.as_ref().and_then(|d| {
             let name = "description".to_string();
@@ -303,8 +371,23 @@ fn to_ir_attributes(
             }
         });
 
-        let meta = vec![description, alias, dynamic_type, skip]
+        let default_value = default_value
+            .as_ref()
+            .and_then(|v| v.repr(db).ok())
+            .map(|v| ("default".to_string(), v));
+
+        let streaming = [
+            ("stream.done", streaming.done),
+            ("stream.not_null", streaming.not_null),
+            ("stream.with_state", streaming.with_state),
+        ]
+        .into_iter()
+        .filter(|(_, is_set)| *is_set)
+        .map(|(name, _)| Some((name.to_string(), Expression::Bool(true))));
+
+        let meta = vec![description, alias, dynamic_type, skip, default_value]
             .into_iter()
+            .chain(streaming)
             .filter_map(|s| s)
             .collect();
         (meta, constraints.clone())
@@ -413,7 +496,7 @@ impl WithRepr<FieldType> for ast::FieldType {
             }
             ast::FieldType::Symbol(arity, idn, ..) => type_with_arity(
                 match db.find_type(idn) {
-                    Some(Either::Left(class_walker)) => {
+                    Some(TypeWalker::Class(class_walker)) => {
                         let base_class = FieldType::Class(class_walker.name().to_string());
                         let maybe_constraints = class_walker.get_constraints(SubType::Class);
                         match maybe_constraints {
@@ -424,7 +507,7 @@ impl WithRepr<FieldType> for ast::FieldType {
                             _ => base_class,
                         }
                     }
-                    Some(Either::Right(enum_walker)) => {
+                    Some(TypeWalker::Enum(enum_walker)) => {
                         let base_type = FieldType::Enum(enum_walker.name().to_string());
                         let maybe_constraints = enum_walker.get_constraints(SubType::Enum);
                         match maybe_constraints {
@@ -435,6 +518,15 @@ impl WithRepr<FieldType> for ast::FieldType {
                             _ => base_type,
                         }
                     }
+                    // Recursive aliases are kept by name, everything else
+                    // resolves to the type the alias points to.
+                    Some(TypeWalker::TypeAlias(alias_walker)) => {
+                        if alias_walker.is_recursive() {
+                            FieldType::RecursiveTypeAlias(alias_walker.name().to_string())
+                        } else {
+                            alias_walker.target().repr(db)?
+                        }
+                    }
                     None => return Err(anyhow!("Field type uses unresolvable local identifier")),
                 },
                 arity,
@@ -492,6 +584,12 @@ impl WithRepr<FieldType> for ast::FieldType {
     }
 }
 
+impl WithRepr<FieldType> for TypeAliasWalker<'_> {
+    fn repr(&self, db: &ParserDatabase) -> Result<FieldType> {
+        self.target().repr(db)
+    }
+}
+
 #[derive(serde::Serialize, Debug)]
 pub enum Identifier {
     /// Starts with env.*
@@ -621,6 +719,7 @@ impl WithRepr<TemplateString> for TemplateStringWalker<'_> {
                             .map(|f| Field {
                                 name: id.name().to_string(),
                                 r#type: f,
+                                docstring: None,
                             })
                             .ok()
                     })
@@ -630,15 +729,31 @@ impl WithRepr<TemplateString> for TemplateStringWalker<'_> {
         })
     }
 }
+
+/// The `///` doc comment above (or after) a declaration.
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Docstring(pub String);
+
+impl Docstring {
+    fn from_documentation(documentation: Option<&str>) -> Option<Docstring> {
+        documentation
+            .map(str::trim)
+            .filter(|doc| !doc.is_empty())
+            .map(|doc| Docstring(doc.to_string()))
+    }
+}
+
 type EnumId = String;
 
+/// An enum value, and its doc comment.
 #[derive(serde::Serialize, Debug)]
-pub struct EnumValue(pub String);
+pub struct EnumValue(pub String, pub Option<Docstring>);
 
 #[derive(serde::Serialize, Debug)]
 pub struct Enum {
     pub name: EnumId,
     pub values: Vec<Node<EnumValue>>,
+    pub docstring: Option<Docstring>,
 }
 
 impl WithRepr<EnumValue> for EnumValueWalker<'_> {
@@ -654,7 +769,10 @@ impl WithRepr<EnumValue> for EnumValueWalker<'_> {
     }
 
     fn repr(&self, _db: &ParserDatabase) -> Result<EnumValue> {
-        Ok(EnumValue(self.name().to_string()))
+        Ok(EnumValue(
+            self.name().to_string(),
+            Docstring::from_documentation(self.documentation()),
+        ))
     }
 }
 
@@ -677,6 +795,7 @@ impl WithRepr<Enum> for EnumWalker<'_> {
                 .values()
                 .map(|v| v.node(db))
                 .collect::<Result<Vec<_>>>()?,
+            docstring: Docstring::from_documentation(self.ast_type_block().documentation()),
         })
     }
 }
@@ -685,6 +804,7 @@ impl WithRepr<Enum> for EnumWalker<'_> {
 pub struct Field {
     pub name: String,
     pub r#type: Node<FieldType>,
+    pub docstring: Option<Docstring>,
 }
 
 impl WithRepr<Field> for FieldWalker<'_> {
@@ -714,6 +834,7 @@ impl WithRepr<Field> for FieldWalker<'_> {
                     .repr(db)?,
                 attributes: self.attributes(db),
             },
+            docstring: Docstring::from_documentation(self.ast_field().documentation()),
         })
     }
 }
@@ -731,6 +852,9 @@ pub struct Class {
 
     /// Parameters to the class definition.
     pub inputs: Vec<(String, FieldType)>,
+
+    /// Doc comment of the class.
+    pub docstring: Option<Docstring>,
 }
 
 impl WithRepr<Class> for ClassWalker<'_> {
@@ -764,6 +888,7 @@ impl WithRepr<Class> for ClassWalker<'_> {
                     .collect::<Result<Vec<_>>>()?,
                 None => Vec::new(),
             },
+            docstring: Docstring::from_documentation(self.ast_type_block().documentation()),
         })
     }
 }
@@ -858,6 +983,7 @@ pub struct Function {
     pub tests: Vec<Node<TestCase>>,
     pub configs: Vec<FunctionConfig>,
     pub default_config: String,
+    pub docstring: Option<Docstring>,
 }
 
 #[derive(serde::Serialize, Debug)]
@@ -867,6 +993,8 @@ pub struct FunctionConfig {
     #[serde(skip)]
     pub prompt_span: ast::Span,
     pub client: ClientSpec,
+    /// Total time allowed for a call, across all retries and fallbacks.
+    pub timeout_ms: Option<u64>,
 }
 
 // NB(sam): we used to use this to bridge the wasm layer, but
@@ -999,12 +1127,14 @@ impl WithRepr<Function> for FunctionWalker<'_> {
                     Ok(spec) => ClientSpec::from(spec),
                     Err(e) => anyhow::bail!("{}", e.message()),
                 },
+                timeout_ms: self.metadata().timeout_ms,
             }],
             default_config: "default_config".to_string(),
             tests: self
                 .walk_tests()
                 .map(|e| e.node(db))
                 .collect::<Result<Vec<_>>>()?,
+            docstring: Docstring::from_documentation(self.ast_function().documentation()),
         })
     }
 }
@@ -1055,6 +1185,7 @@ pub struct RetryPolicy {
     pub name: RetryPolicyId,
     pub max_retries: u32,
     pub strategy: RetryPolicyStrategy,
+    pub retry_on_parse_failure: bool,
     // NB: the parser DB has a notion of "empty options" vs "no options"; we collapse
     // those here into an empty vec
     options: Vec<(String, Expression)>,
@@ -1074,6 +1205,7 @@ impl WithRepr<RetryPolicy> for ConfigurationWalker<'_> {
             name: RetryPolicyId(self.name().to_string()),
             max_retries: self.retry_policy().max_retries,
             strategy: self.retry_policy().strategy,
+            retry_on_parse_failure: self.retry_policy().retry_on_parse_failure,
             options: match &self.retry_policy().options {
                 Some(o) => o
                     .iter()
@@ -1099,6 +1231,27 @@ pub struct TestCase {
     pub name: String,
     pub functions: Vec<Node<TestCaseFunction>>,
     pub args: IndexMap<String, Expression>,
+    pub expected_output: Option<ExpectedOutput>,
+    pub max_latency_ms: Option<u64>,
+    pub max_total_tokens: Option<u64>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub enum ExpectedOutput {
+    Exact(Expression),
+    Partial(Expression),
+}
+
+impl ExpectedOutput {
+    pub fn expression(&self) -> &Expression {
+        match self {
+            ExpectedOutput::Exact(e) | ExpectedOutput::Partial(e) => e,
+        }
+    }
+
+    pub fn is_partial(&self) -> bool {
+        matches!(self, ExpectedOutput::Partial(_))
+    }
 }
 
 impl WithRepr<TestCaseFunction> for (&ConfigurationWalker<'_>, usize) {
@@ -1123,7 +1276,7 @@ impl WithRepr<TestCase> for ConfigurationWalker<'_> {
         NodeAttributes {
             meta: IndexMap::new(),
             span: Some(self.span().clone()),
-            constraints: Vec::new(),
+            constraints: self.test_case().constraints.clone(),
         }
     }
 
@@ -1140,6 +1293,15 @@ impl WithRepr<TestCase> for ConfigurationWalker<'_> {
                 .map(|(k, (_, v))| Ok((k.clone(), v.repr(db)?)))
                 .collect::<Result<IndexMap<_, _>>>()?,
             functions,
+            expected_output: match &self.test_case().expected_output {
+                Some(ParserExpectedOutput::Exact(e)) => Some(ExpectedOutput::Exact(e.repr(db)?)),
+                Some(ParserExpectedOutput::Partial(e)) => {
+                    Some(ExpectedOutput::Partial(e.repr(db)?))
+                }
+                None => None,
+            },
+            max_latency_ms: self.test_case().max_latency_ms,
+            max_total_tokens: self.test_case().max_total_tokens,
         })
     }
 }
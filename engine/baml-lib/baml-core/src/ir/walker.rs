@@ -1,5 +1,5 @@
 use anyhow::Result;
-use baml_types::BamlValue;
+use baml_types::{BamlValue, Constraint, StreamingBehavior};
 use indexmap::IndexMap;
 
 use internal_baml_parser_database::RetryPolicyStrategy;
@@ -11,7 +11,7 @@ use super::{
     Class, Client, Enum, EnumValue, Expression, Field, FunctionNode, IRHelper, Identifier, Impl,
     RetryPolicy, TemplateString, TestCase, Walker,
 };
-use crate::ir::jinja_helpers::render_expression;
+use crate::ir::{evaluate_default_value, jinja_helpers::render_expression};
 
 fn provider_to_env_vars(
     provider: &str,
@@ -112,6 +112,11 @@ impl<'a> Walker<'a, &'a FunctionNode> {
         self.elem().inputs()
     }
 
+    /// The `///` doc comment of the function.
+    pub fn docstring(&self) -> Option<&'a str> {
+        self.elem().docstring.as_ref().map(|d| d.0.as_str())
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
@@ -130,7 +135,7 @@ impl<'a> Walker<'a, &'a Enum> {
             .transpose()
     }
 
-    pub fn walk_values(&'a self) -> impl Iterator<Item = Walker<'a, &'a EnumValue>> {
+    pub fn walk_values(&self) -> impl Iterator<Item = Walker<'a, &'a EnumValue>> {
         self.item.elem.values.iter().map(|v| Walker {
             db: self.db,
             item: v,
@@ -149,10 +154,23 @@ impl<'a> Walker<'a, &'a Enum> {
             })
     }
 
+    pub fn description(&self, env_values: &HashMap<String, String>) -> Result<Option<String>> {
+        self.item
+            .attributes
+            .get("description")
+            .map(|v| v.as_string_value(env_values))
+            .transpose()
+    }
+
     pub fn elem(&self) -> &'a repr::Enum {
         &self.item.elem
     }
 
+    /// The `///` doc comment of the enum.
+    pub fn docstring(&self) -> Option<&'a str> {
+        self.elem().docstring.as_ref().map(|d| d.0.as_str())
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
@@ -186,6 +204,11 @@ impl<'a> Walker<'a, &'a EnumValue> {
             .map(|v| v.as_string_value(env_values))
             .transpose()
     }
+
+    /// The `///` doc comment of the value.
+    pub fn docstring(&self) -> Option<&'a str> {
+        self.item.elem.1.as_ref().map(|d| d.0.as_str())
+    }
 }
 
 impl Expression {
@@ -316,6 +339,23 @@ impl<'a> Walker<'a, (&'a FunctionNode, &'a TestCase)> {
             .collect()
     }
 
+    /// The `@@assert` and `@@check` attributes of the test.
+    pub fn constraints(&self) -> &'a Vec<Constraint> {
+        &self.item.1.attributes.constraints
+    }
+
+    /// The `expected` or `expected_partial` value of the test, resolved
+    /// against the environment.
+    pub fn expected_output(
+        &self,
+        env_values: &HashMap<String, String>,
+    ) -> Option<Result<BamlValue>> {
+        self.test_case()
+            .expected_output
+            .as_ref()
+            .map(|expected| expected.expression().normalize(env_values))
+    }
+
     pub fn function(&'a self) -> Walker<'a, &'a FunctionNode> {
         Walker {
             db: self.db,
@@ -337,7 +377,7 @@ impl<'a> Walker<'a, &'a Class> {
             .transpose()
     }
 
-    pub fn walk_fields(&'a self) -> impl Iterator<Item = Walker<'a, &'a Field>> {
+    pub fn walk_fields(&self) -> impl Iterator<Item = Walker<'a, &'a Field>> {
         self.item.elem.static_fields.iter().map(|f| Walker {
             db: self.db,
             item: f,
@@ -356,10 +396,28 @@ impl<'a> Walker<'a, &'a Class> {
             })
     }
 
+    pub fn description(&self, env_values: &HashMap<String, String>) -> Result<Option<String>> {
+        self.item
+            .attributes
+            .get("description")
+            .map(|v| v.as_string_value(env_values))
+            .transpose()
+    }
+
     pub fn elem(&self) -> &'a repr::Class {
         &self.item.elem
     }
 
+    /// The `///` doc comment of the class.
+    pub fn docstring(&self) -> Option<&'a str> {
+        self.elem().docstring.as_ref().map(|d| d.0.as_str())
+    }
+
+    /// `@@stream.done` on the class.
+    pub fn streaming_behavior(&self) -> StreamingBehavior {
+        self.item.attributes.streaming()
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
@@ -426,6 +484,10 @@ impl<'a> Walker<'a, &'a RetryPolicy> {
         &self.elem().strategy
     }
 
+    pub fn retry_on_parse_failure(&self) -> bool {
+        self.elem().retry_on_parse_failure
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
@@ -488,6 +550,27 @@ impl<'a> Walker<'a, &'a Field> {
             .transpose()
     }
 
+    /// The `@stream.*` attributes on the field.
+    pub fn streaming_behavior(&self) -> StreamingBehavior {
+        self.item.attributes.streaming()
+    }
+
+    /// The value of `@default(...)` on the field, if any.
+    pub fn default_value(&self) -> Option<Result<BamlValue>> {
+        self.item.attributes.get("default").map(|expr| {
+            evaluate_default_value(expr, self.r#type(), &|name, value| {
+                self.db
+                    .find_enum(name)
+                    .is_ok_and(|e| e.find_value(value).is_some())
+            })
+        })
+    }
+
+    /// The `///` doc comment of the field.
+    pub fn docstring(&self) -> Option<&'a str> {
+        self.item.elem.docstring.as_ref().map(|d| d.0.as_str())
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
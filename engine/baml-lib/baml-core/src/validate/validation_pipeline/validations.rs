@@ -5,6 +5,7 @@ mod cycle;
 mod enums;
 mod functions;
 mod template_strings;
+mod type_aliases;
 mod types;
 
 use super::context::Context;
@@ -12,6 +13,7 @@ use super::context::Context;
 pub(super) fn validate(ctx: &mut Context<'_>) {
     enums::validate(ctx);
     classes::validate(ctx);
+    type_aliases::validate(ctx);
     functions::validate(ctx);
     clients::validate(ctx);
     template_strings::validate(ctx);
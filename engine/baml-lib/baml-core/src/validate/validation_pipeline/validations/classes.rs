@@ -1,7 +1,11 @@
-use internal_baml_schema_ast::ast::{WithName, WithSpan};
+use internal_baml_parser_database::walkers::TypeWalker;
+use internal_baml_schema_ast::ast::{self, WithName, WithSpan};
 
 use super::types::validate_type;
-use crate::validate::validation_pipeline::context::Context;
+use crate::{
+    ir::{evaluate_default_value, repr::WithRepr},
+    validate::validation_pipeline::context::Context,
+};
 use internal_baml_diagnostics::DatamodelError;
 
 pub(super) fn validate(ctx: &mut Context<'_>) {
@@ -14,6 +18,13 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
             let field = c.ast_field();
             if let Some(ft) = &field.expr {
                 validate_type(ctx, &ft);
+
+                let default_value = c
+                    .get_default_attributes()
+                    .and_then(|attrs| attrs.default_value.as_ref());
+                if let Some(default_value) = default_value {
+                    validate_default_value(ctx, ft, default_value);
+                }
             }
         }
 
@@ -45,3 +56,26 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         defined_types.errors_mut().clear();
     }
 }
+
+/// Checks that the value given to `@default(...)` fits the type of the field.
+fn validate_default_value(
+    ctx: &mut Context<'_>,
+    field_type: &ast::FieldType,
+    default_value: &ast::Expression,
+) {
+    // Types that don't resolve are reported by `validate_type`.
+    let (Ok(field_type), Ok(expr)) = (field_type.repr(ctx.db), default_value.repr(ctx.db)) else {
+        return;
+    };
+
+    let has_enum_value = |name: &str, value: &str| match ctx.db.find_type_by_str(name) {
+        Some(TypeWalker::Enum(enm)) => enm.find_value(value).is_some(),
+        _ => false,
+    };
+    if let Err(e) = evaluate_default_value(&expr, &field_type, &has_enum_value) {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!("Invalid default value: {e}"),
+            default_value.span().clone(),
+        ));
+    }
+}
@@ -1,8 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
-use either::Either;
 use internal_baml_diagnostics::DatamodelError;
-use internal_baml_parser_database::Tarjan;
+use internal_baml_parser_database::{walkers::TypeWalker, Tarjan};
 use internal_baml_schema_ast::ast::{FieldType, TypeExpId, WithName, WithSpan};
 
 use crate::validate::validation_pipeline::context::Context;
@@ -67,8 +66,17 @@ fn insert_required_deps(
 ) {
     match field {
         FieldType::Symbol(arity, ident, _) if arity.is_required() => {
-            if let Some(Either::Left(class)) = ctx.db.find_type_by_str(ident.name()) {
-                deps.insert(class.id);
+            match ctx.db.find_type_by_str(ident.name()) {
+                Some(TypeWalker::Class(class)) => {
+                    deps.insert(class.id);
+                }
+                // Aliases are transparent, the class depends on whatever the
+                // alias points to. Alias cycles can only go through lists and
+                // maps, which we don't follow here, so this always terminates.
+                Some(TypeWalker::TypeAlias(alias)) => {
+                    insert_required_deps(id, alias.target(), ctx, deps);
+                }
+                _ => {}
             }
         }
 
@@ -2,10 +2,12 @@ use std::collections::HashSet;
 
 use crate::validate::validation_pipeline::context::Context;
 
-use either::Either;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
 
-use internal_baml_schema_ast::ast::{FieldType, TypeExpId, WithIdentifier, WithName, WithSpan};
+use internal_baml_parser_database::walkers::TypeWalker;
+use internal_baml_schema_ast::ast::{
+    FieldType, TypeAliasId, TypeExpId, WithIdentifier, WithName, WithSpan,
+};
 
 use super::types::validate_type;
 
@@ -227,6 +229,7 @@ pub(super) fn has_checks_nested(ctx: &Context<'_>, field_type: &FieldType) -> bo
 struct NestedChecks<'c> {
     ctx: &'c Context<'c>,
     visited: HashSet<TypeExpId>,
+    visited_aliases: HashSet<TypeAliasId>,
 }
 
 impl<'c> NestedChecks<'c> {
@@ -234,6 +237,7 @@ impl<'c> NestedChecks<'c> {
         Self {
             ctx,
             visited: HashSet::new(),
+            visited_aliases: HashSet::new(),
         }
     }
 
@@ -246,7 +250,7 @@ impl<'c> NestedChecks<'c> {
 
         match field_type {
             FieldType::Symbol(_, id, ..) => match self.ctx.db.find_type(id) {
-                Some(Either::Left(class_walker)) => {
+                Some(TypeWalker::Class(class_walker)) => {
                     // Stop recursion when dealing with recursive types.
                     if !self.visited.insert(class_walker.id) {
                         return false;
@@ -261,6 +265,14 @@ impl<'c> NestedChecks<'c> {
                             .map_or(false, |ft| self.has_checks_nested(&ft))
                     })
                 }
+                Some(TypeWalker::TypeAlias(alias_walker)) => {
+                    // Same as classes, aliases can point to themselves.
+                    if !self.visited_aliases.insert(alias_walker.id) {
+                        return false;
+                    }
+
+                    self.has_checks_nested(alias_walker.target())
+                }
                 _ => false,
             },
 
@@ -0,0 +1,24 @@
+use super::types::validate_type;
+use crate::validate::validation_pipeline::context::Context;
+use internal_baml_diagnostics::DatamodelError;
+use internal_baml_schema_ast::ast::WithName;
+
+pub(super) fn validate(ctx: &mut Context<'_>) {
+    for alias in ctx.db.walk_type_aliases() {
+        validate_type(ctx, alias.target());
+
+        // Things like @alias or @description belong to the fields that use
+        // the type, only constraints make sense on the type itself.
+        for attr in alias.target().attributes() {
+            if !["assert", "check"].contains(&attr.name()) {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Type aliases only support @assert and @check attributes, found @{}",
+                        attr.name()
+                    ),
+                    attr.span.clone(),
+                ));
+            }
+        }
+    }
+}
@@ -7,7 +7,6 @@ use std::{
 use serde::ser::SerializeMap;
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::media::BamlMediaType;
 use crate::{BamlMap, BamlMedia, ResponseCheck};
 
 #[derive(Clone, Debug, PartialEq)]
@@ -98,11 +97,7 @@ impl BamlValue {
                     format!("list<{}>", value_type)
                 }
             }
-            BamlValue::Media(m) => match m.media_type {
-                BamlMediaType::Image => "image",
-                BamlMediaType::Audio => "audio",
-            }
-            .into(),
+            BamlValue::Media(m) => m.media_type.to_string(),
             BamlValue::Enum(e, _) => format!("enum {}", e),
             BamlValue::Class(c, _) => format!("class {}", c),
             BamlValue::Null => "null".into(),
@@ -45,6 +45,10 @@ impl FieldType {
         FieldType::Class(name.to_string())
     }
 
+    pub fn recursive_type_alias(name: &str) -> Self {
+        FieldType::RecursiveTypeAlias(name.to_string())
+    }
+
     pub fn list(inner: FieldType) -> Self {
         FieldType::List(Box::new(inner))
     }
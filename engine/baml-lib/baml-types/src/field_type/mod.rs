@@ -23,6 +23,8 @@ impl TypeValue {
             "null" => Some(TypeValue::Null),
             "image" => Some(TypeValue::Media(BamlMediaType::Image)),
             "audio" => Some(TypeValue::Media(BamlMediaType::Audio)),
+            "pdf" => Some(TypeValue::Media(BamlMediaType::Pdf)),
+            "video" => Some(TypeValue::Media(BamlMediaType::Video)),
             _ => None,
         }
     }
@@ -36,8 +38,7 @@ impl std::fmt::Display for TypeValue {
             TypeValue::Float => write!(f, "float"),
             TypeValue::Bool => write!(f, "bool"),
             TypeValue::Null => write!(f, "null"),
-            TypeValue::Media(BamlMediaType::Image) => write!(f, "image"),
-            TypeValue::Media(BamlMediaType::Audio) => write!(f, "audio"),
+            TypeValue::Media(media_type) => write!(f, "{media_type}"),
         }
     }
 }
@@ -77,6 +78,10 @@ pub enum FieldType {
     Enum(String),
     Literal(LiteralValue),
     Class(String),
+    /// A type alias that references itself through a list or a map, like
+    /// `type JsonValue = int | JsonValue[]`. Non-recursive aliases are
+    /// expanded in place, so this only ever holds the recursive ones.
+    RecursiveTypeAlias(String),
     List(Box<FieldType>),
     Map(Box<FieldType>, Box<FieldType>),
     Union(Vec<FieldType>),
@@ -92,7 +97,9 @@ pub enum FieldType {
 impl std::fmt::Display for FieldType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            FieldType::Enum(name) | FieldType::Class(name) => {
+            FieldType::Enum(name)
+            | FieldType::Class(name)
+            | FieldType::RecursiveTypeAlias(name) => {
                 write!(f, "{}", name)
             }
             FieldType::Primitive(t) => write!(f, "{}", t),
@@ -161,35 +168,91 @@ impl FieldType {
     /// BAML types are subtypes of others, and we need to be able to test this
     /// when checking the types of values.
     ///
+    /// Recursive type aliases can't be expanded without the IR, so here an
+    /// alias is only a subtype of itself. Use
+    /// [`FieldType::is_subtype_of_with_aliases`] when the aliases are known.
+    ///
     /// For examples of pairs of types and their subtyping relationship, see
     /// this module's test suite.
     ///
     /// Consider renaming this to `is_assignable_to`.
     pub fn is_subtype_of(&self, other: &FieldType) -> bool {
+        self.is_subtype_of_with_aliases(other, &|_| None)
+    }
+
+    /// Same as [`FieldType::is_subtype_of`], expanding recursive type aliases
+    /// to the types `aliases` resolves them to.
+    pub fn is_subtype_of_with_aliases<'a>(
+        &self,
+        other: &FieldType,
+        aliases: &dyn Fn(&str) -> Option<&'a FieldType>,
+    ) -> bool {
+        self.subtype(other, aliases, &mut Vec::new())
+    }
+
+    fn subtype<'a>(
+        &self,
+        other: &FieldType,
+        aliases: &dyn Fn(&str) -> Option<&'a FieldType>,
+        visited: &mut Vec<(FieldType, FieldType)>,
+    ) -> bool {
         if self == other {
             true
         } else {
             if let FieldType::Union(items) = other {
-                if items.iter().any(|item| self.is_subtype_of(item)) {
+                if items
+                    .iter()
+                    .any(|item| self.subtype(item, aliases, visited))
+                {
                     return true;
                 }
             }
             match (self, other) {
+                // Expanding a recursive alias can lead back to the pair of
+                // types being compared, in which case we assume it holds and
+                // let the rest of the comparison decide. Same as the coercer
+                // does when it comes back to an alias it's already coercing to.
+                (FieldType::RecursiveTypeAlias(_), _) | (_, FieldType::RecursiveTypeAlias(_)) => {
+                    let pair = (self.clone(), other.clone());
+                    if visited.contains(&pair) {
+                        return true;
+                    }
+                    visited.push(pair);
+
+                    let self_target = match self {
+                        FieldType::RecursiveTypeAlias(name) => aliases(name),
+                        _ => Some(self),
+                    };
+                    let other_target = match other {
+                        FieldType::RecursiveTypeAlias(name) => aliases(name),
+                        _ => Some(other),
+                    };
+                    let is_subtype = match (self_target, other_target) {
+                        (Some(self_target), Some(other_target)) => {
+                            self_target.subtype(other_target, aliases, visited)
+                        }
+                        _ => false,
+                    };
+                    visited.pop();
+                    is_subtype
+                }
+
                 (FieldType::Primitive(TypeValue::Null), FieldType::Optional(_)) => true,
                 (FieldType::Optional(self_item), FieldType::Optional(other_item)) => {
-                    self_item.is_subtype_of(other_item)
+                    self_item.subtype(other_item, aliases, visited)
                 }
-                (_, FieldType::Optional(t)) => self.is_subtype_of(t),
+                (_, FieldType::Optional(t)) => self.subtype(t, aliases, visited),
                 (FieldType::Optional(_), _) => false,
 
                 // Handle types that nest other types.
                 (FieldType::List(self_item), FieldType::List(other_item)) => {
-                    self_item.is_subtype_of(other_item)
+                    self_item.subtype(other_item, aliases, visited)
                 }
                 (FieldType::List(_), _) => false,
 
                 (FieldType::Map(self_k, self_v), FieldType::Map(other_k, other_v)) => {
-                    other_k.is_subtype_of(self_k) && (**self_v).is_subtype_of(other_v)
+                    other_k.subtype(self_k, aliases, visited)
+                        && (**self_v).subtype(other_v, aliases, visited)
                 }
                 (FieldType::Map(_, _), _) => false,
 
@@ -202,41 +265,43 @@ impl FieldType {
                         base: other_base,
                         constraints: other_cs,
                     },
-                ) => self_base.is_subtype_of(other_base) && self_cs == other_cs,
-                (FieldType::Constrained { base, .. }, _) => base.is_subtype_of(other),
-                (_, FieldType::Constrained { base, .. }) => self.is_subtype_of(base),
+                ) => self_base.subtype(other_base, aliases, visited) && self_cs == other_cs,
+                (FieldType::Constrained { base, .. }, _) => base.subtype(other, aliases, visited),
+                (_, FieldType::Constrained { base, .. }) => self.subtype(base, aliases, visited),
                 (
                     FieldType::Literal(LiteralValue::Bool(_)),
                     FieldType::Primitive(TypeValue::Bool),
                 ) => true,
                 (FieldType::Literal(LiteralValue::Bool(_)), _) => {
-                    self.is_subtype_of(&FieldType::Primitive(TypeValue::Bool))
+                    self.subtype(&FieldType::Primitive(TypeValue::Bool), aliases, visited)
                 }
                 (
                     FieldType::Literal(LiteralValue::Int(_)),
                     FieldType::Primitive(TypeValue::Int),
                 ) => true,
                 (FieldType::Literal(LiteralValue::Int(_)), _) => {
-                    self.is_subtype_of(&FieldType::Primitive(TypeValue::Int))
+                    self.subtype(&FieldType::Primitive(TypeValue::Int), aliases, visited)
                 }
                 (
                     FieldType::Literal(LiteralValue::String(_)),
                     FieldType::Primitive(TypeValue::String),
                 ) => true,
                 (FieldType::Literal(LiteralValue::String(_)), _) => {
-                    self.is_subtype_of(&FieldType::Primitive(TypeValue::String))
+                    self.subtype(&FieldType::Primitive(TypeValue::String), aliases, visited)
                 }
 
                 (FieldType::Union(self_items), _) => self_items
                     .iter()
-                    .all(|self_item| self_item.is_subtype_of(other)),
+                    .all(|self_item| self_item.subtype(other, aliases, visited)),
 
                 (FieldType::Tuple(self_items), FieldType::Tuple(other_items)) => {
                     self_items.len() == other_items.len()
                         && self_items
                             .iter()
                             .zip(other_items)
-                            .all(|(self_item, other_item)| self_item.is_subtype_of(other_item))
+                            .all(|(self_item, other_item)| {
+                                self_item.subtype(other_item, aliases, visited)
+                            })
                 }
                 (FieldType::Tuple(_), _) => false,
 
@@ -333,9 +398,72 @@ mod tests {
         assert!(x.is_subtype_of(&y));
     }
 
+    fn mk_alias(name: &str) -> FieldType {
+        FieldType::RecursiveTypeAlias(name.to_string())
+    }
+
+    #[test]
+    fn subtype_unresolved_alias() {
+        let a = mk_alias("JsonValue");
+        assert!(a.is_subtype_of(&a));
+        assert!(!mk_int().is_subtype_of(&a));
+        assert!(!mk_list(mk_int()).is_subtype_of(&a));
+        assert!(!a.is_subtype_of(&mk_int()));
+    }
+
+    #[test]
+    fn subtype_recursive_alias() {
+        // type JsonValue = int | string | JsonValue[]
+        let json_value = mk_union(vec![mk_int(), mk_str(), mk_list(mk_alias("JsonValue"))]);
+        // type IntList = int | IntList[]
+        let int_list = mk_union(vec![mk_int(), mk_list(mk_alias("IntList"))]);
+        let aliases = |name: &str| match name {
+            "JsonValue" => Some(&json_value),
+            "IntList" => Some(&int_list),
+            _ => None,
+        };
+        let is_subtype = |x: &FieldType, y: &FieldType| x.is_subtype_of_with_aliases(y, &aliases);
+
+        let a = mk_alias("JsonValue");
+        assert!(is_subtype(&mk_int(), &a));
+        assert!(is_subtype(&mk_list(mk_list(mk_str())), &a));
+        assert!(!is_subtype(&mk_bool(), &a));
+        assert!(!is_subtype(&mk_list(mk_bool()), &a));
+        assert!(!is_subtype(&a, &mk_int()));
+
+        let b = mk_alias("IntList");
+        assert!(is_subtype(&b, &a));
+        assert!(!is_subtype(&a, &b));
+        assert!(!is_subtype(&mk_alias("Unknown"), &a));
+    }
+
     #[test]
     fn subtype_media() {
         let x = FieldType::Primitive(TypeValue::Media(BamlMediaType::Audio));
         assert!(x.is_subtype_of(&x));
+
+        let pdf = FieldType::Primitive(TypeValue::Media(BamlMediaType::Pdf));
+        let video = FieldType::Primitive(TypeValue::Media(BamlMediaType::Video));
+        assert!(pdf.is_subtype_of(&pdf));
+        assert!(video.is_subtype_of(&mk_union(vec![pdf.clone(), video.clone()])));
+        assert!(!pdf.is_subtype_of(&video));
+        assert!(!video.is_subtype_of(&x));
+    }
+
+    #[test]
+    fn media_type_names_round_trip() {
+        for name in ["image", "audio", "pdf", "video"] {
+            let t = TypeValue::from_str(name).unwrap();
+            assert!(matches!(t, TypeValue::Media(_)));
+            assert_eq!(t.to_string(), name);
+        }
+        assert_eq!(
+            BamlMediaType::Pdf.mime_type_from_extension("pdf"),
+            "application/pdf"
+        );
+        assert_eq!(
+            BamlMediaType::Video.mime_type_from_extension("mp4"),
+            "video/mp4"
+        );
     }
 }
@@ -20,6 +20,9 @@ pub enum GeneratorOutputType {
 
     #[strum(serialize = "ruby/sorbet")]
     RubySorbet,
+
+    #[strum(serialize = "rust")]
+    Rust,
 }
 
 impl GeneratorOutputType {
@@ -31,6 +34,7 @@ impl GeneratorOutputType {
             Self::PythonPydantic => GeneratorDefaultClientMode::Async,
             Self::Typescript => GeneratorDefaultClientMode::Async,
             Self::RubySorbet => GeneratorDefaultClientMode::Sync,
+            Self::Rust => GeneratorDefaultClientMode::Async,
         }
     }
 
@@ -41,6 +45,7 @@ impl GeneratorOutputType {
             Self::PythonPydantic => GeneratorDefaultClientMode::Sync,
             Self::Typescript => GeneratorDefaultClientMode::Async,
             Self::RubySorbet => GeneratorDefaultClientMode::Sync,
+            Self::Rust => GeneratorDefaultClientMode::Async,
         }
     }
 }
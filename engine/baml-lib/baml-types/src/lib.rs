@@ -2,6 +2,7 @@ mod constraint;
 mod map;
 mod media;
 mod minijinja;
+mod streaming;
 
 mod baml_value;
 mod field_type;
@@ -14,3 +15,4 @@ pub use generator::{GeneratorDefaultClientMode, GeneratorOutputType};
 pub use map::Map as BamlMap;
 pub use media::{BamlMedia, BamlMediaContent, BamlMediaType, MediaBase64, MediaUrl};
 pub use minijinja::JinjaExpression;
+pub use streaming::StreamingBehavior;
@@ -7,6 +7,20 @@ use std::{borrow::Cow, fmt, path::PathBuf};
 pub enum BamlMediaType {
     Image,
     Audio,
+    Pdf,
+    Video,
+}
+
+impl BamlMediaType {
+    /// The mime type to assume for a file with this extension, e.g.
+    /// "image/png" for `image { file "photo.png" }`.
+    pub fn mime_type_from_extension(&self, extension: &str) -> String {
+        match self {
+            // PDFs are the only documents, and their mime type isn't "pdf/pdf".
+            BamlMediaType::Pdf => format!("application/{extension}"),
+            _ => format!("{self}/{extension}"),
+        }
+    }
 }
 
 impl fmt::Display for BamlMediaType {
@@ -14,6 +28,8 @@ impl fmt::Display for BamlMediaType {
         match *self {
             BamlMediaType::Image => write!(f, "image"),
             BamlMediaType::Audio => write!(f, "audio"),
+            BamlMediaType::Pdf => write!(f, "pdf"),
+            BamlMediaType::Video => write!(f, "video"),
         }
     }
 }
@@ -28,8 +44,8 @@ impl fmt::Display for BamlMediaType {
 pub struct BamlMedia {
     pub media_type: BamlMediaType,
 
-    /// Explicitly specified by the 'media_type' field on img and audio structs in BAML files.
-    /// example: "image/png", "image/jpeg", "audio/mp3"
+    /// Explicitly specified by the 'media_type' field on media structs in BAML files.
+    /// example: "image/png", "image/jpeg", "audio/mp3", "application/pdf", "video/mp4"
     pub mime_type: Option<String>,
     pub content: BamlMediaContent,
 }
@@ -153,3 +169,28 @@ impl fmt::Debug for MediaBase64 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_type_from_extension() {
+        assert_eq!(
+            BamlMediaType::Image.mime_type_from_extension("png"),
+            "image/png"
+        );
+        assert_eq!(
+            BamlMediaType::Audio.mime_type_from_extension("mp3"),
+            "audio/mp3"
+        );
+        assert_eq!(
+            BamlMediaType::Pdf.mime_type_from_extension("pdf"),
+            "application/pdf"
+        );
+        assert_eq!(
+            BamlMediaType::Video.mime_type_from_extension("mp4"),
+            "video/mp4"
+        );
+    }
+}
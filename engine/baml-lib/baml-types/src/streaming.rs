@@ -0,0 +1,20 @@
+/// How a class, or a class field, shows up in partial results while the
+/// response is still streaming in. Set with the `@stream.*` attributes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize)]
+pub struct StreamingBehavior {
+    /// `@stream.done`: the value is left out until it is complete.
+    pub done: bool,
+    /// `@stream.not_null`: the enclosing object is held back until this field
+    /// has a value.
+    pub not_null: bool,
+    /// `@stream.with_state`: the value is wrapped with whether it is still
+    /// incomplete.
+    pub with_state: bool,
+}
+
+impl StreamingBehavior {
+    /// Whether streaming this value needs any special handling.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,107 @@
+/// How serious a diagnostic is. Errors stop the project from being built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+macro_rules! diagnostic_codes {
+    ($($(#[$doc:meta])* $variant:ident = ($code:literal, $name:literal),)*) => {
+        /// A stable identifier for a kind of diagnostic, for tools that need to
+        /// tell diagnostics apart without matching on the message.
+        ///
+        /// Codes are never renumbered or reused: add new ones at the end.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum DiagnosticCode {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl DiagnosticCode {
+            pub const ALL: &'static [DiagnosticCode] = &[$(DiagnosticCode::$variant,)*];
+
+            /// e.g. `E0012`
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(DiagnosticCode::$variant => $code,)*
+                }
+            }
+
+            /// e.g. `unknown-type`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(DiagnosticCode::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+diagnostic_codes! {
+    /// The source could not be parsed.
+    SyntaxError = ("E0001", "syntax-error"),
+    /// Any other error. More specific codes are preferred where they exist.
+    ValidationError = ("E0002", "validation-error"),
+    InvalidLiteral = ("E0003", "invalid-literal"),
+    MissingArgument = ("E0004", "missing-argument"),
+    ArgumentCountMismatch = ("E0005", "argument-count-mismatch"),
+    InvalidAttribute = ("E0006", "invalid-attribute"),
+    DuplicateAttribute = ("E0007", "duplicate-attribute"),
+    UnsupportedNativeType = ("E0008", "unsupported-native-type"),
+    ReservedName = ("E0009", "reserved-name"),
+    /// Two top-level declarations or tests share a name.
+    DuplicateDefinition = ("E0010", "duplicate-definition"),
+    /// A field, enum value or config key is declared twice.
+    DuplicateField = ("E0011", "duplicate-field"),
+    UnknownType = ("E0012", "unknown-type"),
+    /// A reference to a client, function or other named item that does not
+    /// exist.
+    UnknownName = ("E0013", "unknown-name"),
+    UnknownAttribute = ("E0014", "unknown-attribute"),
+    UnknownProperty = ("E0015", "unknown-property"),
+    UnknownArgument = ("E0016", "unknown-argument"),
+    DuplicateArgument = ("E0017", "duplicate-argument"),
+    TypeMismatch = ("E0018", "type-mismatch"),
+    MissingProperty = ("E0019", "missing-property"),
+    InvalidFunctionSyntax = ("E0020", "invalid-function-syntax"),
+    InvalidName = ("E0021", "invalid-name"),
+    UnsupportedListType = ("E0022", "unsupported-list-type"),
+    /// A prompt refers to a type that is not part of the function's output.
+    TypeNotUsedInPrompt = ("E0023", "type-not-used-in-prompt"),
+    /// Any other warning.
+    Warning = ("W0001", "warning"),
+    FieldWarning = ("W0002", "field-warning"),
+    UnknownTypeWarning = ("W0003", "unknown-type"),
+    TypeNotUsedInPromptWarning = ("W0004", "type-not-used-in-prompt"),
+    UnusedPromptVariable = ("W0005", "unused-prompt-variable"),
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique() {
+        let mut codes = DiagnosticCode::ALL
+            .iter()
+            .map(|c| c.code())
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), DiagnosticCode::ALL.len());
+    }
+}
@@ -119,34 +119,18 @@ impl Diagnostics {
     }
 
     pub fn adjust_spans(&mut self, position_mapping: &HashMap<usize, usize>) {
-        self.errors = self
-            .errors
-            .iter()
-            .map(|err| {
-                let new_start = *position_mapping
-                    .get(&err.span().start)
-                    .unwrap_or(&err.span().start);
-                let new_end = *position_mapping
-                    .get(&err.span().end)
-                    .unwrap_or(&err.span().end);
-                let new_span = Span::new(err.span().file.clone(), new_start, new_end);
-                DatamodelError::new(err.message().to_string(), new_span)
-            })
-            .collect();
-
-        self.warnings = self
-            .warnings
-            .iter()
-            .map(|warn| {
-                let new_start = *position_mapping
-                    .get(&warn.span().start)
-                    .unwrap_or(&warn.span().start);
-                let new_end = *position_mapping
-                    .get(&warn.span().end)
-                    .unwrap_or(&warn.span().end);
-                let new_span = Span::new(warn.span().file.clone(), new_start, new_end);
-                DatamodelWarning::new(warn.message().to_string(), new_span)
-            })
-            .collect();
+        let adjust = |span: &Span| {
+            Span::new(
+                span.file.clone(),
+                *position_mapping.get(&span.start).unwrap_or(&span.start),
+                *position_mapping.get(&span.end).unwrap_or(&span.end),
+            )
+        };
+        self.errors
+            .iter_mut()
+            .for_each(|err| err.map_spans(&adjust));
+        self.warnings
+            .iter_mut()
+            .for_each(|warn| warn.map_spans(&adjust));
     }
 }
@@ -1,16 +1,20 @@
 use colored::{ColoredString, Colorize};
 
 use crate::{
+    fix::did_you_mean,
     pretty_print::{pretty_print, DiagnosticColorer},
-    Span,
+    DiagnosticCode, Fix, RelatedSpan, Severity, Span,
 };
 use std::iter::Iterator;
 use std::{borrow::Cow, ops::Index};
 
 #[derive(Debug, Clone)]
 pub struct DatamodelError {
+    code: DiagnosticCode,
     span: Span,
     message: Cow<'static, str>,
+    related: Vec<RelatedSpan>,
+    fixes: Vec<Fix>,
 }
 
 /// Sorts a collection of strings based on their similarity to a given name.
@@ -64,17 +68,52 @@ where
 }
 
 impl DatamodelError {
-    pub(crate) fn new(message: impl Into<Cow<'static, str>>, span: Span) -> Self {
+    pub(crate) fn new(
+        code: DiagnosticCode,
+        message: impl Into<Cow<'static, str>>,
+        span: Span,
+    ) -> Self {
         let message = message.into();
-        DatamodelError { message, span }
+        DatamodelError {
+            code,
+            message,
+            span,
+            related: vec![],
+            fixes: vec![],
+        }
+    }
+
+    /// Overrides the code of a generic error, e.g. one created with
+    /// [`DatamodelError::new_validation_error`].
+    pub fn with_code(mut self, code: DiagnosticCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push(RelatedSpan {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    fn with_fixes(mut self, fixes: Vec<Fix>) -> Self {
+        self.fixes.extend(fixes);
+        self
     }
 
     pub fn new_anyhow_error(error: anyhow::Error, span: Span) -> Self {
-        Self::new(format!("{error:#}"), span)
+        Self::new(DiagnosticCode::ValidationError, format!("{error:#}"), span)
     }
 
     pub fn new_static(message: &'static str, span: Span) -> Self {
-        Self::new(message, span)
+        Self::new(DiagnosticCode::ValidationError, message, span)
     }
 
     pub fn new_literal_parser_error(
@@ -83,13 +122,18 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         Self::new(
+            DiagnosticCode::InvalidLiteral,
             format!("\"{raw_value}\" is not a valid value for {literal_type}."),
             span,
         )
     }
 
     pub fn new_argument_not_found_error(argument_name: &str, span: Span) -> DatamodelError {
-        Self::new(format!("Argument \"{argument_name}\" is missing."), span)
+        Self::new(
+            DiagnosticCode::MissingArgument,
+            format!("Argument \"{argument_name}\" is missing."),
+            span,
+        )
     }
 
     pub fn new_argument_count_mismatch_error(
@@ -99,7 +143,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("Function \"{function_name}\" takes {required_count} arguments, but received {given_count}.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::ArgumentCountMismatch, msg, span)
     }
 
     pub fn new_attribute_argument_not_found_error(
@@ -108,6 +152,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         Self::new(
+            DiagnosticCode::MissingArgument,
             format!("Argument \"{argument_name}\" is missing in attribute \"@{attribute_name}\"."),
             span,
         )
@@ -119,6 +164,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         Self::new(
+            DiagnosticCode::MissingArgument,
             format!(
                 "Argument \"{argument_name}\" is missing in generator block \"{generator_name}\"."
             ),
@@ -132,6 +178,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         Self::new(
+            DiagnosticCode::InvalidAttribute,
             format!("Error parsing attribute \"{attribute_name}\": {message}"),
             span,
         )
@@ -139,7 +186,7 @@ impl DatamodelError {
 
     pub fn new_duplicate_attribute_error(attribute_name: &str, span: Span) -> DatamodelError {
         let msg = format!("Attribute \"@{attribute_name}\" can only be defined once.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateAttribute, msg, span)
     }
 
     pub fn new_incompatible_native_type(
@@ -151,7 +198,7 @@ impl DatamodelError {
         let msg = format!(
             "Native type {native_type} is not compatible with declared field type {field_type}, expected field type {expected_types}.",
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::UnsupportedNativeType, msg, span)
     }
 
     pub fn new_invalid_native_type_argument(
@@ -162,7 +209,7 @@ impl DatamodelError {
     ) -> DatamodelError {
         let msg =
             format!("Invalid argument for type {native_type}: {got}. Allowed values: {expected}.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::UnsupportedNativeType, msg, span)
     }
 
     pub fn new_invalid_prefix_for_native_types(
@@ -172,22 +219,22 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg =  format!("The prefix {given_prefix} is invalid. It must be equal to the name of an existing datasource e.g. {expected_prefix}. Did you mean to use {suggestion}?");
-        DatamodelError::new(msg, span)
+        Self::new(DiagnosticCode::UnsupportedNativeType, msg, span)
     }
 
     pub fn new_native_types_not_supported(connector_name: String, span: Span) -> DatamodelError {
         let msg = format!("Native types are not supported with {connector_name} connector");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::UnsupportedNativeType, msg, span)
     }
 
     pub fn new_reserved_scalar_type_error(type_name: &str, span: Span) -> DatamodelError {
         let msg = format!("\"{type_name}\" is a reserved scalar type name and cannot be used.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::ReservedName, msg, span)
     }
 
     pub fn new_duplicate_enum_database_name_error(span: Span) -> DatamodelError {
         let msg = "An enum with the same database name is already defined.";
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_duplicate_model_database_name_error(
@@ -196,7 +243,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("The model with database name \"{model_database_name}\" could not be defined because another model or view with this name exists: \"{existing_model_name}\"");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_duplicate_view_database_name_error(
@@ -205,7 +252,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("The view with database name \"{model_database_name}\" could not be defined because another model or view with this name exists: \"{existing_model_name}\"");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_duplicate_test_error(
@@ -215,7 +262,7 @@ impl DatamodelError {
     ) -> DatamodelError {
         let msg =
             format!("Test \"{test_name}\" is already defined for function \"{function_name}\".");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_duplicate_top_error(
@@ -227,7 +274,7 @@ impl DatamodelError {
         let msg = format!(
             "The {top_type} \"{name}\" cannot be defined because a {existing_top_type} with that name already exists.",
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_duplicate_config_key_error(
@@ -236,32 +283,34 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("Key \"{key_name}\" is already defined in {conf_block_name}.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateField, msg, span)
     }
 
     pub fn new_duplicate_argument_error(arg_name: &str, span: Span) -> DatamodelError {
         Self::new(
+            DiagnosticCode::DuplicateArgument,
             format!("Argument \"{arg_name}\" is already specified."),
             span,
         )
     }
 
     pub fn new_unused_argument_error(span: Span) -> DatamodelError {
-        Self::new("No such argument.", span)
+        Self::new(DiagnosticCode::UnknownArgument, "No such argument.", span)
     }
 
     pub fn new_duplicate_default_argument_error(arg_name: &str, span: Span) -> DatamodelError {
         let msg = format!("Argument \"{arg_name}\" is already specified as unnamed argument.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateArgument, msg, span)
     }
 
     pub fn new_duplicate_function_errors(func_name: &str, span: Span) -> DatamodelError {
         let msg = format!("Function \"{func_name}\" is already specified for this test case.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateDefinition, msg, span)
     }
 
     pub fn new_invalid_function_syntax_error(func_name: &str, span: Span) -> DatamodelError {
         Self::new(
+            DiagnosticCode::InvalidFunctionSyntax,
             format!("Invalid syntax for function \"{func_name}\". Use:\nfunction {func_name}(params...) -> ReturnType {{ ... }}"),
             span,
         )
@@ -273,7 +322,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("Value \"{value_name}\" is already defined on enum \"{enum_name}\".",);
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateField, msg, span)
     }
 
     pub fn new_composite_type_duplicate_field_error(
@@ -285,7 +334,7 @@ impl DatamodelError {
             "Field \"{}\" is already defined on {} \"{}\".",
             field_name, "composite type", type_name
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateField, msg, span)
     }
 
     pub fn new_duplicate_field_error(
@@ -296,7 +345,7 @@ impl DatamodelError {
     ) -> DatamodelError {
         let msg =
             format!("Field \"{field_name}\" is already defined on {container} \"{model_name}\".",);
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::DuplicateField, msg, span)
     }
 
     pub fn new_scalar_list_fields_are_not_supported(
@@ -306,7 +355,7 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         let msg = format!("Field \"{field_name}\" in {container} \"{container_name}\" can't be a list. The current connector does not support lists of primitive types.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::UnsupportedListType, msg, span)
     }
 
     pub fn new_model_validation_error(
@@ -316,17 +365,23 @@ impl DatamodelError {
         span: Span,
     ) -> DatamodelError {
         Self::new(
+            DiagnosticCode::ValidationError,
             format!("Error validating {block_type} \"{model_name}\": {message}"),
             span,
         )
     }
 
     pub fn new_name_error(_type: &str, message: &str, span: Span) -> DatamodelError {
-        Self::new(format!("Invalid name for `{_type}`: {message}"), span)
+        Self::new(
+            DiagnosticCode::InvalidName,
+            format!("Invalid name for `{_type}`: {message}"),
+            span,
+        )
     }
 
     pub fn new_enum_validation_error(message: &str, enum_name: &str, span: Span) -> DatamodelError {
         Self::new(
+            DiagnosticCode::ValidationError,
             format!("Error validating enum `{enum_name}`: {message}"),
             span,
         )
@@ -342,7 +397,7 @@ impl DatamodelError {
             "Error validating field `{}` in {} `{}`: {}",
             field, "composite type", composite_type_name, message
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::ValidationError, msg, span)
     }
 
     pub fn new_field_validation_error(
@@ -355,25 +410,30 @@ impl DatamodelError {
         let msg = format!(
             "Error validating field `{field}` in {container_type} `{container_name}`: {message}",
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::ValidationError, msg, span)
     }
 
     pub fn new_source_validation_error(message: &str, source: &str, span: Span) -> DatamodelError {
         Self::new(
+            DiagnosticCode::ValidationError,
             format!("Error validating datasource `{source}`: {message}"),
             span,
         )
     }
 
     pub fn new_validation_error(message: &str, span: Span) -> DatamodelError {
-        Self::new(format!("Error validating: {message}"), span)
+        Self::new(
+            DiagnosticCode::ValidationError,
+            format!("Error validating: {message}"),
+            span,
+        )
     }
 
     pub fn new_legacy_parser_error(
         message: impl Into<Cow<'static, str>>,
         span: Span,
     ) -> DatamodelError {
-        Self::new(message.into(), span)
+        Self::new(DiagnosticCode::SyntaxError, message.into(), span)
     }
 
     pub fn new_optional_argument_count_mismatch(
@@ -386,11 +446,12 @@ impl DatamodelError {
             "Native type {native_type} takes {optional_count} optional arguments, but received {given_count}.",
         );
 
-        DatamodelError::new(msg, span)
+        Self::new(DiagnosticCode::ArgumentCountMismatch, msg, span)
     }
 
     pub fn new_parser_error(expected_str: String, span: Span) -> DatamodelError {
         Self::new(
+            DiagnosticCode::SyntaxError,
             format!("Unexpected token. Expected one of: {expected_str}"),
             span,
         )
@@ -400,7 +461,7 @@ impl DatamodelError {
         message: impl Into<Cow<'static, str>>,
         span: Span,
     ) -> DatamodelError {
-        Self::new(message.into(), span)
+        Self::new(DiagnosticCode::ValidationError, message.into(), span)
     }
 
     pub fn not_found_error(
@@ -417,6 +478,8 @@ impl DatamodelError {
             "string".to_string(),
             "image".to_string(),
             "audio".to_string(),
+            "pdf".to_string(),
+            "video".to_string(),
             "null".to_string(),
         ];
         names.extend(primitives);
@@ -438,7 +501,13 @@ impl DatamodelError {
             )
         };
 
-        Self::new(format!("{type_name} {name} not found.{suggestions}"), span)
+        let fixes = did_you_mean(name, &span, &close_names);
+        Self::new(
+            DiagnosticCode::UnknownName,
+            format!("{type_name} {name} not found.{suggestions}"),
+            span,
+        )
+        .with_fixes(fixes)
     }
 
     pub fn type_not_used_in_prompt_error(
@@ -489,7 +558,13 @@ impl DatamodelError {
             )
         };
 
-        Self::new(format!("{}{}", prefix, suggestions), span)
+        let fixes = did_you_mean(type_name, &span, &close_names);
+        Self::new(
+            DiagnosticCode::TypeNotUsedInPrompt,
+            format!("{}{}", prefix, suggestions),
+            span,
+        )
+        .with_fixes(fixes)
     }
 
     pub fn new_type_not_found_error(
@@ -517,11 +592,16 @@ impl DatamodelError {
             )
         };
 
-        Self::new(msg, span)
+        let fixes = did_you_mean(type_name, &span, &close_names);
+        Self::new(DiagnosticCode::UnknownType, msg, span).with_fixes(fixes)
     }
 
     pub fn new_attribute_not_known_error(attribute_name: &str, span: Span) -> DatamodelError {
-        Self::new(format!("Attribute not known: \"@{attribute_name}\"."), span)
+        Self::new(
+            DiagnosticCode::UnknownAttribute,
+            format!("Attribute not known: \"@{attribute_name}\"."),
+            span,
+        )
     }
 
     pub fn new_property_not_known_error<I, T>(
@@ -535,8 +615,9 @@ impl DatamodelError {
         for<'a> &'a I: IntoIterator<Item = &'a T>,
     {
         let close_names = sort_by_match(property_name, &alternatives, None);
+        let fixes = did_you_mean(property_name, &span, &close_names);
 
-        Self::new(match close_names.len() {
+        Self::new(DiagnosticCode::UnknownProperty, match close_names.len() {
             0 => format!("Property not known: \"{property_name}\".",),
             1 =>
                 format!(
@@ -549,15 +630,20 @@ impl DatamodelError {
                     close_names = close_names.join("\", \"")
                 ),
         }, span)
+        .with_fixes(fixes)
     }
 
     pub fn new_argument_not_known_error(property_name: &str, span: Span) -> DatamodelError {
-        Self::new(format!("Argument not known: \"{property_name}\"."), span)
+        Self::new(
+            DiagnosticCode::UnknownArgument,
+            format!("Argument not known: \"{property_name}\"."),
+            span,
+        )
     }
 
     pub fn new_value_parser_error(expected_type: &str, raw: &str, span: Span) -> DatamodelError {
         let msg = format!("Expected {expected_type}, but found {raw}.");
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::TypeMismatch, msg, span)
     }
 
     pub fn new_type_mismatch_error(
@@ -569,7 +655,7 @@ impl DatamodelError {
         let msg = format!(
             "Expected a {expected_type} value, but received {received_type} value `{raw}`."
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::TypeMismatch, msg, span)
     }
 
     pub fn new_missing_required_property_error(
@@ -579,7 +665,7 @@ impl DatamodelError {
     ) -> DatamodelError {
         let msg =
             format!("The required property \"{property_name}\" on \"{object_name}\" is missing.",);
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::MissingProperty, msg, span)
     }
 
     pub fn new_config_property_missing_value_error(
@@ -591,7 +677,7 @@ impl DatamodelError {
         let msg = format!(
             "Property {property_name} in {config_kind} {config_name} needs to be assigned a value"
         );
-        Self::new(msg, span)
+        Self::new(DiagnosticCode::MissingProperty, msg, span)
     }
 
     pub fn span(&self) -> &Span {
@@ -602,6 +688,29 @@ impl DatamodelError {
         &self.message
     }
 
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
+    pub fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    pub fn related(&self) -> &[RelatedSpan] {
+        &self.related
+    }
+
+    pub fn fixes(&self) -> &[Fix] {
+        &self.fixes
+    }
+
+    /// Moves every span of the error, including related spans and fixes.
+    pub(crate) fn map_spans(&mut self, f: &impl Fn(&Span) -> Span) {
+        self.span = f(&self.span);
+        self.related.iter_mut().for_each(|r| r.map_span(f));
+        self.fixes.iter_mut().for_each(|fix| fix.map_spans(f));
+    }
+
     pub fn pretty_print(&self, f: &mut dyn std::io::Write) -> std::io::Result<()> {
         pretty_print(
             f,
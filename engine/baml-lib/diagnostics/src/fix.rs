@@ -0,0 +1,60 @@
+use crate::Span;
+
+/// Another location that helps explain a diagnostic, e.g. the first
+/// definition of a duplicated name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedSpan {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A change that resolves a diagnostic, e.g. replacing a misspelled type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+impl Fix {
+    pub fn replace(message: impl Into<String>, span: Span, new_text: impl Into<String>) -> Fix {
+        Fix {
+            message: message.into(),
+            edits: vec![TextEdit {
+                span,
+                new_text: new_text.into(),
+            }],
+        }
+    }
+
+    pub(crate) fn map_spans(&mut self, f: &impl Fn(&Span) -> Span) {
+        for edit in &mut self.edits {
+            edit.span = f(&edit.span);
+        }
+    }
+}
+
+/// One fix per "did you mean" suggestion, replacing `name` with it.
+///
+/// `span` is not always exactly the name, e.g. a quoted client name, so we
+/// only suggest edits when it is.
+pub(crate) fn did_you_mean(name: &str, span: &Span, suggestions: &[&str]) -> Vec<Fix> {
+    if span.file.as_str().get(span.start..span.end) != Some(name) {
+        return vec![];
+    }
+    suggestions
+        .iter()
+        .map(|s| Fix::replace(format!("Replace with `{s}`"), span.clone(), *s))
+        .collect()
+}
+
+impl RelatedSpan {
+    pub(crate) fn map_span(&mut self, f: &impl Fn(&Span) -> Span) {
+        self.span = f(&self.span);
+    }
+}
@@ -1,12 +1,16 @@
+mod code;
 mod collection;
 mod error;
+mod fix;
 mod pretty_print;
 mod source_file;
 mod span;
 mod warning;
 
+pub use code::{DiagnosticCode, Severity};
 pub use collection::Diagnostics;
 pub use error::DatamodelError;
+pub use fix::{Fix, RelatedSpan, TextEdit};
 pub use source_file::SourceFile;
 pub use span::Span;
 pub use warning::DatamodelWarning;
@@ -1,7 +1,8 @@
 use crate::{
     error::sort_by_match,
+    fix::did_you_mean,
     pretty_print::{pretty_print, DiagnosticColorer},
-    Span,
+    DiagnosticCode, Fix, RelatedSpan, Severity, Span,
 };
 use colored::{ColoredString, Colorize};
 // use indoc::indoc;
@@ -10,15 +11,47 @@ use colored::{ColoredString, Colorize};
 /// For fancy printing, please use the `pretty_print_error` function.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DatamodelWarning {
+    code: DiagnosticCode,
     message: String,
     span: Span,
+    related: Vec<RelatedSpan>,
+    fixes: Vec<Fix>,
 }
 
 impl DatamodelWarning {
     /// You should avoid using this constructor directly when possible, and define warnings as public methods of this class.
     /// The constructor is only left public for supporting connector-specific warnings (which should not live in the core).
     pub fn new(message: String, span: Span) -> DatamodelWarning {
-        DatamodelWarning { message, span }
+        DatamodelWarning {
+            code: DiagnosticCode::Warning,
+            message,
+            span,
+            related: vec![],
+            fixes: vec![],
+        }
+    }
+
+    pub fn with_code(mut self, code: DiagnosticCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push(RelatedSpan {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    fn with_fixes(mut self, fixes: Vec<Fix>) -> Self {
+        self.fixes.extend(fixes);
+        self
     }
 
     pub fn new_field_validation(
@@ -32,7 +65,7 @@ impl DatamodelWarning {
             field, "model", model, message
         );
 
-        Self::new(msg, span)
+        Self::new(msg, span).with_code(DiagnosticCode::FieldWarning)
     }
 
     pub fn new_type_not_found_error(
@@ -60,7 +93,10 @@ impl DatamodelWarning {
             )
         };
 
+        let fixes = did_you_mean(type_name, &span, &close_names);
         Self::new(msg, span)
+            .with_code(DiagnosticCode::UnknownTypeWarning)
+            .with_fixes(fixes)
     }
 
     pub fn type_not_used_in_prompt_error(
@@ -141,11 +177,19 @@ impl DatamodelWarning {
             )
         };
 
+        let close_names = close_names
+            .iter()
+            .take(3)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let fixes = did_you_mean(type_name, &span, &close_names);
         Self::new(format!("{}{}", prefix, suggestions), span)
+            .with_code(DiagnosticCode::TypeNotUsedInPromptWarning)
+            .with_fixes(fixes)
     }
 
     pub fn prompt_variable_unused(message: &str, span: Span) -> DatamodelWarning {
-        Self::new(message.to_string(), span)
+        Self::new(message.to_string(), span).with_code(DiagnosticCode::UnusedPromptVariable)
     }
 
     /// The user-facing warning message.
@@ -158,6 +202,29 @@ impl DatamodelWarning {
         &self.span
     }
 
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
+    pub fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    pub fn related(&self) -> &[RelatedSpan] {
+        &self.related
+    }
+
+    pub fn fixes(&self) -> &[Fix] {
+        &self.fixes
+    }
+
+    /// Moves every span of the warning, including related spans and fixes.
+    pub(crate) fn map_spans(&mut self, f: &impl Fn(&Span) -> Span) {
+        self.span = f(&self.span);
+        self.related.iter_mut().for_each(|r| r.map_span(f));
+        self.fixes.iter_mut().for_each(|fix| fix.map_spans(f));
+    }
+
     pub fn pretty_print(&self, f: &mut dyn std::io::Write) -> std::io::Result<()> {
         pretty_print(
             f,
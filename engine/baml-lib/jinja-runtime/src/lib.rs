@@ -5,6 +5,7 @@ mod chat_message_part;
 mod output_format;
 use internal_baml_core::ir::jinja_helpers::get_env;
 use internal_baml_core::ir::repr::IntermediateRepr;
+pub use output_format::json_schema;
 pub use output_format::types;
 mod baml_value_to_jinja_value;
 
@@ -952,6 +953,68 @@ mod render_tests {
         Ok(())
     }
 
+    #[test]
+    fn render_output_format_style() -> anyhow::Result<()> {
+        setup_logging();
+
+        let args: BamlValue = BamlValue::Map(BamlMap::new());
+
+        let ir = make_test_ir(
+            "
+            class C {
+                
+            }
+            ",
+        )?;
+
+        let ctx = || RenderContext {
+            client: RenderContext_Client {
+                name: "gpt4".to_string(),
+                provider: "openai".to_string(),
+                default_role: "system".to_string(),
+            },
+            output_format: OutputFormatContent::new_array(),
+            tags: HashMap::new(),
+        };
+
+        let rendered = render_prompt(
+            "{{ ctx.output_format(style='yaml', prefix='YAML:\n') }}",
+            &args,
+            ctx(),
+            &vec![],
+            &ir,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            rendered,
+            RenderedPrompt::Completion("YAML:\n- string\n# more items as needed".to_string())
+        );
+
+        // Options for BAML's own syntax don't mix with other styles.
+        let rendered = render_prompt(
+            "{{ ctx.output_format(style='xml', or_splitter=' | ') }}",
+            &args,
+            ctx(),
+            &vec![],
+            &ir,
+            &HashMap::new(),
+        );
+        assert!(rendered.is_err());
+
+        let rendered = render_prompt(
+            "{{ ctx.output_format(style='toml') }}",
+            &args,
+            ctx(),
+            &vec![],
+            &ir,
+            &HashMap::new(),
+        );
+        assert!(rendered.is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn render_chat_param_failures() -> anyhow::Result<()> {
         setup_logging();
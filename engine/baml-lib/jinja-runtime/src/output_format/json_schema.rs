@@ -0,0 +1,394 @@
+use baml_types::{FieldType, LiteralValue, TypeValue};
+use indexmap::IndexSet;
+use serde_json::{json, Map, Value};
+
+use super::types::{Class, Enum, OutputFormatContent};
+
+/// The flavour of schema a provider accepts for tool parameters and
+/// structured outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonSchemaDialect {
+    /// Plain JSON schema. Classes and recursive aliases are emitted once in
+    /// `$defs` and referenced with `$ref`. Used by OpenAI, Anthropic and
+    /// Bedrock.
+    JsonSchema,
+    /// The OpenAPI 3.0 subset Gemini understands: no `$ref` and no `null`
+    /// type. Definitions are inlined, optional values are marked `nullable`,
+    /// and recursion is cut off with an untyped object.
+    OpenApi,
+}
+
+impl OutputFormatContent {
+    /// Describes the target type as a schema, using the rendered (aliased)
+    /// names of classes, fields and enum values so that whatever the model
+    /// returns can be parsed the same way as a prompt based response.
+    pub fn json_schema(&self, dialect: JsonSchemaDialect) -> Value {
+        let mut builder = SchemaBuilder {
+            content: self,
+            dialect,
+            defs: Map::new(),
+            inlining: IndexSet::new(),
+        };
+
+        // The root of the schema is always expanded in place, providers
+        // expect an object at the top level rather than a reference.
+        let mut schema = match strip_constraints(&self.target) {
+            FieldType::Class(name) => match self.classes.get(name) {
+                Some(class) => builder.inline_class(name, class),
+                None => json!({ "type": "object" }),
+            },
+            other => builder.field_type(other),
+        };
+
+        if !builder.defs.is_empty() {
+            if let Value::Object(obj) = &mut schema {
+                obj.insert("$defs".into(), Value::Object(builder.defs));
+            }
+        }
+        schema
+    }
+}
+
+struct SchemaBuilder<'a> {
+    content: &'a OutputFormatContent,
+    dialect: JsonSchemaDialect,
+    defs: Map<String, Value>,
+    /// Classes and aliases currently being inlined, used to detect recursion
+    /// in the [`JsonSchemaDialect::OpenApi`] dialect.
+    inlining: IndexSet<String>,
+}
+
+impl SchemaBuilder<'_> {
+    fn field_type(&mut self, field_type: &FieldType) -> Value {
+        match field_type {
+            FieldType::Primitive(t) => primitive(t),
+            FieldType::Literal(v) => self.literal(v),
+            FieldType::Enum(name) => match self.content.enums.get(name) {
+                Some(enm) => enum_schema(enm),
+                None => json!({ "type": "string" }),
+            },
+            FieldType::Class(name) => match self.content.classes.get(name) {
+                Some(class) => self.class_ref(name, class),
+                None => json!({ "type": "object" }),
+            },
+            FieldType::RecursiveTypeAlias(name) => {
+                match self.content.structural_recursive_aliases.get(name) {
+                    Some(target) => self.alias_ref(name, target),
+                    None => json!({ "type": "object" }),
+                }
+            }
+            FieldType::List(inner) => json!({
+                "type": "array",
+                "items": self.field_type(inner),
+            }),
+            FieldType::Tuple(items) => {
+                let items = items.iter().map(|t| self.field_type(t)).collect();
+                json!({
+                    "type": "array",
+                    "items": self.any_of(items),
+                })
+            }
+            FieldType::Map(_, value) => match self.dialect {
+                JsonSchemaDialect::JsonSchema => json!({
+                    "type": "object",
+                    "additionalProperties": self.field_type(value),
+                }),
+                // Gemini has no way to describe free-form keys.
+                JsonSchemaDialect::OpenApi => json!({ "type": "object" }),
+            },
+            FieldType::Optional(inner) => self.nullable(inner),
+            FieldType::Union(options) => {
+                let (nulls, options): (Vec<_>, Vec<_>) = options.iter().partition(|t| t.is_null());
+                let schema = match options.as_slice() {
+                    [] => primitive(&TypeValue::Null),
+                    [single] => self.field_type(single),
+                    _ => {
+                        let options = options.iter().map(|t| self.field_type(t)).collect();
+                        self.any_of(options)
+                    }
+                };
+                if nulls.is_empty() {
+                    schema
+                } else {
+                    self.with_null(schema)
+                }
+            }
+            FieldType::Constrained { base, .. } => self.field_type(base),
+        }
+    }
+
+    fn nullable(&mut self, inner: &FieldType) -> Value {
+        let schema = self.field_type(inner);
+        self.with_null(schema)
+    }
+
+    fn with_null(&self, mut schema: Value) -> Value {
+        match self.dialect {
+            JsonSchemaDialect::JsonSchema => json!({ "anyOf": [schema, { "type": "null" }] }),
+            JsonSchemaDialect::OpenApi => {
+                if let Value::Object(obj) = &mut schema {
+                    obj.insert("nullable".into(), Value::Bool(true));
+                }
+                schema
+            }
+        }
+    }
+
+    fn literal(&self, v: &LiteralValue) -> Value {
+        match (v, self.dialect) {
+            (LiteralValue::String(s), _) => json!({ "type": "string", "enum": [s] }),
+            (LiteralValue::Int(i), JsonSchemaDialect::JsonSchema) => {
+                json!({ "type": "integer", "enum": [i] })
+            }
+            (LiteralValue::Bool(b), JsonSchemaDialect::JsonSchema) => {
+                json!({ "type": "boolean", "enum": [b] })
+            }
+            // OpenAPI only allows `enum` on strings.
+            (LiteralValue::Int(_), JsonSchemaDialect::OpenApi) => json!({ "type": "integer" }),
+            (LiteralValue::Bool(_), JsonSchemaDialect::OpenApi) => json!({ "type": "boolean" }),
+        }
+    }
+
+    fn any_of(&self, options: Vec<Value>) -> Value {
+        json!({ "anyOf": options })
+    }
+
+    fn class_ref(&mut self, name: &str, class: &Class) -> Value {
+        let key = class.name.rendered_name().to_string();
+        match self.dialect {
+            JsonSchemaDialect::JsonSchema => {
+                if !self.defs.contains_key(&key) {
+                    // Reserve the slot first so recursive references
+                    // terminate.
+                    self.defs.insert(key.clone(), Value::Null);
+                    let schema = self.inline_class(name, class);
+                    self.defs.insert(key.clone(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{key}") })
+            }
+            JsonSchemaDialect::OpenApi => {
+                if self.inlining.contains(name) {
+                    return json!({ "type": "object" });
+                }
+                self.inline_class(name, class)
+            }
+        }
+    }
+
+    fn alias_ref(&mut self, name: &str, target: &FieldType) -> Value {
+        match self.dialect {
+            JsonSchemaDialect::JsonSchema => {
+                if !self.defs.contains_key(name) {
+                    self.defs.insert(name.to_string(), Value::Null);
+                    let schema = self.field_type(target);
+                    self.defs.insert(name.to_string(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            JsonSchemaDialect::OpenApi => {
+                if self.inlining.contains(name) {
+                    return json!({ "type": "object" });
+                }
+                self.inlining.insert(name.to_string());
+                let schema = self.field_type(target);
+                self.inlining.shift_remove(name);
+                schema
+            }
+        }
+    }
+
+    fn inline_class(&mut self, name: &str, class: &Class) -> Value {
+        self.inlining.insert(name.to_string());
+
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for (field_name, field_type, description) in &class.fields {
+            let mut schema = self.field_type(field_type);
+            if let (Some(description), Value::Object(obj)) = (description, &mut schema) {
+                obj.insert("description".into(), description.trim().into());
+            }
+            if !field_type.is_optional() {
+                required.push(Value::from(field_name.rendered_name()));
+            }
+            properties.insert(field_name.rendered_name().to_string(), schema);
+        }
+
+        self.inlining.shift_remove(name);
+
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        if self.dialect == JsonSchemaDialect::JsonSchema {
+            schema["additionalProperties"] = Value::Bool(false);
+        }
+        schema
+    }
+}
+
+fn strip_constraints(field_type: &FieldType) -> &FieldType {
+    match field_type {
+        FieldType::Constrained { base, .. } => strip_constraints(base),
+        other => other,
+    }
+}
+
+fn primitive(t: &TypeValue) -> Value {
+    match t {
+        TypeValue::String | TypeValue::Media(_) => json!({ "type": "string" }),
+        TypeValue::Int => json!({ "type": "integer" }),
+        TypeValue::Float => json!({ "type": "number" }),
+        TypeValue::Bool => json!({ "type": "boolean" }),
+        TypeValue::Null => json!({ "type": "null" }),
+    }
+}
+
+fn enum_schema(enm: &Enum) -> Value {
+    let values = enm
+        .values
+        .iter()
+        .map(|(name, _)| Value::from(name.rendered_name()))
+        .collect::<Vec<_>>();
+
+    let descriptions = enm
+        .values
+        .iter()
+        .filter_map(|(name, description)| {
+            description
+                .as_ref()
+                .map(|d| format!("{}: {}", name.rendered_name(), d.trim()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut schema = json!({ "type": "string", "enum": values });
+    if !descriptions.is_empty() {
+        schema["description"] = descriptions.join("\n").into();
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::types::Name;
+    use super::*;
+
+    fn person() -> Class {
+        Class {
+            name: Name::new("Person".to_string()),
+            fields: vec![
+                (
+                    Name::new("name".to_string()),
+                    FieldType::string(),
+                    Some("The person's name".to_string()),
+                ),
+                (
+                    Name::new_with_alias("age".to_string(), Some("years".to_string())),
+                    FieldType::optional(FieldType::int()),
+                    None,
+                ),
+            ],
+            constraints: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn class_root_is_inlined() {
+        let content = OutputFormatContent::target(FieldType::class("Person"))
+            .classes(vec![person()])
+            .build();
+
+        assert_eq!(
+            content.json_schema(JsonSchemaDialect::JsonSchema),
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "The person's name" },
+                    "years": { "anyOf": [{ "type": "integer" }, { "type": "null" }] },
+                },
+                "required": ["name"],
+                "additionalProperties": false,
+            })
+        );
+        assert_eq!(
+            content.json_schema(JsonSchemaDialect::OpenApi),
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "The person's name" },
+                    "years": { "type": "integer", "nullable": true },
+                },
+                "required": ["name"],
+            })
+        );
+    }
+
+    #[test]
+    fn nested_classes_use_defs() {
+        let content = OutputFormatContent::target(FieldType::list(FieldType::class("Person")))
+            .classes(vec![person()])
+            .build();
+
+        let schema = content.json_schema(JsonSchemaDialect::JsonSchema);
+        assert_eq!(schema["items"], json!({ "$ref": "#/$defs/Person" }));
+        assert_eq!(schema["$defs"]["Person"]["required"], json!(["name"]));
+    }
+
+    #[test]
+    fn recursive_class_is_cut_off_for_openapi() {
+        let node = Class {
+            name: Name::new("Node".to_string()),
+            fields: vec![(
+                Name::new("children".to_string()),
+                FieldType::list(FieldType::class("Node")),
+                None,
+            )],
+            constraints: Vec::new(),
+        };
+        let content = OutputFormatContent::target(FieldType::class("Node"))
+            .classes(vec![node])
+            .build();
+
+        assert_eq!(
+            content.json_schema(JsonSchemaDialect::OpenApi)["properties"]["children"],
+            json!({ "type": "array", "items": { "type": "object" } })
+        );
+        let schema = content.json_schema(JsonSchemaDialect::JsonSchema);
+        assert_eq!(
+            schema["properties"]["children"]["items"],
+            json!({ "$ref": "#/$defs/Node" })
+        );
+        assert_eq!(schema["$defs"]["Node"]["type"], json!("object"));
+    }
+
+    #[test]
+    fn enums_and_literals() {
+        let color = Enum {
+            name: Name::new("Color".to_string()),
+            values: vec![
+                (Name::new("Red".to_string()), Some("Warm".to_string())),
+                (
+                    Name::new_with_alias("Blue".to_string(), Some("B".to_string())),
+                    None,
+                ),
+            ],
+            constraints: Vec::new(),
+        };
+        let content = OutputFormatContent::target(FieldType::union(vec![
+            FieldType::Enum("Color".to_string()),
+            FieldType::literal_string("none".to_string()),
+        ]))
+        .enums(vec![color])
+        .build();
+
+        assert_eq!(
+            content.json_schema(JsonSchemaDialect::JsonSchema),
+            json!({
+                "anyOf": [
+                    { "type": "string", "enum": ["Red", "B"], "description": "Red: Warm" },
+                    { "type": "string", "enum": ["none"] },
+                ]
+            })
+        );
+    }
+}
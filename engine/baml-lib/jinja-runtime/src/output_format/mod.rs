@@ -1,3 +1,5 @@
+pub mod json_schema;
+mod styles;
 pub mod types;
 
 use std::str::FromStr;
@@ -131,6 +133,20 @@ impl minijinja::value::Object for OutputFormat {
                 None
             };
 
+        let show_defaults = if kwargs.has("show_defaults") {
+            match kwargs.get::<bool>("show_defaults") {
+                Ok(show_defaults) => Some(show_defaults),
+                Err(e) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!("Invalid value for show_defaults (expected bool): {}", e),
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
         let map_style = if kwargs.has("map_style") {
             match kwargs
                 .get::<String>("map_style")
@@ -162,21 +178,72 @@ impl minijinja::value::Object for OutputFormat {
             None
         };
 
+        let style = if kwargs.has("style") {
+            match kwargs
+                .get::<String>("style")
+                .map(|s| types::OutputFormatStyle::from_str(s.as_str()))
+            {
+                Ok(Ok(style)) => style,
+                Ok(Err(e)) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!(
+                            "Invalid value for style (expected one of {}): {}",
+                            types::OutputFormatStyle::VARIANTS.join(", "),
+                            e
+                        ),
+                    ))
+                }
+                Err(e) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!(
+                            "Invalid value for style (expected one of {}): {}",
+                            types::OutputFormatStyle::VARIANTS.join(", "),
+                            e
+                        ),
+                    ))
+                }
+            }
+        } else {
+            types::OutputFormatStyle::Baml
+        };
+
         let Ok(_) = kwargs.assert_all_used() else {
             return Err(Error::new(
                 ErrorKind::TooManyArguments,
-                "output_format() got an unexpected keyword argument (only 'prefix', 'always_host_enums', 'enum_value_prefix', and 'or_splitter' are allowed)",
+                "output_format() got an unexpected keyword argument (only 'prefix', 'always_host_enums', 'enum_value_prefix', 'or_splitter', 'map_style', 'hoisted_class_prefix', 'show_defaults' and 'style' are allowed)",
             ));
         };
 
-        let content = self.text.render(RenderOptions::new(
-            prefix,
-            or_splitter,
-            enum_value_prefix,
-            always_hoist_enums,
-            map_style,
-            hoisted_class_prefix,
-        ))?;
+        let content = if style == types::OutputFormatStyle::Baml {
+            self.text.render(RenderOptions::new(
+                prefix,
+                or_splitter,
+                enum_value_prefix,
+                always_hoist_enums,
+                map_style,
+                hoisted_class_prefix,
+                show_defaults,
+            ))?
+        } else {
+            // The remaining options only tweak BAML's own syntax.
+            let baml_only = [
+                ("or_splitter", or_splitter.is_some()),
+                ("enum_value_prefix", enum_value_prefix.is_some()),
+                ("always_hoist_enums", always_hoist_enums.is_some()),
+                ("map_style", map_style.is_some()),
+                ("hoisted_class_prefix", hoisted_class_prefix.is_some()),
+                ("show_defaults", show_defaults.is_some()),
+            ];
+            if let Some((name, _)) = baml_only.iter().find(|(_, given)| *given) {
+                return Err(Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("output_format() argument '{name}' only applies to style='baml'"),
+                ));
+            }
+            self.text.render_style(style, prefix)?
+        };
 
         match content {
             Some(content) => Ok(Value::from_safe_string(content)),
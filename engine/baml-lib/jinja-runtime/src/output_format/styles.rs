@@ -0,0 +1,614 @@
+use baml_types::{FieldType, TypeValue};
+use indexmap::IndexSet;
+
+use super::{
+    json_schema::JsonSchemaDialect,
+    types::{OutputFormatContent, OutputFormatStyle, RenderOptions},
+};
+
+/// Tag used for the root element in XML when the target is not a class.
+const XML_ROOT_TAG: &str = "answer";
+/// Tag used for each element of a list in XML.
+const XML_LIST_ITEM_TAG: &str = "item";
+/// Placeholder name for the keys of a map.
+const MAP_KEY_PLACEHOLDER: &str = "KEY";
+
+/// Language independent description of the expected output, printed as an XML
+/// or YAML skeleton.
+enum Shape {
+    /// A value written inline, like `string` or `A or B`. Notes are
+    /// printed as comments next to it (enum value descriptions).
+    Leaf {
+        text: String,
+        notes: Vec<String>,
+    },
+    Object(Vec<FieldShape>),
+    List(Box<Shape>),
+    Map(Box<Shape>),
+    /// Union with at least one non-inline member, printed as alternatives.
+    OneOf(Vec<Shape>),
+    /// A recursive class or alias that is already being printed further up.
+    Recursive(String),
+}
+
+struct FieldShape {
+    name: String,
+    description: Option<String>,
+    shape: Shape,
+}
+
+impl Shape {
+    fn leaf(text: impl Into<String>) -> Self {
+        Shape::Leaf {
+            text: text.into(),
+            notes: vec![],
+        }
+    }
+}
+
+struct ShapeBuilder<'a> {
+    content: &'a OutputFormatContent,
+    /// Classes and aliases currently being expanded, used to stop recursion.
+    expanding: IndexSet<String>,
+}
+
+impl ShapeBuilder<'_> {
+    fn shape(&mut self, field_type: &FieldType) -> Result<Shape, minijinja::Error> {
+        Ok(match field_type {
+            FieldType::Primitive(t) => match t {
+                TypeValue::String => Shape::leaf("string"),
+                TypeValue::Int => Shape::leaf("int"),
+                TypeValue::Float => Shape::leaf("float"),
+                TypeValue::Bool => Shape::leaf("bool"),
+                TypeValue::Null => Shape::leaf("null"),
+                TypeValue::Media(media_type) => {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        format!("type '{media_type}' is not supported in outputs"),
+                    ))
+                }
+            },
+            FieldType::Literal(v) => Shape::leaf(v.to_string()),
+            FieldType::Constrained { base, .. } => self.shape(base)?,
+            FieldType::Enum(e) => {
+                let Some(enm) = self.content.enums.get(e) else {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        format!("Enum {e} not found"),
+                    ));
+                };
+
+                Shape::Leaf {
+                    text: enm
+                        .values
+                        .iter()
+                        .map(|(name, _)| name.rendered_name())
+                        .collect::<Vec<_>>()
+                        .join(" or "),
+                    notes: enm
+                        .values
+                        .iter()
+                        .filter_map(|(name, description)| {
+                            let description = description.as_ref()?;
+                            Some(format!("{}: {description}", name.rendered_name()))
+                        })
+                        .collect(),
+                }
+            }
+            FieldType::Class(cls) => {
+                let Some(class) = self.content.classes.get(cls) else {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        format!("Class {cls} not found"),
+                    ));
+                };
+
+                if !self.expanding.insert(cls.to_owned()) {
+                    return Ok(Shape::Recursive(class.name.rendered_name().to_string()));
+                }
+                let fields = class
+                    .fields
+                    .iter()
+                    .map(|(name, field_type, description)| {
+                        Ok(FieldShape {
+                            name: name.rendered_name().to_string(),
+                            description: description.clone(),
+                            shape: self.shape(field_type)?,
+                        })
+                    })
+                    .collect::<Result<_, minijinja::Error>>();
+                self.expanding.shift_remove(cls);
+
+                Shape::Object(fields?)
+            }
+            FieldType::RecursiveTypeAlias(alias) => {
+                let Some(resolved) = self.content.structural_recursive_aliases.get(alias) else {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        format!("Type alias {alias} not found"),
+                    ));
+                };
+
+                if !self.expanding.insert(alias.to_owned()) {
+                    return Ok(Shape::Recursive(alias.to_owned()));
+                }
+                let shape = self.shape(resolved);
+                self.expanding.shift_remove(alias);
+
+                shape?
+            }
+            FieldType::List(inner) => Shape::List(Box::new(self.shape(inner)?)),
+            FieldType::Map(_, value) => Shape::Map(Box::new(self.shape(value)?)),
+            FieldType::Union(items) => self.one_of(items.iter())?,
+            FieldType::Optional(inner) => {
+                self.one_of([inner.as_ref(), &FieldType::Primitive(TypeValue::Null)].into_iter())?
+            }
+            FieldType::Tuple(_) => {
+                return Err(minijinja::Error::new(
+                    minijinja::ErrorKind::BadSerialization,
+                    "Tuple type is not supported in outputs",
+                ))
+            }
+        })
+    }
+
+    /// Unions of inline values are written inline (`int or null`), anything
+    /// else is printed as a list of alternatives.
+    fn one_of<'t>(
+        &mut self,
+        items: impl Iterator<Item = &'t FieldType>,
+    ) -> Result<Shape, minijinja::Error> {
+        let mut alternatives = Vec::new();
+        for item in items {
+            match self.shape(item)? {
+                // Flatten nested unions, optionals of unions and so on.
+                Shape::OneOf(nested) => alternatives.extend(nested),
+                shape => alternatives.push(shape),
+            }
+        }
+
+        if !alternatives
+            .iter()
+            .all(|shape| matches!(shape, Shape::Leaf { .. }))
+        {
+            return Ok(Shape::OneOf(alternatives));
+        }
+
+        let mut texts = IndexSet::new();
+        let mut notes = Vec::new();
+        for shape in alternatives {
+            if let Shape::Leaf { text, notes: n } = shape {
+                texts.insert(text);
+                notes.extend(n);
+            }
+        }
+        Ok(Shape::Leaf {
+            text: texts.into_iter().collect::<Vec<_>>().join(" or "),
+            notes,
+        })
+    }
+}
+
+fn indent(lines: &mut [String], depth: usize) {
+    let pad = "  ".repeat(depth);
+    for line in lines.iter_mut() {
+        line.insert_str(0, &pad);
+    }
+}
+
+fn xml(shape: &Shape, tag: &str) -> Vec<String> {
+    match shape {
+        Shape::Leaf { text, notes } => notes
+            .iter()
+            .map(|note| format!("<!-- {note} -->"))
+            .chain(std::iter::once(format!("<{tag}>{text}</{tag}>")))
+            .collect(),
+        Shape::Recursive(name) => {
+            vec![format!(
+                "<{tag}><!-- {name}, same structure as above --></{tag}>"
+            )]
+        }
+        Shape::OneOf(alternatives) => {
+            let mut lines = Vec::new();
+            for (i, alternative) in alternatives.iter().enumerate() {
+                if i > 0 {
+                    lines.push("<!-- or -->".to_string());
+                }
+                lines.extend(xml(alternative, tag));
+            }
+            lines
+        }
+        Shape::Object(fields) => {
+            let mut inner = Vec::new();
+            for field in fields {
+                if let Some(description) = &field.description {
+                    inner.push(format!("<!-- {} -->", description.replace('\n', " ")));
+                }
+                inner.extend(xml(&field.shape, &field.name));
+            }
+            wrap_xml(tag, inner)
+        }
+        Shape::List(item) => {
+            let mut inner = xml(item, XML_LIST_ITEM_TAG);
+            inner.push(format!(
+                "<!-- more <{XML_LIST_ITEM_TAG}> elements as needed -->"
+            ));
+            wrap_xml(tag, inner)
+        }
+        Shape::Map(value) => {
+            let mut inner = vec!["<!-- one element per entry, named after its key -->".to_string()];
+            inner.extend(xml(value, MAP_KEY_PLACEHOLDER));
+            wrap_xml(tag, inner)
+        }
+    }
+}
+
+fn wrap_xml(tag: &str, mut inner: Vec<String>) -> Vec<String> {
+    indent(&mut inner, 1);
+    let mut lines = vec![format!("<{tag}>")];
+    lines.extend(inner);
+    lines.push(format!("</{tag}>"));
+    lines
+}
+
+/// YAML for a value: either inline after the key or a block below it.
+enum Yaml {
+    Inline { text: String, comments: Vec<String> },
+    Block(Vec<String>),
+}
+
+fn yaml(shape: &Shape) -> Yaml {
+    match shape {
+        Shape::Leaf { text, notes } => Yaml::Inline {
+            text: text.clone(),
+            comments: notes.iter().map(|note| format!("# {note}")).collect(),
+        },
+        Shape::Recursive(name) => Yaml::Block(vec![format!("# {name}, same structure as above")]),
+        Shape::OneOf(alternatives) => {
+            let mut lines = Vec::new();
+            for (i, alternative) in alternatives.iter().enumerate() {
+                if i > 0 {
+                    lines.push("# or".to_string());
+                }
+                match yaml(alternative) {
+                    Yaml::Inline { text, comments } => {
+                        lines.extend(comments);
+                        lines.push(text);
+                    }
+                    Yaml::Block(block) => lines.extend(block),
+                }
+            }
+            Yaml::Block(lines)
+        }
+        Shape::Object(fields) => {
+            let mut lines = Vec::new();
+            for field in fields {
+                if let Some(description) = &field.description {
+                    lines.extend(description.lines().map(|line| format!("# {line}")));
+                }
+                yaml_entry(&mut lines, &field.name, &field.shape);
+            }
+            Yaml::Block(lines)
+        }
+        Shape::List(item) => {
+            let mut lines: Vec<_> = match yaml(item) {
+                Yaml::Inline { text, comments } => {
+                    comments.into_iter().chain([format!("- {text}")]).collect()
+                }
+                Yaml::Block(block) => block
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, line)| match i {
+                        0 => format!("- {line}"),
+                        _ => format!("  {line}"),
+                    })
+                    .collect(),
+            };
+            lines.push("# more items as needed".to_string());
+            Yaml::Block(lines)
+        }
+        Shape::Map(value) => {
+            let mut lines = vec!["# one entry per key".to_string()];
+            yaml_entry(&mut lines, MAP_KEY_PLACEHOLDER, value);
+            Yaml::Block(lines)
+        }
+    }
+}
+
+fn yaml_entry(lines: &mut Vec<String>, key: &str, shape: &Shape) {
+    match yaml(shape) {
+        Yaml::Inline { text, comments } => {
+            lines.extend(comments);
+            lines.push(format!("{key}: {text}"));
+        }
+        Yaml::Block(mut block) => {
+            indent(&mut block, 1);
+            lines.push(format!("{key}:"));
+            lines.extend(block);
+        }
+    }
+}
+
+impl OutputFormatContent {
+    /// Renders the schema in one of the [`OutputFormatStyle`]s other than
+    /// BAML's own, which is handled by [`OutputFormatContent::render`].
+    ///
+    /// `prefix` follows the same convention as
+    /// [`super::types::RenderOptions::new`]: `None` picks the default for the
+    /// style, `Some(None)` omits it.
+    pub(crate) fn render_style(
+        &self,
+        style: OutputFormatStyle,
+        prefix: Option<Option<String>>,
+    ) -> Result<Option<String>, minijinja::Error> {
+        // Nothing to describe, same as BAML's own style.
+        if prefix.is_none()
+            && style != OutputFormatStyle::Baml
+            && matches!(self.target, FieldType::Primitive(TypeValue::String))
+        {
+            return Ok(None);
+        }
+
+        let (default_prefix, schema) = match style {
+            OutputFormatStyle::Baml => {
+                return self.render(RenderOptions::new(
+                    prefix, None, None, None, None, None, None,
+                ))
+            }
+            OutputFormatStyle::JsonSchema => (
+                "Answer in JSON that matches this JSON schema:\n",
+                serde_json::to_string_pretty(&self.json_schema(JsonSchemaDialect::JsonSchema))
+                    .map_err(|e| {
+                        minijinja::Error::new(minijinja::ErrorKind::BadSerialization, e.to_string())
+                    })?,
+            ),
+            OutputFormatStyle::Xml => {
+                let shape = self.shape()?;
+                let root = match &self.target {
+                    FieldType::Class(cls) => self
+                        .classes
+                        .get(cls)
+                        .map_or(cls.as_str(), |class| class.name.rendered_name()),
+                    _ => XML_ROOT_TAG,
+                };
+                (
+                    "Answer in XML using this format:\n",
+                    xml(&shape, root).join("\n"),
+                )
+            }
+            OutputFormatStyle::Yaml => {
+                let lines = match yaml(&self.shape()?) {
+                    Yaml::Inline { text, comments } => {
+                        comments.into_iter().chain([text]).collect::<Vec<_>>()
+                    }
+                    Yaml::Block(lines) => lines,
+                };
+                ("Answer in YAML using this format:\n", lines.join("\n"))
+            }
+        };
+
+        let mut output = match prefix {
+            None => default_prefix.to_string(),
+            Some(Some(prefix)) => prefix,
+            Some(None) => String::new(),
+        };
+        output.push_str(&schema);
+        Ok(Some(output))
+    }
+
+    fn shape(&self) -> Result<Shape, minijinja::Error> {
+        ShapeBuilder {
+            content: self,
+            expanding: IndexSet::new(),
+        }
+        .shape(&self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::LiteralValue;
+
+    use super::*;
+    use crate::types::{Builder, Class, Enum, Name};
+
+    fn resume() -> Builder {
+        OutputFormatContent::target(FieldType::class("Resume"))
+            .enums(vec![Enum {
+                name: Name::new("Level".to_string()),
+                values: vec![
+                    (Name::new("JUNIOR".to_string()), None),
+                    (
+                        Name::new("SENIOR".to_string()),
+                        Some("5+ years".to_string()),
+                    ),
+                ],
+                constraints: vec![],
+            }])
+            .classes(vec![
+                Class {
+                    name: Name::new("Resume".to_string()),
+                    fields: vec![
+                        (
+                            Name::new("name".to_string()),
+                            FieldType::string(),
+                            Some("Full name".to_string()),
+                        ),
+                        (
+                            Name::new("skills".to_string()),
+                            FieldType::List(Box::new(FieldType::string())),
+                            None,
+                        ),
+                        (
+                            Name::new("jobs".to_string()),
+                            FieldType::List(Box::new(FieldType::class("Job"))),
+                            None,
+                        ),
+                        (
+                            Name::new("level".to_string()),
+                            FieldType::Optional(Box::new(FieldType::Enum("Level".to_string()))),
+                            None,
+                        ),
+                    ],
+                    constraints: vec![],
+                },
+                Class {
+                    name: Name::new("Job".to_string()),
+                    fields: vec![
+                        (Name::new("company".to_string()), FieldType::string(), None),
+                        (Name::new("years".to_string()), FieldType::int(), None),
+                    ],
+                    constraints: vec![],
+                },
+            ])
+    }
+
+    #[test]
+    fn render_xml() {
+        let content = resume().build();
+        let rendered = content
+            .render_style(OutputFormatStyle::Xml, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            rendered,
+            r#"Answer in XML using this format:
+<Resume>
+  <!-- Full name -->
+  <name>string</name>
+  <skills>
+    <item>string</item>
+    <!-- more <item> elements as needed -->
+  </skills>
+  <jobs>
+    <item>
+      <company>string</company>
+      <years>int</years>
+    </item>
+    <!-- more <item> elements as needed -->
+  </jobs>
+  <!-- SENIOR: 5+ years -->
+  <level>JUNIOR or SENIOR or null</level>
+</Resume>"#
+        );
+    }
+
+    #[test]
+    fn render_yaml() {
+        let content = resume().build();
+        let rendered = content
+            .render_style(OutputFormatStyle::Yaml, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            rendered,
+            r#"Answer in YAML using this format:
+# Full name
+name: string
+skills:
+  - string
+  # more items as needed
+jobs:
+  - company: string
+    years: int
+  # more items as needed
+# SENIOR: 5+ years
+level: JUNIOR or SENIOR or null"#
+        );
+    }
+
+    #[test]
+    fn render_json_schema() {
+        let content =
+            OutputFormatContent::target(FieldType::List(Box::new(FieldType::int()))).build();
+        let rendered = content
+            .render_style(
+                OutputFormatStyle::JsonSchema,
+                Some(Some("Schema:\n".into())),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            rendered,
+            r#"Schema:
+{
+  "type": "array",
+  "items": {
+    "type": "integer"
+  }
+}"#
+        );
+    }
+
+    #[test]
+    fn render_recursive_yaml_and_xml() {
+        let content = OutputFormatContent::target(FieldType::class("Node"))
+            .classes(vec![Class {
+                name: Name::new("Node".to_string()),
+                fields: vec![
+                    (Name::new("data".to_string()), FieldType::int(), None),
+                    (
+                        Name::new("next".to_string()),
+                        FieldType::Optional(Box::new(FieldType::class("Node"))),
+                        None,
+                    ),
+                ],
+                constraints: vec![],
+            }])
+            .recursive_classes(IndexSet::from(["Node".to_string()]))
+            .build();
+
+        assert_eq!(
+            content
+                .render_style(OutputFormatStyle::Xml, Some(None))
+                .unwrap()
+                .unwrap(),
+            r#"<Node>
+  <data>int</data>
+  <next><!-- Node, same structure as above --></next>
+  <!-- or -->
+  <next>null</next>
+</Node>"#
+        );
+        assert_eq!(
+            content
+                .render_style(OutputFormatStyle::Yaml, Some(None))
+                .unwrap()
+                .unwrap(),
+            r#"data: int
+next:
+  # Node, same structure as above
+  # or
+  null"#
+        );
+    }
+
+    #[test]
+    fn render_scalars() {
+        let content = OutputFormatContent::target(FieldType::Union(vec![
+            FieldType::int(),
+            FieldType::Literal(LiteralValue::String("none".into())),
+        ]))
+        .build();
+        assert_eq!(
+            content
+                .render_style(OutputFormatStyle::Xml, None)
+                .unwrap()
+                .unwrap(),
+            "Answer in XML using this format:\n<answer>int or \"none\"</answer>"
+        );
+        assert_eq!(
+            content
+                .render_style(OutputFormatStyle::Yaml, None)
+                .unwrap()
+                .unwrap(),
+            "Answer in YAML using this format:\nint or \"none\""
+        );
+
+        let string = OutputFormatContent::target(FieldType::string()).build();
+        assert_eq!(
+            string.render_style(OutputFormatStyle::Xml, None).unwrap(),
+            None
+        );
+    }
+}
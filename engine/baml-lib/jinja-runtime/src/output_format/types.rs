@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use baml_types::{Constraint, FieldType, TypeValue};
+use baml_types::{BamlValue, Constraint, FieldType, StreamingBehavior, TypeValue};
 use indexmap::{IndexMap, IndexSet};
 
 #[derive(Debug)]
@@ -53,11 +53,37 @@ pub struct Class {
     pub constraints: Vec<Constraint>,
 }
 
+/// The `@stream.*` attributes of a class and its fields. Only consulted by
+/// `jsonish` when parsing partial responses.
+#[derive(Debug, Default)]
+pub struct ClassStreaming {
+    /// `@@stream.done` on the class.
+    pub done: bool,
+    /// Field name to the attributes on that field.
+    pub fields: IndexMap<String, StreamingBehavior>,
+}
+
+impl ClassStreaming {
+    pub fn is_default(&self) -> bool {
+        !self.done && self.fields.values().all(StreamingBehavior::is_default)
+    }
+
+    pub fn field(&self, name: &str) -> StreamingBehavior {
+        self.fields.get(name).copied().unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OutputFormatContent {
     pub enums: Arc<IndexMap<String, Enum>>,
     pub classes: Arc<IndexMap<String, Class>>,
     recursive_classes: Arc<IndexSet<String>>,
+    pub structural_recursive_aliases: Arc<IndexMap<String, FieldType>>,
+    /// Streaming attributes, keyed by class name. Classes without any are
+    /// left out.
+    class_streaming: Arc<IndexMap<String, ClassStreaming>>,
+    /// `@default(...)` values, keyed by class name and then field name.
+    field_defaults: Arc<IndexMap<String, IndexMap<String, BamlValue>>>,
     pub target: FieldType,
 }
 
@@ -67,6 +93,10 @@ pub struct Builder {
     classes: Vec<Class>,
     /// Order matters for this one.
     recursive_classes: IndexSet<String>,
+    /// Recursive type aliases and the types they resolve to.
+    structural_recursive_aliases: IndexMap<String, FieldType>,
+    class_streaming: IndexMap<String, ClassStreaming>,
+    field_defaults: IndexMap<String, IndexMap<String, BamlValue>>,
     target: FieldType,
 }
 
@@ -76,6 +106,9 @@ impl Builder {
             enums: vec![],
             classes: vec![],
             recursive_classes: IndexSet::new(),
+            structural_recursive_aliases: IndexMap::new(),
+            class_streaming: IndexMap::new(),
+            field_defaults: IndexMap::new(),
             target,
         }
     }
@@ -95,6 +128,27 @@ impl Builder {
         self
     }
 
+    pub fn structural_recursive_aliases(
+        mut self,
+        structural_recursive_aliases: IndexMap<String, FieldType>,
+    ) -> Self {
+        self.structural_recursive_aliases = structural_recursive_aliases;
+        self
+    }
+
+    pub fn class_streaming(mut self, class_streaming: IndexMap<String, ClassStreaming>) -> Self {
+        self.class_streaming = class_streaming;
+        self
+    }
+
+    pub fn field_defaults(
+        mut self,
+        field_defaults: IndexMap<String, IndexMap<String, BamlValue>>,
+    ) -> Self {
+        self.field_defaults = field_defaults;
+        self
+    }
+
     pub fn target(mut self, target: FieldType) -> Self {
         self.target = target;
         self
@@ -115,6 +169,9 @@ impl Builder {
                     .collect(),
             ),
             recursive_classes: Arc::new(self.recursive_classes.into_iter().collect()),
+            structural_recursive_aliases: Arc::new(self.structural_recursive_aliases),
+            class_streaming: Arc::new(self.class_streaming),
+            field_defaults: Arc::new(self.field_defaults),
             target: self.target,
         }
     }
@@ -141,6 +198,23 @@ pub(crate) enum MapStyle {
     ObjectLiteral,
 }
 
+/// Which kind of schema `ctx.output_format(style=...)` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumString, strum::VariantNames)]
+pub(crate) enum OutputFormatStyle {
+    /// BAML's own type definitions, see [`OutputFormatContent::render`].
+    #[strum(serialize = "baml")]
+    Baml,
+
+    #[strum(serialize = "json_schema")]
+    JsonSchema,
+
+    #[strum(serialize = "xml")]
+    Xml,
+
+    #[strum(serialize = "yaml")]
+    Yaml,
+}
+
 pub(crate) struct RenderOptions {
     prefix: RenderSetting<String>,
     pub(crate) or_splitter: String,
@@ -148,6 +222,7 @@ pub(crate) struct RenderOptions {
     hoisted_class_prefix: RenderSetting<String>,
     always_hoist_enums: RenderSetting<bool>,
     map_style: MapStyle,
+    show_defaults: bool,
 }
 
 impl Default for RenderOptions {
@@ -159,6 +234,7 @@ impl Default for RenderOptions {
             hoisted_class_prefix: RenderSetting::Auto,
             always_hoist_enums: RenderSetting::Auto,
             map_style: MapStyle::TypeParameters,
+            show_defaults: false,
         }
     }
 }
@@ -174,6 +250,7 @@ impl RenderOptions {
         always_hoist_enums: Option<bool>,
         map_style: Option<MapStyle>,
         hoisted_class_prefix: Option<Option<String>>,
+        show_defaults: Option<bool>,
     ) -> Self {
         Self {
             prefix: prefix.map_or(RenderSetting::Auto, |p| {
@@ -189,6 +266,7 @@ impl RenderOptions {
             hoisted_class_prefix: hoisted_class_prefix.map_or(RenderSetting::Auto, |p| {
                 p.map_or(RenderSetting::Never, RenderSetting::Always)
             }),
+            show_defaults: show_defaults.unwrap_or(false),
         }
     }
 
@@ -250,6 +328,8 @@ struct ClassFieldRender {
     name: String,
     r#type: String,
     description: Option<String>,
+    /// JSON of the `@default(...)` value, when asked to show defaults.
+    default: Option<String>,
 }
 
 impl std::fmt::Display for ClassRender {
@@ -259,12 +339,16 @@ impl std::fmt::Display for ClassRender {
             if let Some(desc) = &value.description {
                 writeln!(f, "  // {}", desc.replace("\n", "\n  // "))?;
             }
-            writeln!(
+            write!(
                 f,
                 "  {}: {},",
                 value.name,
                 value.r#type.replace('\n', "\n  ")
             )?;
+            match &value.default {
+                Some(default) => writeln!(f, " // default: {default}")?,
+                None => writeln!(f)?,
+            }
         }
         write!(f, "}}")
     }
@@ -333,6 +417,15 @@ impl OutputFormatContent {
 
                     Some(format!("Answer in JSON using this {type_prefix}:{end}"))
                 }
+                // Recursive aliases are always hoisted, so just inline the name.
+                FieldType::RecursiveTypeAlias(_) => {
+                    let type_prefix = match &options.hoisted_class_prefix {
+                        RenderSetting::Always(prefix) if !prefix.is_empty() => prefix,
+                        _ => RenderOptions::DEFAULT_TYPE_PREFIX_IN_RENDER_MESSAGE,
+                    };
+
+                    Some(format!("Answer in JSON using this {type_prefix}: "))
+                }
                 FieldType::List(_) => Some(String::from(
                     "Answer with a JSON Array using this schema:\n",
                 )),
@@ -371,7 +464,8 @@ impl OutputFormatContent {
         .to_string(options)
     }
 
-    /// Recursive classes are rendered using their name instead of schema.
+    /// Recursive classes and recursive type aliases are rendered using their
+    /// name instead of schema.
     ///
     /// The schema must be hoisted and named, otherwise there's no way to refer
     /// to a recursive class.
@@ -397,6 +491,8 @@ impl OutputFormatContent {
                 Ok(nested_class.to_owned())
             }
 
+            FieldType::RecursiveTypeAlias(alias) => Ok(alias.to_owned()),
+
             _ => self.inner_type_render(options, field_type, render_state, group_hoisted_literals),
         }
     }
@@ -469,6 +565,11 @@ impl OutputFormatContent {
                             Ok(ClassFieldRender {
                                 name: name.rendered_name().to_string(),
                                 description: description.clone(),
+                                default: options
+                                    .show_defaults
+                                    .then(|| self.field_default(cls, name.real_name()))
+                                    .flatten()
+                                    .map(|v| serde_json::json!(v).to_string()),
                                 r#type: self.render_possibly_recursive_type(
                                     options,
                                     field_type,
@@ -481,9 +582,25 @@ impl OutputFormatContent {
                 }
                 .to_string()
             }
+            FieldType::RecursiveTypeAlias(alias) => {
+                let Some(resolved) = self.structural_recursive_aliases.get(alias) else {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        format!("Type alias {alias} not found"),
+                    ));
+                };
+
+                self.render_possibly_recursive_type(
+                    options,
+                    resolved,
+                    render_state,
+                    group_hoisted_literals,
+                )?
+            }
             FieldType::List(inner) => {
                 let is_recursive = match inner.as_ref() {
                     FieldType::Class(nested_class) => self.recursive_classes.contains(nested_class),
+                    FieldType::RecursiveTypeAlias(_) => true,
                     _ => false,
                 };
 
@@ -574,6 +691,11 @@ impl OutputFormatContent {
             }
         }
 
+        // Same for recursive aliases.
+        if let FieldType::RecursiveTypeAlias(alias) = &self.target {
+            message = Some(alias.to_owned());
+        }
+
         let enum_definitions = Vec::from_iter(render_state.hoisted_enums.iter().map(|e| {
             let enm = self.enums.get(e).expect("Enum not found"); // TODO: Jinja Err
             self.enum_to_string(enm, &options)
@@ -601,6 +723,22 @@ impl OutputFormatContent {
             });
         }
 
+        // Recursive aliases have to be hoisted as well, there's no way to
+        // render them inline.
+        let mut type_alias_definitions = Vec::new();
+
+        for (alias, target) in self.structural_recursive_aliases.iter() {
+            let recursive_pointer =
+                self.inner_type_render(&options, target, &mut render_state, false)?;
+
+            type_alias_definitions.push(match &options.hoisted_class_prefix {
+                RenderSetting::Always(prefix) if !prefix.is_empty() => {
+                    format!("{prefix} {alias} = {recursive_pointer}")
+                }
+                _ => format!("{alias} = {recursive_pointer}"),
+            });
+        }
+
         let mut output = String::new();
 
         if enum_definitions.len() > 0 {
@@ -613,6 +751,11 @@ impl OutputFormatContent {
             output.push_str("\n\n");
         }
 
+        if type_alias_definitions.len() > 0 {
+            output.push_str(&type_alias_definitions.join("\n"));
+            output.push_str("\n\n");
+        }
+
         if let Some(p) = prefix {
             output.push_str(&p);
         }
@@ -657,6 +800,14 @@ impl OutputFormatContent {
             .get(name)
             .ok_or_else(|| anyhow::anyhow!("Class {} not found", name))
     }
+
+    pub fn class_streaming(&self, name: &str) -> Option<&ClassStreaming> {
+        self.class_streaming.get(name)
+    }
+
+    pub fn field_default(&self, class: &str, field: &str) -> Option<&BamlValue> {
+        self.field_defaults.get(class)?.get(field)
+    }
 }
 
 #[cfg(test)]
@@ -751,6 +902,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_class_with_defaults() {
+        let classes = vec![Class {
+            name: Name::new("Person".to_string()),
+            fields: vec![
+                (Name::new("name".to_string()), FieldType::string(), None),
+                (Name::new("age".to_string()), FieldType::int(), None),
+            ],
+            constraints: Vec::new(),
+        }];
+
+        let content = OutputFormatContent::target(FieldType::class("Person"))
+            .classes(classes)
+            .field_defaults(IndexMap::from([(
+                "Person".to_string(),
+                IndexMap::from([("age".to_string(), BamlValue::Int(18))]),
+            )]))
+            .build();
+
+        let rendered = content.render(RenderOptions::default()).unwrap();
+        assert_eq!(
+            rendered,
+            Some(String::from(
+                "Answer in JSON using this schema:\n{\n  name: string,\n  age: int,\n}"
+            ))
+        );
+
+        let rendered = content
+            .render(RenderOptions::new(None, None, None, None, None, None, Some(true)))
+            .unwrap();
+        assert_eq!(
+            rendered,
+            Some(String::from(
+                "Answer in JSON using this schema:\n{\n  name: string,\n  age: int, // default: 18\n}"
+            ))
+        );
+    }
+
     #[test]
     fn render_class_with_multiline_descriptions() {
         let classes = vec![Class {
@@ -2210,6 +2399,81 @@ Answer in JSON using this schema:
     field: string,
     data: int,
   }>,
+}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn render_top_level_recursive_alias() {
+        let json_value = FieldType::union(vec![
+            FieldType::int(),
+            FieldType::string(),
+            FieldType::list(FieldType::RecursiveTypeAlias("JsonValue".to_string())),
+            FieldType::map(
+                FieldType::string(),
+                FieldType::RecursiveTypeAlias("JsonValue".to_string()),
+            ),
+        ]);
+
+        let content =
+            OutputFormatContent::target(FieldType::RecursiveTypeAlias("JsonValue".to_string()))
+                .structural_recursive_aliases(IndexMap::from([(
+                    "JsonValue".to_string(),
+                    json_value,
+                )]))
+                .build();
+        let rendered = content.render(RenderOptions::default()).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(
+            rendered,
+            Some(String::from(
+r#"JsonValue = int or string or JsonValue[] or map<string, JsonValue>
+
+Answer in JSON using this schema: JsonValue"#
+            ))
+        );
+    }
+
+    #[test]
+    fn render_class_with_recursive_alias_field() {
+        let classes = vec![Class {
+            name: Name::new("Payload".to_string()),
+            fields: vec![
+                (Name::new("kind".to_string()), FieldType::string(), None),
+                (
+                    Name::new("data".to_string()),
+                    FieldType::RecursiveTypeAlias("JsonValue".to_string()),
+                    None,
+                ),
+            ],
+            constraints: Vec::new(),
+        }];
+
+        let json_value = FieldType::union(vec![
+            FieldType::int(),
+            FieldType::list(FieldType::RecursiveTypeAlias("JsonValue".to_string())),
+            FieldType::map(
+                FieldType::string(),
+                FieldType::RecursiveTypeAlias("JsonValue".to_string()),
+            ),
+        ]);
+
+        let content = OutputFormatContent::target(FieldType::class("Payload"))
+            .classes(classes)
+            .structural_recursive_aliases(IndexMap::from([("JsonValue".to_string(), json_value)]))
+            .build();
+        let rendered = content.render(RenderOptions::default()).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(
+            rendered,
+            Some(String::from(
+r#"JsonValue = int or JsonValue[] or map<string, JsonValue>
+
+Answer in JSON using this schema:
+{
+  kind: string,
+  data: JsonValue,
 }"#
             ))
         );
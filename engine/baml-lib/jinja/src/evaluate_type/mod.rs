@@ -147,7 +147,10 @@ impl TypeError {
     }
 
     fn new_unknown_arg(func: &str, span: Span, name: &str, valid_args: HashSet<&String>) -> Self {
-        let names = valid_args.into_iter().collect::<Vec<_>>();
+        // Sorted, so that names equally close to `name` are picked the same
+        // way every time.
+        let mut names = valid_args.into_iter().collect::<Vec<_>>();
+        names.sort();
         let mut close_names = sort_by_match(name, &names, Some(3));
         close_names.sort();
         let close_names = close_names;
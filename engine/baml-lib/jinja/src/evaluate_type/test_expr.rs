@@ -269,9 +269,14 @@ fn test_output_format() {
         vec!["Function 'baml::OutputFormat' expects argument 'hoisted_class_prefix' to be of type (none | string), but got literal[1]"]
     );
 
+    assert_eq!(
+        assert_evaluates_to!("ctx.output_format(style='xml')", &types),
+        Type::String
+    );
+
     assert_eq!(
         assert_fails_to!("ctx.output_format(prefix='1', unknown=1)", &types),
-        vec!["Function 'baml::OutputFormat' does not have an argument 'unknown'. Did you mean one of these: 'always_hoist_enums', 'enum_value_prefix', 'or_splitter'?"]
+        vec!["Function 'baml::OutputFormat' does not have an argument 'unknown'. Did you mean one of these: 'always_hoist_enums', 'or_splitter', 'style'?"]
     );
 }
 
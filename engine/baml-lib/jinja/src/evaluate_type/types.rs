@@ -35,6 +35,8 @@ pub enum Type {
     FunctionRef(String),
     Image,
     Audio,
+    Pdf,
+    Video,
 }
 
 impl Type {
@@ -94,6 +96,8 @@ impl Type {
             (Type::FunctionRef(_), _) => false,
             (Type::Image, _) => false,
             (Type::Audio, _) => false,
+            (Type::Pdf, _) => false,
+            (Type::Video, _) => false,
             (Type::String, _) => false,
             (Type::Bool, _) => false,
         }
@@ -149,6 +153,8 @@ impl Type {
             Type::FunctionRef(name) => format!("function {}", name),
             Type::Image => "image".into(),
             Type::Audio => "audio".into(),
+            Type::Pdf => "pdf".into(),
+            Type::Video => "video".into(),
         }
     }
 
@@ -278,6 +284,11 @@ impl PredefinedTypes {
                                 "hoisted_class_prefix".into(),
                                 Type::merge(vec![Type::String, Type::None]),
                             ),
+                            (
+                                "show_defaults".into(),
+                                Type::merge(vec![Type::Bool, Type::None]),
+                            ),
+                            ("style".into(), Type::merge(vec![Type::String, Type::None])),
                         ],
                     ),
                 ),
@@ -0,0 +1,233 @@
+//! Streams responses shaped like the test fixtures, a few characters at a
+//! time, through `from_str` (parsing everything received so far for every
+//! chunk) and through `IncrementalParser`.
+//!
+//! `streaming` times whole responses, `last_chunk` times a single chunk at
+//! the end of a response. The chunk is what grows with the response, compare
+//! it across sizes to see how the cost of every chunk scales.
+//!
+//! Run with `cargo bench -p jsonish`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use internal_baml_core::ir::FieldType;
+use jsonish::{from_str, IncrementalParser};
+
+#[path = "../src/tests/fixtures.rs"]
+mod fixtures;
+#[path = "../src/tests/schema.rs"]
+mod schema;
+
+/// How many ingredients, steps, skills and jobs each response lists.
+const SIZES: [usize; 2] = [10, 50];
+
+/// Roughly one LLM token per chunk.
+const CHUNK_LEN: usize = 4;
+
+fn recipe_json(n: usize) -> String {
+    let ingredients = (0..n)
+        .map(|i| format!(r#"{{"name": "ingredient {i}", "quantity": "{i} cups"}}"#))
+        .collect::<Vec<_>>()
+        .join(",\n    ");
+    let steps = (0..n)
+        .map(|i| format!(r#""Step {i}: stir for {i} minutes""#))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"{{
+  "title": "Pancakes",
+  "summary": "Fluffy",
+  "ingredients": [
+    {ingredients}
+  ],
+  "steps": [{steps}],
+  "servings": 4
+}}"#
+    )
+}
+
+/// Prose around a markdown block that needs fixing: unquoted keys and
+/// values, single quotes and trailing commas.
+fn recipe_markdown(n: usize) -> String {
+    let ingredients = (0..n)
+        .map(|i| format!("{{name: ingredient {i}, quantity: '{i} cups',}},"))
+        .collect::<Vec<_>>()
+        .join("\n    ");
+    let steps = (0..n)
+        .map(|i| format!("'Step {i}: stir for {i} minutes',"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"Here is the recipe:
+
+```json
+{{
+  title: Pancakes,
+  summary: "Fluffy",
+  ingredients: [
+    {ingredients}
+  ],
+  steps: [{steps}],
+  servings: 4,
+}}
+```
+
+Enjoy!"#
+    )
+}
+
+fn resume_xml(n: usize) -> String {
+    let skills = (0..n)
+        .map(|i| format!("<item>skill {i}</item>"))
+        .collect::<Vec<_>>()
+        .join("\n    ");
+    let jobs = (0..n)
+        .map(|i| format!("<item><company>Company {i}</company><years>{i}</years></item>"))
+        .collect::<Vec<_>>()
+        .join("\n    ");
+    format!(
+        r#"<Resume>
+  <name>Ada Lovelace</name>
+  <bio>Wrote <b>the first</b> program &amp; more</bio>
+  <skills>
+    {skills}
+  </skills>
+  <jobs>
+    {jobs}
+  </jobs>
+  <level>SENIOR</level>
+</Resume>"#
+    )
+}
+
+fn resume_yaml(n: usize) -> String {
+    let skills = (0..n)
+        .map(|i| format!("  - skill {i}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let jobs = (0..n)
+        .map(|i| format!("  - company: Company {i}\n    years: {i}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        r#"```yaml
+name: Ada Lovelace
+bio: |
+  Wrote the first program
+skills:
+{skills}
+jobs:
+{jobs}
+level: SENIOR
+```"#
+    )
+}
+
+/// A response in each output style, with the schema and type it's parsed
+/// into.
+struct Style {
+    name: &'static str,
+    schema: &'static str,
+    target: FieldType,
+    render: fn(usize) -> String,
+}
+
+fn styles() -> [Style; 4] {
+    let style = |name, schema, class, render| Style {
+        name,
+        schema,
+        target: FieldType::class(class),
+        render,
+    };
+    [
+        style("json", fixtures::RECIPE, "Recipe", recipe_json),
+        style("markdown", fixtures::RECIPE, "Recipe", recipe_markdown),
+        style("xml", fixtures::RESUME, "Resume", resume_xml),
+        style("yaml", fixtures::RESUME, "Resume", resume_yaml),
+    ]
+}
+
+/// Where each chunk of `response` ends.
+fn chunk_ends(response: &str) -> Vec<usize> {
+    response
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .skip(1)
+        .step_by(CHUNK_LEN)
+        .chain(std::iter::once(response.len()))
+        .collect()
+}
+
+fn streaming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming");
+    group.sample_size(10);
+    for style in styles() {
+        let ir = schema::load_test_ir(style.schema);
+        let of = schema::render_output_format(&ir, &style.target, &Default::default()).unwrap();
+        let target = &style.target;
+
+        for n in SIZES {
+            let response = (style.render)(n);
+            let chunk_ends = chunk_ends(&response);
+            let id = format!("{}/{}", style.name, response.len());
+
+            group.bench_with_input(BenchmarkId::new("from_str", &id), &chunk_ends, |b, ends| {
+                b.iter(|| {
+                    for &end in ends {
+                        let _ = from_str(&of, target, &response[..end], true);
+                    }
+                })
+            });
+            group.bench_with_input(
+                BenchmarkId::new("incremental", &id),
+                &chunk_ends,
+                |b, ends| {
+                    b.iter(|| {
+                        let mut parser = IncrementalParser::new(&of, target);
+                        for &end in ends {
+                            let _ = parser.parse(&response[..end]);
+                        }
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn last_chunk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("last_chunk");
+    group.sample_size(10);
+    for style in styles() {
+        let ir = schema::load_test_ir(style.schema);
+        let of = schema::render_output_format(&ir, &style.target, &Default::default()).unwrap();
+        let target = &style.target;
+
+        for n in SIZES {
+            let response = (style.render)(n);
+            let chunk_ends = chunk_ends(&response);
+            let (&last, before) = chunk_ends.split_last().unwrap();
+            let id = format!("{}/{}", style.name, response.len());
+
+            group.bench_function(BenchmarkId::new("from_str", &id), |b| {
+                b.iter(|| from_str(&of, target, &response[..last], true))
+            });
+            group.bench_function(BenchmarkId::new("incremental", &id), |b| {
+                b.iter_batched_ref(
+                    || {
+                        let mut parser = IncrementalParser::new(&of, target);
+                        for &end in before {
+                            let _ = parser.parse(&response[..end]);
+                        }
+                        parser
+                    },
+                    |parser| parser.parse(&response[..last]),
+                    BatchSize::PerIteration,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, streaming, last_chunk);
+criterion_main!(benches);
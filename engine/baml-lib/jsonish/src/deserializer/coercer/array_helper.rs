@@ -115,6 +115,39 @@ pub(super) fn pick_best(
                 }
             }
 
+            // A string that was produced by stringifying a JSON object or array
+            // is a last resort, prefer any composite that parsed the value
+            // structurally. This matters for recursive aliases like
+            // `JsonValue` where both options exist in the same union.
+            if matches!(target, FieldType::Union(_)) {
+                let is_json_string = |v: &BamlValueWithFlags| {
+                    matches!(v, BamlValueWithFlags::String(..))
+                        && v.conditions()
+                            .flags
+                            .iter()
+                            .any(|f| matches!(f, Flag::JsonToString(..)))
+                };
+                let is_composite = |v: &BamlValueWithFlags| {
+                    matches!(
+                        v,
+                        BamlValueWithFlags::List(..)
+                            | BamlValueWithFlags::Map(..)
+                            | BamlValueWithFlags::Class(..)
+                    )
+                };
+
+                match (
+                    is_json_string(a_val) && is_composite(b_val),
+                    is_composite(a_val) && is_json_string(b_val),
+                ) {
+                    // Return B
+                    (true, false) => return std::cmp::Ordering::Greater,
+                    // Return A
+                    (false, true) => return std::cmp::Ordering::Less,
+                    _ => {}
+                }
+            }
+
             // De-value default values when comparing
             match (a_val, b_val) {
                 (
@@ -158,7 +191,9 @@ pub(super) fn pick_best(
                         cond.conditions().flags.iter().any(|f| {
                             matches!(
                                 f,
-                                Flag::OptionalDefaultFromNoValue | Flag::DefaultFromNoValue
+                                Flag::OptionalDefaultFromNoValue
+                                    | Flag::DefaultFromNoValue
+                                    | Flag::DefaultFromAttribute
                             )
                         })
                     });
@@ -166,7 +201,9 @@ pub(super) fn pick_best(
                         cond.conditions().flags.iter().any(|f| {
                             matches!(
                                 f,
-                                Flag::OptionalDefaultFromNoValue | Flag::DefaultFromNoValue
+                                Flag::OptionalDefaultFromNoValue
+                                    | Flag::DefaultFromNoValue
+                                    | Flag::DefaultFromAttribute
                             )
                         })
                     });
@@ -0,0 +1,267 @@
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use internal_baml_core::ir::FieldType;
+
+use crate::{deserializer::types::BamlValueWithFlags, jsonish};
+
+use super::{ParsingContext, ParsingError};
+
+type CoercionResult = Result<BamlValueWithFlags, ParsingError>;
+
+/// Identifies a node of a parsed value by a hash of its content: nodes that
+/// are equal have the same id, in the same parse or the next one, so a node
+/// keeps its id for as long as that part of the response doesn't change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct NodeId(u64);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Node {
+    id: NodeId,
+    /// How many nodes there are in the subtree.
+    size: usize,
+}
+
+/// Coercion results from the previous parse of a streamed response, so that
+/// the parts of the response that didn't change since then are not coerced
+/// again.
+///
+/// Coercing a value only depends on the types, the value, the scope it's
+/// coerced in, whether it's still in progress and the class-value pairs
+/// visited on the way there (plus the output format and `allow_partials`,
+/// which stay the same for a given cache), so a result is reused when all of
+/// those match. Values are told apart by their [`NodeId`], worked out once
+/// for every node of the parsed value before coercing it.
+///
+/// Only values that already showed up in the previous parse are cached: the
+/// part of the response that is still streaming in is different every time,
+/// and holding on to its results would only cost clones.
+#[derive(Default)]
+pub(crate) struct CoercionCache {
+    previous: RefCell<Generation>,
+    current: RefCell<Generation>,
+    /// The nodes of the value being coerced, by address.
+    nodes: RefCell<HashMap<usize, Node>>,
+}
+
+#[derive(Default)]
+struct Generation {
+    entries: HashMap<u64, Vec<Entry>>,
+    seen: HashSet<u64>,
+}
+
+struct Entry {
+    scope: Vec<String>,
+    in_progress: bool,
+    field_type: FieldType,
+    target: FieldType,
+    value: Option<NodeId>,
+    visited: Vec<(String, NodeId)>,
+    result: CoercionResult,
+}
+
+impl Entry {
+    fn matches(
+        &self,
+        scope: &[String],
+        in_progress: bool,
+        field_type: &FieldType,
+        target: &FieldType,
+        value: Option<NodeId>,
+        visited: &[(&str, NodeId)],
+    ) -> bool {
+        self.scope == scope
+            && self.in_progress == in_progress
+            && &self.field_type == field_type
+            && &self.target == target
+            && self.value == value
+            && self.visited.len() == visited.len()
+            && visited
+                .iter()
+                .all(|&(name, id)| self.visited.iter().any(|(n, i)| n == name && *i == id))
+    }
+}
+
+impl CoercionCache {
+    /// Works out the [`NodeId`] of every node of `value`, the parsed value
+    /// that is about to be coerced.
+    pub(super) fn start_parse(&self, value: &jsonish::Value) {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.clear();
+        index(value, &mut nodes);
+    }
+
+    /// The node `value` is, if it's part of the value being coerced rather
+    /// than one made up along the way.
+    pub(super) fn node(&self, value: &jsonish::Value) -> Option<Node> {
+        self.nodes.borrow().get(&address(value)).copied()
+    }
+
+    pub(super) fn get_or_coerce(
+        &self,
+        ctx: &ParsingContext,
+        field_type: &FieldType,
+        target: &FieldType,
+        value: Option<&jsonish::Value>,
+        coerce: impl FnOnce() -> CoercionResult,
+    ) -> CoercionResult {
+        let node = match value {
+            Some(value) => match self.node(value) {
+                Some(node) => Some(node),
+                None => return coerce(),
+            },
+            None => None,
+        };
+        let Some(visited) = relevant_visited(ctx, node) else {
+            return coerce();
+        };
+
+        let key = {
+            let mut hasher = DefaultHasher::new();
+            ctx.scope.hash(&mut hasher);
+            ctx.in_progress.hash(&mut hasher);
+            node.map(|node| node.id).hash(&mut hasher);
+            hasher.finish()
+        };
+        self.current.borrow_mut().seen.insert(key);
+        if !self.previous.borrow().seen.contains(&key) {
+            return coerce();
+        }
+
+        let value = node.map(|node| node.id);
+        let matches = |entry: &Entry| {
+            entry.matches(
+                &ctx.scope,
+                ctx.in_progress,
+                field_type,
+                target,
+                value,
+                &visited,
+            )
+        };
+
+        if let Some(entries) = self.current.borrow().entries.get(&key) {
+            if let Some(entry) = entries.iter().find(|entry| matches(entry)) {
+                return entry.result.clone();
+            }
+        }
+
+        let previous = self
+            .previous
+            .borrow_mut()
+            .entries
+            .get_mut(&key)
+            .and_then(|entries| {
+                let idx = entries.iter().position(|entry| matches(entry))?;
+                Some(entries.swap_remove(idx))
+            });
+        let entry = match previous {
+            Some(entry) => entry,
+            None => Entry {
+                scope: ctx.scope.clone(),
+                in_progress: ctx.in_progress,
+                field_type: field_type.clone(),
+                target: target.clone(),
+                value,
+                visited: visited
+                    .into_iter()
+                    .map(|(name, id)| (name.to_string(), id))
+                    .collect(),
+                result: coerce(),
+            },
+        };
+
+        let result = entry.result.clone();
+        self.current
+            .borrow_mut()
+            .entries
+            .entry(key)
+            .or_default()
+            .push(entry);
+        result
+    }
+
+    /// Drops everything the last parse didn't use: what the next parse of the
+    /// same response can reuse is what this one coerced.
+    pub(crate) fn finish_parse(&self) {
+        *self.previous.borrow_mut() = self.current.take();
+        self.nodes.borrow_mut().clear();
+    }
+}
+
+/// The visited class-value pairs that coercing `node` could run into, or
+/// `None` if some of them can't be told apart.
+///
+/// Coercion only ever descends into parts of a value, or into a value of the
+/// same size (e.g. when wrapping it in a single-field class), so a pair
+/// whose value is larger than `node` can't come up.
+fn relevant_visited<'c>(
+    ctx: &'c ParsingContext,
+    node: Option<Node>,
+) -> Option<Vec<(&'c str, NodeId)>> {
+    let Some(node) = node else {
+        return Some(vec![]);
+    };
+    ctx.visited_nodes
+        .iter()
+        .filter(|(_, visited)| !matches!(visited, Some(visited) if visited.size > node.size))
+        .map(|(name, visited)| visited.map(|visited| (name.as_str(), visited.id)))
+        .collect()
+}
+
+/// Records the [`Node`] of `value` and of everything in it.
+fn index(value: &jsonish::Value, nodes: &mut HashMap<usize, Node>) -> Node {
+    let mut hasher = DefaultHasher::new();
+    let mut size = 1;
+    let mut child = |value: &jsonish::Value, hasher: &mut DefaultHasher| {
+        let node = index(value, nodes);
+        node.id.hash(hasher);
+        size += node.size;
+    };
+
+    std::mem::discriminant(value).hash(&mut hasher);
+    match value {
+        jsonish::Value::String(s) => s.hash(&mut hasher),
+        jsonish::Value::Number(n) => n.to_string().hash(&mut hasher),
+        jsonish::Value::Boolean(b) => b.hash(&mut hasher),
+        jsonish::Value::Null => {}
+        jsonish::Value::Object(fields) => {
+            fields.len().hash(&mut hasher);
+            for (key, value) in fields {
+                key.hash(&mut hasher);
+                child(value, &mut hasher);
+            }
+        }
+        jsonish::Value::Array(items) => {
+            items.len().hash(&mut hasher);
+            items.iter().for_each(|item| child(item, &mut hasher));
+        }
+        jsonish::Value::Markdown(tag, value) => {
+            tag.hash(&mut hasher);
+            child(value, &mut hasher);
+        }
+        jsonish::Value::FixedJson(value, fixes) => {
+            fixes.hash(&mut hasher);
+            child(value, &mut hasher);
+        }
+        jsonish::Value::AnyOf(items, raw) => {
+            raw.hash(&mut hasher);
+            items.len().hash(&mut hasher);
+            items.iter().for_each(|item| child(item, &mut hasher));
+        }
+    }
+
+    let node = Node {
+        id: NodeId(hasher.finish()),
+        size,
+    };
+    nodes.insert(address(value), node);
+    node
+}
+
+fn address(value: &jsonish::Value) -> usize {
+    value as *const jsonish::Value as usize
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use internal_baml_core::ir::FieldType;
+
+use crate::deserializer::types::BamlValueWithFlags;
+
+use super::{ParsingContext, ParsingError, TypeCoercer};
+
+pub(super) fn coerce_alias(
+    ctx: &ParsingContext,
+    target: &FieldType,
+    value: Option<&crate::jsonish::Value>,
+) -> Result<BamlValueWithFlags, ParsingError> {
+    assert!(matches!(target, FieldType::RecursiveTypeAlias(_)));
+    log::debug!(
+        "scope: {scope} :: coercing to: {name} (current: {current})",
+        name = target.to_string(),
+        scope = ctx.display_scope(),
+        current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
+    );
+
+    let FieldType::RecursiveTypeAlias(alias) = target else {
+        unreachable!()
+    };
+
+    let Some(resolved) = ctx.of.structural_recursive_aliases.get(alias) else {
+        return Err(ctx.error_internal(format!("Type alias {alias} not found")));
+    };
+
+    // Same as recursive classes, an alias can point back to itself without
+    // consuming any input (for example when a single value is coerced into a
+    // list), so we track which alias-value pairs we're already coercing.
+    let mut nested_ctx = None;
+
+    if let Some(v) = value {
+        let alias_value_pair = (alias.to_owned(), v.to_owned());
+
+        if ctx.visited.contains(&alias_value_pair) {
+            return Err(ctx.error_circular_reference(alias, v));
+        }
+
+        nested_ctx = Some(ctx.visit_class_value_pair(alias_value_pair, v));
+    }
+
+    let ctx = nested_ctx.as_ref().unwrap_or(ctx);
+
+    resolved.coerce(ctx, resolved, value)
+}
@@ -33,7 +33,10 @@ pub(super) fn coerce_array(
     match &value {
         Some(crate::jsonish::Value::Array(arr)) => {
             for (i, item) in arr.iter().enumerate() {
-                match inner.coerce(&ctx.enter_scope(&format!("{i}")), inner, Some(item)) {
+                let item_ctx = ctx
+                    .enter_scope(&format!("{i}"))
+                    .with_in_progress(i + 1 == arr.len());
+                match inner.coerce(&item_ctx, inner, Some(item)) {
                     Ok(v) => items.push(v),
                     // TODO(vbv): document why we penalize in proportion to how deep into an array a parse error is
                     Err(e) => flags.add_flag(Flag::ArrayItemParseError(i, e)),
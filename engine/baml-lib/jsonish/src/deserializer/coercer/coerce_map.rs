@@ -38,8 +38,9 @@ pub(super) fn coerce_map(
     match &value {
         crate::jsonish::Value::Object(obj) => {
             let mut items = BamlMap::new();
-            for (key, value) in obj.iter() {
-                match value_type.coerce(&ctx.enter_scope(key), value_type, Some(value)) {
+            for (idx, (key, value)) in obj.iter().enumerate() {
+                let value_ctx = ctx.enter_scope(key).with_in_progress(idx + 1 == obj.len());
+                match value_type.coerce(&value_ctx, value_type, Some(value)) {
                     Ok(v) => {
                         items.insert(key.clone(), (DeserializerConditions::new(), v));
                     }
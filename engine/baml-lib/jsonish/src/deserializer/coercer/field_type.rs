@@ -9,7 +9,7 @@ use crate::deserializer::{
 };
 
 use super::{
-    array_helper, coerce_array::coerce_array, coerce_map::coerce_map,
+    array_helper, coerce_alias::coerce_alias, coerce_array::coerce_array, coerce_map::coerce_map,
     coerce_optional::coerce_optional, coerce_union::coerce_union, ir_ref::IrRef, ParsingContext,
     ParsingError,
 };
@@ -21,91 +21,106 @@ impl TypeCoercer for FieldType {
         target: &FieldType,
         value: Option<&crate::jsonish::Value>,
     ) -> Result<BamlValueWithFlags, ParsingError> {
-        match value {
-            Some(crate::jsonish::Value::AnyOf(candidates, primitive)) => {
-                log::debug!(
-                    "scope: {scope} :: coercing to: {name} (current: {current})",
-                    name = target.to_string(),
-                    scope = ctx.display_scope(),
-                    current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
-                );
-                if matches!(target, FieldType::Primitive(TypeValue::String)) {
-                    self.coerce(
-                        ctx,
-                        target,
-                        Some(&crate::jsonish::Value::String(primitive.clone())),
-                    )
-                } else {
-                    array_helper::coerce_array_to_singular(
-                        ctx,
-                        target,
-                        &candidates.iter().collect::<Vec<_>>(),
-                        &|val| self.coerce(ctx, target, Some(val)),
-                    )
-                }
-            }
-            Some(crate::jsonish::Value::Markdown(_t, v)) => {
-                log::debug!(
-                    "scope: {scope} :: coercing to: {name} (current: {current})",
-                    name = target.to_string(),
-                    scope = ctx.display_scope(),
-                    current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
-                );
-                self.coerce(ctx, target, Some(v)).and_then(|mut v| {
-                    v.add_flag(Flag::ObjectFromMarkdown(
-                        if matches!(target, FieldType::Primitive(TypeValue::String)) {
-                            1
-                        } else {
-                            0
-                        },
-                    ));
+        match ctx.cache {
+            Some(cache) => cache.get_or_coerce(ctx, self, target, value, || {
+                coerce_field_type(self, ctx, target, value)
+            }),
+            None => coerce_field_type(self, ctx, target, value),
+        }
+    }
+}
 
-                    Ok(v)
-                })
+fn coerce_field_type(
+    field_type: &FieldType,
+    ctx: &ParsingContext,
+    target: &FieldType,
+    value: Option<&crate::jsonish::Value>,
+) -> Result<BamlValueWithFlags, ParsingError> {
+    match value {
+        Some(crate::jsonish::Value::AnyOf(candidates, primitive)) => {
+            log::debug!(
+                "scope: {scope} :: coercing to: {name} (current: {current})",
+                name = target.to_string(),
+                scope = ctx.display_scope(),
+                current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
+            );
+            if matches!(target, FieldType::Primitive(TypeValue::String)) {
+                field_type.coerce(
+                    ctx,
+                    target,
+                    Some(&crate::jsonish::Value::String(primitive.clone())),
+                )
+            } else {
+                array_helper::coerce_array_to_singular(
+                    ctx,
+                    target,
+                    &candidates.iter().collect::<Vec<_>>(),
+                    &|val| field_type.coerce(ctx, target, Some(val)),
+                )
             }
-            Some(crate::jsonish::Value::FixedJson(v, fixes)) => {
-                log::debug!(
-                    "scope: {scope} :: coercing to: {name} (current: {current})",
-                    name = target.to_string(),
-                    scope = ctx.display_scope(),
-                    current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
-                );
-                let mut v = self.coerce(ctx, target, Some(v))?;
-                v.add_flag(Flag::ObjectFromFixedJson(fixes.to_vec()));
+        }
+        Some(crate::jsonish::Value::Markdown(_t, v)) => {
+            log::debug!(
+                "scope: {scope} :: coercing to: {name} (current: {current})",
+                name = target.to_string(),
+                scope = ctx.display_scope(),
+                current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
+            );
+            field_type.coerce(ctx, target, Some(v)).and_then(|mut v| {
+                v.add_flag(Flag::ObjectFromMarkdown(
+                    if matches!(target, FieldType::Primitive(TypeValue::String)) {
+                        1
+                    } else {
+                        0
+                    },
+                ));
+
                 Ok(v)
-            }
-            _ => match self {
-                FieldType::Primitive(p) => p.coerce(ctx, target, value),
-                FieldType::Enum(e) => IrRef::Enum(e).coerce(ctx, target, value),
-                FieldType::Literal(l) => l.coerce(ctx, target, value),
-                FieldType::Class(c) => IrRef::Class(c).coerce(ctx, target, value),
-                FieldType::List(_) => coerce_array(ctx, self, value),
-                FieldType::Union(_) => coerce_union(ctx, self, value),
-                FieldType::Optional(_) => coerce_optional(ctx, self, value),
-                FieldType::Map(_, _) => coerce_map(ctx, self, value),
-                FieldType::Tuple(_) => Err(ctx.error_internal("Tuple not supported")),
-                FieldType::Constrained { base, .. } => {
-                    let mut coerced_value = base.coerce(ctx, base, value)?;
-                    let constraint_results = run_user_checks(&coerced_value.clone().into(), &self)
-                        .map_err(|e| ParsingError {
-                            reason: format!("Failed to evaluate constraints: {:?}", e),
-                            scope: ctx.scope.clone(),
-                            causes: Vec::new(),
-                        })?;
-                    validate_asserts(&constraint_results)?;
-                    let check_results = constraint_results
-                        .into_iter()
-                        .filter_map(|(maybe_check, result)| {
-                            maybe_check
-                                .as_check()
-                                .map(|(label, expr)| (label, expr, result))
-                        })
-                        .collect();
-                    coerced_value.add_flag(Flag::ConstraintResults(check_results));
-                    Ok(coerced_value)
-                }
-            },
+            })
+        }
+        Some(crate::jsonish::Value::FixedJson(v, fixes)) => {
+            log::debug!(
+                "scope: {scope} :: coercing to: {name} (current: {current})",
+                name = target.to_string(),
+                scope = ctx.display_scope(),
+                current = value.map(|v| v.r#type()).unwrap_or("<null>".into())
+            );
+            let mut v = field_type.coerce(ctx, target, Some(v))?;
+            v.add_flag(Flag::ObjectFromFixedJson(fixes.to_vec()));
+            Ok(v)
         }
+        _ => match field_type {
+            FieldType::Primitive(p) => p.coerce(ctx, target, value),
+            FieldType::Enum(e) => IrRef::Enum(e).coerce(ctx, target, value),
+            FieldType::Literal(l) => l.coerce(ctx, target, value),
+            FieldType::Class(c) => IrRef::Class(c).coerce(ctx, target, value),
+            FieldType::RecursiveTypeAlias(_) => coerce_alias(ctx, field_type, value),
+            FieldType::List(_) => coerce_array(ctx, field_type, value),
+            FieldType::Union(_) => coerce_union(ctx, field_type, value),
+            FieldType::Optional(_) => coerce_optional(ctx, field_type, value),
+            FieldType::Map(_, _) => coerce_map(ctx, field_type, value),
+            FieldType::Tuple(_) => Err(ctx.error_internal("Tuple not supported")),
+            FieldType::Constrained { base, .. } => {
+                let mut coerced_value = base.coerce(ctx, base, value)?;
+                let constraint_results = run_user_checks(&coerced_value.clone().into(), field_type)
+                    .map_err(|e| ParsingError {
+                        reason: format!("Failed to evaluate constraints: {:?}", e),
+                        scope: ctx.scope.clone(),
+                        causes: Vec::new(),
+                    })?;
+                validate_asserts(&constraint_results)?;
+                let check_results = constraint_results
+                    .into_iter()
+                    .filter_map(|(maybe_check, result)| {
+                        maybe_check
+                            .as_check()
+                            .map(|(label, expr)| (label, expr, result))
+                    })
+                    .collect();
+                coerced_value.add_flag(Flag::ConstraintResults(check_results));
+                Ok(coerced_value)
+            }
+        },
     }
 }
 
@@ -163,6 +178,7 @@ impl DefaultValue for FieldType {
             FieldType::Enum(e) => None,
             FieldType::Literal(_) => None,
             FieldType::Class(_) => None,
+            FieldType::RecursiveTypeAlias(_) => None,
             FieldType::List(_) => Some(BamlValueWithFlags::List(get_flags(), Vec::new())),
             FieldType::Union(items) => items.iter().find_map(|i| i.default_value(error)),
             FieldType::Primitive(TypeValue::Null) | FieldType::Optional(_) => {
@@ -49,7 +49,7 @@ impl TypeCoercer for Class {
             // recursive class should start from scratch with an empty visited
             // set so they will not fail because this class has already been
             // coerced for a different field.
-            nested_ctx = Some(ctx.visit_class_value_pair(cls_value_pair));
+            nested_ctx = Some(ctx.visit_class_value_pair(cls_value_pair, v));
         }
 
         // Now just maintain the previous context or get the new one and proceed
@@ -84,14 +84,17 @@ impl TypeCoercer for Class {
                 // match keys, if that fails, then do something fancy later.
                 let mut extra_keys = vec![];
                 let mut found_keys = false;
-                obj.iter().for_each(|(key, v)| {
+                obj.iter().enumerate().for_each(|(idx, (key, v))| {
                     if let Some(field) = self
                         .fields
                         .iter()
                         .find(|(name, ..)| name.rendered_name().trim() == key)
                     {
-                        let scope = ctx.enter_scope(field.0.real_name());
+                        let scope = ctx
+                            .enter_scope(field.0.real_name())
+                            .with_in_progress(idx + 1 == obj.len());
                         let parsed = field.1.coerce(&scope, &field.1, Some(v));
+                        let parsed = mark_in_progress(self, &scope, parsed);
                         update_map(&mut required_values, &mut optional_values, field, parsed);
                         found_keys = true;
                     } else {
@@ -114,6 +117,7 @@ impl TypeCoercer for Class {
                             v.add_flag(Flag::ImpliedKey(field.0.real_name().into()));
                             v
                         });
+                    let parsed = mark_in_progress(self, &scope, parsed);
 
                     if let Ok(parsed_value) = parsed {
                         update_map(
@@ -144,6 +148,7 @@ impl TypeCoercer for Class {
                         }
                         Err(e) => Err(e),
                     };
+                    let parsed = mark_in_progress(self, &scope, parsed);
                     update_map(&mut required_values, &mut optional_values, field, parsed);
                 }
 
@@ -172,6 +177,7 @@ impl TypeCoercer for Class {
                         }
                         Err(e) => Err(e),
                     };
+                    let parsed = mark_in_progress(self, &scope, parsed);
                     update_map(&mut required_values, &mut optional_values, field, parsed);
                 }
             }
@@ -193,10 +199,14 @@ impl TypeCoercer for Class {
                                 t.default_value(Some(e))
                             }
                             // If we're missing a field, thats ok!
-                            None => Some(BamlValueWithFlags::Null(
-                                DeserializerConditions::new()
-                                    .with_flag(Flag::OptionalDefaultFromNoValue),
-                            )),
+                            None => {
+                                default_from_attribute(self, ctx, field_name, t).or_else(|| {
+                                    Some(BamlValueWithFlags::Null(
+                                        DeserializerConditions::new()
+                                            .with_flag(Flag::OptionalDefaultFromNoValue),
+                                    ))
+                                })
+                            }
                         };
 
                         if let Some(next) = next {
@@ -218,16 +228,18 @@ impl TypeCoercer for Class {
                                     None
                                 }
                             }),
-                            None => t.default_value(None).or_else(|| {
-                                if ctx.allow_partials {
-                                    Some(BamlValueWithFlags::Null(
-                                        DeserializerConditions::new()
-                                            .with_flag(Flag::OptionalDefaultFromNoValue),
-                                    ))
-                                } else {
-                                    None
-                                }
-                            }),
+                            None => default_from_attribute(self, ctx, field_name, t)
+                                .or_else(|| t.default_value(None))
+                                .or_else(|| {
+                                    if ctx.allow_partials {
+                                        Some(BamlValueWithFlags::Null(
+                                            DeserializerConditions::new()
+                                                .with_flag(Flag::OptionalDefaultFromNoValue),
+                                        ))
+                                    } else {
+                                        None
+                                    }
+                                }),
                         };
 
                         if let Some(next) = next {
@@ -326,6 +338,10 @@ impl TypeCoercer for Class {
                     }
                 }
 
+                if ctx.in_progress && ctx.of.class_streaming(self.name.real_name()).is_some() {
+                    flags.add_flag(Flag::Incomplete);
+                }
+
                 let completed_instance = Ok(BamlValueWithFlags::Class(
                     self.name.real_name().into(),
                     flags,
@@ -345,6 +361,48 @@ impl TypeCoercer for Class {
     }
 }
 
+/// Flags field values that may still be streaming in, so that the
+/// `@stream.*` attributes can be applied once the whole response is coerced.
+/// See `semantic_streaming`.
+fn mark_in_progress(
+    class: &Class,
+    ctx: &ParsingContext,
+    parsed: Result<BamlValueWithFlags, ParsingError>,
+) -> Result<BamlValueWithFlags, ParsingError> {
+    if !ctx.in_progress || ctx.of.class_streaming(class.name.real_name()).is_none() {
+        return parsed;
+    }
+    parsed.map(|mut v| {
+        v.add_flag(Flag::Incomplete);
+        v
+    })
+}
+
+/// The `@default(...)` value of a field missing from the response. Nothing is
+/// filled in while the class is still streaming in, since the field may yet
+/// show up.
+fn default_from_attribute(
+    class: &Class,
+    ctx: &ParsingContext,
+    field: &Name,
+    t: &FieldType,
+) -> Option<BamlValueWithFlags> {
+    if ctx.in_progress {
+        return None;
+    }
+    let default = ctx
+        .of
+        .field_default(class.name.real_name(), field.real_name())?;
+    let value = serde_json::to_value(default)
+        .and_then(serde_json::from_value::<crate::jsonish::Value>)
+        .ok()?;
+
+    let scope = ctx.enter_scope(field.real_name());
+    let mut value = t.coerce(&scope, t, Some(&value)).ok()?;
+    value.add_flag(Flag::DefaultFromAttribute);
+    Some(value)
+}
+
 pub fn apply_constraints(
     class_type: &FieldType,
     scope: Vec<String>,
@@ -2,10 +2,11 @@
 //!
 //! Used mostly for matching enum variants or literal strings.
 
-use std::{cmp::Ordering, collections::HashMap};
+use std::cmp::Ordering;
 
 use anyhow::Result;
 use baml_types::FieldType;
+use indexmap::IndexMap;
 
 use crate::{
     deserializer::{
@@ -181,9 +182,11 @@ fn string_match_strategy<'c>(
         }
     }
 
-    // Count occurrences of each variant in non-overlapping matches.
+    // Count occurrences of each variant in non-overlapping matches, in the
+    // order they're first matched so that ties are broken the same way
+    // every time.
     // (count, variant)
-    let mut variant_counts = HashMap::<&'c str, usize>::new();
+    let mut variant_counts = IndexMap::<&'c str, usize>::new();
     for (_, _, _, variant) in &filtered_matches {
         if let Some(count) = variant_counts.get_mut(*variant) {
             // Increment count if variant already exists.
@@ -1,4 +1,6 @@
 mod array_helper;
+mod cache;
+mod coerce_alias;
 mod coerce_array;
 mod coerce_literal;
 mod coerce_map;
@@ -22,11 +24,43 @@ use crate::jsonish;
 
 use super::types::BamlValueWithFlags;
 
+pub(crate) use cache::CoercionCache;
+
 pub struct ParsingContext<'a> {
     pub scope: Vec<String>,
     visited: HashSet<(String, jsonish::Value)>,
+    /// The nodes of the values in `visited`, as far as the cache can tell.
+    visited_nodes: Vec<(String, Option<cache::Node>)>,
     pub of: &'a OutputFormatContent,
     pub allow_partials: bool,
+    /// Whether the value being coerced may still be streaming in. Only the
+    /// last item of a partial response, recursively, can be.
+    pub in_progress: bool,
+    cache: Option<&'a CoercionCache>,
+}
+
+impl<'a> ParsingContext<'a> {
+    /// Reuses, and records, coercion results in `cache`. The cache must only
+    /// ever be used with the same `of` and `allow_partials`.
+    pub(crate) fn with_cache(mut self, cache: &'a CoercionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Called with the parsed value right before coercing it, so that the
+    /// cache can tell its nodes apart.
+    pub(crate) fn start_coercing(&self, value: &jsonish::Value) {
+        if let Some(cache) = self.cache {
+            cache.start_parse(value);
+        }
+    }
+
+    /// For one of several siblings (array items, object entries): only the
+    /// last one can still be streaming in.
+    pub(crate) fn with_in_progress(mut self, is_last: bool) -> Self {
+        self.in_progress = self.in_progress && is_last;
+        self
+    }
 }
 
 impl ParsingContext<'_> {
@@ -41,8 +75,11 @@ impl ParsingContext<'_> {
         ParsingContext {
             scope: Vec::new(),
             visited: HashSet::new(),
+            visited_nodes: Vec::new(),
             of,
             allow_partials,
+            in_progress: allow_partials,
+            cache: None,
         }
     }
 
@@ -52,25 +89,40 @@ impl ParsingContext<'_> {
         ParsingContext {
             scope: new_scope,
             visited: self.visited.clone(),
+            visited_nodes: self.visited_nodes.clone(),
             of: self.of,
             allow_partials: self.allow_partials,
+            in_progress: self.in_progress,
+            cache: self.cache,
         }
     }
 
     // TODO: This function and `enter_scope` are clonning both the scope vector
     // and visited hash set each time. Maybe it can be optimized with interior
     // mutability or something.
+    //
+    // `value` is the value of the pair where it was found, by which the cache
+    // knows it.
     pub(crate) fn visit_class_value_pair(
         &self,
         cls_value_pair: (String, jsonish::Value),
+        value: &jsonish::Value,
     ) -> ParsingContext {
+        let mut new_visited_nodes = self.visited_nodes.clone();
+        new_visited_nodes.push((
+            cls_value_pair.0.clone(),
+            self.cache.and_then(|cache| cache.node(value)),
+        ));
         let mut new_visited = self.visited.clone();
         new_visited.insert(cls_value_pair);
         ParsingContext {
             scope: self.scope.clone(),
             visited: new_visited,
+            visited_nodes: new_visited_nodes,
             of: self.of,
             allow_partials: self.allow_partials,
+            in_progress: self.in_progress,
+            cache: self.cache,
         }
     }
 
@@ -139,6 +191,22 @@ impl ParsingContext<'_> {
         }
     }
 
+    pub(crate) fn error_pdf_not_supported(&self) -> ParsingError {
+        ParsingError {
+            reason: "Pdf type is not supported here".to_string(),
+            scope: self.scope.clone(),
+            causes: vec![],
+        }
+    }
+
+    pub(crate) fn error_video_not_supported(&self) -> ParsingError {
+        ParsingError {
+            reason: "Video type is not supported here".to_string(),
+            scope: self.scope.clone(),
+            causes: vec![],
+        }
+    }
+
     pub(crate) fn error_map_must_have_string_key(&self, key_type: &FieldType) -> ParsingError {
         ParsingError {
             reason: format!("Maps may only have strings for keys, but got {}", key_type),
@@ -206,11 +274,11 @@ impl ParsingContext<'_> {
 
     pub(crate) fn error_circular_reference(
         &self,
-        cls: &str,
+        name: &str,
         value: &jsonish::Value,
     ) -> ParsingError {
         ParsingError {
-            reason: format!("Circular reference detected for class-value pair {cls} <-> {value}"),
+            reason: format!("Circular reference detected for type-value pair {name} <-> {value}"),
             scope: self.scope.clone(),
             causes: vec![],
         }
@@ -33,6 +33,8 @@ pub enum Flag {
     DefaultFromNoValue,
     DefaultButHadValue(crate::jsonish::Value),
     OptionalDefaultFromNoValue,
+    /// The field was missing, so its `@default(...)` value was used.
+    DefaultFromAttribute,
 
     // String -> X convertions.
     StringToBool(String),
@@ -47,6 +49,9 @@ pub enum Flag {
 
     /// Constraint results (only contains checks)
     ConstraintResults(Vec<(String, JinjaExpression, bool)>),
+
+    /// The value may still be streaming in.
+    Incomplete,
 }
 
 #[derive(Clone)]
@@ -88,6 +93,7 @@ impl DeserializerConditions {
                 Flag::DefaultFromNoValue => None,
                 Flag::DefaultButHadValue(_) => None,
                 Flag::OptionalDefaultFromNoValue => None,
+                Flag::DefaultFromAttribute => None,
                 Flag::StringToBool(_) => None,
                 Flag::StringToNull(_) => None,
                 Flag::StringToChar(_) => None,
@@ -96,6 +102,7 @@ impl DeserializerConditions {
                 Flag::UnionMatch(_idx, _) => None,
                 Flag::DefaultButHadUnparseableValue(e) => Some(e.clone()),
                 Flag::ConstraintResults(_) => None,
+                Flag::Incomplete => None,
             })
             .collect::<Vec<_>>()
     }
@@ -145,6 +152,9 @@ impl std::fmt::Display for Flag {
             Flag::DefaultFromNoValue => {
                 write!(f, "Default value")?;
             }
+            Flag::DefaultFromAttribute => {
+                write!(f, "Default value from @default")?;
+            }
             Flag::ObjectFromFixedJson(fixes) => {
                 write!(f, "JSON (Fixed {} mistakes)", fixes.len())?;
             }
@@ -256,6 +266,9 @@ impl std::fmt::Display for Flag {
                     )?;
                 }
             }
+            Flag::Incomplete => {
+                write!(f, "Incomplete")?;
+            }
         }
         Ok(())
     }
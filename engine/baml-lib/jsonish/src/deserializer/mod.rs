@@ -2,4 +2,5 @@ pub mod coercer;
 pub mod deserialize_flags;
 // pub mod schema;
 mod score;
+pub(crate) mod semantic_streaming;
 pub mod types;
@@ -36,6 +36,7 @@ impl WithScore for Flag {
         match self {
             Flag::InferedObject(_) => 0, // Dont penalize for this but instead handle it at the top level
             Flag::OptionalDefaultFromNoValue => 1,
+            Flag::DefaultFromAttribute => 1,
             Flag::DefaultFromNoValue => 100,
             Flag::DefaultButHadValue(_) => 110,
             Flag::ObjectFromFixedJson(_) => 0,
@@ -69,6 +70,7 @@ impl WithScore for Flag {
             Flag::NoFields(_) => 1,
             // No scores for contraints
             Flag::ConstraintResults(_) => 0,
+            Flag::Incomplete => 0,
         }
     }
 }
@@ -0,0 +1,131 @@
+use baml_types::BamlMap;
+use internal_baml_jinja::types::OutputFormatContent;
+
+use super::{
+    deserialize_flags::{DeserializerConditions, Flag},
+    types::BamlValueWithFlags,
+};
+
+/// Applies the `@stream.*` attributes to a partial result:
+///
+/// - `@stream.done` fields are null until their value is complete.
+/// - `@stream.not_null` fields hold back the enclosing object while null.
+/// - `@stream.with_state` fields are wrapped as `{ value, state }`, where
+///   state is `Incomplete` or `Complete`.
+/// - `@@stream.done` classes are held back until they are complete.
+///
+/// A held back class field becomes null, and a held back list item or map
+/// entry is dropped. Returns `None` if the value itself is held back.
+///
+/// Relies on the coercer flagging the values that may still be streaming in
+/// with [`Flag::Incomplete`].
+pub(crate) fn apply(
+    of: &OutputFormatContent,
+    value: BamlValueWithFlags,
+) -> Option<BamlValueWithFlags> {
+    match value {
+        BamlValueWithFlags::List(flags, items) => Some(BamlValueWithFlags::List(
+            flags,
+            items
+                .into_iter()
+                .filter_map(|item| apply(of, item))
+                .collect(),
+        )),
+        BamlValueWithFlags::Map(flags, entries) => Some(BamlValueWithFlags::Map(
+            flags,
+            entries
+                .into_iter()
+                .filter_map(|(key, (entry_flags, value))| {
+                    Some((key, (entry_flags, apply(of, value)?)))
+                })
+                .collect(),
+        )),
+        BamlValueWithFlags::Class(name, flags, fields) => {
+            let Some(streaming) = of.class_streaming(&name) else {
+                let fields = fields
+                    .into_iter()
+                    .map(|(key, value)| (key, apply(of, value).unwrap_or_else(held_back)))
+                    .collect();
+                return Some(BamlValueWithFlags::Class(name, flags, fields));
+            };
+
+            let class_incomplete = is_incomplete(&flags);
+            if streaming.done && class_incomplete {
+                return None;
+            }
+
+            let mut applied = BamlMap::new();
+            for (key, value) in fields {
+                let behavior = streaming.field(&key);
+                let incomplete = is_incomplete(value.conditions());
+                let missing = is_missing(&value);
+
+                let value = if behavior.done && incomplete {
+                    None
+                } else {
+                    apply(of, value)
+                }
+                .unwrap_or_else(held_back);
+
+                if behavior.not_null && matches!(value, BamlValueWithFlags::Null(_)) {
+                    return None;
+                }
+
+                let value = if behavior.with_state {
+                    let complete = !class_incomplete || (!incomplete && !missing);
+                    with_state(value, complete)
+                } else {
+                    value
+                };
+                applied.insert(key, value);
+            }
+
+            Some(BamlValueWithFlags::Class(name, flags, applied))
+        }
+        BamlValueWithFlags::String(_)
+        | BamlValueWithFlags::Int(_)
+        | BamlValueWithFlags::Float(_)
+        | BamlValueWithFlags::Bool(_)
+        | BamlValueWithFlags::Enum(_, _)
+        | BamlValueWithFlags::Null(_)
+        | BamlValueWithFlags::Media(_) => Some(value),
+    }
+}
+
+fn is_incomplete(conditions: &DeserializerConditions) -> bool {
+    conditions
+        .flags()
+        .iter()
+        .any(|flag| matches!(flag, Flag::Incomplete))
+}
+
+/// The field didn't show up in the response (yet).
+fn is_missing(value: &BamlValueWithFlags) -> bool {
+    value.conditions().flags().iter().any(|flag| {
+        matches!(
+            flag,
+            Flag::DefaultFromNoValue | Flag::OptionalDefaultFromNoValue
+        )
+    })
+}
+
+fn held_back() -> BamlValueWithFlags {
+    BamlValueWithFlags::Null(DeserializerConditions::new())
+}
+
+fn with_state(value: BamlValueWithFlags, complete: bool) -> BamlValueWithFlags {
+    let state = if complete { "Complete" } else { "Incomplete" };
+    BamlValueWithFlags::Map(
+        DeserializerConditions::new(),
+        BamlMap::from_iter([
+            ("value".to_string(), (DeserializerConditions::new(), value)),
+            (
+                "state".to_string(),
+                (
+                    DeserializerConditions::new(),
+                    BamlValueWithFlags::String(state.to_string().into()),
+                ),
+            ),
+        ]),
+    )
+}
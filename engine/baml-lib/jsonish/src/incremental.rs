@@ -0,0 +1,80 @@
+use anyhow::Result;
+use baml_types::FieldType;
+use internal_baml_core::ir::TypeValue;
+use internal_baml_jinja::types::OutputFormatContent;
+
+use crate::{
+    deserializer::coercer::{CoercionCache, ParsingContext},
+    jsonish, BamlValueWithFlags,
+};
+
+/// Parses a response as it streams in.
+///
+/// Every call returns exactly what [`crate::from_str`] (with `allow_partials`)
+/// returns for everything received so far, but without starting over for
+/// every chunk: the parsers resume from where they left off, and only the
+/// parts of the response that changed since the last chunk are coerced
+/// again.
+pub struct IncrementalParser<'a> {
+    of: &'a OutputFormatContent,
+    target: &'a FieldType,
+    content: String,
+    checkpoints: jsonish::Checkpoints,
+    coercions: CoercionCache,
+}
+
+impl<'a> IncrementalParser<'a> {
+    pub fn new(of: &'a OutputFormatContent, target: &'a FieldType) -> Self {
+        Self {
+            of,
+            target,
+            content: String::new(),
+            checkpoints: Default::default(),
+            coercions: Default::default(),
+        }
+    }
+
+    /// Everything received so far.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Appends `delta` to the response and parses everything received so far.
+    pub fn push(&mut self, delta: &str) -> Result<BamlValueWithFlags> {
+        self.content.push_str(delta);
+        self.checkpoints.extend(&self.content);
+        self.parse_content()
+    }
+
+    /// Parses `content`, the whole response so far. Only the part that was
+    /// appended since the last call is new to the parser; if `content`
+    /// doesn't start with what was received before, the response starts over.
+    pub fn parse(&mut self, content: &str) -> Result<BamlValueWithFlags> {
+        match content.strip_prefix(self.content.as_str()) {
+            Some(delta) => self.push(delta),
+            None => {
+                self.content.clear();
+                self.checkpoints = Default::default();
+                self.push(content)
+            }
+        }
+    }
+
+    fn parse_content(&self) -> Result<BamlValueWithFlags> {
+        if matches!(self.target, FieldType::Primitive(TypeValue::String)) {
+            return Ok(BamlValueWithFlags::String(self.content.clone().into()));
+        }
+
+        let options = jsonish::ParseOptions::default().with_checkpoints(&self.checkpoints);
+        let result = jsonish::parse(&self.content, options).and_then(|value| {
+            crate::coerce(
+                ParsingContext::new(self.of, true).with_cache(&self.coercions),
+                self.target,
+                &self.content,
+                value,
+            )
+        });
+        self.coercions.finish_parse();
+        result
+    }
+}
@@ -8,4 +8,4 @@ mod value;
 pub use value::{Fixes, Value};
 
 // pub use iterative_parser::{parse_jsonish_value, JSONishOptions};
-pub use parser::{parse, ParseOptions};
+pub use parser::{parse, Checkpoints, ParseOptions};
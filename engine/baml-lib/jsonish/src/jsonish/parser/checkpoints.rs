@@ -0,0 +1,255 @@
+use std::{
+    cell::RefCell,
+    hash::{BuildHasher, Hasher},
+};
+
+use super::{
+    fixing_parser::JsonParseState, markdown_parser::MarkdownBlocks, multi_json_parser::BracketScan,
+    xml_parser::XmlParser, JsonScan,
+};
+
+/// How many states of each parser [`Checkpoints`] keeps around. A single call
+/// to `entry::parse` can reach a parser with a few different slices of the
+/// same response (the whole response, the JSON-looking part of it, the
+/// contents of a markdown block), each of which gets its own checkpoint.
+const MAX_CHECKPOINTS: usize = 8;
+
+/// The Mersenne prime 2^61 - 1, the modulus of the prefix hashes.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// Parser states saved by earlier calls to `entry::parse`.
+///
+/// The state a parser reaches after consuming some input only depends on that
+/// input and on whatever the parser peeked at past it, so as long as the
+/// parser never peeked at the end of the input, the state is still valid once
+/// more input is appended. A streamed response that grew since the last call
+/// resumes every parser from its latest such state instead of going over the
+/// whole response again.
+#[derive(Default)]
+pub struct Checkpoints {
+    response: PrefixHashes,
+    pub(super) json: Saved<JsonScan>,
+    pub(super) xml: Saved<XmlParser>,
+    pub(super) markdown: Saved<MarkdownBlocks>,
+    pub(super) multi_json: Saved<BracketScan>,
+    pub(super) fixing: Saved<JsonParseState>,
+}
+
+impl std::fmt::Debug for Checkpoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Checkpoints(json: {}, xml: {}, markdown: {}, multi_json: {}, fixing: {})",
+            self.json.len(),
+            self.xml.len(),
+            self.markdown.len(),
+            self.multi_json.len(),
+            self.fixing.len()
+        )
+    }
+}
+
+impl Checkpoints {
+    /// Hashes whatever was appended to `response` since the last call. The
+    /// response must only ever grow: it has to start with what it was the
+    /// last time.
+    pub fn extend(&mut self, response: &str) {
+        self.response.extend(response);
+    }
+
+    /// `str` as input to the parsers, whose checkpoints it may resume.
+    pub(super) fn input<'a>(&'a self, str: &'a str) -> Input<'a> {
+        Input {
+            str,
+            hashes: &self.response,
+        }
+    }
+}
+
+/// Input to one of the parsers.
+pub(super) struct Input<'a> {
+    str: &'a str,
+    hashes: &'a PrefixHashes,
+}
+
+impl Input<'_> {
+    fn starts_with(&self, len: usize, hash: u64) -> bool {
+        self.str
+            .get(..len)
+            .is_some_and(|prefix| self.hashes.hash(prefix) == hash)
+    }
+}
+
+/// Polynomial hashes of every prefix of the response being streamed, from
+/// which the hash of any slice of it is worked out in constant time. That
+/// keeps checking whether a parser's input starts with what the parser saw
+/// before from going over all of it again for every chunk.
+struct PrefixHashes {
+    /// Picked at random so that no response can be crafted to collide.
+    base: u64,
+    /// Where the response is in memory, to tell slices of it apart.
+    start: usize,
+    /// `prefixes[i]` is the hash of the first `i` bytes of the response.
+    prefixes: Vec<u64>,
+}
+
+impl Default for PrefixHashes {
+    fn default() -> Self {
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        Self {
+            base: 256 + random % (MODULUS - 512),
+            start: 0,
+            prefixes: vec![0],
+        }
+    }
+}
+
+impl PrefixHashes {
+    fn extend(&mut self, response: &str) {
+        let hashed = self.prefixes.len() - 1;
+        let mut hash = self.prefixes[hashed];
+        for &byte in &response.as_bytes()[hashed.min(response.len())..] {
+            hash = self.push(hash, byte);
+            self.prefixes.push(hash);
+        }
+        self.start = response.as_ptr() as usize;
+    }
+
+    fn push(&self, hash: u64, byte: u8) -> u64 {
+        (mul(hash, self.base) + byte as u64 + 1) % MODULUS
+    }
+
+    fn hash(&self, str: &str) -> u64 {
+        let hashed = self.prefixes.len() - 1;
+        let offset = (str.as_ptr() as usize).wrapping_sub(self.start);
+        if offset > hashed || str.len() > hashed - offset {
+            // Not part of the response, which only happens to input that
+            // didn't come from a streamed response in the first place.
+            return str.bytes().fold(0, |hash, byte| self.push(hash, byte));
+        }
+
+        let shifted = mul(self.prefixes[offset], pow(self.base, str.len()));
+        (self.prefixes[offset + str.len()] + MODULUS - shifted) % MODULUS
+    }
+}
+
+fn mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+fn pow(mut base: u64, mut exp: usize) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// The states of one parser, each with the length and hash of the input it
+/// was reached with and where in that input it resumes.
+pub(super) struct Saved<T> {
+    saved: RefCell<Vec<Checkpoint<T>>>,
+}
+
+struct Checkpoint<T> {
+    seen_len: usize,
+    seen_hash: u64,
+    resume_at: usize,
+    state: T,
+}
+
+impl<T> Default for Saved<T> {
+    fn default() -> Self {
+        Self {
+            saved: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl<T> Saved<T> {
+    fn len(&self) -> usize {
+        self.saved.borrow().len()
+    }
+
+    /// Removes the state that got the furthest into `input`, among those
+    /// `usable` accepts, and returns it along with where in `input` it
+    /// resumes.
+    pub(super) fn take(&self, input: &Input, usable: impl Fn(&T) -> bool) -> Option<(usize, T)> {
+        let mut saved = self.saved.borrow_mut();
+        let idx = saved
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| input.starts_with(c.seen_len, c.seen_hash) && usable(&c.state))
+            .max_by_key(|(_, c)| c.resume_at)
+            .map(|(idx, _)| idx)?;
+        let checkpoint = saved.remove(idx);
+        Some((checkpoint.resume_at, checkpoint.state))
+    }
+
+    /// Saves `state`, reached at `resume_at` while parsing `seen`. It is only
+    /// resumed for input that starts with all of `seen`, so it may depend on
+    /// whatever the parser peeked at past `resume_at`.
+    pub(super) fn save(&self, seen: &Input, resume_at: usize, state: T) {
+        let mut saved = self.saved.borrow_mut();
+        if saved.len() >= MAX_CHECKPOINTS {
+            saved.remove(0);
+        }
+        saved.push(Checkpoint {
+            seen_len: seen.str.len(),
+            seen_hash: seen.hashes.hash(seen.str),
+            resume_at,
+            state,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slices_of_the_response_hash_like_any_other_string() {
+        let mut checkpoints = Checkpoints::default();
+        let mut response = String::from("Here is the JSON: {\"a\": ");
+        checkpoints.extend(&response);
+        response.push_str("[1, 2, \"three\"]}");
+        checkpoints.extend(&response);
+
+        let copy = response.clone();
+        for start in 0..response.len() {
+            for end in start..=response.len() {
+                assert_eq!(
+                    checkpoints.response.hash(&response[start..end]),
+                    checkpoints.response.hash(&copy[start..end]),
+                    "{:?}",
+                    &response[start..end]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn only_resumes_input_that_starts_with_what_was_seen() {
+        let mut checkpoints = Checkpoints::default();
+        let response = "{\"a\": 1, \"b\": 2}";
+        checkpoints.extend(response);
+
+        let saved = Saved::default();
+        saved.save(&checkpoints.input(&response[..8]), 8, ());
+        assert_eq!(
+            saved.take(&checkpoints.input("{\"a\": 2, \"b\""), |_| true),
+            None
+        );
+        assert_eq!(saved.take(&checkpoints.input("{\"a\": 1"), |_| true), None);
+        assert_eq!(
+            saved.take(&checkpoints.input(response), |_| true),
+            Some((8, ()))
+        );
+    }
+}
@@ -2,9 +2,9 @@ use anyhow::Result;
 
 use crate::jsonish::{
     parser::{
-        fixing_parser,
+        fixing_parser, json_scan,
         markdown_parser::{self, MarkdownResult},
-        multi_json_parser,
+        multi_json_parser, xml_parser,
     },
     value::Fixes,
     Value,
@@ -22,25 +22,65 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
         ));
     }
 
-    match serde_json::from_str(str) {
-        Ok(v) => return Ok(Value::AnyOf(vec![v], str.to_string())),
-        Err(e) => {
-            log::debug!("Invalid JSON: {:?}", e);
+    if json_scan::may_be_json(str, &options) {
+        match serde_json::from_str(str) {
+            Ok(v) => return Ok(Value::AnyOf(vec![v], str.to_string())),
+            Err(e) => {
+                log::debug!("Invalid JSON: {:?}", e);
+            }
+        };
+    }
+
+    // Responses written entirely in XML, as asked for by
+    // `ctx.output_format(style="xml")`. An element's body is only ever a
+    // string though, so JSON wrapped in a tag (`<answer>{"a": 1}</answer>`)
+    // is left to the other parsers and their values are kept as candidates
+    // too. Anything else only goes through the XML parser once the other
+    // parsers found nothing.
+    let xml =
+        xml_parser::starts_with_tag(str.trim_start()).then(|| xml_parser::parse(str, &options));
+
+    match (xml, parse_embedded(str, &options)?) {
+        (Some(Ok(doc)), embedded) if doc.is_entire_input => {
+            let mut candidates = vec![doc.value];
+            match embedded {
+                Some(Value::AnyOf(others, _)) => candidates.extend(others),
+                Some(other) => candidates.push(other),
+                None => {}
+            }
+            return Ok(Value::AnyOf(candidates, str.to_string()));
         }
-    };
+        (_, Some(value)) => return Ok(value),
+        (xml, None) => match xml.unwrap_or_else(|| xml_parser::parse(str, &options)) {
+            Ok(doc) => return Ok(Value::AnyOf(vec![doc.value], str.to_string())),
+            Err(e) => {
+                log::debug!("Invalid XML: {:?}", e);
+            }
+        },
+    }
 
+    if options.allow_as_string {
+        return Ok(Value::String(str.to_string()));
+    }
+
+    Err(anyhow::anyhow!("Failed to parse JSON"))
+}
+
+/// Looks for JSON in markdown blocks, JSON objects in prose and JSON that
+/// needs fixing, in that order.
+fn parse_embedded(str: &str, options: &ParseOptions) -> Result<Option<Value>> {
     if options.allow_markdown_json {
-        match markdown_parser::parse(str, &options) {
+        match markdown_parser::parse(str, options) {
             Ok(items) => match items.len() {
                 0 => {}
                 1 => {
                     let res = items.into_iter().next();
                     match res {
                         Some(MarkdownResult::CodeBlock(s, v)) => {
-                            return Ok(Value::AnyOf(
+                            return Ok(Some(Value::AnyOf(
                                 vec![Value::Markdown(s.to_string(), Box::new(v))],
                                 str.to_string(),
-                            ));
+                            )));
                         }
                         _ => {
                             log::debug!("Unexpected markdown result: {:?}", res);
@@ -93,7 +133,7 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
                         .chain(std::iter::once(array))
                         .chain(others)
                         .collect::<Vec<_>>();
-                    return Ok(Value::AnyOf(items, str.to_string()));
+                    return Ok(Some(Value::AnyOf(items, str.to_string())));
                 }
             },
             Err(e) => {
@@ -103,11 +143,11 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
     }
 
     if options.all_finding_all_json_objects {
-        match multi_json_parser::parse(str, &options) {
+        match multi_json_parser::parse(str, options) {
             Ok(items) => match items.len() {
                 0 => {}
                 1 => {
-                    return Ok(Value::AnyOf(
+                    return Ok(Some(Value::AnyOf(
                         vec![Value::FixedJson(
                             items
                                 .into_iter()
@@ -117,7 +157,7 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
                             vec![Fixes::GreppedForJSON],
                         )],
                         str.to_string(),
-                    ))
+                    )))
                 }
                 _ => {
                     let items_clone = Value::Array(items.clone());
@@ -126,7 +166,7 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
                         .chain(std::iter::once(items_clone))
                         .map(|v| Value::FixedJson(v.into(), vec![Fixes::GreppedForJSON]))
                         .collect::<Vec<_>>();
-                    return Ok(Value::AnyOf(items, str.to_string()));
+                    return Ok(Some(Value::AnyOf(items, str.to_string())));
                 }
             },
             Err(e) => {
@@ -136,7 +176,7 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
     }
 
     if options.allow_fixes {
-        match fixing_parser::parse(str, &options) {
+        match fixing_parser::parse(str, options) {
             Ok(items) => {
                 match items.len() {
                     0 => {}
@@ -144,10 +184,10 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
                         let (v, fixes) = items.into_iter().next().ok_or_else(|| {
                             anyhow::anyhow!("Expected 1 item when performing fixes")
                         })?;
-                        return Ok(Value::AnyOf(
+                        return Ok(Some(Value::AnyOf(
                             vec![Value::FixedJson(v.into(), fixes)],
                             str.to_string(),
-                        ));
+                        )));
                     }
                     _ => {
                         // In the case of multiple JSON objects:
@@ -169,7 +209,7 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
                             .into_iter()
                             .chain(std::iter::once(items_clone))
                             .collect::<Vec<_>>();
-                        return Ok(Value::AnyOf(items, str.to_string()));
+                        return Ok(Some(Value::AnyOf(items, str.to_string())));
                     }
                 }
             }
@@ -179,9 +219,5 @@ pub fn parse<'a>(str: &'a str, mut options: ParseOptions) -> Result<Value> {
         }
     }
 
-    if options.allow_as_string {
-        return Ok(Value::String(str.to_string()));
-    }
-
-    Err(anyhow::anyhow!("Failed to parse JSON"))
+    Ok(None)
 }
@@ -1,14 +1,66 @@
 mod json_collection;
 mod json_parse_state;
 
+use std::cell::Cell;
+
 use crate::jsonish::{value::Fixes, Value};
 
-use self::json_parse_state::JsonParseState;
+pub(super) use self::json_parse_state::JsonParseState;
 
 use super::ParseOptions;
 use anyhow::Result;
 
-pub fn parse<'a>(str: &'a str, _options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes>)>> {
+/// The lookahead handed to [`JsonParseState::process_token`], which records
+/// whether the parser looked all the way to the end of the input.
+struct Lookahead<'a, I> {
+    chars: I,
+    reached_end: &'a Cell<bool>,
+}
+
+impl<I: Iterator<Item = (usize, char)>> Iterator for Lookahead<'_, I> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.chars.next();
+        if next.is_none() {
+            self.reached_end.set(true);
+        }
+        next
+    }
+}
+
+/// Feeds the tokens in `str[start..end]` to `state`.
+///
+/// Returns the offset of the first token whose processing depended on where
+/// `str` ends, or `end` if there was none: more input can't change the state
+/// reached before that offset.
+fn process(state: &mut JsonParseState, str: &str, start: usize, end: usize) -> Result<usize> {
+    let reached_end = Cell::new(false);
+    let mut stable_until = None;
+
+    let mut chars = str[start..end].char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        let count = start + offset;
+        let lookahead = Lookahead {
+            chars: str[count + c.len_utf8()..].char_indices(),
+            reached_end: &reached_end,
+        };
+        let increments = state.process_token(c, lookahead.peekable())?;
+        for _ in 0..increments {
+            if chars.next().is_none() {
+                reached_end.set(true);
+                break;
+            }
+        }
+        if reached_end.get() && stable_until.is_none() {
+            stable_until = Some(count);
+        }
+    }
+
+    Ok(stable_until.unwrap_or(end))
+}
+
+pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes>)>> {
     // Try to fix some common JSON issues
     // - Unquoted single word strings
     // - Single quoted strings
@@ -27,23 +79,36 @@ pub fn parse<'a>(str: &'a str, _options: &ParseOptions) -> Result<Vec<(Value, Ve
     // - Unterminated objects
     // - Unterminated strings
 
-    let mut state = JsonParseState::new();
+    let Some(checkpoints) = options.checkpoints else {
+        let mut state = JsonParseState::new();
+        process(&mut state, str, 0, str.len())?;
+        return complete(state);
+    };
 
-    let mut chars = str.char_indices().peekable();
-    while let Some((count, c)) = chars.next() {
-        let peekable = str[count + c.len_utf8()..].char_indices().peekable();
-        match state.process_token(c, peekable) {
-            Ok(increments) => {
-                for _ in 0..increments {
-                    chars.next();
-                }
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
-    }
+    let input = checkpoints.input(str);
+    let (start, resumed) = checkpoints
+        .fixing
+        .take(&input, |_| true)
+        .unwrap_or_else(|| (0, JsonParseState::new()));
+
+    let mut state = resumed.clone();
+    let stable_until = process(&mut state, str, start, str.len())?;
+
+    // Save the state as of `stable_until`, replaying the tokens that led up
+    // to it when it's somewhere in the middle of what was just parsed.
+    let stable_state = if stable_until == str.len() {
+        state.clone()
+    } else {
+        let mut stable_state = resumed;
+        process(&mut stable_state, str, start, stable_until)?;
+        stable_state
+    };
+    checkpoints.fixing.save(&input, stable_until, stable_state);
+
+    complete(state)
+}
 
+fn complete(mut state: JsonParseState) -> Result<Vec<(Value, Vec<Fixes>)>> {
     // If we still have a collection open, close it
     while !state.collection_stack.is_empty() {
         state.complete_collection();
@@ -3,7 +3,7 @@ use bstd::dedent;
 
 use crate::jsonish::Value;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum JsonCollection {
     // Key, Value
     Object(Vec<String>, Vec<Value>),
@@ -0,0 +1,126 @@
+use super::ParseOptions;
+
+/// Keeps track of whether a response could be a single JSON value, so that a
+/// streamed response isn't handed to `serde_json` for every chunk while its
+/// top level object is still open.
+///
+/// The scan only ever rules out input `serde_json` would reject: an object,
+/// array or string that isn't closed yet, or one followed by anything but
+/// whitespace. Everything else still goes to `serde_json`, which gives up on
+/// most text within a few characters.
+#[derive(Clone, Default)]
+pub(super) struct JsonScan {
+    first: Option<char>,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    closed: bool,
+    trailing: bool,
+}
+
+impl JsonScan {
+    fn push(&mut self, c: char) {
+        if self.trailing {
+            return;
+        }
+        if self.closed {
+            self.trailing = !c.is_whitespace();
+            return;
+        }
+        let Some(first) = self.first else {
+            if !c.is_whitespace() {
+                self.first = Some(c);
+                self.depth = matches!(c, '{' | '[') as usize;
+                self.in_string = c == '"';
+            }
+            return;
+        };
+
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if c == '\\' {
+                self.escaped = true;
+            } else if c == '"' {
+                self.in_string = false;
+                self.closed = first == '"';
+            }
+            return;
+        }
+        if matches!(first, '{' | '[') {
+            match c {
+                '"' => self.in_string = true,
+                '{' | '[' => self.depth += 1,
+                '}' | ']' => {
+                    self.depth -= 1;
+                    self.closed = self.depth == 0;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn may_be_complete(&self) -> bool {
+        match self.first {
+            None => false,
+            Some('{' | '[' | '"') => self.closed && !self.trailing,
+            Some(_) => true,
+        }
+    }
+}
+
+/// Whether `str` is worth handing to `serde_json`.
+pub(super) fn may_be_json(str: &str, options: &ParseOptions) -> bool {
+    let Some(checkpoints) = options.checkpoints else {
+        return true;
+    };
+
+    let input = checkpoints.input(str);
+    let (start, mut scan) = checkpoints.json.take(&input, |_| true).unwrap_or_default();
+    str[start..].chars().for_each(|c| scan.push(c));
+    let may_be_complete = scan.may_be_complete();
+    checkpoints.json.save(&input, str.len(), scan);
+    may_be_complete
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(str: &str) -> bool {
+        let mut scan = JsonScan::default();
+        str.chars().for_each(|c| scan.push(c));
+        scan.may_be_complete()
+    }
+
+    #[test]
+    fn only_rules_out_what_serde_rejects() {
+        for input in [
+            "",
+            "  ",
+            "{",
+            r#"{"a": "}"#,
+            r#"{"a": "\"}"}"#,
+            r#"{"a": [1, 2]} and more"#,
+            r#"["a", {"b": null}]]"#,
+            r#""unterminated"#,
+            r#""a" "b""#,
+            r#"{"a": 1}"#,
+            "[1, [2, [3]]]\n",
+            r#""a \"quoted\" string""#,
+            "12",
+            "true",
+            "Here is the JSON",
+        ] {
+            let serde_accepts = serde_json::from_str::<serde_json::Value>(input).is_ok();
+            assert!(scan(input) || !serde_accepts, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn waits_for_the_top_level_value_to_close() {
+        assert!(!scan(r#"{"a": {"b": 1}"#));
+        assert!(scan(r#"{"a": "}"}"#));
+        assert!(!scan(r#"{"a": 1} trailing"#));
+    }
+}
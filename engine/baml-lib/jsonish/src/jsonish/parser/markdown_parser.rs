@@ -1,36 +1,62 @@
+use std::sync::OnceLock;
+
 use crate::jsonish::{
-    parser::{entry, ParsingMode},
+    parser::{entry, Mode, ParsingMode},
     Value,
 };
 
 use super::ParseOptions;
 use anyhow::Result;
+use regex::Regex;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum MarkdownResult {
     CodeBlock(String, Value),
     String(String),
 }
 
-pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<MarkdownResult>> {
-    let mut values = vec![];
+/// The blocks found in the part of a response that was consumed, none of
+/// which can change once more of the response comes in.
+pub(super) struct MarkdownBlocks {
+    mode: Mode,
+    values: Vec<MarkdownResult>,
+}
 
-    let mut remaining = str;
-    // Find regex for markdown blocks (```<tag><EOF|newline>)
+// Find regex for markdown blocks (```<tag><EOF|newline>)
+fn md_tag_start() -> &'static Regex {
+    static MD_TAG_START: OnceLock<Regex> = OnceLock::new();
+    MD_TAG_START.get_or_init(|| Regex::new(r"```([a-zA-Z0-9 ]+)(?:\n|$)").unwrap())
+}
 
-    let md_tag_start = regex::Regex::new(r"```([a-zA-Z0-9 ]+)(?:\n|$)")
-        .map_err(|e| anyhow::Error::from(e).context("Failed to build regex for md-tag-start"))?;
-    let md_tag_end = regex::Regex::new(r"```(?:\n|$)")
-        .map_err(|e| anyhow::Error::from(e).context("Failed to build regex for md-tag-end"))?;
+fn md_tag_end() -> &'static Regex {
+    static MD_TAG_END: OnceLock<Regex> = OnceLock::new();
+    MD_TAG_END.get_or_init(|| Regex::new(r"```(?:\n|$)").unwrap())
+}
 
+pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<MarkdownResult>> {
+    let mode = options.mode();
+    let (consumed, mut values) = match options.checkpoints {
+        Some(checkpoints) => checkpoints
+            .markdown
+            .take(&checkpoints.input(str), |blocks| blocks.mode == mode)
+            .map_or((0, vec![]), |(consumed, blocks)| (consumed, blocks.values)),
+        None => (0, vec![]),
+    };
+    // Blocks whose closing fence is followed by a newline stay the same
+    // however the response goes on, the rest may still change.
+    let mut stable = (consumed, values.len());
+
+    let mut remaining = &str[consumed..];
     let mut should_loop = true;
 
-    while let Some(cap) = md_tag_start.find(remaining) {
+    while let Some(cap) = md_tag_start().find(remaining) {
         let tag = cap.as_str();
         log::trace!("Found tag: {:#?}", cap);
 
-        let md_content = if let Some(end) = md_tag_end.find(&remaining[cap.end()..]) {
+        let mut closed = false;
+        let md_content = if let Some(end) = md_tag_end().find(&remaining[cap.end()..]) {
             let next = remaining[cap.end()..cap.end() + end.start()].trim();
+            closed = end.as_str().ends_with('\n');
             remaining = &remaining[cap.end() + end.end()..];
             next
         } else {
@@ -40,10 +66,13 @@ pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<MarkdownRes
 
         log::trace!("Content:\n-----\n{}\n-----\n", md_content);
 
-        let res = entry::parse(
-            md_content,
-            options.next_from_mode(ParsingMode::JsonMarkdown),
-        );
+        let res = match yaml_block(tag, md_content) {
+            Some(v) => Ok(v),
+            None => entry::parse(
+                md_content,
+                options.next_from_mode(ParsingMode::JsonMarkdown),
+            ),
+        };
 
         match res {
             Ok(v) => {
@@ -63,11 +92,27 @@ pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<MarkdownRes
             }
         };
 
+        if closed {
+            stable = (str.len() - remaining.len(), values.len());
+        }
+
         if !should_loop {
             break;
         }
     }
 
+    if let Some(checkpoints) = options.checkpoints {
+        let (consumed, count) = stable;
+        if count > 0 {
+            let values = values[..count].to_vec();
+            checkpoints.markdown.save(
+                &checkpoints.input(&str[..consumed]),
+                consumed,
+                MarkdownBlocks { mode, values },
+            );
+        }
+    }
+
     if values.is_empty() {
         anyhow::bail!("No markdown blocks found")
     } else {
@@ -78,6 +123,28 @@ pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<MarkdownRes
     }
 }
 
+/// Parses the content of a ```yaml block, as asked for by
+/// `ctx.output_format(style="yaml")`.
+///
+/// Only mappings and sequences are taken as YAML. Any text is a valid YAML
+/// scalar, so those are left to the JSON parser.
+fn yaml_block(tag: &str, content: &str) -> Option<Value> {
+    if !matches!(tag[3..].trim(), "yaml" | "yml") {
+        return None;
+    }
+
+    match serde_yaml::from_str(content) {
+        Ok(v @ (Value::Object(_) | Value::Array(_))) => {
+            Some(Value::AnyOf(vec![v], content.to_string()))
+        }
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Invalid YAML in markdown block: {:?}", e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
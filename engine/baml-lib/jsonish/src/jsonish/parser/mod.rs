@@ -1,20 +1,26 @@
+mod checkpoints;
 mod entry;
 mod fixing_parser;
+mod json_scan;
 mod markdown_parser;
 mod multi_json_parser;
+mod xml_parser;
 
+pub use checkpoints::Checkpoints;
 pub use entry::parse;
+use json_scan::JsonScan;
 
 #[derive(Clone, Copy, Debug)]
-pub struct ParseOptions {
+pub struct ParseOptions<'a> {
     all_finding_all_json_objects: bool,
     allow_markdown_json: bool,
     allow_fixes: bool,
     allow_as_string: bool,
     depth: usize,
+    checkpoints: Option<&'a Checkpoints>,
 }
 
-impl Default for ParseOptions {
+impl Default for ParseOptions<'_> {
     fn default() -> Self {
         Self {
             all_finding_all_json_objects: true,
@@ -22,17 +28,46 @@ impl Default for ParseOptions {
             allow_fixes: true,
             allow_as_string: true,
             depth: 0,
+            checkpoints: None,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Mode {
+    all_finding_all_json_objects: bool,
+    allow_markdown_json: bool,
+    allow_fixes: bool,
+    allow_as_string: bool,
+    depth: usize,
+}
+
 pub(super) enum ParsingMode {
     JsonMarkdown,
     JsonMarkdownString,
     AllJsonObjects,
 }
 
-impl ParseOptions {
+impl<'a> ParseOptions<'a> {
+    /// Lets the parsers resume from, and save, states in `checkpoints`.
+    pub fn with_checkpoints(mut self, checkpoints: &'a Checkpoints) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// Everything but the checkpoints. What the markdown and multi-JSON
+    /// parsers make of a block depends on it, so their states are only
+    /// resumed under the same mode.
+    pub(super) fn mode(&self) -> Mode {
+        Mode {
+            all_finding_all_json_objects: self.all_finding_all_json_objects,
+            allow_markdown_json: self.allow_markdown_json,
+            allow_fixes: self.allow_fixes,
+            allow_as_string: self.allow_as_string,
+            depth: self.depth,
+        }
+    }
+
     pub(super) fn next_from_mode(&self, curr_mode: ParsingMode) -> Self {
         let mut new = self.clone();
         match curr_mode {
@@ -1,53 +1,62 @@
 use crate::jsonish::Value;
 
-use super::{entry, ParseOptions};
+use super::{entry, Mode, ParseOptions};
 use anyhow::Result;
 
+/// How far [`parse`] got: the brackets still open and the balanced objects
+/// found so far. The scan never looks ahead, so all of it stays valid as more
+/// of the response comes in.
+#[derive(Clone)]
+pub(super) struct BracketScan {
+    mode: Mode,
+    stack: Vec<char>,
+    json_str_start: Option<usize>,
+    json_objects: Vec<Value>,
+    mismatched: bool,
+}
+
 pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<Value>> {
     // Find all balanced JSON objects but w/o any fixes.
-    let mut stack = Vec::new();
-    let mut json_str_start = None;
-    let mut json_objects = Vec::new();
-
-    for (index, character) in str.char_indices() {
-        match character {
-            '{' | '[' => {
-                if stack.is_empty() {
-                    json_str_start = Some(index);
-                }
-                stack.push(character);
-            }
-            '}' | ']' => {
-                if let Some(last) = stack.last() {
-                    let expected_open = if character == '}' { '{' } else { '[' };
-                    if *last == expected_open {
-                        stack.pop();
-                    } else {
-                        return Err(anyhow::anyhow!("Mismatched brackets"));
-                    }
-                }
-
-                if stack.is_empty() {
-                    let end_index = index + 1;
-                    let json_str = if let Some(start) = json_str_start {
-                        &str[start..end_index]
-                    } else {
-                        &str[..end_index]
-                    };
-                    match entry::parse(
-                        json_str,
-                        options.next_from_mode(super::ParsingMode::AllJsonObjects),
-                    ) {
-                        Ok(json) => json_objects.push(json),
-                        Err(e) => {
-                            // Ignore errors
-                            log::error!("Failed to parse JSON object: {:?}", e);
-                        }
-                    }
-                }
-            }
-            _ => {}
+    let mode = options.mode();
+    let (start, mut scan) = options
+        .checkpoints
+        .and_then(|checkpoints| {
+            checkpoints
+                .multi_json
+                .take(&checkpoints.input(str), |scan| scan.mode == mode)
+        })
+        .unwrap_or_else(|| {
+            (
+                0,
+                BracketScan {
+                    mode,
+                    stack: Vec::new(),
+                    json_str_start: None,
+                    json_objects: Vec::new(),
+                    mismatched: false,
+                },
+            )
+        });
+    if !scan.mismatched {
+        scan.scan(str, start, options);
+    }
+    let BracketScan {
+        stack,
+        json_str_start,
+        mut json_objects,
+        mismatched,
+        ..
+    } = match options.checkpoints {
+        Some(checkpoints) => {
+            checkpoints
+                .multi_json
+                .save(&checkpoints.input(str), str.len(), scan.clone());
+            scan
         }
+        None => scan,
+    };
+    if mismatched {
+        return Err(anyhow::anyhow!("Mismatched brackets"));
     }
 
     if !stack.is_empty() {
@@ -78,6 +87,54 @@ pub fn parse<'a>(str: &'a str, options: &ParseOptions) -> Result<Vec<Value>> {
     }
 }
 
+impl BracketScan {
+    /// Scans `str[start..]`, parsing every object that gets balanced.
+    fn scan(&mut self, str: &str, start: usize, options: &ParseOptions) {
+        for (offset, character) in str[start..].char_indices() {
+            let index = start + offset;
+            match character {
+                '{' | '[' => {
+                    if self.stack.is_empty() {
+                        self.json_str_start = Some(index);
+                    }
+                    self.stack.push(character);
+                }
+                '}' | ']' => {
+                    if let Some(last) = self.stack.last() {
+                        let expected_open = if character == '}' { '{' } else { '[' };
+                        if *last == expected_open {
+                            self.stack.pop();
+                        } else {
+                            self.mismatched = true;
+                            return;
+                        }
+                    }
+
+                    if self.stack.is_empty() {
+                        let end_index = index + 1;
+                        let json_str = if let Some(start) = self.json_str_start {
+                            &str[start..end_index]
+                        } else {
+                            &str[..end_index]
+                        };
+                        match entry::parse(
+                            json_str,
+                            options.next_from_mode(super::ParsingMode::AllJsonObjects),
+                        ) {
+                            Ok(json) => self.json_objects.push(json),
+                            Err(e) => {
+                                // Ignore errors
+                                log::error!("Failed to parse JSON object: {:?}", e);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,525 @@
+use std::{collections::HashMap, rc::Rc};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::jsonish::Value;
+
+use super::ParseOptions;
+
+/// XML found in an LLM response, converted to a [`Value`].
+#[derive(Debug)]
+pub struct XmlDocument {
+    pub value: Value,
+    /// Whether the response is nothing but XML, as opposed to XML embedded in
+    /// prose.
+    pub is_entire_input: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    /// Shared with saved parser states, closed elements don't change.
+    children: Vec<Rc<Element>>,
+    text: String,
+    /// Everything between the opening and the closing tag.
+    raw_inner: String,
+}
+
+/// Parses the XML responses that `ctx.output_format(style="xml")` asks for.
+///
+/// The parser is forgiving the same way the JSON one is: unclosed tags are
+/// closed at the end of the input (so partial responses stream), stray `<`
+/// and `&` are kept as text, and mismatched closing tags are ignored.
+///
+/// Each element becomes the value of its content:
+/// - Text only elements become strings, or null when empty or `null`.
+/// - Elements with children become objects keyed by tag. Repeated tags are
+///   collected into an array, and when every child has the same tag the
+///   children are also offered as a list (`<items><item/><item/></items>`).
+///
+/// A single root element is unwrapped, the root tag carries no data.
+pub fn parse(str: &str, options: &ParseOptions) -> Result<XmlDocument> {
+    let Some(start) = find_start(str) else {
+        anyhow::bail!("No XML elements found");
+    };
+    let leading = str.len() - str.trim_start().len();
+
+    // Where XML in prose starts depends on tags closed later on, only XML
+    // that opens the input is sure to start at the same place as it grows.
+    let checkpoints = options.checkpoints.filter(|_| start == leading);
+    let resumed = checkpoints
+        .and_then(|checkpoints| {
+            checkpoints
+                .xml
+                .take(&checkpoints.input(str), |parser| parser.start == start)
+        })
+        .map_or_else(|| XmlParser::new(start), |(_, parser)| parser);
+
+    let mut parser = resumed.clone();
+    let stable_until = parser.run(str, str.len());
+    if let Some(checkpoints) = checkpoints {
+        // Save the state as of `stable_until`, replaying the steps that led
+        // up to it when it's somewhere in the middle of what was just parsed.
+        let stable = if stable_until == str.len() {
+            parser.clone()
+        } else {
+            let mut stable = resumed;
+            stable.run(str, stable_until);
+            stable
+        };
+        checkpoints
+            .xml
+            .save(&checkpoints.input(str), stable.pos, stable);
+    }
+
+    Ok(parser.finish(str, start == leading))
+}
+
+/// Where the XML starts: right away if the input opens with a tag, otherwise
+/// at the first tag that is closed later on.
+fn find_start(str: &str) -> Option<usize> {
+    let leading = str.len() - str.trim_start().len();
+    if starts_with_tag(&str[leading..]) {
+        return Some(leading);
+    }
+
+    // Where each tag is last closed, so that opening tags can be matched
+    // without searching the rest of the input for each one.
+    let mut last_closed = HashMap::new();
+    for (pos, _) in str.match_indices("</") {
+        if let Some(name) = tag_name(&str[pos + 2..]) {
+            if str[pos + 2 + name.len()..].starts_with('>') {
+                last_closed.insert(name, pos);
+            }
+        }
+    }
+
+    str.match_indices('<').map(|(pos, _)| pos).find(|&pos| {
+        tag_name(&str[pos + 1..])
+            .and_then(|name| last_closed.get(name))
+            .is_some_and(|&closed| closed > pos)
+    })
+}
+
+pub fn starts_with_tag(str: &str) -> bool {
+    str.strip_prefix('<').and_then(tag_name).is_some()
+}
+
+fn tag_name(str: &str) -> Option<&str> {
+    let first = str.chars().next()?;
+    if !(first.is_alphabetic() || first == '_') {
+        return None;
+    }
+    let end = str
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+        .unwrap_or(str.len());
+    Some(&str[..end])
+}
+
+/// An element whose closing tag hasn't been read yet.
+#[derive(Clone, Debug)]
+struct OpenElement {
+    element: Element,
+    /// Where the content of the element starts.
+    inner_start: usize,
+}
+
+/// The state of the parser between two steps, each of which reads a piece of
+/// text, a tag or a comment.
+#[derive(Clone, Debug)]
+pub(super) struct XmlParser {
+    start: usize,
+    pos: usize,
+    /// The elements currently being read, outermost first.
+    open: Vec<OpenElement>,
+    roots: Vec<Rc<Element>>,
+    /// Where the last root element ends.
+    end: usize,
+    /// Set once the XML is followed by something other than a tag.
+    trailing_text: bool,
+}
+
+impl XmlParser {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            pos: start,
+            open: vec![],
+            roots: vec![],
+            end: start,
+            trailing_text: false,
+        }
+    }
+
+    /// Parses `input` up to `end`.
+    ///
+    /// Returns where the first step whose outcome depended on where `input`
+    /// ends started, or `end` if there was none: more input can't change the
+    /// state reached before that.
+    fn run(&mut self, input: &str, end: usize) -> usize {
+        let mut stable_until = None;
+        while self.pos < end && !self.trailing_text {
+            let step_start = self.pos;
+            if !self.step(input) && stable_until.is_none() {
+                stable_until = Some(step_start);
+            }
+        }
+        stable_until.unwrap_or(end)
+    }
+
+    /// Closes the elements left open, the response was cut off.
+    fn finish(mut self, input: &str, at_start: bool) -> XmlDocument {
+        self.pos = input.len();
+        while !self.open.is_empty() {
+            self.close_open(input, input.len());
+        }
+
+        let value = match self.roots.len() {
+            1 => element_value(&self.roots[0]),
+            _ => children_value(&self.roots, input[self.start..self.end].trim().to_string()),
+        };
+        XmlDocument {
+            value,
+            is_entire_input: at_start && !self.trailing_text,
+        }
+    }
+
+    /// Reads the next piece of input and returns whether more input could
+    /// have changed what it read.
+    fn step(&mut self, input: &str) -> bool {
+        let rest = &input[self.pos..];
+        if self.open.is_empty() {
+            // Between root elements.
+            let trimmed = rest.trim_start();
+            if trimmed.is_empty() {
+                self.pos = input.len();
+                return false;
+            }
+            self.pos += rest.len() - trimmed.len();
+            return match trimmed.strip_prefix('<').and_then(tag_name) {
+                Some(name) => self.element(input, name),
+                None => {
+                    self.trailing_text = true;
+                    trimmed.len() > 1
+                }
+            };
+        }
+
+        let Some(next) = rest.find('<') else {
+            // Never closed, the response was cut off.
+            self.text(rest);
+            self.pos = input.len();
+            return false;
+        };
+        self.text(&rest[..next]);
+        self.pos += next;
+
+        let rest = &input[self.pos..];
+        // What the `<` starts is up to the next few characters.
+        let mut stable = rest.len() >= "<![CDATA[".len();
+        if rest.starts_with("<!--") {
+            stable &= self.skip_past(input, "-->");
+        } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            self.text(&cdata[..end]);
+            stable &= self.skip_past(input, "]]>");
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            stable &= self.skip_past(input, ">");
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let Some(closing_name) = tag_name(closing) else {
+                self.text("<");
+                self.pos += 1;
+                return stable;
+            };
+            // The name may go on.
+            stable &= closing_name.len() < closing.len();
+            let end = self.pos;
+            let is_open = |open: &OpenElement| open.element.name == closing_name;
+            if self.open.last().is_some_and(is_open) {
+                stable &= self.skip_past(input, ">");
+                self.close_open(input, end);
+            } else if self.open.iter().any(is_open) {
+                // Closes a parent, so this element was left open. Leave the
+                // tag for the parent to consume.
+                self.close_open(input, end);
+            } else {
+                // Stray closing tag, drop it.
+                stable &= self.skip_past(input, ">");
+            }
+        } else if let Some(name) = rest.strip_prefix('<').and_then(tag_name) {
+            stable &= self.element(input, name);
+        } else {
+            // A literal `<`, as in `a < b`.
+            self.text("<");
+            self.pos += 1;
+        }
+        stable
+    }
+
+    /// Reads the opening tag of `name` at the current position.
+    fn element(&mut self, input: &str, name: &str) -> bool {
+        let rest = &input[self.pos..];
+        let element = Element::empty(name.to_string());
+
+        // Attributes are ignored, the schema never asks for them.
+        let Some(tag_end) = rest.find('>') else {
+            // The opening tag itself is still streaming in.
+            self.pos = input.len();
+            self.close(element);
+            return false;
+        };
+        let self_closing = rest[..tag_end].ends_with('/');
+        self.pos += tag_end + 1;
+        if self_closing {
+            self.close(element);
+        } else {
+            self.open.push(OpenElement {
+                element,
+                inner_start: self.pos,
+            });
+        }
+        true
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(open) = self.open.last_mut() {
+            open.element.text.push_str(text);
+        }
+    }
+
+    /// Moves past the next `end`, and returns whether there was one.
+    fn skip_past(&mut self, input: &str, end: &str) -> bool {
+        match input[self.pos..].find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                true
+            }
+            None => {
+                self.pos = input.len();
+                false
+            }
+        }
+    }
+
+    /// Closes the innermost open element, whose content ends at `end`.
+    fn close_open(&mut self, input: &str, end: usize) {
+        if let Some(OpenElement {
+            mut element,
+            inner_start,
+        }) = self.open.pop()
+        {
+            element.raw_inner = input[inner_start..end].to_string();
+            self.close(element);
+        }
+    }
+
+    fn close(&mut self, element: Element) {
+        match self.open.last_mut() {
+            Some(parent) => parent.element.children.push(Rc::new(element)),
+            None => {
+                self.roots.push(Rc::new(element));
+                self.end = self.pos;
+            }
+        }
+    }
+}
+
+impl Element {
+    fn empty(name: String) -> Self {
+        Self {
+            name,
+            children: vec![],
+            text: String::new(),
+            raw_inner: String::new(),
+        }
+    }
+}
+
+fn element_value(element: &Element) -> Value {
+    if element.children.is_empty() {
+        let text = decode_entities(element.text.trim());
+        return match text.as_str() {
+            // Keep the original text around in case a string is expected.
+            "" | "null" => Value::AnyOf(vec![Value::Null], text),
+            _ => Value::String(text),
+        };
+    }
+
+    children_value(&element.children, element.raw_inner.trim().to_string())
+}
+
+fn children_value(children: &[Rc<Element>], raw: String) -> Value {
+    let same_tag = children.iter().all(|c| c.name == children[0].name);
+
+    let mut fields = IndexMap::<String, Vec<Value>>::new();
+    for child in children {
+        let name = child.name.clone();
+        fields.entry(name).or_default().push(element_value(child));
+    }
+
+    let items = same_tag.then(|| Value::Array(fields.values().flatten().cloned().collect()));
+    let object = Value::Object(
+        fields
+            .into_iter()
+            .map(|(name, mut values)| match values.len() {
+                1 => (name, values.pop().unwrap_or(Value::Null)),
+                _ => (name, Value::Array(values)),
+            })
+            .collect(),
+    );
+
+    Value::AnyOf(std::iter::once(object).chain(items).collect(), raw)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(entity, _)| match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#')?.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+
+        match (replacement, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(str: &str) -> Result<XmlDocument> {
+        super::parse(str, &ParseOptions::default())
+    }
+
+    fn object(value: &Value) -> &Vec<(String, Value)> {
+        match value {
+            Value::AnyOf(items, _) => match &items[0] {
+                Value::Object(fields) => fields,
+                other => panic!("Expected an object, got {other:?}"),
+            },
+            other => panic!("Expected candidates, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unwraps_root_and_collects_lists() {
+        let doc = parse(
+            "<Resume>\n  <name>Ada &amp; co</name>\n  <skills>\n    <item>math</item>\n    <item>code</item>\n  </skills>\n</Resume>",
+        )
+        .unwrap();
+        assert!(doc.is_entire_input);
+
+        let fields = object(&doc.value);
+        assert_eq!(fields[0], ("name".into(), Value::String("Ada & co".into())));
+        let Value::AnyOf(skills, _) = &fields[1].1 else {
+            panic!("Expected candidates for skills");
+        };
+        assert_eq!(
+            skills[1],
+            Value::Array(vec![
+                Value::String("math".into()),
+                Value::String("code".into())
+            ])
+        );
+    }
+
+    #[test]
+    fn single_item_lists_are_offered_as_lists() {
+        let doc = parse("<skills><item>math</item></skills>").unwrap();
+        let Value::AnyOf(candidates, _) = doc.value else {
+            panic!("Expected candidates");
+        };
+        assert_eq!(
+            candidates[1],
+            Value::Array(vec![Value::String("math".into())])
+        );
+    }
+
+    #[test]
+    fn partial_documents_are_closed() {
+        let doc = parse("<Resume><name>Ada</name><bio>Born in").unwrap();
+        assert!(doc.is_entire_input);
+        let fields = object(&doc.value);
+        assert_eq!(fields[1], ("bio".into(), Value::String("Born in".into())));
+    }
+
+    #[test]
+    fn finds_xml_in_prose() {
+        let doc = parse("Sure! <Answer><score>3</score></Answer> Hope that helps.").unwrap();
+        assert!(!doc.is_entire_input);
+        assert_eq!(
+            object(&doc.value)[0],
+            ("score".into(), Value::String("3".into()))
+        );
+
+        assert!(parse("if a < b then <br> nothing").is_err());
+    }
+
+    #[test]
+    fn starts_at_the_first_tag_closed_later() {
+        let doc = parse("</a> <b> then <a><c>1</c></a>").unwrap();
+        assert_eq!(
+            object(&doc.value)[0],
+            ("c".into(), Value::String("1".into()))
+        );
+        assert!(parse("<b>1</b> then <a>").is_ok());
+        assert!(parse("text </a> then <a>").is_err());
+    }
+
+    #[test]
+    fn unclosed_children_end_with_their_parent() {
+        let doc = parse("<a><b>one</a>").unwrap();
+        assert_eq!(object(&doc.value)[0].1, Value::String("one".into()));
+    }
+
+    #[test]
+    fn keeps_text_and_cdata() {
+        let doc = parse("<a><b><![CDATA[x < y]]></b><c/><!-- note --><d>null</d></a>").unwrap();
+        let fields = object(&doc.value);
+        assert_eq!(fields[0].1, Value::String("x < y".into()));
+        assert_eq!(fields[1].1, Value::AnyOf(vec![Value::Null], "".into()));
+        assert_eq!(fields[2].1, Value::AnyOf(vec![Value::Null], "null".into()));
+    }
+
+    #[test]
+    fn resumes_where_it_left_off() {
+        let input = "<Resume><name>Ada &amp; co</name><!-- note --><skills><item>a < b</item>\
+                     <item><![CDATA[x]]></item></skills><bio>Born</Resume> <more/>";
+        let checkpoints = crate::jsonish::Checkpoints::default();
+        let options = ParseOptions::default().with_checkpoints(&checkpoints);
+        for end in (2..=input.len()).filter(|&end| input.is_char_boundary(end)) {
+            let str = &input[..end];
+            let expected = parse(str).unwrap();
+            let resumed = super::parse(str, &options).unwrap();
+            assert_eq!(expected.value, resumed.value, "{str:?}");
+            assert_eq!(expected.is_entire_input, resumed.is_entire_input, "{str:?}");
+        }
+    }
+}
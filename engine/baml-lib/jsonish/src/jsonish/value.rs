@@ -1,11 +1,9 @@
-use std::{
-    collections::HashSet,
-    hash::{Hash, Hasher},
-};
+use std::hash::{Hash, Hasher};
 
 use baml_types::BamlMap;
+use indexmap::IndexSet;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Fixes {
     GreppedForJSON,
     InferredArray,
@@ -83,7 +81,7 @@ impl Value {
                 let items = i
                     .iter()
                     .map(|v| v.r#type())
-                    .collect::<HashSet<String>>()
+                    .collect::<IndexSet<String>>()
                     .into_iter()
                     .collect::<Vec<String>>()
                     .join(" | ");
@@ -3,12 +3,14 @@ mod tests;
 
 use anyhow::Result;
 pub mod deserializer;
+mod incremental;
 mod jsonish;
 
 use baml_types::FieldType;
 use deserializer::coercer::{ParsingContext, TypeCoercer};
 
 pub use deserializer::types::BamlValueWithFlags;
+pub use incremental::IncrementalParser;
 use internal_baml_core::ir::TypeValue;
 use internal_baml_jinja::types::OutputFormatContent;
 
@@ -26,18 +28,31 @@ pub fn from_str(
     }
 
     // When the schema is just a string, i should really just return the raw_string w/o parsing it.
-    let mut value = jsonish::parse(raw_string, jsonish::ParseOptions::default())?;
+    let value = jsonish::parse(raw_string, jsonish::ParseOptions::default())?;
     // let schema = deserializer::schema::from_jsonish_value(&value, None);
 
+    coerce(
+        ParsingContext::new(of, allow_partials),
+        target,
+        raw_string,
+        value,
+    )
+}
+
+fn coerce(
+    ctx: ParsingContext,
+    target: &FieldType,
+    raw_string: &str,
+    mut value: Value,
+) -> Result<BamlValueWithFlags> {
     // See Note [Streaming Number Invalidation]
-    if allow_partials {
+    if ctx.allow_partials {
         invalidate_numbers_in_progress(&mut value, raw_string);
     }
 
     // Pick the schema that is the most specific.
     // log::info!("Parsed: {}", schema);
     log::debug!("Parsed JSONish (step 1 of parsing): {:#?}", value);
-    let ctx = ParsingContext::new(of, allow_partials);
     // let res = schema.cast_to(target);
     // log::info!("Casted: {:?}", res);
 
@@ -49,6 +64,7 @@ pub fn from_str(
     // Determine the best way to get the desired schema from the parsed schema.
 
     // Lets try to now coerce the value into the expected schema.
+    ctx.start_coercing(&value);
     match target.coerce(&ctx, target, Some(&value)) {
         Ok(v) => {
             if v.conditions()
@@ -59,7 +75,13 @@ pub fn from_str(
                 anyhow::bail!("Failed to coerce value: {:?}", v.conditions().flags());
             }
 
-            Ok(v)
+            if !ctx.allow_partials {
+                return Ok(v);
+            }
+            // A partial result that the `@stream.*` attributes hold back
+            // entirely is null until there's more of it.
+            Ok(deserializer::semantic_streaming::apply(ctx.of, v)
+                .unwrap_or_else(|| BamlValueWithFlags::Null(Default::default())))
         }
         Err(e) => anyhow::bail!("Failed to coerce value: {}", e),
     }
@@ -69,13 +91,14 @@ pub fn from_str(
 ///
 /// See note [Streaming Number Invalidation]
 fn invalidate_numbers_in_progress(value: &mut Value, raw_string: &str) {
+    // Only the last character matters, don't walk the value unless it's a
+    // digit.
     let ends_in_digit = raw_string
         .chars()
-        .last()
+        .next_back()
         .map_or(false, |c| c.is_numeric() || c == '.');
-    let last_values = last_value_as_number(value);
     if ends_in_digit {
-        last_values.into_iter().for_each(|v| {
+        last_value_as_number(value).into_iter().for_each(|v| {
             *v = Value::Null;
         })
     }
@@ -0,0 +1,39 @@
+//! Schemas shared by the tests and the benches.
+
+/// Uses every streaming attribute.
+pub const RECIPE: &str = r#"
+class Ingredient {
+  name string
+  quantity string
+  @@stream.done
+}
+
+class Recipe {
+  title string @stream.not_null
+  summary string @stream.done
+  ingredients Ingredient[]
+  steps string[] @stream.with_state
+  servings int? @stream.with_state
+}
+"#;
+
+/// Responses in every output style are parsed into it.
+pub const RESUME: &str = r#"
+enum Level {
+    JUNIOR
+    SENIOR
+}
+
+class Job {
+    company string
+    years int
+}
+
+class Resume {
+    name string
+    bio string
+    skills string[]
+    jobs Job[]
+    level Level?
+}
+"#;
@@ -51,6 +51,8 @@ macro_rules! test_deserializer {
 
             assert!(result.is_ok(), "Failed to parse: {:?}", result);
 
+            assert_parses_incrementally(&target, &$target_type, $raw_string);
+
             let value = result.unwrap();
             log::trace!("Score: {}", value.score());
             let value: BamlValue = value.into();
@@ -99,6 +101,8 @@ macro_rules! test_partial_deserializer {
 
             assert!(result.is_ok(), "Failed to parse: {:?}", result);
 
+            assert_parses_incrementally(&target, &$target_type, $raw_string);
+
             let value = result.unwrap();
             log::trace!("Score: {}", value.score());
             let value: BamlValue = value.into();
@@ -1,22 +1,28 @@
 use anyhow::Result;
-use internal_baml_jinja::types::{Class, Enum, Name, OutputFormatContent};
+use internal_baml_jinja::types::{Class, ClassStreaming, Enum, Name, OutputFormatContent};
 
 #[macro_use]
 pub mod macros;
 
+mod fixtures;
+mod schema;
+mod test_aliases;
 mod test_basics;
 mod test_class;
 mod test_class_2;
 mod test_code;
 mod test_constraints;
+mod test_defaults;
 mod test_enum;
 mod test_lists;
 mod test_literals;
 mod test_maps;
+mod test_output_styles;
 mod test_partials;
+mod test_streaming;
 mod test_unions;
 
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
@@ -30,216 +36,43 @@ use internal_baml_core::{
 };
 use serde_json::json;
 
-use crate::from_str;
-
-fn load_test_ir(file_content: &str) -> IntermediateRepr {
-    let mut schema = validate(
-        &PathBuf::from("./baml_src"),
-        vec![SourceFile::from((
-            PathBuf::from("./baml_src/example.baml"),
-            file_content.to_string(),
-        ))],
-    );
-    match schema.diagnostics.to_result() {
-        Ok(_) => {}
-        Err(e) => {
-            panic!("Failed to validate schema: {}", e);
-        }
-    }
-
-    IntermediateRepr::from_parser_database(&schema.db, schema.configuration).unwrap()
-}
-
-fn render_output_format(
-    ir: &IntermediateRepr,
-    output: &FieldType,
-    env_values: &HashMap<String, String>,
-) -> Result<OutputFormatContent> {
-    let (enums, classes, recursive_classes) = relevant_data_models(ir, output, env_values)?;
-
-    Ok(OutputFormatContent::target(output.clone())
-        .enums(enums)
-        .classes(classes)
-        .recursive_classes(recursive_classes)
-        .build())
-}
-
-fn find_existing_class_field<'a>(
-    class_name: &str,
-    field_name: &str,
-    class_walker: &Result<ClassWalker<'a>>,
-    env_values: &HashMap<String, String>,
-) -> Result<(Name, FieldType, Option<String>)> {
-    let Ok(class_walker) = class_walker else {
-        anyhow::bail!("Class {} does not exist", class_name);
-    };
-
-    let Some(field_walker) = class_walker.find_field(field_name) else {
-        anyhow::bail!("Class {} does not have a field: {}", class_name, field_name);
-    };
-
-    let name = Name::new_with_alias(field_name.to_string(), field_walker.alias(env_values)?);
-    let desc = field_walker.description(env_values)?;
-    let r#type = field_walker.r#type();
-    Ok((name, r#type.clone(), desc))
-}
-
-fn find_enum_value(
-    enum_name: &str,
-    value_name: &str,
-    enum_walker: &Result<EnumWalker<'_>>,
-    env_values: &HashMap<String, String>,
-) -> Result<Option<(Name, Option<String>)>> {
-    if enum_walker.is_err() {
-        anyhow::bail!("Enum {} does not exist", enum_name);
-    }
-
-    let value_walker = match enum_walker {
-        Ok(e) => e.find_value(value_name),
-        Err(_) => None,
-    };
-
-    let value_walker = match value_walker {
-        Some(v) => v,
-        None => return Ok(None),
-    };
-
-    if value_walker.skip(env_values)? {
-        return Ok(None);
-    }
-
-    let name = Name::new_with_alias(value_name.to_string(), value_walker.alias(env_values)?);
-    let desc = value_walker.description(env_values)?;
-
-    Ok(Some((name, desc)))
-}
-
-// TODO: This function is "almost" a duplicate of `relevant_data_models` at
-// baml-runtime/src/internal/prompt_renderer/render_output_format.rs
-//
-// Should be refactored.
-//
-// TODO: (Greg) Is the use of `String` as a hash key safe? Is there some way to
-// get a collision that results in some type not getting put onto the stack?
-fn relevant_data_models<'a>(
-    ir: &'a IntermediateRepr,
-    output: &'a FieldType,
-    env_values: &HashMap<String, String>,
-) -> Result<(Vec<Enum>, Vec<Class>, IndexSet<String>)> {
-    let mut checked_types: HashSet<String> = HashSet::new();
-    let mut enums = Vec::new();
-    let mut classes: Vec<Class> = Vec::new();
-    let mut recursive_classes = IndexSet::new();
-    let mut start: Vec<baml_types::FieldType> = vec![output.clone()];
-
-    while !start.is_empty() {
-        let output = start.pop().unwrap();
-        match ir.distribute_constraints(&output) {
-            (FieldType::Enum(enm), constraints) => {
-                if checked_types.insert(output.to_string()) {
-                    let walker = ir.find_enum(&enm);
-
-                    let real_values = walker
-                        .as_ref()
-                        .map(|e| e.walk_values().map(|v| v.name().to_string()))
-                        .ok();
-                    let values = real_values
-                        .into_iter()
-                        .flatten()
-                        .into_iter()
-                        .map(|value| {
-                            let meta = find_enum_value(enm.as_str(), &value, &walker, env_values)?;
-                            Ok(meta.map(|m| m))
-                        })
-                        .filter_map(|v| v.transpose())
-                        .collect::<Result<Vec<_>>>()?;
-
-                    enums.push(Enum {
-                        name: Name::new_with_alias(enm.to_string(), walker?.alias(env_values)?),
-                        values,
-                        constraints,
-                    });
-                }
-            }
-            (FieldType::List(inner), _constraints) | (FieldType::Optional(inner), _constraints) => {
-                if !checked_types.contains(&inner.to_string()) {
-                    start.push(inner.as_ref().clone());
-                }
-            }
-            (FieldType::Map(k, v), _constraints) => {
-                if checked_types.insert(output.to_string()) {
-                    if !checked_types.contains(&k.to_string()) {
-                        start.push(k.as_ref().clone());
-                    }
-                    if !checked_types.contains(&v.to_string()) {
-                        start.push(v.as_ref().clone());
-                    }
-                }
-            }
-            (FieldType::Tuple(options), _constraints)
-            | (FieldType::Union(options), _constraints) => {
-                if checked_types.insert((&output).to_string()) {
-                    for inner in options {
-                        if !checked_types.contains(&inner.to_string()) {
-                            start.push(inner.clone());
-                        }
-                    }
-                }
-            }
-            (FieldType::Class(cls), constraints) => {
-                if checked_types.insert(output.to_string()) {
-                    let walker = ir.find_class(&cls);
-
-                    let real_fields = walker
-                        .as_ref()
-                        .map(|e| e.walk_fields().map(|v| v.name().to_string()))
-                        .ok();
-
-                    let fields = real_fields.into_iter().flatten().into_iter().map(|field| {
-                        let meta = find_existing_class_field(&cls, &field, &walker, env_values)?;
-                        Ok(meta)
-                    });
-
-                    let fields = fields.collect::<Result<Vec<_>>>()?;
-
-                    for (_, t, _) in fields.iter().as_ref() {
-                        if !checked_types.contains(&t.to_string()) {
-                            start.push(t.clone());
-                        }
-                    }
-
-                    // TODO: O(n) algorithm. Maybe a Merge-Find Set can optimize
-                    // this to O(log n) or something like that
-                    // (maybe, IDK though ¯\_(ツ)_/¯)
-                    //
-                    // Also there's a lot of cloning in this process of going
-                    // from Parser DB to IR to Jinja Output Format, not only
-                    // with recursive classes but also the rest of models.
-                    // There's room for optimization here.
-                    //
-                    // Also take a look at the TODO on top of this function.
-                    for cycle in ir.finite_recursive_cycles() {
-                        if cycle.contains(cls) {
-                            recursive_classes.extend(cycle.iter().map(ToOwned::to_owned));
-                        }
-                    }
-
-                    classes.push(Class {
-                        name: Name::new_with_alias(cls.to_string(), walker?.alias(env_values)?),
-                        fields,
-                        constraints,
-                    });
-                }
-            }
-            (FieldType::Literal(_), _) => {}
-            (FieldType::Primitive(_), _constraints) => {}
-            (FieldType::Constrained { .. }, _) => {
-                unreachable!("It is guaranteed that a call to distribute_constraints will not return FieldType::Constrained")
-            }
-        }
+use crate::{from_str, IncrementalParser};
+use fixtures::{RECIPE, RESUME};
+use schema::{load_test_ir, render_output_format};
+
+/// Streams `raw_string` through an [`IncrementalParser`] a few characters at
+/// a time, checking that every chunk parses exactly like `from_str` parses
+/// everything received so far. `cargo bench -p jsonish` compares how long
+/// the two take.
+fn assert_parses_incrementally(of: &OutputFormatContent, target: &FieldType, raw_string: &str) {
+    let boundaries = raw_string
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .skip(1)
+        .chain(std::iter::once(raw_string.len()))
+        .collect::<Vec<_>>();
+
+    // Checking every prefix of the longer fixtures takes too long.
+    let stride = (boundaries.len() / 16).max(1);
+    let mut parser = IncrementalParser::new(of, target);
+    let mut start = 0;
+    for end in boundaries
+        .iter()
+        .copied()
+        .skip(stride - 1)
+        .step_by(stride)
+        .chain((boundaries.len() % stride != 0).then_some(raw_string.len()))
+    {
+        let expected = from_str(of, target, &raw_string[..end], true).map_err(|e| e.to_string());
+        let actual = parser.push(&raw_string[start..end]).map_err(|e| e.to_string());
+        assert_eq!(
+            format!("{actual:?}"),
+            format!("{expected:?}"),
+            "Incremental parse differs after {:?}",
+            &raw_string[..end]
+        );
+        start = end;
     }
-
-    Ok((enums, classes, recursive_classes))
 }
 
 const EMPTY_FILE: &str = r#"
@@ -0,0 +1,186 @@
+use super::*;
+
+test_deserializer!(
+    test_simple_recursive_alias_list,
+    r#"
+type A = A[]
+    "#,
+    "[[], [], [[]]]",
+    FieldType::RecursiveTypeAlias("A".into()),
+    [[], [], [[]]]
+);
+
+test_deserializer!(
+    test_simple_recursive_alias_map,
+    r#"
+type A = map<string, A>
+    "#,
+    r#"{"one": {"two": {}}, "three": {"four": {}}}"#,
+    FieldType::RecursiveTypeAlias("A".into()),
+    {
+        "one": {"two": {}},
+        "three": {"four": {}},
+    }
+);
+
+test_deserializer!(
+    test_recursive_alias_cycle,
+    r#"
+type A = B
+type B = C
+type C = A[]
+    "#,
+    "[[], [], [[]]]",
+    FieldType::RecursiveTypeAlias("A".into()),
+    [[], [], [[]]]
+);
+
+test_deserializer!(
+    test_json_without_nested_objects,
+    r#"
+type JsonValue = int | float | bool | string | null | JsonValue[] | map<string, JsonValue>
+    "#,
+    r#"
+    {
+        "int": 1,
+        "float": 1.0,
+        "string": "test",
+        "bool": true
+    }
+    "#,
+    FieldType::RecursiveTypeAlias("JsonValue".into()),
+    {
+        "int": 1,
+        "float": 1.0,
+        "string": "test",
+        "bool": true
+    }
+);
+
+test_deserializer!(
+    test_json_with_nested_list,
+    r#"
+type JsonValue = int | float | bool | string | null | JsonValue[] | map<string, JsonValue>
+    "#,
+    r#"
+    {
+        "number": 1,
+        "string": "test",
+        "bool": true,
+        "list": [1, 2, 3]
+    }
+    "#,
+    FieldType::RecursiveTypeAlias("JsonValue".into()),
+    {
+        "number": 1,
+        "string": "test",
+        "bool": true,
+        "list": [1, 2, 3]
+    }
+);
+
+test_deserializer!(
+    test_json_with_nested_object,
+    r#"
+type JsonValue = int | float | bool | string | null | JsonValue[] | map<string, JsonValue>
+    "#,
+    r#"
+    {
+        "number": 1,
+        "string": "test",
+        "bool": true,
+        "json": {
+            "number": 1,
+            "string": "test",
+            "bool": true
+        }
+    }
+    "#,
+    FieldType::RecursiveTypeAlias("JsonValue".into()),
+    {
+        "number": 1,
+        "string": "test",
+        "bool": true,
+        "json": {
+            "number": 1,
+            "string": "test",
+            "bool": true
+        }
+    }
+);
+
+test_deserializer!(
+    test_json_alias_as_class_field,
+    r#"
+type JsonValue = int | float | bool | string | null | JsonValue[] | map<string, JsonValue>
+
+class Payload {
+    kind "event"
+    data JsonValue
+}
+    "#,
+    r#"
+    {
+        "kind": "event",
+        "data": {"ids": [1, 2], "meta": {"ok": true}}
+    }
+    "#,
+    FieldType::class("Payload"),
+    {
+        "kind": "event",
+        "data": {"ids": [1, 2], "meta": {"ok": true}}
+    }
+);
+
+test_deserializer!(
+    test_non_recursive_alias_is_expanded,
+    r#"
+type Status = "active" | "inactive"
+
+class User {
+    status Status
+}
+    "#,
+    r#"{"status": "inactive"}"#,
+    FieldType::class("User"),
+    {"status": "inactive"}
+);
+
+test_failing_deserializer!(
+    test_non_recursive_alias_rejects_other_literals,
+    r#"
+type Status = "active" | "inactive"
+
+class User {
+    status Status
+}
+    "#,
+    r#"{"status": "deleted"}"#,
+    FieldType::class("User")
+);
+
+#[test]
+fn test_recursive_alias_output_format() {
+    let ir = load_test_ir(
+        r#"
+type JsonValue = int | string | JsonValue[] | map<string, JsonValue>
+        "#,
+    );
+    let target = FieldType::RecursiveTypeAlias("JsonValue".into());
+    let output_format = render_output_format(&ir, &target, &Default::default()).unwrap();
+
+    assert_eq!(
+        output_format
+            .structural_recursive_aliases
+            .get("JsonValue")
+            .map(ToString::to_string),
+        Some("(int | string | JsonValue[] | map<string, JsonValue>)".to_string())
+    );
+
+    let result = from_str(&output_format, &target, r#"["a", 1]"#, false).unwrap();
+    let value: BamlValue = result.into();
+    assert_eq!(
+        value,
+        BamlValue::List(vec![BamlValue::String("a".into()), BamlValue::Int(1)])
+    );
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+const SETTINGS: &str = r#"
+enum Level {
+  Low
+  High
+}
+
+class Settings {
+  name string
+  retries int @default(3)
+  level Level @default(High)
+  tags string[] @default(["general"])
+  note string? @default("none")
+}
+"#;
+
+test_deserializer!(
+    test_default_fills_missing_fields,
+    SETTINGS,
+    r#"{"name": "prod"}"#,
+    FieldType::Class("Settings".to_string()),
+    {
+        "name": "prod",
+        "retries": 3,
+        "level": "High",
+        "tags": ["general"],
+        "note": "none"
+    }
+);
+
+test_deserializer!(
+    test_default_not_used_when_present,
+    SETTINGS,
+    r#"{"name": "prod", "retries": 5, "level": "Low", "tags": [], "note": null}"#,
+    FieldType::Class("Settings".to_string()),
+    {
+        "name": "prod",
+        "retries": 5,
+        "level": "Low",
+        "tags": [],
+        "note": null
+    }
+);
+
+test_deserializer!(
+    test_default_in_list_of_classes,
+    SETTINGS,
+    r#"[{"name": "a"}, {"name": "b", "retries": 1}]"#,
+    FieldType::List(Box::new(FieldType::Class("Settings".to_string()))),
+    [
+        {"name": "a", "retries": 3, "level": "High", "tags": ["general"], "note": "none"},
+        {"name": "b", "retries": 1, "level": "High", "tags": ["general"], "note": "none"}
+    ]
+);
+
+test_partial_deserializer!(
+    test_default_not_used_while_streaming,
+    SETTINGS,
+    r#"{"name": "pro"#,
+    FieldType::Class("Settings".to_string()),
+    {
+        "name": "pro",
+        "retries": null,
+        "level": null,
+        "tags": [],
+        "note": null
+    }
+);
@@ -0,0 +1,136 @@
+use super::*;
+
+test_deserializer!(
+    test_xml_class,
+    RESUME,
+    r#"
+<Resume>
+  <name>Ada Lovelace</name>
+  <bio>Wrote <b>the first</b> program &amp; more</bio>
+  <skills>
+    <item>math</item>
+    <item>poetry</item>
+  </skills>
+  <jobs>
+    <item>
+      <company>Analytical Engine Co.</company>
+      <years>10</years>
+    </item>
+  </jobs>
+  <level>SENIOR</level>
+</Resume>
+"#,
+    FieldType::class("Resume"),
+    {
+        "name": "Ada Lovelace",
+        "bio": "Wrote <b>the first</b> program &amp; more",
+        "skills": ["math", "poetry"],
+        "jobs": [{"company": "Analytical Engine Co.", "years": 10}],
+        "level": "SENIOR"
+    }
+);
+
+test_deserializer!(
+    test_xml_in_markdown_and_prose,
+    RESUME,
+    r#"Here is the resume:
+
+```xml
+<Resume>
+  <name>Ada</name>
+  <bio></bio>
+  <skills><item>math</item></skills>
+  <jobs></jobs>
+  <level>null</level>
+</Resume>
+```
+"#,
+    FieldType::class("Resume"),
+    {
+        "name": "Ada",
+        "bio": "",
+        "skills": ["math"],
+        "jobs": [],
+        "level": null
+    }
+);
+
+test_deserializer!(
+    test_xml_embedded_in_prose,
+    "",
+    r#"Sure, the answer is <answer><item>1</item><item>2</item></answer>, hope that helps."#,
+    FieldType::List(FieldType::int().into()),
+    [1, 2]
+);
+
+test_deserializer!(
+    test_json_wrapped_in_a_tag,
+    RESUME,
+    r#"<answer>{"name": "Ada", "bio": "", "skills": ["math"], "jobs": [{"company": "Analytical Engine Co.", "years": 10}], "level": "SENIOR"}</answer>"#,
+    FieldType::class("Resume"),
+    {
+        "name": "Ada",
+        "bio": "",
+        "skills": ["math"],
+        "jobs": [{"company": "Analytical Engine Co.", "years": 10}],
+        "level": "SENIOR"
+    }
+);
+
+test_partial_deserializer!(
+    test_xml_partial,
+    RESUME,
+    r#"<Resume>
+  <name>Ada</name>
+  <skills>
+    <item>math</item>
+    <item>poe"#,
+    FieldType::class("Resume"),
+    {
+        "name": "Ada",
+        "bio": null,
+        "skills": ["math", "poe"],
+        "jobs": [],
+        "level": null
+    }
+);
+
+test_deserializer!(
+    test_yaml_class,
+    RESUME,
+    r#"
+```yaml
+name: Ada Lovelace
+bio: |
+  Wrote the first program
+skills:
+  - math
+  - poetry
+jobs:
+  - company: Analytical Engine Co.
+    years: 10
+level: SENIOR
+```
+"#,
+    FieldType::class("Resume"),
+    {
+        "name": "Ada Lovelace",
+        "bio": "Wrote the first program\n",
+        "skills": ["math", "poetry"],
+        "jobs": [{"company": "Analytical Engine Co.", "years": 10}],
+        "level": "SENIOR"
+    }
+);
+
+test_deserializer!(
+    test_yaml_list,
+    "",
+    r#"
+```yml
+- 1
+- 2
+```
+"#,
+    FieldType::List(FieldType::int().into()),
+    [1, 2]
+);
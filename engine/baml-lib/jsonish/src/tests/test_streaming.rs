@@ -0,0 +1,89 @@
+use super::*;
+
+test_partial_deserializer!(
+    test_streaming_in_progress_string,
+    RECIPE,
+    r#"{"title": "Pan"#,
+    FieldType::Class("Recipe".to_string()),
+    {
+        "title": "Pan",
+        "summary": null,
+        "ingredients": [],
+        "steps": {"value": [], "state": "Incomplete"},
+        "servings": {"value": null, "state": "Incomplete"}
+    }
+);
+
+test_partial_deserializer!(
+    test_streaming_not_null_holds_back_class,
+    RECIPE,
+    r#"{"summary": "Fluffy"#,
+    FieldType::Class("Recipe".to_string()),
+    null
+);
+
+test_partial_deserializer!(
+    test_streaming_done_field,
+    RECIPE,
+    r#"{"title": "Pancakes", "summary": "Fluffy pan"#,
+    FieldType::Class("Recipe".to_string()),
+    {
+        "title": "Pancakes",
+        "summary": null,
+        "ingredients": [],
+        "steps": {"value": [], "state": "Incomplete"},
+        "servings": {"value": null, "state": "Incomplete"}
+    }
+);
+
+test_partial_deserializer!(
+    test_streaming_done_class_in_list,
+    RECIPE,
+    r#"{"title": "Pancakes", "summary": "Fluffy", "ingredients": [
+      {"name": "flour", "quantity": "1 cup"},
+      {"name": "eggs", "quantity": "2"#,
+    FieldType::Class("Recipe".to_string()),
+    {
+        "title": "Pancakes",
+        "summary": "Fluffy",
+        "ingredients": [{"name": "flour", "quantity": "1 cup"}],
+        "steps": {"value": [], "state": "Incomplete"},
+        "servings": {"value": null, "state": "Incomplete"}
+    }
+);
+
+test_partial_deserializer!(
+    test_streaming_with_state,
+    RECIPE,
+    r#"{"title": "Pancakes", "servings": 4, "steps": ["Mix", "Fry"#,
+    FieldType::Class("Recipe".to_string()),
+    {
+        "title": "Pancakes",
+        "summary": null,
+        "ingredients": [],
+        "steps": {"value": ["Mix", "Fry"], "state": "Incomplete"},
+        "servings": {"value": 4, "state": "Complete"}
+    }
+);
+
+test_partial_deserializer!(
+    test_streaming_list_of_done_classes,
+    RECIPE,
+    r#"[{"name": "flour", "quantity": "1 cup"}, {"name": "eggs"#,
+    FieldType::List(FieldType::Class("Ingredient".to_string()).into()),
+    [{"name": "flour", "quantity": "1 cup"}]
+);
+
+test_deserializer!(
+    test_streaming_attributes_ignored_when_complete,
+    RECIPE,
+    r#"{"title": "Pancakes", "summary": "Fluffy", "ingredients": [], "steps": ["Mix"], "servings": 4}"#,
+    FieldType::Class("Recipe".to_string()),
+    {
+        "title": "Pancakes",
+        "summary": "Fluffy",
+        "ingredients": [],
+        "steps": ["Mix"],
+        "servings": 4
+    }
+);
@@ -16,7 +16,20 @@ pub(super) fn visit_constraint_attributes(
         .map(|(_arg_id, arg)| arg)
         .collect();
 
-    let level = match attribute_name.as_str() {
+    if let Some(constraint) = parse_constraint(&attribute_name, &arguments, span, ctx) {
+        attributes.constraints.push(constraint);
+    }
+}
+
+/// Build the constraint for an `@assert`/`@check` (or `@@assert`/`@@check`)
+/// attribute from its arguments, reporting malformed ones.
+pub(crate) fn parse_constraint(
+    attribute_name: &str,
+    arguments: &[&Expression],
+    span: Span,
+    ctx: &mut Context<'_>,
+) -> Option<Constraint> {
+    let level = match attribute_name {
         "assert" => ConstraintLevel::Assert,
         "check" => ConstraintLevel::Check,
         other_name => {
@@ -25,16 +38,16 @@ pub(super) fn visit_constraint_attributes(
                 other_name,
                 span
             ));
-            return ();
+            return None;
         }
     };
 
-    let (label, expression) = match arguments.as_slice() {
+    let (label, expression) = match arguments {
         [Expression::JinjaExpressionValue(expression, _)] => {
             if level == ConstraintLevel::Check {
                 ctx.push_error(DatamodelError::new_attribute_validation_error(
                     "Checks must specify a label.",
-                    attribute_name.as_str(),
+                    attribute_name,
                     span,
                 ));
             }
@@ -47,17 +60,17 @@ pub(super) fn visit_constraint_attributes(
             ctx.push_error(
                 DatamodelError::new_attribute_validation_error(
                     "Checks and asserts may have either a label and an expression, or a lone expression.",
-                    attribute_name.as_str(),
+                    attribute_name,
                     span
                 )
             );
-            return ();
+            return None;
         }
     };
 
-    attributes.constraints.push(Constraint {
+    Some(Constraint {
         level,
         expression,
         label,
-    });
+    })
 }
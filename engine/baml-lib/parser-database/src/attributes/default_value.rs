@@ -0,0 +1,22 @@
+use internal_baml_schema_ast::ast::Expression;
+
+use crate::context::Context;
+
+/// Visits `@default(...)` on a class field. Whether the value fits the type
+/// of the field is checked by the validation pipeline.
+pub(super) fn visit(ctx: &mut Context<'_>) -> Option<Expression> {
+    if !ctx.visit_optional_single_attr("default") {
+        return None;
+    }
+
+    let value = match ctx.visit_default_arg_with_idx("value") {
+        Ok((_, value)) => Some(value.clone()),
+        Err(err) => {
+            ctx.push_error(err);
+            None
+        }
+    };
+    ctx.validate_visited_arguments();
+
+    value
+}
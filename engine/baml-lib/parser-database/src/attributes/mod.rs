@@ -2,11 +2,14 @@ use internal_baml_schema_ast::ast::{Top, TopId, TypeExpId, TypeExpressionBlock};
 
 mod alias;
 mod constraint;
+mod default_value;
 mod description;
+mod stream;
 mod to_string_attribute;
+pub(crate) use constraint::parse_constraint;
 use crate::interner::StringId;
 use crate::{context::Context, types::ClassAttributes, types::EnumAttributes};
-use baml_types::Constraint;
+use baml_types::{Constraint, StreamingBehavior};
 use internal_baml_schema_ast::ast::{Expression, SubType};
 
 ///
@@ -26,6 +29,12 @@ pub struct Attributes {
 
     /// @check and @assert attributes attached to the node.
     pub constraints: Vec<Constraint>,
+
+    /// @stream.* attributes attached to the node.
+    pub streaming: StreamingBehavior,
+
+    /// Value used for the field when the LLM leaves it out.
+    pub default_value: Option<Expression>,
 }
 
 impl Attributes {
@@ -114,7 +123,9 @@ fn resolve_type_exp_block_attributes<'db>(
 
             for (field_idx, field) in ast_typexpr.iter_fields() {
                 ctx.assert_all_attributes_processed((type_id, field_idx).into());
-                if let Some(attrs) = to_string_attribute::visit(ctx, &field.span, false) {
+                let attrs = to_string_attribute::visit(ctx, &field.span, false);
+                let attrs = with_streaming(attrs, stream::visit(ctx, false));
+                if let Some(attrs) = with_default_value(attrs, default_value::visit(ctx)) {
                     class_attributes.field_serilizers.insert(field_idx, attrs);
                 }
                 ctx.validate_visited_attributes();
@@ -122,7 +133,8 @@ fn resolve_type_exp_block_attributes<'db>(
 
             // Now validate the class attributes.
             ctx.assert_all_attributes_processed(type_id.into());
-            class_attributes.serilizer = to_string_attribute::visit(ctx, &span, true);
+            let attrs = to_string_attribute::visit(ctx, &span, true);
+            class_attributes.serilizer = with_streaming(attrs, stream::visit(ctx, true));
             ctx.validate_visited_attributes();
 
             ctx.types.class_attributes.insert(type_id, class_attributes);
@@ -131,3 +143,27 @@ fn resolve_type_exp_block_attributes<'db>(
         _ => (),
     }
 }
+
+fn with_streaming(
+    attributes: Option<Attributes>,
+    streaming: StreamingBehavior,
+) -> Option<Attributes> {
+    if streaming.is_default() {
+        return attributes;
+    }
+    let mut attributes = attributes.unwrap_or_default();
+    attributes.streaming = streaming;
+    Some(attributes)
+}
+
+fn with_default_value(
+    attributes: Option<Attributes>,
+    default_value: Option<Expression>,
+) -> Option<Attributes> {
+    let Some(default_value) = default_value else {
+        return attributes;
+    };
+    let mut attributes = attributes.unwrap_or_default();
+    attributes.default_value = Some(default_value);
+    Some(attributes)
+}
@@ -0,0 +1,28 @@
+use baml_types::StreamingBehavior;
+
+use crate::context::Context;
+
+/// Visits `@stream.done`, `@stream.not_null` and `@stream.with_state` on a
+/// class field, or `@@stream.done` on a class.
+pub(super) fn visit(ctx: &mut Context<'_>, as_block: bool) -> StreamingBehavior {
+    let mut streaming = StreamingBehavior::default();
+
+    if ctx.visit_optional_single_attr("stream.done") {
+        streaming.done = true;
+        ctx.validate_visited_arguments();
+    }
+
+    if !as_block {
+        if ctx.visit_optional_single_attr("stream.not_null") {
+            streaming.not_null = true;
+            ctx.validate_visited_arguments();
+        }
+
+        if ctx.visit_optional_single_attr("stream.with_state") {
+            streaming.with_state = true;
+            ctx.validate_visited_arguments();
+        }
+    }
+
+    streaming
+}
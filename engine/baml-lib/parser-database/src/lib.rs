@@ -39,18 +39,18 @@ mod types;
 use std::collections::{HashMap, HashSet};
 
 pub use coerce_expression::{coerce, coerce_array, coerce_opt};
-use either::Either;
 pub use internal_baml_schema_ast::ast;
 use internal_baml_schema_ast::ast::SchemaAst;
 pub use tarjan::Tarjan;
 pub use types::{
-    Attributes, ContantDelayStrategy, ExponentialBackoffStrategy, PrinterType, PromptAst,
-    PromptVariable, RetryPolicy, RetryPolicyStrategy, StaticType,
+    Attributes, ContantDelayStrategy, ExpectedOutput, ExponentialBackoffStrategy, PrinterType,
+    PromptAst, PromptVariable, RetryPolicy, RetryPolicyStrategy, StaticType,
 };
 
 use self::{context::Context, interner::StringId, types::Types};
 use internal_baml_diagnostics::{DatamodelError, Diagnostics};
 use names::Names;
+use walkers::TypeWalker;
 
 /// ParserDatabase is a container for a Schema AST, together with information
 /// gathered during schema validation. Each validation step enriches the
@@ -131,6 +131,37 @@ impl ParserDatabase {
     }
 
     fn finalize_dependencies(&mut self, diag: &mut Diagnostics) {
+        // Type aliases are just names for other types, so anything that
+        // depends on an alias also depends on whatever the alias points to.
+        let class_dependencies = self
+            .types
+            .class_dependencies
+            .iter()
+            .map(|(&id, deps)| (id, self.expand_alias_dependencies(deps)))
+            .collect::<Vec<_>>();
+        let function_dependencies = self
+            .types
+            .function
+            .iter()
+            .map(|(&id, func)| {
+                let (input, output) = &func.dependencies;
+                (
+                    id,
+                    (
+                        self.expand_alias_dependencies(input),
+                        self.expand_alias_dependencies(output),
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (id, deps) in class_dependencies {
+            self.types.class_dependencies.insert(id, deps);
+        }
+        for (id, deps) in function_dependencies {
+            self.types.function.get_mut(&id).unwrap().dependencies = deps;
+        }
+
         // NOTE: Class dependency cycles are already checked at
         // baml-lib/baml-core/src/validate/validation_pipeline/validations/cycle.rs
         //
@@ -157,8 +188,8 @@ impl ParserDatabase {
                 let deps =
                     HashSet::from_iter(deps.iter().filter_map(
                         |dep| match self.find_type_by_str(dep) {
-                            Some(Either::Left(cls)) => Some(cls.id),
-                            Some(Either::Right(_)) => None,
+                            Some(TypeWalker::Class(cls)) => Some(cls.id),
+                            Some(TypeWalker::Enum(_) | TypeWalker::TypeAlias(_)) => None,
                             None => panic!("Unknown class `{dep}`"),
                         },
                     ));
@@ -184,8 +215,10 @@ impl ParserDatabase {
                 let input_deps = input
                     .iter()
                     .filter_map(|f| match self.find_type_by_str(f) {
-                        Some(Either::Left(walker)) => Some(walker.dependencies().iter().cloned()),
-                        Some(Either::Right(_)) => None,
+                        Some(TypeWalker::Class(walker)) => {
+                            Some(walker.dependencies().iter().cloned())
+                        }
+                        Some(TypeWalker::Enum(_) | TypeWalker::TypeAlias(_)) => None,
                         _ => panic!("Unknown class `{}`", f),
                     })
                     .flatten()
@@ -194,8 +227,10 @@ impl ParserDatabase {
                 let output_deps = output
                     .iter()
                     .filter_map(|f| match self.find_type_by_str(f) {
-                        Some(Either::Left(walker)) => Some(walker.dependencies().iter().cloned()),
-                        Some(Either::Right(_)) => None,
+                        Some(TypeWalker::Class(walker)) => {
+                            Some(walker.dependencies().iter().cloned())
+                        }
+                        Some(TypeWalker::Enum(_) | TypeWalker::TypeAlias(_)) => None,
                         _ => panic!("Unknown class `{}`", f),
                     })
                     .flatten()
@@ -212,6 +247,25 @@ impl ParserDatabase {
         }
     }
 
+    /// Adds the types referenced by every alias in `deps`, following aliases
+    /// that point to other aliases.
+    fn expand_alias_dependencies(&self, deps: &HashSet<String>) -> HashSet<String> {
+        let mut expanded = deps.clone();
+        let mut stack = Vec::from_iter(deps.iter().cloned());
+
+        while let Some(dep) = stack.pop() {
+            if let Some(TypeWalker::TypeAlias(alias)) = self.find_type_by_str(&dep) {
+                for alias_dep in alias.dependencies() {
+                    if expanded.insert(alias_dep.to_owned()) {
+                        stack.push(alias_dep.to_owned());
+                    }
+                }
+            }
+        }
+
+        expanded
+    }
+
     /// The parsed AST.
     pub fn ast(&self) -> &ast::SchemaAst {
         &self.ast
@@ -71,17 +71,22 @@ pub(super) fn resolve_names(ctx: &mut Context<'_>) {
                     validate_attribute_identifiers(field, ctx);
 
                     let field_name_id = ctx.interner.intern(field.name());
-                    if names
+                    if let Some(existing) = names
                         .model_fields
                         .insert((model_id, field_name_id), field_id)
-                        .is_some()
                     {
-                        ctx.push_error(DatamodelError::new_duplicate_field_error(
-                            ast_class.identifier().name(),
-                            field.name(),
-                            "class",
-                            field.identifier().span().clone(),
-                        ))
+                        ctx.push_error(
+                            DatamodelError::new_duplicate_field_error(
+                                ast_class.identifier().name(),
+                                field.name(),
+                                "class",
+                                field.identifier().span().clone(),
+                            )
+                            .with_related(
+                                ast_class[existing].identifier().span().clone(),
+                                format!("`{}` is first defined here", field.name()),
+                            ),
+                        )
                     }
                 }
 
@@ -90,6 +95,14 @@ pub(super) fn resolve_names(ctx: &mut Context<'_>) {
             (_, ast::Top::Class(_)) => {
                 unreachable!("Encountered impossible class declaration during parsing")
             }
+            (ast::TopId::TypeAlias(_), ast::Top::TypeAlias(assignment)) => {
+                validate_type_alias_name(assignment, ctx.diagnostics);
+
+                Some(either::Left(&mut names.tops))
+            }
+            (_, ast::Top::TypeAlias(_)) => {
+                unreachable!("Encountered impossible type alias declaration during parsing")
+            }
             (ast::TopId::TemplateString(_), ast::Top::TemplateString(template_string)) => {
                 validate_template_string_name(template_string, ctx.diagnostics);
                 validate_attribute_identifiers(template_string, ctx);
@@ -216,6 +229,10 @@ fn duplicate_top_error(existing: &ast::Top, duplicate: &ast::Top) -> DatamodelEr
         existing.get_type(),
         duplicate.identifier().span().clone(),
     )
+    .with_related(
+        existing.identifier().span().clone(),
+        format!("`{}` is first defined here", existing.name()),
+    )
 }
 
 fn assert_is_not_a_reserved_scalar_type(ident: &ast::Identifier, ctx: &mut Context<'_>) {
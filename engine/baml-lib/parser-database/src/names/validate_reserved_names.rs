@@ -6,6 +6,13 @@ use crate::{
 };
 
 pub(crate) fn validate_attribute_name(ast_attr: &ast::Attribute, diagnostics: &mut Diagnostics) {
+    // `@stream.*` attributes are namespaced. Whether the rest of the name is
+    // an attribute we know about is checked when the attributes are visited.
+    if let ast::Identifier::Ref(identifier, _) = ast_attr.identifier() {
+        if identifier.path == ["stream"] {
+            return;
+        }
+    }
     validate_name("attribute", ast_attr.identifier(), diagnostics, false);
 }
 
@@ -44,6 +51,13 @@ pub(crate) fn validate_class_name(
     validate_name("class", ast_class.identifier(), diagnostics, true);
 }
 
+pub(crate) fn validate_type_alias_name(
+    ast_type_alias: &ast::Assignment,
+    diagnostics: &mut Diagnostics,
+) {
+    validate_name("type alias", ast_type_alias.identifier(), diagnostics, true);
+}
+
 pub(crate) fn validate_class_field_name<T>(
     ast_class_field: &ast::Field<T>,
     diagnostics: &mut Diagnostics,
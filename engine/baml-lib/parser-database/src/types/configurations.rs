@@ -1,11 +1,19 @@
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
-use internal_baml_schema_ast::ast::{ValExpId, ValueExprBlock, WithIdentifier, WithName, WithSpan};
+use internal_baml_schema_ast::ast::{
+    Expression, ValExpId, ValueExprBlock, WithIdentifier, WithName, WithSpan,
+};
 use regex::Regex;
 use std::collections::HashSet;
 
-use crate::{coerce, coerce_array, coerce_expression::coerce_map, context::Context};
+use crate::{
+    attributes::parse_constraint, coerce, coerce_array, coerce_expression::coerce_map,
+    context::Context,
+};
 
-use super::{ContantDelayStrategy, ExponentialBackoffStrategy, RetryPolicy, RetryPolicyStrategy};
+use super::{
+    ContantDelayStrategy, ExpectedOutput, ExponentialBackoffStrategy, RetryPolicy,
+    RetryPolicyStrategy,
+};
 
 fn dedent(s: &str) -> String {
     // Find the shortest indentation in the string (that's not an empty line).
@@ -35,6 +43,7 @@ pub(crate) fn visit_retry_policy<'db>(
     ctx: &mut Context<'db>,
 ) {
     let mut max_reties = None;
+    let mut retry_on_parse_failure = false;
 
     let mut strategy = Some(RetryPolicyStrategy::ConstantDelay(
         super::ContantDelayStrategy { delay_ms: 200 },
@@ -56,6 +65,16 @@ pub(crate) fn visit_retry_policy<'db>(
                 Some(val) => max_reties = Some(val as u32),
                 None => {}
             },
+            // Also accepts a bare `true`/`false`, which parses as a boolean
+            // literal rather than a constant.
+            ("retry_on_parse_failure", Some(Expression::BoolValue(val, _))) => {
+                retry_on_parse_failure = *val
+            }
+            ("retry_on_parse_failure", Some(val)) => {
+                if let Some(val) = coerce::boolean(val, ctx.diagnostics) {
+                    retry_on_parse_failure = val
+                }
+            }
             ("strategy", Some(val)) => {
                 match coerce_map(val, &coerce::string_with_span, ctx.diagnostics) {
                     Some(val) => match visit_strategy(f.span(), val, ctx.diagnostics) {
@@ -80,7 +99,13 @@ pub(crate) fn visit_retry_policy<'db>(
             (name, Some(_)) => ctx.push_error(DatamodelError::new_property_not_known_error(
                 name,
                 f.identifier().span().clone(),
-                ["max_retries", "strategy", "options"].to_vec(),
+                [
+                    "max_retries",
+                    "strategy",
+                    "retry_on_parse_failure",
+                    "options",
+                ]
+                .to_vec(),
             )),
         });
     match (max_reties, strategy) {
@@ -90,6 +115,7 @@ pub(crate) fn visit_retry_policy<'db>(
                 RetryPolicy {
                     max_retries,
                     strategy,
+                    retry_on_parse_failure,
                     options,
                 },
             );
@@ -201,6 +227,9 @@ pub(crate) fn visit_test_case<'db>(
 ) {
     let mut functions = None;
     let mut args = None;
+    let mut expected_output = None;
+    let mut max_latency_ms = None;
+    let mut max_total_tokens = None;
 
     config
         .iter_fields()
@@ -281,13 +310,74 @@ pub(crate) fn visit_test_case<'db>(
                     None => {}
                 }
             }
+            (name @ ("expected" | "expected_partial"), Some(val)) => {
+                if expected_output.is_some() {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        "Only one of `expected` and `expected_partial` may be set",
+                        f.identifier().span().clone(),
+                    ));
+                } else if name == "expected" {
+                    expected_output = Some(ExpectedOutput::Exact(val.clone()));
+                } else {
+                    expected_output = Some(ExpectedOutput::Partial(val.clone()));
+                }
+            }
+            (name @ ("max_latency_ms" | "max_total_tokens"), Some(val)) => {
+                let budget = match coerce::integer(val, ctx.diagnostics) {
+                    Some(budget) if budget >= 0 => Some(budget as u64),
+                    Some(_) => {
+                        ctx.push_error(DatamodelError::new_validation_error(
+                            &format!("`{name}` must not be negative"),
+                            val.span().clone(),
+                        ));
+                        None
+                    }
+                    None => None,
+                };
+                if name == "max_latency_ms" {
+                    max_latency_ms = budget;
+                } else {
+                    max_total_tokens = budget;
+                }
+            }
             (name, Some(_)) => ctx.push_error(DatamodelError::new_property_not_known_error(
                 name,
                 f.identifier().span().clone(),
-                ["functions", "args"].to_vec(),
+                [
+                    "functions",
+                    "args",
+                    "expected",
+                    "expected_partial",
+                    "max_latency_ms",
+                    "max_total_tokens",
+                ]
+                .to_vec(),
             )),
         });
 
+    let mut constraints = Vec::new();
+    for attribute in config.attributes.iter() {
+        let arguments = attribute
+            .arguments
+            .arguments
+            .iter()
+            .map(|arg| &arg.value)
+            .collect::<Vec<_>>();
+        match attribute.name() {
+            name @ ("assert" | "check") => {
+                if let Some(constraint) =
+                    parse_constraint(name, &arguments, attribute.span.clone(), ctx)
+                {
+                    constraints.push(constraint);
+                }
+            }
+            name => ctx.push_error(DatamodelError::new_attribute_not_known_error(
+                &format!("@{name}"),
+                attribute.span.clone(),
+            )),
+        }
+    }
+
     match (functions, args) {
         (None, _) => ctx.push_error(DatamodelError::new_validation_error(
             "Missing `functions` property",
@@ -304,6 +394,10 @@ pub(crate) fn visit_test_case<'db>(
                     functions,
                     args,
                     args_field_span: args_field_span.clone(),
+                    constraints,
+                    expected_output,
+                    max_latency_ms,
+                    max_total_tokens,
                 },
             );
         }
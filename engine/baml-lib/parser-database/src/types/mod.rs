@@ -3,8 +3,9 @@ use std::hash::Hash;
 
 use crate::coerce;
 use crate::types::configurations::visit_test_case;
-use crate::{context::Context, DatamodelError};
+use crate::{context::Context, DatamodelError, Tarjan};
 
+use baml_types::Constraint;
 use indexmap::IndexMap;
 use internal_baml_diagnostics::Span;
 use internal_baml_prompt_parser::ast::{ChatBlock, PrinterBlock, Variable};
@@ -32,6 +33,10 @@ pub(super) fn resolve_types(ctx: &mut Context<'_>) {
                 visit_class(idx, model, ctx);
             }
             (_, ast::Top::Class(_)) => unreachable!("Class misconfigured"),
+            (ast::TopId::TypeAlias(idx), ast::Top::TypeAlias(assignment)) => {
+                visit_type_alias(idx, assignment, ctx);
+            }
+            (_, ast::Top::TypeAlias(_)) => unreachable!("Type alias misconfigured"),
             (ast::TopId::TemplateString(idx), ast::Top::TemplateString(template_string)) => {
                 visit_template_string(idx, template_string, ctx)
             }
@@ -58,7 +63,10 @@ pub(super) fn resolve_types(ctx: &mut Context<'_>) {
             _ => {}
         }
     }
+
+    resolve_type_alias_cycles(ctx);
 }
+
 #[derive(Debug, Clone)]
 /// Variables used inside of raw strings.
 pub enum PromptVariable {
@@ -138,6 +146,21 @@ pub struct TestCase {
     // The span is the span of the argument (the expression has its own span)
     pub args: IndexMap<String, (Span, Expression)>,
     pub args_field_span: Span,
+    /// `@@assert` and `@@check` attributes, evaluated against the output.
+    pub constraints: Vec<Constraint>,
+    pub expected_output: Option<ExpectedOutput>,
+    pub max_latency_ms: Option<u64>,
+    pub max_total_tokens: Option<u64>,
+}
+
+/// The value a test expects its function to return.
+#[derive(Debug, Clone)]
+pub enum ExpectedOutput {
+    /// `expected`: the output must be exactly this value.
+    Exact(Expression),
+    /// `expected_partial`: only the fields listed must match, anything else
+    /// in the output is ignored.
+    Partial(Expression),
 }
 
 #[derive(Debug, Clone)]
@@ -171,6 +194,9 @@ pub struct RetryPolicy {
     pub max_retries: u32,
     /// The strategy to use.
     pub strategy: RetryPolicyStrategy,
+    /// Whether a response that fails to parse (or fails an `@assert`) should
+    /// be retried like a failed request.
+    pub retry_on_parse_failure: bool,
     /// Any additional options.
     pub options: Option<Vec<((String, Span), Expression)>>,
 }
@@ -207,6 +233,8 @@ pub struct FunctionType {
     pub dependencies: (HashSet<String>, HashSet<String>),
     pub prompt: Option<RawString>,
     pub client: Option<(String, Span)>,
+    /// Total time allowed for a call, across all retries and fallbacks.
+    pub timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -237,6 +265,13 @@ pub(super) struct Types {
     /// Merge-Find Set or something like that.
     pub(super) finite_recursive_cycles: Vec<Vec<ast::TypeExpId>>,
 
+    pub(super) type_alias_dependencies: HashMap<ast::TypeAliasId, HashSet<String>>,
+
+    /// Cycles of type aliases that reference each other through a list or a
+    /// map, like `type JsonValue = int | JsonValue[]`. These are the only
+    /// recursive aliases we accept, anything else can't ever be constructed.
+    pub(super) structural_recursive_alias_cycles: Vec<Vec<ast::TypeAliasId>>,
+
     pub(super) function: HashMap<ast::ValExpId, FunctionType>,
 
     pub(super) client_properties: HashMap<ast::ValExpId, ClientProperties>,
@@ -336,6 +371,105 @@ fn visit_class<'db>(
     });
 }
 
+fn visit_type_alias<'db>(
+    alias_id: ast::TypeAliasId,
+    assignment: &'db ast::Assignment,
+    ctx: &mut Context<'db>,
+) {
+    ctx.types.type_alias_dependencies.insert(
+        alias_id,
+        assignment
+            .value
+            .flat_idns()
+            .iter()
+            .map(|id| id.name().to_string())
+            .collect(),
+    );
+}
+
+/// Finds type aliases that point to themselves.
+///
+/// An alias can only reference itself if the recursion goes through a list or
+/// a map, which can always be terminated with an empty value. Anything else,
+/// such as `type A = B` and `type B = A`, or `type A = int | A`, is an error.
+/// Valid cycles are stored so that later stages don't try to expand them.
+fn resolve_type_alias_cycles(ctx: &mut Context<'_>) {
+    let mut direct_graph = HashMap::new();
+    let mut full_graph = HashMap::new();
+
+    for (top_id, top) in ctx.ast.iter_tops() {
+        let (Some(alias_id), ast::Top::TypeAlias(assignment)) = (top_id.as_type_alias_id(), top)
+        else {
+            continue;
+        };
+
+        let mut direct = HashSet::new();
+        let mut full = HashSet::new();
+        insert_alias_refs(&assignment.value, ctx, false, &mut direct, &mut full);
+
+        direct_graph.insert(alias_id, direct);
+        full_graph.insert(alias_id, full);
+    }
+
+    let invalid_cycles = Tarjan::components(&direct_graph);
+
+    for cycle in &invalid_cycles {
+        let path = cycle
+            .iter()
+            .chain(std::iter::once(&cycle[0]))
+            .map(|id| ctx.ast[*id].name())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!("These aliases form a dependency cycle: {path}"),
+            ctx.ast[cycle[0]].span().clone(),
+        ));
+    }
+
+    if invalid_cycles.is_empty() {
+        ctx.types.structural_recursive_alias_cycles = Tarjan::components(&full_graph);
+    }
+}
+
+/// Collects the aliases referenced by `field_type`. Aliases found inside a list
+/// or a map only go into `full`, everything else goes into both sets.
+fn insert_alias_refs(
+    field_type: &ast::FieldType,
+    ctx: &Context<'_>,
+    in_container: bool,
+    direct: &mut HashSet<ast::TypeAliasId>,
+    full: &mut HashSet<ast::TypeAliasId>,
+) {
+    match field_type {
+        ast::FieldType::Symbol(_, idn, _) => {
+            let alias_id = ctx
+                .interner
+                .lookup(idn.name())
+                .and_then(|name_id| ctx.names.tops.get(&name_id))
+                .and_then(|top_id| top_id.as_type_alias_id());
+
+            if let Some(alias_id) = alias_id {
+                if !in_container {
+                    direct.insert(alias_id);
+                }
+                full.insert(alias_id);
+            }
+        }
+        ast::FieldType::Union(_, items, ..) | ast::FieldType::Tuple(_, items, ..) => {
+            for item in items {
+                insert_alias_refs(item, ctx, in_container, direct, full);
+            }
+        }
+        ast::FieldType::List(_, item, ..) => insert_alias_refs(item, ctx, true, direct, full),
+        ast::FieldType::Map(_, kv, ..) => {
+            insert_alias_refs(&kv.0, ctx, true, direct, full);
+            insert_alias_refs(&kv.1, ctx, true, direct, full);
+        }
+        ast::FieldType::Primitive(..) | ast::FieldType::Literal(..) => {}
+    }
+}
+
 fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &mut Context<'db>) {
     let input_deps = function
         .input()
@@ -363,6 +497,7 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
 
     let mut prompt = None;
     let mut client = None;
+    let mut timeout_ms = None;
     function
         .iter_fields()
         .for_each(|(_idx, field)| match field.name() {
@@ -379,6 +514,22 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     None => None,
                 }
             }
+            "timeout_ms" => {
+                timeout_ms = match &field.expr {
+                    Some(val) => match coerce::integer(val, ctx.diagnostics) {
+                        Some(ms) if ms > 0 => Some(ms as u64),
+                        Some(_) => {
+                            ctx.push_error(DatamodelError::new_validation_error(
+                                "`timeout_ms` must be a positive number of milliseconds",
+                                val.span().clone(),
+                            ));
+                            None
+                        }
+                        None => None,
+                    },
+                    None => None,
+                }
+            }
             config => ctx.push_error(DatamodelError::new_validation_error(
                 &format!("Unknown field `{}` in function", config),
                 field.span().clone(),
@@ -393,6 +544,7 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     dependencies: (input_deps.clone(), output_deps),
                     prompt: Some(prompt.clone()),
                     client: Some(client),
+                    timeout_ms,
                 },
             );
 
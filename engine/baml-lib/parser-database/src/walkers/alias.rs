@@ -0,0 +1,58 @@
+use internal_baml_schema_ast::ast::{self, FieldType, WithDocumentation, WithIdentifier, WithSpan};
+
+use super::Walker;
+
+/// A `type` alias declaration in the schema.
+pub type TypeAliasWalker<'db> = Walker<'db, ast::TypeAliasId>;
+
+impl<'db> TypeAliasWalker<'db> {
+    /// The AST node.
+    pub fn ast_type_alias(self) -> &'db ast::Assignment {
+        &self.db.ast[self.id]
+    }
+
+    /// The type the alias points to.
+    ///
+    /// ```ignore
+    /// type Foo = int | string
+    ///            ^^^^^^^^^^^^
+    /// ```
+    pub fn target(self) -> &'db FieldType {
+        &self.ast_type_alias().value
+    }
+
+    /// Names of all the types referenced by the alias target.
+    pub fn dependencies(self) -> &'db std::collections::HashSet<String> {
+        &self.db.types.type_alias_dependencies[&self.id]
+    }
+
+    /// Whether the alias is part of a recursive cycle of aliases, such as
+    /// `type JsonValue = int | string | JsonValue[] | map<string, JsonValue>`.
+    ///
+    /// Recursive aliases can't be expanded in place, so they are kept by name
+    /// in the IR and generated code.
+    pub fn is_recursive(self) -> bool {
+        self.db
+            .types
+            .structural_recursive_alias_cycles
+            .iter()
+            .any(|cycle| cycle.contains(&self.id))
+    }
+
+    /// The alias documentation.
+    pub fn documentation(self) -> Option<&'db str> {
+        self.ast_type_alias().documentation()
+    }
+}
+
+impl<'db> WithIdentifier for TypeAliasWalker<'db> {
+    fn identifier(&self) -> &ast::Identifier {
+        self.ast_type_alias().identifier()
+    }
+}
+
+impl<'db> WithSpan for TypeAliasWalker<'db> {
+    fn span(&self) -> &internal_baml_diagnostics::Span {
+        self.ast_type_alias().span()
+    }
+}
@@ -1,9 +1,8 @@
 use std::collections::HashSet;
 
-use super::{field::FieldWalker, EnumWalker};
+use super::{field::FieldWalker, EnumWalker, TypeWalker};
 use crate::types::Attributes;
 use baml_types::Constraint;
-use either::Either;
 use internal_baml_schema_ast::ast::Identifier;
 use internal_baml_schema_ast::ast::SubType;
 use internal_baml_schema_ast::ast::{self, ArgumentId, WithIdentifier, WithName, WithSpan};
@@ -42,9 +41,8 @@ impl<'db> ClassWalker<'db> {
         self.db.types.class_dependencies[&self.class_id()]
             .iter()
             .filter_map(|f| match self.db.find_type_by_str(f) {
-                Some(Either::Left(_cls)) => None,
-                Some(Either::Right(walker)) => Some(walker),
-                None => None,
+                Some(TypeWalker::Enum(walker)) => Some(walker),
+                _ => None,
             })
     }
 
@@ -53,9 +51,8 @@ impl<'db> ClassWalker<'db> {
         self.db.types.class_dependencies[&self.class_id()]
             .iter()
             .filter_map(|f| match self.db.find_type_by_str(f) {
-                Some(Either::Left(walker)) => Some(walker),
-                Some(Either::Right(_enm)) => None,
-                None => None,
+                Some(TypeWalker::Class(walker)) => Some(walker),
+                _ => None,
             })
     }
 
@@ -166,9 +163,8 @@ impl<'db> ArgWalker<'db> {
         input
             .iter()
             .filter_map(|f| match self.db.find_type_by_str(f) {
-                Some(Either::Left(_cls)) => None,
-                Some(Either::Right(walker)) => Some(walker),
-                None => None,
+                Some(TypeWalker::Enum(walker)) => Some(walker),
+                _ => None,
             })
     }
 
@@ -178,9 +174,8 @@ impl<'db> ArgWalker<'db> {
         input
             .iter()
             .filter_map(|f| match self.db.find_type_by_str(f) {
-                Some(Either::Left(walker)) => Some(walker),
-                Some(Either::Right(_enm)) => None,
-                None => None,
+                Some(TypeWalker::Class(walker)) => Some(walker),
+                _ => None,
             })
     }
 }
@@ -6,6 +6,7 @@
 //! - Know about relations.
 //! - Do not know anything about connectors, they are generic.
 
+mod alias;
 mod r#class;
 mod client;
 mod configuration;
@@ -14,14 +15,16 @@ mod field;
 mod function;
 mod template_string;
 
+pub use alias::*;
 use baml_types::TypeValue;
 pub use client::*;
 pub use configuration::*;
-use either::Either;
 pub use field::*;
 pub use function::{FunctionWalker, ClientSpec};
 pub use template_string::TemplateStringWalker;
-use internal_baml_schema_ast::ast::{FieldType, Identifier, TopId, TypeExpId, WithName};
+use internal_baml_schema_ast::ast::{
+    FieldType, Identifier, TopId, TypeAliasId, TypeExpId, WithName,
+};
 pub use r#class::*;
 pub use r#enum::*;
 
@@ -50,11 +53,22 @@ where
     }
 }
 
+/// A type that can be referenced by name from a field type.
+#[derive(Clone, Copy)]
+pub enum TypeWalker<'db> {
+    /// A `class` declaration.
+    Class(ClassWalker<'db>),
+    /// An `enum` declaration.
+    Enum(EnumWalker<'db>),
+    /// A `type` alias declaration.
+    TypeAlias(TypeAliasWalker<'db>),
+}
+
 impl<'db> crate::ParserDatabase {
     /// Find an enum by name.
     pub fn find_enum(&'db self, idn: &Identifier) -> Option<EnumWalker<'db>> {
-        self.find_type(idn).and_then(|either| match either {
-            Either::Right(class) => Some(class),
+        self.find_type(idn).and_then(|walker| match walker {
+            TypeWalker::Enum(enm) => Some(enm),
             _ => None,
         })
     }
@@ -66,22 +80,17 @@ impl<'db> crate::ParserDatabase {
     }
 
     /// Find a type by name.
-    pub fn find_type_by_str(
-        &'db self,
-        name: &str,
-    ) -> Option<Either<ClassWalker<'db>, EnumWalker<'db>>> {
+    pub fn find_type_by_str(&'db self, name: &str) -> Option<TypeWalker<'db>> {
         self.find_top_by_str(name).and_then(|top_id| match top_id {
-            TopId::Class(class_id) => Some(Either::Left(self.walk(*class_id))),
-            TopId::Enum(enum_id) => Some(Either::Right(self.walk(*enum_id))),
+            TopId::Class(class_id) => Some(TypeWalker::Class(self.walk(*class_id))),
+            TopId::Enum(enum_id) => Some(TypeWalker::Enum(self.walk(*enum_id))),
+            TopId::TypeAlias(alias_id) => Some(TypeWalker::TypeAlias(self.walk(*alias_id))),
             _ => None,
         })
     }
 
     /// Find a type by name.
-    pub fn find_type(
-        &'db self,
-        idn: &Identifier,
-    ) -> Option<Either<ClassWalker<'db>, EnumWalker<'db>>> {
+    pub fn find_type(&'db self, idn: &Identifier) -> Option<TypeWalker<'db>> {
         match idn {
             Identifier::Local(local, _) => self.find_type_by_str(local),
             _ => None,
@@ -90,8 +99,16 @@ impl<'db> crate::ParserDatabase {
 
     /// Find a model by name.
     pub fn find_class(&'db self, idn: &Identifier) -> Option<ClassWalker<'db>> {
-        self.find_type(idn).and_then(|either| match either {
-            Either::Left(class) => Some(class),
+        self.find_type(idn).and_then(|walker| match walker {
+            TypeWalker::Class(class) => Some(class),
+            _ => None,
+        })
+    }
+
+    /// Find a type alias by name.
+    pub fn find_type_alias(&'db self, idn: &Identifier) -> Option<TypeAliasWalker<'db>> {
+        self.find_type(idn).and_then(|walker| match walker {
+            TypeWalker::TypeAlias(alias) => Some(alias),
             _ => None,
         })
     }
@@ -133,6 +150,13 @@ impl<'db> crate::ParserDatabase {
         &self.types.finite_recursive_cycles
     }
 
+    /// Returns all the type aliases that are part of some recursive definition
+    /// going through a list or a map, like
+    /// `type JsonValue = int | JsonValue[] | map<string, JsonValue>`.
+    pub fn structural_recursive_alias_cycles(&self) -> &[Vec<TypeAliasId>] {
+        &self.types.structural_recursive_alias_cycles
+    }
+
     /// Traverse a schema element by id.
     pub fn walk<I>(&self, id: I) -> Walker<'_, I> {
         Walker { db: self, id }
@@ -142,6 +166,7 @@ impl<'db> crate::ParserDatabase {
     pub fn valid_type_names(&'db self) -> Vec<String> {
         let mut names: Vec<String> = self.walk_classes().map(|c| c.name().to_string()).collect();
         names.extend(self.walk_enums().map(|e| e.name().to_string()));
+        names.extend(self.walk_type_aliases().map(|a| a.name().to_string()));
         // Add primitive types
         names.extend(
             vec!["string", "int", "float", "bool", "true", "false"]
@@ -192,6 +217,17 @@ impl<'db> crate::ParserDatabase {
             })
     }
 
+    /// Walk all type aliases in the schema.
+    pub fn walk_type_aliases(&self) -> impl Iterator<Item = TypeAliasWalker<'_>> {
+        self.ast()
+            .iter_tops()
+            .filter_map(|(top_id, _)| top_id.as_type_alias_id())
+            .map(move |top_id| Walker {
+                db: self,
+                id: top_id,
+            })
+    }
+
     /// Walk all template strings in the schema.
     pub fn walk_templates(&self) -> impl Iterator<Item = TemplateStringWalker<'_>> {
         self.ast()
@@ -255,8 +291,12 @@ impl<'db> crate::ParserDatabase {
             FieldType::Symbol(arity, idn, ..) => {
                 let mut t = match self.find_type(idn) {
                     None => Type::Undefined,
-                    Some(Either::Left(_)) => Type::ClassRef(idn.to_string()),
-                    Some(Either::Right(_)) => Type::String,
+                    Some(TypeWalker::Class(_)) => Type::ClassRef(idn.to_string()),
+                    Some(TypeWalker::Enum(_)) => Type::String,
+                    // Recursive aliases can't be expanded, so Jinja only gets
+                    // to type check their first level.
+                    Some(TypeWalker::TypeAlias(alias)) if alias.is_recursive() => Type::Unknown,
+                    Some(TypeWalker::TypeAlias(alias)) => self.to_jinja_type(alias.target()),
                 };
                 if arity.is_optional() {
                     t = Type::None | t;
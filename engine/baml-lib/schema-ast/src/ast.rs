@@ -1,4 +1,5 @@
 mod argument;
+mod assignment;
 mod attribute;
 
 mod comment;
@@ -19,6 +20,7 @@ mod value_expression_block;
 pub(crate) use self::comment::Comment;
 
 pub use argument::{ArgumentId, Argument, ArgumentsList};
+pub use assignment::Assignment;
 pub use attribute::{Attribute, AttributeContainer, AttributeId};
 pub use config::ConfigBlockProperty;
 pub use expression::{Expression, RawString};
@@ -128,6 +130,18 @@ impl std::ops::Index<TemplateStringId> for SchemaAst {
     }
 }
 
+/// An opaque identifier for a type alias in a schema AST. Use the
+/// `schema[alias_id]` syntax to resolve the id to an `ast::Assignment`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeAliasId(u32);
+impl std::ops::Index<TypeAliasId> for SchemaAst {
+    type Output = Assignment;
+
+    fn index(&self, index: TypeAliasId) -> &Self::Output {
+        self.tops[index.0 as usize].as_type_alias_assignment().unwrap()
+    }
+}
+
 /// An identifier for a top-level item in a schema AST. Use the `schema[top_id]`
 /// syntax to resolve the id to an `ast::Top`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -138,6 +152,9 @@ pub enum TopId {
     // A class declaration
     Class(TypeExpId),
 
+    // A type alias declaration
+    TypeAlias(TypeAliasId),
+
     // A function declaration
     Function(ValExpId),
 
@@ -173,6 +190,14 @@ impl TopId {
         }
     }
 
+    /// Try to interpret the top as a type alias.
+    pub fn as_type_alias_id(self) -> Option<TypeAliasId> {
+        match self {
+            TopId::TypeAlias(id) => Some(id),
+            _ => None,
+        }
+    }
+
     /// Try to interpret the top as a function.
     pub fn as_function_id(self) -> Option<ValExpId> {
         match self {
@@ -217,6 +242,7 @@ impl std::ops::Index<TopId> for SchemaAst {
         let idx = match index {
             TopId::Enum(TypeExpId(idx)) => idx,
             TopId::Class(TypeExpId(idx)) => idx,
+            TopId::TypeAlias(TypeAliasId(idx)) => idx,
             TopId::Function(ValExpId(idx)) => idx,
             TopId::TemplateString(TemplateStringId(idx)) => idx,
             TopId::Client(ValExpId(idx)) => idx,
@@ -233,6 +259,7 @@ fn top_idx_to_top_id(top_idx: usize, top: &Top) -> TopId {
     match top {
         Top::Enum(_) => TopId::Enum(TypeExpId(top_idx as u32)),
         Top::Class(_) => TopId::Class(TypeExpId(top_idx as u32)),
+        Top::TypeAlias(_) => TopId::TypeAlias(TypeAliasId(top_idx as u32)),
         Top::Function(_) => TopId::Function(ValExpId(top_idx as u32)),
         Top::Client(_) => TopId::Client(ValExpId(top_idx as u32)),
         Top::TemplateString(_) => TopId::TemplateString(TemplateStringId(top_idx as u32)),
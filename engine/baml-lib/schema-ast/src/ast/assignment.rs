@@ -0,0 +1,57 @@
+use super::{
+    Comment, FieldType, Identifier, Span, WithDocumentation, WithIdentifier, WithSpan,
+};
+
+/// A type alias declaration.
+///
+/// ```ignore
+/// type JsonValue = int | float | bool | string | null | JsonValue[] | map<string, JsonValue>
+/// ```
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    /// The name of the alias.
+    ///
+    /// ```ignore
+    /// type Foo = int | string
+    ///      ^^^
+    /// ```
+    pub identifier: Identifier,
+
+    /// The type the alias points to.
+    ///
+    /// ```ignore
+    /// type Foo = int | string
+    ///            ^^^^^^^^^^^^
+    /// ```
+    pub value: FieldType,
+
+    /// The documentation for this alias.
+    ///
+    /// ```ignore
+    /// /// Lorem ipsum
+    ///     ^^^^^^^^^^^
+    /// type Foo = int | string
+    /// ```
+    pub(crate) documentation: Option<Comment>,
+
+    /// The location of this alias in the text representation.
+    pub span: Span,
+}
+
+impl WithIdentifier for Assignment {
+    fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+}
+
+impl WithSpan for Assignment {
+    fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+impl WithDocumentation for Assignment {
+    fn documentation(&self) -> Option<&str> {
+        self.documentation.as_ref().map(|doc| doc.text.as_str())
+    }
+}
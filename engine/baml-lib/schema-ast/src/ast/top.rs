@@ -1,6 +1,6 @@
 use super::{
-    traits::WithSpan, Identifier, Span, TemplateString, TypeExpressionBlock, ValueExprBlock,
-    WithIdentifier,
+    traits::WithSpan, Assignment, Identifier, Span, TemplateString, TypeExpressionBlock,
+    ValueExprBlock, WithIdentifier,
 };
 
 /// Enum for distinguishing between top-level entries
@@ -10,6 +10,8 @@ pub enum Top {
     Enum(TypeExpressionBlock),
     // A class declaration
     Class(TypeExpressionBlock),
+    // A type alias declaration
+    TypeAlias(Assignment),
     // A function declaration
     Function(ValueExprBlock),
 
@@ -33,6 +35,7 @@ impl Top {
             // Top::CompositeType(_) => "composite type",
             Top::Enum(_) => "enum",
             Top::Class(_) => "class",
+            Top::TypeAlias(_) => "type_alias",
             Top::Function(_) => "function",
             Top::Client(_) => "client<llm>",
             Top::TemplateString(_) => "template_string",
@@ -51,6 +54,14 @@ impl Top {
         }
     }
 
+    /// Try to interpret the item as a type alias declaration.
+    pub fn as_type_alias_assignment(&self) -> Option<&Assignment> {
+        match self {
+            Top::TypeAlias(assignment) => Some(assignment),
+            _ => None,
+        }
+    }
+
     pub fn as_value_exp(&self) -> Option<&ValueExprBlock> {
         match self {
             Top::Function(func) => Some(func),
@@ -77,6 +88,7 @@ impl WithIdentifier for Top {
             // Top::CompositeType(ct) => &ct.name,
             Top::Enum(x) => x.identifier(),
             Top::Class(x) => x.identifier(),
+            Top::TypeAlias(x) => x.identifier(),
             Top::Function(x) => x.identifier(),
             Top::Client(x) => x.identifier(),
             Top::TemplateString(x) => x.identifier(),
@@ -92,6 +104,7 @@ impl WithSpan for Top {
         match self {
             Top::Enum(en) => en.span(),
             Top::Class(class) => class.span(),
+            Top::TypeAlias(assignment) => assignment.span(),
             Top::Function(func) => func.span(),
             Top::TemplateString(template) => template.span(),
             Top::Client(client) => client.span(),
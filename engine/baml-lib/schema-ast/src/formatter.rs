@@ -0,0 +1,826 @@
+use std::path::PathBuf;
+
+use internal_baml_diagnostics::{Diagnostics, SourceFile};
+use pest::Parser;
+
+use crate::parser::{BAMLParser, Rule};
+
+type Pair<'a> = pest::iterators::Pair<'a, Rule>;
+
+const INDENT: &str = "  ";
+
+/// Arrays and argument lists longer than this are split over several lines.
+const MAX_INLINE_WIDTH: usize = 80;
+
+/// Formats a BAML file.
+///
+/// Only whitespace, the placement of comments and attributes, separators in
+/// maps and arrays and the delimiters of raw strings are changed, so the
+/// output parses to the same AST. Formatting is idempotent.
+///
+/// Files with syntax errors are not formatted, their diagnostics are returned
+/// instead.
+pub fn format_schema(root_path: &PathBuf, source: &SourceFile) -> Result<String, Diagnostics> {
+    let (_, diagnostics) = crate::parse_schema(root_path, source)?;
+    if diagnostics.has_errors() {
+        return Err(diagnostics);
+    }
+
+    let schema = BAMLParser::parse(Rule::schema, source.as_str())
+        .expect("the schema has already been parsed")
+        .next()
+        .unwrap();
+    let mut formatter = Formatter::new(source.as_str());
+    formatter.schema(schema);
+    Ok(formatter.finish())
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    out: String,
+    indent: usize,
+    /// Set after opening a block, so that it doesn't start with a blank line.
+    at_block_start: bool,
+    /// A line comment runs to the end of the line, so nothing can follow it.
+    line_has_comment: bool,
+    /// Comments in a field or type, which are written after it so that they
+    /// don't comment out the attributes that follow them.
+    comments: Vec<Pair<'a>>,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(source: &'a str) -> Self {
+        Formatter {
+            source,
+            out: String::new(),
+            indent: 0,
+            at_block_start: false,
+            line_has_comment: false,
+            comments: Vec::new(),
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out
+    }
+
+    /// Lines are terminated lazily, so that trailing comments can still be
+    /// added to the previous one.
+    fn line(&mut self, blank_line_before: bool) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+            if blank_line_before && !self.at_block_start {
+                self.out.push('\n');
+            }
+        }
+        self.out.push_str(&INDENT.repeat(self.indent));
+        self.at_block_start = false;
+        self.line_has_comment = false;
+    }
+
+    /// Whether `pair` is on the same line as the code before it.
+    fn is_trailing(&self, pair: &Pair<'_>) -> bool {
+        let before = &self.source[..pair.as_span().start()];
+        let line = before.rsplit('\n').next().unwrap_or_default();
+        !line.trim().is_empty()
+    }
+
+    /// Whether there is a blank line right before `pair`. Several blank lines
+    /// are collapsed into one.
+    fn is_after_blank_line(&self, pair: &Pair<'_>) -> bool {
+        let before = self.source[..pair.as_span().start()].trim_end_matches([' ', '\t']);
+        match before.strip_suffix('\n') {
+            Some(before) => before.trim_end_matches([' ', '\t', '\r']).ends_with('\n'),
+            None => false,
+        }
+    }
+
+    fn schema(&mut self, schema: Pair<'a>) {
+        // Declarations are always separated by a blank line, comments are
+        // kept attached to the declaration that follows them.
+        let mut after_declaration = false;
+        for item in schema.into_inner() {
+            match item.as_rule() {
+                Rule::EOI | Rule::empty_lines => {}
+                Rule::comment_block => {
+                    for comment in item.into_inner() {
+                        let blank_line_before =
+                            after_declaration || self.is_after_blank_line(&comment);
+                        if !self.comment(comment, blank_line_before) {
+                            after_declaration = false;
+                        }
+                    }
+                }
+                _ => {
+                    self.line(after_declaration || self.is_after_blank_line(&item));
+                    match item.as_rule() {
+                        Rule::type_expression_block => self.type_expression_block(item),
+                        Rule::value_expression_block => self.value_expression_block(item),
+                        Rule::template_declaration => self.template_declaration(item),
+                        Rule::type_alias => self.type_alias(item),
+                        Rule::raw_string_literal => self.raw_string(item),
+                        _ => self.verbatim(item),
+                    }
+                    self.flush_comments();
+                    after_declaration = true;
+                }
+            }
+        }
+    }
+
+    /// Writes a comment at the end of the current line if it follows code on
+    /// the same line, and on its own line otherwise. Returns whether it was
+    /// written at the end of the line.
+    fn comment(&mut self, comment: Pair<'_>, blank_line_before: bool) -> bool {
+        let trailing = !self.out.is_empty() && !self.line_has_comment && self.is_trailing(&comment);
+        if trailing {
+            self.out.push(' ');
+        } else {
+            self.line(blank_line_before);
+        }
+        self.out.push_str(comment.as_str().trim());
+        self.line_has_comment = true;
+        trailing
+    }
+
+    fn comment_block(&mut self, block: Pair<'_>) {
+        for comment in block.into_inner() {
+            let blank_line_before = self.is_after_blank_line(&comment);
+            self.comment(comment, blank_line_before);
+        }
+    }
+
+    fn defer_comments(&mut self, pair: Pair<'a>) {
+        self.comments
+            .extend(pair.into_inner().flatten().filter(|p| {
+                matches!(
+                    p.as_rule(),
+                    Rule::comment | Rule::doc_comment | Rule::block_comment
+                )
+            }));
+    }
+
+    fn flush_comments(&mut self) {
+        for comment in std::mem::take(&mut self.comments) {
+            let blank_line_before = self.is_after_blank_line(&comment);
+            self.comment(comment, blank_line_before);
+        }
+    }
+
+    /// Comments between the name of a block and its opening brace have
+    /// nowhere to go on the line, so they are moved above the block.
+    fn hoist_comments(&mut self, header_start: usize) {
+        let comments = std::mem::take(&mut self.comments);
+        if comments.is_empty() {
+            return;
+        }
+        let mut hoisted = String::new();
+        for comment in comments {
+            hoisted.push_str(comment.as_str().trim());
+            hoisted.push('\n');
+            hoisted.push_str(&INDENT.repeat(self.indent));
+        }
+        self.out.insert_str(header_start, &hoisted);
+    }
+
+    fn verbatim(&mut self, pair: Pair<'_>) {
+        self.out.push_str(pair.as_str().trim());
+    }
+
+    fn open_block(&mut self) -> usize {
+        self.out.push('{');
+        self.indent += 1;
+        self.at_block_start = true;
+        self.out.len()
+    }
+
+    fn close_block(&mut self, opened_at: usize) {
+        self.indent -= 1;
+        if self.out.len() != opened_at {
+            self.line(false);
+        }
+        self.out.push('}');
+        self.at_block_start = false;
+    }
+
+    /// `class Foo { ... }` and `enum Foo { ... }`
+    fn type_expression_block(&mut self, block: Pair<'a>) {
+        let header_start = self.out.len();
+        let mut opened_at = 0;
+        let mut is_keyword = true;
+        for current in block.into_inner() {
+            match current.as_rule() {
+                Rule::identifier => {
+                    if !is_keyword {
+                        self.out.push(' ');
+                    }
+                    self.verbatim(current);
+                    is_keyword = false;
+                }
+                Rule::named_argument_list => self.named_argument_list(current),
+                Rule::BLOCK_OPEN => {
+                    self.hoist_comments(header_start);
+                    self.out.push(' ');
+                    opened_at = self.open_block();
+                }
+                Rule::type_expression_contents => self.block_contents(current),
+                Rule::BLOCK_CLOSE => self.close_block(opened_at),
+                _ => self.verbatim(current),
+            }
+        }
+    }
+
+    /// `function`, `test`, `client`, `retry_policy` and `generator` blocks.
+    fn value_expression_block(&mut self, block: Pair<'a>) {
+        let header_start = self.out.len();
+        let mut opened_at = 0;
+        for current in block.into_inner() {
+            match current.as_rule() {
+                Rule::value_expression_keyword => self.verbatim(current),
+                Rule::identifier => {
+                    self.out.push(' ');
+                    self.verbatim(current);
+                }
+                Rule::named_argument_list => self.named_argument_list(current),
+                Rule::ARROW => {
+                    self.defer_comments(current);
+                    self.out.push_str(" ->");
+                }
+                Rule::field_type_chain => {
+                    self.out.push(' ');
+                    self.field_type(current);
+                }
+                Rule::SPACER_TEXT => self.defer_comments(current),
+                Rule::BLOCK_OPEN => {
+                    self.hoist_comments(header_start);
+                    self.out.push(' ');
+                    opened_at = self.open_block();
+                }
+                Rule::value_expression_contents => self.block_contents(current),
+                Rule::BLOCK_CLOSE => self.close_block(opened_at),
+                _ => self.verbatim(current),
+            }
+        }
+    }
+
+    fn block_contents(&mut self, contents: Pair<'a>) {
+        for item in contents.into_inner() {
+            match item.as_rule() {
+                Rule::empty_lines => {}
+                Rule::comment_block => self.comment_block(item),
+                Rule::type_expression | Rule::value_expression => {
+                    self.line(self.is_after_blank_line(&item));
+                    self.field(item);
+                }
+                Rule::block_attribute => {
+                    self.line(self.is_after_blank_line(&item));
+                    self.attribute(item);
+                }
+                _ => {
+                    self.line(self.is_after_blank_line(&item));
+                    self.verbatim(item);
+                }
+            }
+        }
+    }
+
+    /// A field of a class, a value of an enum or a property of a function,
+    /// client, etc. Attributes are moved to the same line as the field.
+    fn field(&mut self, field: Pair<'a>) {
+        for current in field.into_inner() {
+            match current.as_rule() {
+                Rule::identifier => self.verbatim(current),
+                Rule::field_type_chain => {
+                    self.out.push(' ');
+                    self.field_type(current);
+                }
+                Rule::expression => {
+                    self.out.push(' ');
+                    self.expression(current);
+                }
+                Rule::field_attribute => {
+                    self.out.push(' ');
+                    self.attribute(current);
+                }
+                Rule::trailing_comment => self.defer_comments(current),
+                _ => self.verbatim(current),
+            }
+        }
+        self.flush_comments();
+    }
+
+    /// `template_string Foo(arg: string) #"..."#`
+    fn template_declaration(&mut self, template: Pair<'a>) {
+        for current in template.into_inner() {
+            match current.as_rule() {
+                Rule::TEMPLATE_KEYWORD => self.verbatim(current),
+                Rule::identifier => {
+                    self.out.push(' ');
+                    self.verbatim(current);
+                }
+                Rule::assignment => self.out.push_str(" ="),
+                Rule::named_argument_list => self.named_argument_list(current),
+                Rule::raw_string_literal => {
+                    self.out.push(' ');
+                    self.raw_string(current);
+                }
+                _ => self.verbatim(current),
+            }
+        }
+    }
+
+    /// `type Foo = Bar | Baz`
+    fn type_alias(&mut self, alias: Pair<'a>) {
+        for current in alias.into_inner() {
+            match current.as_rule() {
+                Rule::TYPE_KEYWORD => self.verbatim(current),
+                Rule::identifier => {
+                    self.out.push(' ');
+                    self.verbatim(current);
+                }
+                Rule::assignment => self.out.push_str(" = "),
+                _ => self.field_type(current),
+            }
+        }
+    }
+
+    /// `(arg: string, other: int)`, split over several lines with a trailing
+    /// comma if it contains comments or is too long.
+    fn named_argument_list(&mut self, list: Pair<'a>) {
+        let has_comments = list.clone().into_inner().flatten().any(|p| {
+            matches!(
+                p.as_rule(),
+                Rule::comment | Rule::doc_comment | Rule::block_comment
+            )
+        });
+        if !has_comments {
+            let arguments = list
+                .clone()
+                .into_inner()
+                .filter(|p| p.as_rule() == Rule::named_argument)
+                .map(|argument| {
+                    let mut formatter = Formatter::new(self.source);
+                    formatter.named_argument(argument);
+                    formatter.out
+                })
+                .collect::<Vec<_>>();
+            let inline = format!("({})", arguments.join(", "));
+            if inline.len() <= MAX_INLINE_WIDTH && !inline.contains('\n') {
+                self.out.push_str(&inline);
+                return;
+            }
+        }
+
+        let outer_comments = std::mem::take(&mut self.comments);
+        self.out.push('(');
+        self.indent += 1;
+        self.at_block_start = true;
+        for current in list.into_inner() {
+            match current.as_rule() {
+                Rule::openParan | Rule::closeParan => {}
+                Rule::SPACER_TEXT => {
+                    self.defer_comments(current);
+                    self.flush_comments();
+                }
+                Rule::named_argument => {
+                    self.line(self.is_after_blank_line(&current));
+                    self.named_argument(current);
+                    self.out.push(',');
+                    self.flush_comments();
+                }
+                _ => {
+                    self.line(false);
+                    self.verbatim(current);
+                }
+            }
+        }
+        self.indent -= 1;
+        self.line(false);
+        self.out.push(')');
+        self.comments = outer_comments;
+    }
+
+    /// `arg: string`
+    fn named_argument(&mut self, argument: Pair<'a>) {
+        for current in argument.into_inner() {
+            match current.as_rule() {
+                Rule::identifier => self.verbatim(current),
+                Rule::colon => self.out.push(':'),
+                _ => {
+                    self.out.push_str(": ");
+                    self.field_type(current);
+                }
+            }
+        }
+    }
+
+    /// Types are written on one line, with a single space around `|` and
+    /// before attributes.
+    fn field_type(&mut self, pair: Pair<'a>) {
+        match pair.as_rule() {
+            Rule::field_operator => self.out.push_str(" | "),
+            Rule::field_attribute => {
+                self.out.push(' ');
+                self.attribute(pair);
+            }
+            Rule::trailing_comment => self.defer_comments(pair),
+            Rule::map => {
+                self.out.push_str("map<");
+                for (i, current) in pair.into_inner().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.field_type(current);
+                }
+                self.out.push('>');
+            }
+            Rule::tuple => {
+                self.out.push('(');
+                let elements = pair
+                    .into_inner()
+                    .filter(|p| !matches!(p.as_rule(), Rule::openParan | Rule::closeParan));
+                for (i, current) in elements.enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.field_type(current);
+                }
+                self.out.push(')');
+            }
+            Rule::field_type_chain
+            | Rule::field_type_with_attr
+            | Rule::field_type
+            | Rule::union
+            | Rule::non_union
+            | Rule::base_type_with_attr
+            | Rule::base_type
+            | Rule::base_type_without_array
+            | Rule::array_notation
+            | Rule::group
+            | Rule::parenthesized_type
+            | Rule::literal_type => {
+                for current in pair.into_inner() {
+                    self.field_type(current);
+                }
+            }
+            _ => self.verbatim(pair),
+        }
+    }
+
+    /// `@alias("foo")` or `@@dynamic`
+    fn attribute(&mut self, attribute: Pair<'a>) {
+        self.out.push_str(match attribute.as_rule() {
+            Rule::block_attribute => "@@",
+            _ => "@",
+        });
+        for current in attribute.into_inner() {
+            match current.as_rule() {
+                Rule::arguments_list => {
+                    self.out.push('(');
+                    for (i, argument) in current.into_inner().enumerate() {
+                        if i > 0 {
+                            self.out.push_str(", ");
+                        }
+                        self.expression(argument);
+                    }
+                    self.out.push(')');
+                }
+                _ => self.verbatim(current),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: Pair<'a>) {
+        let Some(value) = expression.clone().into_inner().next() else {
+            return self.verbatim(expression);
+        };
+        match value.as_rule() {
+            Rule::map_expression => self.map(value),
+            Rule::array_expression => self.array(value),
+            Rule::string_literal => match value.clone().into_inner().next() {
+                Some(raw) if raw.as_rule() == Rule::raw_string_literal => self.raw_string(raw),
+                _ => self.verbatim(value),
+            },
+            _ => self.verbatim(value),
+        }
+    }
+
+    /// Raw strings keep their contents as is, but are delimited with as few
+    /// `#`s as possible.
+    fn raw_string(&mut self, raw: Pair<'_>) {
+        let mut language = "";
+        let mut content = "";
+        for current in raw.into_inner() {
+            match current.as_rule() {
+                Rule::single_word => language = current.as_str(),
+                _ => content = current.as_str(),
+            }
+        }
+        let hashes = "#".repeat(raw_string_hashes(content));
+        self.out.push_str(language);
+        self.out.push_str(&hashes);
+        self.out.push('"');
+        self.out.push_str(content);
+        self.out.push('"');
+        self.out.push_str(&hashes);
+    }
+
+    /// Maps are written with one entry per line and no commas.
+    fn map(&mut self, map: Pair<'a>) {
+        let outer_comments = std::mem::take(&mut self.comments);
+        let opened_at = self.open_block();
+        for current in map.into_inner() {
+            match current.as_rule() {
+                Rule::map_entry => self.map_entry(current),
+                Rule::comment_block => self.comment_block(current),
+                Rule::empty_lines => {}
+                _ => {
+                    self.line(false);
+                    self.verbatim(current);
+                }
+            }
+        }
+        self.close_block(opened_at);
+        self.comments = outer_comments;
+    }
+
+    fn map_entry(&mut self, entry: Pair<'a>) {
+        for current in entry.into_inner() {
+            match current.as_rule() {
+                Rule::comment_block => self.comment_block(current),
+                Rule::empty_lines => {}
+                Rule::map_key => {
+                    self.line(self.is_after_blank_line(&current));
+                    self.verbatim(current);
+                }
+                Rule::expression => {
+                    self.out.push(' ');
+                    self.expression(current);
+                }
+                Rule::trailing_comment => self.defer_comments(current),
+                _ => {
+                    self.out.push(' ');
+                    self.verbatim(current);
+                }
+            }
+        }
+        self.flush_comments();
+    }
+
+    /// Short arrays of simple values are written on one line, others with one
+    /// element per line. Commas go between elements, since a comment can't
+    /// be followed by a trailing comma.
+    fn array(&mut self, array: Pair<'a>) {
+        let is_element =
+            |p: &Pair<'_>| matches!(p.as_rule(), Rule::expression | Rule::ARRAY_CATCH_ALL);
+        let children = array.into_inner().collect::<Vec<_>>();
+
+        let has_comments = children
+            .iter()
+            .any(|p| matches!(p.as_rule(), Rule::comment_block | Rule::trailing_comment));
+        if !has_comments {
+            let elements = children
+                .iter()
+                .filter(|p| is_element(p))
+                .map(|element| {
+                    let mut formatter = Formatter::new(self.source);
+                    formatter.expression(element.clone());
+                    formatter.out
+                })
+                .collect::<Vec<_>>();
+            let inline = format!("[{}]", elements.join(", "));
+            if inline.len() <= MAX_INLINE_WIDTH && !inline.contains('\n') {
+                self.out.push_str(&inline);
+                return;
+            }
+        }
+
+        let last_element = children.iter().rposition(is_element);
+        self.out.push('[');
+        self.indent += 1;
+        self.at_block_start = true;
+        for (i, current) in children.into_iter().enumerate() {
+            match current.as_rule() {
+                Rule::expression | Rule::ARRAY_CATCH_ALL => {
+                    self.line(self.is_after_blank_line(&current));
+                    self.expression(current);
+                    if Some(i) != last_element {
+                        self.out.push(',');
+                    }
+                }
+                Rule::comment_block => self.comment_block(current),
+                Rule::trailing_comment => {
+                    for comment in current.into_inner() {
+                        self.comment(comment, false);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.indent -= 1;
+        self.line(false);
+        self.out.push(']');
+    }
+}
+
+/// The fewest `#`s that can delimit a raw string with this content.
+fn raw_string_hashes(content: &str) -> usize {
+    content
+        .split('"')
+        .skip(1)
+        .map(|after_quote| after_quote.len() - after_quote.trim_start_matches('#').len())
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(input: &str) -> SourceFile {
+        SourceFile::new_allocated(PathBuf::from("./baml_src/main.baml"), input.into())
+    }
+
+    /// Formats `input`, checking that formatting the result again changes
+    /// nothing.
+    fn format(input: &str) -> String {
+        let root_path = PathBuf::from("./baml_src");
+        let formatted = format_schema(&root_path, &source(input)).unwrap();
+        assert_eq!(
+            format_schema(&root_path, &source(&formatted)).unwrap(),
+            formatted,
+            "formatting is not idempotent"
+        );
+        formatted
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = r####"class Foo {
+    bar string   @alias("b")
+       baz   int?
+         @description(##"the baz"##)
+
+
+    qux  (string | int)[]
+    m map<string,   int>
+  @@dynamic
+}
+enum Color {
+  RED
+  GREEN @alias("g")
+}
+class Empty {
+}
+type Alias = Foo|  Color
+function Extract(text: string,   img: image) -> Foo | Color {
+    client "openai/gpt-4o"
+    prompt ###"
+      {{ _.role("user") }} "hi" {{ text }}
+    "###
+}
+"####;
+        let expected = r###"class Foo {
+  bar string @alias("b")
+  baz int? @description(#"the baz"#)
+
+  qux (string | int)[]
+  m map<string, int>
+  @@dynamic
+}
+
+enum Color {
+  RED
+  GREEN @alias("g")
+}
+
+class Empty {}
+
+type Alias = Foo | Color
+
+function Extract(text: string, img: image) -> Foo | Color {
+  client "openai/gpt-4o"
+  prompt #"
+      {{ _.role("user") }} "hi" {{ text }}
+    "#
+}
+"###;
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = r#"// The main class
+class Foo {   // opening
+  /// Docs for bar
+  bar string // bar
+    @alias("b")
+  {// a block
+     comment //}
+  baz int
+}  // closing
+
+
+// Free floating
+"#;
+        let expected = r#"// The main class
+class Foo { // opening
+  /// Docs for bar
+  bar string @alias("b") // bar
+  {// a block
+     comment //}
+  baz int
+} // closing
+
+// Free floating
+"#;
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_maps_and_arrays() {
+        let input = r#"test Foo {
+  functions [Extract,Other]
+  args {
+    text "hello", count 2
+
+    options { nested true }
+    items [
+      "a", // first
+      "b"
+    ]
+  }
+}
+"#;
+        let expected = r#"test Foo {
+  functions [Extract, Other]
+  args {
+    text "hello"
+    count 2
+
+    options {
+      nested true
+    }
+    items [
+      "a", // first
+      "b"
+    ]
+  }
+}
+"#;
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_long_argument_lists() {
+        let input = r##"function Foo(first_argument: string, second_argument: int, third_argument: float, fourth_argument: bool) -> string {
+  client "openai/gpt-4o"
+  prompt #"{{ first_argument }}"#
+}
+
+function Bar(
+  a: string, // the a
+  b: int
+) -> string {
+  client "openai/gpt-4o"
+  prompt #"{{ a }}"#
+}
+"##;
+        let expected = r##"function Foo(
+  first_argument: string,
+  second_argument: int,
+  third_argument: float,
+  fourth_argument: bool,
+) -> string {
+  client "openai/gpt-4o"
+  prompt #"{{ first_argument }}"#
+}
+
+function Bar(
+  a: string, // the a
+  b: int,
+) -> string {
+  client "openai/gpt-4o"
+  prompt #"{{ a }}"#
+}
+"##;
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_raw_string_hashes() {
+        assert_eq!(raw_string_hashes("no quotes"), 1);
+        assert_eq!(raw_string_hashes(r#"a "quote""#), 1);
+        assert_eq!(raw_string_hashes(r##"a "# inside"##), 2);
+    }
+
+    #[test]
+    fn test_syntax_errors_are_not_formatted() {
+        let root_path = PathBuf::from("./baml_src");
+        assert!(format_schema(&root_path, &source("class Foo {\n  bar: string\n}\n")).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use super::{
+    helpers::{parsing_catch_all, Pair},
+    parse_comments::*,
+    parse_field::parse_field_type_with_attr,
+    parse_identifier::parse_identifier,
+    Rule,
+};
+use crate::{assert_correct_parser, ast::*};
+use internal_baml_diagnostics::{DatamodelError, Diagnostics};
+
+/// Parses a type alias declaration such as `type Foo = int | string`.
+pub(crate) fn parse_assignment(
+    pair: Pair<'_>,
+    doc_comment: Option<Pair<'_>>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Assignment, DatamodelError> {
+    assert_correct_parser!(pair, Rule::type_alias);
+
+    let pair_span = pair.as_span();
+    let mut identifier: Option<Identifier> = None;
+    let mut value: Option<FieldType> = None;
+
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::TYPE_KEYWORD | Rule::assignment => {}
+            Rule::identifier => identifier = Some(parse_identifier(current, diagnostics)),
+            Rule::field_type_with_attr => {
+                value = parse_field_type_with_attr(current, false, diagnostics)
+            }
+            _ => parsing_catch_all(current, "type_alias"),
+        }
+    }
+
+    match (identifier, value) {
+        (Some(identifier), Some(value)) => Ok(Assignment {
+            identifier,
+            value,
+            documentation: doc_comment.and_then(parse_comment_block),
+            span: diagnostics.span(pair_span),
+        }),
+        (identifier, _) => Err(DatamodelError::new_model_validation_error(
+            "Invalid type alias syntax. Valid syntax is `type MyAlias = int | string`.",
+            "type_alias",
+            identifier.as_ref().map_or("<unknown>", |i| i.name()),
+            diagnostics.span(pair_span),
+        )),
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use super::{
-    parse_template_string::parse_template_string,
+    parse_assignment::parse_assignment, parse_template_string::parse_template_string,
     parse_type_expression_block::parse_type_expression_block,
     parse_value_expression_block::parse_value_expression_block, BAMLParser, Rule,
 };
@@ -105,6 +105,19 @@ pub fn parse_schema(
                         }
                     }
 
+                    Rule::type_alias => {
+                        match parse_assignment(
+                            current,
+                            pending_block_comment.take(),
+                            &mut diagnostics,
+                        ) {
+                            Ok(assignment) => {
+                                top_level_definitions.push(Top::TypeAlias(assignment))
+                            }
+                            Err(e) => diagnostics.push_error(e),
+                        }
+                    }
+
                     Rule::EOI => {}
                     Rule::CATCH_ALL => {
                         diagnostics.push_error(DatamodelError::new_validation_error(
@@ -118,9 +131,15 @@ pub fn parse_schema(
                             Some(Rule::empty_lines) => {
                                 // free floating
                             }
-                            // Some(Rule::enum_declaration) => {
-                            //     pending_block_comment = Some(current);
-                            // }
+                            // Doc comments directly above a declaration.
+                            Some(
+                                Rule::type_expression_block
+                                | Rule::value_expression_block
+                                | Rule::template_declaration
+                                | Rule::type_alias,
+                            ) => {
+                                pending_block_comment = Some(current);
+                            }
                             _ => (),
                         }
                     }
@@ -205,6 +224,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_doc_comments() {
+        let input = r#"
+            /// A person.
+            /// Second line.
+            class Person {
+                /// Their full name.
+                name string
+            }
+
+            // Not a doc comment.
+            /// Free floating.
+
+            enum Color {
+                Red /// The color red.
+            }
+        "#;
+
+        let root_path = "test_file.baml";
+        let source = SourceFile::new_static(root_path.into(), input);
+
+        let (schema_ast, _) = parse_schema(&root_path.into(), &source).unwrap();
+
+        match &schema_ast.tops[..] {
+            [Top::Class(class), Top::Enum(r#enum)] => {
+                assert_eq!(class.documentation(), Some("A person.\nSecond line."));
+                assert_eq!(class.fields[0].documentation(), Some("Their full name."));
+                assert_eq!(r#enum.documentation(), None);
+                assert_eq!(r#enum.fields[0].documentation(), Some("The color red."));
+            }
+            _ => panic!("Expected a class and an enum"),
+        }
+    }
+
     #[test]
     fn test_example() {
         let input = r##"
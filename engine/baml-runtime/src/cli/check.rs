@@ -0,0 +1,317 @@
+use anyhow::{Context, Result};
+use internal_baml_core::{
+    internal_baml_diagnostics::{
+        DatamodelError, DatamodelWarning, DiagnosticCode, Diagnostics, Fix, RelatedSpan, Severity,
+        SourceFile, Span,
+    },
+    validate,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::path::PathBuf;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckFormat {
+    /// The same output as `baml-cli generate`.
+    Text,
+    Json,
+    /// SARIF 2.1.0, for code scanning tools like GitHub's.
+    Sarif,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(long, value_enum, default_value = "text")]
+    format: CheckFormat,
+}
+
+impl CheckArgs {
+    pub fn run(&self) -> Result<()> {
+        let files = crate::baml_src_files(&self.from)?
+            .into_iter()
+            .map(|path| {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                Ok(SourceFile::from((path, contents)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let diagnostics = validate(&self.from, files).diagnostics;
+
+        match self.format {
+            CheckFormat::Text => {
+                eprint!("{}", diagnostics.warnings_to_pretty_string());
+                eprint!("{}", diagnostics.to_pretty_string());
+            }
+            CheckFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&to_json(&diagnostics))?)
+            }
+            CheckFormat::Sarif => {
+                println!("{}", serde_json::to_string_pretty(&to_sarif(&diagnostics))?)
+            }
+        }
+
+        match (diagnostics.errors().len(), diagnostics.warnings().len()) {
+            (0, 0) => {
+                log::info!("No issues found in {}", self.from.display());
+                Ok(())
+            }
+            (0, warnings) => {
+                log::warn!("{warnings} warning(s) found in {}", self.from.display());
+                Ok(())
+            }
+            (errors, warnings) => anyhow::bail!(
+                "{errors} error(s) and {warnings} warning(s) found in {}",
+                self.from.display()
+            ),
+        }
+    }
+}
+
+/// The parts of an error or warning that are shared by both output formats.
+struct Diagnostic<'a> {
+    code: DiagnosticCode,
+    severity: Severity,
+    message: &'a str,
+    span: &'a Span,
+    related: &'a [RelatedSpan],
+    fixes: &'a [Fix],
+}
+
+impl<'a> From<&'a DatamodelError> for Diagnostic<'a> {
+    fn from(e: &'a DatamodelError) -> Self {
+        Diagnostic {
+            code: e.code(),
+            severity: e.severity(),
+            message: e.message(),
+            span: e.span(),
+            related: e.related(),
+            fixes: e.fixes(),
+        }
+    }
+}
+
+impl<'a> From<&'a DatamodelWarning> for Diagnostic<'a> {
+    fn from(w: &'a DatamodelWarning) -> Self {
+        Diagnostic {
+            code: w.code(),
+            severity: w.severity(),
+            message: w.message(),
+            span: w.span(),
+            related: w.related(),
+            fixes: w.fixes(),
+        }
+    }
+}
+
+fn all(diagnostics: &Diagnostics) -> impl Iterator<Item = Diagnostic<'_>> {
+    diagnostics
+        .errors()
+        .iter()
+        .map(Diagnostic::from)
+        .chain(diagnostics.warnings().iter().map(Diagnostic::from))
+}
+
+/// 1-based, like the line numbers in the text output.
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+struct Location {
+    file: String,
+    start: Position,
+    end: Position,
+}
+
+impl From<&Span> for Location {
+    fn from(span: &Span) -> Self {
+        let ((start_line, start_column), (end_line, end_column)) = span.line_and_column();
+        Location {
+            file: span.file.path(),
+            start: Position {
+                line: start_line + 1,
+                column: start_column + 1,
+            },
+            end: Position {
+                line: end_line + 1,
+                column: end_column + 1,
+            },
+        }
+    }
+}
+
+fn to_json(diagnostics: &Diagnostics) -> serde_json::Value {
+    let diagnostics = all(diagnostics)
+        .map(|d| {
+            json!({
+                "code": d.code.code(),
+                "name": d.code.name(),
+                "severity": d.severity.as_str(),
+                "message": d.message,
+                "location": Location::from(d.span),
+                "related": d.related.iter().map(|r| json!({
+                    "message": r.message,
+                    "location": Location::from(&r.span),
+                })).collect::<Vec<_>>(),
+                "fixes": d.fixes.iter().map(|f| json!({
+                    "message": f.message,
+                    "edits": f.edits.iter().map(|e| json!({
+                        "location": Location::from(&e.span),
+                        "new_text": e.new_text,
+                    })).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "diagnostics": diagnostics })
+}
+
+fn sarif_location(span: &Span) -> serde_json::Value {
+    let location = Location::from(span);
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": sarif_uri(&location.file) },
+            "region": sarif_region(&location),
+        }
+    })
+}
+
+fn sarif_region(location: &Location) -> serde_json::Value {
+    json!({
+        "startLine": location.start.line,
+        "startColumn": location.start.column,
+        "endLine": location.end.line,
+        "endColumn": location.end.column,
+    })
+}
+
+fn sarif_uri(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+fn to_sarif(diagnostics: &Diagnostics) -> serde_json::Value {
+    let rules = DiagnosticCode::ALL
+        .iter()
+        .map(|code| {
+            json!({
+                "id": code.code(),
+                "name": code.name(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = all(diagnostics)
+        .map(|d| {
+            let related = d
+                .related
+                .iter()
+                .enumerate()
+                .map(|(id, r)| {
+                    let mut location = sarif_location(&r.span);
+                    location["id"] = json!(id);
+                    location["message"] = json!({ "text": r.message });
+                    location
+                })
+                .collect::<Vec<_>>();
+            let fixes = d
+                .fixes
+                .iter()
+                .map(|f| {
+                    json!({
+                        "description": { "text": f.message },
+                        "artifactChanges": f.edits.iter().map(|e| {
+                            let location = Location::from(&e.span);
+                            json!({
+                                "artifactLocation": { "uri": sarif_uri(&location.file) },
+                                "replacements": [{
+                                    "deletedRegion": sarif_region(&location),
+                                    "insertedContent": { "text": e.new_text },
+                                }],
+                            })
+                        }).collect::<Vec<_>>(),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            json!({
+                "ruleId": d.code.code(),
+                "ruleIndex": DiagnosticCode::ALL.iter().position(|c| *c == d.code),
+                "level": d.severity.as_str(),
+                "message": { "text": d.message },
+                "locations": [sarif_location(d.span)],
+                "relatedLocations": related,
+                "fixes": fixes,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "baml-cli",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://docs.boundaryml.com",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(source: &str) -> Diagnostics {
+        let file = SourceFile::from((PathBuf::from("baml_src/main.baml"), source.to_string()));
+        validate(&PathBuf::from("baml_src"), vec![file]).diagnostics
+    }
+
+    #[test]
+    fn test_json() {
+        let diagnostics = check("class Foo {\n  bar Strng\n}\n");
+        let json = to_json(&diagnostics);
+        let diagnostic = &json["diagnostics"][0];
+
+        assert_eq!(diagnostic["code"], "E0012");
+        assert_eq!(diagnostic["name"], "unknown-type");
+        assert_eq!(diagnostic["severity"], "error");
+        assert_eq!(
+            diagnostic["location"],
+            json!({
+                "file": "baml_src/main.baml",
+                "start": {"line": 2, "column": 7},
+                "end": {"line": 2, "column": 12},
+            })
+        );
+        let fix = &diagnostic["fixes"][0];
+        assert_eq!(fix["message"], "Replace with `string`");
+        assert_eq!(fix["edits"][0]["new_text"], "string");
+    }
+
+    #[test]
+    fn test_sarif_related_locations() {
+        let diagnostics = check("class Foo {\n  bar string\n}\n\nclass Foo {\n  baz int\n}\n");
+        let sarif = to_sarif(&diagnostics);
+        let result = &sarif["runs"][0]["results"][0];
+
+        assert_eq!(result["ruleId"], "E0010");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            5
+        );
+        assert_eq!(
+            result["relatedLocations"][0]["physicalLocation"]["region"]["startLine"],
+            1
+        );
+    }
+}
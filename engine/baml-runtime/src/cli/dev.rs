@@ -1,7 +1,7 @@
 use anyhow::Result;
 use notify_debouncer_full::{new_debouncer, notify::*};
-use std::ops::DerefMut;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::{cli::generate::GenerateArgs, BamlRuntime};
@@ -111,7 +111,7 @@ Thanks for trying out BAML!
                         );
                         let start = Instant::now();
                         match BamlRuntime::from_directory(&self.from, std::env::vars().collect()) {
-                            Ok(mut new_runtime) => {
+                            Ok(new_runtime) => {
                                 let elapsed = start.elapsed();
                                 let _ = GenerateArgs {
                                     from: self.from.clone(),
@@ -119,10 +119,7 @@ Thanks for trying out BAML!
                                 }
                                 .run(defaults);
 
-                                std::mem::swap(
-                                    server.b.write().await.deref_mut(),
-                                    &mut new_runtime,
-                                );
+                                *server.b.write().await = Arc::new(new_runtime);
                                 log::info!(
                                     "Reloaded runtime in {}ms ({})",
                                     elapsed.as_millis(),
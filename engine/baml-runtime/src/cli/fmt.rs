@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use internal_baml_core::{
+    internal_baml_diagnostics::SourceFile, internal_baml_schema_ast::format_schema,
+};
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug)]
+pub struct FmtArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(
+        long,
+        help = "List the files that are not formatted instead of formatting them, and fail if there are any"
+    )]
+    check: bool,
+}
+
+impl FmtArgs {
+    pub fn run(&self) -> Result<()> {
+        let mut unformatted = vec![];
+        let mut failed = 0;
+
+        let files = crate::baml_src_files(&self.from)?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "baml"));
+        for path in files {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let source = SourceFile::from((path.clone(), contents.as_str()));
+
+            let formatted = match format_schema(&self.from, &source) {
+                Ok(formatted) => formatted,
+                Err(diagnostics) => {
+                    eprint!("{}", diagnostics.to_pretty_string());
+                    failed += 1;
+                    continue;
+                }
+            };
+            if formatted == contents {
+                continue;
+            }
+
+            if self.check {
+                println!("{}", path.display());
+            } else {
+                std::fs::write(&path, formatted)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                log::info!("Formatted {}", path.display());
+            }
+            unformatted.push(path);
+        }
+
+        if failed > 0 {
+            anyhow::bail!("{failed} file(s) could not be formatted because of syntax errors");
+        }
+        if self.check && !unformatted.is_empty() {
+            anyhow::bail!("{} file(s) are not formatted", unformatted.len());
+        }
+        Ok(())
+    }
+}
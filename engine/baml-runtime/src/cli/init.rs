@@ -87,6 +87,7 @@ impl InitArgs {
                 GeneratorOutputType::PythonPydantic => "Python clients".to_string(),
                 GeneratorOutputType::Typescript => "TypeScript clients".to_string(),
                 GeneratorOutputType::RubySorbet => "Ruby clients".to_string(),
+                GeneratorOutputType::Rust => "Rust clients".to_string(),
                 GeneratorOutputType::OpenApi => match &self.openapi_client_type {
                     Some(s) => format!("{} clients via OpenAPI", s),
                     None => "REST clients".to_string(),
@@ -99,6 +100,7 @@ impl InitArgs {
                 GeneratorOutputType::PythonPydantic => "python",
                 GeneratorOutputType::Typescript => "typescript",
                 GeneratorOutputType::RubySorbet => "ruby",
+                GeneratorOutputType::Rust => "rust",
                 GeneratorOutputType::OpenApi => "openapi",
             }
         );
@@ -114,7 +116,9 @@ fn generate_main_baml_content(
 ) -> String {
     let default_client_mode = match output_type {
         GeneratorOutputType::OpenApi | GeneratorOutputType::RubySorbet => "".to_string(),
-        GeneratorOutputType::PythonPydantic | GeneratorOutputType::Typescript => format!(
+        GeneratorOutputType::PythonPydantic
+        | GeneratorOutputType::Typescript
+        | GeneratorOutputType::Rust => format!(
             r#"
     // Valid values: "sync", "async"
     // This controls what `b.FunctionName()` will be (sync or async).
@@ -183,7 +187,7 @@ fn generate_main_baml_content(
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "{output_type}"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -221,7 +225,7 @@ mod tests {
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "python/pydantic"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -250,7 +254,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "typescript"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -279,7 +283,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "ruby/sorbet"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -295,6 +299,35 @@ generator target {{
         );
     }
 
+    #[test]
+    fn test_generate_content_rust() {
+        assert_eq!(
+            generate_main_baml_content(GeneratorOutputType::Rust, None, None),
+            format!(r#"
+// This helps use auto generate libraries you can use in the language of
+// your choice. You can have multiple generators if you use multiple languages.
+// Just ensure that the output_dir is different for each generator.
+generator target {{
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
+    output_type "rust"
+
+    // Where the generated code will be saved (relative to baml_src/)
+    output_dir "../"
+
+    // The version of the BAML package you have installed (e.g. same version as your baml-py or @boundaryml/baml).
+    // The BAML VSCode extension version should also match this version.
+    version "{}"
+
+    // Valid values: "sync", "async"
+    // This controls what `b.FunctionName()` will be (sync or async).
+    default_client_mode async
+}}
+"#,
+                env!("CARGO_PKG_VERSION")
+            ).trim_start()
+        );
+    }
+
     #[test]
     fn test_generate_content_openapi_go() {
         assert_eq!(
@@ -304,7 +337,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -333,7 +366,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -362,7 +395,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rust", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -0,0 +1,273 @@
+//! Completions for attribute names and client options.
+//!
+//! These work on the text rather than the AST, since the document usually
+//! doesn't parse while something is being typed.
+
+use lsp_types::{CompletionItem, CompletionItemKind};
+
+/// Attributes on a field or enum value, e.g. `name string @alias("full_name")`.
+const FIELD_ATTRIBUTES: &[(&str, &str)] = &[
+    ("alias", "The name the LLM sees instead of this one"),
+    ("description", "Extra context for the LLM in the prompt"),
+    (
+        "skip",
+        "Leaves this out of the prompt and the parsed output",
+    ),
+    (
+        "check",
+        "A named condition on the value, reported without failing",
+    ),
+    ("assert", "A condition the value must satisfy"),
+    ("default", "The value used when the LLM leaves this field out"),
+    (
+        "stream.done",
+        "Streams this field in only once it's complete",
+    ),
+    (
+        "stream.not_null",
+        "Holds back the object while streaming until this field has a value",
+    ),
+    (
+        "stream.with_state",
+        "Streams this field along with whether it's complete yet",
+    ),
+];
+
+/// Attributes on a class or enum, e.g. `@@dynamic`.
+const BLOCK_ATTRIBUTES: &[(&str, &str)] = &[
+    ("alias", "The name the LLM sees instead of this one"),
+    ("description", "Extra context for the LLM in the prompt"),
+    ("dynamic", "Lets a TypeBuilder add to this at runtime"),
+    (
+        "check",
+        "A named condition on the value, reported without failing",
+    ),
+    ("assert", "A condition the value must satisfy"),
+    (
+        "stream.done",
+        "Streams this class in only once it's complete",
+    ),
+];
+
+/// Options that every LLM provider understands.
+const COMMON_CLIENT_OPTIONS: &[&str] = &[
+    "default_role",
+    "allowed_role_metadata",
+    "supports_streaming",
+    "output_mode",
+    "request_timeout_ms",
+    "idle_timeout_ms",
+];
+
+fn client_options(provider: &str) -> Vec<&'static str> {
+    let provider_options: &[&str] = match provider.trim_start_matches("baml-") {
+        "openai" | "openai-chat" | "openai-generic" | "ollama" | "ollama-chat" => {
+            &["api_key", "base_url", "model", "headers"]
+        }
+        "azure-openai" | "azure-chat" => &[
+            "resource_name",
+            "deployment_id",
+            "api_version",
+            "api_key",
+            "base_url",
+            "headers",
+        ],
+        "anthropic" | "anthropic-chat" | "google-ai" => {
+            &["api_key", "base_url", "model", "headers"]
+        }
+        "vertex-ai" => &[
+            "project_id",
+            "location",
+            "model",
+            "credentials",
+            "credentials_content",
+            "authorization",
+            "base_url",
+            "headers",
+        ],
+        "aws-bedrock" => &["model", "region", "inference_configuration"],
+        // Strategies don't call an LLM themselves.
+        "fallback" => return vec!["strategy", "retry_on_parse_failure"],
+        "round-robin" => return vec!["strategy", "start", "retry_on_parse_failure"],
+        _ => &[],
+    };
+    provider_options
+        .iter()
+        .chain(COMMON_CLIENT_OPTIONS)
+        .copied()
+        .collect()
+}
+
+pub(super) fn completions(text: &str, offset: usize) -> Vec<CompletionItem> {
+    let before = &text[..offset];
+    // `.` is part of attribute names like `@stream.done`.
+    let word = before.trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+
+    if word.ends_with("@@") {
+        return attribute_items(BLOCK_ATTRIBUTES);
+    }
+    if word.ends_with('@') {
+        return attribute_items(FIELD_ATTRIBUTES);
+    }
+
+    // Option names only go at the start of a line.
+    let line = &word[word.rfind('\n').map_or(0, |i| i + 1)..];
+    if !line.trim().is_empty() {
+        return vec![];
+    }
+    let blocks = open_blocks(before);
+    let [.., client, options] = blocks.as_slice() else {
+        return vec![];
+    };
+    if header(text, *options) != "options" || !header(text, *client).starts_with("client") {
+        return vec![];
+    }
+    let Some(provider) = block_lines(text, *client)
+        .into_iter()
+        .find_map(|l| l.strip_prefix("provider "))
+    else {
+        return vec![];
+    };
+
+    let present = block_lines(text, *options)
+        .into_iter()
+        .filter_map(|l| l.split_whitespace().next())
+        .collect::<Vec<_>>();
+    client_options(provider.trim().trim_matches('"'))
+        .into_iter()
+        .filter(|option| !present.contains(option))
+        .map(|option| CompletionItem {
+            label: option.to_string(),
+            kind: Some(CompletionItemKind::PROPERTY),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn attribute_items(attributes: &[(&str, &str)]) -> Vec<CompletionItem> {
+    attributes
+        .iter()
+        .map(|(name, detail)| CompletionItem {
+            label: name.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some(detail.to_string()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// What comes before an opening brace on its line, e.g. `client<llm> Foo`.
+fn header(text: &str, brace: usize) -> &str {
+    let line_start = text[..brace].rfind('\n').map_or(0, |i| i + 1);
+    text[line_start..brace].trim()
+}
+
+/// The trimmed lines directly inside the block opened at `brace`, skipping
+/// the ones in nested blocks.
+fn block_lines(text: &str, brace: usize) -> Vec<&str> {
+    let mut depth = 0;
+    text[brace..]
+        .lines()
+        .skip(1)
+        .take_while(move |line| {
+            depth += line.matches('{').count() as i32;
+            depth -= line.matches('}').count() as i32;
+            depth >= 0
+        })
+        .map(str::trim)
+        .collect()
+}
+
+/// The offsets of the braces that are still open at the end of `text`,
+/// outermost first, ignoring braces in strings and comments.
+fn open_blocks(text: &str) -> Vec<usize> {
+    let mut blocks = vec![];
+    let mut rest = text.char_indices().peekable();
+    while let Some((i, c)) = rest.next() {
+        match c {
+            '{' => blocks.push(i),
+            '}' => {
+                blocks.pop();
+            }
+            '/' if rest.peek().map(|(_, c)| *c) == Some('/') => {
+                rest.by_ref().find(|(_, c)| *c == '\n');
+            }
+            '"' => {
+                rest.by_ref().find(|(_, c)| *c == '"' || *c == '\n');
+            }
+            '#' => {
+                // Raw strings, e.g. `#"..."#` or `##"..."##`, may contain
+                // braces for Jinja.
+                let hashes = 1 + text[i + 1..].chars().take_while(|c| *c == '#').count();
+                if text[i + hashes..].starts_with('"') {
+                    let close = format!("\"{}", "#".repeat(hashes));
+                    let Some(end) = text[i + hashes + 1..].find(&close) else {
+                        break;
+                    };
+                    let end = i + hashes + 1 + end + close.len();
+                    while rest.peek().is_some_and(|(j, _)| *j < end) {
+                        rest.next();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(text: &str) -> Vec<String> {
+        let offset = text.find('|').unwrap();
+        let text = text.replace('|', "");
+        completions(&text, offset)
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_attributes() {
+        assert_eq!(
+            labels("class Foo {\n  bar string @al|\n}"),
+            vec![
+                "alias",
+                "description",
+                "skip",
+                "check",
+                "assert",
+                "default",
+                "stream.done",
+                "stream.not_null",
+                "stream.with_state"
+            ]
+        );
+        assert_eq!(labels("class Foo {\n  bar string @stream.|\n}").len(), 9);
+        assert!(labels("class Foo {\n  bar string\n  @@|\n}").contains(&"dynamic".to_string()));
+    }
+
+    #[test]
+    fn test_client_options() {
+        let text = "client<llm> Foo {\n  provider anthropic\n  options {\n    model \"claude\"\n    |\n  }\n}";
+        let labels = labels(text);
+        assert!(labels.contains(&"api_key".to_string()));
+        assert!(labels.contains(&"request_timeout_ms".to_string()));
+        assert!(!labels.contains(&"model".to_string()));
+
+        let text = "client<llm> Foo {\n  provider round-robin\n  options {\n    |\n  }\n}";
+        assert_eq!(
+            self::labels(text),
+            vec!["strategy", "start", "retry_on_parse_failure"]
+        );
+    }
+
+    #[test]
+    fn test_no_options_outside_clients() {
+        assert!(labels("function Foo() -> string {\n  |\n}").is_empty());
+        let text = "function Foo() -> string {\n  prompt #\"\n    {{ options {\n  \"#\n}\n\nclass Bar {\n  options {\n    |\n  }\n}";
+        assert!(labels(text).is_empty());
+    }
+}
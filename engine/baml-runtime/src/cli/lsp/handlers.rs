@@ -0,0 +1,372 @@
+//! One function per LSP request, all reading from the latest [`Snapshot`].
+//!
+//! [`Snapshot`]: super::project::Snapshot
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use internal_baml_core::internal_baml_diagnostics::Span;
+use lsp_types::{
+    CompletionParams, CompletionResponse, DocumentSymbolParams, DocumentSymbolResponse,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams, Location,
+    MarkupContent, MarkupKind, PrepareRenameResponse, ReferenceParams, RenameParams,
+    TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+};
+
+use super::{
+    completion::completions,
+    hover::hover as render_hover,
+    position::{position_to_offset, span_to_range},
+    project::Project,
+    symbols::{Definition, SymbolIndex},
+};
+
+pub(super) fn definition(
+    project: &Project,
+    params: GotoDefinitionParams,
+) -> Result<Option<GotoDefinitionResponse>> {
+    let definition = definition_at(project, &params.text_document_position_params);
+    Ok(definition
+        .and_then(|d| location(&d.name_span))
+        .map(GotoDefinitionResponse::Scalar))
+}
+
+pub(super) fn references(
+    project: &Project,
+    params: ReferenceParams,
+) -> Result<Option<Vec<Location>>> {
+    let Some(definition) = definition_at(project, &params.text_document_position) else {
+        return Ok(None);
+    };
+    let index = &project.snapshot().index;
+    let declaration = params
+        .context
+        .include_declaration
+        .then_some(&definition.name_span);
+    Ok(Some(
+        declaration
+            .into_iter()
+            .chain(index.references_to(definition))
+            .filter_map(location)
+            .collect(),
+    ))
+}
+
+pub(super) fn prepare_rename(
+    project: &Project,
+    params: TextDocumentPositionParams,
+) -> Result<Option<PrepareRenameResponse>> {
+    let Some(definition) = definition_at(project, &params) else {
+        return Ok(None);
+    };
+    if !definition.kind.is_renameable() {
+        anyhow::bail!("`{}` can't be renamed", definition.name);
+    }
+
+    // The range to rename is the name under the cursor, which may be a
+    // reference rather than the declaration.
+    let offset = offset(project, &params).map(|(_, offset)| offset);
+    let index = &project.snapshot().index;
+    let span = std::iter::once(&definition.name_span)
+        .chain(index.references_to(definition))
+        .find(|span| offset.is_some_and(|offset| span.contains(offset)))
+        .unwrap_or(&definition.name_span);
+    Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
+        range: span_to_range(span),
+        placeholder: definition.name.clone(),
+    }))
+}
+
+pub(super) fn rename(project: &Project, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+    let Some(definition) = definition_at(project, &params.text_document_position) else {
+        return Ok(None);
+    };
+    if !definition.kind.is_renameable() {
+        anyhow::bail!("`{}` can't be renamed", definition.name);
+    }
+
+    let new_name = params.new_name.as_str();
+    let is_identifier = new_name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && new_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        anyhow::bail!("`{new_name}` is not a valid name");
+    }
+    let index = &project.snapshot().index;
+    if new_name != definition.name && index.find(new_name).is_some() {
+        anyhow::bail!("`{new_name}` is already declared");
+    }
+
+    let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+    for span in std::iter::once(&definition.name_span).chain(index.references_to(definition)) {
+        if let Some(location) = location(span) {
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(TextEdit::new(location.range, new_name.to_string()));
+        }
+    }
+    Ok(Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }))
+}
+
+pub(super) fn hover(project: &Project, params: HoverParams) -> Result<Option<Hover>> {
+    let Some(definition) = definition_at(project, &params.text_document_position_params) else {
+        return Ok(None);
+    };
+    Ok(Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: render_hover(project.snapshot(), definition),
+        }),
+        range: None,
+    }))
+}
+
+pub(super) fn completion(
+    project: &Project,
+    params: CompletionParams,
+) -> Result<Option<CompletionResponse>> {
+    let position = &params.text_document_position;
+    let Some((path, offset)) = offset(project, position) else {
+        return Ok(None);
+    };
+    let Some(text) = project.text(&path) else {
+        return Ok(None);
+    };
+    let items = completions(text, offset);
+    Ok((!items.is_empty()).then_some(CompletionResponse::Array(items)))
+}
+
+pub(super) fn document_symbols(
+    project: &Project,
+    params: DocumentSymbolParams,
+) -> Result<Option<DocumentSymbolResponse>> {
+    let Some(path) = project.path(&params.text_document.uri) else {
+        return Ok(None);
+    };
+    let symbols = SymbolIndex::document_symbols(project.snapshot().db.ast(), &path);
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+fn offset(project: &Project, params: &TextDocumentPositionParams) -> Option<(PathBuf, usize)> {
+    let path = project.path(&params.text_document.uri)?;
+    let offset = position_to_offset(project.text(&path)?, params.position);
+    Some((path, offset))
+}
+
+fn definition_at<'a>(
+    project: &'a Project,
+    params: &TextDocumentPositionParams,
+) -> Option<&'a Definition> {
+    let (path, offset) = offset(project, params)?;
+    project.snapshot().index.definition_at(&path, offset)
+}
+
+fn location(span: &Span) -> Option<Location> {
+    Some(Location::new(
+        Url::from_file_path(span.file.path_buf()).ok()?,
+        span_to_range(span),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{ReferenceContext, TextDocumentIdentifier, WorkDoneProgressParams};
+
+    const MAIN: &str = r##"
+type Skills = string[]
+
+class Resume {
+  name string
+  skills Skills
+  /// How senior the candidate is.
+  level Level?
+}
+
+enum Level {
+  JUNIOR
+  SENIOR
+}
+
+client<llm> GPT4 {
+  provider openai
+  options {
+    model gpt-4o
+    api_key env.OPENAI_API_KEY
+  }
+}
+
+function ExtractResume(resume: string) -> Resume {
+  client GPT4
+  prompt #"
+    {{ resume }}
+    {{ ctx.output_format }}
+  "#
+}
+"##;
+
+    const TESTS: &str = r##"
+test ExtractResumeTest {
+  functions [ExtractResume]
+  args {
+    resume "Ada"
+  }
+}
+"##;
+
+    fn project() -> (Project, Url, Url) {
+        let root = std::env::temp_dir().join("baml_lsp_test").join("baml_src");
+        let main = root.join("main.baml");
+        let tests = root.join("tests.baml");
+        let project = Project::new(
+            root,
+            HashMap::from([
+                (main.clone(), MAIN.to_string()),
+                (tests.clone(), TESTS.to_string()),
+            ]),
+        );
+        let main = Url::from_file_path(main).unwrap();
+        let tests = Url::from_file_path(tests).unwrap();
+        (project, main, tests)
+    }
+
+    fn at(uri: &Url, text: &str, needle: &str) -> TextDocumentPositionParams {
+        let offset = text.find(needle).unwrap();
+        TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            position: super::super::position::offset_to_position(text, offset),
+        }
+    }
+
+    #[test]
+    fn test_diagnostics_are_valid() {
+        let (mut project, ..) = project();
+        assert!(!project.snapshot().diagnostics.has_errors());
+        assert!(project.publish_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_definition_across_files() {
+        let (project, main, tests) = project();
+        let response = definition(
+            &project,
+            GotoDefinitionParams {
+                text_document_position_params: at(&tests, TESTS, "ExtractResume]"),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let Some(GotoDefinitionResponse::Scalar(location)) = response else {
+            panic!("expected a single location, got {response:?}");
+        };
+        assert_eq!(location.uri, main);
+        assert_eq!(
+            location.range.start,
+            at(&main, MAIN, "ExtractResume(").position
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let (project, main, _) = project();
+        let locations = references(
+            &project,
+            ReferenceParams {
+                text_document_position: at(&main, MAIN, "Level {"),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: Default::default(),
+                context: ReferenceContext {
+                    include_declaration: true,
+                },
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let starts = locations.iter().map(|l| l.range.start).collect::<Vec<_>>();
+        assert_eq!(
+            starts,
+            vec![
+                at(&main, MAIN, "Level {").position,
+                at(&main, MAIN, "Level?").position
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        let (project, main, tests) = project();
+        let params = |new_name: &str| RenameParams {
+            text_document_position: at(&main, MAIN, "ExtractResume("),
+            new_name: new_name.to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        let edit = rename(&project, params("ParseResume")).unwrap().unwrap();
+        let changes = edit.changes.unwrap();
+        assert_eq!(changes[&main].len(), 1);
+        assert_eq!(changes[&tests].len(), 1);
+        assert_eq!(changes[&tests][0].new_text, "ParseResume");
+
+        assert!(rename(&project, params("not valid")).is_err());
+        assert!(rename(&project, params("Resume")).is_err());
+    }
+
+    #[test]
+    fn test_hover_renders_ir_types() {
+        let (project, main, _) = project();
+        let hover = hover(
+            &project,
+            HoverParams {
+                text_document_position_params: at(&main, MAIN, "Resume {"),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markdown");
+        };
+        assert_eq!(
+            contents.value,
+            "```baml\nclass Resume {\n  name string\n  skills string[]\n  level Level?\n}\n```"
+        );
+    }
+
+    #[test]
+    fn test_hover_without_ir() {
+        let (mut project, main, _) = project();
+        let broken = MAIN.replace("level Level?", "level Levl?");
+        project.change(main.to_file_path().unwrap(), broken.clone());
+        assert!(project.snapshot().ir.is_none());
+
+        let hover = hover(
+            &project,
+            HoverParams {
+                text_document_position_params: at(&main, &broken, "ExtractResume("),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markdown");
+        };
+        assert_eq!(
+            contents.value,
+            "```baml\nfunction ExtractResume(resume: string) -> Resume\n```"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Hover text for a declaration.
+//!
+//! Types are rendered from the IR, so that e.g. optional fields and type
+//! aliases read the same way they do in generated code. While the project has
+//! errors there is no IR, so we show the declaration as written instead.
+
+use internal_baml_core::{
+    ast::WithName,
+    internal_baml_parser_database::ParserDatabase,
+    ir::{repr::IntermediateRepr, repr::WithRepr, IRHelper},
+};
+
+use super::{
+    project::Snapshot,
+    symbols::{Definition, SymbolKind},
+};
+
+pub(super) fn hover(snapshot: &Snapshot, definition: &Definition) -> String {
+    let rendered = snapshot
+        .ir
+        .as_ref()
+        .and_then(|ir| render(ir, &snapshot.db, definition))
+        .unwrap_or_else(|| as_written(definition));
+
+    let mut hover = format!("```baml\n{rendered}\n```");
+    if let Some(documentation) = &definition.documentation {
+        hover.push_str("\n\n");
+        hover.push_str(documentation);
+    }
+    hover
+}
+
+fn render(ir: &IntermediateRepr, db: &ParserDatabase, definition: &Definition) -> Option<String> {
+    let name = definition.name.as_str();
+    let rendered = match definition.kind {
+        SymbolKind::Class => {
+            let fields = ir
+                .find_class(name)
+                .ok()?
+                .elem()
+                .static_fields
+                .iter()
+                .map(|field| format!("  {} {}\n", field.elem.name, field.elem.r#type.elem))
+                .collect::<String>();
+            format!("class {name} {{\n{fields}}}")
+        }
+        SymbolKind::Enum => {
+            let values = ir
+                .find_enum(name)
+                .ok()?
+                .elem()
+                .values
+                .iter()
+                .map(|value| format!("  {}\n", value.elem.0))
+                .collect::<String>();
+            format!("enum {name} {{\n{values}}}")
+        }
+        SymbolKind::TypeAlias => {
+            let alias = db.walk_type_aliases().find(|alias| alias.name() == name)?;
+            format!("type {name} = {}", alias.repr(db).ok()?)
+        }
+        SymbolKind::Function => {
+            let function = ir.find_function(name).ok()?;
+            let args = function
+                .inputs()
+                .iter()
+                .map(|(arg, r#type)| format!("{arg}: {type}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("function {name}({args}) -> {}", function.output())
+        }
+        SymbolKind::TemplateString => {
+            let args = ir
+                .find_template_string(name)
+                .ok()?
+                .elem()
+                .params
+                .iter()
+                .map(|param| format!("{}: {}", param.name, param.r#type.elem))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("template_string {name}({args})")
+        }
+        SymbolKind::Client => {
+            let client = ir.find_client(name).ok()?;
+            let client = client.elem();
+            let retry_policy = client
+                .retry_policy_id
+                .as_ref()
+                .map(|policy| format!("  retry_policy {policy}\n"))
+                .unwrap_or_default();
+            format!(
+                "client<llm> {name} {{\n  provider {}\n{retry_policy}}}",
+                client.provider
+            )
+        }
+        SymbolKind::RetryPolicy | SymbolKind::Generator | SymbolKind::Test => return None,
+    };
+    Some(rendered)
+}
+
+/// The declaration's source, or just its header for blocks whose body isn't
+/// a type, like a function's prompt.
+fn as_written(definition: &Definition) -> String {
+    let span = &definition.span;
+    let source = &span.file.as_str()[span.start..span.end];
+    match definition.kind {
+        SymbolKind::Class | SymbolKind::Enum | SymbolKind::TypeAlias => source.trim().to_string(),
+        _ => {
+            let header_end = [source.find('{'), source.find('#')]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(source.len());
+            source[..header_end].trim().to_string()
+        }
+    }
+}
@@ -0,0 +1,230 @@
+mod completion;
+mod handlers;
+mod hover;
+mod position;
+mod project;
+mod symbols;
+
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, PrepareRenameRequest,
+        References, RegisterCapability, Rename, Request as _,
+    },
+    CompletionOptions, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, FileSystemWatcher, GlobPattern, HoverProviderCapability,
+    InitializeParams, OneOf, Registration, RegistrationParams, RenameOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+use std::path::PathBuf;
+
+use project::Project;
+
+#[derive(clap::Args, Debug)]
+pub struct LspArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    /// Accepted for editors that always pass it; stdio is the only transport.
+    #[arg(long, hide = true)]
+    stdio: bool,
+}
+
+impl LspArgs {
+    pub fn run(&self) -> Result<()> {
+        let project = Project::load(&self.from)?;
+
+        let (connection, io_threads) = Connection::stdio();
+        let params = connection.initialize(serde_json::to_value(capabilities())?)?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+        log::info!("Language server started for {}", self.from.display());
+
+        Server {
+            connection,
+            project,
+        }
+        .run(&params)?;
+        io_threads.join()?;
+        Ok(())
+    }
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["@".to_string()]),
+            ..Default::default()
+        }),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+struct Server {
+    connection: Connection,
+    project: Project,
+}
+
+impl Server {
+    fn run(mut self, params: &InitializeParams) -> Result<()> {
+        let watches_files = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.did_change_watched_files)
+            .and_then(|w| w.dynamic_registration)
+            .unwrap_or(false);
+        if watches_files {
+            self.watch_files()?;
+        }
+        self.publish_diagnostics()?;
+
+        let receiver = self.connection.receiver.clone();
+        for message in receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                // The only requests we send are registrations.
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let project = &self.project;
+        let Request { id, method, params } = request;
+        let result = match method.as_str() {
+            GotoDefinition::METHOD => {
+                dispatch::<GotoDefinition>(project, params, handlers::definition)
+            }
+            References::METHOD => dispatch::<References>(project, params, handlers::references),
+            PrepareRenameRequest::METHOD => {
+                dispatch::<PrepareRenameRequest>(project, params, handlers::prepare_rename)
+            }
+            Rename::METHOD => dispatch::<Rename>(project, params, handlers::rename),
+            HoverRequest::METHOD => dispatch::<HoverRequest>(project, params, handlers::hover),
+            Completion::METHOD => dispatch::<Completion>(project, params, handlers::completion),
+            DocumentSymbolRequest::METHOD => {
+                dispatch::<DocumentSymbolRequest>(project, params, handlers::document_symbols)
+            }
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request: {method}"),
+                )
+            }
+        };
+
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(e) => Response::new_err(id, ErrorCode::RequestFailed as i32, format!("{e:#}")),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        let project = &mut self.project;
+        let Notification { method, params } = notification;
+        match method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(params)?;
+                let Some(path) = project.path(&params.text_document.uri) else {
+                    return Ok(());
+                };
+                project.open(path, params.text_document.text);
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(params)?;
+                // We only ask for full syncs, so the last change is the whole document.
+                let (Some(path), Some(change)) = (
+                    project.path(&params.text_document.uri),
+                    params.content_changes.into_iter().last(),
+                ) else {
+                    return Ok(());
+                };
+                project.change(path, change.text);
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(params)?;
+                let Some(path) = project.path(&params.text_document.uri) else {
+                    return Ok(());
+                };
+                project.close(path);
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams = serde_json::from_value(params)?;
+                for change in params.changes {
+                    if let Some(path) = project.path(&change.uri) {
+                        project.reload(path);
+                    }
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.publish_diagnostics()
+    }
+
+    fn publish_diagnostics(&mut self) -> Result<()> {
+        for params in self.project.publish_diagnostics() {
+            let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Notification(notification))?;
+        }
+        Ok(())
+    }
+
+    /// Asks the editor to tell us about .baml files that change on disk, e.g.
+    /// when switching branches.
+    fn watch_files(&self) -> Result<()> {
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![FileSystemWatcher {
+                glob_pattern: GlobPattern::String("**/*.{baml,json}".to_string()),
+                kind: None,
+            }],
+        };
+        let params = RegistrationParams {
+            registrations: vec![Registration {
+                id: "baml-watched-files".to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: Some(serde_json::to_value(options)?),
+            }],
+        };
+        let request = Request::new(
+            RequestId::from("baml-watched-files".to_string()),
+            RegisterCapability::METHOD.to_string(),
+            params,
+        );
+        self.connection.sender.send(Message::Request(request))?;
+        Ok(())
+    }
+}
+
+fn dispatch<R: lsp_types::request::Request>(
+    project: &Project,
+    params: serde_json::Value,
+    handler: fn(&Project, R::Params) -> Result<R::Result>,
+) -> Result<serde_json::Value> {
+    let params = serde_json::from_value(params)?;
+    Ok(serde_json::to_value(handler(project, params)?)?)
+}
@@ -0,0 +1,77 @@
+//! Conversions between byte offsets and LSP positions.
+//!
+//! BAML spans are byte offsets into the file, while LSP positions are a line
+//! and a character offset counted in UTF-16 code units.
+
+use internal_baml_core::internal_baml_diagnostics::Span;
+use lsp_types::{Position, Range};
+
+pub(super) fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = text[..line_start].matches('\n').count();
+    let character = text[line_start..offset]
+        .chars()
+        .map(char::len_utf16)
+        .sum::<usize>();
+    Position::new(line as u32, character as u32)
+}
+
+/// Positions past the end of a line are clamped to the end of that line.
+pub(super) fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+
+    let mut character = 0;
+    for (i, c) in text[line_start..].char_indices() {
+        if c == '\n' || character >= position.character as usize {
+            return line_start + i;
+        }
+        character += c.len_utf16();
+    }
+    text.len()
+}
+
+pub(super) fn span_to_range(span: &Span) -> Range {
+    let text = span.file.as_str();
+    Range::new(
+        offset_to_position(text, span.start),
+        offset_to_position(text, span.end),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "class Foo {\n  bar string\n}\n";
+        for offset in 0..=text.len() {
+            let position = offset_to_position(text, offset);
+            assert_eq!(position_to_offset(text, position), offset);
+        }
+        assert_eq!(offset_to_position(text, 14), Position::new(1, 2));
+    }
+
+    #[test]
+    fn test_utf16() {
+        // 'é' is two bytes but one UTF-16 unit, '😀' is four bytes and two units.
+        let text = "// é😀\nclass Foo {}";
+        assert_eq!(offset_to_position(text, 9), Position::new(0, 6));
+        assert_eq!(position_to_offset(text, Position::new(0, 6)), 9);
+        assert_eq!(position_to_offset(text, Position::new(1, 6)), 16);
+    }
+
+    #[test]
+    fn test_clamps_to_line_end() {
+        let text = "ab\ncd";
+        assert_eq!(position_to_offset(text, Position::new(0, 10)), 2);
+        assert_eq!(position_to_offset(text, Position::new(5, 0)), text.len());
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use internal_baml_core::{
+    internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Diagnostics, SourceFile},
+    internal_baml_parser_database::ParserDatabase,
+    ir::repr::IntermediateRepr,
+    validate,
+};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    PublishDiagnosticsParams, Url,
+};
+
+use super::{position::span_to_range, symbols::SymbolIndex};
+
+/// The baml_src directory the server was started for, with the contents of
+/// any documents the editor has open in place of what is on disk.
+pub(super) struct Project {
+    root: PathBuf,
+    files: HashMap<PathBuf, String>,
+    open: HashSet<PathBuf>,
+    /// Files we last published diagnostics for, so they can be cleared once
+    /// they are fixed.
+    published: HashSet<PathBuf>,
+    snapshot: Snapshot,
+}
+
+/// The result of validating every file in the project.
+pub(super) struct Snapshot {
+    pub db: ParserDatabase,
+    pub diagnostics: Diagnostics,
+    /// Only available while the project has no errors.
+    pub ir: Option<IntermediateRepr>,
+    pub index: SymbolIndex,
+}
+
+impl Project {
+    pub(super) fn load(root: &Path) -> Result<Project> {
+        let root = std::fs::canonicalize(root)
+            .with_context(|| format!("Failed to resolve {}", root.display()))?;
+        let files = crate::baml_src_files(&root)?
+            .into_iter()
+            .map(|path| {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                Ok((path, contents))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(Project::new(root, files))
+    }
+
+    pub(super) fn new(root: PathBuf, files: HashMap<PathBuf, String>) -> Project {
+        let snapshot = Snapshot::new(&root, &files);
+        Project {
+            root,
+            files,
+            open: HashSet::new(),
+            published: HashSet::new(),
+            snapshot,
+        }
+    }
+
+    pub(super) fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// The path of a document in this project, or `None` for documents
+    /// outside of baml_src.
+    pub(super) fn path(&self, uri: &Url) -> Option<PathBuf> {
+        let path = uri.to_file_path().ok()?;
+        // Editors may open the file through a symlink, but unsaved files
+        // can't be canonicalized yet.
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        let is_source = path
+            .extension()
+            .is_some_and(|ext| ext == "baml" || ext == "json");
+        (is_source && path.starts_with(&self.root)).then_some(path)
+    }
+
+    pub(super) fn text(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    pub(super) fn open(&mut self, path: PathBuf, text: String) {
+        self.open.insert(path.clone());
+        self.files.insert(path, text);
+        self.revalidate();
+    }
+
+    pub(super) fn change(&mut self, path: PathBuf, text: String) {
+        self.files.insert(path, text);
+        self.revalidate();
+    }
+
+    pub(super) fn close(&mut self, path: PathBuf) {
+        self.open.remove(&path);
+        self.reload(path);
+    }
+
+    /// Picks up a change made on disk, unless the editor has the file open.
+    pub(super) fn reload(&mut self, path: PathBuf) {
+        if self.open.contains(&path) {
+            return;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => self.files.insert(path, contents),
+            Err(_) => self.files.remove(&path),
+        };
+        self.revalidate();
+    }
+
+    fn revalidate(&mut self) {
+        self.snapshot = Snapshot::new(&self.root, &self.files);
+    }
+
+    /// Diagnostics for every file that has any, plus empty ones for the files
+    /// whose diagnostics were fixed since the last call.
+    pub(super) fn publish_diagnostics(&mut self) -> Vec<PublishDiagnosticsParams> {
+        let mut by_file = HashMap::<PathBuf, Vec<Diagnostic>>::new();
+        let diagnostics = &self.snapshot.diagnostics;
+        for error in diagnostics.errors() {
+            by_file
+                .entry(error.span().file.path_buf().clone())
+                .or_default()
+                .push(error_to_lsp(error));
+        }
+        for warning in diagnostics.warnings() {
+            by_file
+                .entry(warning.span().file.path_buf().clone())
+                .or_default()
+                .push(warning_to_lsp(warning));
+        }
+
+        let fixed = self
+            .published
+            .iter()
+            .filter(|path| !by_file.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.published = by_file.keys().cloned().collect();
+
+        by_file
+            .into_iter()
+            .chain(fixed.into_iter().map(|path| (path, vec![])))
+            .filter_map(|(path, diagnostics)| {
+                Some(PublishDiagnosticsParams {
+                    uri: Url::from_file_path(&path).ok()?,
+                    diagnostics,
+                    version: None,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Snapshot {
+    fn new(root: &Path, files: &HashMap<PathBuf, String>) -> Snapshot {
+        let files = files
+            .iter()
+            .map(|(path, contents)| SourceFile::from((path.clone(), contents.clone())))
+            .collect();
+        let schema = validate(&root.to_path_buf(), files);
+
+        let ir = if schema.diagnostics.has_errors() {
+            None
+        } else {
+            IntermediateRepr::from_parser_database(&schema.db, schema.configuration).ok()
+        };
+        let index = SymbolIndex::new(schema.db.ast());
+
+        Snapshot {
+            db: schema.db,
+            diagnostics: schema.diagnostics,
+            ir,
+            index,
+        }
+    }
+}
+
+fn error_to_lsp(error: &DatamodelError) -> Diagnostic {
+    Diagnostic {
+        range: span_to_range(error.span()),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(error.code().code().to_string())),
+        source: Some("baml".to_string()),
+        message: error.message().to_string(),
+        related_information: related_information(error.related()),
+        ..Default::default()
+    }
+}
+
+fn warning_to_lsp(warning: &DatamodelWarning) -> Diagnostic {
+    Diagnostic {
+        range: span_to_range(warning.span()),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(warning.code().code().to_string())),
+        source: Some("baml".to_string()),
+        message: warning.message().to_string(),
+        related_information: related_information(warning.related()),
+        ..Default::default()
+    }
+}
+
+fn related_information(
+    related: &[internal_baml_core::internal_baml_diagnostics::RelatedSpan],
+) -> Option<Vec<DiagnosticRelatedInformation>> {
+    let related = related
+        .iter()
+        .filter_map(|r| {
+            Some(DiagnosticRelatedInformation {
+                location: Location::new(
+                    Url::from_file_path(r.span.file.path_buf()).ok()?,
+                    span_to_range(&r.span),
+                ),
+                message: r.message.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+    (!related.is_empty()).then_some(related)
+}
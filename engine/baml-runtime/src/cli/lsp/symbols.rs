@@ -0,0 +1,561 @@
+//! An index of the declarations in a BAML project and of the places that
+//! refer to them, for go-to-definition, find-references and rename.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use internal_baml_core::{
+    ast::{
+        Expression, Field, FieldType, Identifier, SchemaAst, Top, WithDocumentation,
+        WithIdentifier, WithName, WithSpan,
+    },
+    internal_baml_diagnostics::Span,
+};
+use lsp_types::{DocumentSymbol, SymbolKind as LspSymbolKind};
+
+use super::position::span_to_range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SymbolKind {
+    Class,
+    Enum,
+    TypeAlias,
+    Function,
+    Client,
+    TemplateString,
+    RetryPolicy,
+    Generator,
+    Test,
+}
+
+impl SymbolKind {
+    fn of(top: &Top) -> SymbolKind {
+        match top {
+            Top::Class(_) => SymbolKind::Class,
+            Top::Enum(_) => SymbolKind::Enum,
+            Top::TypeAlias(_) => SymbolKind::TypeAlias,
+            Top::Function(_) => SymbolKind::Function,
+            Top::Client(_) => SymbolKind::Client,
+            Top::TemplateString(_) => SymbolKind::TemplateString,
+            Top::RetryPolicy(_) => SymbolKind::RetryPolicy,
+            Top::Generator(_) => SymbolKind::Generator,
+            Top::TestCase(_) => SymbolKind::Test,
+        }
+    }
+
+    fn lsp_kind(self) -> LspSymbolKind {
+        match self {
+            SymbolKind::Class => LspSymbolKind::CLASS,
+            SymbolKind::Enum => LspSymbolKind::ENUM,
+            SymbolKind::TypeAlias => LspSymbolKind::TYPE_PARAMETER,
+            SymbolKind::Function | SymbolKind::TemplateString => LspSymbolKind::FUNCTION,
+            SymbolKind::Client | SymbolKind::RetryPolicy | SymbolKind::Generator => {
+                LspSymbolKind::OBJECT
+            }
+            SymbolKind::Test => LspSymbolKind::EVENT,
+        }
+    }
+
+    /// Whether `rename` may change the name of a declaration of this kind.
+    pub(super) fn is_renameable(self) -> bool {
+        !matches!(self, SymbolKind::Generator | SymbolKind::Test)
+    }
+}
+
+/// What a reference can point at, based on where it appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferenceKind {
+    /// A class, enum or type alias used in a type.
+    Type,
+    Client,
+    RetryPolicy,
+    Function,
+    TemplateString,
+}
+
+impl ReferenceKind {
+    fn matches(self, kind: SymbolKind) -> bool {
+        match self {
+            ReferenceKind::Type => matches!(
+                kind,
+                SymbolKind::Class | SymbolKind::Enum | SymbolKind::TypeAlias
+            ),
+            ReferenceKind::Client => kind == SymbolKind::Client,
+            ReferenceKind::RetryPolicy => kind == SymbolKind::RetryPolicy,
+            ReferenceKind::Function => kind == SymbolKind::Function,
+            ReferenceKind::TemplateString => kind == SymbolKind::TemplateString,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Definition {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Just the name, e.g. `Foo` in `class Foo { .. }`.
+    pub name_span: Span,
+    /// The whole declaration.
+    pub span: Span,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug)]
+struct Reference {
+    name: String,
+    kind: ReferenceKind,
+    /// Just the name, without the quotes of a quoted client name.
+    span: Span,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct SymbolIndex {
+    definitions: Vec<Definition>,
+    references: Vec<Reference>,
+}
+
+impl SymbolIndex {
+    pub(super) fn new(ast: &SchemaAst) -> SymbolIndex {
+        let definitions = ast
+            .tops
+            .iter()
+            .map(|top| Definition {
+                name: top.name().to_string(),
+                kind: SymbolKind::of(top),
+                name_span: top.identifier().span().clone(),
+                span: top.span().clone(),
+                documentation: documentation(top).map(str::to_string),
+            })
+            .collect::<Vec<_>>();
+
+        let template_strings = definitions
+            .iter()
+            .filter(|d| d.kind == SymbolKind::TemplateString)
+            .map(|d| d.name.as_str())
+            .collect::<HashSet<_>>();
+
+        let mut references = vec![];
+        for top in &ast.tops {
+            collect_references(top, &template_strings, &mut references);
+        }
+
+        SymbolIndex {
+            definitions,
+            references,
+        }
+    }
+
+    pub(super) fn find(&self, name: &str) -> Option<&Definition> {
+        self.definitions.iter().find(|d| d.name == name)
+    }
+
+    /// The declaration of the symbol under the cursor, whether the cursor is
+    /// on the declaration itself or on a reference to it.
+    pub(super) fn definition_at(&self, path: &Path, offset: usize) -> Option<&Definition> {
+        if let Some(definition) = self
+            .definitions
+            .iter()
+            .find(|d| in_file(&d.name_span, path) && d.name_span.contains(offset))
+        {
+            return Some(definition);
+        }
+
+        let reference = self
+            .references
+            .iter()
+            .find(|r| in_file(&r.span, path) && r.span.contains(offset))?;
+        self.definitions
+            .iter()
+            .find(|d| d.name == reference.name && reference.kind.matches(d.kind))
+    }
+
+    /// The spans of every reference to `definition`, not including the
+    /// declaration itself.
+    pub(super) fn references_to<'a>(
+        &'a self,
+        definition: &'a Definition,
+    ) -> impl Iterator<Item = &'a Span> {
+        self.references
+            .iter()
+            .filter(|r| r.name == definition.name && r.kind.matches(definition.kind))
+            .map(|r| &r.span)
+    }
+
+    /// The outline of one file, with the fields of classes and the values of
+    /// enums nested under them.
+    pub(super) fn document_symbols(ast: &SchemaAst, path: &Path) -> Vec<DocumentSymbol> {
+        ast.tops
+            .iter()
+            .filter(|top| in_file(top.span(), path))
+            .map(|top| {
+                let children = top.as_type_expression().map(|block| {
+                    let kind = match top {
+                        Top::Enum(_) => LspSymbolKind::ENUM_MEMBER,
+                        _ => LspSymbolKind::FIELD,
+                    };
+                    block
+                        .fields
+                        .iter()
+                        .map(|field| {
+                            document_symbol(
+                                field.name(),
+                                kind,
+                                field.identifier(),
+                                field.span(),
+                                None,
+                            )
+                        })
+                        .collect()
+                });
+                document_symbol(
+                    top.name(),
+                    SymbolKind::of(top).lsp_kind(),
+                    top.identifier(),
+                    top.span(),
+                    children,
+                )
+            })
+            .collect()
+    }
+}
+
+fn in_file(span: &Span, path: &Path) -> bool {
+    span.file.path_buf() == path
+}
+
+#[allow(deprecated)]
+fn document_symbol(
+    name: &str,
+    kind: LspSymbolKind,
+    identifier: &Identifier,
+    span: &Span,
+    children: Option<Vec<DocumentSymbol>>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_string(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: span_to_range(span),
+        selection_range: span_to_range(identifier.span()),
+        children,
+    }
+}
+
+fn documentation(top: &Top) -> Option<&str> {
+    match top {
+        Top::Enum(x) | Top::Class(x) => x.documentation(),
+        Top::TypeAlias(x) => x.documentation(),
+        Top::TemplateString(x) => x.documentation(),
+        Top::Function(x)
+        | Top::Client(x)
+        | Top::Generator(x)
+        | Top::TestCase(x)
+        | Top::RetryPolicy(x) => x.documentation(),
+    }
+}
+
+fn collect_references(top: &Top, template_strings: &HashSet<&str>, out: &mut Vec<Reference>) {
+    let mut types = |field_type: &FieldType| {
+        for idn in field_type.flat_idns() {
+            out.push(Reference {
+                name: idn.name().to_string(),
+                kind: ReferenceKind::Type,
+                span: idn.span().clone(),
+            });
+        }
+    };
+
+    match top {
+        Top::Class(class) => class
+            .fields
+            .iter()
+            .filter_map(|field| field.expr.as_ref())
+            .for_each(&mut types),
+        Top::Enum(_) => {}
+        Top::TypeAlias(alias) => types(&alias.value),
+        Top::TemplateString(template) => {
+            template
+                .input()
+                .into_iter()
+                .flat_map(|args| args.args.iter())
+                .for_each(|(_, arg)| types(&arg.field_type));
+            if let Expression::RawStringValue(raw) = template.value() {
+                template_string_calls(raw.span(), template_strings, out);
+            }
+        }
+        Top::Function(function) => {
+            function
+                .input()
+                .into_iter()
+                .flat_map(|args| args.args.iter())
+                .for_each(|(_, arg)| types(&arg.field_type));
+            if let Some(output) = function.output() {
+                types(&output.field_type);
+            }
+            for field in &function.fields {
+                match (field.name(), &field.expr) {
+                    ("client", Some(expr)) => {
+                        named_reference(expr, ReferenceKind::Client, out);
+                    }
+                    ("prompt", Some(Expression::RawStringValue(raw))) => {
+                        template_string_calls(raw.span(), template_strings, out);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Top::Client(client) => {
+            for field in &client.fields {
+                match (field.name(), &field.expr) {
+                    ("retry_policy", Some(expr)) => {
+                        named_reference(expr, ReferenceKind::RetryPolicy, out);
+                    }
+                    ("options", Some(Expression::Map(entries, _))) => {
+                        let strategy = entries
+                            .iter()
+                            .find(|(key, _)| key_name(key) == Some("strategy"));
+                        if let Some((_, Expression::Array(clients, _))) = strategy {
+                            for client in clients {
+                                named_reference(client, ReferenceKind::Client, out);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Top::TestCase(test) => {
+            let functions = test.fields.iter().find(|f| f.name() == "functions");
+            if let Some(Field {
+                expr: Some(Expression::Array(functions, _)),
+                ..
+            }) = functions
+            {
+                for function in functions {
+                    named_reference(function, ReferenceKind::Function, out);
+                }
+            }
+        }
+        Top::Generator(_) | Top::RetryPolicy(_) => {}
+    }
+}
+
+fn key_name(key: &Expression) -> Option<&str> {
+    match key {
+        Expression::Identifier(idn) => Some(idn.name()),
+        Expression::StringValue(value, _) => Some(value),
+        _ => None,
+    }
+}
+
+/// A reference by name, e.g. `client GPT4` or `client "GPT4"`.
+fn named_reference(expr: &Expression, kind: ReferenceKind, out: &mut Vec<Reference>) {
+    let (name, span) = match expr {
+        Expression::Identifier(idn @ Identifier::Local(..)) => (idn.name(), idn.span().clone()),
+        Expression::StringValue(value, span) => {
+            // Only point at the name inside the quotes, so that a rename keeps them.
+            let inner = span.file.as_str().get(span.start..span.end);
+            match inner {
+                Some(text) if text.len() == value.len() + 2 && text.starts_with('"') => (
+                    value.as_str(),
+                    Span::new(span.file.clone(), span.start + 1, span.end - 1),
+                ),
+                _ => return,
+            }
+        }
+        _ => return,
+    };
+    out.push(Reference {
+        name: name.to_string(),
+        kind,
+        span,
+    });
+}
+
+/// Calls to template strings inside a prompt, e.g. `{{ PrintUser(user) }}`.
+///
+/// Scans the prompt as it is written in the file, so that the spans don't
+/// depend on how the prompt is dedented.
+fn template_string_calls(span: &Span, template_strings: &HashSet<&str>, out: &mut Vec<Reference>) {
+    let Some(text) = span.file.as_str().get(span.start..span.end) else {
+        return;
+    };
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !(c.is_ascii_alphabetic() || c == '_') {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+            if !is_ident(c) {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+
+        let name = &text[start..end];
+        let is_call = text[end..].trim_start().starts_with('(');
+        let is_attribute = text[..start].ends_with('.');
+        if is_call && !is_attribute && template_strings.contains(name) {
+            out.push(Reference {
+                name: name.to_string(),
+                kind: ReferenceKind::TemplateString,
+                span: Span::new(span.file.clone(), span.start + start, span.start + end),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use internal_baml_core::{internal_baml_diagnostics::SourceFile, validate};
+    use std::path::PathBuf;
+
+    const SOURCE: &str = r##"
+class User {
+  name string
+  friends User[]
+}
+
+enum Role {
+  ADMIN
+  USER
+}
+
+template_string PrintUser(user: User) #"
+  {{ user.name }}
+"#
+
+retry_policy Retry {
+  max_retries 3
+}
+
+client<llm> GPT4 {
+  provider openai
+  retry_policy Retry
+  options {
+    model gpt-4o
+  }
+}
+
+client<llm> Backup {
+  provider fallback
+  options {
+    strategy [GPT4, "GPT4"]
+  }
+}
+
+function Greet(user: User) -> Role | User {
+  client "GPT4"
+  prompt #"
+    {{ PrintUser(user) }} {{ user.PrintUser() }}
+  "#
+}
+
+test GreetTest {
+  functions [Greet]
+  args {
+    user {
+      name "Ada"
+      friends []
+    }
+  }
+}
+"##;
+
+    fn index() -> (SymbolIndex, PathBuf) {
+        let path = PathBuf::from("baml_src/main.baml");
+        let file = SourceFile::from((path.clone(), SOURCE.to_string()));
+        let schema = validate(&PathBuf::from("baml_src"), vec![file]);
+        (SymbolIndex::new(schema.db.ast()), path)
+    }
+
+    fn offset_of(needle: &str, nth: usize) -> usize {
+        SOURCE.match_indices(needle).nth(nth).unwrap().0
+    }
+
+    fn references(index: &SymbolIndex, name: &str) -> Vec<&'static str> {
+        let definition = index.find(name).unwrap();
+        index
+            .references_to(definition)
+            .map(|span| &SOURCE[span.start - 1..span.end + 1])
+            .collect()
+    }
+
+    #[test]
+    fn test_definition_from_reference() {
+        let (index, path) = index();
+
+        let definition = index.definition_at(&path, offset_of("User[]", 0)).unwrap();
+        assert_eq!(definition.name, "User");
+        assert_eq!(definition.kind, SymbolKind::Class);
+
+        let definition = index.definition_at(&path, offset_of("GPT4\"", 1)).unwrap();
+        assert_eq!(definition.kind, SymbolKind::Client);
+
+        let definition = index.definition_at(&path, offset_of("Retry\n", 0)).unwrap();
+        assert_eq!(definition.kind, SymbolKind::RetryPolicy);
+
+        let definition = index.definition_at(&path, offset_of("Greet]", 0)).unwrap();
+        assert_eq!(definition.kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_references() {
+        let (index, _) = index();
+
+        assert_eq!(
+            references(&index, "User"),
+            vec![" User[", " User)", " User)", " User "]
+        );
+        assert_eq!(references(&index, "Role"), vec![" Role "]);
+        assert_eq!(
+            references(&index, "GPT4"),
+            vec!["[GPT4,", "\"GPT4\"", "\"GPT4\""]
+        );
+        // `user.PrintUser()` is an attribute access, not a call.
+        assert_eq!(references(&index, "PrintUser"), vec![" PrintUser("]);
+    }
+
+    #[test]
+    fn test_document_symbols() {
+        let path = PathBuf::from("baml_src/main.baml");
+        let file = SourceFile::from((path.clone(), SOURCE.to_string()));
+        let schema = validate(&PathBuf::from("baml_src"), vec![file]);
+        let symbols = SymbolIndex::document_symbols(schema.db.ast(), &path);
+
+        let names = symbols.iter().map(|s| s.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "User",
+                "Role",
+                "PrintUser",
+                "Retry",
+                "GPT4",
+                "Backup",
+                "Greet",
+                "GreetTest"
+            ]
+        );
+        let values = symbols[1]
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("ADMIN", LspSymbolKind::ENUM_MEMBER),
+                ("USER", LspSymbolKind::ENUM_MEMBER)
+            ]
+        );
+    }
+}
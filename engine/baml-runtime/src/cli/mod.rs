@@ -1,7 +1,12 @@
+pub mod check;
 pub mod dev;
+pub mod fmt;
 pub mod generate;
 pub mod init;
+pub mod lsp;
+pub mod replay;
 pub mod serve;
+pub mod test;
 
 use internal_baml_core::configuration::GeneratorOutputType;
 
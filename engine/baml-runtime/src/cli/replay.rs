@@ -0,0 +1,352 @@
+use anyhow::{Context, Result};
+use baml_types::{BamlMap, BamlValue};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::{
+    runtime_interface::ExperimentalTracingInterface, BamlRuntime, FunctionResult,
+    RuntimeContextManager,
+};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Re-parse the recorded raw LLM response against the current schema.
+    Parse,
+    /// Re-render the prompt from the recorded arguments and call the LLM again.
+    Call,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(
+        required = true,
+        help = "Trace files written with BAML_TRACE_DIR (e.g. baml_traces.jsonl)"
+    )]
+    files: Vec<PathBuf>,
+    #[arg(
+        long = "event-id",
+        short = 'e',
+        help = "Only replay the events with this id. May be repeated. Defaults to every LLM function call in the files."
+    )]
+    event_ids: Vec<String>,
+    #[arg(long, value_enum, default_value = "parse")]
+    mode: ReplayMode,
+}
+
+/// A BAML function call recovered from one line of a trace file.
+#[derive(Debug, PartialEq)]
+struct RecordedCall {
+    event_id: String,
+    function: String,
+    args: BamlMap<String, BamlValue>,
+    /// The raw text of the last LLM response, if any attempt got one.
+    raw_output: Option<String>,
+    /// The parsed output, as JSON. `None` if the call failed.
+    output: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ReplayOutcome {
+    Unchanged,
+    Changed,
+    /// The recorded call failed, but the replay succeeded.
+    Fixed,
+    /// The replay failed.
+    Failed,
+    /// There was nothing to replay, e.g. a parse replay of a call whose LLM
+    /// requests all failed.
+    Skipped,
+}
+
+impl ReplayArgs {
+    pub fn run(&self) -> Result<()> {
+        let mut calls = vec![];
+        for file in &self.files {
+            let contents = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read trace file {}", file.display()))?;
+            for (index, line) in contents.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let invalid = || {
+                    format!(
+                        "{}:{} is not a valid trace event",
+                        file.display(),
+                        index + 1
+                    )
+                };
+                let record: serde_json::Value = serde_json::from_str(line).with_context(invalid)?;
+                if let Some(call) = RecordedCall::from_record(&record).with_context(invalid)? {
+                    if self.event_ids.is_empty() || self.event_ids.contains(&call.event_id) {
+                        calls.push(call);
+                    }
+                }
+            }
+        }
+
+        if calls.is_empty() {
+            anyhow::bail!("No matching LLM function calls found in the trace files");
+        }
+        for id in &self.event_ids {
+            if !calls.iter().any(|c| &c.event_id == id) {
+                log::warn!("Event {id} was not found in the trace files");
+            }
+        }
+
+        let runtime = BamlRuntime::from_directory(&self.from, std::env::vars().collect())
+            .context("Failed to build BAML runtime")?;
+        let ctx = runtime.create_ctx_manager(BamlValue::String("cli".to_string()), None);
+        let t = BamlRuntime::get_tokio_singleton()?;
+
+        let mut failed = 0;
+        for call in &calls {
+            let replayed = match self.mode {
+                ReplayMode::Parse => call.raw_output.as_ref().map(|raw_output| {
+                    runtime.parse_llm_response(
+                        &call.function,
+                        raw_output.clone(),
+                        false,
+                        &ctx,
+                        None,
+                        None,
+                    )
+                }),
+                ReplayMode::Call => Some(t.block_on(replay_call(&runtime, call, &ctx))),
+            };
+
+            let (outcome, output) = compare(call, replayed);
+            if outcome == ReplayOutcome::Failed {
+                failed += 1;
+            }
+            log_outcome(call, &outcome, &output);
+        }
+        if let Err(e) = runtime.flush() {
+            log::debug!("Error while flushing traces: {:#}", e);
+        }
+
+        match failed {
+            0 => Ok(()),
+            n => anyhow::bail!("{n} of {} replays failed", calls.len()),
+        }
+    }
+}
+
+async fn replay_call(
+    runtime: &BamlRuntime,
+    call: &RecordedCall,
+    ctx: &RuntimeContextManager,
+) -> Result<FunctionResult> {
+    // Always go to the LLM; a cached response would defeat the replay.
+    let (result, _) = runtime
+        .call_function(
+            call.function.clone(),
+            &call.args,
+            ctx,
+            None,
+            None,
+            None,
+            true,
+        )
+        .await;
+    result
+}
+
+/// Compares a replayed result with the recorded one, returning the outcome
+/// and either the new output or the replay error.
+fn compare(
+    call: &RecordedCall,
+    replayed: Option<Result<FunctionResult>>,
+) -> (ReplayOutcome, String) {
+    let replayed = match replayed {
+        None => {
+            return (
+                ReplayOutcome::Skipped,
+                "no LLM response was recorded".into(),
+            )
+        }
+        Some(Err(e)) => return (ReplayOutcome::Failed, format!("{e:#}")),
+        Some(Ok(result)) => result,
+    };
+    let value = match replayed.result_with_constraints_content() {
+        Ok(value) => serde_json::to_value(BamlValue::from(value)).unwrap_or_default(),
+        Err(e) => return (ReplayOutcome::Failed, format!("{e:#}")),
+    };
+    let pretty = serde_json::to_string_pretty(&value).unwrap_or_default();
+
+    match &call.output {
+        None => (ReplayOutcome::Fixed, pretty),
+        Some(recorded) if recorded == &value => (ReplayOutcome::Unchanged, pretty),
+        Some(_) => (ReplayOutcome::Changed, pretty),
+    }
+}
+
+fn log_outcome(call: &RecordedCall, outcome: &ReplayOutcome, output: &str) {
+    let name = format!("{} ({})", call.function, call.event_id);
+    let recorded = || match (&call.output, &call.error) {
+        (Some(output), _) => serde_json::to_string_pretty(output).unwrap_or_default(),
+        (None, Some(error)) => error.clone(),
+        (None, None) => String::new(),
+    };
+    match outcome {
+        ReplayOutcome::Unchanged => log::info!("UNCHANGED {name}"),
+        ReplayOutcome::Skipped => log::warn!("SKIPPED {name}: {output}"),
+        ReplayOutcome::Fixed => log::info!(
+            "FIXED {name}\n{}\n{}\n{}\n{}",
+            "---Recorded Error---".blue(),
+            recorded().as_str().red(),
+            "---Replayed Output---".blue(),
+            output
+        ),
+        ReplayOutcome::Changed => log::warn!(
+            "CHANGED {name}\n{}\n{}\n{}\n{}",
+            "---Recorded Output---".blue(),
+            recorded(),
+            "---Replayed Output---".blue(),
+            output
+        ),
+        ReplayOutcome::Failed => log::error!(
+            "FAILED {name}\n{}\n{}\n{}\n{}",
+            "---Recorded Output---".blue(),
+            recorded(),
+            "---Replay Error---".blue(),
+            output.red()
+        ),
+    }
+}
+
+impl RecordedCall {
+    /// Returns `None` for events that are not BAML LLM function calls, like
+    /// spans from `@trace`.
+    fn from_record(record: &serde_json::Value) -> Result<Option<Self>> {
+        if record["event_type"] != "func_llm" {
+            return Ok(None);
+        }
+
+        let event_id = record["event_id"]
+            .as_str()
+            .context("Missing event_id")?
+            .to_string();
+        let function = record["context"]["event_chain"]
+            .as_array()
+            .and_then(|chain| chain.last())
+            .and_then(|f| f["function_name"].as_str())
+            .context("Missing function name")?
+            .to_string();
+
+        let args = match io_value_to_json(&record["io"]["input"])? {
+            Some(serde_json::Value::Object(args)) => args
+                .into_iter()
+                .map(|(k, v)| Ok((k, serde_json::from_value::<BamlValue>(v)?)))
+                .collect::<Result<BamlMap<_, _>>>()?,
+            Some(_) => anyhow::bail!("Function arguments must be a map"),
+            None => Default::default(),
+        };
+
+        let raw_output = record["metadata"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .chain(record["metadata"].as_object().map(|_| &record["metadata"]))
+            .filter_map(|attempt| attempt["output"]["raw_text"].as_str())
+            .next_back()
+            .map(str::to_string);
+
+        Ok(Some(Self {
+            event_id,
+            function,
+            args,
+            raw_output,
+            output: io_value_to_json(&record["io"]["output"])?,
+            error: record["error"]["message"].as_str().map(str::to_string),
+        }))
+    }
+}
+
+/// Recovers the JSON value of a recorded `IOValue`. Single values are stored
+/// as one JSON string; maps as a list of JSON strings, named by the keys of
+/// `type.fields`.
+fn io_value_to_json(io_value: &serde_json::Value) -> Result<Option<serde_json::Value>> {
+    match &io_value["value"] {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(value) => Ok(Some(serde_json::from_str(value)?)),
+        serde_json::Value::Array(values) => {
+            let names = io_value["type"]["fields"]
+                .as_object()
+                .context("Missing field names")?
+                .keys();
+            Ok(Some(serde_json::Value::Object(
+                names
+                    .zip(values)
+                    .map(|(name, value)| {
+                        let value = value.as_str().context("Field values must be strings")?;
+                        Ok((
+                            name.clone(),
+                            serde_json::from_str::<serde_json::Value>(value)?,
+                        ))
+                    })
+                    .collect::<Result<serde_json::Map<_, _>>>()?,
+            )))
+        }
+        _ => anyhow::bail!("Unexpected IO value: {io_value}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_call_from_record() {
+        let record = serde_json::json!({
+            "event_type": "func_llm",
+            "event_id": "abc",
+            "context": {
+                "event_chain": [
+                    {"function_name": "outer"},
+                    {"function_name": "ExtractResume"}
+                ]
+            },
+            "io": {
+                "input": {
+                    "type": {"name": "multi", "fields": {"resume": "string", "pages": "int"}},
+                    "value": ["\"Vaibhav Gupta\"", "2"]
+                },
+                "output": {
+                    "type": {"name": "single", "fields": {"value": "class"}},
+                    "value": "{\"name\":\"Vaibhav Gupta\"}"
+                }
+            },
+            "error": null,
+            "metadata": [
+                {"output": null, "error": "rate limited"},
+                {"output": {"raw_text": "{name: Vaibhav Gupta}"}, "error": null},
+            ]
+        });
+
+        let call = RecordedCall::from_record(&record).unwrap().unwrap();
+        assert_eq!(call.event_id, "abc");
+        assert_eq!(call.function, "ExtractResume");
+        assert_eq!(
+            call.args,
+            BamlMap::from_iter([
+                (
+                    "resume".to_string(),
+                    BamlValue::String("Vaibhav Gupta".to_string())
+                ),
+                ("pages".to_string(), BamlValue::Int(2)),
+            ])
+        );
+        assert_eq!(call.raw_output.as_deref(), Some("{name: Vaibhav Gupta}"));
+        assert_eq!(
+            call.output,
+            Some(serde_json::json!({"name": "Vaibhav Gupta"}))
+        );
+        assert_eq!(call.error, None);
+
+        let traced = serde_json::json!({"event_type": "func_code", "event_id": "def"});
+        assert_eq!(RecordedCall::from_record(&traced).unwrap(), None);
+    }
+}
@@ -66,6 +66,8 @@ impl BamlError {
                     | crate::internal::llm_client::ErrorCode::RateLimited
                     | crate::internal::llm_client::ErrorCode::ServerError
                     | crate::internal::llm_client::ErrorCode::ServiceUnavailable
+                    | crate::internal::llm_client::ErrorCode::Timeout
+                    | crate::internal::llm_client::ErrorCode::Cancelled
                     | crate::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                         Self::ClientError {
                             message: format!("{:?}", err),
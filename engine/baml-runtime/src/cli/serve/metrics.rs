@@ -0,0 +1,503 @@
+//! Prometheus metrics for `baml-cli serve`, rendered in the text exposition
+//! format on `/metrics`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use crate::{
+    internal::llm_client::{orchestrator::ExecutionScope, LLMResponse},
+    FunctionResult,
+};
+
+/// Request latencies, in seconds. LLM calls are slow, so the buckets go up to
+/// several minutes.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+struct CounterVec {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Mutex<BTreeMap<Vec<String>, u64>>,
+}
+
+impl CounterVec {
+    fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Default::default(),
+        }
+    }
+
+    fn inc_by(&self, labels: &[&str], by: u64) {
+        debug_assert_eq!(labels.len(), self.labels.len());
+        let key = labels.iter().map(|l| l.to_string()).collect();
+        *self.values.lock().unwrap().entry(key).or_default() += by;
+    }
+
+    fn inc(&self, labels: &[&str]) {
+        self.inc_by(labels, 1)
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} counter", self.name);
+        for (values, count) in self.values.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "{}{} {}",
+                self.name,
+                render_labels(self.labels, values, None),
+                count
+            );
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct Histogram {
+    // Not cumulative; one slot per bucket in `LATENCY_BUCKETS`.
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+struct HistogramVec {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Mutex<BTreeMap<Vec<String>, Histogram>>,
+}
+
+impl HistogramVec {
+    fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Default::default(),
+        }
+    }
+
+    fn observe(&self, labels: &[&str], value: f64) {
+        debug_assert_eq!(labels.len(), self.labels.len());
+        let key = labels.iter().map(|l| l.to_string()).collect();
+        let mut values = self.values.lock().unwrap();
+        let histogram = values.entry(key).or_insert_with(|| Histogram {
+            buckets: vec![0; LATENCY_BUCKETS.len()],
+            ..Default::default()
+        });
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|b| value <= *b) {
+            histogram.buckets[i] += 1;
+        }
+        histogram.sum += value;
+        histogram.count += 1;
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", self.name);
+        for (values, histogram) in self.values.lock().unwrap().iter() {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{} {}",
+                    self.name,
+                    render_labels(self.labels, values, Some(&bound.to_string())),
+                    cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{}_bucket{} {}",
+                self.name,
+                render_labels(self.labels, values, Some("+Inf")),
+                histogram.count
+            );
+            let labels = render_labels(self.labels, values, None);
+            let _ = writeln!(out, "{}_sum{} {}", self.name, labels, histogram.sum);
+            let _ = writeln!(out, "{}_count{} {}", self.name, labels, histogram.count);
+        }
+    }
+}
+
+struct Gauge {
+    name: &'static str,
+    help: &'static str,
+    value: AtomicI64,
+}
+
+impl Gauge {
+    fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            value: AtomicI64::new(0),
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} gauge", self.name);
+        let _ = writeln!(out, "{} {}", self.name, self.value.load(Ordering::Relaxed));
+    }
+}
+
+fn render_labels(names: &[&str], values: &[String], le: Option<&str>) -> String {
+    let pairs = names
+        .iter()
+        .zip(values)
+        .map(|(name, value)| (*name, value.as_str()))
+        .chain(le.map(|le| ("le", le)))
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+        .collect::<Vec<_>>();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Which HTTP endpoint served a request.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Endpoint {
+    Call,
+    Stream,
+}
+
+impl Endpoint {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Endpoint::Call => "call",
+            Endpoint::Stream => "stream",
+        }
+    }
+}
+
+pub(super) struct Metrics {
+    requests: CounterVec,
+    request_duration: HistogramVec,
+    llm_requests: CounterVec,
+    llm_request_duration: HistogramVec,
+    tokens: CounterVec,
+    retries: CounterVec,
+    fallbacks: CounterVec,
+    parse_failures: CounterVec,
+    requests_in_flight: Gauge,
+    streams_in_flight: Gauge,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            requests: CounterVec::new(
+                "baml_requests_total",
+                "BAML function calls served, by outcome.",
+                &["function", "endpoint", "outcome"],
+            ),
+            request_duration: HistogramVec::new(
+                "baml_request_duration_seconds",
+                "Time to serve a BAML function call, including every LLM attempt.",
+                &["function", "endpoint"],
+            ),
+            llm_requests: CounterVec::new(
+                "baml_llm_requests_total",
+                "Requests sent to LLM clients, including retries and fallbacks.",
+                &["function", "client", "outcome"],
+            ),
+            llm_request_duration: HistogramVec::new(
+                "baml_llm_request_duration_seconds",
+                "Latency of a single LLM request.",
+                &["function", "client"],
+            ),
+            tokens: CounterVec::new(
+                "baml_llm_tokens_total",
+                "Tokens used, as reported by the LLM provider.",
+                &["function", "client", "type"],
+            ),
+            retries: CounterVec::new(
+                "baml_llm_retries_total",
+                "LLM requests that were retries under a retry policy.",
+                &["function", "client", "policy"],
+            ),
+            fallbacks: CounterVec::new(
+                "baml_llm_fallbacks_total",
+                "LLM requests sent to a fallback client after an earlier client failed.",
+                &["function", "client", "strategy"],
+            ),
+            parse_failures: CounterVec::new(
+                "baml_parse_failures_total",
+                "LLM responses that could not be parsed into the function's return type.",
+                &["function", "client"],
+            ),
+            requests_in_flight: Gauge::new(
+                "baml_requests_in_flight",
+                "BAML function calls currently being served.",
+            ),
+            streams_in_flight: Gauge::new(
+                "baml_streams_in_flight",
+                "Streaming BAML function calls currently open.",
+            ),
+        }
+    }
+}
+
+/// Decrements an in-flight gauge when dropped.
+pub(super) struct InFlight {
+    metrics: Arc<Metrics>,
+    endpoint: Endpoint,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.metrics
+            .gauge(self.endpoint)
+            .value
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Metrics {
+    fn gauge(&self, endpoint: Endpoint) -> &Gauge {
+        match endpoint {
+            Endpoint::Call => &self.requests_in_flight,
+            Endpoint::Stream => &self.streams_in_flight,
+        }
+    }
+
+    /// Marks a request as in flight until the returned guard is dropped.
+    pub fn start(self: &Arc<Self>, endpoint: Endpoint) -> InFlight {
+        self.gauge(endpoint).value.fetch_add(1, Ordering::Relaxed);
+        InFlight {
+            metrics: self.clone(),
+            endpoint,
+        }
+    }
+
+    /// The number of `/call` and `/stream` requests currently being served.
+    pub fn in_flight(&self) -> i64 {
+        self.requests_in_flight.value.load(Ordering::Relaxed)
+            + self.streams_in_flight.value.load(Ordering::Relaxed)
+    }
+
+    /// Records a request that was rejected before the function was called.
+    pub fn record_rejected(&self, endpoint: Endpoint, function: &str) {
+        self.requests
+            .inc(&[function, endpoint.as_str(), "invalid_argument"]);
+    }
+
+    pub fn record(
+        &self,
+        endpoint: Endpoint,
+        function: &str,
+        result: &Result<FunctionResult>,
+        duration: Duration,
+    ) {
+        self.requests
+            .inc(&[function, endpoint.as_str(), request_outcome(result)]);
+        self.request_duration
+            .observe(&[function, endpoint.as_str()], duration.as_secs_f64());
+
+        let Ok(result) = result else {
+            return;
+        };
+
+        for (scope, response, parsed, _) in result.event_chain() {
+            let (client, outcome, latency) = match response {
+                LLMResponse::Success(r) => (r.client.as_str(), "success", Some(r.latency)),
+                LLMResponse::LLMFailure(e) => (e.client.as_str(), "failure", Some(e.latency)),
+                LLMResponse::UserFailure(_) | LLMResponse::InternalFailure(_) => {
+                    ("unknown", "failure", None)
+                }
+            };
+
+            self.llm_requests.inc(&[function, client, outcome]);
+            if let Some(latency) = latency {
+                self.llm_request_duration
+                    .observe(&[function, client], latency.as_secs_f64());
+            }
+
+            if let LLMResponse::Success(r) = response {
+                if let Some(tokens) = r.metadata.prompt_tokens {
+                    self.tokens.inc_by(&[function, client, "input"], tokens);
+                }
+                if let Some(tokens) = r.metadata.output_tokens {
+                    self.tokens.inc_by(&[function, client, "output"], tokens);
+                }
+            }
+
+            if matches!(parsed, Some(Err(_))) {
+                self.parse_failures.inc(&[function, client]);
+            }
+
+            for s in &scope.scope {
+                match s {
+                    ExecutionScope::Retry(policy, count, _) if *count > 0 => {
+                        self.retries.inc(&[function, client, policy.as_str()]);
+                    }
+                    ExecutionScope::Fallback(strategy, index) if *index > 0 => {
+                        self.fallbacks.inc(&[function, client, strategy.as_str()]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.requests.render(&mut out);
+        self.request_duration.render(&mut out);
+        self.requests_in_flight.render(&mut out);
+        self.streams_in_flight.render(&mut out);
+        self.llm_requests.render(&mut out);
+        self.llm_request_duration.render(&mut out);
+        self.tokens.render(&mut out);
+        self.retries.render(&mut out);
+        self.fallbacks.render(&mut out);
+        self.parse_failures.render(&mut out);
+        out
+    }
+}
+
+/// Mirrors the status codes returned by the `/call` and `/stream` handlers.
+fn request_outcome(result: &Result<FunctionResult>) -> &'static str {
+    match result {
+        Ok(result) => match result.llm_response() {
+            LLMResponse::Success(_) => match result.result_with_constraints_content() {
+                Ok(_) => "success",
+                Err(_) => "validation_failure",
+            },
+            LLMResponse::LLMFailure(_) => "client_error",
+            LLMResponse::UserFailure(_) => "invalid_argument",
+            LLMResponse::InternalFailure(_) => "internal_error",
+        },
+        Err(_) => "internal_error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::llm_client::{
+        orchestrator::OrchestrationScope, ErrorCode, LLMCompleteResponse,
+        LLMCompleteResponseMetadata, LLMErrorResponse,
+    };
+    use internal_baml_jinja::RenderedPrompt;
+
+    fn success(client: &str) -> LLMResponse {
+        LLMResponse::Success(LLMCompleteResponse {
+            client: client.to_string(),
+            model: "gpt-4o".to_string(),
+            prompt: RenderedPrompt::Completion("prompt".to_string()),
+            request_options: Default::default(),
+            content: "not json".to_string(),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::from_millis(300),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: true,
+                finish_reason: Some("stop".to_string()),
+                prompt_tokens: Some(10),
+                output_tokens: Some(5),
+                total_tokens: Some(15),
+                cached: false,
+            },
+        })
+    }
+
+    fn failure(client: &str) -> LLMResponse {
+        LLMResponse::LLMFailure(LLMErrorResponse {
+            client: client.to_string(),
+            model: None,
+            prompt: RenderedPrompt::Completion("prompt".to_string()),
+            request_options: Default::default(),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::from_millis(100),
+            message: "rate limited".to_string(),
+            code: ErrorCode::RateLimited,
+        })
+    }
+
+    #[test]
+    fn test_render() {
+        let metrics = Arc::new(Metrics::default());
+        let fallback = |index: usize, client: &str| {
+            OrchestrationScope::from(vec![
+                ExecutionScope::Fallback("MyFallback".to_string(), index),
+                ExecutionScope::Direct(client.to_string()),
+            ])
+        };
+        let result = FunctionResult::new_chain(vec![
+            (fallback(0, "GPT4"), failure("GPT4"), None, None),
+            (
+                fallback(1, "Claude"),
+                success("Claude"),
+                Some(Err(anyhow::anyhow!("Failed to parse"))),
+                Some(Err(anyhow::anyhow!("Failed to parse"))),
+            ),
+        ])
+        .unwrap();
+
+        let stream = metrics.start(Endpoint::Stream);
+        metrics.record(
+            Endpoint::Call,
+            "ExtractResume",
+            &Ok(result),
+            Duration::from_millis(400),
+        );
+        let rendered = metrics.render();
+        drop(stream);
+
+        for line in [
+            r#"baml_requests_total{function="ExtractResume",endpoint="call",outcome="validation_failure"} 1"#,
+            r#"baml_request_duration_seconds_bucket{function="ExtractResume",endpoint="call",le="0.25"} 0"#,
+            r#"baml_request_duration_seconds_bucket{function="ExtractResume",endpoint="call",le="0.5"} 1"#,
+            r#"baml_request_duration_seconds_bucket{function="ExtractResume",endpoint="call",le="+Inf"} 1"#,
+            r#"baml_request_duration_seconds_count{function="ExtractResume",endpoint="call"} 1"#,
+            r#"baml_llm_requests_total{function="ExtractResume",client="Claude",outcome="success"} 1"#,
+            r#"baml_llm_requests_total{function="ExtractResume",client="GPT4",outcome="failure"} 1"#,
+            r#"baml_llm_tokens_total{function="ExtractResume",client="Claude",type="input"} 10"#,
+            r#"baml_llm_tokens_total{function="ExtractResume",client="Claude",type="output"} 5"#,
+            r#"baml_llm_fallbacks_total{function="ExtractResume",client="Claude",strategy="MyFallback"} 1"#,
+            r#"baml_parse_failures_total{function="ExtractResume",client="Claude"} 1"#,
+            "baml_streams_in_flight 1",
+            "baml_requests_in_flight 0",
+        ] {
+            assert!(
+                rendered.lines().any(|l| l == line),
+                "missing {line} in:\n{rendered}"
+            );
+        }
+        assert!(!rendered.contains("baml_llm_retries_total{"));
+        assert!(metrics.render().contains("baml_streams_in_flight 0"));
+    }
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(
+            render_labels(&["function"], &["a\"b\\c\nd".to_string()], None),
+            r#"{function="a\"b\\c\nd"}"#
+        );
+    }
+}
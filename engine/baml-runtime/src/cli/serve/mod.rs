@@ -1,11 +1,15 @@
 mod arg_validation;
 mod error;
 mod json_response;
+mod metrics;
 mod ping;
+mod type_builder;
 use error::BamlError;
 use indexmap::IndexMap;
 use internal_baml_codegen::GeneratorArgs;
 use json_response::Json;
+use metrics::{Endpoint, Metrics};
+use type_builder::TypeBuilderOptions;
 
 use anyhow::{Context, Result};
 use arg_validation::BamlServeValidate;
@@ -28,7 +32,14 @@ use core::pin::Pin;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{path::PathBuf, sync::Arc, task::Poll};
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    path::PathBuf,
+    sync::Arc,
+    task::Poll,
+    time::{Duration, Instant},
+};
 use tokio::{net::TcpListener, sync::RwLock};
 use tokio_stream::StreamExt;
 
@@ -36,6 +47,8 @@ use crate::{
     client_registry::ClientRegistry,
     errors::ExposedError,
     internal::llm_client::{LLMResponse, ResponseBamlValue},
+    runtime_interface::ExperimentalTracingInterface,
+    type_builder::TypeBuilder,
     BamlRuntime, FunctionResult, RuntimeContextManager,
 };
 use internal_baml_codegen::openapi::OpenApiSchema;
@@ -54,11 +67,41 @@ pub struct ServeArgs {
         default_value_t = false
     )]
     no_version_check: bool,
+    #[arg(
+        long,
+        help = "seconds to wait for in-flight requests to finish after SIGTERM or Ctrl-C",
+        default_value = "30"
+    )]
+    shutdown_timeout: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct BamlOptions {
     pub client_registry: Option<ClientRegistry>,
+    pub type_builder: Option<TypeBuilderOptions>,
+    /// Added to the trace of the call, like `set_tags` in the client libraries.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+impl BamlOptions {
+    fn build_type_builder(&self, runtime: &BamlRuntime) -> Result<Option<TypeBuilder>, BamlError> {
+        self.type_builder
+            .as_ref()
+            .map(|tb| tb.build(runtime))
+            .transpose()
+    }
+
+    fn ctx_manager(&self) -> RuntimeContextManager {
+        let ctx_mgr = RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None);
+        if let Some(tags) = &self.tags {
+            ctx_mgr.upsert_tags(
+                tags.iter()
+                    .map(|(k, v)| (k.clone(), BamlValue::String(v.clone())))
+                    .collect(),
+            );
+        }
+        ctx_mgr
+    }
 }
 
 impl ServeArgs {
@@ -112,7 +155,9 @@ Thanks for trying out BAML!
 
         let (server, tcp_listener) = t.block_on(Server::new(self.from.clone(), self.port))?;
 
-        t.block_on(server.serve(tcp_listener))?;
+        t.block_on(
+            server.serve_until_shutdown(tcp_listener, Duration::from_secs(self.shutdown_timeout)),
+        )?;
 
         Ok(())
     }
@@ -126,7 +171,11 @@ Thanks for trying out BAML!
 pub(super) struct Server {
     src_dir: PathBuf,
     port: u16,
-    pub(super) b: Arc<RwLock<BamlRuntime>>,
+    /// Requests clone the inner `Arc` and release the lock, so that a reload
+    /// does not wait for in-flight requests, which finish on the runtime they
+    /// started with.
+    pub(super) b: Arc<RwLock<Arc<BamlRuntime>>>,
+    metrics: Arc<Metrics>,
 }
 
 #[derive(Debug)]
@@ -212,10 +261,11 @@ impl Server {
             Arc::new(Self {
                 src_dir: src_dir.clone(),
                 port,
-                b: Arc::new(RwLock::new(BamlRuntime::from_directory(
+                b: Arc::new(RwLock::new(Arc::new(BamlRuntime::from_directory(
                     &src_dir,
                     std::env::vars().collect(),
-                )?)),
+                )?))),
+                metrics: Default::default(),
             }),
             tcp_listener,
         ))
@@ -282,7 +332,24 @@ impl Server {
         next.run(request).await
     }
 
-    pub async fn serve(self: Arc<Self>, tcp_listener: TcpListener) -> Result<()> {
+    /// Re-parses `src_dir` and swaps in the new runtime. If the new sources
+    /// have errors, the current runtime is kept.
+    pub(super) async fn reload(&self) -> Result<()> {
+        let start = Instant::now();
+        // Parsing and validating the sources is blocking work, which would
+        // otherwise hold up the requests being served meanwhile.
+        let src_dir = self.src_dir.clone();
+        let runtime = tokio::task::spawn_blocking(move || {
+            BamlRuntime::from_directory(&src_dir, std::env::vars().collect())
+        })
+        .await?
+        .context("Failed to reload BAML runtime, keeping the current one")?;
+        *self.b.write().await = Arc::new(runtime);
+        log::info!("Reloaded runtime in {}ms", start.elapsed().as_millis());
+        Ok(())
+    }
+
+    fn router(self: &Arc<Self>) -> axum::Router {
         // build our application with a route
         let app = axum::Router::new();
 
@@ -303,31 +370,22 @@ impl Server {
         let s = self.clone();
         let app = app.route("/docs", get(move || s.clone().docs_handler()));
 
+        let s = self.clone();
+        let app = app.route("/metrics", get(move || s.clone().metrics_handler()));
+
+        let s = self.clone();
+        let app = app.route("/_admin/reload", post(move || s.clone().reload_handler()));
+
         let s = self.clone();
         let app = app.route(
             "/openapi.json",
             get(move || s.clone().openapi_json_handler()),
         );
 
-        let service = axum::serve(
-            tcp_listener,
-            app.layer(axum::middleware::from_fn(Server::auth_middleware)),
-        );
-        // TODO: we do not handle this ourselves, because tokio's default
-        // handling is pretty good on unix.
-        //
-        // Not totally sure if this WAI on
-        // windows, but there are some potential pitfalls that we can run into
-        // if we try to handle this ourselves, see
-        // https://docs.rs/tokio/latest/tokio/signal/fn.ctrl_c.html#caveats
-        //
-        // Namely- we need to ensure resilient delivery of Ctrl-C to everything, and I
-        // suspect we need to do a bit of work to ensure that we handle that bookkeeping
-        // correctly. Shutting down the BAML runtime, tokio runtime, _and_ axum is not
-        // super straightforward, because I don't know how much is handled for us
-        // out of the box.
-        //
-        // .with_graceful_shutdown(signal::ctrl_c());
+        app.layer(axum::middleware::from_fn(Server::auth_middleware))
+    }
+
+    fn log_listening(&self) {
         log::info!(
             r#"BAML-over-HTTP listening on port {}, serving from {}
 
@@ -339,30 +397,150 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
             self.src_dir.display(),
             self.port,
         );
+    }
+
+    /// Serves until the process is killed. Used by `baml-cli dev`, which
+    /// reloads the runtime itself when files change.
+    pub async fn serve(self: Arc<Self>, tcp_listener: TcpListener) -> Result<()> {
+        let service = axum::serve(tcp_listener, self.router());
+        self.log_listening();
 
         service.await?;
 
         Ok(())
     }
 
+    /// Serves until SIGTERM or Ctrl-C, reloading the runtime on SIGHUP.
+    ///
+    /// On shutdown we stop accepting connections and wait up to
+    /// `shutdown_timeout` for in-flight calls and streams to finish.
+    pub async fn serve_until_shutdown(
+        self: Arc<Self>,
+        tcp_listener: TcpListener,
+        shutdown_timeout: Duration,
+    ) -> Result<()> {
+        let signal = self.clone().shutdown_signal()?;
+        self.serve_until(tcp_listener, signal, shutdown_timeout)
+            .await
+    }
+
+    /// Serves until `signal` resolves, then drains in-flight requests.
+    async fn serve_until(
+        self: Arc<Self>,
+        tcp_listener: TcpListener,
+        signal: impl Future<Output = ()> + Send + 'static,
+        shutdown_timeout: Duration,
+    ) -> Result<()> {
+        let (draining_tx, mut draining_rx) = tokio::sync::oneshot::channel();
+        let service = axum::serve(tcp_listener, self.router())
+            .with_graceful_shutdown(async move {
+                signal.await;
+                let _ = draining_tx.send(());
+            })
+            .into_future();
+        tokio::pin!(service);
+        self.log_listening();
+
+        tokio::select! {
+            result = &mut service => result?,
+            Ok(()) = &mut draining_rx => {
+                log::info!(
+                    "Shutting down, waiting up to {}s for {} in-flight requests to finish",
+                    shutdown_timeout.as_secs(),
+                    self.metrics.in_flight(),
+                );
+                match tokio::time::timeout(shutdown_timeout, service).await {
+                    Ok(result) => result?,
+                    Err(_) => log::warn!(
+                        "Shutdown timed out, dropping {} in-flight requests",
+                        self.metrics.in_flight()
+                    ),
+                }
+            }
+        }
+
+        if let Err(e) = self.b.read().await.flush() {
+            log::debug!("Error while flushing traces: {:#}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a future that resolves on SIGTERM or Ctrl-C. Until then, SIGHUP
+    /// reloads the runtime.
+    fn shutdown_signal(self: Arc<Self>) -> Result<impl Future<Output = ()>> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let mut terminate =
+                signal(SignalKind::terminate()).context("Failed to listen for SIGTERM")?;
+            let mut hangup = signal(SignalKind::hangup()).context("Failed to listen for SIGHUP")?;
+            Ok(async move {
+                loop {
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => break,
+                        _ = terminate.recv() => break,
+                        _ = hangup.recv() => {
+                            if let Err(e) = self.reload().await {
+                                log::error!("{:#}", e);
+                            }
+                        }
+                    }
+                }
+            })
+        }
+
+        // There is no SIGHUP on Windows; use POST /_admin/reload instead.
+        #[cfg(not(unix))]
+        {
+            let _ = self;
+            Ok(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+        }
+    }
+
     async fn baml_call(
         self: Arc<Self>,
         b_fn: String,
         b_args: serde_json::Value,
-        b_options: Option<BamlOptions>,
+        b_options: BamlOptions,
     ) -> Response {
+        let _in_flight = self.metrics.start(Endpoint::Call);
+        let start = Instant::now();
+
         let args = match parse_args(&b_fn, b_args) {
             Ok(args) => args,
-            Err(e) => return e.into_response(),
+            Err(e) => {
+                self.metrics.record_rejected(Endpoint::Call, &b_fn);
+                return e.into_response();
+            }
         };
 
-        let ctx_mgr = RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None);
-        let client_registry = b_options.and_then(|options| options.client_registry);
+        let runtime = self.b.read().await.clone();
+        let tb = match b_options.build_type_builder(&runtime) {
+            Ok(tb) => tb,
+            Err(e) => {
+                self.metrics.record_rejected(Endpoint::Call, &b_fn);
+                return e.into_response();
+            }
+        };
+        let ctx_mgr = b_options.ctx_manager();
 
-        let locked = self.b.read().await;
-        let (result, _trace_id) = locked
-            .call_function(b_fn, &args, &ctx_mgr, None, client_registry.as_ref())
+        let (result, _trace_id) = runtime
+            .call_function(
+                b_fn.clone(),
+                &args,
+                &ctx_mgr,
+                tb.as_ref(),
+                b_options.client_registry.as_ref(),
+                None,
+                false,
+            )
             .await;
+        self.metrics
+            .record(Endpoint::Call, &b_fn, &result, start.elapsed());
 
         match result {
             Ok(function_result) => match function_result.llm_response() {
@@ -414,13 +592,13 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
         extract::Path(b_fn): extract::Path<String>,
         extract::Json(b_args): extract::Json<serde_json::Value>,
     ) -> Response {
-        let mut b_options = None;
+        let mut b_options = BamlOptions::default();
         if let Some(options_value) = b_args.get("__baml_options__") {
             match serde_json::from_value::<BamlOptions>(options_value.clone()) {
-                Ok(opts) => b_options = Some(opts),
-                Err(_) => {
+                Ok(opts) => b_options = opts,
+                Err(e) => {
                     return BamlError::InvalidArgument {
-                        message: "Failed to parse __baml_options__".to_string(),
+                        message: format!("Failed to parse __baml_options__: {}", e),
                     }
                     .into_response()
                 }
@@ -429,31 +607,44 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
         self.baml_call(b_fn, b_args, b_options).await
     }
 
-    fn baml_stream(
+    async fn baml_stream(
         self: Arc<Self>,
         b_fn: String,
         b_args: serde_json::Value,
-        b_options: Option<BamlOptions>,
+        b_options: BamlOptions,
     ) -> Response {
         let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
 
         let args = match parse_args(&b_fn, b_args) {
             Ok(args) => args,
-            Err(e) => return e.into_response(),
+            Err(e) => {
+                self.metrics.record_rejected(Endpoint::Stream, &b_fn);
+                return e.into_response();
+            }
         };
 
-        let client_registry = b_options.and_then(|options| options.client_registry);
+        let runtime = self.b.read().await.clone();
+        let tb = match b_options.build_type_builder(&runtime) {
+            Ok(tb) => tb,
+            Err(e) => {
+                self.metrics.record_rejected(Endpoint::Stream, &b_fn);
+                return e.into_response();
+            }
+        };
 
         tokio::spawn(async move {
-            let ctx_mgr =
-                RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None);
+            let _in_flight = self.metrics.start(Endpoint::Stream);
+            let start = Instant::now();
+            let ctx_mgr = b_options.ctx_manager();
+            let client_registry = b_options.client_registry.as_ref();
 
-            let result_stream = self.b.read().await.stream_function(
-                b_fn,
+            let result_stream = runtime.stream_function(
+                b_fn.clone(),
                 &args,
                 &ctx_mgr,
-                None,
-                client_registry.as_ref(),
+                tb.as_ref(),
+                client_registry,
+                false,
             );
 
             match result_stream {
@@ -471,10 +662,12 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
                                 }
                             }),
                             &ctx_mgr,
-                            None,
-                            None,
+                            tb.as_ref(),
+                            client_registry,
                         )
                         .await;
+                    self.metrics
+                        .record(Endpoint::Stream, &b_fn, &result, start.elapsed());
 
                     match result {
                         Ok(function_result) => match function_result.llm_response() {
@@ -547,19 +740,19 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
         extract::Path(path): extract::Path<String>,
         extract::Json(body): extract::Json<serde_json::Value>,
     ) -> Response {
-        let mut b_options = None;
+        let mut b_options = BamlOptions::default();
         if let Some(options_value) = body.get("__baml_options__") {
             match serde_json::from_value::<BamlOptions>(options_value.clone()) {
-                Ok(opts) => b_options = Some(opts),
-                Err(_) => {
+                Ok(opts) => b_options = opts,
+                Err(e) => {
                     return BamlError::InvalidArgument {
-                        message: "Failed to parse __baml_options__".to_string(),
+                        message: format!("Failed to parse __baml_options__: {}", e),
                     }
                     .into_response()
                 }
             }
         }
-        self.baml_stream(path, body, b_options)
+        self.baml_stream(path, body, b_options).await
     }
 
     /// Serve an HTML page that loads swagger-ui from local static files.
@@ -608,6 +801,32 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
         Html(page.to_string()).into_response()
     }
 
+    /// Render the metrics in the Prometheus text exposition format.
+    async fn metrics_handler(self: Arc<Self>) -> Response {
+        (
+            [(
+                axum::http::header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )],
+            self.metrics.render(),
+        )
+            .into_response()
+    }
+
+    async fn reload_handler(self: Arc<Self>) -> Response {
+        match self.reload().await {
+            Ok(()) => (StatusCode::OK, Json(json!({ "reloaded": true }))).into_response(),
+            Err(e) => {
+                log::error!("{:#}", e);
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({ "reloaded": false, "message": format!("{:#}", e) })),
+                )
+                    .into_response()
+            }
+        }
+    }
+
     /// Render the openapi spec. This endpoint is used by the swagger ui.
     async fn openapi_json_handler(self: Arc<Self>) -> Result<String, BamlError> {
         let locked = self.b.read().await;
@@ -706,3 +925,177 @@ fn parse_args(
 
     Ok(args)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    use super::*;
+
+    /// Answers every chat completion with `content`, after `delay`.
+    fn mock_openai_server(content: &str, delay: Duration) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let body = json!({
+            "id": "mock",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "mock",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop",
+            }],
+        })
+        .to_string();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    return;
+                };
+                let body = body.clone();
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream);
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut request = vec![0; content_length];
+                    reader.read_exact(&mut request).unwrap();
+
+                    std::thread::sleep(delay);
+                    let mut stream = reader.into_inner();
+                    write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                });
+            }
+        });
+
+        base_url
+    }
+
+    fn baml_src(base_url: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("baml-serve-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("main.baml"),
+            format!(
+                r##"
+client<llm> Mock {{
+  provider openai-generic
+  options {{
+    base_url "{base_url}"
+    model "mock"
+  }}
+}}
+
+function Echo(input: string) -> string {{
+  client Mock
+  prompt #"{{{{ input }}}}"#
+}}
+"##
+            ),
+        )
+        .unwrap();
+        dir
+    }
+
+    /// Starts a server over `src_dir`, which runs until the returned sender
+    /// fires or is dropped.
+    async fn start(
+        src_dir: &PathBuf,
+    ) -> (
+        Arc<Server>,
+        u16,
+        tokio::sync::oneshot::Sender<()>,
+        tokio::task::JoinHandle<Result<()>>,
+    ) {
+        let (server, tcp_listener) = Server::new(src_dir.clone(), 0).await.unwrap();
+        let port = tcp_listener.local_addr().unwrap().port();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        let serving = tokio::spawn(server.clone().serve_until(
+            tcp_listener,
+            async {
+                let _ = shutdown_rx.await;
+            },
+            Duration::from_secs(10),
+        ));
+        (server, port, shutdown_tx, serving)
+    }
+
+    async fn call_echo(port: u16) -> reqwest::Response {
+        reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{port}/call/Echo"))
+            .json(&json!({ "input": "hi" }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn in_flight_requests_drain_on_shutdown() {
+        let src_dir = baml_src(&mock_openai_server("done", Duration::from_millis(500)));
+        let (server, port, shutdown_tx, serving) = start(&src_dir).await;
+
+        let call = tokio::spawn(call_echo(port));
+        while server.metrics.in_flight() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        shutdown_tx.send(()).unwrap();
+
+        let response = call.await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.json::<serde_json::Value>().await.unwrap(),
+            json!("done")
+        );
+        serving.await.unwrap().unwrap();
+        assert_eq!(server.metrics.in_flight(), 0);
+
+        std::fs::remove_dir_all(&src_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_reload_keeps_the_old_runtime() {
+        let src_dir = baml_src(&mock_openai_server("done", Duration::ZERO));
+        let (_server, port, shutdown_tx, serving) = start(&src_dir).await;
+
+        std::fs::write(src_dir.join("main.baml"), "function Echo(").unwrap();
+        let reload = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{port}/_admin/reload"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(reload.status().as_u16(), 422);
+        assert_eq!(
+            reload.json::<serde_json::Value>().await.unwrap()["reloaded"],
+            json!(false)
+        );
+
+        let response = call_echo(port).await;
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.json::<serde_json::Value>().await.unwrap(),
+            json!("done")
+        );
+
+        shutdown_tx.send(()).unwrap();
+        serving.await.unwrap().unwrap();
+        std::fs::remove_dir_all(&src_dir).unwrap();
+    }
+}
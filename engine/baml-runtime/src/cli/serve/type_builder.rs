@@ -0,0 +1,191 @@
+use baml_types::BamlValue;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    type_builder::{TypeBuilder, WithMeta},
+    BamlRuntime,
+};
+
+use super::error::BamlError;
+
+/// A [`TypeBuilder`] sent as JSON in `__baml_options__.type_builder`.
+///
+/// Property types are written in BAML, e.g. `"string[]"` or `"Job | null"`,
+/// and may refer to the classes, enums and type aliases declared in `baml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TypeBuilderOptions {
+    #[serde(default)]
+    classes: IndexMap<String, DynamicClass>,
+    #[serde(default)]
+    enums: IndexMap<String, DynamicEnum>,
+    /// Classes, enums and type aliases declared in BAML, see
+    /// [`TypeBuilder::add_baml`].
+    baml: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct DynamicClass {
+    alias: Option<String>,
+    #[serde(default)]
+    properties: IndexMap<String, DynamicProperty>,
+}
+
+/// Without a `type`, the attributes are applied to an existing property.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct DynamicProperty {
+    r#type: Option<String>,
+    alias: Option<String>,
+    description: Option<String>,
+    skip: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct DynamicEnum {
+    alias: Option<String>,
+    #[serde(default)]
+    values: IndexMap<String, DynamicEnumValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct DynamicEnumValue {
+    alias: Option<String>,
+    description: Option<String>,
+    skip: Option<bool>,
+}
+
+impl TypeBuilderOptions {
+    pub fn build(&self, runtime: &BamlRuntime) -> Result<TypeBuilder, BamlError> {
+        let tb = TypeBuilder::new();
+
+        let baml = self.to_baml();
+        if !baml.trim().is_empty() {
+            tb.add_baml(&baml, runtime)
+                .map_err(|diagnostics| BamlError::InvalidArgument {
+                    message: format!("Invalid type_builder:\n{}", diagnostics.to_pretty_string()),
+                })?;
+        }
+
+        for (name, class) in &self.classes {
+            let builder = tb.class(name);
+            let builder = builder.lock().unwrap();
+            if let Some(alias) = &class.alias {
+                builder.with_meta("alias", BamlValue::String(alias.clone()));
+            }
+            for (name, property) in &class.properties {
+                let property_builder = builder.property(name);
+                let property_builder = property_builder.lock().unwrap();
+                set_attributes(
+                    &*property_builder,
+                    &property.alias,
+                    &property.description,
+                    property.skip,
+                );
+            }
+        }
+
+        for (name, enm) in &self.enums {
+            let builder = tb.r#enum(name);
+            let builder = builder.lock().unwrap();
+            if let Some(alias) = &enm.alias {
+                builder.with_meta("alias", BamlValue::String(alias.clone()));
+            }
+            for (name, value) in &enm.values {
+                let value_builder = builder.value(name);
+                let value_builder = value_builder.lock().unwrap();
+                set_attributes(
+                    &*value_builder,
+                    &value.alias,
+                    &value.description,
+                    value.skip,
+                );
+            }
+        }
+
+        Ok(tb)
+    }
+
+    /// `baml`, followed by a class declaration for every class with typed
+    /// properties, so that the types are parsed and checked by
+    /// [`TypeBuilder::add_baml`]. Diagnostics in `baml` keep their line
+    /// numbers.
+    fn to_baml(&self) -> String {
+        let mut baml = self.baml.clone().unwrap_or_default();
+        for (name, class) in &self.classes {
+            let typed = class
+                .properties
+                .iter()
+                .filter_map(|(name, p)| p.r#type.as_ref().map(|ty| (name, ty)))
+                .collect::<Vec<_>>();
+            if typed.is_empty() {
+                continue;
+            }
+            baml.push_str(&format!("\nclass {name} {{\n"));
+            for (name, ty) in typed {
+                baml.push_str(&format!("  {name} {ty}\n"));
+            }
+            baml.push_str("}\n");
+        }
+        baml
+    }
+}
+
+fn set_attributes(
+    builder: &impl WithMeta,
+    alias: &Option<String>,
+    description: &Option<String>,
+    skip: Option<bool>,
+) {
+    if let Some(alias) = alias {
+        builder.with_meta("alias", BamlValue::String(alias.clone()));
+    }
+    if let Some(description) = description {
+        builder.with_meta("description", BamlValue::String(description.clone()));
+    }
+    if let Some(skip) = skip {
+        builder.with_meta("skip", BamlValue::Bool(skip));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_baml() {
+        let options: TypeBuilderOptions = serde_json::from_value(serde_json::json!({
+            "classes": {
+                "User": {
+                    "properties": {
+                        "email": {"type": "string", "description": "Work email"},
+                        "name": {"alias": "full_name"}
+                    }
+                },
+                "Address": {
+                    "alias": "Location",
+                    "properties": {"city": {"type": "Hobby[]"}}
+                }
+            },
+            "baml": "enum Hobby {\n  SPORTS\n}\n"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            options.to_baml(),
+            "enum Hobby {\n  SPORTS\n}\n\nclass User {\n  email string\n}\n\nclass Address {\n  city Hobby[]\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let options = serde_json::from_value::<TypeBuilderOptions>(serde_json::json!({
+            "classes": {"User": {"properties": {"email": {"typ": "string"}}}}
+        }));
+        assert!(options.is_err());
+    }
+}
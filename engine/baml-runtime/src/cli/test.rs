@@ -0,0 +1,377 @@
+use anyhow::{Context, Result};
+use baml_types::BamlValue;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use web_time::Instant;
+
+use crate::{
+    internal::llm_client::LLMResponse, runtime_interface::ExperimentalTracingInterface,
+    BamlRuntime, FunctionResult, TestFailReason, TestStatus,
+};
+
+#[derive(clap::Args, Debug)]
+pub struct TestArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(
+        long = "function",
+        short = 'f',
+        help = "Only run tests of functions matching this name (supports * wildcards). May be repeated."
+    )]
+    functions: Vec<String>,
+    #[arg(
+        long = "test",
+        short = 't',
+        help = "Only run tests matching this name (supports * wildcards). May be repeated."
+    )]
+    tests: Vec<String>,
+    #[arg(
+        long,
+        help = "Maximum number of tests to run at once",
+        default_value = "10",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    parallel: u16,
+    #[arg(long, help = "List the selected tests without running them")]
+    list: bool,
+    #[arg(long, help = "Write a JUnit XML report to this path")]
+    junit_xml: Option<PathBuf>,
+    #[arg(long, help = "Write a JSON report to this path")]
+    json: Option<PathBuf>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum TestOutcome {
+    Passed,
+    /// The function ran, but its output was wrong.
+    Failed,
+    /// The function could not be run, or the LLM call failed.
+    Error,
+}
+
+#[derive(Serialize, Debug)]
+struct TestCaseReport {
+    function: String,
+    test: String,
+    outcome: TestOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    duration_ms: u64,
+}
+
+#[derive(Serialize, Debug)]
+struct TestReport {
+    passed: usize,
+    failed: usize,
+    errors: usize,
+    duration_ms: u64,
+    tests: Vec<TestCaseReport>,
+}
+
+impl TestArgs {
+    pub fn run(&self) -> Result<()> {
+        let runtime = BamlRuntime::from_directory(&self.from, std::env::vars().collect())
+            .context("Failed to build BAML runtime")?;
+
+        let selected = runtime
+            .inner
+            .ir
+            .walk_tests()
+            .filter(|t| {
+                matches_any(&self.functions, t.function().name())
+                    && matches_any(&self.tests, &t.test_case().name)
+            })
+            .map(|t| (t.function().name().to_string(), t.test_case().name.clone()))
+            .collect::<Vec<_>>();
+
+        if selected.is_empty() {
+            if self.functions.is_empty() && self.tests.is_empty() {
+                log::warn!("No tests found in {}", self.from.display());
+                return Ok(());
+            }
+            anyhow::bail!("No tests matched the given filters");
+        }
+
+        if self.list {
+            for (function, test) in &selected {
+                println!("{function}::{test}");
+            }
+            return Ok(());
+        }
+
+        log::info!(
+            "Running {} test{}",
+            selected.len(),
+            if selected.len() == 1 { "" } else { "s" }
+        );
+
+        let start = Instant::now();
+        let t = BamlRuntime::get_tokio_singleton()?;
+        let tests = t.block_on(
+            futures::stream::iter(
+                selected
+                    .iter()
+                    .map(|(function, test)| run_one(&runtime, function, test)),
+            )
+            .buffered(self.parallel as usize)
+            .inspect(log_result)
+            .collect::<Vec<_>>(),
+        );
+        if let Err(e) = runtime.flush() {
+            log::debug!("Error while flushing traces: {:#}", e);
+        }
+
+        let count = |outcome| tests.iter().filter(|t| t.outcome == outcome).count();
+        let report = TestReport {
+            passed: count(TestOutcome::Passed),
+            failed: count(TestOutcome::Failed),
+            errors: count(TestOutcome::Error),
+            duration_ms: start.elapsed().as_millis() as u64,
+            tests,
+        };
+
+        if let Some(path) = &self.json {
+            write_report(path, &serde_json::to_string_pretty(&report)?)?;
+        }
+        if let Some(path) = &self.junit_xml {
+            write_report(path, &report.to_junit_xml())?;
+        }
+
+        log::info!(
+            "{} passed, {} failed, {} errors in {:.2}s",
+            report.passed,
+            report.failed,
+            report.errors,
+            report.duration_ms as f64 / 1000.0
+        );
+
+        match report.failed + report.errors {
+            0 => Ok(()),
+            n => anyhow::bail!("{n} of {} tests did not pass", report.tests.len()),
+        }
+    }
+}
+
+async fn run_one(runtime: &BamlRuntime, function: &str, test: &str) -> TestCaseReport {
+    let ctx = runtime.create_ctx_manager(BamlValue::String("cli".to_string()), None);
+    let start = Instant::now();
+    let (response, _) = runtime
+        .run_test(function, test, &ctx, None::<fn(FunctionResult)>)
+        .await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    let (outcome, message) = match &response {
+        Ok(response) => match response.status() {
+            TestStatus::Pass => (TestOutcome::Passed, None),
+            TestStatus::Fail(reason) => {
+                let message = match reason {
+                    // The full error response is too noisy for a report.
+                    TestFailReason::TestLLMFailure(LLMResponse::LLMFailure(e)) => format!(
+                        "LLM call to {} failed ({}): {}",
+                        e.client, e.code, e.message
+                    ),
+                    _ => reason.to_string(),
+                };
+                let outcome = match reason {
+                    TestFailReason::TestUnspecified(_) | TestFailReason::TestLLMFailure(_) => {
+                        TestOutcome::Error
+                    }
+                    _ => TestOutcome::Failed,
+                };
+                (outcome, Some(message))
+            }
+        },
+        Err(e) => (TestOutcome::Error, Some(format!("{e:#}"))),
+    };
+
+    TestCaseReport {
+        function: function.to_string(),
+        test: test.to_string(),
+        outcome,
+        message,
+        duration_ms,
+    }
+}
+
+fn log_result(test: &TestCaseReport) {
+    let name = format!("{}::{}", test.function, test.test);
+    match (test.outcome, &test.message) {
+        (TestOutcome::Passed, _) => log::info!("PASS {name} ({}ms)", test.duration_ms),
+        (TestOutcome::Failed, message) => log::error!(
+            "FAIL {name} ({}ms)\n{}",
+            test.duration_ms,
+            message.as_deref().unwrap_or_default()
+        ),
+        (TestOutcome::Error, message) => log::error!(
+            "ERROR {name} ({}ms)\n{}",
+            test.duration_ms,
+            message.as_deref().unwrap_or_default()
+        ),
+    }
+}
+
+fn write_report(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write test report to {}", path.display()))?;
+    log::info!("Wrote test report to {}", path.display());
+    Ok(())
+}
+
+/// An empty filter list matches everything.
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    patterns.is_empty() || patterns.iter().any(|p| matches_pattern(p, name))
+}
+
+/// Match `name` against a pattern where `*` stands for any run of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard at all.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+impl TestReport {
+    fn to_junit_xml(&self) -> String {
+        let mut suites: IndexMap<&str, Vec<&TestCaseReport>> = IndexMap::new();
+        for test in &self.tests {
+            suites.entry(&test.function).or_default().push(test);
+        }
+
+        let seconds = |ms: u64| format!("{:.3}", ms as f64 / 1000.0);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"baml\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+            self.tests.len(),
+            self.failed,
+            self.errors,
+            seconds(self.duration_ms)
+        ));
+        for (function, tests) in suites {
+            let count = |outcome| tests.iter().filter(|t| t.outcome == outcome).count();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+                xml_escape(function),
+                tests.len(),
+                count(TestOutcome::Failed),
+                count(TestOutcome::Error),
+                seconds(tests.iter().map(|t| t.duration_ms).sum())
+            ));
+            for test in tests {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+                    xml_escape(function),
+                    xml_escape(&test.test),
+                    seconds(test.duration_ms)
+                ));
+                let tag = match test.outcome {
+                    TestOutcome::Passed => {
+                        xml.push_str("/>\n");
+                        continue;
+                    }
+                    TestOutcome::Failed => "failure",
+                    TestOutcome::Error => "error",
+                };
+                let message = test.message.as_deref().unwrap_or_default();
+                xml.push_str(&format!(
+                    ">\n      <{tag} message=\"{}\">{}</{tag}>\n    </testcase>\n",
+                    xml_escape(message.lines().next().unwrap_or_default()),
+                    xml_escape(message)
+                ));
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_wildcards() {
+        assert!(matches_pattern("ExtractResume", "ExtractResume"));
+        assert!(!matches_pattern("Extract", "ExtractResume"));
+        assert!(matches_pattern("Extract*", "ExtractResume"));
+        assert!(matches_pattern("*Resume", "ExtractResume"));
+        assert!(matches_pattern("E*t*e", "ExtractResume"));
+        assert!(!matches_pattern("*tt*", "ExtractResume"));
+        assert!(!matches_pattern("ab*ba", "aba"));
+        assert!(matches_any(&[], "anything"));
+    }
+
+    #[test]
+    fn junit_xml_report() {
+        let report = TestReport {
+            passed: 1,
+            failed: 1,
+            errors: 0,
+            duration_ms: 1500,
+            tests: vec![
+                TestCaseReport {
+                    function: "Classify".to_string(),
+                    test: "Simple".to_string(),
+                    outcome: TestOutcome::Passed,
+                    message: None,
+                    duration_ms: 500,
+                },
+                TestCaseReport {
+                    function: "Classify".to_string(),
+                    test: "Tricky".to_string(),
+                    outcome: TestOutcome::Failed,
+                    message: Some("@@assert {{ this < 2 }} failed".to_string()),
+                    duration_ms: 1000,
+                },
+            ],
+        };
+
+        assert_eq!(
+            report.to_junit_xml(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="baml" tests="2" failures="1" errors="0" time="1.500">
+  <testsuite name="Classify" tests="2" failures="1" errors="0" time="1.500">
+    <testcase classname="Classify" name="Simple" time="0.500"/>
+    <testcase classname="Classify" name="Tricky" time="1.000">
+      <failure message="@@assert {{ this &lt; 2 }} failed">@@assert {{ this &lt; 2 }} failed</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
@@ -80,6 +80,7 @@ impl IterOrchestrator for Arc<LLMProvider> {
     ) -> Result<OrchestratorNodeIterator> {
         if let Some(retry_policy) = self.retry_policy_name() {
             let policy = client_lookup.get_retry_policy(retry_policy, ctx)?;
+            let retry_on_parse_failure = policy.retry_on_parse_failure();
             Ok(policy
                 .into_iter()
                 .enumerate()
@@ -110,6 +111,13 @@ impl IterOrchestrator for Arc<LLMProvider> {
                         nodes
                             .into_iter()
                             .map(move |node| node.prefix(scope.clone()))
+                            .map(|node| {
+                                if retry_on_parse_failure {
+                                    node.with_retry_on_parse_failure()
+                                } else {
+                                    node
+                                }
+                            })
                             .collect::<Vec<_>>()
                     })
                 })
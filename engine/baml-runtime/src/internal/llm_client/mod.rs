@@ -3,19 +3,23 @@ use std::collections::{HashMap, HashSet};
 use colored::*;
 pub mod llm_provider;
 pub mod orchestrator;
+pub mod output_schema;
 pub mod primitive;
 
 mod properties_hander;
 pub mod retry_policy;
 mod strategy;
+#[cfg(test)]
+mod test_support;
 pub mod traits;
 
 use anyhow::Result;
 
-use baml_types::{BamlValueWithMeta, JinjaExpression, ResponseCheck};
+use baml_types::{BamlMediaType, BamlValueWithMeta, JinjaExpression, ResponseCheck};
 use internal_baml_core::ir::ClientWalker;
 use internal_baml_jinja::RenderedPrompt;
 use jsonish::BamlValueWithFlags;
+use output_schema::OutputMode;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 
@@ -44,6 +48,11 @@ pub fn parsed_value_to_response(baml_value: &BamlValueWithFlags) -> Result<Respo
     }))
 }
 
+/// The error for media of a type `provider` can't send.
+pub(crate) fn unsupported_media(provider: &str, media_type: BamlMediaType) -> anyhow::Error {
+    anyhow::anyhow!("{media_type} is not supported by provider {provider}")
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum ResolveMediaUrls {
     // there are 5 input formats:
@@ -76,6 +85,7 @@ pub struct ModelFeatures {
     pub anthropic_system_constraints: bool,
     pub resolve_media_urls: ResolveMediaUrls,
     pub allowed_metadata: AllowedMetadata,
+    pub output_mode: OutputMode,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -94,6 +104,16 @@ pub struct SupportedRequestModes {
     pub stream: Option<bool>,
 }
 
+/// Limits on a single request to a client, from its `request_timeout_ms` and
+/// `idle_timeout_ms` options. Unset means no limit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestTimeouts {
+    /// The whole request, including reading a streamed response.
+    pub request: Option<web_time::Duration>,
+    /// Each wait for the next chunk of a streamed response.
+    pub idle: Option<web_time::Duration>,
+}
+
 impl AllowedMetadata {
     pub fn is_allowed(&self, key: &str) -> bool {
         match self {
@@ -198,6 +218,11 @@ pub enum ErrorCode {
     ServerError,           // 500
     ServiceUnavailable,    // 503
 
+    // We gave up waiting on the provider
+    Timeout, // 408
+    // The caller cancelled the function call
+    Cancelled, // 499
+
     // We failed to parse the response
     UnsupportedResponse(u16),
 
@@ -205,19 +230,23 @@ pub enum ErrorCode {
     Other(u16),
 }
 
-impl ErrorCode {
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ErrorCode::InvalidAuthentication => "InvalidAuthentication (401)".into(),
-            ErrorCode::NotSupported => "NotSupported (403)".into(),
-            ErrorCode::RateLimited => "RateLimited (429)".into(),
-            ErrorCode::ServerError => "ServerError (500)".into(),
-            ErrorCode::ServiceUnavailable => "ServiceUnavailable (503)".into(),
-            ErrorCode::UnsupportedResponse(code) => format!("BadResponse {}", code),
-            ErrorCode::Other(code) => format!("Unspecified error code: {}", code),
+            ErrorCode::InvalidAuthentication => write!(f, "InvalidAuthentication (401)"),
+            ErrorCode::NotSupported => write!(f, "NotSupported (403)"),
+            ErrorCode::RateLimited => write!(f, "RateLimited (429)"),
+            ErrorCode::ServerError => write!(f, "ServerError (500)"),
+            ErrorCode::ServiceUnavailable => write!(f, "ServiceUnavailable (503)"),
+            ErrorCode::Timeout => write!(f, "Timeout (408)"),
+            ErrorCode::Cancelled => write!(f, "Cancelled (499)"),
+            ErrorCode::UnsupportedResponse(code) => write!(f, "BadResponse {}", code),
+            ErrorCode::Other(code) => write!(f, "Unspecified error code: {}", code),
         }
     }
+}
 
+impl ErrorCode {
     pub fn from_status(status: StatusCode) -> Self {
         match status.as_u16() {
             401 => ErrorCode::InvalidAuthentication,
@@ -236,6 +265,8 @@ impl ErrorCode {
             429 => ErrorCode::RateLimited,
             500 => ErrorCode::ServerError,
             503 => ErrorCode::ServiceUnavailable,
+            408 => ErrorCode::Timeout,
+            499 => ErrorCode::Cancelled,
             code => ErrorCode::Other(code),
         }
     }
@@ -247,6 +278,8 @@ impl ErrorCode {
             ErrorCode::RateLimited => 429,
             ErrorCode::ServerError => 500,
             ErrorCode::ServiceUnavailable => 503,
+            ErrorCode::Timeout => 408,
+            ErrorCode::Cancelled => 499,
             ErrorCode::UnsupportedResponse(code) => *code,
             ErrorCode::Other(code) => *code,
         }
@@ -273,6 +306,9 @@ pub struct LLMCompleteResponseMetadata {
     pub prompt_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
+    /// Whether the response was served from the response cache instead of
+    /// the provider.
+    pub cached: bool,
 }
 
 // This is how the response gets logged if you print the result to the console.
@@ -282,10 +318,11 @@ impl std::fmt::Display for LLMCompleteResponse {
             f,
             "{}",
             format!(
-                "Client: {} ({}) - {}ms. StopReason: {}",
+                "Client: {} ({}) - {}ms{}. StopReason: {}",
                 self.client,
                 self.model,
                 self.latency.as_millis(),
+                if self.metadata.cached { " (cached)" } else { "" },
                 self.metadata.finish_reason.as_deref().unwrap_or("unknown")
             )
             .yellow()
@@ -304,10 +341,11 @@ impl crate::tracing::Visualize for LLMCompleteResponse {
             format!(
                 "{}",
                 format!(
-                    "Client: {} ({}) - {}ms. StopReason: {}. Tokens(in/out): {}/{}",
+                    "Client: {} ({}) - {}ms{}. StopReason: {}. Tokens(in/out): {}/{}",
                     self.client,
                     self.model,
                     self.latency.as_millis(),
+                    if self.metadata.cached { " (cached)" } else { "" },
                     self.metadata.finish_reason.as_deref().unwrap_or("unknown"),
                     self.metadata
                         .prompt_tokens
@@ -364,7 +402,7 @@ impl crate::tracing::Visualize for LLMErrorResponse {
         }
         s.push(format!(
             "{}",
-            format!("---ERROR ({})---", self.code.to_string()).red()
+            format!("---ERROR ({})---", self.code).red()
         ));
         s.push(format!(
             "{}",
@@ -7,14 +7,14 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response, traits::{WithPrompt, WithSingleCallable}, LLMResponse, ResponseBamlValue
+            parsed_value_to_response, traits::{WithClientProperties, WithPrompt, WithSingleCallable}, LLMResponse, ResponseBamlValue
         },
         prompt_renderer::PromptRenderer,
     },
-    RuntimeContext,
+    ResponseCache, RuntimeContext,
 };
 
-use super::{OrchestrationScope, OrchestratorNodeIterator};
+use super::{limits::CallLimits, OrchestrationScope, OrchestratorNodeIterator};
 
 pub async fn orchestrate(
     iter: OrchestratorNodeIterator,
@@ -34,6 +34,8 @@ pub async fn orchestrate(
 ) {
     let mut results = Vec::new();
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
+    let limits = CallLimits::new(prompt.timeout(), ctx.cancellation.clone());
+    let mut after_parse_failure = false;
 
     for node in iter {
         let prompt = match node.render_prompt(ir, prompt, ctx, params).await {
@@ -48,31 +50,96 @@ pub async fn orchestrate(
                 continue;
             }
         };
-        let response = node.single_call(&ctx, &prompt).await;
+        // A call that is cancelled or out of time makes no further requests.
+        if let Some(interruption) = limits.check() {
+            let start = (web_time::SystemTime::now(), web_time::Instant::now());
+            results.push((
+                node.scope,
+                interruption.into_response(&node, prompt, start),
+                None,
+                None,
+            ));
+            break;
+        }
+        let cache = ctx.response_cache.as_ref().map(|cache| {
+            let key = ResponseCache::key(
+                node.provider.name(),
+                node.provider.provider(),
+                &node.provider.resolved_options(),
+                ctx.output_schema.as_deref(),
+                &prompt,
+            );
+            (cache, key)
+        });
+        let start = (web_time::SystemTime::now(), web_time::Instant::now());
+        let attempt = limits.attempt(*node.provider.request_timeouts());
+        // A response that failed to parse is retried against the LLM, never
+        // looked up again. The cache counts against the attempt's limits like
+        // the request it stands in for; an interrupted lookup is a miss, and
+        // the request then reports the interruption.
+        let cached = match &cache {
+            Some((cache, key)) if !after_parse_failure => {
+                let options = node.provider.request_options();
+                let lookup = cache.get(key, node.provider.name(), options, &prompt);
+                attempt.run(lookup, false).await.ok().flatten()
+            }
+            _ => None,
+        };
+        let (response, interruption) = match cached {
+            Some(response) => (LLMResponse::Success(response), None),
+            None => match attempt.run(node.single_call(ctx, &prompt), false).await {
+                Ok(response) => (response, None),
+                Err(interruption) => (
+                    interruption.into_response(&node, prompt, start),
+                    Some(interruption),
+                ),
+            },
+        };
         let parsed_response = match &response {
             LLMResponse::Success(s) => Some(parse_fn(&s.content)),
             _ => None,
         };
 
         let sleep_duration = node.error_sleep_duration().cloned();
+        let retry_on_parse_failure = node.retry_on_parse_failure;
         let (parsed_response, response_with_constraints) = match parsed_response {
                 Some(Ok(v)) => (Some(Ok(v.clone())), Some(parsed_value_to_response(&v))),
                 Some(Err(e)) => (None, Some(Err(e))),
                 None => (None, None),
             };
+        // Only responses that parse, asserts included, are worth reusing.
+        if let (Some((cache, key)), LLMResponse::Success(s), Some(Ok(_))) =
+            (&cache, &response, &response_with_constraints)
+        {
+            if !s.metadata.cached {
+                // The request is done, so only the call's own limits apply.
+                // A write cut short by them just isn't reused.
+                let _ = limits
+                    .attempt(Default::default())
+                    .run(cache.set(key, s), false)
+                    .await;
+            }
+        }
+        after_parse_failure = matches!(response_with_constraints, Some(Err(_)));
         results.push((node.scope, response, parsed_response, response_with_constraints));
 
-        // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
-        if results
-            .last()
-            .map_or(false, |(_, r, _, _)| matches!(r, LLMResponse::Success(_)))
-        {
+        // We break out of the loop once an LLM responded, unless the result couldn't be parsed
+        // and the node opted into treating parse failures as retryable.
+        if results.last().map_or(false, |(_, r, _, v)| {
+            matches!(r, LLMResponse::Success(_))
+                && !(retry_on_parse_failure && matches!(v, Some(Err(_))))
+        }) {
             break;
-        } else {
-            if let Some(duration) = sleep_duration {
-                total_sleep_duration += duration;
-                async_std::task::sleep(duration).await;
-            }
+        }
+        // Once the call is cancelled or out of time, there's no point trying
+        // another node.
+        if interruption.is_some_and(|i| i.ends_call()) {
+            break;
+        }
+        if let Some(duration) = sleep_duration {
+            total_sleep_duration += duration;
+            // If the call ends while we wait, the next attempt reports it.
+            let _ = limits.sleep(duration).await;
         }
     }
 
@@ -0,0 +1,254 @@
+use std::future::Future;
+
+use futures::future::{select, Either};
+use internal_baml_jinja::RenderedPrompt;
+use web_time::{Duration, Instant, SystemTime};
+
+use crate::{
+    internal::llm_client::{
+        traits::WithClientProperties, ErrorCode, LLMErrorResponse, LLMResponse, RequestTimeouts,
+    },
+    CancellationToken,
+};
+
+use super::OrchestratorNode;
+
+/// Why a request was abandoned before the provider finished responding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interruption {
+    /// The caller cancelled the function call.
+    Cancelled,
+    /// The function's `timeout_ms` ran out.
+    Deadline(Duration),
+    /// The client's `request_timeout_ms` ran out.
+    RequestTimeout(Duration),
+    /// The client's `idle_timeout_ms` passed without a new chunk of the stream.
+    IdleTimeout(Duration),
+}
+
+impl Interruption {
+    /// Whether the whole call is over, as opposed to just this attempt. Only
+    /// per-request timeouts move on to the next retry or fallback.
+    pub fn ends_call(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::Deadline(_))
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Cancelled => "Function call was cancelled".to_string(),
+            Self::Deadline(t) => format!(
+                "Function call did not finish within timeout_ms ({}ms)",
+                t.as_millis()
+            ),
+            Self::RequestTimeout(t) => format!(
+                "Request did not finish within request_timeout_ms ({}ms)",
+                t.as_millis()
+            ),
+            Self::IdleTimeout(t) => {
+                format!("No data received for idle_timeout_ms ({}ms)", t.as_millis())
+            }
+        }
+    }
+
+    pub fn into_response(
+        self,
+        node: &OrchestratorNode,
+        prompt: RenderedPrompt,
+        (start_time, start): (SystemTime, Instant),
+    ) -> LLMResponse {
+        LLMResponse::LLMFailure(LLMErrorResponse {
+            client: node.provider.name().into(),
+            model: None,
+            prompt,
+            start_time,
+            latency: start.elapsed(),
+            request_options: node.provider.request_options().clone(),
+            message: self.message(),
+            code: match self {
+                Self::Cancelled => ErrorCode::Cancelled,
+                _ => ErrorCode::Timeout,
+            },
+        })
+    }
+}
+
+/// Limits shared by every attempt of one function call.
+pub struct CallLimits {
+    cancellation: CancellationToken,
+    /// The function's `timeout_ms`, and when it runs out.
+    deadline: Option<(Duration, Instant)>,
+}
+
+impl CallLimits {
+    pub fn new(timeout: Option<Duration>, cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation,
+            deadline: timeout.map(|t| (t, Instant::now() + t)),
+        }
+    }
+
+    /// Why no further attempts may be made, if any.
+    pub fn check(&self) -> Option<Interruption> {
+        if self.cancellation.is_cancelled() {
+            return Some(Interruption::Cancelled);
+        }
+        match self.deadline {
+            Some((timeout, at)) if Instant::now() >= at => Some(Interruption::Deadline(timeout)),
+            _ => None,
+        }
+    }
+
+    /// Limits for one attempt against a client with the given timeouts.
+    pub fn attempt(&self, timeouts: RequestTimeouts) -> AttemptLimits<'_> {
+        AttemptLimits {
+            call: self,
+            request: timeouts.request.map(|t| (t, Instant::now() + t)),
+            idle: timeouts.idle,
+        }
+    }
+
+    /// Wait before the next retry, returning early if the call ends.
+    pub async fn sleep(&self, duration: Duration) -> Result<(), Interruption> {
+        self.attempt(RequestTimeouts::default())
+            .run(async_std::task::sleep(duration), false)
+            .await
+    }
+}
+
+/// Limits on one request, on top of those of its call.
+pub struct AttemptLimits<'a> {
+    call: &'a CallLimits,
+    /// The client's `request_timeout_ms`, and when it runs out.
+    request: Option<(Duration, Instant)>,
+    idle: Option<Duration>,
+}
+
+impl AttemptLimits<'_> {
+    /// Run part of a request, giving up if it is cancelled or runs out of
+    /// time. `idle` also applies the client's `idle_timeout_ms`, for waiting
+    /// on a chunk of a stream.
+    pub async fn run<T>(
+        &self,
+        fut: impl Future<Output = T>,
+        idle: bool,
+    ) -> Result<T, Interruption> {
+        if self.call.cancellation.is_cancelled() {
+            return Err(Interruption::Cancelled);
+        }
+
+        let now = Instant::now();
+        let limits = [
+            self.call
+                .deadline
+                .map(|(t, at)| (Interruption::Deadline(t), at)),
+            self.request
+                .map(|(t, at)| (Interruption::RequestTimeout(t), at)),
+            self.idle
+                .filter(|_| idle)
+                .map(|t| (Interruption::IdleTimeout(t), now + t)),
+        ];
+        let first = limits.into_iter().flatten().min_by_key(|(_, at)| *at);
+
+        let timeout = async move {
+            match first {
+                Some((interruption, at)) => {
+                    async_std::task::sleep(at.saturating_duration_since(now)).await;
+                    interruption
+                }
+                None => futures::future::pending().await,
+            }
+        };
+        let interrupted = async move {
+            match select(
+                Box::pin(self.call.cancellation.cancelled()),
+                Box::pin(timeout),
+            )
+            .await
+            {
+                Either::Left(_) => Interruption::Cancelled,
+                Either::Right((interruption, _)) => interruption,
+            }
+        };
+
+        match select(Box::pin(fut), Box::pin(interrupted)).await {
+            Either::Left((value, _)) => Ok(value),
+            Either::Right((interruption, _)) => Err(interruption),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_timeout_interrupts_attempt() {
+        let call = CallLimits::new(None, CancellationToken::new());
+        let attempt = call.attempt(RequestTimeouts {
+            request: Some(Duration::from_millis(10)),
+            idle: None,
+        });
+
+        let result = attempt
+            .run(async_std::task::sleep(Duration::from_secs(5)), false)
+            .await;
+        assert_eq!(
+            result,
+            Err(Interruption::RequestTimeout(Duration::from_millis(10)))
+        );
+        assert_eq!(call.check(), None);
+    }
+
+    #[tokio::test]
+    async fn earliest_limit_wins() {
+        let call = CallLimits::new(Some(Duration::from_millis(10)), CancellationToken::new());
+        let attempt = call.attempt(RequestTimeouts {
+            request: Some(Duration::from_secs(5)),
+            idle: Some(Duration::from_secs(5)),
+        });
+
+        let result = attempt
+            .run(async_std::task::sleep(Duration::from_secs(5)), true)
+            .await;
+        assert_eq!(
+            result,
+            Err(Interruption::Deadline(Duration::from_millis(10)))
+        );
+        assert_eq!(
+            call.check(),
+            Some(Interruption::Deadline(Duration::from_millis(10)))
+        );
+    }
+
+    #[tokio::test]
+    async fn cancellation_interrupts_attempt() {
+        let token = CancellationToken::new();
+        let call = CallLimits::new(None, token.clone());
+        let attempt = call.attempt(RequestTimeouts::default());
+
+        let canceller = async {
+            async_std::task::sleep(Duration::from_millis(10)).await;
+            token.cancel();
+        };
+        let (result, _) = futures::join!(
+            attempt.run(async_std::task::sleep(Duration::from_secs(5)), false),
+            canceller
+        );
+        assert_eq!(result, Err(Interruption::Cancelled));
+        assert_eq!(call.check(), Some(Interruption::Cancelled));
+        assert_eq!(
+            call.sleep(Duration::from_secs(5)).await,
+            Err(Interruption::Cancelled)
+        );
+    }
+
+    #[tokio::test]
+    async fn finished_work_is_not_interrupted() {
+        let call = CallLimits::new(Some(Duration::from_secs(5)), CancellationToken::new());
+        let attempt = call.attempt(RequestTimeouts {
+            request: Some(Duration::from_secs(5)),
+            idle: Some(Duration::from_secs(5)),
+        });
+        assert_eq!(attempt.run(async { 42 }, true).await, Ok(42));
+    }
+}
@@ -1,9 +1,10 @@
 mod call;
+mod limits;
 mod stream;
 
 use web_time::Duration; // Add this line
 
-use crate::RenderCurlSettings;
+use crate::{HTTPRequest, RenderCurlSettings};
 use crate::{
     internal::prompt_renderer::PromptRenderer, runtime_interface::InternalClientLookup,
     RuntimeContext,
@@ -31,6 +32,10 @@ use std::{collections::HashMap, sync::Arc};
 pub struct OrchestratorNode {
     pub scope: OrchestrationScope,
     pub provider: Arc<LLMPrimitiveProvider>,
+    /// When set, a response that fails to parse (or fails an `@assert`) is
+    /// treated like a failed request and the orchestrator moves on to the
+    /// next node instead of returning.
+    pub retry_on_parse_failure: bool,
 }
 
 impl std::fmt::Display for ExecutionScope {
@@ -65,6 +70,7 @@ impl OrchestratorNode {
         OrchestratorNode {
             scope: scope.into(),
             provider,
+            retry_on_parse_failure: false,
         }
     }
 
@@ -72,6 +78,14 @@ impl OrchestratorNode {
         OrchestratorNode {
             scope: self.scope.prefix_scopes(scope.into().scope),
             provider: self.provider.clone(),
+            retry_on_parse_failure: self.retry_on_parse_failure,
+        }
+    }
+
+    pub fn with_retry_on_parse_failure(self) -> OrchestratorNode {
+        OrchestratorNode {
+            retry_on_parse_failure: true,
+            ..self
         }
     }
 
@@ -191,6 +205,15 @@ impl WithRenderRawCurl for OrchestratorNode {
             .render_raw_curl(ctx, prompt, render_settings)
             .await
     }
+
+    async fn render_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        self.provider.render_request(ctx, prompt, stream).await
+    }
 }
 
 impl WithSingleCallable for OrchestratorNode {
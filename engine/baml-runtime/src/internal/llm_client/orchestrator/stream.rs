@@ -8,14 +8,14 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response, traits::{WithPrompt, WithStreamable}, LLMErrorResponse, LLMResponse, ResponseBamlValue
+            parsed_value_to_response, traits::{WithClientProperties, WithPrompt, WithStreamable}, LLMErrorResponse, LLMResponse, ResponseBamlValue
         },
         prompt_renderer::PromptRenderer,
     },
-    FunctionResult, RuntimeContext,
+    FunctionResult, ResponseCache, RuntimeContext,
 };
 
-use super::{OrchestrationScope, OrchestratorNodeIterator};
+use super::{limits::CallLimits, OrchestrationScope, OrchestratorNodeIterator};
 
 pub async fn orchestrate_stream<F>(
     iter: OrchestratorNodeIterator,
@@ -23,7 +23,7 @@ pub async fn orchestrate_stream<F>(
     ctx: &RuntimeContext,
     prompt: &PromptRenderer,
     params: &BamlValue,
-    partial_parse_fn: impl Fn(&str) -> Result<BamlValueWithFlags>,
+    mut partial_parse_fn: impl FnMut(&str) -> Result<BamlValueWithFlags>,
     parse_fn: impl Fn(&str) -> Result<BamlValueWithFlags>,
     on_event: Option<F>,
 ) -> (
@@ -40,6 +40,8 @@ where
 {
     let mut results = Vec::new();
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
+    let limits = CallLimits::new(prompt.timeout(), ctx.cancellation.clone());
+    let mut after_parse_failure = false;
 
     //advanced curl viewing, use render_raw_curl on each node. TODO
     for node in iter {
@@ -56,48 +58,114 @@ where
             }
         };
 
-        let (system_start, instant_start) = (web_time::SystemTime::now(), web_time::Instant::now());
-        let stream_res = node.stream(ctx, &prompt).await;
-        let final_response = match stream_res {
-            Ok(response) => response
-                .map(|stream_part| {
-                    if let Some(on_event) = on_event.as_ref() {
-                        match &stream_part {
-                            LLMResponse::Success(s) => {
-                                let parsed = partial_parse_fn(&s.content);
-                                let (parsed, response_value) = match parsed {
-                                    Ok(v) => (Some(Ok(v.clone())), Some(parsed_value_to_response(&v))),
-                                    Err(e) => (None, Some(Err(e))),
-                                };
-                                on_event(FunctionResult::new(
-                                    node.scope.clone(),
-                                    LLMResponse::Success(s.clone()),
-                                    parsed,
-                                    response_value,
-                                ));
+        // A call that is cancelled or out of time makes no further requests.
+        if let Some(interruption) = limits.check() {
+            let start = (web_time::SystemTime::now(), web_time::Instant::now());
+            results.push((
+                node.scope,
+                interruption.into_response(&node, prompt, start),
+                None,
+                None,
+            ));
+            break;
+        }
+
+        let cache = ctx.response_cache.as_ref().map(|cache| {
+            let key = ResponseCache::key(
+                node.provider.name(),
+                node.provider.provider(),
+                &node.provider.resolved_options(),
+                ctx.output_schema.as_deref(),
+                &prompt,
+            );
+            (cache, key)
+        });
+        let start = (web_time::SystemTime::now(), web_time::Instant::now());
+        let attempt = limits.attempt(*node.provider.request_timeouts());
+        // A response that failed to parse is retried against the LLM, never
+        // looked up again. The cache counts against the attempt's limits like
+        // the request it stands in for; an interrupted lookup is a miss, and
+        // the request then reports the interruption.
+        let cached = match &cache {
+            Some((cache, key)) if !after_parse_failure => {
+                let options = node.provider.request_options();
+                let lookup = cache.get(key, node.provider.name(), options, &prompt);
+                attempt.run(lookup, false).await.ok().flatten()
+            }
+            _ => None,
+        };
+
+        let (final_response, interruption) = if let Some(response) = cached {
+            // A cached response arrives all at once, as a single event.
+            if let Some(on_event) = on_event.as_ref() {
+                let (parsed, response_value) = match partial_parse_fn(&response.content) {
+                    Ok(v) => (Some(Ok(v.clone())), Some(parsed_value_to_response(&v))),
+                    Err(e) => (None, Some(Err(e))),
+                };
+                on_event(FunctionResult::new(
+                    node.scope.clone(),
+                    LLMResponse::Success(response.clone()),
+                    parsed,
+                    response_value,
+                ));
+            }
+            (LLMResponse::Success(response), None)
+        } else {
+            match attempt.run(node.stream(ctx, &prompt), false).await {
+                Ok(Ok(mut response)) => {
+                    let mut last = None;
+                    let mut interruption = None;
+                    loop {
+                        match attempt.run(response.next(), true).await {
+                            Ok(Some(stream_part)) => {
+                                if let Some(on_event) = on_event.as_ref() {
+                                    if let LLMResponse::Success(s) = &stream_part {
+                                        let parsed = partial_parse_fn(&s.content);
+                                        let (parsed, response_value) = match parsed {
+                                            Ok(v) => (Some(Ok(v.clone())), Some(parsed_value_to_response(&v))),
+                                            Err(e) => (None, Some(Err(e))),
+                                        };
+                                        on_event(FunctionResult::new(
+                                            node.scope.clone(),
+                                            LLMResponse::Success(s.clone()),
+                                            parsed,
+                                            response_value,
+                                        ));
+                                    }
+                                }
+                                last = Some(stream_part);
+                            }
+                            Ok(None) => break,
+                            Err(i) => {
+                                interruption = Some(i);
+                                break;
                             }
-                            _ => {}
                         }
                     }
-                    stream_part
-                })
-                .fold(None, |_, current| Some(current))
-                .await
-                .unwrap_or_else(|| {
-                    LLMResponse::LLMFailure(LLMErrorResponse {
-                        client: node.provider.name().into(),
-                        model: None,
-                        prompt,
-                        start_time: system_start,
-                        latency: instant_start.elapsed(),
-                        request_options: node.provider.request_options().clone(),
-                        message: "Stream ended without response".to_string(),
-                        code: crate::internal::llm_client::ErrorCode::from_u16(2),
-                    })
-                }),
-            Err(response) => response,
+                    match interruption {
+                        // A partial response is dropped along with the request.
+                        Some(i) => (i.into_response(&node, prompt, start), Some(i)),
+                        None => (
+                            last.unwrap_or_else(|| {
+                                LLMResponse::LLMFailure(LLMErrorResponse {
+                                    client: node.provider.name().into(),
+                                    model: None,
+                                    prompt,
+                                    start_time: start.0,
+                                    latency: start.1.elapsed(),
+                                    request_options: node.provider.request_options().clone(),
+                                    message: "Stream ended without response".to_string(),
+                                    code: crate::internal::llm_client::ErrorCode::from_u16(2),
+                                })
+                            }),
+                            None,
+                        ),
+                    }
+                }
+                Ok(Err(response)) => (response, None),
+                Err(i) => (i.into_response(&node, prompt, start), Some(i)),
+            }
         };
-
         let parsed_response = match &final_response {
             LLMResponse::Success(s) => Some(parse_fn(&s.content)),
             _ => None,
@@ -107,21 +175,42 @@ where
             Some(Err(e)) => (None, Some(Err(e))),
             None => (None, None),
         };
+        // Only responses that parse, asserts included, are worth reusing.
+        if let (Some((cache, key)), LLMResponse::Success(s), Some(Ok(_))) =
+            (&cache, &final_response, &response_value)
+        {
+            if !s.metadata.cached {
+                // The request is done, so only the call's own limits apply.
+                // A write cut short by them just isn't reused.
+                let _ = limits
+                    .attempt(Default::default())
+                    .run(cache.set(key, s), false)
+                    .await;
+            }
+        }
+        after_parse_failure = matches!(response_value, Some(Err(_)));
             // parsed_response.map(|r| r.and_then(|v| parsed_value_to_response(v)));
         let sleep_duration = node.error_sleep_duration().cloned();
+        let retry_on_parse_failure = node.retry_on_parse_failure;
         results.push((node.scope, final_response, parsed_response, response_value));
 
-        // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
-        if results
-            .last()
-            .map_or(false, |(_, r, _, _)| matches!(r, LLMResponse::Success(_)))
-        {
+        // We break out of the loop once an LLM responded, unless the result couldn't be parsed
+        // and the node opted into treating parse failures as retryable.
+        if results.last().map_or(false, |(_, r, _, v)| {
+            matches!(r, LLMResponse::Success(_))
+                && !(retry_on_parse_failure && matches!(v, Some(Err(_))))
+        }) {
             break;
-        } else {
-            if let Some(duration) = sleep_duration {
-                total_sleep_duration += duration;
-                async_std::task::sleep(duration).await;
-            }
+        }
+        // Once the call is cancelled or out of time, there's no point trying
+        // another node.
+        if interruption.is_some_and(|i| i.ends_call()) {
+            break;
+        }
+        if let Some(duration) = sleep_duration {
+            total_sleep_duration += duration;
+            // If the call ends while we wait, the next attempt reports it.
+            let _ = limits.sleep(duration).await;
         }
     }
 
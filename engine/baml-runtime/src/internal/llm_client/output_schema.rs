@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use internal_baml_jinja::{json_schema::JsonSchemaDialect, types::OutputFormatContent};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::RuntimeContext;
+
+/// How a client asks the model for structured output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// The schema is only described in the prompt via `ctx.output_format`.
+    #[default]
+    Prompt,
+    /// The schema is sent as the single tool the model is forced to call.
+    Tool,
+    /// The schema is sent as the provider's structured output / response
+    /// schema setting.
+    JsonSchema,
+}
+
+impl OutputMode {
+    /// The schema to send along with the request, if this mode sends one.
+    pub fn schema(self, ctx: &RuntimeContext) -> Option<Arc<OutputSchema>> {
+        match self {
+            OutputMode::Prompt => None,
+            OutputMode::Tool | OutputMode::JsonSchema => ctx.output_schema.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputMode::Prompt => write!(f, "prompt"),
+            OutputMode::Tool => write!(f, "tool"),
+            OutputMode::JsonSchema => write!(f, "json_schema"),
+        }
+    }
+}
+
+/// Key the return value is nested under when the function does not return a
+/// class. Tool parameters and structured outputs must be JSON objects.
+const WRAPPER_KEY: &str = "output";
+
+/// The return type of a function, in the shapes providers accept for tool
+/// parameters and response schemas.
+#[derive(Debug)]
+pub struct OutputSchema {
+    /// Name of the tool (or schema) the model is asked to produce, this is
+    /// always the BAML function name.
+    pub name: String,
+    json_schema: Value,
+    openapi_schema: Value,
+    wrapped: bool,
+}
+
+impl OutputSchema {
+    pub fn new(name: &str, output_format: &OutputFormatContent) -> Self {
+        let json_schema = output_format.json_schema(JsonSchemaDialect::JsonSchema);
+        let openapi_schema = output_format.json_schema(JsonSchemaDialect::OpenApi);
+        let wrapped = json_schema.get("properties").is_none();
+
+        Self {
+            name: name.to_string(),
+            json_schema: if wrapped {
+                wrap(json_schema, JsonSchemaDialect::JsonSchema)
+            } else {
+                json_schema
+            },
+            openapi_schema: if wrapped {
+                wrap(openapi_schema, JsonSchemaDialect::OpenApi)
+            } else {
+                openapi_schema
+            },
+            wrapped,
+        }
+    }
+
+    /// Schema for OpenAI, Anthropic and Bedrock.
+    pub fn json_schema(&self) -> &Value {
+        &self.json_schema
+    }
+
+    /// Schema for Gemini (Google AI and Vertex).
+    pub fn openapi_schema(&self) -> &Value {
+        &self.openapi_schema
+    }
+
+    pub fn description(&self) -> String {
+        format!("Respond with the output of {}.", self.name)
+    }
+
+    /// Turns the JSON a model produced for the schema back into the text BAML
+    /// parses: the arguments themselves when the function returns a class, or
+    /// the value under the wrapper key otherwise. Strings are returned without
+    /// quotes, the same as a prompt based response would be.
+    ///
+    /// `arguments` may be incomplete while streaming.
+    pub fn unwrap_arguments(&self, arguments: &str) -> String {
+        if !self.wrapped {
+            return arguments.to_string();
+        }
+
+        if let Ok(Value::Object(mut obj)) = serde_json::from_str::<Value>(arguments) {
+            return match obj.remove(WRAPPER_KEY) {
+                Some(Value::String(s)) => s,
+                Some(value) => value.to_string(),
+                None => arguments.to_string(),
+            };
+        }
+
+        // Partial JSON: strip everything up to the wrapper key and hand the
+        // rest to the parser, which handles incomplete values.
+        let Some(value) = strip_wrapper_prefix(arguments) else {
+            // Nothing of the value has arrived yet.
+            let compact: String = arguments.chars().filter(|c| !c.is_whitespace()).collect();
+            if format!("{{\"{WRAPPER_KEY}\":").starts_with(&compact) {
+                return String::new();
+            }
+            return arguments.to_string();
+        };
+        match value.strip_prefix('"') {
+            Some(partial) => unescape_partial_string(partial),
+            None => value.to_string(),
+        }
+    }
+}
+
+fn wrap(schema: Value, dialect: JsonSchemaDialect) -> Value {
+    let mut schema = schema;
+    let defs = schema.as_object_mut().and_then(|obj| obj.remove("$defs"));
+
+    let mut wrapped = json!({
+        "type": "object",
+        "properties": { WRAPPER_KEY: schema },
+        "required": [WRAPPER_KEY],
+    });
+    if dialect == JsonSchemaDialect::JsonSchema {
+        wrapped["additionalProperties"] = Value::Bool(false);
+    }
+    if let Some(defs) = defs {
+        wrapped["$defs"] = defs;
+    }
+    wrapped
+}
+
+fn strip_wrapper_prefix(arguments: &str) -> Option<&str> {
+    let rest = arguments.trim_start().strip_prefix('{')?.trim_start();
+    let rest = rest
+        .strip_prefix(&format!("\"{WRAPPER_KEY}\""))?
+        .trim_start();
+    let rest = rest.strip_prefix(':')?.trim_start();
+    // Drop the closing brace of the wrapper if it already arrived.
+    Some(match rest.trim_end().strip_suffix('}') {
+        Some(inner) if !rest.starts_with('{') => inner.trim_end(),
+        _ => rest,
+    })
+}
+
+fn unescape_partial_string(partial: &str) -> String {
+    let mut out = String::with_capacity(partial.len());
+    let mut chars = partial.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('b') => out.push('\u{8}'),
+                Some('f') => out.push('\u{c}'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        Some(c) if code.len() == 4 => out.push(c),
+                        _ => break,
+                    }
+                }
+                Some(other) => out.push(other),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::FieldType;
+
+    use super::*;
+
+    fn schema_for(target: FieldType) -> OutputSchema {
+        OutputSchema::new("Extract", &OutputFormatContent::target(target).build())
+    }
+
+    #[test]
+    fn non_class_outputs_are_wrapped() {
+        let schema = schema_for(FieldType::list(FieldType::string()));
+        assert_eq!(
+            schema.json_schema(),
+            &json!({
+                "type": "object",
+                "properties": {
+                    "output": { "type": "array", "items": { "type": "string" } },
+                },
+                "required": ["output"],
+                "additionalProperties": false,
+            })
+        );
+        assert_eq!(
+            schema.unwrap_arguments(r#"{"output": ["a", "b"]}"#),
+            r#"["a","b"]"#
+        );
+    }
+
+    #[test]
+    fn unwrap_partial_arguments() {
+        let schema = schema_for(FieldType::list(FieldType::int()));
+        assert_eq!(schema.unwrap_arguments(r#"{"output": [1, 2"#), "[1, 2");
+        assert_eq!(schema.unwrap_arguments(r#"{"outp"#), "");
+
+        let schema = schema_for(FieldType::string());
+        assert_eq!(schema.unwrap_arguments(r#"{"output": "hello"}"#), "hello");
+        assert_eq!(
+            schema.unwrap_arguments(r#"{"output": "line\nbreak \"quoted"#),
+            "line\nbreak \"quoted"
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use crate::internal::llm_client::{
+    output_schema::{OutputMode, OutputSchema},
     properties_hander::PropertiesHandler,
     traits::{ToProviderMessage, ToProviderMessageExt, WithClientProperties},
-    AllowedMetadata, ResolveMediaUrls, SupportedRequestModes,
+    unsupported_media, AllowedMetadata, RequestTimeouts, ResolveMediaUrls, SupportedRequestModes,
 };
 use std::collections::HashMap;
 
 use anyhow::{Context, Result};
-use baml_types::{BamlMedia, BamlMediaContent};
+use baml_types::{BamlMedia, BamlMediaContent, BamlMediaType};
 use eventsource_stream::Eventsource;
 use futures::StreamExt;
 use internal_baml_core::ir::ClientWalker;
@@ -18,7 +19,7 @@ use crate::{
     client_registry::ClientProperty,
     internal::llm_client::{
         primitive::{
-            anthropic::types::{AnthropicMessageResponse, StopReason},
+            anthropic::types::{AnthropicMessageContent, AnthropicMessageResponse, StopReason},
             request::{make_parsed_request, make_request, RequestBuilder},
         },
         traits::{
@@ -47,6 +48,8 @@ struct PostRequestProperities {
     // These are passed directly to the Anthropic API.
     properties: HashMap<String, serde_json::Value>,
     supported_request_modes: SupportedRequestModes,
+    output_mode: OutputMode,
+    request_timeouts: RequestTimeouts,
 }
 
 // represents client that interacts with the Anthropic API
@@ -84,6 +87,13 @@ fn resolve_properties(
         .or_insert("2023-06-01".to_string());
 
         let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
+    if output_mode == OutputMode::JsonSchema {
+        anyhow::bail!(
+            "anthropic does not support output_mode \"json_schema\", use \"tool\" instead"
+        );
+    }
 
     let mut properties = properties.finalize();
     // Anthropic has a very low max_tokens by default, so we increase it to 4096.
@@ -102,6 +112,8 @@ fn resolve_properties(
         properties,
         proxy_url: ctx.env.get("BOUNDARY_PROXY_URL").map(|s| s.to_string()),
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
 
@@ -122,6 +134,17 @@ impl WithClientProperties for AnthropicClient {
     fn supports_streaming(&self) -> bool {
         self.properties.supported_request_modes.stream.unwrap_or(true)
     }
+    fn request_timeouts(&self) -> &crate::internal::llm_client::RequestTimeouts {
+        &self.properties.request_timeouts
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        json!({
+            "base_url": self.properties.base_url,
+            "headers": self.properties.headers,
+            "properties": self.properties.properties,
+            "output_mode": self.properties.output_mode,
+        })
+    }
 }
 
 impl WithClient for AnthropicClient {
@@ -140,6 +163,7 @@ impl WithNoCompletion for AnthropicClient {}
 impl SseResponseTrait for AnthropicClient {
     fn response_stream(
         &self,
+        ctx: &RuntimeContext,
         resp: reqwest::Response,
         prompt: &Vec<RenderedChatMessage>,
         system_start: web_time::SystemTime,
@@ -148,6 +172,8 @@ impl SseResponseTrait for AnthropicClient {
         let prompt = prompt.clone();
         let client_name = self.context.name.clone();
         let params = self.properties.properties.clone();
+        let output_schema = self.features.output_mode.schema(ctx);
+        let mut arguments = String::new();
 
         Ok(Box::pin(
             resp.bytes_stream()
@@ -170,6 +196,7 @@ impl SseResponseTrait for AnthropicClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -205,9 +232,9 @@ impl SseResponseTrait for AnthropicClient {
                                 inner.model = body.model;
                                 let ref mut inner = inner.metadata;
                                 inner.baml_is_complete = match body.stop_reason {
-                                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn) => {
-                                        true
-                                    }
+                                    Some(StopReason::StopSequence)
+                                    | Some(StopReason::EndTurn)
+                                    | Some(StopReason::ToolUse) => true,
                                     _ => false,
                                 };
                                 inner.finish_reason =
@@ -218,7 +245,15 @@ impl SseResponseTrait for AnthropicClient {
                                     Some(body.usage.input_tokens + body.usage.output_tokens);
                             }
                             MessageChunk::ContentBlockDelta(event) => {
-                                inner.content += &event.delta.text;
+                                match (&output_schema, &event.delta.partial_json) {
+                                    (Some(schema), Some(partial_json)) => {
+                                        arguments += partial_json;
+                                        inner.content = schema.unwrap_arguments(&arguments);
+                                    }
+                                    // Text next to the tool call is not part of the output.
+                                    (Some(_), None) => (),
+                                    (None, _) => inner.content += &event.delta.text,
+                                }
                             }
                             MessageChunk::ContentBlockStart(_) => (),
                             MessageChunk::ContentBlockStop(_) => (),
@@ -227,9 +262,9 @@ impl SseResponseTrait for AnthropicClient {
                                 let ref mut inner = inner.metadata;
 
                                 inner.baml_is_complete = match body.delta.stop_reason {
-                                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn) => {
-                                        true
-                                    }
+                                    Some(StopReason::StopSequence)
+                                    | Some(StopReason::EndTurn)
+                                    | Some(StopReason::ToolUse) => true,
                                     _ => false,
                                 };
                                 inner.finish_reason = body
@@ -277,15 +312,15 @@ impl SseResponseTrait for AnthropicClient {
 impl WithStreamChat for AnthropicClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &Vec<RenderedChatMessage>,
     ) -> StreamResponse {
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
-        self.response_stream(response, prompt, system_now, instant_now)
+        self.response_stream(ctx, response, prompt, system_now, instant_now)
     }
 }
 
@@ -307,6 +342,7 @@ impl AnthropicClient {
                 anthropic_system_constraints: true,
                 resolve_media_urls: ResolveMediaUrls::Always,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client.retry_policy.clone(),
             client: create_client()?,
@@ -331,6 +367,7 @@ impl AnthropicClient {
                 anthropic_system_constraints: true,
                 resolve_media_urls: ResolveMediaUrls::Always,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client
                 .elem()
@@ -351,6 +388,7 @@ impl RequestBuilder for AnthropicClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &Vec<RenderedChatMessage>>,
         allow_proxy: bool,
         stream: bool,
@@ -391,6 +429,23 @@ impl RequestBuilder for AnthropicClient {
             }
         }
 
+        if let (Some(schema), either::Either::Right(_)) =
+            (self.features.output_mode.schema(ctx), prompt)
+        {
+            body_obj.insert(
+                "tools".into(),
+                json!([{
+                    "name": schema.name,
+                    "description": schema.description(),
+                    "input_schema": schema.json_schema(),
+                }]),
+            );
+            body_obj.insert(
+                "tool_choice".into(),
+                json!({ "type": "tool", "name": schema.name }),
+            );
+        }
+
         if stream {
             body_obj.insert("stream".into(), true.into());
         }
@@ -411,19 +466,40 @@ impl WithChat for AnthropicClient {
         ))
     }
 
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
-        let (response, system_now, instant_now) = match make_parsed_request::<
-            AnthropicMessageResponse,
-        >(
-            self, either::Either::Right(prompt), false
-        )
-        .await
-        {
-            Ok(v) => v,
-            Err(e) => return e,
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
+        let (response, system_now, instant_now) =
+            match make_parsed_request::<AnthropicMessageResponse>(
+                self,
+                ctx,
+                either::Either::Right(prompt),
+                false,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => return e,
+            };
+
+        let content = match self.features.output_mode.schema(ctx) {
+            Some(schema) => match tool_use_arguments(&schema, &response.content) {
+                Ok(arguments) => Some(arguments),
+                Err(message) => {
+                    return LLMResponse::LLMFailure(LLMErrorResponse {
+                        client: self.context.name.to_string(),
+                        model: Some(response.model),
+                        prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
+                        start_time: system_now,
+                        request_options: self.properties.properties.clone(),
+                        latency: instant_now.elapsed(),
+                        message,
+                        code: ErrorCode::Other(200),
+                    });
+                }
+            },
+            None => None,
         };
 
-        if response.content.len() != 1 {
+        if content.is_none() && response.content.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
                 client: self.context.name.to_string(),
                 model: None,
@@ -442,14 +518,16 @@ impl WithChat for AnthropicClient {
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
-            content: response.content[0].text.clone(),
+            content: content.unwrap_or_else(|| response.content[0].text.clone()),
             start_time: system_now,
             latency: instant_now.elapsed(),
             request_options: self.properties.properties.clone(),
             model: response.model,
             metadata: LLMCompleteResponseMetadata {
                 baml_is_complete: match response.stop_reason {
-                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn) => true,
+                    Some(StopReason::StopSequence)
+                    | Some(StopReason::EndTurn)
+                    | Some(StopReason::ToolUse) => true,
                     _ => false,
                 },
                 finish_reason: response
@@ -459,11 +537,23 @@ impl WithChat for AnthropicClient {
                 prompt_tokens: Some(response.usage.input_tokens),
                 output_tokens: Some(response.usage.output_tokens),
                 total_tokens: Some(response.usage.input_tokens + response.usage.output_tokens),
+                cached: false,
             },
         })
     }
 }
 
+/// The unwrapped input of the `tool_use` block the output mode forced.
+fn tool_use_arguments(
+    schema: &OutputSchema,
+    content: &[AnthropicMessageContent],
+) -> Result<String, String> {
+    match content.iter().find_map(|c| c.input.as_ref()) {
+        Some(input) => Ok(schema.unwrap_arguments(&input.to_string())),
+        None => Err(format!("Expected a call to tool {}", schema.name)),
+    }
+}
+
 impl ToProviderMessage for AnthropicClient {
     fn to_chat_message(
         &self,
@@ -480,9 +570,17 @@ impl ToProviderMessage for AnthropicClient {
         mut content: serde_json::Map<String, serde_json::Value>,
         media: &baml_types::BamlMedia,
     ) -> Result<serde_json::Map<String, serde_json::Value>> {
+        // PDFs are sent as documents; Anthropic doesn't accept audio or video.
+        let block_type = match media.media_type {
+            BamlMediaType::Image => "image",
+            BamlMediaType::Pdf => "document",
+            BamlMediaType::Audio | BamlMediaType::Video => {
+                return Err(unsupported_media(&self.context.provider, media.media_type))
+            }
+        };
         match &media.content {
             BamlMediaContent::Base64(data) => {
-                content.insert("type".into(), media.media_type.to_string().into());
+                content.insert("type".into(), block_type.into());
                 let mut source = serde_json::Map::new();
                 source.insert("type".into(), "base64".into());
                 source.insert("media_type".into(), media.mime_type_as_ok()?.into());
@@ -561,3 +659,106 @@ fn convert_completion_prompt_to_body(prompt: &String) -> HashMap<String, serde_j
     map.insert("prompt".into(), json!(prompt));
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::llm_client::test_support::{self, schema};
+
+    fn client(output_mode: &str) -> Result<(AnthropicClient, RuntimeContext)> {
+        test_support::client(
+            AnthropicClient::dynamic_new,
+            "anthropic",
+            &[
+                ("model", "claude-3-5-sonnet-latest"),
+                ("output_mode", output_mode),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn tool_mode_forces_the_tool() -> Result<()> {
+        let (client, ctx) = client("tool")?;
+        let body = test_support::request_body(&client, &ctx).await?;
+
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "name": "Extract",
+                "description": "Respond with the output of Extract.",
+                "input_schema": schema().json_schema(),
+            }])
+        );
+        assert_eq!(
+            body["tool_choice"],
+            json!({ "type": "tool", "name": "Extract" })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_schema_mode_is_rejected() {
+        let err = client("json_schema").err().unwrap();
+        assert!(err.to_string().contains("use \"tool\" instead"));
+    }
+
+    #[test]
+    fn tool_use_input_is_extracted() -> Result<()> {
+        let content: Vec<AnthropicMessageContent> = serde_json::from_value(json!([
+            { "type": "text", "text": "Sure." },
+            { "type": "tool_use", "id": "toolu_1", "name": "Extract", "input": { "output": ["a"] } },
+        ]))?;
+        assert_eq!(
+            tool_use_arguments(&schema(), &content),
+            Ok(r#"["a"]"#.to_string())
+        );
+
+        let content: Vec<AnthropicMessageContent> =
+            serde_json::from_value(json!([{ "type": "text", "text": "no" }]))?;
+        assert_eq!(
+            tool_use_arguments(&schema(), &content),
+            Err("Expected a call to tool Extract".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pdfs_are_sent_as_documents() -> Result<()> {
+        let (client, _) = client("prompt")?;
+        let pdf = BamlMedia::base64(
+            BamlMediaType::Pdf,
+            "AAAA".into(),
+            Some("application/pdf".into()),
+        );
+        assert_eq!(
+            json!(client.to_media_message(Default::default(), &pdf)?),
+            json!({
+                "type": "document",
+                "source": {
+                    "type": "base64",
+                    "media_type": "application/pdf",
+                    "data": "AAAA",
+                },
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn video_is_rejected() -> Result<()> {
+        let (client, _) = client("prompt")?;
+        let video = BamlMedia::base64(
+            BamlMediaType::Video,
+            "AAAA".into(),
+            Some("video/mp4".into()),
+        );
+        assert_eq!(
+            client
+                .to_media_message(Default::default(), &video)
+                .unwrap_err()
+                .to_string(),
+            "video is not supported by provider anthropic"
+        );
+        Ok(())
+    }
+}
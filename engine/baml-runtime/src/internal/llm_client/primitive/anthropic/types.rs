@@ -4,7 +4,11 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct AnthropicMessageContent {
     pub r#type: String,
+    /// Set for `text` blocks.
+    #[serde(default)]
     pub text: String,
+    /// The arguments of a `tool_use` block.
+    pub input: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -32,6 +36,7 @@ pub enum StopReason {
     MaxTokens,
     StopSequence,
     EndTurn,
+    ToolUse,
     #[serde(other)]
     Unknown,
 }
@@ -52,6 +57,8 @@ pub enum ContentType {
     Image,
     /// text_delta
     TextDelta,
+    /// input_json_delta
+    InputJsonDelta,
     /// tool_use
     ToolUse,
     /// tool_result
@@ -60,10 +67,11 @@ pub enum ContentType {
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TextContentBlock {
-    /// The content type. It is always `text`.
+    /// The content type, `text` or `tool_use`.
     #[serde(rename = "type")]
     pub _type: ContentType,
     /// The text content.
+    #[serde(default)]
     pub text: String,
 }
 
@@ -144,11 +152,14 @@ pub struct MessageDeltaChunk {
 /// The text delta content block.
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct TextDeltaContentBlock {
-    /// The content type. It is always `text_delta`.
+    /// The content type, `text_delta` or `input_json_delta`.
     #[serde(rename = "type")]
     pub _type: ContentType,
     /// The text delta content.
+    #[serde(default)]
     pub text: String,
+    /// A fragment of the JSON arguments of a `tool_use` block.
+    pub partial_json: Option<String>,
 }
 
 /// The stream stop information.
@@ -181,6 +192,7 @@ mod tests {
             delta: TextDeltaContentBlock {
                 _type: ContentType::TextDelta,
                 text: "Hello".to_string(),
+                partial_json: None,
             },
         });
         println!("serialized = {}", serde_json::to_string(&chunk)?);
@@ -7,7 +7,7 @@ use aws_sdk_bedrockruntime::{self as bedrock, operation::converse::ConverseOutpu
 use anyhow::{Context, Result};
 use aws_smithy_json::serialize::JsonObjectWriter;
 use aws_smithy_runtime_api::client::result::SdkError;
-use aws_smithy_types::Blob;
+use aws_smithy_types::{Blob, Document};
 use baml_types::BamlMediaContent;
 use baml_types::{BamlMedia, BamlMediaType};
 use futures::stream;
@@ -18,8 +18,13 @@ use serde_json::Map;
 use web_time::Instant;
 use web_time::SystemTime;
 
-use crate::internal::llm_client::traits::{ToProviderMessageExt, WithClientProperties};
-use crate::internal::llm_client::{AllowedMetadata, SupportedRequestModes};
+use crate::internal::llm_client::traits::{
+    process_media_urls, ToProviderMessageExt, WithClientProperties,
+};
+use crate::internal::llm_client::{
+    output_schema::{OutputMode, OutputSchema},
+    unsupported_media, AllowedMetadata, RequestTimeouts, SupportedRequestModes,
+};
 use crate::internal::llm_client::{
     primitive::request::RequestBuilder,
     traits::{
@@ -30,7 +35,7 @@ use crate::internal::llm_client::{
     ModelFeatures, ResolveMediaUrls,
 };
 
-use crate::{RenderCurlSettings, RuntimeContext};
+use crate::{HTTPRequest, RenderCurlSettings, RuntimeContext};
 
 // stores properties required for making a post request to the API
 struct RequestProperties {
@@ -45,6 +50,8 @@ struct RequestProperties {
     request_options: HashMap<String, serde_json::Value>,
     ctx_env: HashMap<String, String>,
     supported_request_modes: SupportedRequestModes,
+    output_mode: OutputMode,
+    request_timeouts: RequestTimeouts,
 }
 
 // represents client that interacts with the Anthropic API
@@ -90,6 +97,13 @@ fn resolve_properties(client: &ClientWalker, ctx: &RuntimeContext) -> Result<Req
         .unwrap_or_else(|_| ctx.env.get("AWS_REGION").map(|s| s.to_string()));
 
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
+    if output_mode == OutputMode::JsonSchema {
+        anyhow::bail!(
+            "aws-bedrock does not support output_mode \"json_schema\", use \"tool\" instead"
+        );
+    }
 
     Ok(RequestProperties {
         model_id,
@@ -100,6 +114,8 @@ fn resolve_properties(client: &ClientWalker, ctx: &RuntimeContext) -> Result<Req
         request_options: properties.finalize(),
         ctx_env: ctx.env.clone(),
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
 
@@ -121,6 +137,7 @@ impl AwsClient {
                 anthropic_system_constraints: true,
                 resolve_media_urls: ResolveMediaUrls::Always,
                 allowed_metadata: post_properties.allowed_metadata.clone(),
+                output_mode: post_properties.output_mode,
             },
             retry_policy: client
                 .elem()
@@ -189,13 +206,16 @@ impl AwsClient {
         Ok(bedrock::Client::new(&config))
     }
 
-    async fn chat_anyhow<'r>(&self, response: &'r ConverseOutput) -> Result<&'r String> {
+    async fn chat_anyhow(&self, ctx: &RuntimeContext, response: &ConverseOutput) -> Result<String> {
         let Some(bedrock::types::ConverseOutput::Message(ref message)) = response.output else {
             anyhow::bail!(
                 "Expected message output in response, but is type {}",
                 "unknown"
             );
         };
+        if let Some(schema) = self.features.output_mode.schema(ctx) {
+            return tool_use_arguments(&schema, message);
+        }
         let content = message
             .content
             .get(0)
@@ -214,7 +234,7 @@ impl AwsClient {
             );
         };
 
-        Ok(content)
+        Ok(content.clone())
     }
 
     fn build_request(
@@ -243,11 +263,19 @@ impl AwsClient {
             .map(|m| self.role_to_message(m))
             .collect::<Result<Vec<_>>>()?;
 
+        let tool_config = self
+            .features
+            .output_mode
+            .schema(ctx)
+            .map(|schema| tool_configuration(&schema))
+            .transpose()?;
+
         bedrock::operation::converse::ConverseInput::builder()
             .set_inference_config(self.properties.inference_config.clone())
             .set_model_id(Some(self.properties.model_id.clone()))
             .set_system(system_message)
             .set_messages(Some(converse_messages))
+            .set_tool_config(tool_config)
             .build()
             .context("Failed to convert BAML prompt to AWS Bedrock request")
     }
@@ -271,6 +299,192 @@ fn try_to_json<
     Ok(out)
 }
 
+/// Forces the model to call a single tool whose input is the output schema.
+fn tool_configuration(schema: &OutputSchema) -> Result<bedrock::types::ToolConfiguration> {
+    Ok(bedrock::types::ToolConfiguration::builder()
+        .tools(bedrock::types::Tool::ToolSpec(
+            bedrock::types::ToolSpecification::builder()
+                .name(schema.name.clone())
+                .description(schema.description())
+                .input_schema(bedrock::types::ToolInputSchema::Json(json_to_document(
+                    schema.json_schema(),
+                )))
+                .build()?,
+        ))
+        .tool_choice(bedrock::types::ToolChoice::Tool(
+            bedrock::types::SpecificToolChoice::builder()
+                .name(schema.name.clone())
+                .build()?,
+        ))
+        .build()?)
+}
+
+/// The unwrapped input of the `toolUse` block the tool configuration forced.
+fn tool_use_arguments(schema: &OutputSchema, message: &bedrock::types::Message) -> Result<String> {
+    let tool_use = message
+        .content
+        .iter()
+        .find_map(|content| match content {
+            bedrock::types::ContentBlock::ToolUse(tool_use) => Some(tool_use),
+            _ => None,
+        })
+        .with_context(|| format!("Expected a call to tool {}", schema.name))?;
+    Ok(schema.unwrap_arguments(&document_to_json(&tool_use.input).to_string()))
+}
+
+fn json_to_document(value: &serde_json::Value) -> Document {
+    match value {
+        serde_json::Value::Null => Document::Null,
+        serde_json::Value::Bool(b) => Document::Bool(*b),
+        serde_json::Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => Document::Number(aws_smithy_types::Number::PosInt(u)),
+            (_, Some(i), _) => Document::Number(aws_smithy_types::Number::NegInt(i)),
+            (_, _, f) => Document::Number(aws_smithy_types::Number::Float(f.unwrap_or_default())),
+        },
+        serde_json::Value::String(s) => Document::String(s.clone()),
+        serde_json::Value::Array(items) => {
+            Document::Array(items.iter().map(json_to_document).collect())
+        }
+        serde_json::Value::Object(obj) => Document::Object(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), json_to_document(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn document_to_json(document: &Document) -> serde_json::Value {
+    match document {
+        Document::Null => serde_json::Value::Null,
+        Document::Bool(b) => serde_json::Value::Bool(*b),
+        Document::Number(aws_smithy_types::Number::PosInt(u)) => (*u).into(),
+        Document::Number(aws_smithy_types::Number::NegInt(i)) => (*i).into(),
+        Document::Number(aws_smithy_types::Number::Float(f)) => (*f).into(),
+        Document::String(s) => serde_json::Value::String(s.clone()),
+        Document::Array(items) => {
+            serde_json::Value::Array(items.iter().map(document_to_json).collect())
+        }
+        Document::Object(obj) => serde_json::Value::Object(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), document_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// The JSON body the Converse API receives for `input`. The SDK keeps its
+/// serializers private, so this covers the shapes `build_request` produces.
+fn converse_body(input: &bedrock::operation::converse::ConverseInput) -> Result<serde_json::Value> {
+    let mut body = Map::new();
+
+    if let Some(messages) = &input.messages {
+        let messages = messages
+            .iter()
+            .map(|message| {
+                Ok(serde_json::json!({
+                    "role": message.role.as_str(),
+                    "content": message
+                        .content
+                        .iter()
+                        .map(content_block_json)
+                        .collect::<Result<Vec<_>>>()?,
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        body.insert("messages".into(), messages.into());
+    }
+
+    if let Some(system) = &input.system {
+        let system = system
+            .iter()
+            .map(|block| match block {
+                bedrock::types::SystemContentBlock::Text(text) => {
+                    Ok(serde_json::json!({ "text": text }))
+                }
+                other => anyhow::bail!("Can't render system content {:?}", other),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        body.insert("system".into(), system.into());
+    }
+
+    if let Some(config) = &input.inference_config {
+        let mut inference_config = Map::new();
+        if let Some(max_tokens) = config.max_tokens {
+            inference_config.insert("maxTokens".into(), max_tokens.into());
+        }
+        if let Some(temperature) = config.temperature {
+            inference_config.insert("temperature".into(), temperature.into());
+        }
+        if let Some(top_p) = config.top_p {
+            inference_config.insert("topP".into(), top_p.into());
+        }
+        if let Some(stop_sequences) = &config.stop_sequences {
+            inference_config.insert("stopSequences".into(), stop_sequences.clone().into());
+        }
+        body.insert("inferenceConfig".into(), inference_config.into());
+    }
+
+    if let Some(tool_config) = &input.tool_config {
+        let tools = tool_config
+            .tools
+            .iter()
+            .map(|tool| match tool {
+                bedrock::types::Tool::ToolSpec(spec) => {
+                    let mut tool_spec = Map::new();
+                    tool_spec.insert("name".into(), spec.name.clone().into());
+                    if let Some(description) = &spec.description {
+                        tool_spec.insert("description".into(), description.clone().into());
+                    }
+                    if let Some(bedrock::types::ToolInputSchema::Json(schema)) = &spec.input_schema
+                    {
+                        tool_spec.insert(
+                            "inputSchema".into(),
+                            serde_json::json!({ "json": document_to_json(schema) }),
+                        );
+                    }
+                    Ok(serde_json::json!({ "toolSpec": tool_spec }))
+                }
+                other => anyhow::bail!("Can't render tool {:?}", other),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut config = Map::new();
+        config.insert("tools".into(), tools.into());
+        if let Some(tool_choice) = &tool_config.tool_choice {
+            let tool_choice = match tool_choice {
+                bedrock::types::ToolChoice::Auto(_) => serde_json::json!({ "auto": {} }),
+                bedrock::types::ToolChoice::Any(_) => serde_json::json!({ "any": {} }),
+                bedrock::types::ToolChoice::Tool(choice) => {
+                    serde_json::json!({ "tool": { "name": choice.name } })
+                }
+                other => anyhow::bail!("Can't render tool choice {:?}", other),
+            };
+            config.insert("toolChoice".into(), tool_choice);
+        }
+        body.insert("toolConfig".into(), config.into());
+    }
+
+    Ok(body.into())
+}
+
+fn content_block_json(block: &bedrock::types::ContentBlock) -> Result<serde_json::Value> {
+    match block {
+        bedrock::types::ContentBlock::Text(text) => Ok(serde_json::json!({ "text": text })),
+        bedrock::types::ContentBlock::Image(image) => {
+            let Some(bedrock::types::ImageSource::Bytes(bytes)) = image.source() else {
+                anyhow::bail!("Can't render image source {:?}", image.source());
+            };
+            Ok(serde_json::json!({
+                "image": {
+                    "format": image.format().as_str(),
+                    "source": { "bytes": aws_smithy_types::base64::encode(bytes.as_ref()) },
+                }
+            }))
+        }
+        other => anyhow::bail!("Can't render content {:?}", other),
+    }
+}
+
 impl WithRenderRawCurl for AwsClient {
     async fn render_raw_curl(
         &self,
@@ -289,6 +503,41 @@ impl WithRenderRawCurl for AwsClient {
             "TODO"
         ))
     }
+
+    /// The SigV4 headers are added by the AWS SDK when the request is sent,
+    /// so they are left out of the rendered request.
+    async fn render_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        let internal_baml_jinja::RenderedPrompt::Chat(chat) = prompt else {
+            anyhow::bail!("aws-bedrock does not support completion prompts");
+        };
+        let chat =
+            process_media_urls(self.features.resolve_media_urls, true, None, ctx, chat).await?;
+        let request = self.build_request(ctx, &chat)?;
+
+        let operation = if stream && self.supports_streaming() {
+            "converse-stream"
+        } else {
+            "converse"
+        };
+        Ok(HTTPRequest {
+            url: format!(
+                "https://bedrock-runtime.{}.amazonaws.com/model/{}/{}",
+                self.properties.aws_region.as_deref().unwrap_or("<region>"),
+                self.properties.model_id,
+                operation
+            ),
+            method: "POST".to_string(),
+            headers: [("content-type".to_string(), "application/json".to_string())]
+                .into_iter()
+                .collect(),
+            body: converse_body(&request)?,
+        })
+    }
 }
 
 // getters for client info
@@ -308,6 +557,18 @@ impl WithClientProperties for AwsClient {
     fn supports_streaming(&self) -> bool {
         self.properties.supported_request_modes.stream.unwrap_or(true)
     }
+    fn request_timeouts(&self) -> &crate::internal::llm_client::RequestTimeouts {
+        &self.properties.request_timeouts
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        serde_json::json!({
+            "model_id": self.properties.model_id,
+            "aws_region": self.properties.aws_region,
+            "inference_config": format!("{:?}", self.properties.inference_config),
+            "request_options": self.properties.request_options,
+            "output_mode": self.properties.output_mode,
+        })
+    }
 }
 
 impl WithClient for AwsClient {
@@ -370,7 +631,8 @@ impl WithStreamChat for AwsClient {
             .set_model_id(request.model_id)
             .set_inference_config(request.inference_config)
             .set_system(request.system)
-            .set_messages(request.messages);
+            .set_messages(request.messages)
+            .set_tool_config(request.tool_config);
 
         let system_start = SystemTime::now();
         let instant_start = Instant::now();
@@ -416,6 +678,7 @@ impl WithStreamChat for AwsClient {
             }
         };
 
+        let output_schema = self.features.output_mode.schema(ctx);
         let stream = stream::unfold(
             (
                 Some(LLMCompleteResponse {
@@ -432,11 +695,14 @@ impl WithStreamChat for AwsClient {
                         prompt_tokens: None,
                         output_tokens: None,
                         total_tokens: None,
+                        cached: false,
                     },
                 }),
                 response,
+                String::new(),
             ),
-            move |(initial_state, mut response)| {
+            move |(initial_state, mut response, mut arguments)| {
+                let output_schema = output_schema.clone();
                 async move {
                     let Some(mut new_state) = initial_state else {
                         return None;
@@ -448,12 +714,25 @@ impl WithStreamChat for AwsClient {
                                 bedrock::types::ConverseStreamOutput::ContentBlockDelta(
                                     content_block_delta,
                                 ) => {
-                                    if let Some(bedrock::types::ContentBlockDelta::Text(
-                                        ref delta,
-                                    )) = content_block_delta.delta
-                                    {
-                                        new_state.content += delta;
-                                        // TODO- handle
+                                    match (content_block_delta.delta, &output_schema) {
+                                        (
+                                            Some(bedrock::types::ContentBlockDelta::Text(
+                                                ref delta,
+                                            )),
+                                            None,
+                                        ) => {
+                                            new_state.content += delta;
+                                        }
+                                        (
+                                            Some(bedrock::types::ContentBlockDelta::ToolUse(
+                                                ref delta,
+                                            )),
+                                            Some(schema),
+                                        ) => {
+                                            arguments += &delta.input;
+                                            new_state.content = schema.unwrap_arguments(&arguments);
+                                        }
+                                        _ => {}
                                     }
                                     // TODO- handle
                                 }
@@ -469,7 +748,8 @@ impl WithStreamChat for AwsClient {
                                 bedrock::types::ConverseStreamOutput::MessageStop(stop) => {
                                     new_state.metadata.baml_is_complete = match stop.stop_reason {
                                         bedrock::types::StopReason::StopSequence
-                                        | bedrock::types::StopReason::EndTurn => true,
+                                        | bedrock::types::StopReason::EndTurn
+                                        | bedrock::types::StopReason::ToolUse => true,
                                         _ => false,
                                     };
                                     // TODO- handle
@@ -491,7 +771,7 @@ impl WithStreamChat for AwsClient {
                             new_state.latency = instant_start.elapsed();
                             Some((
                                 LLMResponse::Success(new_state.clone()),
-                                (Some(new_state), response),
+                                (Some(new_state), response, arguments),
                             ))
                         }
                         Ok(None) => None,
@@ -506,7 +786,7 @@ impl WithStreamChat for AwsClient {
                                 message: format!("Failed to parse event: {:#?}", e),
                                 code: ErrorCode::Other(2),
                             }),
-                            (None, response),
+                            (None, response, arguments),
                         )),
                     }
                 }
@@ -527,10 +807,7 @@ impl AwsClient {
         media: &baml_types::BamlMedia,
     ) -> Result<bedrock::types::ContentBlock> {
         if media.media_type != BamlMediaType::Image {
-            anyhow::bail!(
-                "AWS supports images, but does not support this media type: {:#?}",
-                media
-            )
+            return Err(unsupported_media(&self.context.provider, media.media_type));
         }
         match &media.content {
             BamlMediaContent::File(_) => {
@@ -625,7 +902,7 @@ impl WithChat for AwsClient {
 
     async fn chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         chat_messages: &Vec<RenderedChatMessage>,
     ) -> LLMResponse {
         let client = self.context.name.to_string();
@@ -649,7 +926,7 @@ impl WithChat for AwsClient {
             }
         };
 
-        let request = match self.build_request(_ctx, chat_messages) {
+        let request = match self.build_request(ctx, chat_messages) {
             Ok(r) => r,
             Err(e) => {
                 return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -669,7 +946,8 @@ impl WithChat for AwsClient {
             .set_model_id(request.model_id)
             .set_inference_config(request.inference_config)
             .set_system(request.system)
-            .set_messages(request.messages);
+            .set_messages(request.messages)
+            .set_tool_config(request.tool_config);
 
         let system_start = SystemTime::now();
         let instant_start = Instant::now();
@@ -691,11 +969,11 @@ impl WithChat for AwsClient {
             }
         };
 
-        match self.chat_anyhow(&response).await {
+        match self.chat_anyhow(ctx, &response).await {
             Ok(content) => LLMResponse::Success(LLMCompleteResponse {
                 client,
                 prompt,
-                content: content.clone(),
+                content,
                 start_time: system_start.clone(),
                 latency: instant_start.elapsed(),
                 request_options,
@@ -703,7 +981,8 @@ impl WithChat for AwsClient {
                 metadata: LLMCompleteResponseMetadata {
                     baml_is_complete: match response.stop_reason {
                         bedrock::types::StopReason::StopSequence
-                        | bedrock::types::StopReason::EndTurn => true,
+                        | bedrock::types::StopReason::EndTurn
+                        | bedrock::types::StopReason::ToolUse => true,
                         _ => false,
                     },
                     finish_reason: Some(response.stop_reason().as_str().into()),
@@ -722,6 +1001,7 @@ impl WithChat for AwsClient {
                         .as_ref()
                         .map(|i| i.total_tokens.try_into().ok())
                         .flatten(),
+                    cached: false,
                 },
             }),
             Err(e) => LLMResponse::LLMFailure(LLMErrorResponse {
@@ -737,3 +1017,171 @@ impl WithChat for AwsClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::ir::{repr::make_test_ir, IRHelper};
+
+    use super::*;
+    use crate::internal::llm_client::test_support::{self, schema};
+
+    // Bedrock clients can't be built from a `ClientProperty`, only from the IR.
+    fn client(output_mode: &str) -> Result<(AwsClient, RuntimeContext)> {
+        let ir = make_test_ir(&format!(
+            r#"
+            client<llm> Test {{
+                provider aws-bedrock
+                options {{
+                    model "anthropic.claude-3-5-sonnet-20240620-v1:0"
+                    region "us-east-1"
+                    output_mode "{output_mode}"
+                }}
+            }}
+            "#
+        ))?;
+        let ctx = test_support::context();
+        let client = AwsClient::new(&ir.find_client("Test")?, &ctx)?;
+        Ok((client, ctx))
+    }
+
+    fn request(output_mode: &str) -> Result<bedrock::operation::converse::ConverseInput> {
+        let (client, ctx) = client(output_mode)?;
+        client.build_request(&ctx, &test_support::prompt())
+    }
+
+    #[test]
+    fn tool_mode_forces_the_tool() -> Result<()> {
+        let tool_config = request("tool")?.tool_config.context("tool config")?;
+
+        let [bedrock::types::Tool::ToolSpec(spec)] = tool_config.tools.as_slice() else {
+            panic!("expected a single tool spec, got {:?}", tool_config.tools);
+        };
+        assert_eq!(spec.name, "Extract");
+        assert_eq!(
+            spec.description.as_deref(),
+            Some("Respond with the output of Extract.")
+        );
+        let Some(bedrock::types::ToolInputSchema::Json(input_schema)) = &spec.input_schema else {
+            panic!("expected a JSON input schema");
+        };
+        assert_eq!(&document_to_json(input_schema), schema().json_schema());
+        assert!(matches!(
+            tool_config.tool_choice,
+            Some(bedrock::types::ToolChoice::Tool(ref choice)) if choice.name == "Extract"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn prompt_mode_sends_no_tools() -> Result<()> {
+        assert!(request("prompt")?.tool_config.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn tool_use_input_is_extracted() -> Result<()> {
+        let message = bedrock::types::Message::builder()
+            .role(bedrock::types::ConversationRole::Assistant)
+            .content(bedrock::types::ContentBlock::Text("Sure.".to_string()))
+            .content(bedrock::types::ContentBlock::ToolUse(
+                bedrock::types::ToolUseBlock::builder()
+                    .tool_use_id("tooluse_1")
+                    .name("Extract")
+                    .input(json_to_document(&serde_json::json!({ "output": ["a"] })))
+                    .build()?,
+            ))
+            .build()?;
+        assert_eq!(tool_use_arguments(&schema(), &message)?, r#"["a"]"#);
+
+        let message = bedrock::types::Message::builder()
+            .role(bedrock::types::ConversationRole::Assistant)
+            .content(bedrock::types::ContentBlock::Text("no".to_string()))
+            .build()?;
+        assert_eq!(
+            tool_use_arguments(&schema(), &message)
+                .unwrap_err()
+                .to_string(),
+            "Expected a call to tool Extract"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn render_request_renders_the_converse_body() -> Result<()> {
+        let (client, ctx) = client("tool")?;
+        let mut chat = test_support::prompt();
+        chat.insert(
+            0,
+            RenderedChatMessage {
+                role: "system".to_string(),
+                allow_duplicate_role: false,
+                parts: vec![ChatMessagePart::Text("be brief".to_string())],
+            },
+        );
+        let prompt = internal_baml_jinja::RenderedPrompt::Chat(chat);
+
+        let request = client.render_request(&ctx, &prompt, false).await?;
+        assert_eq!(
+            request.url,
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/anthropic.claude-3-5-sonnet-20240620-v1:0/converse"
+        );
+        assert_eq!(request.method, "POST");
+        assert_eq!(
+            request.body,
+            serde_json::json!({
+                "messages": [{ "role": "user", "content": [{ "text": "hi" }] }],
+                "system": [{ "text": "be brief" }],
+                "toolConfig": {
+                    "tools": [{
+                        "toolSpec": {
+                            "name": "Extract",
+                            "description": "Respond with the output of Extract.",
+                            "inputSchema": { "json": schema().json_schema() },
+                        }
+                    }],
+                    "toolChoice": { "tool": { "name": "Extract" } },
+                },
+            })
+        );
+
+        let request = client.render_request(&ctx, &prompt, true).await?;
+        assert!(request.url.ends_with("/converse-stream"));
+
+        let completion = internal_baml_jinja::RenderedPrompt::Completion("hi".to_string());
+        assert_eq!(
+            client
+                .render_request(&ctx, &completion, false)
+                .await
+                .unwrap_err()
+                .to_string(),
+            "aws-bedrock does not support completion prompts"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pdf_and_video_are_rejected() -> Result<()> {
+        let (client, _) = client("prompt")?;
+        for (media_type, mime_type) in [
+            (BamlMediaType::Pdf, "application/pdf"),
+            (BamlMediaType::Video, "video/mp4"),
+        ] {
+            let media = BamlMedia::base64(media_type, "AAAA".into(), Some(mime_type.into()));
+            assert_eq!(
+                client.to_media_message(&media).unwrap_err().to_string(),
+                format!("{media_type} is not supported by provider aws-bedrock")
+            );
+        }
+
+        let image = BamlMedia::base64(
+            BamlMediaType::Image,
+            "AAAA".into(),
+            Some("image/png".into()),
+        );
+        let bedrock::types::ContentBlock::Image(image) = client.to_media_message(&image)? else {
+            panic!("expected an image block");
+        };
+        assert_eq!(image.format(), &bedrock::types::ImageFormat::Png);
+        Ok(())
+    }
+}
@@ -3,12 +3,15 @@ use crate::internal::llm_client::properties_hander::{PropertiesHandler};
 use crate::internal::llm_client::traits::{
     ToProviderMessage, ToProviderMessageExt, WithClientProperties,
 };
-use crate::internal::llm_client::{AllowedMetadata, ResolveMediaUrls, SupportedRequestModes};
+use crate::internal::llm_client::{
+    output_schema::{OutputMode, OutputSchema},
+    AllowedMetadata, RequestTimeouts, ResolveMediaUrls, SupportedRequestModes,
+};
 use crate::RuntimeContext;
 use crate::{
     internal::llm_client::{
         primitive::{
-            google::types::{FinishReason, GoogleResponse},
+            google::types::{FinishReason, GoogleResponse, Part},
             request::{make_parsed_request, make_request, RequestBuilder},
         },
         traits::{
@@ -39,6 +42,8 @@ struct PostRequestProperities {
     properties: HashMap<String, serde_json::Value>,
     allowed_metadata: AllowedMetadata,
     supported_request_modes: SupportedRequestModes,
+    output_mode: OutputMode,
+    request_timeouts: RequestTimeouts,
 }
 
 pub struct GoogleAIClient {
@@ -71,6 +76,8 @@ fn resolve_properties(
     let headers = properties.pull_headers()?;
 
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
 
     Ok(PostRequestProperities {
         default_role,
@@ -82,6 +89,8 @@ fn resolve_properties(
         properties: properties.finalize(),
         allowed_metadata,
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
 
@@ -101,6 +110,18 @@ impl WithClientProperties for GoogleAIClient {
     fn supports_streaming(&self) -> bool {
         self.properties.supported_request_modes.stream.unwrap_or(true)
     }
+    fn request_timeouts(&self) -> &crate::internal::llm_client::RequestTimeouts {
+        &self.properties.request_timeouts
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        json!({
+            "base_url": self.properties.base_url,
+            "headers": self.properties.headers,
+            "model_id": self.properties.model_id,
+            "properties": self.properties.properties,
+            "output_mode": self.properties.output_mode,
+        })
+    }
 }
 
 impl WithClient for GoogleAIClient {
@@ -118,6 +139,7 @@ impl WithNoCompletion for GoogleAIClient {}
 impl SseResponseTrait for GoogleAIClient {
     fn response_stream(
         &self,
+        ctx: &RuntimeContext,
         resp: reqwest::Response,
         prompt: &Vec<RenderedChatMessage>,
         system_start: web_time::SystemTime,
@@ -127,6 +149,9 @@ impl SseResponseTrait for GoogleAIClient {
         let client_name = self.context.name.clone();
         let model_id = self.properties.model_id.clone().unwrap_or_default();
         let params = self.properties.properties.clone();
+        let output_mode = self.features.output_mode;
+        let output_schema = output_mode.schema(ctx);
+        let mut arguments = String::new();
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -152,6 +177,7 @@ impl SseResponseTrait for GoogleAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -184,8 +210,31 @@ impl SseResponseTrait for GoogleAIClient {
                         };
 
                         if let Some(choice) = event.candidates.get(0) {
-                            if let Some(content) = choice.content.parts.get(0) {
-                                inner.content += &content.text;
+                            match (&output_schema, output_mode) {
+                                (Some(schema), OutputMode::Tool) => {
+                                    // Function call arguments are not streamed, they
+                                    // arrive whole in a single event.
+                                    if let Some(args) = choice
+                                        .content
+                                        .parts
+                                        .iter()
+                                        .find_map(|p| p.function_call.as_ref())
+                                        .and_then(|f| f.args.as_ref())
+                                    {
+                                        inner.content = schema.unwrap_arguments(&args.to_string());
+                                    }
+                                }
+                                (Some(schema), _) => {
+                                    if let Some(content) = choice.content.parts.get(0) {
+                                        arguments += &content.text;
+                                    }
+                                    inner.content = schema.unwrap_arguments(&arguments);
+                                }
+                                (None, _) => {
+                                    if let Some(content) = choice.content.parts.get(0) {
+                                        inner.content += &content.text;
+                                    }
+                                }
                             }
                             match choice.finish_reason.as_ref() {
                                 Some(FinishReason::Stop) => {
@@ -208,16 +257,16 @@ impl SseResponseTrait for GoogleAIClient {
 impl WithStreamChat for GoogleAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &Vec<RenderedChatMessage>,
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
-        self.response_stream(response, prompt, system_now, instant_now)
+        self.response_stream(ctx, response, prompt, system_now, instant_now)
     }
 }
 
@@ -239,6 +288,7 @@ impl GoogleAIClient {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Always,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client
                 .elem()
@@ -267,6 +317,7 @@ impl GoogleAIClient {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Always,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client.retry_policy.clone(),
             client: create_client()?,
@@ -282,6 +333,7 @@ impl RequestBuilder for GoogleAIClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &Vec<RenderedChatMessage>>,
         allow_proxy: bool,
         stream: bool,
@@ -329,6 +381,45 @@ impl RequestBuilder for GoogleAIClient {
             }
         }
 
+        if let (Some(schema), either::Either::Right(_)) =
+            (self.features.output_mode.schema(ctx), prompt)
+        {
+            match self.features.output_mode {
+                OutputMode::Tool => {
+                    body_obj.insert(
+                        "tools".into(),
+                        json!([{
+                            "functionDeclarations": [{
+                                "name": schema.name,
+                                "description": schema.description(),
+                                "parameters": schema.openapi_schema(),
+                            }],
+                        }]),
+                    );
+                    body_obj.insert(
+                        "toolConfig".into(),
+                        json!({
+                            "functionCallingConfig": {
+                                "mode": "ANY",
+                                "allowedFunctionNames": [schema.name],
+                            },
+                        }),
+                    );
+                }
+                _ => {
+                    let generation_config = body_obj
+                        .entry("generationConfig")
+                        .or_insert_with(|| json!({}));
+                    if let Some(generation_config) = generation_config.as_object_mut() {
+                        generation_config
+                            .insert("responseMimeType".into(), json!("application/json"));
+                        generation_config
+                            .insert("responseSchema".into(), schema.openapi_schema().clone());
+                    }
+                }
+            }
+        }
+
         Ok(req.json(&body))
     }
 
@@ -345,15 +436,19 @@ impl WithChat for GoogleAIClient {
         ))
     }
 
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<GoogleResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<GoogleResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -371,10 +466,30 @@ impl WithChat for GoogleAIClient {
             });
         }
 
+        let content = match candidate_content(
+            self.features.output_mode,
+            self.features.output_mode.schema(ctx).as_deref(),
+            &response.candidates[0].content.parts,
+        ) {
+            Ok(content) => content,
+            Err(message) => {
+                return LLMResponse::LLMFailure(LLMErrorResponse {
+                    client: self.context.name.to_string(),
+                    model: None,
+                    prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
+                    start_time: system_now,
+                    request_options: self.properties.properties.clone(),
+                    latency: instant_now.elapsed(),
+                    message,
+                    code: ErrorCode::Other(200),
+                })
+            }
+        };
+
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
-            content: response.candidates[0].content.parts[0].text.clone(),
+            content,
             start_time: system_now,
             latency: instant_now.elapsed(),
             request_options: self.properties.properties.clone(),
@@ -383,7 +498,7 @@ impl WithChat for GoogleAIClient {
                 .properties
                 .get("model")
                 .and_then(|v| v.as_str().map(|s| s.to_string()))
-                .or_else(|| _ctx.env.get("default model").map(|s| s.to_string()))
+                .or_else(|| ctx.env.get("default model").map(|s| s.to_string()))
                 .unwrap_or_else(|| "".to_string()),
             metadata: LLMCompleteResponseMetadata {
                 baml_is_complete: match response.candidates[0].finish_reason {
@@ -397,6 +512,7 @@ impl WithChat for GoogleAIClient {
                 prompt_tokens: response.usage_metadata.prompt_token_count,
                 output_tokens: response.usage_metadata.candidates_token_count,
                 total_tokens: response.usage_metadata.total_token_count,
+                cached: false,
             },
         })
     }
@@ -432,6 +548,29 @@ impl ToProviderMessageExt for GoogleAIClient {
     }
 }
 
+/// The text BAML parses out of a candidate: the text of its first part, or
+/// the unwrapped function call arguments / structured output when the output
+/// mode sends a schema.
+fn candidate_content(
+    output_mode: OutputMode,
+    output_schema: Option<&OutputSchema>,
+    parts: &[Part],
+) -> Result<String, String> {
+    let text = parts.first().map_or("", |p| p.text.as_str());
+    let Some(schema) = output_schema else {
+        return Ok(text.to_string());
+    };
+    match output_mode {
+        OutputMode::Tool => parts
+            .iter()
+            .find_map(|p| p.function_call.as_ref())
+            .and_then(|f| f.args.as_ref())
+            .map(|args| schema.unwrap_arguments(&args.to_string()))
+            .ok_or_else(|| format!("Expected a call to function {}", schema.name)),
+        _ => Ok(schema.unwrap_arguments(text)),
+    }
+}
+
 impl ToProviderMessage for GoogleAIClient {
     fn to_chat_message(
         &self,
@@ -480,3 +619,93 @@ impl ToProviderMessage for GoogleAIClient {
         Ok(message)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::llm_client::test_support::{self, schema};
+
+    async fn request_body(output_mode: &str) -> Result<serde_json::Value> {
+        let (client, ctx) = test_support::client(
+            GoogleAIClient::dynamic_new,
+            "google-ai",
+            &[
+                ("model", "gemini-1.5-pro"),
+                ("api_key", "test"),
+                ("output_mode", output_mode),
+            ],
+        )?;
+        test_support::request_body(&client, &ctx).await
+    }
+
+    fn parts(value: serde_json::Value) -> Vec<Part> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tool_mode_forces_the_function() -> Result<()> {
+        let body = request_body("tool").await?;
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "functionDeclarations": [{
+                    "name": "Extract",
+                    "description": "Respond with the output of Extract.",
+                    "parameters": schema().openapi_schema(),
+                }],
+            }])
+        );
+        assert_eq!(
+            body["toolConfig"],
+            json!({
+                "functionCallingConfig": { "mode": "ANY", "allowedFunctionNames": ["Extract"] },
+            })
+        );
+        assert!(body.get("generationConfig").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn json_schema_mode_sets_response_schema() -> Result<()> {
+        let body = request_body("json_schema").await?;
+        assert_eq!(
+            body["generationConfig"],
+            json!({
+                "responseMimeType": "application/json",
+                "responseSchema": schema().openapi_schema(),
+            })
+        );
+        assert!(body.get("tools").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn function_call_args_are_extracted() {
+        let parts = parts(json!([
+            { "functionCall": { "name": "Extract", "args": { "output": ["a"] } } },
+        ]));
+        assert_eq!(
+            candidate_content(OutputMode::Tool, Some(&schema()), &parts),
+            Ok(r#"["a"]"#.to_string())
+        );
+
+        let parts = self::parts(json!([{ "text": "no" }]));
+        assert_eq!(
+            candidate_content(OutputMode::Tool, Some(&schema()), &parts),
+            Err("Expected a call to function Extract".to_string())
+        );
+    }
+
+    #[test]
+    fn structured_output_is_unwrapped() {
+        let parts = parts(json!([{ "text": "{\"output\": [\"a\"]}" }]));
+        assert_eq!(
+            candidate_content(OutputMode::JsonSchema, Some(&schema()), &parts),
+            Ok(r#"["a"]"#.to_string())
+        );
+        assert_eq!(
+            candidate_content(OutputMode::Prompt, None, &parts),
+            Ok("{\"output\": [\"a\"]}".to_string())
+        );
+    }
+}
@@ -6,7 +6,7 @@ use internal_baml_core::ir::{repr::IntermediateRepr, ClientWalker};
 
 use crate::{
     client_registry::ClientProperty, internal::prompt_renderer::PromptRenderer,
-    runtime_interface::InternalClientLookup, RenderCurlSettings, RuntimeContext,
+    runtime_interface::InternalClientLookup, HTTPRequest, RenderCurlSettings, RuntimeContext,
 };
 
 use self::{
@@ -96,6 +96,12 @@ impl WithClientProperties for LLMPrimitiveProvider {
     fn supports_streaming(&self) -> bool {
         match_llm_provider!(self, supports_streaming)
     }
+    fn request_timeouts(&self) -> &super::RequestTimeouts {
+        match_llm_provider!(self, request_timeouts)
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        match_llm_provider!(self, resolved_options)
+    }
 }
 
 impl TryFrom<(&ClientProperty, &RuntimeContext)> for LLMPrimitiveProvider {
@@ -193,6 +199,15 @@ impl WithRenderRawCurl for LLMPrimitiveProvider {
     ) -> Result<String> {
         match_llm_provider!(self, render_raw_curl, async, ctx, prompt, render_settings)
     }
+
+    async fn render_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        match_llm_provider!(self, render_request, async, ctx, prompt, stream)
+    }
 }
 
 impl WithSingleCallable for LLMPrimitiveProvider {
@@ -247,6 +262,10 @@ impl LLMPrimitiveProvider {
         &match_llm_provider!(self, context).name
     }
 
+    pub fn provider(&self) -> &str {
+        &match_llm_provider!(self, context).provider
+    }
+
     pub fn request_options(&self) -> &std::collections::HashMap<String, serde_json::Value> {
         match_llm_provider!(self, request_options)
     }
@@ -8,11 +8,15 @@ use internal_baml_jinja::{ChatMessagePart, RenderContext_Client, RenderedChatMes
 use serde_json::json;
 
 use crate::internal::llm_client::{
-    ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse,
+    unsupported_media, ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata,
+    LLMErrorResponse,
 };
 
 use super::properties::{self, PostRequestProperties};
-use super::types::{ChatCompletionResponse, ChatCompletionResponseDelta, FinishReason};
+use super::types::{
+    ChatCompletionResponse, ChatCompletionResponseDelta, ChatCompletionResponseMessage,
+    FinishReason,
+};
 
 use crate::client_registry::ClientProperty;
 use crate::internal::llm_client::primitive::request::{
@@ -23,6 +27,7 @@ use crate::internal::llm_client::traits::{
     WithClientProperties, WithStreamChat,
 };
 use crate::internal::llm_client::{
+    output_schema::{OutputMode, OutputSchema},
     traits::{WithChat, WithClient, WithNoCompletion, WithRetryPolicy},
     LLMResponse, ModelFeatures,
 };
@@ -71,6 +76,18 @@ impl WithClientProperties for OpenAIClient {
             }
         }
     }
+    fn request_timeouts(&self) -> &crate::internal::llm_client::RequestTimeouts {
+        &self.properties.request_timeouts
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        json!({
+            "base_url": self.properties.base_url,
+            "headers": self.properties.headers,
+            "query_params": self.properties.query_params,
+            "properties": self.properties.properties,
+            "output_mode": self.properties.output_mode,
+        })
+    }
 }
 
 impl WithClient for OpenAIClient {
@@ -163,10 +180,11 @@ impl WithChat for OpenAIClient {
         ))
     }
 
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
         let (response, system_start, instant_start) =
             match make_parsed_request::<ChatCompletionResponse>(
                 self,
+                ctx,
                 either::Either::Right(prompt),
                 false,
             )
@@ -193,16 +211,30 @@ impl WithChat for OpenAIClient {
         }
 
         let usage = response.usage.as_ref();
+        let content = match message_content(
+            self.features.output_mode,
+            self.features.output_mode.schema(ctx).as_deref(),
+            &response.choices[0].message,
+        ) {
+            Ok(content) => content,
+            Err(message) => {
+                return LLMResponse::LLMFailure(LLMErrorResponse {
+                    client: self.context.name.to_string(),
+                    model: Some(response.model.clone()),
+                    prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
+                    start_time: system_start,
+                    latency: instant_start.elapsed(),
+                    request_options: self.properties.properties.clone(),
+                    message,
+                    code: ErrorCode::Other(200),
+                })
+            }
+        };
 
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
-            content: response.choices[0]
-                .message
-                .content
-                .as_ref()
-                .map_or("", |s| s.as_str())
-                .to_string(),
+            content,
             start_time: system_start,
             latency: instant_start.elapsed(),
             model: response.model,
@@ -210,7 +242,7 @@ impl WithChat for OpenAIClient {
             metadata: LLMCompleteResponseMetadata {
                 baml_is_complete: match response.choices.get(0) {
                     Some(c) => match c.finish_reason {
-                        Some(FinishReason::Stop) => true,
+                        Some(FinishReason::Stop) | Some(FinishReason::ToolCalls) => true,
                         _ => false,
                     },
                     None => false,
@@ -226,11 +258,36 @@ impl WithChat for OpenAIClient {
                 prompt_tokens: usage.map(|u| u.prompt_tokens),
                 output_tokens: usage.map(|u| u.completion_tokens),
                 total_tokens: usage.map(|u| u.total_tokens),
+                cached: false,
             },
         })
     }
 }
 
+/// The text BAML parses out of a completion: the message content, or the
+/// unwrapped tool call arguments / structured output when the output mode
+/// sends a schema.
+fn message_content(
+    output_mode: OutputMode,
+    output_schema: Option<&OutputSchema>,
+    message: &ChatCompletionResponseMessage,
+) -> Result<String, String> {
+    let content = message.content.as_deref().unwrap_or("");
+    let Some(schema) = output_schema else {
+        return Ok(content.to_string());
+    };
+    match output_mode {
+        OutputMode::Tool => match message.tool_calls.as_ref().and_then(|t| t.first()) {
+            Some(tool_call) => Ok(schema.unwrap_arguments(&tool_call.function.arguments)),
+            None => Err(format!(
+                "Expected a call to tool {}, but the model responded with: {}",
+                schema.name, content
+            )),
+        },
+        _ => Ok(schema.unwrap_arguments(content)),
+    }
+}
+
 impl RequestBuilder for OpenAIClient {
     fn http_client(&self) -> &reqwest::Client {
         &self.client
@@ -238,6 +295,7 @@ impl RequestBuilder for OpenAIClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &Vec<RenderedChatMessage>>,
         allow_proxy: bool,
         stream: bool,
@@ -285,6 +343,45 @@ impl RequestBuilder for OpenAIClient {
             }
         }
 
+        if let (Some(schema), either::Either::Right(_)) =
+            (self.features.output_mode.schema(ctx), prompt)
+        {
+            match self.features.output_mode {
+                OutputMode::Tool => {
+                    body_obj.insert(
+                        "tools".into(),
+                        json!([{
+                            "type": "function",
+                            "function": {
+                                "name": schema.name,
+                                "description": schema.description(),
+                                "parameters": schema.json_schema(),
+                            },
+                        }]),
+                    );
+                    body_obj.insert(
+                        "tool_choice".into(),
+                        json!({
+                            "type": "function",
+                            "function": { "name": schema.name },
+                        }),
+                    );
+                }
+                _ => {
+                    body_obj.insert(
+                        "response_format".into(),
+                        json!({
+                            "type": "json_schema",
+                            "json_schema": {
+                                "name": schema.name,
+                                "schema": schema.json_schema(),
+                            },
+                        }),
+                    );
+                }
+            }
+        }
+
         if stream {
             body_obj.insert("stream".into(), json!(true));
             if self.provider == "openai" {
@@ -308,6 +405,7 @@ impl RequestBuilder for OpenAIClient {
 impl SseResponseTrait for OpenAIClient {
     fn response_stream(
         &self,
+        ctx: &RuntimeContext,
         resp: reqwest::Response,
         prompt: &Vec<RenderedChatMessage>,
         system_start: web_time::SystemTime,
@@ -316,6 +414,9 @@ impl SseResponseTrait for OpenAIClient {
         let prompt = prompt.clone();
         let client_name = self.context.name.clone();
         let params = self.properties.properties.clone();
+        let output_mode = self.features.output_mode;
+        let output_schema = output_mode.schema(ctx);
+        let mut arguments = String::new();
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -343,6 +444,7 @@ impl SseResponseTrait for OpenAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -374,15 +476,37 @@ impl SseResponseTrait for OpenAIClient {
                             }
                         };
                         if let Some(choice) = event.choices.get(0) {
-                            if let Some(content) = choice.delta.content.as_ref() {
-                                inner.content += content.as_str();
+                            match (&output_schema, output_mode) {
+                                (Some(schema), OutputMode::Tool) => {
+                                    choice
+                                        .delta
+                                        .tool_calls
+                                        .iter()
+                                        .flatten()
+                                        .filter(|t| t.index == 0)
+                                        .filter_map(|t| t.function.as_ref())
+                                        .filter_map(|f| f.arguments.as_ref())
+                                        .for_each(|a| arguments += a);
+                                    inner.content = schema.unwrap_arguments(&arguments);
+                                }
+                                (Some(schema), _) => {
+                                    if let Some(content) = choice.delta.content.as_ref() {
+                                        arguments += content.as_str();
+                                    }
+                                    inner.content = schema.unwrap_arguments(&arguments);
+                                }
+                                (None, _) => {
+                                    if let Some(content) = choice.delta.content.as_ref() {
+                                        inner.content += content.as_str();
+                                    }
+                                }
                             }
                             inner.model = event.model;
                             match choice.finish_reason.as_ref() {
-                                Some(FinishReason::Stop) => {
+                                Some(FinishReason::Stop) | Some(FinishReason::ToolCalls) => {
                                     inner.metadata.baml_is_complete = true;
                                     inner.metadata.finish_reason =
-                                        Some(FinishReason::Stop.to_string());
+                                        choice.finish_reason.as_ref().map(|r| r.to_string());
                                 }
                                 finish_reason => {
                                     inner.metadata.baml_is_complete = false;
@@ -408,15 +532,15 @@ impl SseResponseTrait for OpenAIClient {
 impl WithStreamChat for OpenAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &Vec<RenderedChatMessage>,
     ) -> StreamResponse {
         let (resp, system_start, instant_start) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
-        self.response_stream(resp, prompt, system_start, instant_start)
+        self.response_stream(ctx, resp, prompt, system_start, instant_start)
     }
 }
 
@@ -436,6 +560,7 @@ macro_rules! make_openai_client {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
+                output_mode: $properties.output_mode,
             },
             properties: $properties,
             retry_policy: $client.retry_policy.clone(),
@@ -457,6 +582,7 @@ macro_rules! make_openai_client {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
+                output_mode: $properties.output_mode,
             },
             properties: $properties,
             retry_policy: $client
@@ -543,6 +669,9 @@ impl ToProviderMessage for OpenAIClient {
         let media_type = match media.media_type {
             BamlMediaType::Image => "image",
             BamlMediaType::Audio => "audio",
+            BamlMediaType::Pdf | BamlMediaType::Video => {
+                return Err(unsupported_media(&self.provider, media.media_type))
+            }
         };
         let media_type = format!("{}_url", media_type);
         match &media.content {
@@ -639,3 +768,148 @@ impl ToProviderMessageExt for OpenAIClient {
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::llm_client::test_support::{self, schema};
+
+    fn client(output_mode: &str) -> Result<(OpenAIClient, RuntimeContext)> {
+        test_support::client(
+            OpenAIClient::dynamic_new_generic,
+            "openai-generic",
+            &[
+                ("base_url", "http://localhost:1234/v1"),
+                ("model", "gpt-4o"),
+                ("output_mode", output_mode),
+            ],
+        )
+    }
+
+    async fn request_body(output_mode: &str) -> Result<serde_json::Value> {
+        let (client, ctx) = client(output_mode)?;
+        test_support::request_body(&client, &ctx).await
+    }
+
+    fn message(value: serde_json::Value) -> ChatCompletionResponseMessage {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tool_mode_forces_the_function() -> Result<()> {
+        let body = request_body("tool").await?;
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "type": "function",
+                "function": {
+                    "name": "Extract",
+                    "description": "Respond with the output of Extract.",
+                    "parameters": schema().json_schema(),
+                },
+            }])
+        );
+        assert_eq!(
+            body["tool_choice"],
+            json!({ "type": "function", "function": { "name": "Extract" } })
+        );
+        assert!(body.get("response_format").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn json_schema_mode_sets_response_format() -> Result<()> {
+        let body = request_body("json_schema").await?;
+        assert_eq!(
+            body["response_format"],
+            json!({
+                "type": "json_schema",
+                "json_schema": { "name": "Extract", "schema": schema().json_schema() },
+            })
+        );
+        assert!(body.get("tools").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prompt_mode_sends_no_schema() -> Result<()> {
+        let body = request_body("prompt").await?;
+        assert!(body.get("tools").is_none());
+        assert!(body.get("response_format").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn tool_call_arguments_are_extracted() {
+        let message = message(json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": { "name": "Extract", "arguments": "{\"output\": [\"a\"]}" },
+            }],
+        }));
+        assert_eq!(
+            message_content(OutputMode::Tool, Some(&schema()), &message),
+            Ok(r#"["a"]"#.to_string())
+        );
+    }
+
+    #[test]
+    fn missing_tool_call_is_an_error() {
+        let message = message(json!({ "role": "assistant", "content": "no" }));
+        assert_eq!(
+            message_content(OutputMode::Tool, Some(&schema()), &message),
+            Err("Expected a call to tool Extract, but the model responded with: no".to_string())
+        );
+    }
+
+    #[test]
+    fn structured_output_is_unwrapped() {
+        let message = message(json!({
+            "role": "assistant",
+            "content": "{\"output\": [\"a\"]}",
+        }));
+        assert_eq!(
+            message_content(OutputMode::JsonSchema, Some(&schema()), &message),
+            Ok(r#"["a"]"#.to_string())
+        );
+        assert_eq!(
+            message_content(OutputMode::Prompt, None, &message),
+            Ok("{\"output\": [\"a\"]}".to_string())
+        );
+    }
+
+    #[test]
+    fn pdf_and_video_are_rejected() -> Result<()> {
+        let (client, _) = client("prompt")?;
+        for (media_type, mime_type) in [
+            (BamlMediaType::Pdf, "application/pdf"),
+            (BamlMediaType::Video, "video/mp4"),
+        ] {
+            let media = BamlMedia::base64(media_type, "AAAA".into(), Some(mime_type.into()));
+            let err = client
+                .to_media_message(Default::default(), &media)
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("{media_type} is not supported by provider openai-generic")
+            );
+        }
+
+        let image = BamlMedia::base64(
+            BamlMediaType::Image,
+            "AAAA".into(),
+            Some("image/png".into()),
+        );
+        assert_eq!(
+            json!(client.to_media_message(Default::default(), &image)?),
+            json!({
+                "type": "image_url",
+                "image_url": { "url": "data:image/png;base64,AAAA" },
+            })
+        );
+        Ok(())
+    }
+}
@@ -49,6 +49,8 @@ pub fn resolve_properties(
     };
 
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
 
 
     let properties = {
@@ -71,5 +73,7 @@ pub fn resolve_properties(
         proxy_url: ctx.env.get("BOUNDARY_PROXY_URL").map(|s| s.to_string()),
         query_params,
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
@@ -28,6 +28,8 @@ pub fn resolve_properties(
         _ => None,
     };
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
 
     let properties = properties.finalize();
 
@@ -41,5 +43,7 @@ pub fn resolve_properties(
         query_params: Default::default(),
         allowed_metadata,
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
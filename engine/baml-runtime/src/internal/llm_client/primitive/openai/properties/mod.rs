@@ -3,7 +3,9 @@ pub(crate) mod generic;
 pub(crate) mod ollama;
 pub(crate) mod openai;
 
-use crate::internal::llm_client::{AllowedMetadata, SupportedRequestModes};
+use crate::internal::llm_client::{
+    output_schema::OutputMode, AllowedMetadata, RequestTimeouts, SupportedRequestModes,
+};
 use std::collections::HashMap;
 
 pub struct PostRequestProperties {
@@ -17,4 +19,6 @@ pub struct PostRequestProperties {
     pub properties: HashMap<String, serde_json::Value>,
     pub allowed_metadata: AllowedMetadata,
     pub supported_request_modes: SupportedRequestModes,
+    pub output_mode: OutputMode,
+    pub request_timeouts: RequestTimeouts,
 }
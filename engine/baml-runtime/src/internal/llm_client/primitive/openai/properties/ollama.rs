@@ -21,6 +21,8 @@ pub fn resolve_properties(
     let headers = properties.pull_headers()?;
 
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
 
     Ok(PostRequestProperties {
         default_role,
@@ -36,5 +38,7 @@ pub fn resolve_properties(
             .unwrap_or(None),
         query_params: Default::default(),
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
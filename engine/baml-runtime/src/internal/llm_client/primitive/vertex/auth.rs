@@ -0,0 +1,382 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+#[cfg(not(target_arch = "wasm32"))]
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use web_time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use crate::internal::wasm_jwt::encode_jwt;
+
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_METADATA_HOST: &str = "metadata.google.internal";
+
+/// Tokens are refreshed once they have less than this long left, so that a
+/// request never goes out with a token that expires mid-flight. Tokens that
+/// live less than twice this long are refreshed halfway through instead.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Where an access token comes from. Tokens are cached per source.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) enum TokenSource {
+    /// A credentials file, re-read only when its token needs refreshing.
+    File(String),
+    /// Credentials given inline, identified by their account.
+    Inline(Credentials),
+    /// The GCE metadata server, or a stand-in for it at `GCE_METADATA_HOST`.
+    MetadataServer(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) enum Credentials {
+    ServiceAccount(ServiceAccount),
+    /// User credentials, as written by `gcloud auth application-default login`.
+    AuthorizedUser(AuthorizedUser),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub(super) struct ServiceAccount {
+    client_email: String,
+    private_key: String,
+    #[serde(default)]
+    token_uri: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub(super) struct AuthorizedUser {
+    client_id: String,
+    client_secret: String,
+    refresh_token: String,
+}
+
+impl Credentials {
+    pub(super) fn from_json(value: serde_json::Value) -> Result<Self> {
+        match value.get("type").and_then(|t| t.as_str()) {
+            Some("authorized_user") => Ok(Self::AuthorizedUser(
+                serde_json::from_value(value).context("Failed to parse user credentials")?,
+            )),
+            // Service account keys are the default, since older examples omit `type`.
+            Some("service_account") | None => Ok(Self::ServiceAccount(
+                serde_json::from_value(value)
+                    .context("Failed to parse service account credentials")?,
+            )),
+            Some(other) => anyhow::bail!("Unsupported Google credentials type: {other}"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_file(path: &str) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open credentials file {path}"))?;
+        Self::from_json(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+}
+
+/// Where `gcloud auth application-default login` writes user credentials, if
+/// that file exists.
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn well_known_credentials_file(env: &HashMap<String, String>) -> Option<String> {
+    let config_dir = match env.get("CLOUDSDK_CONFIG") {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None if cfg!(windows) => std::path::Path::new(env.get("APPDATA")?).join("gcloud"),
+        None => std::path::Path::new(env.get("HOME")?).join(".config/gcloud"),
+    };
+    let path = config_dir.join("application_default_credentials.json");
+    path.is_file().then(|| path.to_string_lossy().into_owned())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn metadata_host(env: &HashMap<String, String>) -> String {
+    env.get("GCE_METADATA_HOST")
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_METADATA_HOST.to_string())
+}
+
+#[derive(Clone)]
+struct AccessToken {
+    value: String,
+    /// When to stop using this token, a little before it expires.
+    refresh_at: Instant,
+}
+
+impl AccessToken {
+    fn new(value: String, lifetime: Duration) -> Self {
+        Self {
+            value,
+            refresh_at: Instant::now() + lifetime - REFRESH_MARGIN.min(lifetime / 2),
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        Instant::now() < self.refresh_at
+    }
+}
+
+type TokenSlot = Arc<futures::lock::Mutex<Option<AccessToken>>>;
+
+/// Shared by every Vertex client, so clients using the same account share
+/// one token.
+static TOKEN_CACHE: OnceLock<Mutex<HashMap<TokenSource, TokenSlot>>> = OnceLock::new();
+
+/// Get an access token for `source`, fetching a new one only if the cached
+/// token is missing or about to expire.
+pub(super) async fn access_token(source: &TokenSource) -> Result<String> {
+    cached_token(source, || fetch_token(source)).await
+}
+
+async fn cached_token<F, Fut>(source: &TokenSource, fetch: F) -> Result<String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<AccessToken>>,
+{
+    let slot = TOKEN_CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(source.clone())
+        .or_default()
+        .clone();
+
+    // Waiting on the slot while another request refreshes it means only one
+    // of them goes to the token endpoint.
+    let mut slot = slot.lock().await;
+    if let Some(token) = slot.as_ref().filter(|t| t.is_fresh()) {
+        return Ok(token.value.clone());
+    }
+    let token = fetch().await?;
+    let value = token.value.clone();
+    *slot = Some(token);
+    Ok(value)
+}
+
+async fn fetch_token(source: &TokenSource) -> Result<AccessToken> {
+    match source {
+        #[cfg(not(target_arch = "wasm32"))]
+        TokenSource::File(path) => fetch_for_credentials(&Credentials::from_file(path)?).await,
+        #[cfg(target_arch = "wasm32")]
+        TokenSource::File(_) => anyhow::bail!(
+            "Reading from files not supported in BAML playground. Pass in your credentials file as a string to the 'GOOGLE_APPLICATION_CREDENTIALS_CONTENT' environment variable."
+        ),
+        TokenSource::Inline(credentials) => fetch_for_credentials(credentials).await,
+        TokenSource::MetadataServer(host) => {
+            let request = reqwest::Client::new()
+                .get(format!(
+                    "http://{host}/computeMetadata/v1/instance/service-accounts/default/token"
+                ))
+                .header("Metadata-Flavor", "Google");
+            parse_token_response(request.send().await.with_context(|| {
+                format!(
+                    "No Google credentials were configured, and the metadata server at {host} could not be reached"
+                )
+            })?)
+            .await
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Claims {
+    iss: String,
+    scope: String,
+    aud: String,
+    exp: i64,
+    iat: i64,
+}
+
+async fn fetch_for_credentials(credentials: &Credentials) -> Result<AccessToken> {
+    let client = reqwest::Client::new();
+    let response = match credentials {
+        Credentials::ServiceAccount(service_account) => {
+            let token_uri = service_account
+                .token_uri
+                .as_deref()
+                .unwrap_or(DEFAULT_TOKEN_URI);
+            let now = Utc::now();
+            let claims = Claims {
+                iss: service_account.client_email.clone(),
+                scope: SCOPE.to_string(),
+                aud: token_uri.to_string(),
+                exp: (now + chrono::Duration::hours(1)).timestamp(),
+                iat: now.timestamp(),
+            };
+
+            #[cfg(not(target_arch = "wasm32"))]
+            let jwt = encode(
+                &Header::new(Algorithm::RS256),
+                &claims,
+                &EncodingKey::from_rsa_pem(service_account.private_key.as_bytes())?,
+            )?;
+
+            #[cfg(target_arch = "wasm32")]
+            let jwt = encode_jwt(&serde_json::to_value(claims)?, &service_account.private_key)
+                .await
+                .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+
+            client
+                .post(token_uri)
+                .form(&[
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                    ("assertion", &jwt),
+                ])
+                .send()
+                .await?
+        }
+        Credentials::AuthorizedUser(user) => {
+            client
+                .post(DEFAULT_TOKEN_URI)
+                .form(&[
+                    ("grant_type", "refresh_token"),
+                    ("client_id", &user.client_id),
+                    ("client_secret", &user.client_secret),
+                    ("refresh_token", &user.refresh_token),
+                ])
+                .send()
+                .await?
+        }
+    };
+    parse_token_response(response).await
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    /// Seconds until the token expires.
+    expires_in: Option<u64>,
+}
+
+async fn parse_token_response(response: reqwest::Response) -> Result<AccessToken> {
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!(
+            "Failed to get a Google access token ({}): {}",
+            status,
+            response.text().await.unwrap_or_default()
+        );
+    }
+    let response: TokenResponse = response
+        .json()
+        .await
+        .context("Token exchange did not return an access token")?;
+    Ok(AccessToken::new(
+        response.access_token,
+        // Google tokens last an hour unless told otherwise.
+        Duration::from_secs(response.expires_in.unwrap_or(3600)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn token(value: &str, lifetime: Duration) -> AccessToken {
+        AccessToken::new(value.to_string(), lifetime)
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_share_one_fetch() {
+        let source = TokenSource::MetadataServer("concurrent.test".to_string());
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            Ok(token("shared", Duration::from_secs(3600)))
+        };
+
+        let (a, b) = tokio::join!(cached_token(&source, fetch), cached_token(&source, fetch));
+        assert_eq!(a.unwrap(), "shared");
+        assert_eq!(b.unwrap(), "shared");
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expiring_tokens_are_refreshed() {
+        let source = TokenSource::MetadataServer("expiring.test".to_string());
+        let first = cached_token(&source, || async { Ok(token("old", Duration::ZERO)) }).await;
+        assert_eq!(first.unwrap(), "old");
+
+        let second = cached_token(&source, || async {
+            Ok(token("new", Duration::from_secs(3600)))
+        })
+        .await;
+        assert_eq!(second.unwrap(), "new");
+
+        let third = cached_token(&source, || async {
+            anyhow::bail!("should have used the cached token")
+        })
+        .await;
+        assert_eq!(third.unwrap(), "new");
+    }
+
+    #[tokio::test]
+    async fn short_lived_tokens_are_reused() {
+        let source = TokenSource::MetadataServer("short-lived.test".to_string());
+        let first = cached_token(&source, || async {
+            Ok(token("short", Duration::from_secs(60)))
+        })
+        .await;
+        assert_eq!(first.unwrap(), "short");
+
+        let second = cached_token(&source, || async {
+            anyhow::bail!("should have used the cached token")
+        })
+        .await;
+        assert_eq!(second.unwrap(), "short");
+    }
+
+    #[test]
+    fn refresh_margin_is_capped_at_half_the_lifetime() {
+        let before = Instant::now();
+        let short = token("short", Duration::from_secs(60));
+        assert!(short.refresh_at >= before + Duration::from_secs(30));
+        assert!(short.refresh_at <= Instant::now() + Duration::from_secs(30));
+
+        let before = Instant::now();
+        let long = token("long", Duration::from_secs(3600));
+        assert!(long.refresh_at >= before + Duration::from_secs(3600) - REFRESH_MARGIN);
+        assert!(long.refresh_at <= Instant::now() + Duration::from_secs(3600) - REFRESH_MARGIN);
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_not_cached() {
+        let source = TokenSource::MetadataServer("failing.test".to_string());
+        let failed = cached_token(&source, || async { anyhow::bail!("unreachable") }).await;
+        assert!(failed.is_err());
+
+        let retried = cached_token(&source, || async {
+            Ok(token("recovered", Duration::from_secs(3600)))
+        })
+        .await;
+        assert_eq!(retried.unwrap(), "recovered");
+    }
+
+    #[test]
+    fn parses_credential_types() {
+        let service_account = Credentials::from_json(serde_json::json!({
+            "client_email": "bot@project.iam.gserviceaccount.com",
+            "private_key": "key",
+        }))
+        .unwrap();
+        assert!(matches!(service_account, Credentials::ServiceAccount(_)));
+
+        let user = Credentials::from_json(serde_json::json!({
+            "type": "authorized_user",
+            "client_id": "id",
+            "client_secret": "secret",
+            "refresh_token": "refresh",
+        }))
+        .unwrap();
+        assert!(matches!(user, Credentials::AuthorizedUser(_)));
+
+        assert!(Credentials::from_json(serde_json::json!({ "type": "external_account" })).is_err());
+    }
+}
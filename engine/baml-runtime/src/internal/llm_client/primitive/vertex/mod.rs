@@ -1,3 +1,4 @@
+mod auth;
 mod types;
 mod vertex_client;
 pub use vertex_client::VertexClient;
@@ -3,15 +3,16 @@ use crate::internal::llm_client::properties_hander::{ PropertiesHandler};
 use crate::internal::llm_client::traits::{
     ToProviderMessage, ToProviderMessageExt, WithClientProperties,
 };
-use crate::internal::llm_client::{AllowedMetadata, ResolveMediaUrls, SupportedRequestModes};
-#[cfg(target_arch = "wasm32")]
-use crate::internal::wasm_jwt::{encode_jwt, JwtError};
+use crate::internal::llm_client::{
+    output_schema::{OutputMode, OutputSchema},
+    AllowedMetadata, RequestTimeouts, ResolveMediaUrls, SupportedRequestModes,
+};
 use crate::RuntimeContext;
 use crate::{
     internal::llm_client::{
         primitive::{
             request::{make_parsed_request, make_request, RequestBuilder},
-            vertex::types::{FinishReason, VertexResponse},
+            vertex::types::{FinishReason, Part, VertexResponse},
         },
         traits::{
             SseResponseTrait, StreamResponse, WithChat, WithClient, WithNoCompletion,
@@ -23,16 +24,7 @@ use crate::{
     request::create_client,
 };
 use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
 use futures::StreamExt;
-#[cfg(not(target_arch = "wasm32"))]
-use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-#[cfg(not(target_arch = "wasm32"))]
-use std::fs::File;
-#[cfg(not(target_arch = "wasm32"))]
-use std::io::BufReader;
 
 use baml_types::BamlMediaContent;
 use eventsource_stream::Eventsource;
@@ -42,11 +34,12 @@ use internal_baml_jinja::{RenderContext_Client, RenderedChatMessage};
 use serde_json::json;
 use std::collections::HashMap;
 
+use super::auth::{self, Credentials, TokenSource};
+
 enum ServiceAccountDetails {
     None,
     RawAuthorizationHeader(String),
-    FilePath(String),
-    Json(serde_json::Map<String, serde_json::Value>),
+    Token(TokenSource),
 }
 
 impl Default for ServiceAccountDetails {
@@ -67,6 +60,8 @@ struct PostRequestProperties {
     location: Option<String>,
     allowed_metadata: AllowedMetadata,
     supported_request_modes: SupportedRequestModes,
+    output_mode: OutputMode,
+    request_timeouts: RequestTimeouts,
 }
 
 pub struct VertexClient {
@@ -78,21 +73,6 @@ pub struct VertexClient {
     properties: PostRequestProperties,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Claims {
-    iss: String,
-    scope: String,
-    aud: String,
-    exp: i64,
-    iat: i64,
-}
-
-#[derive(Debug, Deserialize)]
-struct ServiceAccount {
-    client_email: String,
-    private_key: String,
-}
-
 fn resolve_properties(
     mut properties: PropertiesHandler,
     ctx: &RuntimeContext,
@@ -119,31 +99,36 @@ fn resolve_properties(
             );
         }
 
+        let inline = |creds: serde_json::Map<String, serde_json::Value>| {
+            Credentials::from_json(serde_json::Value::Object(creds))
+                .map(|c| ServiceAccountDetails::Token(TokenSource::Inline(c)))
+        };
+
         if let Some(authz) = authz {
             ServiceAccountDetails::RawAuthorizationHeader(authz)
         } else if let Some(creds) = creds {
             match creds {
                 serde_json::Value::String(s) => match serde_json::from_str(&s) {
-                    Ok(service_account) => ServiceAccountDetails::Json(service_account),
-                    Err(_) => ServiceAccountDetails::FilePath(s),
+                    Ok(creds) => inline(creds)?,
+                    Err(_) => ServiceAccountDetails::Token(TokenSource::File(s)),
                 },
-                serde_json::Value::Object(o) => ServiceAccountDetails::Json(o),
+                serde_json::Value::Object(o) => inline(o)?,
                 _ => anyhow::bail!("credentials must be a string or JSON object"),
             }
         } else if let Some(creds_content) = creds_content {
-            ServiceAccountDetails::Json(
+            inline(
                 serde_json::from_str(&creds_content)
                     .context("Failed to parse credentials_content as a JSON object")?,
-            )
+            )?
         } else if let Some(path) = ctx.env.get("GOOGLE_APPLICATION_CREDENTIALS") {
-            ServiceAccountDetails::FilePath(path.to_string())
+            ServiceAccountDetails::Token(TokenSource::File(path.to_string()))
         } else if let Some(creds_content) = ctx.env.get("GOOGLE_APPLICATION_CREDENTIALS_CONTENT") {
-            ServiceAccountDetails::Json(
+            inline(
                 serde_json::from_str(&creds_content)
                     .context("Failed to parse credentials_content as a JSON object")?,
-            )
+            )?
         } else {
-            ServiceAccountDetails::None
+            application_default_credentials(ctx)
         }
     };
     let headers = properties.pull_headers()?;
@@ -167,6 +152,8 @@ fn resolve_properties(
     };
 
     let supported_request_modes = properties.pull_supported_request_modes()?;
+    let output_mode = properties.pull_output_mode()?;
+    let request_timeouts = properties.pull_request_timeouts()?;
 
     Ok(PostRequestProperties {
         default_role,
@@ -180,6 +167,8 @@ fn resolve_properties(
         proxy_url: ctx.env.get("BOUNDARY_PROXY_URL").map(|s| s.to_string()),
         allowed_metadata,
         supported_request_modes,
+        output_mode,
+        request_timeouts,
     })
 }
 
@@ -199,6 +188,20 @@ impl WithClientProperties for VertexClient {
     fn supports_streaming(&self) -> bool {
         self.properties.supported_request_modes.stream.unwrap_or(true)
     }
+    fn request_timeouts(&self) -> &crate::internal::llm_client::RequestTimeouts {
+        &self.properties.request_timeouts
+    }
+    fn resolved_options(&self) -> serde_json::Value {
+        json!({
+            "base_url": self.properties.base_url,
+            "headers": self.properties.headers,
+            "project_id": self.properties.project_id,
+            "location": self.properties.location,
+            "model_id": self.properties.model_id,
+            "properties": self.properties.properties,
+            "output_mode": self.properties.output_mode,
+        })
+    }
 }
 
 impl WithClient for VertexClient {
@@ -216,6 +219,7 @@ impl WithNoCompletion for VertexClient {}
 impl SseResponseTrait for VertexClient {
     fn response_stream(
         &self,
+        ctx: &RuntimeContext,
         resp: reqwest::Response,
         prompt: &Vec<RenderedChatMessage>,
         system_start: web_time::SystemTime,
@@ -225,6 +229,9 @@ impl SseResponseTrait for VertexClient {
         let client_name = self.context.name.clone();
         let model_id = self.properties.model_id.clone().unwrap_or_default();
         let params = self.properties.properties.clone();
+        let output_mode = self.features.output_mode;
+        let output_schema = output_mode.schema(ctx);
+        let mut arguments = String::new();
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -250,6 +257,7 @@ impl SseResponseTrait for VertexClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -282,8 +290,31 @@ impl SseResponseTrait for VertexClient {
                         };
 
                         if let Some(choice) = event.candidates.get(0) {
-                            if let Some(content) = choice.content.parts.get(0) {
-                                inner.content += &content.text;
+                            match (&output_schema, output_mode) {
+                                (Some(schema), OutputMode::Tool) => {
+                                    // Function call arguments are not streamed, they
+                                    // arrive whole in a single event.
+                                    if let Some(args) = choice
+                                        .content
+                                        .parts
+                                        .iter()
+                                        .find_map(|p| p.function_call.as_ref())
+                                        .and_then(|f| f.args.as_ref())
+                                    {
+                                        inner.content = schema.unwrap_arguments(&args.to_string());
+                                    }
+                                }
+                                (Some(schema), _) => {
+                                    if let Some(content) = choice.content.parts.get(0) {
+                                        arguments += &content.text;
+                                    }
+                                    inner.content = schema.unwrap_arguments(&arguments);
+                                }
+                                (None, _) => {
+                                    if let Some(content) = choice.content.parts.get(0) {
+                                        inner.content += &content.text;
+                                    }
+                                }
                             }
                             match choice.finish_reason.as_ref() {
                                 Some(FinishReason::Stop) => {
@@ -311,11 +342,11 @@ impl WithStreamChat for VertexClient {
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
-        self.response_stream(response, prompt, system_now, instant_now)
+        self.response_stream(ctx, response, prompt, system_now, instant_now)
     }
 }
 
@@ -337,6 +368,7 @@ impl VertexClient {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::EnsureMime,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client
                 .elem()
@@ -365,6 +397,7 @@ impl VertexClient {
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::EnsureMime,
                 allowed_metadata: properties.allowed_metadata.clone(),
+                output_mode: properties.output_mode,
             },
             retry_policy: client.retry_policy.clone(),
             client: create_client()?,
@@ -373,52 +406,20 @@ impl VertexClient {
     }
 }
 
-async fn get_access_token(service_account: &ServiceAccount) -> Result<String> {
-    let now = Utc::now();
-    let claims = Claims {
-        iss: service_account.client_email.clone(),
-        scope: "https://www.googleapis.com/auth/cloud-platform".to_string(),
-        aud: "https://oauth2.googleapis.com/token".to_string(),
-        exp: (now + Duration::hours(1)).timestamp(),
-        iat: now.timestamp(),
-    };
-
-    // Create the JWT
-
-    #[cfg(not(target_arch = "wasm32"))]
-    let jwt = encode(
-        &Header::new(Algorithm::RS256),
-        &claims,
-        &EncodingKey::from_rsa_pem(service_account.private_key.as_bytes())?,
-    )?;
+/// Application Default Credentials, for when no credentials were given: the
+/// file written by `gcloud auth application-default login`, or else the
+/// metadata server.
+#[cfg(not(target_arch = "wasm32"))]
+fn application_default_credentials(ctx: &RuntimeContext) -> ServiceAccountDetails {
+    ServiceAccountDetails::Token(match auth::well_known_credentials_file(&ctx.env) {
+        Some(path) => TokenSource::File(path),
+        None => TokenSource::MetadataServer(auth::metadata_host(&ctx.env)),
+    })
+}
 
-    #[cfg(target_arch = "wasm32")]
-    let jwt = encode_jwt(&serde_json::to_value(claims)?, &service_account.private_key)
-        .await
-        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
-
-    // Make the token request
-    let client = reqwest::Client::new();
-    let params = [
-        ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
-        ("assertion", &jwt),
-    ];
-    let res: Value = client
-        .post("https://oauth2.googleapis.com/token")
-        .form(&params)
-        .send()
-        .await?
-        .json()
-        .await?;
-
-    Ok(res
-        .as_object()
-        .context("Token exchange did not return a JSON object")?
-        .get("access_token")
-        .context("Access token not found in response")?
-        .as_str()
-        .context("Access token is not a string")?
-        .to_string())
+#[cfg(target_arch = "wasm32")]
+fn application_default_credentials(_ctx: &RuntimeContext) -> ServiceAccountDetails {
+    ServiceAccountDetails::None
 }
 
 impl RequestBuilder for VertexClient {
@@ -428,6 +429,7 @@ impl RequestBuilder for VertexClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &Vec<RenderedChatMessage>>,
         allow_proxy: bool,
         stream: bool,
@@ -487,27 +489,7 @@ impl RequestBuilder for VertexClient {
                 anyhow::bail!("No service account was specified.");
             }
             ServiceAccountDetails::RawAuthorizationHeader(token) => token.to_string(),
-            ServiceAccountDetails::FilePath(path) => {
-                #[cfg(not(target_arch = "wasm32"))]
-                {
-                    let file = File::open(path)?;
-                    let reader = BufReader::new(file);
-                    let service_account: ServiceAccount = serde_json::from_reader(reader)?;
-
-                    get_access_token(&service_account).await?
-                }
-                #[cfg(target_arch = "wasm32")]
-                {
-                    anyhow::bail!(
-                        "Reading from files not supported in BAML playground. Pass in your credentials file as a string to the 'GOOGLE_APPLICATION_CREDENTIALS_CONTENT' environment variable."
-                    );
-                }
-            }
-            ServiceAccountDetails::Json(token) => {
-                let service_account: ServiceAccount =
-                    serde_json::from_value(serde_json::Value::Object(token.clone()))?;
-                get_access_token(&service_account).await?
-            }
+            ServiceAccountDetails::Token(source) => auth::access_token(source).await?,
         };
 
         req = req.header("Authorization", format!("Bearer {}", access_token));
@@ -526,6 +508,45 @@ impl RequestBuilder for VertexClient {
             either::Either::Right(messages) => body_obj.extend(self.chat_to_message(messages)?),
         }
 
+        if let (Some(schema), either::Either::Right(_)) =
+            (self.features.output_mode.schema(ctx), prompt)
+        {
+            match self.features.output_mode {
+                OutputMode::Tool => {
+                    body_obj.insert(
+                        "tools".into(),
+                        json!([{
+                            "functionDeclarations": [{
+                                "name": schema.name,
+                                "description": schema.description(),
+                                "parameters": schema.openapi_schema(),
+                            }],
+                        }]),
+                    );
+                    body_obj.insert(
+                        "toolConfig".into(),
+                        json!({
+                            "functionCallingConfig": {
+                                "mode": "ANY",
+                                "allowedFunctionNames": [schema.name],
+                            },
+                        }),
+                    );
+                }
+                _ => {
+                    let generation_config = body_obj
+                        .entry("generationConfig")
+                        .or_insert_with(|| json!({}));
+                    if let Some(generation_config) = generation_config.as_object_mut() {
+                        generation_config
+                            .insert("responseMimeType".into(), json!("application/json"));
+                        generation_config
+                            .insert("responseSchema".into(), schema.openapi_schema().clone());
+                    }
+                }
+            }
+        }
+
         Ok(req.json(&body))
     }
     fn request_options(&self) -> &HashMap<String, serde_json::Value> {
@@ -541,15 +562,19 @@ impl WithChat for VertexClient {
         ))
     }
 
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &Vec<RenderedChatMessage>) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<VertexResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<VertexResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -568,10 +593,30 @@ impl WithChat for VertexClient {
         }
         let usage_metadata = response.usage_metadata.clone().unwrap();
 
+        let content = match candidate_content(
+            self.features.output_mode,
+            self.features.output_mode.schema(ctx).as_deref(),
+            &response.candidates[0].content.parts,
+        ) {
+            Ok(content) => content,
+            Err(message) => {
+                return LLMResponse::LLMFailure(LLMErrorResponse {
+                    client: self.context.name.to_string(),
+                    model: None,
+                    prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
+                    start_time: system_now,
+                    request_options: self.properties.properties.clone(),
+                    latency: instant_now.elapsed(),
+                    message,
+                    code: ErrorCode::Other(200),
+                })
+            }
+        };
+
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
-            content: response.candidates[0].content.parts[0].text.clone(),
+            content,
             start_time: system_now,
             latency: instant_now.elapsed(),
             request_options: self.properties.properties.clone(),
@@ -593,6 +638,7 @@ impl WithChat for VertexClient {
                 prompt_tokens: usage_metadata.prompt_token_count,
                 output_tokens: usage_metadata.candidates_token_count,
                 total_tokens: usage_metadata.total_token_count,
+                cached: false,
             },
         })
     }
@@ -611,6 +657,29 @@ fn convert_completion_prompt_to_body(prompt: &String) -> HashMap<String, serde_j
     map
 }
 
+/// The text BAML parses out of a candidate: the text of its first part, or
+/// the unwrapped function call arguments / structured output when the output
+/// mode sends a schema.
+fn candidate_content(
+    output_mode: OutputMode,
+    output_schema: Option<&OutputSchema>,
+    parts: &[Part],
+) -> Result<String, String> {
+    let text = parts.first().map_or("", |p| p.text.as_str());
+    let Some(schema) = output_schema else {
+        return Ok(text.to_string());
+    };
+    match output_mode {
+        OutputMode::Tool => parts
+            .iter()
+            .find_map(|p| p.function_call.as_ref())
+            .and_then(|f| f.args.as_ref())
+            .map(|args| schema.unwrap_arguments(&args.to_string()))
+            .ok_or_else(|| format!("Expected a call to function {}", schema.name)),
+        _ => Ok(schema.unwrap_arguments(text)),
+    }
+}
+
 impl ToProviderMessage for VertexClient {
     fn to_chat_message(
         &self,
@@ -683,3 +752,95 @@ impl ToProviderMessageExt for VertexClient {
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::llm_client::test_support::{self, schema};
+
+    async fn request_body(output_mode: &str) -> Result<serde_json::Value> {
+        let (client, ctx) = test_support::client(
+            VertexClient::dynamic_new,
+            "vertex-ai",
+            &[
+                ("model", "gemini-1.5-pro"),
+                ("location", "us-central1"),
+                ("project_id", "test"),
+                ("authorization", "test"),
+                ("output_mode", output_mode),
+            ],
+        )?;
+        test_support::request_body(&client, &ctx).await
+    }
+
+    fn parts(value: serde_json::Value) -> Vec<Part> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tool_mode_forces_the_function() -> Result<()> {
+        let body = request_body("tool").await?;
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "functionDeclarations": [{
+                    "name": "Extract",
+                    "description": "Respond with the output of Extract.",
+                    "parameters": schema().openapi_schema(),
+                }],
+            }])
+        );
+        assert_eq!(
+            body["toolConfig"],
+            json!({
+                "functionCallingConfig": { "mode": "ANY", "allowedFunctionNames": ["Extract"] },
+            })
+        );
+        assert!(body.get("generationConfig").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn json_schema_mode_sets_response_schema() -> Result<()> {
+        let body = request_body("json_schema").await?;
+        assert_eq!(
+            body["generationConfig"],
+            json!({
+                "responseMimeType": "application/json",
+                "responseSchema": schema().openapi_schema(),
+            })
+        );
+        assert!(body.get("tools").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn function_call_args_are_extracted() {
+        let parts = parts(json!([
+            { "functionCall": { "name": "Extract", "args": { "output": ["a"] } } },
+        ]));
+        assert_eq!(
+            candidate_content(OutputMode::Tool, Some(&schema()), &parts),
+            Ok(r#"["a"]"#.to_string())
+        );
+
+        let parts = self::parts(json!([{ "text": "no" }]));
+        assert_eq!(
+            candidate_content(OutputMode::Tool, Some(&schema()), &parts),
+            Err("Expected a call to function Extract".to_string())
+        );
+    }
+
+    #[test]
+    fn structured_output_is_unwrapped() {
+        let parts = parts(json!([{ "text": "{\"output\": [\"a\"]}" }]));
+        assert_eq!(
+            candidate_content(OutputMode::JsonSchema, Some(&schema()), &parts),
+            Ok(r#"["a"]"#.to_string())
+        );
+        assert_eq!(
+            candidate_content(OutputMode::Prompt, None, &parts),
+            Ok("{\"output\": [\"a\"]}".to_string())
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 
-use super::{AllowedMetadata, SupportedRequestModes};
+use super::{output_schema::OutputMode, AllowedMetadata, RequestTimeouts, SupportedRequestModes};
 
 pub(super) struct PropertiesHandler {
     properties: HashMap<String, serde_json::Value>,
@@ -28,7 +28,10 @@ impl PropertiesHandler {
             | "base_url"
             | "api_key"
             | "headers"
-            | "default_role" => {
+            | "default_role"
+            | "output_mode"
+            | "request_timeout_ms"
+            | "idle_timeout_ms" => {
                 unreachable!("{} is a reserved key in options", key)
             }
             _ => self.properties.remove(key),
@@ -151,6 +154,36 @@ impl PropertiesHandler {
             stream: supports_streaming,
         })
     }
+
+    pub fn pull_request_timeouts(&mut self) -> Result<RequestTimeouts> {
+        let mut pull = |key: &str| match self.get(key) {
+            Some(v) => match v.as_u64() {
+                Some(ms) if ms > 0 => Ok(Some(web_time::Duration::from_millis(ms))),
+                _ => Err(anyhow::anyhow!(
+                    "{key} must be a positive number of milliseconds: Got {:?}",
+                    v
+                )),
+            },
+            None => Ok(None),
+        };
+
+        Ok(RequestTimeouts {
+            request: pull("request_timeout_ms")?,
+            idle: pull("idle_timeout_ms")?,
+        })
+    }
+
+    pub fn pull_output_mode(&mut self) -> Result<OutputMode> {
+        match self.get("output_mode") {
+            Some(v) => serde_json::from_value(v.clone()).map_err(|_| {
+                anyhow::anyhow!(
+                    "output_mode must be one of \"prompt\", \"tool\" or \"json_schema\": Got {:?}",
+                    v
+                )
+            }),
+            None => Ok(OutputMode::Prompt),
+        }
+    }
 }
 
 impl crate::client_registry::ClientProperty {
@@ -8,6 +8,13 @@ pub struct CallablePolicy {
     strategy: RetryPolicyStrategy,
     current: std::time::Duration,
     counter: u32,
+    retry_on_parse_failure: bool,
+}
+
+impl CallablePolicy {
+    pub fn retry_on_parse_failure(&self) -> bool {
+        self.retry_on_parse_failure
+    }
 }
 
 impl From<RetryPolicyWalker<'_>> for CallablePolicy {
@@ -24,6 +31,7 @@ impl From<RetryPolicyWalker<'_>> for CallablePolicy {
                 }
             },
             counter: 0,
+            retry_on_parse_failure: policy.retry_on_parse_failure(),
         }
     }
 }
@@ -7,7 +7,10 @@ use internal_baml_core::ir::{repr::ClientSpec, ClientWalker};
 use crate::{
     client_registry::ClientProperty,
     internal::llm_client::{
-        orchestrator::{ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState},
+        orchestrator::{
+            ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState,
+            OrchestratorNodeIterator,
+        },
         properties_hander::PropertiesHandler,
     },
     runtime_interface::InternalClientLookup,
@@ -19,12 +22,13 @@ pub struct FallbackStrategy {
     pub(super) retry_policy: Option<String>,
     // TODO: We can add conditions to each client
     client_specs: Vec<ClientSpec>,
+    retry_on_parse_failure: bool,
 }
 
 fn resolve_strategy(
     mut properties: PropertiesHandler,
     _ctx: &RuntimeContext,
-) -> Result<Vec<ClientSpec>> {
+) -> Result<(Vec<ClientSpec>, bool)> {
     let strategy = properties
         .remove_serde::<Vec<String>>("strategy")
         .context("Failed to resolve strategy into string[]")?;
@@ -38,9 +42,14 @@ fn resolve_strategy(
         anyhow::bail!("Missing a strategy field");
     };
 
+    let retry_on_parse_failure = properties
+        .remove_serde::<bool>("retry_on_parse_failure")
+        .context("Failed to parse retry_on_parse_failure: not a boolean")?
+        .unwrap_or(false);
+
     let properties = properties.finalize();
     if !properties.is_empty() {
-        let supported_keys = ["strategy"];
+        let supported_keys = ["strategy", "retry_on_parse_failure"];
         let unknown_keys = properties.keys().map(String::from).collect::<Vec<_>>();
         anyhow::bail!(
             "Unknown keys: {}. Supported keys are: {}",
@@ -49,7 +58,10 @@ fn resolve_strategy(
         );
     }
 
-    Ok(strategy.into_iter().map(ClientSpec::new_from_id).collect())
+    Ok((
+        strategy.into_iter().map(ClientSpec::new_from_id).collect(),
+        retry_on_parse_failure,
+    ))
 }
 
 impl TryFrom<(&ClientProperty, &RuntimeContext)> for FallbackStrategy {
@@ -58,11 +70,13 @@ impl TryFrom<(&ClientProperty, &RuntimeContext)> for FallbackStrategy {
     fn try_from(
         (client, ctx): (&ClientProperty, &RuntimeContext),
     ) -> std::result::Result<Self, Self::Error> {
-        let strategy = resolve_strategy(client.property_handler()?, ctx)?;
+        let (strategy, retry_on_parse_failure) =
+            resolve_strategy(client.property_handler()?, ctx)?;
         Ok(Self {
             name: client.name.clone(),
             retry_policy: client.retry_policy.clone(),
             client_specs: strategy,
+            retry_on_parse_failure,
         })
     }
 }
@@ -72,11 +86,12 @@ impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for FallbackStrategy {
 
     fn try_from((client, ctx): (&ClientWalker, &RuntimeContext)) -> Result<Self> {
         let properties = super::super::resolve_properties_walker(client, ctx)?;
-        let strategy = resolve_strategy(properties, ctx)?;
+        let (strategy, retry_on_parse_failure) = resolve_strategy(properties, ctx)?;
         Ok(Self {
             name: client.item.elem.name.clone(),
             retry_policy: client.retry_policy().as_ref().map(String::from),
             client_specs: strategy,
+            retry_on_parse_failure,
         })
     }
 }
@@ -88,8 +103,8 @@ impl IterOrchestrator for FallbackStrategy {
         _previous: OrchestrationScope,
         ctx: &RuntimeContext,
         client_lookup: &'a dyn InternalClientLookup<'a>,
-    ) -> Result<crate::internal::llm_client::orchestrator::OrchestratorNodeIterator> {
-        let items = self
+    ) -> Result<OrchestratorNodeIterator> {
+        let items: OrchestratorNodeIterator = self
             .client_specs
             .iter()
             .enumerate()
@@ -118,6 +133,13 @@ impl IterOrchestrator for FallbackStrategy {
             .flatten()
             .collect();
 
-        Ok(items)
+        if self.retry_on_parse_failure {
+            Ok(items
+                .into_iter()
+                .map(|node| node.with_retry_on_parse_failure())
+                .collect())
+        } else {
+            Ok(items)
+        }
     }
 }
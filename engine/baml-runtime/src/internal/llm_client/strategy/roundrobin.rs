@@ -32,6 +32,7 @@ pub struct RoundRobinStrategy {
     client_specs: Vec<ClientSpec>,
     #[serde(serialize_with = "serialize_atomic")]
     current_index: AtomicUsize,
+    retry_on_parse_failure: bool,
 }
 
 fn serialize_atomic<S>(value: &AtomicUsize, serializer: S) -> Result<S::Ok, S::Error>
@@ -56,7 +57,7 @@ impl RoundRobinStrategy {
 fn resolve_strategy(
     mut properties: PropertiesHandler,
     _ctx: &RuntimeContext,
-) -> Result<(Vec<ClientSpec>, usize)> {
+) -> Result<(Vec<ClientSpec>, usize, bool)> {
     let strategy = properties
         .remove_serde::<Vec<String>>("strategy")
         .context("Failed to parse strategy into string[]")?;
@@ -74,9 +75,14 @@ fn resolve_strategy(
         .remove_serde::<usize>("start")
         .context("Failed to parse start: not a number")?;
 
+    let retry_on_parse_failure = properties
+        .remove_serde::<bool>("retry_on_parse_failure")
+        .context("Failed to parse retry_on_parse_failure: not a boolean")?
+        .unwrap_or(false);
+
     let properties = properties.finalize();
     if !properties.is_empty() {
-        let supported_keys = ["strategy", "start"];
+        let supported_keys = ["strategy", "start", "retry_on_parse_failure"];
         let unknown_keys = properties.keys().map(String::from).collect::<Vec<_>>();
         anyhow::bail!(
             "Unknown keys: {}. Supported keys are: {}",
@@ -105,6 +111,7 @@ fn resolve_strategy(
     Ok((
         strategy.into_iter().map(ClientSpec::new_from_id).collect(),
         start,
+        retry_on_parse_failure,
     ))
 }
 
@@ -114,13 +121,15 @@ impl TryFrom<(&ClientProperty, &RuntimeContext)> for RoundRobinStrategy {
     fn try_from(
         (client, ctx): (&ClientProperty, &RuntimeContext),
     ) -> std::result::Result<Self, Self::Error> {
-        let (strategy, start) = resolve_strategy(client.property_handler()?, ctx)?;
+        let (strategy, start, retry_on_parse_failure) =
+            resolve_strategy(client.property_handler()?, ctx)?;
 
         Ok(RoundRobinStrategy {
             name: client.name.clone(),
             retry_policy: client.retry_policy.clone(),
             client_specs: strategy,
             current_index: AtomicUsize::new(start),
+            retry_on_parse_failure,
         })
     }
 }
@@ -130,12 +139,13 @@ impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for RoundRobinStrategy {
 
     fn try_from((client, ctx): (&ClientWalker, &RuntimeContext)) -> Result<Self> {
         let properties = super::super::resolve_properties_walker(client, ctx)?;
-        let (strategy, start) = resolve_strategy(properties, ctx)?;
+        let (strategy, start, retry_on_parse_failure) = resolve_strategy(properties, ctx)?;
         Ok(Self {
             name: client.item.elem.name.clone(),
             retry_policy: client.retry_policy().as_ref().map(String::from),
             client_specs: strategy,
             current_index: AtomicUsize::new(start),
+            retry_on_parse_failure,
         })
     }
 }
@@ -157,11 +167,20 @@ impl IterOrchestrator for Arc<RoundRobinStrategy> {
         let client_spec = &self.client_specs[next];
         let client = client_lookup.get_llm_provider(client_spec, ctx).unwrap();
         let client = client.clone();
-        client.iter_orchestrator(
+        let nodes = client.iter_orchestrator(
             state,
             ExecutionScope::RoundRobin(self.clone(), next).into(),
             ctx,
             client_lookup,
-        )
+        )?;
+
+        if self.retry_on_parse_failure {
+            Ok(nodes
+                .into_iter()
+                .map(|node| node.with_retry_on_parse_failure())
+                .collect())
+        } else {
+            Ok(nodes)
+        }
     }
 }
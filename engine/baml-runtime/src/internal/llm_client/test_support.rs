@@ -0,0 +1,77 @@
+//! Fixtures shared by the tests of the provider clients.
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use baml_types::{BamlMap, BamlValue, FieldType};
+use internal_baml_jinja::{types::OutputFormatContent, ChatMessagePart, RenderedChatMessage};
+
+use crate::{client_registry::ClientProperty, RuntimeContext, RuntimeContextManager};
+
+use super::{output_schema::OutputSchema, primitive::request::RequestBuilder};
+
+/// What the function under test returns: a list of strings, asked for as
+/// the output of `Extract`.
+pub(crate) fn schema() -> OutputSchema {
+    OutputSchema::new(
+        "Extract",
+        &OutputFormatContent::target(FieldType::list(FieldType::string())).build(),
+    )
+}
+
+/// A context calling a function that returns [`schema`].
+pub(crate) fn context() -> RuntimeContext {
+    let mut ctx = RuntimeContextManager::new_from_env_vars(HashMap::new(), None)
+        .create_ctx_with_default(std::iter::empty::<&str>());
+    ctx.output_schema = Some(Arc::new(schema()));
+    ctx
+}
+
+/// Builds a client named `Test` with `new`, for `provider` with `options`,
+/// along with the [`context`] it runs in.
+pub(crate) fn client<T>(
+    new: impl FnOnce(&ClientProperty, &RuntimeContext) -> Result<T>,
+    provider: &str,
+    options: &[(&str, &str)],
+) -> Result<(T, RuntimeContext)> {
+    let ctx = context();
+    let options: BamlMap<String, BamlValue> = options
+        .iter()
+        .map(|(k, v)| (k.to_string(), BamlValue::String(v.to_string())))
+        .collect();
+    let client = new(
+        &ClientProperty {
+            name: "Test".to_string(),
+            provider: provider.to_string(),
+            retry_policy: None,
+            options,
+        },
+        &ctx,
+    )?;
+    Ok((client, ctx))
+}
+
+/// A user message saying hi.
+pub(crate) fn prompt() -> Vec<RenderedChatMessage> {
+    vec![RenderedChatMessage {
+        role: "user".to_string(),
+        allow_duplicate_role: false,
+        parts: vec![ChatMessagePart::Text("hi".to_string())],
+    }]
+}
+
+/// The JSON body of the request `client` sends for [`prompt`].
+pub(crate) async fn request_body(
+    client: &impl RequestBuilder,
+    ctx: &RuntimeContext,
+) -> Result<serde_json::Value> {
+    let request = client
+        .build_request(ctx, either::Either::Right(&prompt()), false, false)
+        .await?
+        .build()?;
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .ok_or_else(|| anyhow::anyhow!("Request has no body"))?;
+    Ok(serde_json::from_slice(body)?)
+}
@@ -11,7 +11,7 @@ pub use self::{
     completion::{WithCompletion, WithNoCompletion, WithStreamCompletion},
 };
 use super::{primitive::request::RequestBuilder, LLMResponse, ModelFeatures};
-use crate::{internal::llm_client::ResolveMediaUrls, RenderCurlSettings};
+use crate::{internal::llm_client::ResolveMediaUrls, HTTPRequest, RenderCurlSettings};
 use crate::{internal::prompt_renderer::PromptRenderer, RuntimeContext};
 use baml_types::{BamlMedia, BamlMediaContent, BamlMediaType, BamlValue, MediaBase64, MediaUrl};
 use base64::{prelude::BASE64_STANDARD, Engine};
@@ -36,6 +36,11 @@ pub trait WithClientProperties {
     fn client_properties(&self) -> &HashMap<String, serde_json::Value>;
     fn allowed_metadata(&self) -> &super::AllowedMetadata;
     fn supports_streaming(&self) -> bool;
+    fn request_timeouts(&self) -> &super::RequestTimeouts;
+    /// The client's options as resolved into the requests it sends: where
+    /// they go, the model, and everything passed along to the provider.
+    /// Credentials are left out.
+    fn resolved_options(&self) -> serde_json::Value;
 }
 
 pub trait WithSingleCallable {
@@ -139,6 +144,16 @@ pub trait WithRenderRawCurl {
         prompt: &Vec<RenderedChatMessage>,
         render_settings: RenderCurlSettings,
     ) -> Result<String>;
+
+    /// Builds the request `single_call` (or `stream`) would send, without
+    /// sending it.
+    #[allow(async_fn_in_trait)]
+    async fn render_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HTTPRequest>;
 }
 
 impl<T> WithSingleCallable for T
@@ -283,7 +298,7 @@ where
         .await?;
 
         let request_builder = self
-            .build_request(either::Right(&chat_messages), false, render_settings.stream && self.supports_streaming())
+            .build_request(ctx, either::Right(&chat_messages), false, render_settings.stream && self.supports_streaming())
             .await?;
         let mut request = request_builder.build()?;
         let url_header_value = {
@@ -306,12 +321,71 @@ where
 
         Ok(request_str)
     }
+
+    async fn render_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        let stream = stream && self.supports_streaming();
+        let request_builder = match prompt {
+            RenderedPrompt::Chat(chat) => {
+                let chat_messages = process_media_urls(
+                    self.model_features().resolve_media_urls,
+                    true,
+                    None,
+                    ctx,
+                    chat,
+                )
+                .await?;
+                self.build_request(ctx, either::Right(&chat_messages), false, stream)
+                    .await?
+            }
+            RenderedPrompt::Completion(completion) => {
+                self.build_request(ctx, either::Left(completion), false, stream)
+                    .await?
+            }
+        };
+        let request = request_builder.build()?;
+
+        let headers = request
+            .headers()
+            .iter()
+            .filter(|(name, _)| name.as_str() != "baml-original-url")
+            .map(|(name, value)| {
+                Ok((
+                    name.as_str().to_string(),
+                    value
+                        .to_str()
+                        .context(format!("Header {} is not valid UTF-8", name))?
+                        .to_string(),
+                ))
+            })
+            .collect::<Result<_>>()?;
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .unwrap_or_default();
+        let body = match serde_json::from_slice(body) {
+            Ok(json) => json,
+            Err(_) => serde_json::Value::String(String::from_utf8_lossy(body).to_string()),
+        };
+
+        Ok(HTTPRequest {
+            url: request.url().to_string(),
+            method: request.method().to_string(),
+            headers,
+            body,
+        })
+    }
 }
 
 // Stream related
 pub trait SseResponseTrait {
     fn response_stream(
         &self,
+        ctx: &RuntimeContext,
         resp: reqwest::Response,
         prompt: &Vec<internal_baml_jinja::RenderedChatMessage>,
         system_start: web_time::SystemTime,
@@ -389,7 +463,7 @@ where
 
 /// We assume b64 with mime-type is the universally accepted format in an API request.
 /// Other formats will be converted into that, depending on what formats are allowed according to supported_media_formats.
-async fn process_media_urls(
+pub(crate) async fn process_media_urls(
     resolve_media_urls: ResolveMediaUrls,
     resolve_files: bool,
     render_settings: Option<RenderCurlSettings>,
@@ -476,7 +550,7 @@ async fn process_media(
                                 .strip_prefix("file://")
                                 .unwrap_or(media_path.as_str())
                         ),
-                        Some(format!("{}/{}", part.media_type, ext)),
+                        Some(part.media_type.mime_type_from_extension(&ext)),
                     ));
                 }
             }
@@ -493,7 +567,7 @@ async fn process_media(
 
             if mime_type == None {
                 if let Some(ext) = media_file.extension() {
-                    mime_type = Some(format!("{}/{}", part.media_type, ext));
+                    mime_type = Some(part.media_type.mime_type_from_extension(&ext));
                 }
             }
 
@@ -16,11 +16,12 @@ use internal_baml_jinja::{
     TemplateStringMacro,
 };
 
-use crate::RuntimeContext;
+use crate::{internal::llm_client::output_schema::OutputSchema, RuntimeContext};
 
 pub struct PromptRenderer {
     function_name: String,
     client_spec: ClientSpec,
+    timeout: Option<web_time::Duration>,
     output_defs: OutputFormatContent,
     output_type: FieldType,
 }
@@ -42,6 +43,7 @@ impl PromptRenderer {
                 Some((Some(client), _)) => ClientSpec::Named(client.clone()),
                 _ => config.client.clone(),
             },
+            timeout: config.timeout_ms.map(web_time::Duration::from_millis),
             output_defs: render_output_format(ir, ctx, &func_v2.output)?,
             output_type: func_v2.output.clone(),
         })
@@ -51,6 +53,15 @@ impl PromptRenderer {
         &self.client_spec
     }
 
+    /// The function's `timeout_ms`, covering every retry and fallback.
+    pub fn timeout(&self) -> Option<web_time::Duration> {
+        self.timeout
+    }
+
+    pub fn output_schema(&self) -> OutputSchema {
+        OutputSchema::new(&self.function_name, &self.output_defs)
+    }
+
     pub fn parse(&self, raw_string: &str, allow_partials: bool) -> Result<BamlValueWithFlags> {
         jsonish::from_str(
             &self.output_defs,
@@ -60,6 +71,12 @@ impl PromptRenderer {
         )
     }
 
+    /// Parses a streamed response chunk by chunk, giving the same results as
+    /// `parse(content, true)` on the response so far.
+    pub fn partial_parser(&self) -> jsonish::IncrementalParser<'_> {
+        jsonish::IncrementalParser::new(&self.output_defs, &self.output_type)
+    }
+
     pub fn render_prompt(
         &self,
         ir: &IntermediateRepr,
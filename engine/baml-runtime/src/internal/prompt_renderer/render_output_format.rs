@@ -2,11 +2,11 @@ use std::collections::HashSet;
 
 use anyhow::Result;
 use baml_types::BamlValue;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use internal_baml_core::ir::{
     repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType, IRHelper,
 };
-use internal_baml_jinja::types::{Class, Enum, Name, OutputFormatContent};
+use internal_baml_jinja::types::{Class, ClassStreaming, Enum, Name, OutputFormatContent};
 
 use crate::{
     runtime_context::{RuntimeClassOverride, RuntimeEnumOverride},
@@ -18,12 +18,22 @@ pub fn render_output_format(
     ctx: &RuntimeContext,
     output: &FieldType,
 ) -> Result<OutputFormatContent> {
-    let (enums, classes, recursive_classes) = relevant_data_models(ir, output, ctx)?;
+    let (
+        enums,
+        classes,
+        recursive_classes,
+        structural_recursive_aliases,
+        class_streaming,
+        field_defaults,
+    ) = relevant_data_models(ir, output, ctx)?;
 
     return Ok(OutputFormatContent::target(output.clone())
         .enums(enums)
         .classes(classes)
         .recursive_classes(recursive_classes)
+        .structural_recursive_aliases(structural_recursive_aliases)
+        .class_streaming(class_streaming)
+        .field_defaults(field_defaults)
         .build());
 }
 
@@ -140,6 +150,27 @@ fn find_existing_class_field<'a>(
     Ok((name, r#type.clone(), desc))
 }
 
+fn find_class_streaming(class_walker: ClassWalker<'_>) -> ClassStreaming {
+    ClassStreaming {
+        done: class_walker.streaming_behavior().done,
+        fields: class_walker
+            .walk_fields()
+            .map(|field| (field.name().to_string(), field.streaming_behavior()))
+            .filter(|(_, streaming)| !streaming.is_default())
+            .collect(),
+    }
+}
+
+fn find_field_defaults(class_walker: ClassWalker<'_>) -> Result<IndexMap<String, BamlValue>> {
+    class_walker
+        .walk_fields()
+        .filter_map(|field| {
+            let default_value = field.default_value()?;
+            Some(default_value.map(|v| (field.name().to_string(), v)))
+        })
+        .collect()
+}
+
 fn find_enum_value(
     enum_name: &str,
     value_name: &str,
@@ -207,14 +238,36 @@ fn relevant_data_models<'a>(
     ir: &'a IntermediateRepr,
     output: &'a FieldType,
     ctx: &RuntimeContext,
-) -> Result<(Vec<Enum>, Vec<Class>, IndexSet<String>)> {
+) -> Result<(
+    Vec<Enum>,
+    Vec<Class>,
+    IndexSet<String>,
+    IndexMap<String, FieldType>,
+    IndexMap<String, ClassStreaming>,
+    IndexMap<String, IndexMap<String, BamlValue>>,
+)> {
     let mut checked_types = HashSet::new();
     let mut enums = Vec::new();
     let mut classes = Vec::new();
     let mut recursive_classes = IndexSet::new();
+    let mut structural_recursive_aliases = IndexMap::new();
+    let mut class_streaming = IndexMap::new();
+    let mut field_defaults = IndexMap::new();
     let mut start: Vec<baml_types::FieldType> = vec![output.clone()];
 
     while let Some(output) = start.pop() {
+        // Recursive aliases are resolved by distribute_constraints, so they
+        // have to be collected before that happens, otherwise we'd loop
+        // forever following the alias target.
+        if let FieldType::RecursiveTypeAlias(name) = &output {
+            if checked_types.insert(output.to_string()) {
+                let target = ir.find_recursive_alias_target(name)?;
+                structural_recursive_aliases.insert(name.to_owned(), target.clone());
+                start.push(target.clone());
+            }
+            continue;
+        }
+
         match ir.distribute_constraints(&output) {
             (FieldType::Enum(enm), constraints) => {
                 if checked_types.insert(output.to_string()) {
@@ -330,6 +383,18 @@ fn relevant_data_models<'a>(
                         }
                     }
 
+                    if let Ok(walker) = walker {
+                        let streaming = find_class_streaming(walker);
+                        if !streaming.is_default() {
+                            class_streaming.insert(cls.to_string(), streaming);
+                        }
+
+                        let defaults = find_field_defaults(walker)?;
+                        if !defaults.is_empty() {
+                            field_defaults.insert(cls.to_string(), defaults);
+                        }
+                    }
+
                     let fields = fields.chain(new_fields).collect::<Result<Vec<_>>>()?;
 
                     for (_, t, _) in fields.iter().as_ref() {
@@ -365,13 +430,22 @@ fn relevant_data_models<'a>(
             }
             (FieldType::Literal(_), _) => {}
             (FieldType::Primitive(_), _) => {}
+            // Only reachable if the alias does not exist in the IR.
+            (FieldType::RecursiveTypeAlias(_), _) => {}
             (FieldType::Constrained { .. }, _) => {
                 unreachable!("It is guaranteed that a call to distribute_constraints will not return FieldType::Constrained")
             }
         }
     }
 
-    Ok((enums, classes, recursive_classes))
+    Ok((
+        enums,
+        classes,
+        recursive_classes,
+        structural_recursive_aliases,
+        class_streaming,
+        field_defaults,
+    ))
 }
 
 #[cfg(test)]
@@ -76,6 +76,7 @@ pub struct BamlRuntime {
     pub(crate) inner: InternalBamlRuntime,
     tracer: Arc<BamlTracer>,
     env_vars: HashMap<String, String>,
+    response_cache: std::sync::RwLock<Option<ResponseCache>>,
     #[cfg(not(target_arch = "wasm32"))]
     pub async_runtime: Arc<tokio::runtime::Runtime>,
 }
@@ -142,6 +143,7 @@ impl BamlRuntime {
             inner: InternalBamlRuntime::from_directory(&path)?,
             tracer: BamlTracer::new(None, env_vars.into_iter())?.into(),
             env_vars: copy,
+            response_cache: Default::default(),
             #[cfg(not(target_arch = "wasm32"))]
             async_runtime: Self::get_tokio_singleton()?,
         })
@@ -160,11 +162,25 @@ impl BamlRuntime {
             inner: InternalBamlRuntime::from_file_content(root_path, files)?,
             tracer: BamlTracer::new(None, env_vars.into_iter())?.into(),
             env_vars: copy,
+            response_cache: Default::default(),
             #[cfg(not(target_arch = "wasm32"))]
             async_runtime: Self::get_tokio_singleton()?,
         })
     }
 
+    /// Caches the LLM responses of every function call and test run, unless a
+    /// call bypasses it. `None` turns caching off again.
+    pub fn set_response_cache(&self, cache: Option<ResponseCache>) {
+        *self.response_cache.write().unwrap() = cache;
+    }
+
+    fn response_cache(&self, bypass_cache: bool) -> Option<ResponseCache> {
+        if bypass_cache {
+            return None;
+        }
+        self.response_cache.read().unwrap().clone()
+    }
+
     #[cfg(feature = "internal")]
     pub fn internal(&self) -> &impl InternalRuntimeInterface {
         &self.inner
@@ -209,10 +225,18 @@ impl BamlRuntime {
 
         let response = match ctx.create_ctx(None, None) {
             Ok(rctx) => {
-                let params = self.get_test_params(function_name, test_name, &rctx);
+                let params = self
+                    .get_test_params(function_name, test_name, &rctx)
+                    .and_then(|params| {
+                        let expectations = self
+                            .inner
+                            .get_test_expectations(function_name, test_name, &rctx)?;
+                        Ok((params, expectations))
+                    });
                 match params {
-                    Ok(params) => match ctx.create_ctx(None, None) {
-                        Ok(rctx_stream) => {
+                    Ok((params, expectations)) => match ctx.create_ctx(None, None) {
+                        Ok(mut rctx_stream) => {
+                            rctx_stream.response_cache = self.response_cache(false);
                             let stream = self.inner.stream_function_impl(
                                 function_name.into(),
                                 &params,
@@ -228,6 +252,7 @@ impl BamlRuntime {
                                     response.map(|res| TestResponse {
                                         function_response: res,
                                         function_span: span,
+                                        expectations,
                                     })
                                 }
                                 Err(e) => Err(e),
@@ -266,8 +291,18 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation: Option<&CancellationToken>,
+        bypass_cache: bool,
     ) -> (Result<FunctionResult>, Option<uuid::Uuid>) {
-        let fut = self.call_function(function_name, params, ctx, tb, cb);
+        let fut = self.call_function(
+            function_name,
+            params,
+            ctx,
+            tb,
+            cb,
+            cancellation,
+            bypass_cache,
+        );
         self.async_runtime.block_on(fut)
     }
 
@@ -278,11 +313,17 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation: Option<&CancellationToken>,
+        bypass_cache: bool,
     ) -> (Result<FunctionResult>, Option<uuid::Uuid>) {
         log::trace!("Calling function: {}", function_name);
         let span = self.tracer.start_span(&function_name, ctx, &params);
         let response = match ctx.create_ctx(tb, cb) {
-            Ok(rctx) => {
+            Ok(mut rctx) => {
+                if let Some(cancellation) = cancellation {
+                    rctx.cancellation = cancellation.clone();
+                }
+                rctx.response_cache = self.response_cache(bypass_cache);
                 self.inner
                     .call_function_impl(function_name, params, rctx)
                     .await
@@ -313,17 +354,68 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        bypass_cache: bool,
     ) -> Result<FunctionResultStream> {
+        let mut rctx = ctx.create_ctx(tb, cb)?;
+        rctx.response_cache = self.response_cache(bypass_cache);
         self.inner.stream_function_impl(
             function_name,
             params,
             self.tracer.clone(),
-            ctx.create_ctx(tb, cb)?,
+            rctx,
             #[cfg(not(target_arch = "wasm32"))]
             self.async_runtime.clone(),
         )
     }
 
+    /// Renders the HTTP request a call to `function_name` would send to its
+    /// client, without sending it.
+    pub async fn render_request(
+        &self,
+        function_name: &str,
+        params: &BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        let rctx = ctx.create_ctx(tb, cb)?;
+        self.inner
+            .render_request(function_name, &rctx, params, stream)
+            .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn render_request_sync(
+        &self,
+        function_name: &str,
+        params: &BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        let fut = self.render_request(function_name, params, ctx, tb, cb, stream);
+        self.async_runtime.block_on(fut)
+    }
+
+    /// Parses a raw LLM response the way a call to `function_name` would,
+    /// including its checks and asserts. With `allow_partials`, the response
+    /// may be incomplete, like a partial stream.
+    pub fn parse_llm_response(
+        &self,
+        function_name: &str,
+        llm_response: String,
+        allow_partials: bool,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+    ) -> Result<FunctionResult> {
+        let rctx = ctx.create_ctx(tb, cb)?;
+        self.inner
+            .parse_llm_response(function_name, &rctx, llm_response, allow_partials)
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn generate_client(
         &self,
@@ -2,7 +2,10 @@ use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use super::InternalBamlRuntime;
 use crate::internal::llm_client::traits::WithClientProperties;
-use crate::internal::llm_client::{AllowedMetadata, LLMResponse};
+use crate::internal::llm_client::{
+    parsed_value_to_response, AllowedMetadata, LLMCompleteResponse, LLMCompleteResponseMetadata,
+    LLMResponse,
+};
 use crate::{
     client_registry::ClientProperty,
     internal::{
@@ -20,8 +23,9 @@ use crate::{
     },
     runtime_interface::{InternalClientLookup, RuntimeConstructor},
     tracing::BamlTracer,
-    FunctionResult, FunctionResultStream, InternalRuntimeInterface, RenderCurlSettings,
-    RuntimeContext, RuntimeInterface,
+    types::TestExpectations,
+    FunctionResult, FunctionResultStream, HTTPRequest, InternalRuntimeInterface,
+    RenderCurlSettings, RuntimeContext, RuntimeInterface,
 };
 use anyhow::{Context, Result};
 use baml_types::{BamlMap, BamlValue};
@@ -197,6 +201,10 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
         let func = self.get_function(function_name, ctx)?;
 
         let renderer = PromptRenderer::from_function(&func, &self.ir(), ctx)?;
+        let ctx = &RuntimeContext {
+            output_schema: Some(Arc::new(renderer.output_schema())),
+            ..ctx.clone()
+        };
 
         let client_spec = renderer.client_spec();
         let client = self.get_llm_provider(client_spec, ctx)?;
@@ -221,6 +229,98 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
             .await;
     }
 
+    async fn render_request(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        params: &BamlMap<String, BamlValue>,
+        stream: bool,
+    ) -> Result<HTTPRequest> {
+        let func = self.get_function(function_name, ctx)?;
+        let baml_args = self.ir().check_function_params(
+            &func,
+            params,
+            ArgCoercer {
+                span_path: None,
+                allow_implicit_cast_to_string: false,
+            },
+        )?;
+
+        let renderer = PromptRenderer::from_function(&func, &self.ir(), ctx)?;
+        let ctx = &RuntimeContext {
+            output_schema: Some(Arc::new(renderer.output_schema())),
+            ..ctx.clone()
+        };
+
+        // Only the first node is rendered: retries and fallbacks only come
+        // into play once a request fails.
+        let Some(node) = self
+            .orchestration_graph(renderer.client_spec(), ctx)?
+            .into_iter()
+            .next()
+        else {
+            anyhow::bail!(
+                "No clients to render a request for: {}",
+                renderer.client_spec()
+            );
+        };
+        let prompt = node
+            .render_prompt(self.ir(), &renderer, ctx, &baml_args)
+            .await?;
+        node.render_request(ctx, &prompt, stream).await
+    }
+
+    fn parse_llm_response(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        llm_response: String,
+        allow_partials: bool,
+    ) -> Result<FunctionResult> {
+        let func = self.get_function(function_name, ctx)?;
+        let renderer = PromptRenderer::from_function(&func, self.ir(), ctx)?;
+
+        let parsed = renderer.parse(&llm_response, allow_partials);
+        let (parsed, baml_value) = match parsed {
+            Ok(v) => {
+                let baml_value = parsed_value_to_response(&v);
+                (Some(Ok(v)), Some(baml_value))
+            }
+            Err(e) => (None, Some(Err(e))),
+        };
+
+        let (client, model) = match renderer.client_spec() {
+            ClientSpec::Shorthand(provider, model) => {
+                (format!("{}/{}", provider, model), model.clone())
+            }
+            ClientSpec::Named(name) => (name.clone(), String::new()),
+        };
+        let response = LLMResponse::Success(LLMCompleteResponse {
+            client,
+            model,
+            prompt: RenderedPrompt::Chat(vec![]),
+            request_options: Default::default(),
+            content: llm_response,
+            start_time: web_time::SystemTime::now(),
+            latency: web_time::Duration::ZERO,
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: !allow_partials,
+                finish_reason: None,
+                prompt_tokens: None,
+                output_tokens: None,
+                total_tokens: None,
+                cached: false,
+            },
+        });
+
+        Ok(FunctionResult::new(
+            OrchestrationScope::default(),
+            response,
+            parsed,
+            baml_value,
+        ))
+    }
+
     fn get_function<'ir>(
         &'ir self,
         function_name: &str,
@@ -281,6 +381,31 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
             Err(e) => return Err(anyhow::anyhow!("Unable to resolve test params: {:?}", e)),
         }
     }
+
+    fn get_test_expectations(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        ctx: &RuntimeContext,
+    ) -> Result<TestExpectations> {
+        let func = self.get_function(function_name, ctx)?;
+        let test = self.ir().find_test(&func, test_name)?;
+        let case = test.test_case();
+
+        Ok(TestExpectations {
+            constraints: test.constraints().clone(),
+            expected_output: test
+                .expected_output(&ctx.env)
+                .transpose()
+                .context("Unable to resolve expected output")?,
+            partial: case
+                .expected_output
+                .as_ref()
+                .is_some_and(|expected| expected.is_partial()),
+            max_latency_ms: case.max_latency_ms,
+            max_total_tokens: case.max_total_tokens,
+        })
+    }
 }
 
 impl RuntimeConstructor for InternalBamlRuntime {
@@ -371,6 +496,10 @@ impl RuntimeInterface for InternalBamlRuntime {
 
         let renderer = PromptRenderer::from_function(&func, self.ir(), &ctx)?;
         let orchestrator = self.orchestration_graph(renderer.client_spec(), &ctx)?;
+        let ctx = RuntimeContext {
+            output_schema: Some(Arc::new(renderer.output_schema())),
+            ..ctx
+        };
 
         // Now actually execute the code.
         let (history, _) =
@@ -414,6 +543,8 @@ impl RuntimeInterface for InternalBamlRuntime {
             orchestrator,
             tracer,
             renderer,
+            cancellation: ctx.cancellation.clone(),
+            response_cache: ctx.response_cache.clone(),
             #[cfg(not(target_arch = "wasm32"))]
             tokio_runtime,
         })
@@ -14,10 +14,10 @@ use crate::types::on_log_event::LogEventCallbackSync;
 use crate::{
     internal::{ir_features::IrFeatures, llm_client::retry_policy::CallablePolicy},
     runtime::InternalBamlRuntime,
-    types::FunctionResultStream,
+    types::{FunctionResultStream, TestExpectations},
     FunctionResult, RuntimeContext,
 };
-use crate::{RenderCurlSettings, RuntimeContextManager};
+use crate::{HTTPRequest, RenderCurlSettings, RuntimeContextManager};
 
 pub(crate) trait RuntimeConstructor {
     #[cfg(not(target_arch = "wasm32"))]
@@ -151,6 +151,23 @@ pub trait InternalRuntimeInterface {
         node_index: Option<usize>,
     ) -> Result<String>;
 
+    #[allow(async_fn_in_trait)]
+    async fn render_request(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        params: &BamlMap<String, BamlValue>,
+        stream: bool,
+    ) -> Result<HTTPRequest>;
+
+    fn parse_llm_response(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        llm_response: String,
+        allow_partials: bool,
+    ) -> Result<FunctionResult>;
+
     fn ir(&self) -> &IntermediateRepr;
 
     fn get_test_params(
@@ -159,4 +176,11 @@ pub trait InternalRuntimeInterface {
         test_name: &str,
         ctx: &RuntimeContext,
     ) -> Result<BamlMap<String, BamlValue>>;
+
+    fn get_test_expectations(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        ctx: &RuntimeContext,
+    ) -> Result<TestExpectations>;
 }
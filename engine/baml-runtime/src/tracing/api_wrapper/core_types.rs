@@ -4,6 +4,8 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::internal::llm_client::orchestrator::OrchestrationScope;
+
 #[derive(Serialize, Debug)]
 #[allow(dead_code)]
 pub(crate) struct UpdateTestCase {
@@ -130,6 +132,8 @@ pub struct LLMOutputModelMetadata {
     pub output_tokens: Option<i64>,
     pub total_tokens: Option<i64>,
     pub finish_reason: Option<String>,
+    #[serde(default)]
+    pub cached: bool,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -161,6 +165,18 @@ pub enum ContentPart {
     UrlAudio(String),
     #[serde(rename = "b64_audio")]
     B64Audio(String),
+    #[serde(rename = "file_pdf")]
+    FilePdf(String, String),
+    #[serde(rename = "url_pdf")]
+    UrlPdf(String),
+    #[serde(rename = "b64_pdf")]
+    B64Pdf(String),
+    #[serde(rename = "file_video")]
+    FileVideo(String, String),
+    #[serde(rename = "url_video")]
+    UrlVideo(String),
+    #[serde(rename = "b64_video")]
+    B64Video(String),
     #[serde(rename = "with_meta")]
     WithMeta(Box<ContentPart>, HashMap<String, Value>),
 }
@@ -191,6 +207,17 @@ pub struct LLMEventSchema {
     pub input: LLMEventInput,
     pub output: Option<LLMOutputModel>,
     pub error: Option<String>,
+    // Not sent to Boundary; only used to build attempt spans for OTLP.
+    #[serde(skip)]
+    pub(crate) attempt: LLMAttempt,
+}
+
+/// Timing and orchestration details of a single LLM call within a function.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LLMAttempt {
+    pub start_time: Option<web_time::SystemTime>,
+    pub latency_ms: Option<u128>,
+    pub scope: Option<OrchestrationScope>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -0,0 +1,273 @@
+//! Writes BAML trace events to rotating JSONL files on local disk.
+//!
+//! Each line is one complete `LogSchema`, plus the timing and orchestration
+//! scope of every LLM attempt, so that `baml-cli replay` can reproduce the
+//! call later. The sink is enabled by setting `BAML_TRACE_DIR`.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::api_wrapper::core_types::{LLMEventSchema, LogSchema, MetadataType};
+
+pub(crate) const TRACE_FILE_STEM: &str = "baml_traces";
+
+#[derive(Deserialize, Debug)]
+struct EnvConfig {
+    dir: Option<PathBuf>,
+    #[serde(default = "default_max_file_bytes")]
+    max_file_bytes: u64,
+    #[serde(default = "default_max_files")]
+    max_files: usize,
+}
+
+fn default_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+#[derive(Serialize)]
+struct TraceRecord<'a> {
+    #[serde(flatten)]
+    event: &'a LogSchema,
+    /// One entry per LLM attempt, in the same order as `metadata`.
+    attempts: Vec<AttemptRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct AttemptRecord<'a> {
+    client: &'a str,
+    model: &'a str,
+    start_time: Option<String>,
+    latency_ms: Option<u128>,
+    /// e.g. `["Retry(MyPolicy, 1, 200ms)", "Fallback(MyFallback, 0)", "GPT4"]`
+    scope: Vec<String>,
+}
+
+impl<'a> From<&'a LLMEventSchema> for AttemptRecord<'a> {
+    fn from(event: &'a LLMEventSchema) -> Self {
+        AttemptRecord {
+            client: &event.provider,
+            model: &event.model_name,
+            start_time: event.attempt.start_time.as_ref().map(super::to_iso_string),
+            latency_ms: event.attempt.latency_ms,
+            scope: event
+                .attempt
+                .scope
+                .as_ref()
+                .map(|s| s.scope.iter().map(|s| s.to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+struct OpenFile {
+    file: File,
+    size: u64,
+}
+
+pub(crate) struct TraceFileSink {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    current: Mutex<Option<OpenFile>>,
+}
+
+impl TraceFileSink {
+    /// Returns `None` unless `BAML_TRACE_DIR` is set.
+    pub fn from_env_vars<T: AsRef<str>>(
+        env_vars: impl Iterator<Item = (T, T)>,
+    ) -> Result<Option<Self>> {
+        let config: EnvConfig = envy::prefixed("BAML_TRACE_")
+            .from_iter(env_vars.map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string())))
+            .map_err(|err| {
+                anyhow::anyhow!(
+                    "Failed to parse trace file config from environment variables: {}",
+                    err
+                )
+            })?;
+
+        Ok(config
+            .dir
+            .map(|dir| Self::new(dir, config.max_file_bytes, config.max_files)))
+    }
+
+    pub fn new(dir: PathBuf, max_file_bytes: u64, max_files: usize) -> Self {
+        Self {
+            dir,
+            max_file_bytes,
+            max_files: max_files.max(1),
+            current: Mutex::new(None),
+        }
+    }
+
+    fn path(&self, index: usize) -> PathBuf {
+        trace_file_path(&self.dir, index)
+    }
+
+    pub fn write(&self, events: &[LogSchema]) -> Result<()> {
+        let mut current = self.current.lock().unwrap();
+
+        for event in events {
+            let attempts = match &event.metadata {
+                Some(MetadataType::Single(attempt)) => vec![attempt.into()],
+                Some(MetadataType::Multi(attempts)) => attempts.iter().map(Into::into).collect(),
+                None => vec![],
+            };
+            let mut line = serde_json::to_vec(&TraceRecord { event, attempts })?;
+            line.push(b'\n');
+
+            if current
+                .as_ref()
+                .is_some_and(|f| f.size > 0 && f.size + line.len() as u64 > self.max_file_bytes)
+            {
+                *current = None;
+                self.rotate()?;
+            }
+
+            if current.is_none() {
+                *current = Some(self.open()?);
+            }
+            let open = current.as_mut().unwrap();
+            open.file.write_all(&line)?;
+            open.size += line.len() as u64;
+        }
+
+        if let Some(open) = current.as_mut() {
+            open.file.flush()?;
+        }
+        Ok(())
+    }
+
+    fn open(&self) -> Result<OpenFile> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create trace directory {}", self.dir.display()))?;
+        let path = self.path(0);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open trace file {}", path.display()))?;
+        let size = file.metadata()?.len();
+        Ok(OpenFile { file, size })
+    }
+
+    /// Shifts `baml_traces.jsonl` to `baml_traces.1.jsonl`, `.1` to `.2` and so
+    /// on, dropping the oldest file once there are `max_files` of them.
+    fn rotate(&self) -> Result<()> {
+        let oldest = self.path(self.max_files - 1);
+        if oldest.exists() {
+            std::fs::remove_file(&oldest)?;
+        }
+        for index in (0..self.max_files - 1).rev() {
+            let from = self.path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `index` 0 is the file currently being written to.
+pub(crate) fn trace_file_path(dir: &Path, index: usize) -> PathBuf {
+    match index {
+        0 => dir.join(format!("{TRACE_FILE_STEM}.jsonl")),
+        n => dir.join(format!("{TRACE_FILE_STEM}.{n}.jsonl")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracing::api_wrapper::core_types::{EventType, LogSchemaContext, IO};
+
+    fn event(id: usize) -> LogSchema {
+        LogSchema {
+            project_id: None,
+            event_type: EventType::FuncCode,
+            root_event_id: id.to_string(),
+            event_id: id.to_string(),
+            parent_event_id: None,
+            context: LogSchemaContext {
+                hostname: "localhost".to_string(),
+                process_id: "session".to_string(),
+                stage: None,
+                latency_ms: 10,
+                start_time: "2024-10-01T12:00:00.000Z".to_string(),
+                tags: Default::default(),
+                event_chain: vec![],
+            },
+            io: IO {
+                input: None,
+                output: None,
+            },
+            error: None,
+            metadata: None,
+        }
+    }
+
+    fn event_ids(path: &Path) -> Vec<String> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| {
+                let v: serde_json::Value = serde_json::from_str(l).unwrap();
+                v["event_id"].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = std::env::temp_dir().join(format!("baml-trace-sink-{}", uuid::Uuid::new_v4()));
+        let line_len = serde_json::to_vec(&TraceRecord {
+            event: &event(0),
+            attempts: vec![],
+        })
+        .unwrap()
+        .len() as u64
+            + 1;
+
+        // Two events per file, at most three files.
+        let sink = TraceFileSink::new(dir.clone(), line_len * 2, 3);
+        for id in 0..7 {
+            sink.write(&[event(id)]).unwrap();
+        }
+
+        assert_eq!(event_ids(&trace_file_path(&dir, 0)), vec!["6"]);
+        assert_eq!(event_ids(&trace_file_path(&dir, 1)), vec!["4", "5"]);
+        assert_eq!(event_ids(&trace_file_path(&dir, 2)), vec!["2", "3"]);
+        assert!(!trace_file_path(&dir, 3).exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_from_env_vars() {
+        let sink = TraceFileSink::from_env_vars(
+            [
+                ("BAML_TRACE_DIR", "/tmp/traces"),
+                ("BAML_TRACE_MAX_FILES", "2"),
+            ]
+            .into_iter(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(sink.dir, PathBuf::from("/tmp/traces"));
+        assert_eq!(sink.max_files, 2);
+        assert_eq!(sink.max_file_bytes, default_max_file_bytes());
+
+        let sink = TraceFileSink::from_env_vars([("BAML_LOG", "info")].into_iter()).unwrap();
+        assert!(sink.is_none());
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
 
 use self::api_wrapper::{
     core_types::{
-        ContentPart, EventChain, IOValue, LLMChat, LLMEventInput, LLMEventInputPrompt,
+        ContentPart, EventChain, IOValue, LLMAttempt, LLMChat, LLMEventInput, LLMEventInputPrompt,
         LLMEventSchema, LLMOutputModel, LogSchema, LogSchemaContext, MetadataType, Template,
         TypeSchema, IO,
     },
@@ -35,7 +35,11 @@ cfg_if! {
         mod wasm_tracer;
         use self::wasm_tracer::NonThreadedTracer as TracerImpl;
     } else {
+        pub(crate) mod file_sink;
+        mod otlp_exporter;
         mod threaded_tracer;
+        use self::file_sink::TraceFileSink;
+        use self::otlp_exporter::OtlpExporter;
         use self::threaded_tracer::ThreadedTracer as TracerImpl;
     }
 }
@@ -163,6 +167,8 @@ struct BamlEventJson {
     model: String,
     latency_ms: u128,
     stop_reason: Option<String>,
+    // Whether the reply came from the response cache
+    cached: bool,
 
     // Content
     prompt: Option<String>,
@@ -191,23 +197,37 @@ impl BamlTracer {
         options: Option<APIWrapper>,
         env_vars: impl Iterator<Item = (T, T)>,
     ) -> Result<Self> {
+        let env_vars = env_vars
+            .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+            .collect::<Vec<_>>();
         let options = match options {
             Some(wrapper) => wrapper,
-            None => APIWrapper::from_env_vars(env_vars)?,
+            None => APIWrapper::from_env_vars(env_vars.iter().map(|(k, v)| (k, v)))?,
         };
 
         let trace_stats = TraceStats::default();
 
-        let tracer = BamlTracer {
-            tracer: if options.enabled() {
-                Some(TracerImpl::new(&options, 20, trace_stats.clone()))
+        cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                let tracer = options
+                    .enabled()
+                    .then(|| TracerImpl::new(&options, 20, trace_stats.clone()));
             } else {
-                None
-            },
+                let otlp = OtlpExporter::from_env_vars(env_vars.iter().map(|(k, v)| (k, v)))?;
+                let file_sink =
+                    TraceFileSink::from_env_vars(env_vars.iter().map(|(k, v)| (k, v)))?;
+                let tracer = (options.enabled() || otlp.is_some() || file_sink.is_some())
+                    .then(|| {
+                        TracerImpl::new(&options, 20, trace_stats.clone(), otlp, file_sink)
+                    });
+            }
+        }
+
+        Ok(BamlTracer {
+            tracer,
             options,
             trace_stats,
-        };
-        Ok(tracer)
+        })
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -449,6 +469,7 @@ impl BamlTracer {
                 model: "unknown".to_string(),
                 latency_ms: 0,
                 stop_reason: None,
+                cached: false,
                 prompt: None,
                 llm_reply: None,
                 request_options_json: None,
@@ -487,6 +508,7 @@ impl BamlTracer {
                 model: resp.model.clone(),
                 latency_ms: resp.latency.as_millis(),
                 stop_reason: resp.metadata.finish_reason.clone(),
+                cached: resp.metadata.cached,
                 prompt: Some(resp.prompt.to_string()),
                 llm_reply: Some(resp.content.clone()),
                 request_options_json: Some(
@@ -517,6 +539,7 @@ impl BamlTracer {
                 model: err.model.clone().unwrap_or_default(),
                 latency_ms: err.latency.as_millis(),
                 stop_reason: None,
+                cached: false,
                 prompt: Some(err.prompt.to_string()),
                 llm_reply: None,
                 request_options_json: Some(
@@ -535,6 +558,7 @@ impl BamlTracer {
                 model: "unknown".to_string(),
                 latency_ms: 0,
                 stop_reason: None,
+                cached: false,
                 prompt: None,
                 llm_reply: None,
                 request_options_json: None,
@@ -847,7 +871,11 @@ impl From<&FunctionResult> for MetadataType {
             result
                 .event_chain()
                 .iter()
-                .map(|(_, r, _, _)| r.into())
+                .map(|(scope, r, _, _)| {
+                    let mut event: LLMEventSchema = r.into();
+                    event.attempt.scope = Some(scope.clone());
+                    event
+                })
                 .collect::<Vec<_>>(),
         )
     }
@@ -869,6 +897,7 @@ impl From<&LLMResponse> for LLMEventSchema {
                 },
                 output: None,
                 error: Some(s.clone()),
+                attempt: Default::default(),
             },
             LLMResponse::InternalFailure(s) => LLMEventSchema {
                 model_name: "<unknown>".into(),
@@ -883,6 +912,7 @@ impl From<&LLMResponse> for LLMEventSchema {
                 },
                 output: None,
                 error: Some(s.clone()),
+                attempt: Default::default(),
             },
             LLMResponse::Success(s) => LLMEventSchema {
                 model_name: s.model.clone(),
@@ -903,6 +933,11 @@ impl From<&LLMResponse> for LLMEventSchema {
                     r#override: None,
                 }),
                 error: None,
+                attempt: LLMAttempt {
+                    start_time: Some(s.start_time),
+                    latency_ms: Some(s.latency.as_millis()),
+                    scope: None,
+                },
             },
             LLMResponse::LLMFailure(s) => LLMEventSchema {
                 model_name: s
@@ -921,6 +956,11 @@ impl From<&LLMResponse> for LLMEventSchema {
                 },
                 output: None,
                 error: Some(s.message.clone()),
+                attempt: LLMAttempt {
+                    start_time: Some(s.start_time),
+                    latency_ms: Some(s.latency.as_millis()),
+                    scope: None,
+                },
             },
         }
     }
@@ -944,18 +984,42 @@ impl From<&internal_baml_jinja::ChatMessagePart> for ContentPart {
                             data.relpath.to_string_lossy().into_owned(),
                         )
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::File(data)) => {
+                        ContentPart::FilePdf(
+                            data.span_path.to_string_lossy().into_owned(),
+                            data.relpath.to_string_lossy().into_owned(),
+                        )
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::File(data)) => {
+                        ContentPart::FileVideo(
+                            data.span_path.to_string_lossy().into_owned(),
+                            data.relpath.to_string_lossy().into_owned(),
+                        )
+                    }
                     (BamlMediaType::Image, baml_types::BamlMediaContent::Base64(data)) => {
                         ContentPart::B64Image(data.base64.clone())
                     }
                     (BamlMediaType::Audio, baml_types::BamlMediaContent::Base64(data)) => {
                         ContentPart::B64Audio(data.base64.clone())
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::Base64(data)) => {
+                        ContentPart::B64Pdf(data.base64.clone())
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::Base64(data)) => {
+                        ContentPart::B64Video(data.base64.clone())
+                    }
                     (BamlMediaType::Image, baml_types::BamlMediaContent::Url(data)) => {
                         ContentPart::UrlImage(data.url.clone())
                     }
                     (BamlMediaType::Audio, baml_types::BamlMediaContent::Url(data)) => {
                         ContentPart::UrlAudio(data.url.clone())
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::Url(data)) => {
+                        ContentPart::UrlPdf(data.url.clone())
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::Url(data)) => {
+                        ContentPart::UrlVideo(data.url.clone())
+                    }
                 }
             }
             internal_baml_jinja::ChatMessagePart::WithMeta(inner, meta) => ContentPart::WithMeta(
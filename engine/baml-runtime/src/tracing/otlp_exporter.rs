@@ -0,0 +1,951 @@
+//! Exports BAML trace events to an OpenTelemetry collector over OTLP.
+//!
+//! Every `LogSchema` becomes one span for the traced function. LLM functions
+//! additionally get one child span per LLM attempt (including retries and
+//! fallbacks), annotated with the GenAI semantic-convention attributes.
+//!
+//! The exporter is configured through the standard `OTEL_*` environment
+//! variables and is only enabled when an OTLP endpoint is set.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    common::v1::{any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue},
+    resource::v1::Resource,
+    trace::v1::{span, status, ResourceSpans, ScopeSpans, Span, Status},
+};
+use prost::Message;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::internal::llm_client::orchestrator::ExecutionScope;
+
+use super::api_wrapper::core_types::{
+    LLMEventSchema, LogSchema, MetadataType, Template, ValueType,
+};
+
+const INSTRUMENTATION_SCOPE: &str = "baml";
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum OtlpProtocol {
+    #[serde(rename = "grpc")]
+    Grpc,
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+}
+
+/// The subset of the OpenTelemetry SDK environment variables that BAML honors.
+#[derive(Deserialize, Debug, Default)]
+struct EnvConfig {
+    #[serde(default)]
+    sdk_disabled: bool,
+    service_name: Option<String>,
+    resource_attributes: Option<String>,
+    exporter_otlp_endpoint: Option<String>,
+    exporter_otlp_traces_endpoint: Option<String>,
+    exporter_otlp_protocol: Option<OtlpProtocol>,
+    exporter_otlp_traces_protocol: Option<OtlpProtocol>,
+    exporter_otlp_headers: Option<String>,
+    exporter_otlp_traces_headers: Option<String>,
+    exporter_otlp_timeout: Option<u64>,
+    exporter_otlp_traces_timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OtlpConfig {
+    pub protocol: OtlpProtocol,
+    /// Full URL of the traces endpoint for HTTP, or the collector address
+    /// for gRPC.
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+    pub timeout: Duration,
+    pub service_name: String,
+    pub resource_attributes: Vec<(String, String)>,
+}
+
+impl OtlpConfig {
+    /// Returns `None` when no OTLP endpoint is configured or the SDK is disabled.
+    pub fn from_env_vars<T: AsRef<str>>(
+        env_vars: impl Iterator<Item = (T, T)>,
+    ) -> Result<Option<Self>> {
+        let config: EnvConfig = envy::prefixed("OTEL_")
+            .from_iter(env_vars.map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string())))
+            .map_err(|err| {
+                anyhow::anyhow!(
+                    "Failed to parse OpenTelemetry config from environment variables: {}",
+                    err
+                )
+            })?;
+
+        if config.sdk_disabled {
+            return Ok(None);
+        }
+
+        let protocol = config
+            .exporter_otlp_traces_protocol
+            .or(config.exporter_otlp_protocol)
+            .unwrap_or_default();
+
+        // Per the OTLP spec, the signal-specific endpoint is used as-is, while
+        // the generic one gets the signal path appended for HTTP.
+        let endpoint = match (
+            config.exporter_otlp_traces_endpoint,
+            config.exporter_otlp_endpoint,
+        ) {
+            (Some(endpoint), _) => endpoint,
+            (None, Some(endpoint)) => match protocol {
+                OtlpProtocol::Grpc => endpoint,
+                OtlpProtocol::HttpProtobuf => {
+                    format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+                }
+            },
+            (None, None) => return Ok(None),
+        };
+
+        let headers = config
+            .exporter_otlp_traces_headers
+            .or(config.exporter_otlp_headers)
+            .map(|h| parse_key_value_list(&h))
+            .unwrap_or_default();
+
+        let timeout = config
+            .exporter_otlp_traces_timeout
+            .or(config.exporter_otlp_timeout)
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::from_secs(10));
+
+        Ok(Some(Self {
+            protocol,
+            endpoint,
+            headers,
+            timeout,
+            service_name: config.service_name.unwrap_or_else(|| "baml".to_string()),
+            resource_attributes: config
+                .resource_attributes
+                .map(|a| parse_key_value_list(&a))
+                .unwrap_or_default(),
+        }))
+    }
+}
+
+/// Parses the `key1=value1,key2=value2` format used by `OTEL_EXPORTER_OTLP_HEADERS`
+/// and `OTEL_RESOURCE_ATTRIBUTES`.
+fn parse_key_value_list(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (k, v) = pair.split_once('=')?;
+            let (k, v) = (k.trim(), v.trim());
+            (!k.is_empty()).then(|| (k.to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+pub(crate) struct OtlpExporter {
+    config: OtlpConfig,
+    http_client: reqwest::Client,
+    // Created on first use, since the channel must be built inside the
+    // delivery thread's runtime.
+    grpc_client: tokio::sync::OnceCell<TraceServiceClient<tonic::transport::Channel>>,
+}
+
+impl OtlpExporter {
+    pub fn new(config: OtlpConfig) -> Result<Self> {
+        Ok(Self {
+            http_client: crate::request::create_tracing_client()?,
+            grpc_client: tokio::sync::OnceCell::new(),
+            config,
+        })
+    }
+
+    pub fn from_env_vars<T: AsRef<str>>(
+        env_vars: impl Iterator<Item = (T, T)>,
+    ) -> Result<Option<Self>> {
+        OtlpConfig::from_env_vars(env_vars)?
+            .map(Self::new)
+            .transpose()
+    }
+
+    pub async fn export(&self, events: &[LogSchema]) -> Result<()> {
+        let request = self.to_export_request(events);
+        if request.resource_spans[0].scope_spans[0].spans.is_empty() {
+            return Ok(());
+        }
+
+        match self.config.protocol {
+            OtlpProtocol::HttpProtobuf => self.export_http(request).await,
+            OtlpProtocol::Grpc => self.export_grpc(request).await,
+        }
+    }
+
+    async fn export_http(&self, request: ExportTraceServiceRequest) -> Result<()> {
+        let mut req = self
+            .http_client
+            .post(&self.config.endpoint)
+            .timeout(self.config.timeout)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
+            .body(request.encode_to_vec());
+        for (k, v) in &self.config.headers {
+            req = req.header(k, v);
+        }
+
+        let res = req
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch: {}", self.config.endpoint))?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Failed to export OTLP traces: {}. Status: {status}\nBody: {body}",
+                self.config.endpoint
+            );
+        }
+        Ok(())
+    }
+
+    async fn export_grpc(&self, request: ExportTraceServiceRequest) -> Result<()> {
+        let client = self
+            .grpc_client
+            .get_or_try_init(|| async {
+                let mut endpoint =
+                    tonic::transport::Endpoint::from_shared(self.config.endpoint.clone())?
+                        .timeout(self.config.timeout);
+                if self.config.endpoint.starts_with("https://") {
+                    endpoint = endpoint
+                        .tls_config(tonic::transport::ClientTlsConfig::new().with_native_roots())?;
+                }
+                anyhow::Ok(TraceServiceClient::new(endpoint.connect_lazy()))
+            })
+            .await
+            .with_context(|| format!("Invalid OTLP endpoint: {}", self.config.endpoint))?;
+
+        let mut req = tonic::Request::new(request);
+        req.set_timeout(self.config.timeout);
+        for (k, v) in &self.config.headers {
+            let key = tonic::metadata::AsciiMetadataKey::from_bytes(k.to_lowercase().as_bytes())
+                .with_context(|| format!("Invalid OTLP header name: {k}"))?;
+            let value = tonic::metadata::AsciiMetadataValue::try_from(v.as_str())
+                .with_context(|| format!("Invalid OTLP header value for {k}"))?;
+            req.metadata_mut().insert(key, value);
+        }
+
+        // The client is a cheap handle around the shared channel.
+        client
+            .clone()
+            .export(req)
+            .await
+            .with_context(|| format!("Failed to export OTLP traces: {}", self.config.endpoint))?;
+        Ok(())
+    }
+
+    fn to_export_request(&self, events: &[LogSchema]) -> ExportTraceServiceRequest {
+        let resource_attributes = [
+            kv("service.name", self.config.service_name.as_str()),
+            kv("telemetry.sdk.name", "baml"),
+            kv("telemetry.sdk.language", "rust"),
+            kv("telemetry.sdk.version", env!("CARGO_PKG_VERSION")),
+        ]
+        .into_iter()
+        .chain(
+            self.config
+                .resource_attributes
+                .iter()
+                .map(|(k, v)| kv(k, v.as_str())),
+        )
+        .collect();
+
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: resource_attributes,
+                    ..Default::default()
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: INSTRUMENTATION_SCOPE.to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        ..Default::default()
+                    }),
+                    spans: events.iter().flat_map(to_spans).collect(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+}
+
+/// Converts a single trace event into its function span followed by one span
+/// per LLM attempt.
+fn to_spans(event: &LogSchema) -> Vec<Span> {
+    let (Ok(root_id), Ok(event_id)) = (
+        Uuid::parse_str(&event.root_event_id),
+        Uuid::parse_str(&event.event_id),
+    ) else {
+        log::debug!(
+            "Skipping OTLP export of event with invalid id: {}",
+            event.event_id
+        );
+        return vec![];
+    };
+
+    let trace_id = root_id.as_bytes().to_vec();
+    let span_id = span_id_for(event_id, 0);
+    let start = parse_time_nanos(&event.context.start_time);
+    let end = start + millis_to_nanos(event.context.latency_ms.max(0) as u128);
+
+    let function_name = event
+        .context
+        .event_chain
+        .last()
+        .map(|c| c.function_name.as_str())
+        .unwrap_or("<unknown>");
+
+    let mut attributes = vec![
+        kv("baml.function.name", function_name),
+        kv("baml.session_id", event.context.process_id.as_str()),
+        kv("host.name", event.context.hostname.as_str()),
+    ];
+    if let Some(stage) = &event.context.stage {
+        attributes.push(kv("deployment.environment", stage.as_str()));
+    }
+    if let Some(input) = &event.io.input {
+        attributes.push(kv("baml.input", value_to_string(&input.value)));
+    }
+    if let Some(output) = &event.io.output {
+        attributes.push(kv("baml.output", value_to_string(&output.value)));
+    }
+    let mut tags = event.context.tags.iter().collect::<Vec<_>>();
+    tags.sort();
+    attributes.extend(
+        tags.into_iter()
+            .map(|(k, v)| kv(&format!("baml.tag.{k}"), v.as_str())),
+    );
+
+    let attempts = match &event.metadata {
+        Some(MetadataType::Single(attempt)) => std::slice::from_ref(attempt),
+        Some(MetadataType::Multi(attempts)) => attempts.as_slice(),
+        None => &[],
+    };
+    if !attempts.is_empty() {
+        attributes.push(kv("baml.attempts", attempts.len() as i64));
+    }
+
+    let function_span = Span {
+        trace_id: trace_id.clone(),
+        span_id: span_id.clone(),
+        parent_span_id: event
+            .parent_event_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .map(|id| span_id_for(id, 0))
+            .unwrap_or_default(),
+        name: function_name.to_string(),
+        kind: span::SpanKind::Internal as i32,
+        start_time_unix_nano: start,
+        end_time_unix_nano: end,
+        attributes,
+        status: Some(match &event.error {
+            Some(error) => error_status(&error.message),
+            None => Status {
+                code: status::StatusCode::Ok as i32,
+                ..Default::default()
+            },
+        }),
+        ..Default::default()
+    };
+
+    std::iter::once(function_span)
+        .chain(attempts.iter().enumerate().map(|(index, attempt)| {
+            attempt_span(
+                attempt,
+                index,
+                attempts.len(),
+                &trace_id,
+                &span_id,
+                span_id_for(event_id, index + 1),
+                (start, end),
+            )
+        }))
+        .collect()
+}
+
+fn attempt_span(
+    attempt: &LLMEventSchema,
+    index: usize,
+    total: usize,
+    trace_id: &[u8],
+    parent_span_id: &[u8],
+    span_id: Vec<u8>,
+    (function_start, function_end): (u64, u64),
+) -> Span {
+    let operation = match attempt.input.prompt.template {
+        Template::Single(_) => "text_completion",
+        Template::Multiple(_) => "chat",
+    };
+
+    let start = attempt
+        .attempt
+        .start_time
+        .and_then(|t| t.duration_since(web_time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(function_start);
+    let end = attempt
+        .attempt
+        .latency_ms
+        .map(|ms| start + millis_to_nanos(ms))
+        .unwrap_or(function_end);
+
+    let mut attributes = vec![
+        kv("gen_ai.operation.name", operation),
+        kv("gen_ai.request.model", attempt.model_name.as_str()),
+        kv("baml.client", attempt.provider.as_str()),
+        kv("baml.attempt.index", index as i64),
+        kv("baml.attempt.is_last", index + 1 == total),
+    ];
+
+    for (key, option) in [
+        ("gen_ai.request.temperature", "temperature"),
+        ("gen_ai.request.top_p", "top_p"),
+        ("gen_ai.request.max_tokens", "max_tokens"),
+    ] {
+        if let Some(value) = attempt.input.request_options.get(option) {
+            if let Some(value) = json_to_any_value(value) {
+                attributes.push(KeyValue {
+                    key: key.to_string(),
+                    value: Some(value),
+                });
+            }
+        }
+    }
+
+    if let Some(scope) = &attempt.attempt.scope {
+        attributes.push(kv("baml.orchestration.scope", scope.name()));
+        for s in &scope.scope {
+            match s {
+                ExecutionScope::Direct(_) => {}
+                ExecutionScope::Retry(policy, count, delay) => {
+                    attributes.push(kv("baml.retry.policy", policy.as_str()));
+                    attributes.push(kv("baml.retry.count", *count as i64));
+                    attributes.push(kv("baml.retry.delay_ms", delay.as_millis() as i64));
+                }
+                ExecutionScope::Fallback(strategy, client_index) => {
+                    attributes.push(kv("baml.fallback.strategy", strategy.as_str()));
+                    attributes.push(kv("baml.fallback.index", *client_index as i64));
+                }
+                ExecutionScope::RoundRobin(strategy, client_index) => {
+                    attributes.push(kv("baml.round_robin.strategy", strategy.name.as_str()));
+                    attributes.push(kv("baml.round_robin.index", *client_index as i64));
+                }
+            }
+        }
+    }
+
+    let mut events = vec![span::Event {
+        time_unix_nano: start,
+        name: "gen_ai.content.prompt".to_string(),
+        attributes: vec![kv(
+            "gen_ai.prompt",
+            serde_json::to_string(&attempt.input.prompt.template).unwrap_or_default(),
+        )],
+        ..Default::default()
+    }];
+
+    if let Some(output) = &attempt.output {
+        attributes.push(kv("gen_ai.response.model", attempt.model_name.as_str()));
+        attributes.push(kv("baml.cached", output.metadata.cached));
+        if let Some(tokens) = output.metadata.prompt_tokens {
+            attributes.push(kv("gen_ai.usage.input_tokens", tokens));
+        }
+        if let Some(tokens) = output.metadata.output_tokens {
+            attributes.push(kv("gen_ai.usage.output_tokens", tokens));
+        }
+        if let Some(reason) = &output.metadata.finish_reason {
+            attributes.push(KeyValue {
+                key: "gen_ai.response.finish_reasons".to_string(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::ArrayValue(ArrayValue {
+                        values: vec![reason.as_str().into_any_value()],
+                    })),
+                }),
+            });
+        }
+        events.push(span::Event {
+            time_unix_nano: end,
+            name: "gen_ai.content.completion".to_string(),
+            attributes: vec![kv("gen_ai.completion", output.raw_text.as_str())],
+            ..Default::default()
+        });
+    }
+
+    Span {
+        trace_id: trace_id.to_vec(),
+        span_id,
+        parent_span_id: parent_span_id.to_vec(),
+        name: format!("{} {}", operation, attempt.model_name),
+        kind: span::SpanKind::Client as i32,
+        start_time_unix_nano: start,
+        end_time_unix_nano: end,
+        attributes,
+        events,
+        status: Some(match &attempt.error {
+            Some(message) => error_status(message),
+            None => Status {
+                code: status::StatusCode::Ok as i32,
+                ..Default::default()
+            },
+        }),
+        ..Default::default()
+    }
+}
+
+/// Derives a stable 8-byte span id from a BAML event id, so that parents and
+/// children exported in different batches still link up. `index` 0 is the
+/// function span itself; `index` n > 0 is its n-th LLM attempt.
+fn span_id_for(event_id: Uuid, index: usize) -> Vec<u8> {
+    let id = event_id.as_u128();
+    let folded = (id as u64) ^ ((id >> 64) as u64);
+    let folded = folded ^ (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    // An all-zero span id is invalid in OTLP.
+    let folded = if folded == 0 { 1 } else { folded };
+    folded.to_be_bytes().to_vec()
+}
+
+fn parse_time_nanos(time: &str) -> u64 {
+    chrono::DateTime::parse_from_rfc3339(time)
+        .ok()
+        .and_then(|t| t.timestamp_nanos_opt())
+        .map(|n| n.max(0) as u64)
+        .unwrap_or_default()
+}
+
+fn millis_to_nanos(ms: u128) -> u64 {
+    (ms * 1_000_000) as u64
+}
+
+fn error_status(message: &str) -> Status {
+    Status {
+        code: status::StatusCode::Error as i32,
+        message: message.to_string(),
+    }
+}
+
+fn value_to_string(value: &ValueType) -> String {
+    match value {
+        ValueType::String(s) => s.clone(),
+        ValueType::List(items) => serde_json::to_string(items).unwrap_or_default(),
+    }
+}
+
+fn json_to_any_value(value: &serde_json::Value) -> Option<AnyValue> {
+    match value {
+        serde_json::Value::Bool(b) => Some((*b).into_any_value()),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(i.into_any_value()),
+            None => n.as_f64().map(|f| f.into_any_value()),
+        },
+        serde_json::Value::String(s) => Some(s.as_str().into_any_value()),
+        _ => None,
+    }
+}
+
+trait IntoAnyValue {
+    fn into_any_value(self) -> AnyValue;
+}
+
+impl IntoAnyValue for &str {
+    fn into_any_value(self) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::StringValue(self.to_string())),
+        }
+    }
+}
+
+impl IntoAnyValue for String {
+    fn into_any_value(self) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::StringValue(self)),
+        }
+    }
+}
+
+impl IntoAnyValue for i64 {
+    fn into_any_value(self) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::IntValue(self)),
+        }
+    }
+}
+
+impl IntoAnyValue for f64 {
+    fn into_any_value(self) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::DoubleValue(self)),
+        }
+    }
+}
+
+impl IntoAnyValue for bool {
+    fn into_any_value(self) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::BoolValue(self)),
+        }
+    }
+}
+
+fn kv(key: &str, value: impl IntoAnyValue) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(value.into_any_value()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        trace_service_server::{TraceService, TraceServiceServer},
+        ExportTraceServiceResponse,
+    };
+
+    use super::*;
+    use crate::internal::llm_client::orchestrator::OrchestrationScope;
+    use crate::tracing::api_wrapper::core_types::{
+        EventChain, EventType, LLMAttempt, LLMEventInput, LLMEventInputPrompt, LLMOutputModel,
+        LLMOutputModelMetadata, LogSchemaContext, IO,
+    };
+
+    /// An in-process stand-in for an OpenTelemetry collector. It accepts OTLP
+    /// over both HTTP/protobuf and gRPC and records every span it receives.
+    #[derive(Clone, Default)]
+    struct Collector {
+        requests: Arc<Mutex<Vec<ExportTraceServiceRequest>>>,
+    }
+
+    impl Collector {
+        fn spans(&self) -> Vec<Span> {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .flat_map(|r| r.resource_spans.iter())
+                .flat_map(|r| r.scope_spans.iter())
+                .flat_map(|s| s.spans.iter().cloned())
+                .collect()
+        }
+
+        async fn serve_http(&self) -> String {
+            let collector = self.clone();
+            let app = axum::Router::new().route(
+                "/v1/traces",
+                axum::routing::post(move |body: bytes::Bytes| {
+                    let collector = collector.clone();
+                    async move {
+                        let request = ExportTraceServiceRequest::decode(body).unwrap();
+                        collector.requests.lock().unwrap().push(request);
+                        axum::http::StatusCode::OK
+                    }
+                }),
+            );
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            format!("http://{addr}")
+        }
+
+        async fn serve_grpc(&self) -> String {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let incoming =
+                tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+            let service = TraceServiceServer::new(self.clone());
+            tokio::spawn(async move {
+                tonic::transport::Server::builder()
+                    .add_service(service)
+                    .serve_with_incoming(incoming)
+                    .await
+            });
+            format!("http://{addr}")
+        }
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for Collector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+            self.requests.lock().unwrap().push(request.into_inner());
+            Ok(tonic::Response::new(ExportTraceServiceResponse::default()))
+        }
+    }
+
+    fn llm_event(model: &str, error: Option<&str>, scope: Vec<ExecutionScope>) -> LLMEventSchema {
+        LLMEventSchema {
+            model_name: model.to_string(),
+            provider: "MyClient".to_string(),
+            input: LLMEventInput {
+                prompt: LLMEventInputPrompt {
+                    template: Template::Single("Extract the resume".to_string()),
+                    template_args: Default::default(),
+                    r#override: None,
+                },
+                request_options: [("temperature".to_string(), serde_json::json!(0.5))].into(),
+            },
+            output: error.is_none().then(|| LLMOutputModel {
+                raw_text: "{\"name\": \"Vaibhav\"}".to_string(),
+                metadata: LLMOutputModelMetadata {
+                    prompt_tokens: Some(12),
+                    output_tokens: Some(7),
+                    finish_reason: Some("stop".to_string()),
+                    ..Default::default()
+                },
+                r#override: None,
+            }),
+            error: error.map(str::to_string),
+            attempt: LLMAttempt {
+                start_time: None,
+                latency_ms: Some(20),
+                scope: Some(OrchestrationScope::from(scope)),
+            },
+        }
+    }
+
+    fn log_schema(root: Uuid, event: Uuid, parent: Option<Uuid>, name: &str) -> LogSchema {
+        LogSchema {
+            project_id: None,
+            event_type: EventType::FuncLlm,
+            root_event_id: root.to_string(),
+            event_id: event.to_string(),
+            parent_event_id: parent.map(|p| p.to_string()),
+            context: LogSchemaContext {
+                hostname: "localhost".to_string(),
+                process_id: "session".to_string(),
+                stage: Some("development".to_string()),
+                latency_ms: 150,
+                start_time: "2024-10-01T12:00:00.000Z".to_string(),
+                tags: [("env".to_string(), "test".to_string())].into(),
+                event_chain: vec![EventChain {
+                    function_name: name.to_string(),
+                    variant_name: None,
+                }],
+            },
+            io: IO {
+                input: None,
+                output: None,
+            },
+            error: None,
+            metadata: Some(MetadataType::Multi(vec![
+                llm_event(
+                    "gpt-4o",
+                    Some("rate limited"),
+                    vec![
+                        ExecutionScope::Retry("MyPolicy".to_string(), 0, Duration::ZERO),
+                        ExecutionScope::Fallback("MyFallback".to_string(), 0),
+                        ExecutionScope::Direct("GPT4".to_string()),
+                    ],
+                ),
+                llm_event(
+                    "claude-3-5-sonnet",
+                    None,
+                    vec![
+                        ExecutionScope::Retry("MyPolicy".to_string(), 0, Duration::ZERO),
+                        ExecutionScope::Fallback("MyFallback".to_string(), 1),
+                        ExecutionScope::Direct("Claude".to_string()),
+                    ],
+                ),
+            ])),
+        }
+    }
+
+    fn exporter(protocol: OtlpProtocol, endpoint: String) -> OtlpExporter {
+        OtlpExporter::new(OtlpConfig {
+            protocol,
+            endpoint,
+            headers: vec![("x-api-key".to_string(), "secret".to_string())],
+            timeout: Duration::from_secs(5),
+            service_name: "resume-service".to_string(),
+            resource_attributes: vec![],
+        })
+        .unwrap()
+    }
+
+    fn attr<'a>(span: &'a Span, key: &str) -> Option<&'a any_value::Value> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key == key)
+            .and_then(|kv| kv.value.as_ref())
+            .and_then(|v| v.value.as_ref())
+    }
+
+    fn str_attr<'a>(span: &'a Span, key: &str) -> Option<&'a str> {
+        match attr(span, key) {
+            Some(any_value::Value::StringValue(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn int_attr(span: &Span, key: &str) -> Option<i64> {
+        match attr(span, key) {
+            Some(any_value::Value::IntValue(i)) => Some(*i),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_config_from_env_vars() {
+        let config = OtlpConfig::from_env_vars(
+            [
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://localhost:4318/"),
+                (
+                    "OTEL_EXPORTER_OTLP_HEADERS",
+                    "x-api-key=secret, x-team = ml",
+                ),
+                ("OTEL_SERVICE_NAME", "resume-service"),
+            ]
+            .into_iter(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.protocol, OtlpProtocol::HttpProtobuf);
+        assert_eq!(config.endpoint, "http://localhost:4318/v1/traces");
+        assert_eq!(
+            config.headers,
+            vec![
+                ("x-api-key".to_string(), "secret".to_string()),
+                ("x-team".to_string(), "ml".to_string())
+            ]
+        );
+        assert_eq!(config.service_name, "resume-service");
+
+        let config = OtlpConfig::from_env_vars(
+            [
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://localhost:4317"),
+                ("OTEL_EXPORTER_OTLP_PROTOCOL", "grpc"),
+            ]
+            .into_iter(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.protocol, OtlpProtocol::Grpc);
+        assert_eq!(config.endpoint, "http://localhost:4317");
+
+        let config = OtlpConfig::from_env_vars(
+            [("BOUNDARY_SECRET", "secret"), ("OTEL_SERVICE_NAME", "x")].into_iter(),
+        )
+        .unwrap();
+        assert!(config.is_none());
+
+        let config = OtlpConfig::from_env_vars(
+            [
+                (
+                    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+                    "http://localhost:4318/traces",
+                ),
+                ("OTEL_SDK_DISABLED", "true"),
+            ]
+            .into_iter(),
+        )
+        .unwrap();
+        assert!(config.is_none());
+    }
+
+    #[test]
+    fn test_spans_for_fallback() {
+        let (root, event, parent) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let spans = to_spans(&log_schema(root, event, Some(parent), "ExtractResume"));
+        assert_eq!(spans.len(), 3);
+
+        let function = &spans[0];
+        assert_eq!(function.name, "ExtractResume");
+        assert_eq!(function.trace_id, root.as_bytes().to_vec());
+        assert_eq!(function.parent_span_id, span_id_for(parent, 0));
+        assert_eq!(
+            function.end_time_unix_nano - function.start_time_unix_nano,
+            150_000_000
+        );
+        assert_eq!(str_attr(function, "baml.tag.env"), Some("test"));
+        assert_eq!(int_attr(function, "baml.attempts"), Some(2));
+
+        let (failed, succeeded) = (&spans[1], &spans[2]);
+        for attempt in [failed, succeeded] {
+            assert_eq!(attempt.trace_id, function.trace_id);
+            assert_eq!(attempt.parent_span_id, function.span_id);
+            assert_eq!(
+                str_attr(attempt, "baml.fallback.strategy"),
+                Some("MyFallback")
+            );
+            assert_eq!(
+                str_attr(attempt, "gen_ai.operation.name"),
+                Some("text_completion")
+            );
+        }
+        assert_ne!(failed.span_id, succeeded.span_id);
+
+        assert_eq!(failed.name, "text_completion gpt-4o");
+        assert_eq!(int_attr(failed, "baml.fallback.index"), Some(0));
+        assert_eq!(
+            failed.status.as_ref().unwrap().code,
+            status::StatusCode::Error as i32
+        );
+        assert_eq!(failed.status.as_ref().unwrap().message, "rate limited");
+
+        assert_eq!(succeeded.name, "text_completion claude-3-5-sonnet");
+        assert_eq!(int_attr(succeeded, "baml.fallback.index"), Some(1));
+        assert_eq!(int_attr(succeeded, "gen_ai.usage.input_tokens"), Some(12));
+        assert_eq!(int_attr(succeeded, "gen_ai.usage.output_tokens"), Some(7));
+        assert_eq!(
+            str_attr(succeeded, "gen_ai.response.model"),
+            Some("claude-3-5-sonnet")
+        );
+        assert_eq!(
+            attr(succeeded, "gen_ai.request.temperature"),
+            Some(&any_value::Value::DoubleValue(0.5))
+        );
+        assert_eq!(succeeded.events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_export_http() {
+        let collector = Collector::default();
+        let endpoint = format!("{}/v1/traces", collector.serve_http().await);
+
+        let root = Uuid::new_v4();
+        exporter(OtlpProtocol::HttpProtobuf, endpoint)
+            .export(&[log_schema(root, root, None, "ExtractResume")])
+            .await
+            .unwrap();
+
+        let spans = collector.spans();
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0].name, "ExtractResume");
+        assert!(spans[0].parent_span_id.is_empty());
+
+        let requests = collector.requests.lock().unwrap();
+        let resource = requests[0].resource_spans[0].resource.as_ref().unwrap();
+        assert!(resource
+            .attributes
+            .contains(&kv("service.name", "resume-service")));
+    }
+
+    #[tokio::test]
+    async fn test_export_grpc() {
+        let collector = Collector::default();
+        let endpoint = collector.serve_grpc().await;
+
+        let root = Uuid::new_v4();
+        exporter(OtlpProtocol::Grpc, endpoint)
+            .export(&[log_schema(root, root, None, "ExtractResume")])
+            .await
+            .unwrap();
+
+        let spans = collector.spans();
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[2].name, "text_completion claude-3-5-sonnet");
+    }
+}
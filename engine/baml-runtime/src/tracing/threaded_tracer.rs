@@ -9,7 +9,11 @@ use crate::{
     TraceStats,
 };
 
-use super::api_wrapper::{core_types::LogSchema, APIConfig, APIWrapper, BoundaryAPI};
+use super::{
+    api_wrapper::{core_types::LogSchema, APIConfig, APIWrapper, BoundaryAPI},
+    file_sink::TraceFileSink,
+    otlp_exporter::OtlpExporter,
+};
 
 const MAX_TRACE_SEND_CONCURRENCY: usize = 10;
 
@@ -27,6 +31,8 @@ enum ProcessorStatus {
 
 struct DeliveryThread {
     api_config: Arc<APIWrapper>,
+    otlp: Option<OtlpExporter>,
+    file_sink: Option<Arc<TraceFileSink>>,
     span_rx: mpsc::Receiver<TxEventSignal>,
     stop_tx: watch::Sender<ProcessorStatus>,
     rt: tokio::runtime::Runtime,
@@ -38,6 +44,8 @@ struct DeliveryThread {
 impl DeliveryThread {
     fn new(
         api_config: APIWrapper,
+        otlp: Option<OtlpExporter>,
+        file_sink: Option<TraceFileSink>,
         span_rx: mpsc::Receiver<TxEventSignal>,
         stop_tx: watch::Sender<ProcessorStatus>,
         max_batch_size: usize,
@@ -47,6 +55,8 @@ impl DeliveryThread {
 
         Self {
             api_config: Arc::new(api_config),
+            otlp,
+            file_sink: file_sink.map(Arc::new),
             span_rx,
             stop_tx,
             rt,
@@ -57,8 +67,27 @@ impl DeliveryThread {
     }
 
     async fn process_batch(&self, batch: Vec<LogSchema>) {
+        if let Some(file_sink) = self.file_sink.clone() {
+            // The sink writes with blocking std::fs calls.
+            let events = batch.clone();
+            match tokio::task::spawn_blocking(move || file_sink.write(&events)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => log::warn!("Unable to write BAML traces to file: {:#?}", e),
+                Err(e) => log::warn!("Unable to write BAML traces to file: {:#?}", e),
+            }
+        }
+
+        let otlp_work = async {
+            if let Some(otlp) = &self.otlp {
+                if let Err(e) = otlp.export(&batch).await {
+                    log::warn!("Unable to export BAML traces over OTLP: {:#?}", e);
+                }
+            }
+        };
+
         let work = batch
-            .into_iter()
+            .iter()
+            .cloned()
             .map(|work| {
                 let api_config = self.api_config.clone();
                 let semaphore = self.max_concurrency.clone();
@@ -92,7 +121,7 @@ impl DeliveryThread {
             .collect::<Vec<_>>();
 
         // Wait for all the futures to complete
-        futures::future::join_all(work).await;
+        futures::future::join(otlp_work, futures::future::join_all(work)).await;
     }
 
     fn run(&self) {
@@ -156,6 +185,8 @@ pub(super) struct ThreadedTracer {
 impl ThreadedTracer {
     fn start_worker(
         api_config: APIWrapper,
+        otlp: Option<OtlpExporter>,
+        file_sink: Option<TraceFileSink>,
         max_batch_size: usize,
         stats: TraceStats,
     ) -> (
@@ -166,15 +197,35 @@ impl ThreadedTracer {
         let (span_tx, span_rx) = mpsc::channel();
         let (stop_tx, stop_rx) = watch::channel(ProcessorStatus::Active);
         let join_handle = std::thread::spawn(move || {
-            DeliveryThread::new(api_config, span_rx, stop_tx, max_batch_size, stats).run();
+            DeliveryThread::new(
+                api_config,
+                otlp,
+                file_sink,
+                span_rx,
+                stop_tx,
+                max_batch_size,
+                stats,
+            )
+            .run();
         });
 
         (span_tx, stop_rx, join_handle)
     }
 
-    pub fn new(api_config: &APIWrapper, max_batch_size: usize, stats: TraceStats) -> Self {
-        let (span_tx, stop_rx, join_handle) =
-            Self::start_worker(api_config.clone(), max_batch_size, stats.clone());
+    pub fn new(
+        api_config: &APIWrapper,
+        max_batch_size: usize,
+        stats: TraceStats,
+        otlp: Option<OtlpExporter>,
+        file_sink: Option<TraceFileSink>,
+    ) -> Self {
+        let (span_tx, stop_rx, join_handle) = Self::start_worker(
+            api_config.clone(),
+            otlp,
+            file_sink,
+            max_batch_size,
+            stats.clone(),
+        );
 
         Self {
             api_config: Arc::new(api_config.clone()),
@@ -0,0 +1,316 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use baml_types::{BamlValue, ConstraintLevel, FieldType};
+use indexmap::IndexSet;
+use internal_baml_core::{
+    ast::{Top, WithIdentifier, WithName, WithSpan},
+    internal_baml_diagnostics::{DatamodelError, Diagnostics, SourceFile, Span},
+    internal_baml_schema_ast::parse_schema,
+    ir::{repr::IntermediateRepr, IRHelper},
+    validate,
+};
+
+use super::{TypeBuilder, WithMeta};
+use crate::BamlRuntime;
+
+/// The file diagnostics from [`TypeBuilder::add_baml`] point at.
+const SOURCE_FILE: &str = "type_builder.baml";
+/// Stand-ins for the types of the runtime, so the snippet can refer to them.
+const RUNTIME_TYPES_FILE: &str = "runtime_types.baml";
+
+impl TypeBuilder {
+    /// Adds the classes, enums and type aliases declared in `baml` to the
+    /// builder.
+    ///
+    /// The snippet is validated against the types of `runtime`: it may refer
+    /// to them, add new ones, and extend `@@dynamic` classes and enums by
+    /// declaring them again with only the new fields or values. Type aliases
+    /// are resolved into the types that use them.
+    pub fn add_baml(&self, baml: &str, runtime: &BamlRuntime) -> Result<(), Diagnostics> {
+        self.add_baml_to_ir(baml, &runtime.inner.ir, runtime.env_vars())
+    }
+
+    pub(crate) fn add_baml_to_ir(
+        &self,
+        baml: &str,
+        ir: &IntermediateRepr,
+        env: &HashMap<String, String>,
+    ) -> Result<(), Diagnostics> {
+        let root = PathBuf::from(".");
+        let source = SourceFile::from((PathBuf::from(SOURCE_FILE), baml));
+
+        let (ast, mut diagnostics) = parse_schema(&root, &source)?;
+        diagnostics.to_result()?;
+
+        let mut declared = IndexSet::new();
+        for top in &ast.tops {
+            let name = top.identifier().name();
+            let span = top.identifier().span().clone();
+            match top {
+                Top::Class(_) if ir.find_enum(name).is_ok() => diagnostics.push_error(
+                    DatamodelError::new_validation_error(
+                        &format!("`{name}` is already an enum and can't be redeclared as a class"),
+                        span,
+                    ),
+                ),
+                Top::Enum(_) if ir.find_class(name).is_ok() => diagnostics.push_error(
+                    DatamodelError::new_validation_error(
+                        &format!("`{name}` is already a class and can't be redeclared as an enum"),
+                        span,
+                    ),
+                ),
+                Top::Class(_) => match ir.find_class(name) {
+                    Ok(class) if class.item.attributes.get("dynamic_type").is_none() => {
+                        diagnostics.push_error(DatamodelError::new_validation_error(
+                            &format!("Class `{name}` can only be extended if it is marked @@dynamic"),
+                            span,
+                        ))
+                    }
+                    _ => (),
+                },
+                Top::Enum(_) => match ir.find_enum(name) {
+                    Ok(enm) if enm.item.attributes.get("dynamic_type").is_none() => {
+                        diagnostics.push_error(DatamodelError::new_validation_error(
+                            &format!("Enum `{name}` can only be extended if it is marked @@dynamic"),
+                            span,
+                        ))
+                    }
+                    _ => (),
+                },
+                Top::TypeAlias(_) if ir.find_class(name).is_ok() || ir.find_enum(name).is_ok() => {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        &format!("`{name}` is already defined and can't be redeclared as a type alias"),
+                        span,
+                    ))
+                }
+                Top::TypeAlias(_) => (),
+                _ => diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Only classes, enums and type aliases can be added to a TypeBuilder, found {} `{name}`",
+                        top.get_type()
+                    ),
+                    top.span().clone(),
+                )),
+            }
+            declared.insert(name.to_string());
+        }
+        diagnostics.to_result()?;
+
+        let runtime_types = SourceFile::from((
+            PathBuf::from(RUNTIME_TYPES_FILE),
+            runtime_types(ir, &declared),
+        ));
+        let validated = validate(&root, vec![runtime_types, source.clone()]);
+        let mut diagnostics = validated.diagnostics;
+        diagnostics.to_result()?;
+
+        let added = IntermediateRepr::from_parser_database(&validated.db, validated.configuration)
+            .map_err(|e| error(&e.to_string(), Span::empty(source.clone())))?;
+
+        // Type builder overrides have no notion of type aliases, so only the
+        // ones that can be resolved into plain types are supported.
+        for cycle in added.structural_recursive_alias_cycles() {
+            for top in &ast.tops {
+                if cycle.contains_key(top.identifier().name()) {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        "Recursive type aliases can't be added to a TypeBuilder",
+                        top.identifier().span().clone(),
+                    ));
+                }
+            }
+        }
+
+        for class in added.walk_classes() {
+            if !declared.contains(class.name()) {
+                continue;
+            }
+            if !class.item.attributes.constraints.is_empty() {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "Class level @@assert and @@check can't be added to a TypeBuilder",
+                    span(class.span(), &source),
+                ));
+            }
+            let existing = ir.find_class(class.name()).ok();
+            for field in class.walk_fields() {
+                if existing
+                    .as_ref()
+                    .is_some_and(|c| c.find_field(field.name()).is_some())
+                {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "Field `{}` already exists on class `{}`",
+                            field.name(),
+                            class.name()
+                        ),
+                        span(field.span(), &source),
+                    ));
+                }
+            }
+        }
+
+        for enm in added.walk_enums() {
+            let Ok(existing) = ir.find_enum(enm.name()) else {
+                continue;
+            };
+            if !declared.contains(enm.name()) {
+                continue;
+            }
+            for value in enm.walk_values() {
+                if existing.find_value(value.name()).is_some() {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "Value `{}` already exists on enum `{}`",
+                            value.name(),
+                            enm.name()
+                        ),
+                        span(value.item.attributes.span.as_ref(), &source),
+                    ));
+                }
+            }
+        }
+        diagnostics.to_result()?;
+
+        let string = |value: anyhow::Result<Option<String>>, at: Option<&Span>| {
+            value
+                .map(|v| v.map(BamlValue::String))
+                .map_err(|e| error(&e.to_string(), span(at, &source)))
+        };
+
+        for class in added.walk_classes() {
+            if !declared.contains(class.name()) {
+                continue;
+            }
+            let builder = self.class(class.name());
+            let builder = builder.lock().unwrap();
+            if let Some(alias) = string(class.alias(env), class.span())? {
+                builder.with_meta("alias", alias);
+            }
+            for field in class.walk_fields() {
+                let property = builder.property(field.name());
+                let property = property.lock().unwrap();
+                property.r#type(field.r#type().clone());
+                if let Some(alias) = string(field.alias(env), field.span())? {
+                    property.with_meta("alias", alias);
+                }
+                if let Some(description) = string(field.description(env), field.span())? {
+                    property.with_meta("description", description);
+                }
+            }
+        }
+
+        for enm in added.walk_enums() {
+            if !declared.contains(enm.name()) {
+                continue;
+            }
+            let builder = self.r#enum(enm.name());
+            let builder = builder.lock().unwrap();
+            if let Some(alias) = string(enm.alias(env), enm.span())? {
+                builder.with_meta("alias", alias);
+            }
+            for value in enm.walk_values() {
+                let value_span = value.item.attributes.span.as_ref();
+                let value_builder = builder.value(value.name());
+                let value_builder = value_builder.lock().unwrap();
+                if let Some(alias) = string(value.alias(env), value_span)? {
+                    value_builder.with_meta("alias", alias);
+                }
+                if let Some(description) = string(value.description(env), value_span)? {
+                    value_builder.with_meta("description", description);
+                }
+                let skip = value
+                    .skip(env)
+                    .map_err(|e| error(&e.to_string(), span(value_span, &source)))?;
+                if skip {
+                    value_builder.with_meta("skip", BamlValue::Bool(true));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Declarations for every class, enum and type alias of the runtime that the
+/// snippet doesn't declare itself. Only the names of classes and enums matter,
+/// so class fields are left out, but aliases keep their targets since the
+/// snippet's fields resolve to them.
+fn runtime_types(ir: &IntermediateRepr, declared: &IndexSet<String>) -> String {
+    let mut source = String::new();
+    for class in ir.walk_classes() {
+        if !declared.contains(class.name()) {
+            source.push_str(&format!("class {} {{\n}}\n", class.name()));
+        }
+    }
+    for enm in ir.walk_enums() {
+        if !declared.contains(enm.name()) {
+            source.push_str(&format!("enum {} {{\n", enm.name()));
+            for value in enm.walk_values() {
+                source.push_str(&format!("  {}\n", value.name()));
+            }
+            source.push_str("}\n");
+        }
+    }
+    let aliases = ir
+        .type_aliases()
+        .iter()
+        .chain(ir.structural_recursive_alias_cycles().iter().flatten());
+    for (name, target) in aliases {
+        if !declared.contains(name) {
+            source.push_str(&format!("type {} = {}\n", name, baml_type(target)));
+        }
+    }
+    source
+}
+
+/// `r#type` in BAML syntax.
+fn baml_type(r#type: &FieldType) -> String {
+    let join = |types: &[FieldType], separator: &str| {
+        types
+            .iter()
+            .map(baml_type)
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+    match r#type {
+        FieldType::Union(items) => format!("({})", join(items, " | ")),
+        FieldType::Tuple(items) => format!("({})", join(items, ", ")),
+        FieldType::List(item) => format!("{}[]", baml_type(item)),
+        FieldType::Optional(item) => format!("{}?", baml_type(item)),
+        FieldType::Map(key, value) => format!("map<{}, {}>", baml_type(key), baml_type(value)),
+        FieldType::Constrained { base, constraints } => {
+            let mut source = format!("({}", baml_type(base));
+            for constraint in constraints {
+                let level = match constraint.level {
+                    ConstraintLevel::Assert => "assert",
+                    ConstraintLevel::Check => "check",
+                };
+                let label = constraint
+                    .label
+                    .as_ref()
+                    .map(|label| format!("{label}, "))
+                    .unwrap_or_default();
+                source.push_str(&format!(
+                    " @{level}({label}{{{{ {} }}}})",
+                    constraint.expression.0
+                ));
+            }
+            source.push(')');
+            source
+        }
+        FieldType::Primitive(_)
+        | FieldType::Enum(_)
+        | FieldType::Literal(_)
+        | FieldType::Class(_)
+        | FieldType::RecursiveTypeAlias(_) => r#type.to_string(),
+    }
+}
+
+fn span(span: Option<&Span>, source: &SourceFile) -> Span {
+    span.cloned().unwrap_or_else(|| Span::empty(source.clone()))
+}
+
+fn error(message: &str, span: Span) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new(PathBuf::from("."));
+    diagnostics.push_error(DatamodelError::new_validation_error(message, span));
+    diagnostics
+}
@@ -5,6 +5,8 @@ use indexmap::IndexMap;
 
 use crate::runtime_context::{PropertyAttributes, RuntimeClassOverride, RuntimeEnumOverride};
 
+mod add_baml;
+
 type MetaData = Arc<Mutex<IndexMap<String, BamlValue>>>;
 
 trait Meta {
@@ -292,4 +294,165 @@ mod tests {
             .r#type(FieldType::int())
             .with_meta("alias", BamlValue::String("years".to_string()));
     }
+
+    #[test]
+    fn test_add_baml() {
+        let ir = internal_baml_core::ir::repr::make_test_ir(
+            r#"
+            class Resume {
+                name string
+                @@dynamic
+            }
+
+            enum Category {
+                A
+                @@dynamic
+            }
+            "#,
+        )
+        .unwrap();
+
+        let builder = TypeBuilder::new();
+        builder
+            .add_baml_to_ir(
+                r#"
+                type Grade = "junior" | "senior"
+
+                class Resume {
+                    grade Grade @alias("level")
+                    job Job?
+                }
+
+                class Job {
+                    title string @description("The job title")
+                    category Category
+                    years int @assert(positive, {{ this > 0 }})
+                }
+
+                enum Category {
+                    B @alias("bee")
+                    C @skip
+                }
+                "#,
+                &ir,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let (classes, enums) = builder.to_overrides();
+        let (grade, attrs) = &classes["Resume"].new_fields["grade"];
+        assert_eq!(
+            grade,
+            &FieldType::union(vec![
+                FieldType::literal_string("junior".into()),
+                FieldType::literal_string("senior".into()),
+            ])
+        );
+        assert_eq!(attrs.alias, Some(BamlValue::String("level".into())));
+        assert_eq!(
+            classes["Resume"].new_fields["job"].0,
+            FieldType::class("Job").as_optional()
+        );
+        assert_eq!(
+            classes["Job"].new_fields["title"].1.meta["description"],
+            BamlValue::String("The job title".into())
+        );
+        assert_eq!(
+            classes["Job"].new_fields["category"].0,
+            FieldType::r#enum("Category")
+        );
+        assert!(matches!(
+            classes["Job"].new_fields["years"].0,
+            FieldType::Constrained { .. }
+        ));
+        assert_eq!(
+            enums["Category"].values.keys().collect::<Vec<_>>(),
+            vec!["B", "C"]
+        );
+        assert_eq!(enums["Category"].values["C"].skip, Some(true));
+    }
+
+    #[test]
+    fn test_add_baml_existing_aliases() {
+        let ir = internal_baml_core::ir::repr::make_test_ir(
+            r#"
+            type Level = "junior" | "senior"
+            type Age = int @check(positive, {{ this > 0 }})
+            type JsonValue = int | string | JsonValue[] | map<string, JsonValue>
+
+            class Resume {
+                name string
+                @@dynamic
+            }
+            "#,
+        )
+        .unwrap();
+
+        let builder = TypeBuilder::new();
+        builder
+            .add_baml_to_ir(
+                r#"
+                class Resume {
+                    level Level
+                    age Age
+                    extra JsonValue
+                }
+                "#,
+                &ir,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let (classes, _) = builder.to_overrides();
+        let fields = &classes["Resume"].new_fields;
+        assert_eq!(fields["level"].0, ir.type_aliases()["Level"]);
+        assert_eq!(fields["age"].0, ir.type_aliases()["Age"]);
+        assert_eq!(
+            fields["extra"].0,
+            FieldType::RecursiveTypeAlias("JsonValue".to_string())
+        );
+    }
+
+    #[test]
+    fn test_add_baml_diagnostics() {
+        let ir = internal_baml_core::ir::repr::make_test_ir(
+            r#"
+            class Resume {
+                name string
+            }
+            "#,
+        )
+        .unwrap();
+
+        let errors = |baml: &str| {
+            TypeBuilder::new()
+                .add_baml_to_ir(baml, &ir, &Default::default())
+                .unwrap_err()
+                .errors()
+                .iter()
+                .map(|e| e.message().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            errors("class Resume {\n  age int\n}"),
+            vec!["Error validating: Class `Resume` can only be extended if it is marked @@dynamic"]
+        );
+        assert_eq!(
+            errors("function Foo() -> string {\n  client \"openai/gpt-4o\"\n  prompt #\"hi\"#\n}"),
+            vec!["Error validating: Only classes, enums and type aliases can be added to a TypeBuilder, found function `Foo`"]
+        );
+        assert_eq!(
+            errors("type Tree = string | Tree[]"),
+            vec!["Error validating: Recursive type aliases can't be added to a TypeBuilder"]
+        );
+
+        let diagnostics = TypeBuilder::new()
+            .add_baml_to_ir("class Job {\n  title Title\n}", &ir, &Default::default())
+            .unwrap_err();
+        let error = &diagnostics.errors()[0];
+        assert!(error.message().contains("Title"), "{}", error.message());
+        assert_eq!(error.span().file.path(), "type_builder.baml");
+        assert_eq!(error.span().line_and_column().0, (1, 8));
+    }
 }
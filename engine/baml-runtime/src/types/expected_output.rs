@@ -0,0 +1,172 @@
+use baml_types::{BamlMap, BamlValue};
+
+/// The first place where a test's output differs from its `expected` or
+/// `expected_partial` value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputMismatch {
+    /// Where in the output the values differ, e.g. `this.items[0].name`.
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for OutputMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.path, self.expected, self.actual
+        )
+    }
+}
+
+/// Compare a test's output against the value it expects.
+///
+/// Test values are written as plain BAML values, so maps match classes and
+/// strings match enums. Fields the expected value leaves out must be null,
+/// unless `partial` is set, in which case they are ignored.
+pub(crate) fn compare_output(
+    expected: &BamlValue,
+    actual: &BamlValue,
+    partial: bool,
+) -> Result<(), OutputMismatch> {
+    compare_at("this".to_string(), expected, actual, partial)
+}
+
+fn compare_at(
+    path: String,
+    expected: &BamlValue,
+    actual: &BamlValue,
+    partial: bool,
+) -> Result<(), OutputMismatch> {
+    let matches = match (expected, actual) {
+        (
+            BamlValue::Map(expected) | BamlValue::Class(_, expected),
+            BamlValue::Map(actual) | BamlValue::Class(_, actual),
+        ) => return compare_fields(path, expected, actual, partial),
+        (BamlValue::List(expected), BamlValue::List(actual)) if expected.len() == actual.len() => {
+            return expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .try_for_each(|(i, (e, a))| compare_at(format!("{path}[{i}]"), e, a, partial));
+        }
+        (
+            BamlValue::String(e) | BamlValue::Enum(_, e),
+            BamlValue::String(a) | BamlValue::Enum(_, a),
+        ) => e == a,
+        (BamlValue::Int(e), BamlValue::Float(a)) => *e as f64 == *a,
+        (BamlValue::Float(e), BamlValue::Int(a)) => *e == *a as f64,
+        _ => expected == actual,
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        Err(mismatch(path, expected, actual))
+    }
+}
+
+fn compare_fields(
+    path: String,
+    expected: &BamlMap<String, BamlValue>,
+    actual: &BamlMap<String, BamlValue>,
+    partial: bool,
+) -> Result<(), OutputMismatch> {
+    for (key, expected) in expected {
+        let actual = actual.get(key).unwrap_or(&BamlValue::Null);
+        compare_at(format!("{path}.{key}"), expected, actual, partial)?;
+    }
+    if !partial {
+        for (key, actual) in actual {
+            if !expected.contains_key(key) && !matches!(actual, BamlValue::Null) {
+                return Err(mismatch(format!("{path}.{key}"), &BamlValue::Null, actual));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn mismatch(path: String, expected: &BamlValue, actual: &BamlValue) -> OutputMismatch {
+    OutputMismatch {
+        path,
+        expected: serde_json::json!(expected).to_string(),
+        actual: serde_json::json!(actual).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(name: &str, age: i64, tags: &[&str]) -> BamlValue {
+        BamlValue::Class(
+            "Person".to_string(),
+            BamlMap::from_iter([
+                ("name".to_string(), BamlValue::String(name.to_string())),
+                ("age".to_string(), BamlValue::Int(age)),
+                (
+                    "tags".to_string(),
+                    BamlValue::List(
+                        tags.iter()
+                            .map(|t| BamlValue::Enum("Tag".to_string(), t.to_string()))
+                            .collect(),
+                    ),
+                ),
+            ]),
+        )
+    }
+
+    fn map(fields: Vec<(&str, BamlValue)>) -> BamlValue {
+        BamlValue::Map(BamlMap::from_iter(
+            fields.into_iter().map(|(k, v)| (k.to_string(), v)),
+        ))
+    }
+
+    #[test]
+    fn exact_match_requires_every_field() {
+        let actual = person("Bob", 42, &["ADMIN"]);
+        let expected = map(vec![
+            ("name", BamlValue::String("Bob".into())),
+            ("age", BamlValue::Float(42.0)),
+            (
+                "tags",
+                BamlValue::List(vec![BamlValue::String("ADMIN".into())]),
+            ),
+        ]);
+        assert_eq!(compare_output(&expected, &actual, false), Ok(()));
+
+        let expected = map(vec![("name", BamlValue::String("Bob".into()))]);
+        assert_eq!(
+            compare_output(&expected, &actual, false),
+            Err(OutputMismatch {
+                path: "this.age".to_string(),
+                expected: "null".to_string(),
+                actual: "42".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn partial_match_ignores_missing_fields() {
+        let actual = person("Bob", 42, &["ADMIN", "USER"]);
+        let expected = map(vec![("name", BamlValue::String("Bob".into()))]);
+        assert_eq!(compare_output(&expected, &actual, true), Ok(()));
+
+        let expected = map(vec![(
+            "tags",
+            BamlValue::List(vec![
+                BamlValue::String("ADMIN".into()),
+                BamlValue::String("GUEST".into()),
+            ]),
+        )]);
+        assert_eq!(
+            compare_output(&expected, &actual, true),
+            Err(OutputMismatch {
+                path: "this.tags[1]".to_string(),
+                expected: "\"GUEST\"".to_string(),
+                actual: "\"USER\"".to_string(),
+            })
+        );
+    }
+}
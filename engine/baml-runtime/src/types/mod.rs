@@ -1,13 +1,21 @@
+mod cancellation;
 mod context_manager;
+mod expected_output;
 mod expression_helper;
 pub mod on_log_event;
 mod response;
+mod response_cache;
 pub(crate) mod runtime_context;
 mod stream;
 mod trace_stats;
 
+pub use cancellation::CancellationToken;
 pub use context_manager::RuntimeContextManager;
-pub use response::{FunctionResult, TestFailReason, TestResponse, TestStatus};
+pub use expected_output::OutputMismatch;
+pub use response::{FunctionResult, TestExpectations, TestFailReason, TestResponse, TestStatus};
+pub use response_cache::{CacheFuture, MemoryCache, ResponseCache, ResponseCacheBackend};
+#[cfg(not(target_arch = "wasm32"))]
+pub use response_cache::DirectoryCache;
 pub use runtime_context::{RuntimeContext, SpanCtx};
 pub use stream::FunctionResultStream;
 pub use trace_stats::{InnerTraceStats, TraceStats};
@@ -17,3 +25,12 @@ pub struct RenderCurlSettings {
     pub stream: bool,
     pub as_shell_commands: bool,
 }
+
+/// The HTTP request BAML would send to the provider for a function call.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HTTPRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: indexmap::IndexMap<String, String>,
+    pub body: serde_json::Value,
+}
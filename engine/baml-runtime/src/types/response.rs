@@ -6,9 +6,12 @@ use crate::{
 use anyhow::Result;
 use colored::*;
 
-use baml_types::BamlValue;
+use baml_types::{BamlValue, Constraint};
+use internal_baml_core::ir::jinja_helpers::evaluate_predicate;
 use jsonish::BamlValueWithFlags;
 
+use super::expected_output::{compare_output, OutputMismatch};
+
 #[derive(Debug)]
 pub struct FunctionResult {
     event_chain: Vec<(
@@ -60,7 +63,9 @@ impl FunctionResult {
         }
     }
 
-    pub(crate) fn event_chain(
+    /// Every attempt made by the orchestrator, in order. The last entry is the
+    /// one reported by the other accessors.
+    pub fn event_chain(
         &self,
     ) -> &Vec<(
         OrchestrationScope,
@@ -169,9 +174,7 @@ impl FunctionResult {
                     format!("Failed to parse LLM response: {}", actual_error),
                 LLMResponse::LLMFailure(err) => format!(
                     "LLM Failure: {} ({}) - {}",
-                    err.message,
-                    err.code.to_string(),
-                    actual_error
+                    err.message, err.code, actual_error
                 ),
                 LLMResponse::UserFailure(err) =>
                     format!("User Failure: {} - {}", err, actual_error),
@@ -185,6 +188,21 @@ impl FunctionResult {
 pub struct TestResponse {
     pub function_response: FunctionResult,
     pub function_span: Option<uuid::Uuid>,
+    pub expectations: TestExpectations,
+}
+
+/// What a test block expects of its function, resolved against the
+/// environment the test ran in.
+#[derive(Debug, Default)]
+pub struct TestExpectations {
+    /// `@@assert` and `@@check` attributes. Both fail the test when they do
+    /// not hold.
+    pub constraints: Vec<Constraint>,
+    pub expected_output: Option<BamlValue>,
+    /// Whether `expected_output` came from `expected_partial`.
+    pub partial: bool,
+    pub max_latency_ms: Option<u64>,
+    pub max_total_tokens: Option<u64>,
 }
 
 impl std::fmt::Display for TestResponse {
@@ -213,6 +231,21 @@ pub enum TestFailReason<'a> {
     TestUnspecified(&'a anyhow::Error),
     TestLLMFailure(&'a LLMResponse),
     TestParseFailure(&'a anyhow::Error),
+    /// An `@@assert` or `@@check` on the test did not hold. `error` is set
+    /// when the expression could not be evaluated at all.
+    TestConstraintFailure {
+        constraint: &'a Constraint,
+        error: Option<String>,
+    },
+    TestOutputMismatch(OutputMismatch),
+    TestLatencyBudgetExceeded {
+        max_latency_ms: u64,
+        latency_ms: u64,
+    },
+    TestTokenBudgetExceeded {
+        max_total_tokens: u64,
+        total_tokens: u64,
+    },
 }
 
 impl PartialEq for TestFailReason<'_> {
@@ -223,27 +256,163 @@ impl PartialEq for TestFailReason<'_> {
             (Self::TestParseFailure(a), Self::TestParseFailure(b)) => {
                 a.to_string() == b.to_string()
             }
+            (
+                Self::TestConstraintFailure {
+                    constraint: a,
+                    error: a_error,
+                },
+                Self::TestConstraintFailure {
+                    constraint: b,
+                    error: b_error,
+                },
+            ) => a == b && a_error == b_error,
+            (Self::TestOutputMismatch(a), Self::TestOutputMismatch(b)) => a == b,
+            (
+                Self::TestLatencyBudgetExceeded {
+                    max_latency_ms: a_max,
+                    latency_ms: a,
+                },
+                Self::TestLatencyBudgetExceeded {
+                    max_latency_ms: b_max,
+                    latency_ms: b,
+                },
+            ) => a_max == b_max && a == b,
+            (
+                Self::TestTokenBudgetExceeded {
+                    max_total_tokens: a_max,
+                    total_tokens: a,
+                },
+                Self::TestTokenBudgetExceeded {
+                    max_total_tokens: b_max,
+                    total_tokens: b,
+                },
+            ) => a_max == b_max && a == b,
             _ => false,
         }
     }
 }
 
+impl std::fmt::Display for TestFailReason<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TestUnspecified(e) => write!(f, "{e:#}"),
+            Self::TestLLMFailure(r) => write!(f, "{r}"),
+            Self::TestParseFailure(e) => write!(f, "{e:#}"),
+            Self::TestConstraintFailure { constraint, error } => {
+                let kind = match constraint.level {
+                    baml_types::ConstraintLevel::Assert => "assert",
+                    baml_types::ConstraintLevel::Check => "check",
+                };
+                match &constraint.label {
+                    Some(label) => write!(f, "@@{kind} {label} failed")?,
+                    None => write!(f, "@@{kind} {{{{ {} }}}} failed", constraint.expression.0)?,
+                }
+                match error {
+                    Some(error) => write!(f, ": {error}"),
+                    None => Ok(()),
+                }
+            }
+            Self::TestOutputMismatch(mismatch) => {
+                write!(f, "Output does not match the expected value at {mismatch}")
+            }
+            Self::TestLatencyBudgetExceeded {
+                max_latency_ms,
+                latency_ms,
+            } => write!(
+                f,
+                "Took {latency_ms}ms, more than max_latency_ms {max_latency_ms}"
+            ),
+            Self::TestTokenBudgetExceeded {
+                max_total_tokens,
+                total_tokens,
+            } => write!(
+                f,
+                "Used {total_tokens} tokens, more than max_total_tokens {max_total_tokens}"
+            ),
+        }
+    }
+}
+
 impl Eq for TestFailReason<'_> {}
 
 impl TestResponse {
     pub fn status(&self) -> TestStatus {
         let func_res = &self.function_response;
-        if let Some(parsed) = func_res.result_with_constraints() {
-            if parsed.is_ok() {
-                TestStatus::Pass
-            } else {
-                TestStatus::Fail(TestFailReason::TestParseFailure(
-                    parsed.as_ref().unwrap_err(),
-                ))
+        let this: BamlValue = match func_res.result_with_constraints() {
+            Some(Ok(value)) => value.into(),
+            Some(Err(e)) => return TestStatus::Fail(TestFailReason::TestParseFailure(e)),
+            None => {
+                return TestStatus::Fail(TestFailReason::TestLLMFailure(func_res.llm_response()))
             }
-        } else {
-            TestStatus::Fail(TestFailReason::TestLLMFailure(func_res.llm_response()))
+        };
+
+        let expectations = &self.expectations;
+        for constraint in &expectations.constraints {
+            let error = match evaluate_predicate(&this, &constraint.expression) {
+                Ok(true) => continue,
+                Ok(false) => None,
+                Err(e) => Some(format!("{e:#}")),
+            };
+            return TestStatus::Fail(TestFailReason::TestConstraintFailure { constraint, error });
         }
+
+        if let Some(expected) = &expectations.expected_output {
+            if let Err(mismatch) = compare_output(expected, &this, expectations.partial) {
+                return TestStatus::Fail(TestFailReason::TestOutputMismatch(mismatch));
+            }
+        }
+
+        if let Some(max_latency_ms) = expectations.max_latency_ms {
+            let latency_ms = self.latency().as_millis() as u64;
+            if latency_ms > max_latency_ms {
+                return TestStatus::Fail(TestFailReason::TestLatencyBudgetExceeded {
+                    max_latency_ms,
+                    latency_ms,
+                });
+            }
+        }
+
+        // Providers that don't report usage can't exceed a token budget.
+        if let (Some(max_total_tokens), Some(total_tokens)) =
+            (expectations.max_total_tokens, self.total_tokens())
+        {
+            if total_tokens > max_total_tokens {
+                return TestStatus::Fail(TestFailReason::TestTokenBudgetExceeded {
+                    max_total_tokens,
+                    total_tokens,
+                });
+            }
+        }
+
+        TestStatus::Pass
+    }
+
+    /// Time spent in LLM calls, summed over retries and fallbacks.
+    pub fn latency(&self) -> web_time::Duration {
+        self.function_response
+            .event_chain()
+            .iter()
+            .map(|(_, response, _, _)| match response {
+                LLMResponse::Success(r) => r.latency,
+                LLMResponse::LLMFailure(r) => r.latency,
+                LLMResponse::UserFailure(_) | LLMResponse::InternalFailure(_) => {
+                    web_time::Duration::ZERO
+                }
+            })
+            .sum()
+    }
+
+    /// Tokens used, summed over retries and fallbacks. `None` if no call
+    /// reported its usage.
+    pub fn total_tokens(&self) -> Option<u64> {
+        self.function_response
+            .event_chain()
+            .iter()
+            .filter_map(|(_, response, _, _)| match response {
+                LLMResponse::Success(r) => r.metadata.total_tokens,
+                _ => None,
+            })
+            .reduce(|a, b| a + b)
     }
 }
 
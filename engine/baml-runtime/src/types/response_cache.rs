@@ -0,0 +1,513 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use internal_baml_jinja::RenderedPrompt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use web_time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::internal::llm_client::{
+    output_schema::OutputSchema, LLMCompleteResponse, LLMCompleteResponseMetadata,
+};
+
+cfg_if::cfg_if!(
+    if #[cfg(target_arch = "wasm32")] {
+        pub type CacheFuture<'a, T> = futures::future::LocalBoxFuture<'a, Result<T>>;
+    } else {
+        pub type CacheFuture<'a, T> = futures::future::BoxFuture<'a, Result<T>>;
+    }
+);
+
+/// Storage for cached LLM responses.
+///
+/// Keys are hex encoded SHA-256 digests and values are opaque JSON strings,
+/// so a backend only has to store strings. Expiry is handled by
+/// [`ResponseCache`], which removes entries it finds expired.
+pub trait ResponseCacheBackend: Send + Sync {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>>;
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()>;
+
+    /// Backends that can't delete entries may keep them forever, expired
+    /// entries are never returned either way.
+    fn remove<'a>(&'a self, _key: &'a str) -> CacheFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Caches LLM responses that parsed, keyed by the client, its provider and
+/// resolved options, the output schema sent along and the rendered prompt.
+///
+/// Lookups and writes that fail are logged and treated as misses: a broken
+/// cache never fails a function call.
+#[derive(Clone)]
+pub struct ResponseCache {
+    backend: Arc<dyn ResponseCacheBackend>,
+    ttl: Option<Duration>,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResponseCache {
+    pub fn new(backend: impl ResponseCacheBackend + 'static) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            ttl: None,
+        }
+    }
+
+    /// A cache that lives as long as the process.
+    pub fn memory() -> Self {
+        Self::new(MemoryCache::default())
+    }
+
+    /// A cache that stores one file per response in `path`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn directory(path: impl Into<std::path::PathBuf>) -> Self {
+        Self::new(DirectoryCache::new(path))
+    }
+
+    /// Entries older than `ttl` are ignored. Without a TTL they never expire.
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Identifies a request: the client, its provider and resolved options,
+    /// the output schema for tools or structured outputs and the rendered
+    /// prompt.
+    pub(crate) fn key(
+        client: &str,
+        provider: &str,
+        resolved_options: &serde_json::Value,
+        output_schema: Option<&OutputSchema>,
+        prompt: &RenderedPrompt,
+    ) -> String {
+        let input = serde_json::json!({
+            "client": client,
+            "provider": provider,
+            "options": sorted(resolved_options),
+            "output_schema": output_schema.map(|schema| serde_json::json!({
+                "name": schema.name,
+                "schema": schema.json_schema(),
+            })),
+            "prompt": prompt,
+        });
+        Sha256::digest(input.to_string().as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Returns the cached response for the request `key` identifies.
+    pub(crate) async fn get(
+        &self,
+        key: &str,
+        client: &str,
+        request_options: &HashMap<String, serde_json::Value>,
+        prompt: &RenderedPrompt,
+    ) -> Option<LLMCompleteResponse> {
+        let value = match self.backend.get(key).await {
+            Ok(value) => value?,
+            Err(e) => {
+                log::warn!("Failed to read from the response cache: {:#}", e);
+                return None;
+            }
+        };
+        let entry: CacheEntry = match serde_json::from_str(&value) {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!("Ignoring unreadable response cache entry {}: {}", key, e);
+                return None;
+            }
+        };
+        if let Some(ttl) = self.ttl {
+            if since_epoch(SystemTime::now()).saturating_sub(entry.created_at) >= ttl {
+                if let Err(e) = self.backend.remove(key).await {
+                    log::warn!("Failed to remove an expired response cache entry: {:#}", e);
+                }
+                return None;
+            }
+        }
+
+        Some(LLMCompleteResponse {
+            client: client.to_string(),
+            model: entry.model,
+            prompt: prompt.clone(),
+            request_options: request_options.clone(),
+            content: entry.content,
+            start_time: SystemTime::now(),
+            latency: Duration::ZERO,
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: entry.baml_is_complete,
+                finish_reason: entry.finish_reason,
+                prompt_tokens: entry.prompt_tokens,
+                output_tokens: entry.output_tokens,
+                total_tokens: entry.total_tokens,
+                cached: true,
+            },
+        })
+    }
+
+    pub(crate) async fn set(&self, key: &str, response: &LLMCompleteResponse) {
+        let entry = CacheEntry {
+            model: response.model.clone(),
+            content: response.content.clone(),
+            baml_is_complete: response.metadata.baml_is_complete,
+            finish_reason: response.metadata.finish_reason.clone(),
+            prompt_tokens: response.metadata.prompt_tokens,
+            output_tokens: response.metadata.output_tokens,
+            total_tokens: response.metadata.total_tokens,
+            created_at: since_epoch(SystemTime::now()),
+        };
+        let value = match serde_json::to_string(&entry) {
+            Ok(value) => value,
+            Err(e) => {
+                log::warn!("Failed to serialize response cache entry: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.backend.set(key, value).await {
+            log::warn!("Failed to write to the response cache: {:#}", e);
+        }
+    }
+}
+
+/// What gets stored for a response. Everything else is known from the request.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    model: String,
+    content: String,
+    baml_is_complete: bool,
+    finish_reason: Option<String>,
+    prompt_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    total_tokens: Option<u64>,
+    /// Time since the unix epoch.
+    created_at: Duration,
+}
+
+fn since_epoch(time: SystemTime) -> Duration {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+/// `value` with the keys of every object sorted, so the key doesn't depend on
+/// the iteration order of the maps the options came from.
+fn sorted(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), sorted(v)))
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(sorted).collect())
+        }
+        value => value.clone(),
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl ResponseCacheBackend for MemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>> {
+        let value = self.entries.lock().unwrap().get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()> {
+        self.entries.lock().unwrap().insert(key.to_string(), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        self.entries.lock().unwrap().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Stores each entry as `<key>.json` in a directory, which is created on the
+/// first write.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct DirectoryCache {
+    path: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DirectoryCache {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ResponseCacheBackend for DirectoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>> {
+        Box::pin(async move {
+            match tokio::fs::read_to_string(self.path.join(format!("{key}.json"))).await {
+                Ok(value) => Ok(Some(value)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::fs::create_dir_all(&self.path).await?;
+            // Write to a temporary file first, so concurrent readers never see
+            // a partial entry.
+            let tmp = self
+                .path
+                .join(format!("{key}.{}.tmp", uuid::Uuid::new_v4()));
+            tokio::fs::write(&tmp, value).await?;
+            tokio::fs::rename(&tmp, self.path.join(format!("{key}.json"))).await?;
+            Ok(())
+        })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.path.join(format!("{key}.json"))).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::FieldType;
+    use internal_baml_jinja::{types::OutputFormatContent, ChatMessagePart, RenderedChatMessage};
+
+    use super::*;
+
+    fn prompt(text: &str) -> RenderedPrompt {
+        RenderedPrompt::Chat(vec![RenderedChatMessage {
+            role: "user".to_string(),
+            allow_duplicate_role: false,
+            parts: vec![ChatMessagePart::Text(text.to_string())],
+        }])
+    }
+
+    fn options(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    fn response(content: &str) -> LLMCompleteResponse {
+        LLMCompleteResponse {
+            client: "GPT4".to_string(),
+            model: "gpt-4o".to_string(),
+            prompt: prompt("hi"),
+            request_options: HashMap::new(),
+            content: content.to_string(),
+            start_time: SystemTime::now(),
+            latency: Duration::from_millis(500),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: true,
+                finish_reason: Some("stop".to_string()),
+                prompt_tokens: Some(3),
+                output_tokens: Some(5),
+                total_tokens: Some(8),
+                cached: false,
+            },
+        }
+    }
+
+    fn cache_key(client: &str, provider: &str, options: serde_json::Value, text: &str) -> String {
+        ResponseCache::key(client, provider, &options, None, &prompt(text))
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let opts = || {
+            serde_json::json!({
+                "base_url": "https://api.openai.com/v1",
+                "properties": { "model": "gpt-4o", "temperature": 0 },
+            })
+        };
+        let k = cache_key("GPT4", "openai", opts(), "hi");
+        assert_eq!(k.len(), 64);
+        assert_eq!(k, cache_key("GPT4", "openai", opts(), "hi"));
+        assert_ne!(k, cache_key("Claude", "openai", opts(), "hi"));
+        assert_ne!(k, cache_key("GPT4", "openai", opts(), "hello"));
+        assert_ne!(k, cache_key("GPT4", "azure-openai", opts(), "hi"));
+        assert_ne!(
+            k,
+            cache_key(
+                "GPT4",
+                "openai",
+                serde_json::json!({
+                    "base_url": "https://api.openai.com/v1",
+                    "properties": { "model": "gpt-4o" },
+                }),
+                "hi"
+            )
+        );
+        assert_ne!(
+            k,
+            cache_key(
+                "GPT4",
+                "openai",
+                serde_json::json!({
+                    "base_url": "http://localhost:8000/v1",
+                    "properties": { "model": "gpt-4o", "temperature": 0 },
+                }),
+                "hi"
+            )
+        );
+        // The order the options were resolved in doesn't matter.
+        assert_eq!(
+            k,
+            cache_key(
+                "GPT4",
+                "openai",
+                serde_json::json!({
+                    "properties": { "temperature": 0, "model": "gpt-4o" },
+                    "base_url": "https://api.openai.com/v1",
+                }),
+                "hi"
+            )
+        );
+
+        let schema =
+            |target| OutputSchema::new("Extract", &OutputFormatContent::target(target).build());
+        let strings = ResponseCache::key(
+            "GPT4",
+            "openai",
+            &opts(),
+            Some(&schema(FieldType::list(FieldType::string()))),
+            &prompt("hi"),
+        );
+        assert_ne!(k, strings);
+        assert_ne!(
+            strings,
+            ResponseCache::key(
+                "GPT4",
+                "openai",
+                &opts(),
+                Some(&schema(FieldType::list(FieldType::int()))),
+                &prompt("hi")
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache() {
+        let cache = ResponseCache::memory();
+        let opts = options(&[("model", serde_json::json!("gpt-4o"))]);
+        let key = cache_key("GPT4", "openai", serde_json::json!(opts), "hi");
+        assert!(cache
+            .get(&key, "GPT4", &opts, &prompt("hi"))
+            .await
+            .is_none());
+
+        cache.set(&key, &response("hello")).await;
+        let hit = cache.get(&key, "GPT4", &opts, &prompt("hi")).await.unwrap();
+        assert_eq!(hit.content, "hello");
+        assert_eq!(hit.model, "gpt-4o");
+        assert_eq!(hit.request_options, opts);
+        assert_eq!(hit.metadata.total_tokens, Some(8));
+        assert!(hit.metadata.cached);
+        assert_eq!(hit.latency, Duration::ZERO);
+
+        let other = cache_key("GPT4", "openai", serde_json::json!(opts), "hey");
+        assert!(cache
+            .get(&other, "GPT4", &opts, &prompt("hey"))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl() {
+        struct Shared(Arc<MemoryCache>);
+        impl ResponseCacheBackend for Shared {
+            fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>> {
+                self.0.get(key)
+            }
+            fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()> {
+                self.0.set(key, value)
+            }
+            fn remove<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+                self.0.remove(key)
+            }
+        }
+
+        let backend = Arc::new(MemoryCache::default());
+        let opts = HashMap::new();
+        let key = cache_key("GPT4", "openai", serde_json::json!(opts), "hi");
+        ResponseCache::new(Shared(backend.clone()))
+            .set(&key, &response("hello"))
+            .await;
+
+        let fresh =
+            ResponseCache::new(Shared(backend.clone())).with_ttl(Some(Duration::from_secs(60)));
+        assert!(fresh
+            .get(&key, "GPT4", &opts, &prompt("hi"))
+            .await
+            .is_some());
+
+        // Entries expire to the millisecond, not to the whole second.
+        {
+            let mut entries = backend.entries.lock().unwrap();
+            let value = entries.get_mut(&key).unwrap();
+            let mut entry: CacheEntry = serde_json::from_str(value).unwrap();
+            entry.created_at -= Duration::from_millis(1500);
+            *value = serde_json::to_string(&entry).unwrap();
+        }
+        let fresh =
+            ResponseCache::new(Shared(backend.clone())).with_ttl(Some(Duration::from_millis(1900)));
+        assert!(fresh
+            .get(&key, "GPT4", &opts, &prompt("hi"))
+            .await
+            .is_some());
+
+        let expired =
+            ResponseCache::new(Shared(backend.clone())).with_ttl(Some(Duration::from_millis(1100)));
+        assert!(expired
+            .get(&key, "GPT4", &opts, &prompt("hi"))
+            .await
+            .is_none());
+        // Reading an expired entry evicts it.
+        assert!(backend.entries.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_directory_cache() {
+        let dir = std::env::temp_dir().join(format!("baml-cache-{}", uuid::Uuid::new_v4()));
+        let opts = HashMap::new();
+        let key = cache_key("GPT4", "openai", serde_json::json!(opts), "hi");
+
+        ResponseCache::directory(&dir)
+            .set(&key, &response("hello"))
+            .await;
+
+        // A new cache over the same directory sees the entry.
+        let hit = ResponseCache::directory(&dir)
+            .get(&key, "GPT4", &opts, &prompt("hi"))
+            .await
+            .unwrap();
+        assert_eq!(hit.content, "hello");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
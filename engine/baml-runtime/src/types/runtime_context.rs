@@ -6,7 +6,9 @@ use serde;
 use serde_json;
 use std::{collections::HashMap, sync::Arc};
 
-use crate::internal::llm_client::llm_provider::LLMProvider;
+use crate::internal::llm_client::{llm_provider::LLMProvider, output_schema::OutputSchema};
+
+use super::{CancellationToken, ResponseCache};
 
 #[derive(Debug, Clone)]
 pub struct SpanCtx {
@@ -14,20 +16,20 @@ pub struct SpanCtx {
     pub name: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PropertyAttributes {
     pub(crate) alias: Option<BamlValue>,
     pub(crate) skip: Option<bool>,
     pub(crate) meta: IndexMap<String, BamlValue>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RuntimeEnumOverride {
     pub(crate) alias: Option<BamlValue>,
     pub(crate) values: IndexMap<String, PropertyAttributes>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RuntimeClassOverride {
     pub(crate) alias: Option<BamlValue>,
     pub(crate) new_fields: IndexMap<String, (FieldType, PropertyAttributes)>,
@@ -50,6 +52,7 @@ cfg_if::cfg_if!(
 );
 
 // #[derive(Debug)]
+#[derive(Clone)]
 pub struct RuntimeContext {
     // path to baml_src in the local filesystem
     pub baml_src: Arc<BamlSrcReader>,
@@ -58,6 +61,13 @@ pub struct RuntimeContext {
     pub client_overrides: Option<(Option<String>, HashMap<String, Arc<LLMProvider>>)>,
     pub class_override: IndexMap<String, RuntimeClassOverride>,
     pub enum_overrides: IndexMap<String, RuntimeEnumOverride>,
+    /// Return type of the function being called, for clients that send it to
+    /// the provider instead of (or in addition to) describing it in the prompt.
+    pub output_schema: Option<Arc<OutputSchema>>,
+    /// Stops the function call this context was created for.
+    pub cancellation: CancellationToken,
+    /// Where successful LLM responses are looked up and stored, if anywhere.
+    pub response_cache: Option<ResponseCache>,
 }
 
 impl RuntimeContext {
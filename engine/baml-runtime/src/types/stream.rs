@@ -12,7 +12,7 @@ use crate::{
     },
     tracing::BamlTracer,
     type_builder::TypeBuilder,
-    FunctionResult, RuntimeContextManager,
+    CancellationToken, FunctionResult, ResponseCache, RuntimeContext, RuntimeContextManager,
 };
 
 /// Wrapper that holds a stream of responses from a BAML function call.
@@ -27,6 +27,8 @@ pub struct FunctionResultStream {
     pub(crate) ir: Arc<IntermediateRepr>,
     pub(crate) orchestrator: OrchestratorNodeIterator,
     pub(crate) tracer: Arc<BamlTracer>,
+    pub(crate) cancellation: CancellationToken,
+    pub(crate) response_cache: Option<ResponseCache>,
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) tokio_runtime: Arc<tokio::runtime::Runtime>,
 }
@@ -55,6 +57,12 @@ first.scope.clone();
 */
 
 impl FunctionResultStream {
+    /// A token that stops this stream when cancelled, including from another
+    /// thread while `run` is in progress.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn run_sync<F>(
         &mut self,
@@ -94,13 +102,20 @@ impl FunctionResultStream {
         let rctx = ctx.create_ctx(tb, cb);
         let res = match rctx {
             Ok(rctx) => {
+                let rctx = RuntimeContext {
+                    output_schema: Some(Arc::new(self.renderer.output_schema())),
+                    cancellation: self.cancellation.clone(),
+                    response_cache: self.response_cache.clone(),
+                    ..rctx
+                };
+                let mut partial_parser = self.renderer.partial_parser();
                 let (history, _) = orchestrate_stream(
                     local_orchestrator,
                     self.ir.as_ref(),
                     &rctx,
                     &self.renderer,
                     &baml_types::BamlValue::Map(local_params),
-                    |content| self.renderer.parse(content, true),
+                    |content| partial_parser.parse(content),
                     |content| self.renderer.parse(content, false),
                     on_event,
                 )
@@ -6,7 +6,7 @@ mod internal_tests {
     use std::any;
     use std::collections::HashMap;
 
-    use baml_runtime::BamlRuntime;
+    use baml_runtime::{BamlRuntime, FunctionResult};
     use std::sync::Once;
 
     use baml_runtime::internal::llm_client::orchestrator::OrchestrationScope;
@@ -507,4 +507,525 @@ test TestTree {
 
         Ok(())
     }
+
+    #[test]
+    fn test_test_expectations() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(
+            r##"
+class Person {
+  name string
+  age int?
+}
+
+function ExtractPerson(input: string) -> Person {
+  client "openai/gpt-4o"
+  prompt #"
+    {{ input }}
+    {{ ctx.output_format }}
+  "#
+}
+
+test Bob {
+  functions [ExtractPerson]
+  args {
+    input "Bob is 42"
+  }
+  expected_partial {
+    name "Bob"
+  }
+  max_latency_ms 5000
+  @@assert({{ this.age > 18 }})
+  @@check(has_name, {{ this.name|length > 0 }})
+}
+        "##,
+        )?;
+
+        let missing_env_vars = runtime.internal().ir().required_env_vars();
+        let ctx = runtime
+            .create_ctx_manager(BamlValue::String("test".to_string()), None)
+            .create_ctx_with_default(missing_env_vars.iter());
+
+        let expectations = runtime
+            .internal()
+            .get_test_expectations("ExtractPerson", "Bob", &ctx)?;
+
+        assert_eq!(expectations.constraints.len(), 2);
+        assert_eq!(
+            expectations.constraints[1].label.as_deref(),
+            Some("has_name")
+        );
+        assert!(expectations.partial);
+        assert_eq!(
+            expectations.expected_output,
+            Some(BamlValue::Map(
+                [("name".to_string(), BamlValue::String("Bob".to_string()))]
+                    .into_iter()
+                    .collect()
+            ))
+        );
+        assert_eq!(expectations.max_latency_ms, Some(5000));
+        assert_eq!(expectations.max_total_tokens, None);
+
+        Ok(())
+    }
+
+    const EXTRACT_PERSON: &str = r##"
+class Person {
+  name string
+  age int @assert(adult, {{ this >= 18 }})
+}
+
+function ExtractPerson(input: string) -> Person {
+  client "openai/gpt-4o"
+  prompt #"
+    {{ input }}
+    {{ ctx.output_format }}
+  "#
+}
+"##;
+
+    #[test]
+    fn test_render_request() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(EXTRACT_PERSON)?;
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = [(
+            "input".to_string(),
+            BamlValue::String("Bob is 42".to_string()),
+        )]
+        .into_iter()
+        .collect();
+
+        let request =
+            runtime.render_request_sync("ExtractPerson", &params, &ctx, None, None, false)?;
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(
+            request.headers.get("authorization").map(String::as_str),
+            Some("Bearer OPENAI_API_KEY")
+        );
+        assert_eq!(request.body["model"], "gpt-4o");
+        assert!(request.body["messages"][0]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Bob is 42"));
+        assert!(request.body.get("stream").is_none());
+
+        let request =
+            runtime.render_request_sync("ExtractPerson", &params, &ctx, None, None, true)?;
+        assert_eq!(request.body["stream"], true);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_llm_response() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(EXTRACT_PERSON)?;
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+
+        let result = runtime.parse_llm_response(
+            "ExtractPerson",
+            r#"{"name": "Bob", "age": 42}"#.to_string(),
+            false,
+            &ctx,
+            None,
+            None,
+        )?;
+        let value = result.result_with_constraints_content()?;
+        assert_eq!(
+            BamlValue::from(value),
+            BamlValue::Class(
+                "Person".to_string(),
+                [
+                    ("name".to_string(), BamlValue::String("Bob".to_string())),
+                    ("age".to_string(), BamlValue::Int(42)),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+
+        // Asserts fail the same way they do for a call.
+        let result = runtime.parse_llm_response(
+            "ExtractPerson",
+            r#"{"name": "Bob", "age": 12}"#.to_string(),
+            false,
+            &ctx,
+            None,
+            None,
+        )?;
+        assert!(result.result_with_constraints_content().is_err());
+
+        Ok(())
+    }
+
+    /// Serves one canned OpenAI chat completion per request, in order, and
+    /// returns the base URL along with a count of the requests received.
+    /// Requests that ask to stream get the completion as a single chunk.
+    fn mock_openai_server(
+        completions: &[&str],
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let completions = completions
+            .iter()
+            .map(|content| content.to_string())
+            .collect::<Vec<_>>();
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for content in completions {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut request = vec![0; content_length];
+                reader.read_exact(&mut request).unwrap();
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+                let request: serde_json::Value = serde_json::from_slice(&request).unwrap();
+                let (content_type, body) = if request["stream"] == true {
+                    let chunk = serde_json::json!({
+                        "id": "mock",
+                        "object": "chat.completion.chunk",
+                        "created": 0,
+                        "model": "mock",
+                        "choices": [{
+                            "index": 0,
+                            "delta": { "role": "assistant", "content": content },
+                            "finish_reason": "stop",
+                        }],
+                    });
+                    (
+                        "text/event-stream",
+                        format!("data: {chunk}\n\ndata: [DONE]\n\n"),
+                    )
+                } else {
+                    let completion = serde_json::json!({
+                        "id": "mock",
+                        "object": "chat.completion",
+                        "created": 0,
+                        "model": "mock",
+                        "choices": [{
+                            "index": 0,
+                            "message": { "role": "assistant", "content": content },
+                            "finish_reason": "stop",
+                        }],
+                    });
+                    ("application/json", completion.to_string())
+                };
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn extract_person_params() -> baml_types::BamlMap<String, BamlValue> {
+        [(
+            "input".to_string(),
+            BamlValue::String("Bob is 42".to_string()),
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    fn call_extract_person_result(runtime: &BamlRuntime) -> anyhow::Result<FunctionResult> {
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let (result, _) = runtime.async_runtime.block_on(runtime.call_function(
+            "ExtractPerson".to_string(),
+            &extract_person_params(),
+            &ctx,
+            None,
+            None,
+            None,
+            false,
+        ));
+        result
+    }
+
+    fn call_extract_person(runtime: &BamlRuntime) -> anyhow::Result<BamlValue> {
+        let result = call_extract_person_result(runtime)?;
+        Ok(BamlValue::from(result.result_with_constraints_content()?))
+    }
+
+    fn stream_extract_person(runtime: &BamlRuntime) -> anyhow::Result<FunctionResult> {
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let mut stream = runtime.stream_function(
+            "ExtractPerson".to_string(),
+            &extract_person_params(),
+            &ctx,
+            None,
+            None,
+            false,
+        )?;
+        let (result, _) = runtime.async_runtime.block_on(stream.run(
+            None::<fn(FunctionResult)>,
+            &ctx,
+            None,
+            None,
+        ));
+        result
+    }
+
+    const VALID_PERSON: &str = r#"{"name": "Bob", "age": 42}"#;
+
+    /// `ExtractPerson` with a `Person` declared as `person`, called through a
+    /// client that retries once.
+    fn retrying_source(base_url: &str, person: &str, retry_on_parse_failure: bool) -> String {
+        format!(
+            r##"
+{person}
+
+retry_policy Retry {{
+  max_retries 1
+  retry_on_parse_failure {retry_on_parse_failure}
+  strategy {{
+    type constant_delay
+    delay_ms 0
+  }}
+}}
+
+client<llm> Mock {{
+  provider openai-generic
+  retry_policy Retry
+  options {{
+    base_url "{base_url}"
+    model "mock"
+  }}
+}}
+
+function ExtractPerson(input: string) -> Person {{
+  client Mock
+  prompt #"
+    {{{{ input }}}}
+    {{{{ ctx.output_format }}}}
+  "#
+}}
+"##
+        )
+    }
+
+    const PERSON: &str = r#"
+class Person {
+  name string
+  age int
+}
+"#;
+
+    #[test]
+    fn test_retry_on_parse_failure() -> anyhow::Result<()> {
+        let source = |base_url: &str, retry_on_parse_failure: bool| {
+            retrying_source(base_url, PERSON, retry_on_parse_failure)
+        };
+
+        // A completion that doesn't parse is retried.
+        let (base_url, requests) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&source(&base_url, true))?;
+        let person = call_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(matches!(person, BamlValue::Class(name, _) if name == "Person"));
+
+        // Without the option, the parse failure is final.
+        let (base_url, requests) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&source(&base_url, false))?;
+        assert!(call_extract_person(&runtime).is_err());
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The response that didn't parse isn't cached, so the retry reaches the
+        // LLM. The one that did parse is reused by the next call.
+        let (base_url, requests) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&source(&base_url, true))?;
+        runtime.set_response_cache(Some(baml_runtime::ResponseCache::memory()));
+        call_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let person = call_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(matches!(person, BamlValue::Class(name, _) if name == "Person"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_keeps_every_attempt() -> anyhow::Result<()> {
+        let (base_url, _) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&retrying_source(&base_url, PERSON, true))?;
+
+        let result = call_extract_person_result(&runtime)?;
+        let [(_, first, _, first_value), (_, second, _, second_value)] =
+            result.event_chain().as_slice()
+        else {
+            panic!("expected two attempts, got {}", result.event_chain().len());
+        };
+        assert!(matches!(first, LLMResponse::Success(s) if s.content == "I don't know"));
+        assert!(matches!(first_value, Some(Err(_))));
+        assert!(matches!(second, LLMResponse::Success(s) if s.content == VALID_PERSON));
+        assert!(matches!(second_value, Some(Ok(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_on_assert_failure() -> anyhow::Result<()> {
+        let person = r#"
+class Person {
+  name string
+  age int @assert(adult, {{ this >= 18 }})
+}
+"#;
+        let (base_url, requests) =
+            mock_openai_server(&[r#"{"name": "Bob", "age": 4}"#, VALID_PERSON]);
+        let runtime = make_test_runtime(&retrying_source(&base_url, person, true))?;
+
+        let person = call_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(matches!(person, BamlValue::Class(name, _) if name == "Person"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_retry_on_parse_failure() -> anyhow::Result<()> {
+        let (base_url, requests) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&retrying_source(&base_url, PERSON, true))?;
+        let result = stream_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(result.event_chain().len(), 2);
+        assert!(result.result_with_constraints_content().is_ok());
+
+        let (base_url, requests) = mock_openai_server(&["I don't know", VALID_PERSON]);
+        let runtime = make_test_runtime(&retrying_source(&base_url, PERSON, false))?;
+        let result = stream_extract_person(&runtime)?;
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(result.result_with_constraints_content().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_response_cache_counts_against_request_timeout() -> anyhow::Result<()> {
+        struct Hanging;
+        impl baml_runtime::ResponseCacheBackend for Hanging {
+            fn get<'a>(&'a self, _: &'a str) -> baml_runtime::CacheFuture<'a, Option<String>> {
+                Box::pin(futures::future::pending())
+            }
+            fn set<'a>(&'a self, _: &'a str, _: String) -> baml_runtime::CacheFuture<'a, ()> {
+                Box::pin(futures::future::pending())
+            }
+        }
+
+        let (base_url, requests) = mock_openai_server(&[VALID_PERSON]);
+        let runtime = make_test_runtime(&format!(
+            r##"
+class Person {{
+  name string
+  age int
+}}
+
+client<llm> Mock {{
+  provider openai-generic
+  options {{
+    base_url "{base_url}"
+    model "mock"
+    request_timeout_ms 100
+  }}
+}}
+
+function ExtractPerson(input: string) -> Person {{
+  client Mock
+  prompt #"
+    {{{{ input }}}}
+    {{{{ ctx.output_format }}}}
+  "#
+}}
+"##
+        ))?;
+        runtime.set_response_cache(Some(baml_runtime::ResponseCache::new(Hanging)));
+
+        // A lookup that never finishes times out like the request it stands
+        // in for, instead of holding up the call.
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || tx.send(call_extract_person(&runtime).is_err()));
+        assert!(rx.recv_timeout(std::time::Duration::from_secs(10))?);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fallback_on_parse_failure() -> anyhow::Result<()> {
+        let (first_url, first_requests) = mock_openai_server(&["I don't know"]);
+        let (second_url, second_requests) = mock_openai_server(&[VALID_PERSON]);
+        let runtime = make_test_runtime(&format!(
+            r##"
+class Person {{
+  name string
+  age int
+}}
+
+client<llm> First {{
+  provider openai-generic
+  options {{
+    base_url "{first_url}"
+    model "mock"
+  }}
+}}
+
+client<llm> Second {{
+  provider openai-generic
+  options {{
+    base_url "{second_url}"
+    model "mock"
+  }}
+}}
+
+client<llm> Mock {{
+  provider fallback
+  options {{
+    strategy [First, Second]
+    retry_on_parse_failure true
+  }}
+}}
+
+function ExtractPerson(input: string) -> Person {{
+  client Mock
+  prompt #"
+    {{{{ input }}}}
+    {{{{ ctx.output_format }}}}
+  "#
+}}
+"##
+        ))?;
+
+        let person = call_extract_person(&runtime)?;
+        assert_eq!(first_requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(second_requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(matches!(person, BamlValue::Class(name, _) if name == "Person"));
+
+        Ok(())
+    }
 }
@@ -16,6 +16,8 @@ use baml_types::{BamlMediaType, BamlValue, GeneratorOutputType, TypeValue};
 use indexmap::IndexMap;
 use internal_baml_codegen::version_check::GeneratorType;
 use internal_baml_codegen::version_check::{check_version, VersionCheckMode};
+use internal_baml_core::internal_baml_diagnostics::SourceFile;
+use internal_baml_core::internal_baml_schema_ast::format_schema;
 use jsonish::deserializer::deserialize_flags::Flag;
 use jsonish::BamlValueWithFlags;
 
@@ -219,6 +221,26 @@ impl WasmProject {
         }
     }
 
+    /// Formats a file, including its unsaved changes, as `baml-cli fmt` would.
+    /// Files with syntax errors are not formatted.
+    #[wasm_bindgen]
+    pub fn format_file(&self, name: &str) -> Result<String, JsValue> {
+        let content = self
+            .unsaved_files
+            .get(name)
+            .or_else(|| self.files.get(name))
+            .ok_or_else(|| JsValue::from_str(&format!("File not found: {}", name)))?;
+        let source = SourceFile::from((PathBuf::from(name), content.as_str()));
+
+        format_schema(&PathBuf::from(&self.root_dir_name), &source).map_err(|errors| {
+            WasmDiagnosticError {
+                errors,
+                all_files: vec![name.to_string()],
+            }
+            .into()
+        })
+    }
+
     #[wasm_bindgen]
     pub fn diagnostics(&self, rt: &WasmRuntime) -> WasmDiagnosticError {
         let mut hm = self.files.iter().collect::<HashMap<_, _>>();
@@ -420,6 +442,9 @@ pub enum TestStatus {
     LLMFailure,
     ParseFailure,
     UnableToRun,
+    /// The output parsed, but an assertion, expected value or budget on the
+    /// test failed.
+    ExpectationFailure,
 }
 
 #[wasm_bindgen(getter_with_clone, inspectable)]
@@ -584,6 +609,12 @@ impl WasmTestResponse {
                     baml_runtime::TestFailReason::TestUnspecified(_) => TestStatus::UnableToRun,
                     baml_runtime::TestFailReason::TestLLMFailure(_) => TestStatus::LLMFailure,
                     baml_runtime::TestFailReason::TestParseFailure(_) => TestStatus::ParseFailure,
+                    baml_runtime::TestFailReason::TestConstraintFailure { .. }
+                    | baml_runtime::TestFailReason::TestOutputMismatch(_)
+                    | baml_runtime::TestFailReason::TestLatencyBudgetExceeded { .. }
+                    | baml_runtime::TestFailReason::TestTokenBudgetExceeded { .. } => {
+                        TestStatus::ExpectationFailure
+                    }
                 },
             },
             Err(_) => TestStatus::UnableToRun,
@@ -759,6 +790,7 @@ impl WithRenderError for baml_runtime::TestFailReason<'_> {
             baml_runtime::TestFailReason::TestUnspecified(e) => Some(format!("{e:#}")),
             baml_runtime::TestFailReason::TestLLMFailure(f) => f.render_error(),
             baml_runtime::TestFailReason::TestParseFailure(e) => Some(format!("{e:#}")),
+            reason => Some(reason.to_string()),
         }
     }
 }
@@ -768,7 +800,7 @@ impl WithRenderError for baml_runtime::internal::llm_client::LLMResponse {
         match self {
             baml_runtime::internal::llm_client::LLMResponse::Success(_) => None,
             baml_runtime::internal::llm_client::LLMResponse::LLMFailure(f) => {
-                format!("{} {}", f.message, f.code.to_string()).into()
+                format!("{} {}", f.message, f.code).into()
             }
             baml_runtime::internal::llm_client::LLMResponse::UserFailure(e) => {
                 format!("user error: {}", e).into()
@@ -809,6 +841,12 @@ fn get_dummy_value(
                 TypeValue::Media(BamlMediaType::Audio) => {
                     "{ url \"https://actions.google.com/sounds/v1/emergency/beeper_emergency_call.ogg\"}".to_string()
                 }
+                TypeValue::Media(BamlMediaType::Pdf) => {
+                    "{ url \"https://www.w3.org/WAI/ER/tests/xhtml/testfiles/resources/pdf/dummy.pdf\"}".to_string()
+                }
+                TypeValue::Media(BamlMediaType::Video) => {
+                    "{ url \"https://storage.googleapis.com/cloud-samples-data/video/animals.mp4\"}".to_string()
+                }
             };
 
             Some(dummy)
@@ -816,6 +854,7 @@ fn get_dummy_value(
         baml_runtime::FieldType::Literal(_) => None,
         baml_runtime::FieldType::Enum(_) => None,
         baml_runtime::FieldType::Class(_) => None,
+        baml_runtime::FieldType::RecursiveTypeAlias(_) => None,
         baml_runtime::FieldType::List(item) => {
             let dummy = get_dummy_value(indent + 1, allow_multiline, item);
             // Repeat it 2 times
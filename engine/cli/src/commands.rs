@@ -21,12 +21,27 @@ pub(crate) enum Commands {
     #[command(about = "Runs all generators in the baml_src directory")]
     Generate(baml_runtime::cli::generate::GenerateArgs),
 
+    #[command(about = "Checks the baml_src directory for errors and warnings")]
+    Check(baml_runtime::cli::check::CheckArgs),
+
+    #[command(about = "Formats the .baml files in the baml_src directory")]
+    Fmt(baml_runtime::cli::fmt::FmtArgs),
+
     #[command(about = "Starts a server that translates LLM responses to BAML responses")]
     Serve(baml_runtime::cli::serve::ServeArgs),
 
+    #[command(about = "Runs the test blocks in the baml_src directory")]
+    Test(baml_runtime::cli::test::TestArgs),
+
+    #[command(about = "Replays LLM function calls captured in BAML trace files")]
+    Replay(baml_runtime::cli::replay::ReplayArgs),
+
     #[command(about = "Starts a development server")]
     Dev(baml_runtime::cli::dev::DevArgs),
 
+    #[command(about = "Starts a language server for editors, over stdio")]
+    Lsp(baml_runtime::cli::lsp::LspArgs),
+
     #[command(subcommand, about = "Authenticate with Boundary Cloud")]
     Auth(crate::auth::AuthCommands),
 
@@ -50,14 +65,34 @@ impl RuntimeCli {
                 args.run(defaults)
             }
             Commands::Init(args) => args.run(defaults),
+            Commands::Check(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
+            Commands::Fmt(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
             Commands::Serve(args) => {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 args.run()
             }
+            Commands::Test(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
+            Commands::Replay(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
             Commands::Dev(args) => {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 args.run(defaults)
             }
+            Commands::Lsp(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
             Commands::Auth(args) => t.block_on(async { args.run_async().await }),
             Commands::Login(args) => t.block_on(async { args.run_async().await }),
             Commands::Deploy(args) => {
@@ -3,15 +3,16 @@ use baml_types::{Constraint, ConstraintLevel, FieldType};
 use indexmap::IndexMap;
 use internal_baml_core::{
     configuration::{GeneratorDefaultClientMode, GeneratorOutputType},
-    ir::repr::IntermediateRepr,
+    ir::{repr::IntermediateRepr, ClassFieldWalker, ClassWalker, EnumValueWalker, EnumWalker},
 };
-use std::{collections::{BTreeMap, HashSet}, path::PathBuf};
+use std::{collections::{BTreeMap, HashMap, HashSet}, path::PathBuf};
 use version_check::{check_version, GeneratorType, VersionCheckMode};
 
 mod dir_writer;
 pub mod openapi;
 mod python;
 mod ruby;
+mod rust;
 mod typescript;
 pub mod version_check;
 
@@ -181,6 +182,7 @@ impl GenerateClient for GeneratorOutputType {
             GeneratorOutputType::OpenApi => openapi::generate(ir, gen),
             GeneratorOutputType::PythonPydantic => python::generate(ir, gen),
             GeneratorOutputType::RubySorbet => ruby::generate(ir, gen),
+            GeneratorOutputType::Rust => rust::generate(ir, gen),
             GeneratorOutputType::Typescript => typescript::generate(ir, gen),
         }?;
 
@@ -330,6 +332,42 @@ fn field_type_attributes<'a>(field_type: &FieldType) -> Option<TypeCheckAttribut
     }
 }
 
+/// A declaration with a `///` doc comment and/or a `@description`.
+trait Documented {
+    fn docstring(&self) -> Option<&str>;
+    fn description(&self) -> Option<String>;
+
+    /// The text of the doc comment emitted for the declaration: its `///` doc
+    /// comment followed by its `@description`, if any.
+    fn documentation(&self) -> Option<String> {
+        let parts = self
+            .docstring()
+            .map(str::to_string)
+            .into_iter()
+            .chain(self.description().map(|d| d.trim().to_string()).filter(|d| !d.is_empty()))
+            .collect::<Vec<_>>();
+        (!parts.is_empty()).then(|| parts.join("\n\n"))
+    }
+}
+
+macro_rules! impl_documented {
+    ($($walker:ident),*) => {
+        $(
+            impl Documented for $walker<'_> {
+                fn docstring(&self) -> Option<&str> {
+                    $walker::docstring(self)
+                }
+
+                fn description(&self) -> Option<String> {
+                    $walker::description(self, &HashMap::new()).ok().flatten()
+                }
+            }
+        )*
+    };
+}
+
+impl_documented!(ClassWalker, ClassFieldWalker, EnumWalker, EnumValueWalker);
+
 #[cfg(test)]
 mod tests {
     use internal_baml_core::ir::repr::make_test_ir;
@@ -10,7 +10,7 @@ use internal_baml_core::ir::{
 use serde::Serialize;
 use serde_json::json;
 
-use crate::{dir_writer::{FileCollector, LanguageFeatures, RemoveDirBehavior}, field_type_attributes, TypeCheckAttributes};
+use crate::{dir_writer::{FileCollector, LanguageFeatures, RemoveDirBehavior}, field_type_attributes, Documented, TypeCheckAttributes};
 
 #[derive(Default)]
 pub(super) struct OpenApiLanguageFeatures {}
@@ -183,6 +183,72 @@ impl Serialize for OpenApiSchema<'_> {
                             ],
                         }),
                     ),
+                    (
+                        "BamlPdf",
+                        json!({
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "title": "BamlPdfBase64",
+                                    "properties": {
+                                        "base64": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["base64"],
+                                },
+                                {
+                                    "type": "object",
+                                    "title": "BamlPdfUrl",
+                                    "properties": {
+                                        "url": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["url"],
+                                }
+                            ],
+                        }),
+                    ),
+                    (
+                        "BamlVideo",
+                        json!({
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "title": "BamlVideoBase64",
+                                    "properties": {
+                                        "base64": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["base64"],
+                                },
+                                {
+                                    "type": "object",
+                                    "title": "BamlVideoUrl",
+                                    "properties": {
+                                        "url": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["url"],
+                                }
+                            ],
+                        }),
+                    ),
                     (
                         "BamlOptions",
                         json!({
@@ -205,6 +271,121 @@ impl Serialize for OpenApiSchema<'_> {
                                         }
                                     },
                                     "required": ["clients"]
+                                },
+                                "type_builder": {
+                                    "$ref": "#/components/schemas/TypeBuilder"
+                                },
+                                "tags": {
+                                    "type": "object",
+                                    "nullable": false,
+                                    "additionalProperties": {
+                                        "type": "string"
+                                    }
+                                }
+                            }
+                        })
+                    ),
+                    (
+                        "TypeBuilder",
+                        json!({
+                            "type": "object",
+                            "nullable": false,
+                            "properties": {
+                                "classes": {
+                                    "type": "object",
+                                    "additionalProperties": {
+                                        "$ref": "#/components/schemas/DynamicClass"
+                                    }
+                                },
+                                "enums": {
+                                    "type": "object",
+                                    "additionalProperties": {
+                                        "$ref": "#/components/schemas/DynamicEnum"
+                                    }
+                                },
+                                "baml": {
+                                    "type": "string",
+                                    "nullable": false
+                                }
+                            }
+                        })
+                    ),
+                    (
+                        "DynamicClass",
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "alias": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "properties": {
+                                    "type": "object",
+                                    "additionalProperties": {
+                                        "$ref": "#/components/schemas/DynamicProperty"
+                                    }
+                                }
+                            }
+                        })
+                    ),
+                    (
+                        "DynamicProperty",
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "type": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "alias": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "description": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "skip": {
+                                    "type": "boolean",
+                                    "nullable": false
+                                }
+                            }
+                        })
+                    ),
+                    (
+                        "DynamicEnum",
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "alias": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "values": {
+                                    "type": "object",
+                                    "additionalProperties": {
+                                        "$ref": "#/components/schemas/DynamicEnumValue"
+                                    }
+                                }
+                            }
+                        })
+                    ),
+                    (
+                        "DynamicEnumValue",
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "alias": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "description": {
+                                    "type": "string",
+                                    "nullable": false
+                                },
+                                "skip": {
+                                    "type": "boolean",
+                                    "nullable": false
                                 }
                             }
                         })
@@ -255,6 +436,7 @@ impl Serialize for OpenApiSchema<'_> {
 
 struct OpenApiMethodDef<'ir> {
     function_name: &'ir str,
+    description: Option<&'ir str>,
     request_body: TypeSpecWithMeta,
     response: TypeSpecWithMeta,
 }
@@ -277,6 +459,10 @@ impl OpenApiMethodDef<'_> {
             }
         });
 
+        if let Some(description) = self.description {
+            as_json["description"] = description.into();
+        }
+
         as_json.as_object_mut().unwrap().extend(
             if tag == "call" {
                 json!({
@@ -339,6 +525,12 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for OpenApi
                 .into_iter()
                 .chain(ir.walk_enums().map(|e| Ok((e.name(), e.try_into()?))))
                 .chain(ir.walk_classes().map(|c| Ok((c.name(), c.try_into()?))))
+                .chain(
+                    ir.structural_recursive_alias_cycles()
+                        .iter()
+                        .flat_map(|cycle| cycle.iter())
+                        .map(|(name, target)| Ok((name.as_str(), target.to_type_spec(ir)?))),
+                )
                 .collect::<Result<_>>()?,
         })
     }
@@ -393,6 +585,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: true,
                 },
                 type_spec: TypeSpec::Ref {
@@ -402,6 +595,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
         );
         Ok(Self {
             function_name,
+            description: value.docstring(),
             request_body: TypeSpecWithMeta {
                 meta: TypeMetadata {
                     // We _deliberately_ set this, even though OpenAPI doesn't require it,
@@ -416,6 +610,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     title: Some(format!("{}Request", function_name)),
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Class {
@@ -449,6 +644,19 @@ impl<'ir> TryFrom<EnumWalker<'ir>> for TypeSpecWithMeta {
     type Error = anyhow::Error;
 
     fn try_from(e: EnumWalker<'ir>) -> Result<Self> {
+        // JSON schema can't describe single enum values, so they are listed in
+        // the description of the enum.
+        let value_descriptions = e
+            .walk_values()
+            .filter_map(|v| {
+                v.documentation().map(|d| format!("- `{}`: {}", v.item.elem.0, d))
+            })
+            .collect::<Vec<_>>();
+        let description =
+            e.documentation()
+                .into_iter()
+                .chain((!value_descriptions.is_empty()).then(|| value_descriptions.join("\n")))
+                .reduce(|a, b| format!("{a}\n\n{b}"));
         Ok(TypeSpecWithMeta {
             meta: TypeMetadata {
                 title: None,
@@ -461,6 +669,7 @@ impl<'ir> TryFrom<EnumWalker<'ir>> for TypeSpecWithMeta {
                         .collect(),
                 ),
                 r#const: None,
+                description,
                 nullable: false,
             },
             type_spec: TypeSpec::Inline(TypeDef::String),
@@ -477,24 +686,21 @@ impl<'ir> TryFrom<ClassWalker<'ir>> for TypeSpecWithMeta {
                 title: None,
                 r#enum: None,
                 r#const: None,
+                description: c.documentation(),
                 nullable: false,
             },
             type_spec: TypeSpec::Inline(TypeDef::Class {
                 properties: c
-                    .item
-                    .elem
-                    // TODO: should go through walk_fields()
-                    .static_fields
-                    .iter()
+                    .walk_fields()
                     .map(|f| {
-                        Ok((
-                            f.elem.name.to_string(),
-                            f.elem.r#type.elem.to_type_spec(&c.db).context(format!(
-                                "Failed to convert {}.{} to OpenAPI type",
-                                c.name(),
-                                f.elem.name
-                            ))?,
-                        ))
+                        let field = f.elem();
+                        let mut type_spec = field.r#type.elem.to_type_spec(&c.db).context(format!(
+                            "Failed to convert {}.{} to OpenAPI type",
+                            c.name(),
+                            field.name
+                        ))?;
+                        type_spec.meta.description = f.documentation();
+                        Ok((field.name.to_string(), type_spec))
                     })
                     .collect::<Result<_>>()?,
                 required: c
@@ -523,11 +729,14 @@ trait ToTypeReferenceInTypeDefinition<'ir> {
 impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
     fn to_type_spec(&self, ir: &'ir IntermediateRepr) -> Result<TypeSpecWithMeta> {
         Ok(match self {
-            FieldType::Enum(name) | FieldType::Class(name) => TypeSpecWithMeta {
+            FieldType::Enum(name)
+            | FieldType::Class(name)
+            | FieldType::RecursiveTypeAlias(name) => TypeSpecWithMeta {
                 meta: TypeMetadata {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Ref {
@@ -539,6 +748,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: match v {
@@ -552,6 +762,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Array {
@@ -567,6 +778,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         title: None,
                         r#enum: None,
                         r#const: None,
+                        description: None,
                         nullable: false,
                     },
                     type_spec: TypeSpec::Inline(TypeDef::Map {
@@ -579,6 +791,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: match inner {
@@ -597,6 +810,12 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     TypeValue::Media(BamlMediaType::Image) => TypeSpec::Ref {
                         r#ref: format!("#/components/schemas/BamlImage"),
                     },
+                    TypeValue::Media(BamlMediaType::Pdf) => TypeSpec::Ref {
+                        r#ref: format!("#/components/schemas/BamlPdf"),
+                    },
+                    TypeValue::Media(BamlMediaType::Video) => TypeSpec::Ref {
+                        r#ref: format!("#/components/schemas/BamlVideo"),
+                    },
                 },
             },
             FieldType::Union(union) => {
@@ -617,6 +836,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         title: None,
                         r#enum: None,
                         r#const: None,
+                        description: None,
                         nullable: false,
                     },
                     type_spec: TypeSpec::Union { one_of },
@@ -680,7 +900,11 @@ struct TypeMetadata {
     /// We only allow string-shaped const values
     #[serde(skip_serializing_if = "Option::is_none")]
     r#const: Option<String>,
-    // description: Option<String>,
+
+    /// Doc comment and `@description` of the type or property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+
     /// Nulls in OpenAPI are weird: https://swagger.io/docs/specification/data-models/data-types/
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     nullable: bool,
@@ -692,6 +916,7 @@ impl Default for TypeMetadata {
             title: None,
             r#enum: None,
             r#const: None,
+            description: None,
             nullable: false,
         }
     }
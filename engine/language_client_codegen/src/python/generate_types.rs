@@ -1,11 +1,11 @@
 use anyhow::Result;
-use baml_types::LiteralValue;
+use baml_types::{LiteralValue, StreamingBehavior};
 use itertools::Itertools;
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
-use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
+use crate::{field_type_attributes, type_check_attributes, Documented, TypeCheckAttributes};
 
-use super::python_language_features::ToPython;
+use super::python_language_features::{to_python_docstring, ToPython};
 use internal_baml_core::ir::{
     repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType, IRHelper,
 };
@@ -15,6 +15,7 @@ use internal_baml_core::ir::{
 pub(crate) struct PythonTypes<'ir> {
     enums: Vec<PythonEnum<'ir>>,
     classes: Vec<PythonClass<'ir>>,
+    type_aliases: Vec<PythonTypeAlias<'ir>>,
 }
 
 #[derive(askama::Template)]
@@ -27,15 +28,23 @@ pub(crate) struct TypeBuilder<'ir> {
 
 struct PythonEnum<'ir> {
     name: &'ir str,
-    values: Vec<&'ir str>,
+    // the name, and the docstring of the value
+    values: Vec<(&'ir str, Option<String>)>,
     dynamic: bool,
+    docstring: Option<String>,
+}
+
+struct PythonTypeAlias<'ir> {
+    name: &'ir str,
+    target: String,
 }
 
 struct PythonClass<'ir> {
     name: Cow<'ir, str>,
-    // the name, and the type of the field
-    fields: Vec<(Cow<'ir, str>, String)>,
+    // the name, the type (and default) and the docstring of the field
+    fields: Vec<(Cow<'ir, str>, String, Option<String>)>,
     dynamic: bool,
+    docstring: Option<String>,
 }
 
 #[derive(askama::Template)]
@@ -61,6 +70,15 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for PythonT
         Ok(PythonTypes {
             enums: ir.walk_enums().map(PythonEnum::from).collect::<Vec<_>>(),
             classes: ir.walk_classes().map(PythonClass::from).collect::<Vec<_>>(),
+            type_aliases: ir
+                .structural_recursive_alias_cycles()
+                .iter()
+                .flat_map(|cycle| cycle.iter())
+                .map(|(name, target)| PythonTypeAlias {
+                    name,
+                    target: target.to_type_ref(ir),
+                })
+                .collect::<Vec<_>>(),
         })
     }
 }
@@ -89,12 +107,16 @@ impl<'ir> From<EnumWalker<'ir>> for PythonEnum<'ir> {
             name: e.name(),
             dynamic: e.item.attributes.get("dynamic_type").is_some(),
             values: e
-                .item
-                .elem
-                .values
-                .iter()
-                .map(|v| v.elem.0.as_str())
+                .walk_values()
+                .map(|v| {
+                    let docstring = v.documentation();
+                    (
+                        v.item.elem.0.as_str(),
+                        docstring.map(|d| to_python_docstring(&d, "    ")),
+                    )
+                })
                 .collect(),
+            docstring: e.documentation().map(|d| to_python_docstring(&d, "    ")),
         }
     }
 }
@@ -105,20 +127,41 @@ impl<'ir> From<ClassWalker<'ir>> for PythonClass<'ir> {
             name: Cow::Borrowed(c.name()),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
-                .item
-                .elem
-                .static_fields
-                .iter()
+                .walk_fields()
                 .map(|f| {
+                    let field_type = &f.item.elem.r#type.elem;
+                    let type_ref = field_type.to_type_ref(&c.db);
+                    // A `Checked[...]` field can't take a plain value as its default.
+                    let default_value = f
+                        .default_value()
+                        .and_then(Result::ok)
+                        .filter(|_| !c.db.type_has_checks(field_type));
+                    let default_value = match default_value {
+                        Some(v) => Some(v.to_python()),
+                        None if type_ref.starts_with("Optional[") => Some("None".to_string()),
+                        None => None,
+                    };
+                    let value = match f.description(&HashMap::new()).ok().flatten() {
+                        Some(description) => Some(format!(
+                            "pydantic.Field({}description={})",
+                            default_value
+                                .map(|v| format!("default={v}, "))
+                                .unwrap_or_default(),
+                            serde_json::to_string(description.trim()).unwrap_or_default()
+                        )),
+                        None => default_value,
+                    };
                     (
-                        Cow::Borrowed(f.elem.name.as_str()),
-                        add_default_value(
-                            &f.elem.r#type.elem,
-                            &f.elem.r#type.elem.to_type_ref(&c.db),
-                        ),
+                        Cow::Borrowed(f.item.elem.name.as_str()),
+                        match value {
+                            Some(v) => format!("{type_ref} = {v}"),
+                            None => type_ref,
+                        },
+                        f.docstring().map(|d| to_python_docstring(d, "    ")),
                     )
                 })
                 .collect(),
+            docstring: c.documentation().map(|d| to_python_docstring(&d, "    ")),
         }
     }
 }
@@ -151,7 +194,11 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
                         f.elem.name.as_str(),
                         add_default_value(
                             &f.elem.r#type.elem,
-                            &f.elem.r#type.elem.to_partial_type_ref(&c.db, false),
+                            &to_partial_field_type_ref(
+                                &f.elem.r#type.elem,
+                                f.attributes.streaming(),
+                                &c.db,
+                            ),
                         ),
                     )
                 })
@@ -160,6 +207,32 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
     }
 }
 
+/// The type of a field in a partial class, taking its `@stream.*` attributes
+/// into account.
+fn to_partial_field_type_ref(
+    field_type: &FieldType,
+    streaming: StreamingBehavior,
+    ir: &IntermediateRepr,
+) -> String {
+    let mut type_ref = if streaming.done {
+        format!("Optional[{}]", field_type.to_type_ref_in(ir, "types."))
+    } else {
+        field_type.to_partial_type_ref(ir, false)
+    };
+    if streaming.not_null {
+        if let Some(inner) = type_ref
+            .strip_prefix("Optional[")
+            .and_then(|t| t.strip_suffix(']'))
+        {
+            type_ref = inner.to_string();
+        }
+    }
+    if streaming.with_state {
+        type_ref = format!("StreamState[{type_ref}]");
+    }
+    type_ref
+}
+
 pub fn add_default_value(node: &FieldType, type_str: &String) -> String {
     if type_str.starts_with("Optional[") {
         return format!("{} = None", type_str);
@@ -185,9 +258,10 @@ fn type_def_for_checks(checks: TypeCheckAttributes) -> PythonClass<'static> {
         fields: checks
             .0
             .into_iter()
-            .map(|check_name| (Cow::Owned(check_name), "Check".to_string()))
+            .map(|check_name| (Cow::Owned(check_name), "Check".to_string(), None))
             .collect(),
         dynamic: false,
+        docstring: None,
     }
 }
 
@@ -208,12 +282,18 @@ pub fn to_python_literal(literal: &LiteralValue) -> String {
 }
 
 trait ToTypeReferenceInTypeDefinition {
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String;
+    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+        self.to_type_ref_in(ir, "")
+    }
+    /// Like `to_type_ref`, with every type defined in BAML referenced through
+    /// `module` (e.g. `"types."` to reference the final types from
+    /// `partial_types.py`).
+    fn to_type_ref_in(&self, ir: &IntermediateRepr, module: &str) -> String;
     fn to_partial_type_ref(&self, ir: &IntermediateRepr, wrapped: bool) -> String;
 }
 
 impl ToTypeReferenceInTypeDefinition for FieldType {
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+    fn to_type_ref_in(&self, ir: &IntermediateRepr, module: &str) -> String {
         match self {
             FieldType::Enum(name) => {
                 if ir
@@ -221,23 +301,28 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                     .map(|e| e.item.attributes.get("dynamic_type").is_some())
                     .unwrap_or(false)
                 {
-                    format!("Union[\"{name}\", str]")
+                    format!("Union[\"{module}{name}\", str]")
                 } else {
-                    format!("\"{name}\"")
+                    format!("\"{module}{name}\"")
                 }
             }
             FieldType::Literal(value) => to_python_literal(value),
-            FieldType::Class(name) => format!("\"{name}\""),
-            FieldType::List(inner) => format!("List[{}]", inner.to_type_ref(ir)),
+            FieldType::Class(name) => format!("\"{module}{name}\""),
+            FieldType::RecursiveTypeAlias(name) => format!("\"{module}{name}\""),
+            FieldType::List(inner) => format!("List[{}]", inner.to_type_ref_in(ir, module)),
             FieldType::Map(key, value) => {
-                format!("Dict[{}, {}]", key.to_type_ref(ir), value.to_type_ref(ir))
+                format!(
+                    "Dict[{}, {}]",
+                    key.to_type_ref_in(ir, module),
+                    value.to_type_ref_in(ir, module)
+                )
             }
             FieldType::Primitive(r#type) => r#type.to_python(),
             FieldType::Union(inner) => format!(
                 "Union[{}]",
                 inner
                     .iter()
-                    .map(|t| t.to_type_ref(ir))
+                    .map(|t| t.to_type_ref_in(ir, module))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
@@ -245,24 +330,37 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                 "Tuple[{}]",
                 inner
                     .iter()
-                    .map(|t| t.to_type_ref(ir))
+                    .map(|t| t.to_type_ref_in(ir, module))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            FieldType::Optional(inner) => format!("Optional[{}]", inner.to_type_ref(ir)),
+            FieldType::Optional(inner) => format!("Optional[{}]", inner.to_type_ref_in(ir, module)),
             FieldType::Constrained { base, .. } => match field_type_attributes(self) {
                 Some(checks) => {
-                    let base_type_ref = base.to_type_ref(ir);
+                    let base_type_ref = base.to_type_ref_in(ir, module);
                     let checks_type_ref = type_name_for_checks(&checks);
                     format!("Checked[{base_type_ref},{checks_type_ref}]")
                 }
-                None => base.to_type_ref(ir),
+                None => base.to_type_ref_in(ir, module),
             },
         }
     }
 
     fn to_partial_type_ref(&self, ir: &IntermediateRepr, wrapped: bool) -> String {
         match self {
+            // `@@stream.done` classes only show up once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.streaming_behavior().done)
+                    .unwrap_or(false) =>
+            {
+                if wrapped {
+                    format!("types.{name}")
+                } else {
+                    format!("Optional[types.{name}]")
+                }
+            }
             FieldType::Class(name) => {
                 if wrapped {
                     format!("\"{name}\"")
@@ -281,6 +379,7 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                     format!("Optional[types.{name}]")
                 }
             }
+            FieldType::RecursiveTypeAlias(name) => format!("Optional[types.{name}]"),
             FieldType::Literal(value) => to_python_literal(value),
             FieldType::List(inner) => format!("List[{}]", inner.to_partial_type_ref(ir, true)),
             FieldType::Map(key, value) => {
@@ -11,7 +11,7 @@ use internal_baml_core::{
     ir::{repr::IntermediateRepr, FieldType, IRHelper},
 };
 
-use self::python_language_features::{PythonLanguageFeatures, ToPython};
+use self::python_language_features::{to_python_docstring, PythonLanguageFeatures, ToPython};
 use crate::{dir_writer::FileCollector, field_type_attributes};
 
 #[derive(askama::Template)]
@@ -47,6 +47,7 @@ struct PythonFunction {
     partial_return_type: String,
     return_type: String,
     args: Vec<(String, String)>,
+    docstring: Option<String>,
 }
 
 #[derive(askama::Template)]
@@ -167,6 +168,7 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for PythonClient
                                     (name.to_string(), r#type.to_type_ref(ir, false))
                                 })
                                 .collect(),
+                            docstring: f.docstring().map(|d| to_python_docstring(d, "      ")),
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
@@ -202,6 +204,7 @@ impl ToTypeReferenceInClientDefinition for FieldType {
             }
             FieldType::Literal(value) => to_python_literal(value),
             FieldType::Class(name) => format!("types.{name}"),
+            FieldType::RecursiveTypeAlias(name) => format!("types.{name}"),
             FieldType::List(inner) => format!("List[{}]", inner.to_type_ref(ir, with_checked)),
             FieldType::Map(key, value) => {
                 format!(
@@ -255,6 +258,7 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                 }
             }
             FieldType::Class(name) => format!("partial_types.{name}"),
+            FieldType::RecursiveTypeAlias(name) => format!("Optional[types.{name}]"),
             FieldType::Literal(value) => to_python_literal(value),
             FieldType::List(inner) => {
                 format!("List[{}]", inner.to_partial_type_ref(ir, with_checked))
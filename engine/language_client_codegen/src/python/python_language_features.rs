@@ -1,5 +1,6 @@
 use crate::dir_writer::LanguageFeatures;
-use baml_types::{BamlMediaType, TypeValue};
+use baml_types::{BamlMediaType, BamlValue, TypeValue};
+use itertools::Itertools;
 
 #[derive(Default)]
 pub(super) struct PythonLanguageFeatures {}
@@ -38,7 +39,59 @@ impl ToPython for TypeValue {
             TypeValue::Null => "None",
             TypeValue::Media(BamlMediaType::Image) => "baml_py.Image",
             TypeValue::Media(BamlMediaType::Audio) => "baml_py.Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "baml_py.Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "baml_py.Video",
         }
         .to_string()
     }
 }
+
+/// Python literals, for `@default(...)` values. Enum values refer to the
+/// enum classes in `types.py`.
+impl ToPython for BamlValue {
+    fn to_python(&self) -> String {
+        match self {
+            BamlValue::String(s) => serde_json::to_string(s).unwrap_or_default(),
+            BamlValue::Int(i) => i.to_string(),
+            BamlValue::Float(f) => format!("{f:?}"),
+            BamlValue::Bool(true) => "True".to_string(),
+            BamlValue::Bool(false) => "False".to_string(),
+            BamlValue::Null => "None".to_string(),
+            BamlValue::List(items) => {
+                format!("[{}]", items.iter().map(ToPython::to_python).join(", "))
+            }
+            BamlValue::Map(entries) | BamlValue::Class(_, entries) => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(k, v)| format!(
+                        "{}: {}",
+                        serde_json::to_string(k).unwrap_or_default(),
+                        v.to_python()
+                    ))
+                    .join(", ")
+            ),
+            BamlValue::Enum(name, value) => format!("{name}.{value}"),
+            BamlValue::Media(_) => "None".to_string(),
+        }
+    }
+}
+
+/// A Python docstring holding `text`, with its continuation lines indented by
+/// `indent`.
+pub(super) fn to_python_docstring(text: &str, indent: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    let text = match text.strip_suffix('"') {
+        Some(rest) => format!("{rest}\\\""),
+        None => text,
+    };
+    let body = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match line.trim_end() {
+            line if i == 0 || line.is_empty() => line.to_string(),
+            line => format!("{indent}{line}"),
+        })
+        .join("\n");
+    format!("\"\"\"{body}\"\"\"")
+}
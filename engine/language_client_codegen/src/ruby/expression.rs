@@ -1,4 +1,4 @@
-use baml_types::{BamlMediaType, TypeValue};
+use baml_types::{BamlMediaType, BamlValue, TypeValue};
 use internal_baml_core::ir::{Expression, Identifier};
 
 use super::ruby_language_features::ToRuby;
@@ -41,6 +41,41 @@ impl ToRuby for Expression {
     }
 }
 
+impl ToRuby for BamlValue {
+    fn to_ruby(&self) -> String {
+        match self {
+            BamlValue::String(s) => {
+                format!("\"{}\"", s.escape_default().to_string().replace('#', "\\#"))
+            }
+            BamlValue::Int(i) => i.to_string(),
+            BamlValue::Float(f) => format!("{f:?}"),
+            BamlValue::Bool(b) => b.to_string(),
+            BamlValue::Null | BamlValue::Media(_) => "nil".to_string(),
+            BamlValue::List(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| v.to_ruby())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            BamlValue::Map(entries) | BamlValue::Class(_, entries) => format!(
+                "{{ {} }}",
+                entries
+                    .iter()
+                    .map(|(k, v)| format!(
+                        "{} => {}",
+                        BamlValue::String(k.clone()).to_ruby(),
+                        v.to_ruby()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            BamlValue::Enum(name, value) => format!("Baml::Types::{name}::{value}"),
+        }
+    }
+}
+
 impl ToRuby for TypeValue {
     fn to_ruby(&self) -> String {
         match self {
@@ -51,6 +86,8 @@ impl ToRuby for TypeValue {
             TypeValue::Null => "null",
             TypeValue::Media(BamlMediaType::Image) => "Image",
             TypeValue::Media(BamlMediaType::Audio) => "Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "Video",
         }
         .to_string()
     }
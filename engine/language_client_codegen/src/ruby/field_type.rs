@@ -10,6 +10,8 @@ impl ToRuby for FieldType {
         match self {
             FieldType::Class(name) => format!("Baml::Types::{}", name.clone()),
             FieldType::Enum(name) => format!("T.any(Baml::Types::{}, String)", name.clone()),
+            // Sorbet has no recursive type aliases.
+            FieldType::RecursiveTypeAlias(_) => "T.anything".to_string(),
             // TODO: Temporary solution until we figure out Ruby literals.
             FieldType::Literal(value) => value.literal_base_type().to_ruby(),
             // https://sorbet.org/docs/stdlib-generics
@@ -27,6 +29,8 @@ impl ToRuby for FieldType {
                 // TODO: Create Baml::Types::Image
                 TypeValue::Media(BamlMediaType::Image) => "Baml::Image",
                 TypeValue::Media(BamlMediaType::Audio) => "Baml::Audio",
+                TypeValue::Media(BamlMediaType::Pdf) => "Baml::Pdf",
+                TypeValue::Media(BamlMediaType::Video) => "Baml::Video",
             }
             .to_string(),
             FieldType::Union(inner) => format!(
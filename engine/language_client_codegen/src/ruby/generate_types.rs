@@ -4,10 +4,13 @@ use std::collections::HashSet;
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
+use crate::{field_type_attributes, type_check_attributes, Documented, TypeCheckAttributes};
 
-use super::ruby_language_features::ToRuby;
-use internal_baml_core::ir::{repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType};
+use super::ruby_language_features::{to_ruby_comment, ToRuby};
+use baml_types::StreamingBehavior;
+use internal_baml_core::ir::{
+    repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType, IRHelper,
+};
 
 #[derive(askama::Template)]
 #[template(path = "types.rb.j2", escape = "none")]
@@ -18,14 +21,18 @@ pub(crate) struct RubyTypes<'ir> {
 
 struct RubyEnum<'ir> {
     pub name: &'ir str,
-    pub values: Vec<&'ir str>,
+    /// Name and comment of each value.
+    pub values: Vec<(&'ir str, Option<String>)>,
     dynamic: bool,
+    comment: Option<String>,
 }
 
 struct RubyStruct<'ir> {
     name: Cow<'ir, str>,
-    fields: Vec<(Cow<'ir, str>, String)>,
+    /// Name, type, `@default` value and comment of each field.
+    fields: Vec<(Cow<'ir, str>, String, Option<String>, Option<String>)>,
     dynamic: bool,
+    comment: Option<String>,
 }
 
 #[derive(askama::Template)]
@@ -65,12 +72,16 @@ impl<'ir> From<EnumWalker<'ir>> for RubyEnum<'ir> {
             name: e.name(),
             dynamic: e.item.attributes.get("dynamic_type").is_some(),
             values: e
-                .item
-                .elem
-                .values
-                .iter()
-                .map(|v| v.elem.0.as_str())
+                .walk_values()
+                .map(|v| {
+                    let docs = v.documentation();
+                    (
+                        v.item.elem.0.as_str(),
+                        docs.map(|d| to_ruby_comment(&d, "        ")),
+                    )
+                })
                 .collect(),
+            comment: e.documentation().map(|d| to_ruby_comment(&d, "    ")),
         }
     }
 }
@@ -81,16 +92,34 @@ impl<'ir> From<ClassWalker<'ir>> for RubyStruct<'ir> {
             name: Cow::Borrowed(c.name()),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
-                .item
-                .elem
-                .static_fields
-                .iter()
-                .map(|f| (Cow::Borrowed(f.elem.name.as_str()), f.elem.r#type.elem.to_type_ref()))
+                .walk_fields()
+                .map(|f| {
+                    let docs = f.documentation();
+                    (
+                        Cow::Borrowed(f.item.elem.name.as_str()),
+                        f.item.elem.r#type.elem.to_type_ref(),
+                        f.default_value().and_then(Result::ok).map(|v| v.to_ruby()),
+                        docs.map(|d| to_ruby_comment(&d, "      ")),
+                    )
+                })
                 .collect(),
+            comment: c.documentation().map(|d| to_ruby_comment(&d, "    ")),
         }
     }
 }
 
+impl RubyStruct<'_> {
+    /// Fields with a default value are only passed to the constructor when
+    /// set, so that sorbet falls back to the default.
+    fn defaulted_fields(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(_, _, default, _)| default.is_some())
+            .map(|(name, _, _, _)| name.as_ref())
+            .collect()
+    }
+}
+
 impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for RubyStreamTypes<'ir> {
     type Error = anyhow::Error;
 
@@ -113,7 +142,11 @@ impl<'ir> From<ClassWalker<'ir>> for PartialRubyStruct<'ir> {
                 .map(|f| {
                     (
                         f.elem.name.as_str(),
-                        f.elem.r#type.elem.to_partial_type_ref(),
+                        to_partial_field_type_ref(
+                            &f.elem.r#type.elem,
+                            f.attributes.streaming(),
+                            &c.db,
+                        ),
                     )
                 })
                 .collect(),
@@ -121,9 +154,38 @@ impl<'ir> From<ClassWalker<'ir>> for PartialRubyStruct<'ir> {
     }
 }
 
+/// The type of a field in a partial struct, taking its `@stream.*` attributes
+/// into account.
+fn to_partial_field_type_ref(
+    field_type: &FieldType,
+    streaming: StreamingBehavior,
+    ir: &IntermediateRepr,
+) -> String {
+    let mut type_ref = if streaming.done {
+        match field_type {
+            FieldType::Optional(_) => field_type.to_type_ref(),
+            _ => format!("T.nilable({})", field_type.to_type_ref()),
+        }
+    } else {
+        field_type.to_partial_type_ref(ir)
+    };
+    if streaming.not_null {
+        if let Some(inner) = type_ref
+            .strip_prefix("T.nilable(")
+            .and_then(|t| t.strip_suffix(')'))
+        {
+            type_ref = inner.to_string();
+        }
+    }
+    if streaming.with_state {
+        type_ref = format!("Baml::StreamState[{type_ref}]");
+    }
+    type_ref
+}
+
 pub(super) trait ToTypeReferenceInTypeDefinition {
     fn to_type_ref(&self) -> String;
-    fn to_partial_type_ref(&self) -> String;
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String;
 }
 
 impl ToTypeReferenceInTypeDefinition for FieldType {
@@ -132,19 +194,29 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
         self.to_ruby()
     }
 
-    fn to_partial_type_ref(&self) -> String {
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String {
         match self {
+            // `@@stream.done` classes only show up once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.streaming_behavior().done)
+                    .unwrap_or(false) =>
+            {
+                format!("Baml::Types::{}", name.clone())
+            }
             FieldType::Class(name) => format!("Baml::PartialTypes::{}", name.clone()),
             FieldType::Enum(name) => format!("T.nilable(Baml::Types::{})", name.clone()),
+            FieldType::RecursiveTypeAlias(_) => "T.anything".to_string(),
             // TODO: Temporary solution until we figure out Ruby literals.
-            FieldType::Literal(value) => value.literal_base_type().to_partial_type_ref(),
+            FieldType::Literal(value) => value.literal_base_type().to_partial_type_ref(ir),
             // https://sorbet.org/docs/stdlib-generics
-            FieldType::List(inner) => format!("T::Array[{}]", inner.to_partial_type_ref()),
+            FieldType::List(inner) => format!("T::Array[{}]", inner.to_partial_type_ref(ir)),
             FieldType::Map(key, value) => {
                 format!(
                     "T::Hash[{}, {}]",
                     key.to_type_ref(),
-                    value.to_partial_type_ref()
+                    value.to_partial_type_ref(ir)
                 )
             }
             FieldType::Primitive(_) => format!("T.nilable({})", self.to_type_ref()),
@@ -153,7 +225,7 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                 "T.nilable(T.any({}))",
                 inner
                     .iter()
-                    .map(|t| t.to_partial_type_ref())
+                    .map(|t| t.to_partial_type_ref(ir))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
@@ -162,19 +234,19 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                 "T.nilable([{}])",
                 inner
                     .iter()
-                    .map(|t| t.to_partial_type_ref())
+                    .map(|t| t.to_partial_type_ref(ir))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            FieldType::Optional(inner) => inner.to_partial_type_ref(),
+            FieldType::Optional(inner) => inner.to_partial_type_ref(ir),
             FieldType::Constrained{base,..} => {
                 match field_type_attributes(self) {
                     Some(checks) => {
-                        let base_type_ref = base.to_partial_type_ref();
+                        let base_type_ref = base.to_partial_type_ref(ir);
                         format!("Baml::Checked[{base_type_ref}]")
                     }
                     None => {
-                        base.to_partial_type_ref()
+                        base.to_partial_type_ref(ir)
                     }
                 }
             },
@@ -7,7 +7,7 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 use indexmap::IndexMap;
-use ruby_language_features::ToRuby;
+use ruby_language_features::{to_ruby_comment, ToRuby};
 
 
 use internal_baml_core::ir::repr::IntermediateRepr;
@@ -27,6 +27,7 @@ struct RubyFunction {
     partial_return_type: String,
     return_type: String,
     args: Vec<(String, String)>,
+    comment: Option<String>,
 }
 
 #[derive(askama::Template)]
@@ -65,13 +66,14 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for RubyCl
                         let (_function, _impl_) = c.item;
                         Ok(RubyFunction {
                             name: f.name().to_string(),
-                            partial_return_type: f.elem().output().to_partial_type_ref(),
+                            partial_return_type: f.elem().output().to_partial_type_ref(ir),
                             return_type: f.elem().output().to_ruby(),
                             args: f
                                 .inputs()
                                 .iter()
                                 .map(|(name, r#type)| (name.to_string(), r#type.to_type_ref()))
                                 .collect(),
+                            comment: f.docstring().map(|d| to_ruby_comment(d, "    ")),
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
@@ -27,3 +27,12 @@ impl LanguageFeatures for RubyLanguageFeatures {
 pub(super) trait ToRuby {
     fn to_ruby(&self) -> String;
 }
+
+/// A `#` comment holding `text`, with its continuation lines indented by
+/// `indent`.
+pub(super) fn to_ruby_comment(text: &str, indent: &str) -> String {
+    text.lines()
+        .map(|line| format!("# {}", line.trim_end()).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join(&format!("\n{indent}"))
+}
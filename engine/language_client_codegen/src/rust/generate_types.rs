@@ -0,0 +1,468 @@
+use anyhow::Result;
+use baml_types::{LiteralValue, StreamingBehavior, TypeValue};
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use super::rust_language_features::{to_rust_ident, ToRust};
+use crate::field_type_attributes;
+use internal_baml_core::ir::{
+    repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType, IRHelper,
+};
+
+#[derive(askama::Template)]
+#[template(path = "types.rs.j2", escape = "none")]
+pub(crate) struct RustTypes<'ir> {
+    enums: Vec<RustEnum<'ir>>,
+    classes: Vec<RustClass<'ir>>,
+    unions: Vec<RustUnion>,
+    type_aliases: Vec<&'ir str>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "partial_types.rs.j2", escape = "none")]
+pub(crate) struct RustStreamTypes<'ir> {
+    partial_classes: Vec<RustClass<'ir>>,
+    partial_unions: Vec<RustUnion>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "type_builder.rs.j2", escape = "none")]
+pub(crate) struct TypeBuilder<'ir> {
+    enums: Vec<RustEnum<'ir>>,
+    classes: Vec<RustClass<'ir>>,
+}
+
+struct RustEnum<'ir> {
+    name: &'ir str,
+    // the BAML name, the Rust identifier, and whether serde needs a rename
+    values: Vec<(&'ir str, String, bool)>,
+    dynamic: bool,
+}
+
+struct RustClass<'ir> {
+    name: &'ir str,
+    // the BAML name, the Rust identifier, whether serde needs a rename, and the type
+    fields: Vec<(&'ir str, String, bool, String)>,
+    dynamic: bool,
+}
+
+/// A Rust enum standing in for an anonymous BAML union, e.g. `int | string`.
+struct RustUnion {
+    name: String,
+    // the variant name, and the type it holds
+    variants: Vec<(String, String)>,
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for RustTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(RustTypes {
+            enums: ir.walk_enums().map(RustEnum::from).collect(),
+            classes: ir.walk_classes().map(RustClass::from).collect(),
+            unions: collect_unions(ir)
+                .into_iter()
+                .map(|(name, variants)| RustUnion {
+                    name,
+                    variants: variants
+                        .into_iter()
+                        .map(|(variant, t)| (variant, t.to_type_ref(ir, true)))
+                        .collect(),
+                })
+                .collect(),
+            type_aliases: ir
+                .structural_recursive_alias_cycles()
+                .iter()
+                .flat_map(|cycle| cycle.keys())
+                .map(|name| name.as_str())
+                .collect(),
+        })
+    }
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for RustStreamTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(RustStreamTypes {
+            partial_classes: ir.walk_classes().map(RustClass::partial).collect(),
+            partial_unions: collect_unions(ir)
+                .into_iter()
+                .map(|(name, variants)| RustUnion {
+                    name,
+                    variants: variants
+                        .into_iter()
+                        .map(|(variant, t)| (variant, t.to_partial_type_ref(ir, true)))
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for TypeBuilder<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(TypeBuilder {
+            enums: ir.walk_enums().map(RustEnum::from).collect(),
+            classes: ir.walk_classes().map(RustClass::from).collect(),
+        })
+    }
+}
+
+impl<'ir> From<EnumWalker<'ir>> for RustEnum<'ir> {
+    fn from(e: EnumWalker<'ir>) -> RustEnum<'ir> {
+        RustEnum {
+            name: e.name(),
+            dynamic: e.item.attributes.get("dynamic_type").is_some(),
+            values: e
+                .item
+                .elem
+                .values
+                .iter()
+                .map(|v| {
+                    let (ident, renamed) = to_rust_ident(&v.elem.0);
+                    (v.elem.0.as_str(), ident, renamed)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'ir> From<ClassWalker<'ir>> for RustClass<'ir> {
+    fn from(c: ClassWalker<'ir>) -> RustClass<'ir> {
+        RustClass::new(c, |t, _| t.to_type_ref(c.db, true))
+    }
+}
+
+impl<'ir> RustClass<'ir> {
+    /// The class corresponding to Partial<TypeDefinedInBaml>, where every field
+    /// may still be missing.
+    ///
+    /// Fields stay optional even with `@stream.not_null`, since partial
+    /// classes have to implement `Default`.
+    fn partial(c: ClassWalker<'ir>) -> RustClass<'ir> {
+        RustClass::new(c, |t, streaming| {
+            let type_ref = if streaming.done {
+                match t {
+                    FieldType::Optional(_) => t.to_type_ref(c.db, true),
+                    _ => format!("Option<{}>", t.to_type_ref(c.db, true)),
+                }
+            } else {
+                t.to_partial_field_type_ref(c.db)
+            };
+            if streaming.with_state {
+                format!("StreamState<{type_ref}>")
+            } else {
+                type_ref
+            }
+        })
+    }
+
+    fn new(
+        c: ClassWalker<'ir>,
+        type_ref: impl Fn(&FieldType, StreamingBehavior) -> String,
+    ) -> RustClass<'ir> {
+        RustClass {
+            name: c.name(),
+            dynamic: c.item.attributes.get("dynamic_type").is_some(),
+            fields: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .map(|f| {
+                    let (ident, renamed) = to_rust_ident(&f.elem.name);
+                    (
+                        f.elem.name.as_str(),
+                        ident,
+                        renamed,
+                        type_ref(&f.elem.r#type.elem, f.attributes.streaming()),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Every union in the IR that needs its own Rust enum, keyed by enum name.
+fn collect_unions(ir: &IntermediateRepr) -> IndexMap<String, Vec<(String, &FieldType)>> {
+    fn visit<'a>(
+        ir: &IntermediateRepr,
+        t: &'a FieldType,
+        unions: &mut IndexMap<String, Vec<(String, &'a FieldType)>>,
+    ) {
+        match t {
+            FieldType::Union(items) => {
+                let (variants, _) = union_variants(ir, items);
+                if variants.len() > 1 {
+                    unions
+                        .entry(union_name(&variants))
+                        .or_insert_with(|| variants.clone());
+                }
+                for (_, variant) in variants {
+                    visit(ir, variant, unions);
+                }
+            }
+            FieldType::List(inner) | FieldType::Optional(inner) => visit(ir, inner, unions),
+            FieldType::Map(key, value) => {
+                visit(ir, key, unions);
+                visit(ir, value, unions);
+            }
+            FieldType::Tuple(items) => items.iter().for_each(|t| visit(ir, t, unions)),
+            FieldType::Constrained { base, .. } => visit(ir, base, unions),
+            FieldType::Primitive(_)
+            | FieldType::Enum(_)
+            | FieldType::Literal(_)
+            | FieldType::Class(_)
+            | FieldType::RecursiveTypeAlias(_) => {}
+        }
+    }
+
+    let mut unions = IndexMap::new();
+    for class in ir.walk_classes() {
+        for field in class.item.elem.static_fields.iter() {
+            visit(ir, &field.elem.r#type.elem, &mut unions);
+        }
+    }
+    for function in ir.walk_functions() {
+        for (_, arg) in function.inputs() {
+            visit(ir, arg, &mut unions);
+        }
+        visit(ir, function.elem().output(), &mut unions);
+    }
+    unions
+}
+
+/// Flattens a union into its distinct Rust variants, and whether it includes
+/// `null`. Members that map to the same Rust type (e.g. the string literals
+/// of `"a" | "b"`) collapse into a single variant.
+fn union_variants<'a>(
+    ir: &IntermediateRepr,
+    items: &'a [FieldType],
+) -> (Vec<(String, &'a FieldType)>, bool) {
+    fn flatten<'a>(items: &'a [FieldType], out: &mut Vec<&'a FieldType>, nullable: &mut bool) {
+        for item in items {
+            match item {
+                FieldType::Union(inner) => flatten(inner, out, nullable),
+                FieldType::Optional(inner) => {
+                    *nullable = true;
+                    flatten(std::slice::from_ref(inner.as_ref()), out, nullable)
+                }
+                FieldType::Primitive(TypeValue::Null) => *nullable = true,
+                other => out.push(other),
+            }
+        }
+    }
+
+    let mut flat = vec![];
+    let mut nullable = false;
+    flatten(items, &mut flat, &mut nullable);
+
+    let variants = flat
+        .into_iter()
+        .unique_by(|t| t.to_type_ref(ir, false))
+        .map(|t| (variant_name(ir, t), t))
+        .collect();
+    (variants, nullable)
+}
+
+fn union_name(variants: &[(String, &FieldType)]) -> String {
+    format!(
+        "Union{}",
+        variants.iter().map(|(name, _)| name.as_str()).join("Or")
+    )
+}
+
+/// The name of the variant that holds `t` in a union's Rust enum.
+fn variant_name(ir: &IntermediateRepr, t: &FieldType) -> String {
+    match t {
+        FieldType::Primitive(TypeValue::Media(media_type)) => {
+            media_type.to_string()[..1].to_uppercase() + &media_type.to_string()[1..]
+        }
+        FieldType::Primitive(p) => match p {
+            TypeValue::String => "String",
+            TypeValue::Int => "Int",
+            TypeValue::Float => "Float",
+            TypeValue::Bool => "Bool",
+            _ => "Null",
+        }
+        .to_string(),
+        FieldType::Literal(value) => variant_name(ir, &value.literal_base_type()),
+        FieldType::Enum(name) | FieldType::Class(name) | FieldType::RecursiveTypeAlias(name) => {
+            name.clone()
+        }
+        FieldType::List(inner) => format!("{}List", variant_name(ir, inner)),
+        FieldType::Map(key, value) => {
+            format!("{}{}Map", variant_name(ir, key), variant_name(ir, value))
+        }
+        FieldType::Tuple(items) => format!(
+            "Tuple{}",
+            items.iter().map(|t| variant_name(ir, t)).join("")
+        ),
+        FieldType::Optional(inner) => format!("Optional{}", variant_name(ir, inner)),
+        FieldType::Union(items) => {
+            let (variants, nullable) = union_variants(ir, items);
+            let name = match variants.as_slice() {
+                [(name, _)] => name.clone(),
+                _ => union_name(&variants),
+            };
+            if nullable {
+                format!("Optional{name}")
+            } else {
+                name
+            }
+        }
+        FieldType::Constrained { base, .. } => variant_name(ir, base),
+    }
+}
+
+fn literal_type(value: &LiteralValue) -> String {
+    match value {
+        LiteralValue::String(_) => "String",
+        LiteralValue::Int(_) => "i64",
+        LiteralValue::Bool(_) => "bool",
+    }
+    .to_string()
+}
+
+/// Classes that are part of a recursive cycle must be boxed wherever they are
+/// stored inline, or the generated struct would have infinite size.
+fn needs_box(ir: &IntermediateRepr, class: &str) -> bool {
+    ir.finite_recursive_cycles()
+        .iter()
+        .any(|cycle| cycle.contains(class))
+}
+
+fn tuple_type_ref(items: impl Iterator<Item = String>) -> String {
+    let items = items.collect::<Vec<_>>();
+    match items.as_slice() {
+        [single] => format!("({single},)"),
+        _ => format!("({})", items.join(", ")),
+    }
+}
+
+pub(super) trait ToTypeReference {
+    /// `boxed` is set where the reference is stored inline in a generated
+    /// type, and recursive classes have to be boxed.
+    fn to_type_ref(&self, ir: &IntermediateRepr, boxed: bool) -> String;
+
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr, boxed: bool) -> String;
+
+    /// The type of a field in a partial class: like `to_partial_type_ref`,
+    /// but always optional, since the field may not have streamed in yet.
+    fn to_partial_field_type_ref(&self, ir: &IntermediateRepr) -> String;
+}
+
+impl ToTypeReference for FieldType {
+    fn to_type_ref(&self, ir: &IntermediateRepr, boxed: bool) -> String {
+        match self {
+            FieldType::Enum(name) => format!("super::types::{name}"),
+            FieldType::Class(name) => {
+                if boxed && needs_box(ir, name) {
+                    format!("Box<super::types::{name}>")
+                } else {
+                    format!("super::types::{name}")
+                }
+            }
+            FieldType::RecursiveTypeAlias(name) => format!("super::types::{name}"),
+            FieldType::Literal(value) => literal_type(value),
+            FieldType::List(inner) => format!("Vec<{}>", inner.to_type_ref(ir, false)),
+            FieldType::Map(key, value) => format!(
+                "std::collections::HashMap<{}, {}>",
+                key.to_type_ref(ir, false),
+                value.to_type_ref(ir, false)
+            ),
+            FieldType::Primitive(r#type) => r#type.to_rust(),
+            FieldType::Union(items) => {
+                let (variants, nullable) = union_variants(ir, items);
+                let type_ref = match variants.as_slice() {
+                    [] => "()".to_string(),
+                    [(_, single)] => single.to_type_ref(ir, boxed),
+                    _ => format!("super::types::{}", union_name(&variants)),
+                };
+                if nullable && !variants.is_empty() {
+                    format!("Option<{type_ref}>")
+                } else {
+                    type_ref
+                }
+            }
+            FieldType::Tuple(items) => {
+                tuple_type_ref(items.iter().map(|t| t.to_type_ref(ir, boxed)))
+            }
+            FieldType::Optional(inner) => format!("Option<{}>", inner.to_type_ref(ir, boxed)),
+            FieldType::Constrained { base, .. } => match field_type_attributes(self) {
+                Some(_) => format!("super::types::Checked<{}>", base.to_type_ref(ir, boxed)),
+                None => base.to_type_ref(ir, boxed),
+            },
+        }
+    }
+
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr, boxed: bool) -> String {
+        match self {
+            // `@@stream.done` classes only show up once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.streaming_behavior().done)
+                    .unwrap_or(false) =>
+            {
+                self.to_type_ref(ir, boxed)
+            }
+            FieldType::Class(name) => {
+                if boxed && needs_box(ir, name) {
+                    format!("Box<super::partial_types::{name}>")
+                } else {
+                    format!("super::partial_types::{name}")
+                }
+            }
+            FieldType::Enum(_)
+            | FieldType::RecursiveTypeAlias(_)
+            | FieldType::Literal(_)
+            | FieldType::Primitive(_) => self.to_type_ref(ir, boxed),
+            FieldType::List(inner) => {
+                format!("Vec<{}>", inner.to_partial_type_ref(ir, false))
+            }
+            FieldType::Map(key, value) => format!(
+                "std::collections::HashMap<{}, {}>",
+                key.to_type_ref(ir, false),
+                value.to_partial_type_ref(ir, false)
+            ),
+            FieldType::Union(items) => {
+                let (variants, nullable) = union_variants(ir, items);
+                let type_ref = match variants.as_slice() {
+                    [] => "()".to_string(),
+                    [(_, single)] => single.to_partial_type_ref(ir, boxed),
+                    _ => format!("super::partial_types::{}", union_name(&variants)),
+                };
+                if nullable && !variants.is_empty() {
+                    format!("Option<{type_ref}>")
+                } else {
+                    type_ref
+                }
+            }
+            FieldType::Tuple(items) => {
+                tuple_type_ref(items.iter().map(|t| t.to_partial_field_type_ref(ir)))
+            }
+            FieldType::Optional(inner) => {
+                format!("Option<{}>", inner.to_partial_type_ref(ir, boxed))
+            }
+            FieldType::Constrained { base, .. } => match field_type_attributes(self) {
+                Some(_) => format!(
+                    "super::types::Checked<{}>",
+                    base.to_partial_type_ref(ir, boxed)
+                ),
+                None => base.to_partial_type_ref(ir, boxed),
+            },
+        }
+    }
+
+    fn to_partial_field_type_ref(&self, ir: &IntermediateRepr) -> String {
+        if self.is_optional() {
+            self.to_partial_type_ref(ir, true)
+        } else {
+            format!("Option<{}>", self.to_partial_type_ref(ir, true))
+        }
+    }
+}
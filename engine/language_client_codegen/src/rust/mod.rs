@@ -0,0 +1,350 @@
+mod generate_types;
+mod rust_language_features;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use internal_baml_core::{
+    configuration::GeneratorDefaultClientMode,
+    ir::{repr::IntermediateRepr, FieldType},
+};
+
+use self::generate_types::ToTypeReference;
+use self::rust_language_features::{to_rust_ident, RustLanguageFeatures};
+use crate::dir_writer::FileCollector;
+
+#[derive(askama::Template)]
+#[template(path = "async_client.rs.j2", escape = "none")]
+struct AsyncRustClient {
+    funcs: Vec<RustFunction>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "sync_client.rs.j2", escape = "none")]
+struct SyncRustClient {
+    funcs: Vec<RustFunction>,
+}
+
+struct RustClient {
+    funcs: Vec<RustFunction>,
+}
+
+impl From<RustClient> for AsyncRustClient {
+    fn from(value: RustClient) -> Self {
+        Self { funcs: value.funcs }
+    }
+}
+
+impl From<RustClient> for SyncRustClient {
+    fn from(value: RustClient) -> Self {
+        Self { funcs: value.funcs }
+    }
+}
+
+struct RustFunction {
+    name: String,
+    partial_return_type: String,
+    return_type: String,
+    // the BAML name, the Rust identifier, and the type of the argument
+    args: Vec<(String, String, String)>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "mod.rs.j2", escape = "none")]
+struct RustMod {
+    default_client_mode: GeneratorDefaultClientMode,
+}
+
+#[derive(askama::Template)]
+#[template(path = "globals.rs.j2", escape = "none")]
+struct RustGlobals {}
+
+#[derive(askama::Template)]
+#[template(path = "media.rs.j2", escape = "none")]
+struct RustMedia {
+    media_types: Vec<(&'static str, &'static str)>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "inlinedbaml.rs.j2", escape = "none")]
+struct InlinedBaml {
+    file_map: Vec<(String, String)>,
+}
+
+pub(crate) fn generate(
+    ir: &IntermediateRepr,
+    generator: &crate::GeneratorArgs,
+) -> Result<IndexMap<PathBuf, String>> {
+    let mut collector = FileCollector::<RustLanguageFeatures>::new();
+
+    collector
+        .add_template::<generate_types::RustStreamTypes>("partial_types.rs", (ir, generator))?;
+    collector.add_template::<generate_types::RustTypes>("types.rs", (ir, generator))?;
+    collector.add_template::<RustMedia>("media.rs", (ir, generator))?;
+    collector.add_template::<generate_types::TypeBuilder>("type_builder.rs", (ir, generator))?;
+    collector.add_template::<AsyncRustClient>("async_client.rs", (ir, generator))?;
+    collector.add_template::<SyncRustClient>("sync_client.rs", (ir, generator))?;
+    collector.add_template::<RustGlobals>("globals.rs", (ir, generator))?;
+    collector.add_template::<InlinedBaml>("inlinedbaml.rs", (ir, generator))?;
+    collector.add_template::<RustMod>("mod.rs", (ir, generator))?;
+
+    collector.commit(&generator.output_dir())
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for RustMod {
+    type Error = anyhow::Error;
+
+    fn try_from((_, gen): (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(RustMod {
+            default_client_mode: gen.default_client_mode.clone(),
+        })
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for RustGlobals {
+    type Error = anyhow::Error;
+
+    fn try_from((_, _args): (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(RustGlobals {})
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for RustMedia {
+    type Error = anyhow::Error;
+
+    fn try_from((_, _args): (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        Ok(RustMedia {
+            media_types: vec![
+                ("Image", "image"),
+                ("Audio", "audio"),
+                ("Pdf", "pdf"),
+                ("Video", "video"),
+            ],
+        })
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for InlinedBaml {
+    type Error = anyhow::Error;
+
+    fn try_from((_ir, args): (&IntermediateRepr, &crate::GeneratorArgs)) -> Result<Self> {
+        // Debug-formatting a str gives a valid Rust string literal, which JSON
+        // (what file_map() returns) is not: e.g. `\u001b` must be `\u{1b}`.
+        Ok(InlinedBaml {
+            file_map: args
+                .inlined_file_map
+                .iter()
+                .map(|(path, contents)| {
+                    (
+                        format!("{:?}", path.display().to_string()),
+                        format!("{:?}", contents),
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for AsyncRustClient {
+    type Error = anyhow::Error;
+
+    fn try_from(params: (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        let rust_client = RustClient::try_from(params)?;
+        Ok(rust_client.into())
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for SyncRustClient {
+    type Error = anyhow::Error;
+
+    fn try_from(params: (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        let rust_client = RustClient::try_from(params)?;
+        Ok(rust_client.into())
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for RustClient {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
+        let funcs = ir
+            .walk_functions()
+            .map(|f| RustFunction {
+                name: f.name().to_string(),
+                partial_return_type: f.elem().output().to_partial_type_ref(ir, false),
+                return_type: f.elem().output().to_type_ref(ir, false),
+                args: f
+                    .inputs()
+                    .iter()
+                    .map(|(name, r#type)| {
+                        (
+                            name.to_string(),
+                            to_rust_ident(name).0,
+                            to_arg_type_ref(ir, r#type),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(RustClient { funcs })
+    }
+}
+
+/// Arguments are only serialized, so functions borrow them.
+fn to_arg_type_ref(ir: &IntermediateRepr, r#type: &FieldType) -> String {
+    match r#type.to_type_ref(ir, false).as_str() {
+        "String" => "&str".to_string(),
+        other => format!("&{other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use internal_baml_core::ir::repr::make_test_ir;
+
+    fn generate_files(source: &str) -> IndexMap<PathBuf, String> {
+        let ir = make_test_ir(source).expect("Valid source");
+        let files = std::collections::BTreeMap::from([(
+            PathBuf::from("/baml_src/main.baml"),
+            source.to_string(),
+        )]);
+        let out_dir = tempdir();
+        let generator = crate::GeneratorArgs::new(
+            out_dir.join("baml_client"),
+            "/baml_src",
+            files.iter(),
+            "0.0.0".to_string(),
+            true,
+            GeneratorDefaultClientMode::Async,
+            vec![],
+        )
+        .expect("Valid generator args");
+        let generated = generate(&ir, &generator).expect("Codegen succeeds");
+        std::fs::remove_dir_all(&out_dir).ok();
+        generated
+    }
+
+    fn tempdir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "baml-rust-codegen-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file<'a>(files: &'a IndexMap<PathBuf, String>, name: &str) -> &'a str {
+        files
+            .iter()
+            .find(|(path, _)| path.ends_with(name))
+            .map(|(_, contents)| contents.as_str())
+            .unwrap_or_else(|| panic!("{name} was not generated"))
+    }
+
+    const SOURCE: &str = r##"
+client<llm> GPT4 {
+  provider openai
+  options {
+    model gpt-4o
+    api_key env.OPENAI_API_KEY
+  }
+}
+
+enum Category {
+  Refund
+  Question
+  @@dynamic
+}
+
+class Node {
+  value int | string
+  type "leaf" | "branch"
+  next Node?
+  score float @check(positive, {{ this > 0 }})
+}
+
+function Classify(input: string, photo: image?) -> Category {
+  client GPT4
+  prompt #"{{ input }}"#
+}
+
+function Walk(depth: int) -> Node[] {
+  client GPT4
+  prompt #"{{ depth }}"#
+}
+"##;
+
+    #[test]
+    fn generates_types() {
+        let files = generate_files(SOURCE);
+        let types = file(&files, "types.rs");
+
+        assert!(types.contains("pub enum Category {"));
+        assert!(types.contains("Dynamic(String)"));
+        assert!(types.contains("pub struct Node {"));
+        assert!(types.contains("pub value: super::types::UnionIntOrString,"));
+        assert!(types.contains("pub r#type: String,"));
+        assert!(types.contains("pub next: Option<Box<super::types::Node>>,"));
+        assert!(types.contains("pub score: super::types::Checked<f64>,"));
+        assert!(types.contains("pub enum UnionIntOrString {"));
+
+        let partial_types = file(&files, "partial_types.rs");
+        assert!(
+            partial_types.contains("pub value: Option<super::partial_types::UnionIntOrString>,")
+        );
+        assert!(partial_types.contains("pub next: Option<Box<super::partial_types::Node>>,"));
+    }
+
+    #[test]
+    fn generates_streaming_partial_types() {
+        let files = generate_files(
+            r##"
+class Ingredient {
+  name string
+  @@stream.done
+}
+
+class Recipe {
+  title string @stream.not_null
+  summary string @stream.done
+  ingredients Ingredient[]
+  steps string[] @stream.with_state
+}
+"##,
+        );
+        let partial_types = file(&files, "partial_types.rs");
+
+        assert!(partial_types.contains("pub struct StreamState<T> {"));
+        assert!(partial_types.contains("pub title: Option<String>,"));
+        assert!(partial_types.contains("pub summary: Option<String>,"));
+        assert!(partial_types.contains("pub ingredients: Option<Vec<super::types::Ingredient>>,"));
+        assert!(partial_types.contains("pub steps: StreamState<Option<Vec<String>>>,"));
+    }
+
+    #[test]
+    fn generates_clients() {
+        let files = generate_files(SOURCE);
+
+        for client in ["async_client.rs", "sync_client.rs"] {
+            let client = file(&files, client);
+            assert!(client.contains("fn Classify("));
+            assert!(client.contains("input: &str,"));
+            assert!(client.contains("photo: &Option<super::media::Image>,"));
+            assert!(client.contains("anyhow::Result<super::types::Category>"));
+            assert!(client.contains("anyhow::Result<Vec<super::types::Node>>"));
+        }
+
+        let type_builder = file(&files, "type_builder.rs");
+        assert!(type_builder.contains("pub fn Category(&self) -> CategoryBuilder"));
+        assert!(!type_builder.contains("pub fn Node(&self)"));
+
+        let inlined = file(&files, "inlinedbaml.rs");
+        assert!(inlined.contains(r#"("main.baml", "#));
+
+        let module = file(&files, "mod.rs");
+        assert!(module.contains("pub use async_client::b;"));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::dir_writer::LanguageFeatures;
+use baml_types::{BamlMediaType, TypeValue};
+
+#[derive(Default)]
+pub(super) struct RustLanguageFeatures {}
+
+impl LanguageFeatures for RustLanguageFeatures {
+    const CONTENT_PREFIX: &'static str = r#"
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! To use this generated code, please run the following:
+//
+//  $ cargo add baml-runtime baml-types anyhow serde serde_json
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// This file was generated by BAML: please do not edit it. Instead, edit the
+// BAML files and re-generate this code.
+//
+// @generated
+        "#;
+}
+
+pub(super) trait ToRust {
+    fn to_rust(&self) -> String;
+}
+
+impl ToRust for TypeValue {
+    fn to_rust(&self) -> String {
+        match self {
+            TypeValue::Bool => "bool",
+            TypeValue::Float => "f64",
+            TypeValue::Int => "i64",
+            TypeValue::String => "String",
+            TypeValue::Null => "()",
+            TypeValue::Media(BamlMediaType::Image) => "super::media::Image",
+            TypeValue::Media(BamlMediaType::Audio) => "super::media::Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "super::media::Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "super::media::Video",
+        }
+        .to_string()
+    }
+}
+
+/// Rust keywords that can be used as identifiers when prefixed with `r#`.
+const RAW_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Rust keywords that cannot be raw identifiers at all.
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+/// Returns `name` as a valid Rust identifier, and whether it differs from
+/// `name` in a way that serde won't undo on its own (and so needs a
+/// `#[serde(rename)]`).
+///
+/// serde strips the `r#` prefix from raw identifiers, so `r#type` still
+/// (de)serializes as `type`.
+pub(super) fn to_rust_ident(name: &str) -> (String, bool) {
+    if RESERVED_KEYWORDS.contains(&name) {
+        (format!("{name}_"), true)
+    } else if RAW_KEYWORDS.contains(&name) {
+        (format!("r#{name}"), false)
+    } else {
+        (name.to_string(), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_marker_in_content_prefix() {
+        // remove_dir_safe only deletes files that say they were generated by BAML.
+        assert!(RustLanguageFeatures::CONTENT_PREFIX.contains("generated by BAML"));
+    }
+
+    #[test]
+    fn test_rust_idents() {
+        assert_eq!(to_rust_ident("name"), ("name".to_string(), false));
+        assert_eq!(to_rust_ident("type"), ("r#type".to_string(), false));
+        assert_eq!(to_rust_ident("self"), ("self_".to_string(), true));
+    }
+}
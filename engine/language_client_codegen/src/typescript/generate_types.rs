@@ -3,11 +3,12 @@ use std::borrow::Cow;
 use anyhow::Result;
 use itertools::Itertools;
 
-use internal_baml_core::ir::{repr::IntermediateRepr, ClassWalker, EnumWalker};
+use baml_types::StreamingBehavior;
+use internal_baml_core::ir::{repr::IntermediateRepr, ClassWalker, EnumWalker, FieldType};
 
-use crate::{type_check_attributes, GeneratorArgs, TypeCheckAttributes};
+use crate::{type_check_attributes, Documented, GeneratorArgs, TypeCheckAttributes};
 
-use super::ToTypeReferenceInClientDefinition;
+use super::{typescript_language_features::to_ts_doc_comment, ToTypeReferenceInClientDefinition};
 
 #[derive(askama::Template)]
 #[template(path = "type_builder.ts.j2", escape = "none")]
@@ -21,18 +22,34 @@ pub(crate) struct TypeBuilder<'ir> {
 pub(crate) struct TypescriptTypes<'ir> {
     enums: Vec<TypescriptEnum<'ir>>,
     classes: Vec<TypescriptClass<'ir>>,
+    type_aliases: Vec<TypescriptTypeAlias<'ir>>,
+}
+
+#[derive(askama::Template)]
+#[template(path = "partial_types.ts.j2", escape = "none")]
+pub(crate) struct TypescriptStreamTypes<'ir> {
+    partial_classes: Vec<TypescriptClass<'ir>>,
+}
+
+struct TypescriptTypeAlias<'ir> {
+    name: &'ir str,
+    target: String,
 }
 
 struct TypescriptEnum<'ir> {
     pub name: &'ir str,
-    pub values: Vec<&'ir str>,
+    /// Name and doc comment of each value.
+    pub values: Vec<(&'ir str, Option<String>)>,
     pub dynamic: bool,
+    pub doc_comment: Option<String>,
 }
 
 pub struct TypescriptClass<'ir> {
     pub name: Cow<'ir, str>,
-    pub fields: Vec<(Cow<'ir, str>, bool, String)>,
+    /// Name, whether it's optional, type and doc comment of each field.
+    pub fields: Vec<(Cow<'ir, str>, bool, String, Option<String>)>,
     pub dynamic: bool,
+    pub doc_comment: Option<String>,
 }
 
 impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptTypes<'ir> {
@@ -50,6 +67,30 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptTyp
                 .walk_classes()
                 .map(|e| Into::<TypescriptClass>::into(&e))
                 .collect::<Vec<_>>(),
+            type_aliases: ir
+                .structural_recursive_alias_cycles()
+                .iter()
+                .flat_map(|cycle| cycle.iter())
+                .map(|(name, target)| TypescriptTypeAlias {
+                    name,
+                    target: target.to_type_ref(ir),
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptStreamTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        (ir, _): (&'ir IntermediateRepr, &'ir GeneratorArgs),
+    ) -> Result<TypescriptStreamTypes<'ir>> {
+        Ok(TypescriptStreamTypes {
+            partial_classes: ir
+                .walk_classes()
+                .map(|c| TypescriptClass::partial(&c))
+                .collect::<Vec<_>>(),
         })
     }
 }
@@ -77,12 +118,16 @@ impl<'ir> From<&EnumWalker<'ir>> for TypescriptEnum<'ir> {
             name: e.name(),
             dynamic: e.item.attributes.get("dynamic_type").is_some(),
             values: e
-                .item
-                .elem
-                .values
-                .iter()
-                .map(|v| v.elem.0.as_str())
+                .walk_values()
+                .map(|v| {
+                    let docs = v.documentation();
+                    (
+                        v.item.elem.0.as_str(),
+                        docs.map(|d| to_ts_doc_comment(&d, "  ")),
+                    )
+                })
                 .collect(),
+            doc_comment: e.documentation().map(|d| to_ts_doc_comment(&d, "")),
         }
     }
 }
@@ -93,22 +138,87 @@ impl<'ir> From<&ClassWalker<'ir>> for TypescriptClass<'ir> {
             name: Cow::Borrowed(c.name()),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
-                .item
-                .elem
-                .static_fields
-                .iter()
+                .walk_fields()
                 .map(|f| {
+                    let docs = f.documentation();
+                    let default = f
+                        .default_value()
+                        .and_then(Result::ok)
+                        .map(|v| format!("@default {}", serde_json::json!(v)));
+                    let doc_comment = match (docs, default) {
+                        (Some(docs), Some(default)) => Some(format!("{docs}\n{default}")),
+                        (docs, default) => docs.or(default),
+                    };
                     (
-                        Cow::Borrowed(f.elem.name.as_str()),
-                        f.elem.r#type.elem.is_optional(),
-                        f.elem.r#type.elem.to_type_ref(&c.db),
+                        Cow::Borrowed(f.item.elem.name.as_str()),
+                        f.item.elem.r#type.elem.is_optional(),
+                        f.item.elem.r#type.elem.to_type_ref(&c.db),
+                        doc_comment.map(|d| to_ts_doc_comment(&d, "  ")),
                     )
                 })
                 .collect(),
+            doc_comment: c.documentation().map(|d| to_ts_doc_comment(&d, "")),
         }
     }
 }
 
+impl<'ir> TypescriptClass<'ir> {
+    /// The interface corresponding to Partial<TypeDefinedInBaml>, as declared
+    /// in `partial_types.ts`.
+    fn partial(c: &ClassWalker<'ir>) -> TypescriptClass<'ir> {
+        TypescriptClass {
+            name: Cow::Borrowed(c.name()),
+            dynamic: c.item.attributes.get("dynamic_type").is_some(),
+            fields: c
+                .walk_fields()
+                .map(|f| {
+                    let (optional, type_ref) = to_partial_field_type_ref(
+                        &f.item.elem.r#type.elem,
+                        f.item.attributes.streaming(),
+                        &c.db,
+                    );
+                    let docs = f.documentation();
+                    (
+                        Cow::Borrowed(f.item.elem.name.as_str()),
+                        optional,
+                        type_ref,
+                        docs.map(|d| to_ts_doc_comment(&d, "  ")),
+                    )
+                })
+                .collect(),
+            doc_comment: c.documentation().map(|d| to_ts_doc_comment(&d, "")),
+        }
+    }
+}
+
+/// Whether a field of a partial interface is optional, and its type, taking
+/// its `@stream.*` attributes into account.
+fn to_partial_field_type_ref(
+    field_type: &FieldType,
+    streaming: StreamingBehavior,
+    ir: &IntermediateRepr,
+) -> (bool, String) {
+    let field_type = match field_type {
+        FieldType::Optional(inner) => inner.as_ref(),
+        _ => field_type,
+    };
+    let type_ref = if streaming.done {
+        field_type.to_type_ref_in(ir, "types.")
+    } else {
+        field_type.to_partial_type_ref(ir, "types.", "")
+    };
+    let type_ref = if streaming.not_null {
+        type_ref
+    } else {
+        format!("{type_ref} | null")
+    };
+    if streaming.with_state {
+        (false, format!("StreamState<{type_ref}>"))
+    } else {
+        (!streaming.not_null, type_ref)
+    }
+}
+
 pub fn type_name_for_checks(checks: &TypeCheckAttributes) -> String {
     checks.0.iter().map(|check| format!("\"{check}\"")).sorted().join(" | ")
 }
@@ -11,7 +11,7 @@ use internal_baml_core::{
     ir::{repr::IntermediateRepr, FieldType, IRHelper},
 };
 
-use self::typescript_language_features::{ToTypescript, TypescriptLanguageFeatures};
+use self::typescript_language_features::{to_ts_doc_comment, ToTypescript, TypescriptLanguageFeatures};
 use crate::{dir_writer::FileCollector, field_type_attributes};
 
 #[derive(askama::Template)]
@@ -54,9 +54,10 @@ impl From<TypescriptClient> for SyncTypescriptClient {
 #[derive(Debug)]
 struct TypescriptFunction {
     name: String,
-    // partial_return_type: String,
+    partial_return_type: String,
     return_type: String,
     args: Vec<(String, bool, String)>,
+    doc_comment: Option<String>,
 }
 
 #[derive(askama::Template)]
@@ -87,6 +88,10 @@ pub(crate) fn generate(
 ) -> Result<IndexMap<PathBuf, String>> {
     let mut collector = FileCollector::<TypescriptLanguageFeatures>::new();
     collector.add_template::<generate_types::TypescriptTypes>("types.ts", (ir, generator))?;
+    collector.add_template::<generate_types::TypescriptStreamTypes>(
+        "partial_types.ts",
+        (ir, generator),
+    )?;
     collector.add_template::<generate_types::TypeBuilder>("type_builder.ts", (ir, generator))?;
     collector.add_template::<AsyncTypescriptClient>("async_client.ts", (ir, generator))?;
     collector.add_template::<SyncTypescriptClient>("sync_client.ts", (ir, generator))?;
@@ -131,7 +136,11 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptCli
                         Ok(TypescriptFunction {
                             name: f.name().to_string(),
                             return_type: f.elem().output().to_type_ref(ir),
-                            // partial_return_type: f.elem().output().to_partial_type_ref(ir),
+                            partial_return_type: f.elem().output().to_partial_type_ref(
+                                ir,
+                                "",
+                                "partial_types.",
+                            ),
                             args: f
                                 .inputs()
                                 .iter()
@@ -143,6 +152,7 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptCli
                                     )
                                 })
                                 .collect(),
+                            doc_comment: f.docstring().map(|d| to_ts_doc_comment(d, "  ")),
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
@@ -157,6 +167,11 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptCli
             .walk_classes()
             .map(|c| c.name().to_string())
             .chain(ir.walk_enums().map(|e| e.name().to_string()))
+            .chain(
+                ir.structural_recursive_alias_cycles()
+                    .iter()
+                    .flat_map(|cycle| cycle.keys().cloned()),
+            )
             .collect();
         Ok(TypescriptClient {
             funcs: functions,
@@ -202,57 +217,87 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptIni
 }
 
 trait ToTypeReferenceInClientDefinition {
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String;
+    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+        self.to_type_ref_in(ir, "")
+    }
 
-    // fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String;
+    /// Like `to_type_ref`, with every type defined in BAML referenced through
+    /// `types` (e.g. `"types."` to reference the final types from
+    /// `partial_types.ts`).
+    fn to_type_ref_in(&self, ir: &IntermediateRepr, types: &str) -> String;
+
+    /// The type of a value that may still be streaming in. Final types are
+    /// referenced through `types`, and partial ones through `partial_types`.
+    fn to_partial_type_ref(
+        &self,
+        ir: &IntermediateRepr,
+        types: &str,
+        partial_types: &str,
+    ) -> String;
 }
 
 impl ToTypeReferenceInClientDefinition for FieldType {
-    // fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String {
-    //     match self {
-    //         FieldType::Enum(name) => {
-    //             if ir
-    //                 .find_enum(name)
-    //                 .map(|e| e.item.attributes.get("dynamic_type").is_some())
-    //                 .unwrap_or(false)
-    //             {
-    //                 format!("(string | {name} | null)")
-    //             } else {
-    //                 format!("({name} | null)")
-    //             }
-    //         }
-    //         FieldType::Class(name) => format!("(RecursivePartialNull<{name}>)"),
-    //         FieldType::List(inner) => format!("{}[]", inner.to_partial_type_ref(ir)),
-    //         FieldType::Map(key, value) => {
-    //             format!(
-    //                 "(Record<{}, {}> | null)",
-    //                 key.to_type_ref(ir),
-    //                 value.to_partial_type_ref(ir)
-    //             )
-    //         }
-    //         FieldType::Literal(value) => value.to_string(),
-    //         FieldType::Primitive(r#type) => format!("({} | null)", r#type.to_typescript()),
-    //         FieldType::Union(inner) => format!(
-    //             "({} | null)",
-    //             inner
-    //                 .iter()
-    //                 .map(|t| t.to_partial_type_ref(ir))
-    //                 .collect::<Vec<_>>()
-    //                 .join(" | ")
-    //         ),
-    //         FieldType::Tuple(inner) => format!(
-    //             "([{}] | null)",
-    //             inner
-    //                 .iter()
-    //                 .map(|t| t.to_partial_type_ref(ir))
-    //                 .collect::<Vec<_>>()
-    //                 .join(", ")
-    //         ),
-    //         FieldType::Optional(inner) => format!("({} | null)", inner.to_partial_type_ref(ir)),
-    //     }
-    // }
+    fn to_partial_type_ref(
+        &self,
+        ir: &IntermediateRepr,
+        types: &str,
+        partial_types: &str,
+    ) -> String {
+        match self {
+            // `@@stream.done` classes only show up once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.streaming_behavior().done)
+                    .unwrap_or(false) =>
+            {
+                format!("{types}{name}")
+            }
+            FieldType::Class(name) => format!("{partial_types}{name}"),
+            FieldType::Enum(_)
+            | FieldType::RecursiveTypeAlias(_)
+            | FieldType::Literal(_)
+            | FieldType::Primitive(_) => self.to_type_ref_in(ir, types),
+            FieldType::List(inner) => match inner.as_ref() {
+                FieldType::Union(_) | FieldType::Optional(_) => {
+                    format!("({})[]", inner.to_partial_type_ref(ir, types, partial_types))
+                }
+                _ => format!("{}[]", inner.to_partial_type_ref(ir, types, partial_types)),
+            },
+            FieldType::Map(key, value) => format!(
+                "Record<{}, {}>",
+                key.to_type_ref_in(ir, types),
+                value.to_partial_type_ref(ir, types, partial_types)
+            ),
+            FieldType::Union(inner) => inner
+                .iter()
+                .map(|t| t.to_partial_type_ref(ir, types, partial_types))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            FieldType::Tuple(inner) => format!(
+                "[{}]",
+                inner
+                    .iter()
+                    .map(|t| t.to_partial_type_ref(ir, types, partial_types))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            FieldType::Optional(inner) => format!(
+                "{} | null",
+                inner.to_partial_type_ref(ir, types, partial_types)
+            ),
+            FieldType::Constrained { base, .. } => match field_type_attributes(self) {
+                Some(checks) => {
+                    let base_type_ref = base.to_partial_type_ref(ir, types, partial_types);
+                    let checks_type_ref = type_name_for_checks(&checks);
+                    format!("{types}Checked<{base_type_ref},{checks_type_ref}>")
+                }
+                None => base.to_partial_type_ref(ir, types, partial_types),
+            },
+        }
+    }
 
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+    fn to_type_ref_in(&self, ir: &IntermediateRepr, types: &str) -> String {
         match self {
             FieldType::Enum(name) => {
                 if ir
@@ -260,20 +305,25 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                     .map(|e| e.item.attributes.get("dynamic_type").is_some())
                     .unwrap_or(false)
                 {
-                    format!("(string | {name})")
+                    format!("(string | {types}{name})")
                 } else {
-                    format!("{name}")
+                    format!("{types}{name}")
                 }
             }
-            FieldType::Class(name) => format!("{name}"),
+            FieldType::Class(name) => format!("{types}{name}"),
+            FieldType::RecursiveTypeAlias(name) => format!("{types}{name}"),
             FieldType::List(inner) => match inner.as_ref() {
                 FieldType::Union(_) | FieldType::Optional(_) => {
-                    format!("({})[]", inner.to_type_ref(ir))
+                    format!("({})[]", inner.to_type_ref_in(ir, types))
                 }
-                _ => format!("{}[]", inner.to_type_ref(ir)),
+                _ => format!("{}[]", inner.to_type_ref_in(ir, types)),
             },
             FieldType::Map(key, value) => {
-                format!("Record<{}, {}>", key.to_type_ref(ir), value.to_type_ref(ir))
+                format!(
+                    "Record<{}, {}>",
+                    key.to_type_ref_in(ir, types),
+                    value.to_type_ref_in(ir, types)
+                )
             }
             FieldType::Primitive(r#type) => r#type.to_typescript(),
             // In typescript we can just use literal values as type defs.
@@ -282,7 +332,7 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                 "{}",
                 inner
                     .iter()
-                    .map(|t| t.to_type_ref(ir))
+                    .map(|t| t.to_type_ref_in(ir, types))
                     .collect::<Vec<_>>()
                     .join(" | ")
             ),
@@ -290,20 +340,22 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                 "[{}]",
                 inner
                     .iter()
-                    .map(|t| t.to_type_ref(ir))
+                    .map(|t| t.to_type_ref_in(ir, types))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            FieldType::Optional(inner) => format!("{} | null", inner.to_type_ref(ir)),
+            FieldType::Optional(inner) => {
+                format!("{} | null", inner.to_type_ref_in(ir, types))
+            }
             FieldType::Constrained{base,..} => {
                 match field_type_attributes(self) {
                     Some(checks) => {
-                        let base_type_ref = base.to_type_ref(ir);
+                        let base_type_ref = base.to_type_ref_in(ir, types);
                         let checks_type_ref = type_name_for_checks(&checks);
-                        format!("Checked<{base_type_ref},{checks_type_ref}>")
+                        format!("{types}Checked<{base_type_ref},{checks_type_ref}>")
                     }
                     None => {
-                        base.to_type_ref(ir)
+                        base.to_type_ref_in(ir, types)
                     }
                 }
             },
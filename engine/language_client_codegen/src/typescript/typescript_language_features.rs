@@ -40,11 +40,30 @@ impl ToTypescript for TypeValue {
             TypeValue::Null => "null",
             TypeValue::Media(BamlMediaType::Image) => "Image",
             TypeValue::Media(BamlMediaType::Audio) => "Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "Video",
         };
         var_name.to_string()
     }
 }
 
+/// A TSDoc comment holding `text`, with its continuation lines indented by
+/// `indent`.
+pub(super) fn to_ts_doc_comment(text: &str, indent: &str) -> String {
+    let text = text.replace("*/", "*\\/");
+    match text.lines().collect::<Vec<_>>().as_slice() {
+        [line] => format!("/** {} */", line.trim_end()),
+        lines => {
+            let body = lines
+                .iter()
+                .map(|line| format!("{indent} * {}", line.trim_end()).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("/**\n{body}\n{indent} */")
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +102,7 @@ pub fn check_version(
                     GeneratorOutputType::PythonPydantic => format!("pip install --upgrade baml-py=={}", gen_version),
                     GeneratorOutputType::Typescript => format!("npm install --save-dev @boundaryml/baml@{}", gen_version),
                     GeneratorOutputType::RubySorbet => format!("gem install baml -v {}", gen_version),
+                    GeneratorOutputType::Rust => format!("cargo add baml-runtime@={}", gen_version),
                 };
                 (
                     match generator_type {
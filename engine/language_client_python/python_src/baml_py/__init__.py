@@ -13,8 +13,13 @@ from .baml_py import (
     FunctionResultStream,
     BamlImagePy as Image,
     BamlAudioPy as Audio,
+    BamlPdfPy as Pdf,
+    BamlVideoPy as Video,
     invoke_runtime_cli,
     ClientRegistry,
+    CancellationToken,
+    ResponseCache,
+    HTTPRequest,
 )
 from .stream import BamlStream, BamlSyncStream
 from .ctx_manager import CtxManager as BamlCtxManager
@@ -22,6 +27,9 @@ from .ctx_manager import CtxManager as BamlCtxManager
 __all__ = [
     "BamlRuntime",
     "ClientRegistry",
+    "CancellationToken",
+    "ResponseCache",
+    "HTTPRequest",
     "BamlStream",
     "BamlSyncStream",
     "BamlCtxManager",
@@ -29,5 +37,7 @@ __all__ = [
     "FunctionResultStream",
     "Image",
     "Audio",
+    "Pdf",
+    "Video",
     "invoke_runtime_cli",
 ]
@@ -27,6 +27,18 @@ class FunctionResult:
     #   print(val)
     def unstable_internal_repr(self) -> str: ...
 
+class HTTPRequest:
+    """The HTTP request a BAML function sends to its LLM client."""
+
+    @property
+    def url(self) -> str: ...
+    @property
+    def method(self) -> str: ...
+    @property
+    def headers(self) -> Dict[str, str]: ...
+    @property
+    def body(self) -> Any: ...
+
 class FunctionResultStream:
     """The result of a BAML function stream.
 
@@ -39,6 +51,8 @@ class FunctionResultStream:
     def on_event(
         self, on_event: Callable[[FunctionResult], None]
     ) -> FunctionResultStream: ...
+    def cancel(self) -> None:
+        """Stop the stream. Safe to call while `done` is running."""
     async def done(self, ctx: RuntimeContextManager) -> FunctionResult: ...
 
 class SyncFunctionResultStream:
@@ -53,6 +67,8 @@ class SyncFunctionResultStream:
     def on_event(
         self, on_event: Callable[[FunctionResult], None]
     ) -> SyncFunctionResultStream: ...
+    def cancel(self) -> None:
+        """Stop the stream. Safe to call while `done` is running."""
     def done(self, ctx: RuntimeContextManager) -> FunctionResult: ...
 
 class BamlImagePy:
@@ -75,6 +91,26 @@ class BamlAudioPy:
     def as_url(self) -> str: ...
     def as_base64(self) -> Tuple[str, str]: ...
 
+class BamlPdfPy:
+    @staticmethod
+    def from_url(url: str) -> BamlPdfPy: ...
+    @staticmethod
+    def from_base64(media_type: str, base64: str) -> BamlPdfPy: ...
+    def is_url(self) -> bool: ...
+    def is_base64(self) -> bool: ...
+    def as_url(self) -> str: ...
+    def as_base64(self) -> Tuple[str, str]: ...
+
+class BamlVideoPy:
+    @staticmethod
+    def from_url(url: str) -> BamlVideoPy: ...
+    @staticmethod
+    def from_base64(media_type: str, base64: str) -> BamlVideoPy: ...
+    def is_url(self) -> bool: ...
+    def is_base64(self) -> bool: ...
+    def as_url(self) -> str: ...
+    def as_base64(self) -> Tuple[str, str]: ...
+
 class RuntimeContextManager:
     def upsert_tags(self, tags: Dict[str, Any]) -> None: ...
     def deep_clone(self) -> RuntimeContextManager: ...
@@ -90,6 +126,18 @@ class BamlRuntime:
         ctx: RuntimeContextManager,
         tb: Optional[TypeBuilder],
         cr: Optional[ClientRegistry],
+        cancellation_token: Optional[CancellationToken] = None,
+        bypass_cache: bool = False,
+    ) -> FunctionResult: ...
+    def call_function_sync(
+        self,
+        function_name: str,
+        args: Dict[str, Any],
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        cancellation_token: Optional[CancellationToken] = None,
+        bypass_cache: bool = False,
     ) -> FunctionResult: ...
     @staticmethod
     def from_files(
@@ -106,6 +154,7 @@ class BamlRuntime:
         ctx: RuntimeContextManager,
         tb: Optional[TypeBuilder],
         cr: Optional[ClientRegistry],
+        bypass_cache: bool = False,
     ) -> FunctionResultStream: ...
     def stream_function_sync(
         self,
@@ -115,13 +164,42 @@ class BamlRuntime:
         ctx: RuntimeContextManager,
         tb: Optional[TypeBuilder],
         cr: Optional[ClientRegistry],
+        bypass_cache: bool = False,
     ) -> SyncFunctionResultStream: ...
+    async def render_request(
+        self,
+        function_name: str,
+        args: Dict[str, Any],
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        stream: bool = False,
+    ) -> HTTPRequest: ...
+    def render_request_sync(
+        self,
+        function_name: str,
+        args: Dict[str, Any],
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        stream: bool = False,
+    ) -> HTTPRequest: ...
+    def parse_llm_response(
+        self,
+        function_name: str,
+        llm_response: str,
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        allow_partials: bool = False,
+    ) -> FunctionResult: ...
     def create_context_manager(self) -> RuntimeContextManager: ...
     def flush(self) -> None: ...
     def drain_stats(self) -> TraceStats: ...
     def set_log_event_callback(
         self, handler: Optional[Callable[[BamlLogEvent], None]]
     ) -> None: ...
+    def set_response_cache(self, cache: Optional[ResponseCache]) -> None: ...
 
 class LogEventMetadata:
     event_id: str
@@ -176,6 +254,7 @@ class TypeBuilder:
     def __init__(self) -> None: ...
     def enum(self, name: str) -> EnumBuilder: ...
     def class_(self, name: str) -> ClassBuilder: ...
+    def add_baml(self, baml: str, rt: BamlRuntime) -> None: ...
     def string(self) -> FieldType: ...
     def literal_string(self, value: str) -> FieldType: ...
     def literal_int(self, value: int) -> FieldType: ...
@@ -189,6 +268,27 @@ class TypeBuilder:
     def map(self, key_type: FieldType, value_type: FieldType) -> FieldType: ...
     def union(self, *types: FieldType) -> FieldType: ...
 
+class CancellationToken:
+    """Stops the calls it is passed to, from any thread."""
+
+    def __init__(self) -> None: ...
+    def cancel(self) -> None: ...
+    def is_cancelled(self) -> bool: ...
+
+class ResponseCache:
+    """Caches LLM responses, keyed by the client, its options and the prompt."""
+
+    @staticmethod
+    def memory(ttl_secs: Optional[int] = None) -> ResponseCache: ...
+    @staticmethod
+    def directory(path: str, ttl_secs: Optional[int] = None) -> ResponseCache: ...
+    @staticmethod
+    def callback(
+        get: Callable[[str], Optional[str]],
+        set: Callable[[str, str], None],
+        ttl_secs: Optional[int] = None,
+    ) -> ResponseCache: ...
+
 class ClientRegistry:
     def __init__(self) -> None: ...
     def add_llm_client(
@@ -244,3 +344,8 @@ class BamlClientHttpError(BamlClientError):
     """Raised for HTTP-related client errors."""
 
     ...
+
+class BamlCancelledError(BamlError):
+    """Raised when a call is stopped through its CancellationToken."""
+
+    ...
@@ -1,3 +1,4 @@
+from typing import Any, Dict, List
 from .baml_py import BamlError
 
 
@@ -16,3 +17,22 @@ class BamlValidationError(BamlError):
 
     def __repr__(self):
         return f"BamlValidationError(message={self.message}, raw_output={self.raw_output}, prompt={self.prompt})"
+
+
+class BamlSchemaError(BamlError):
+    """Raised when BAML source passed to the runtime doesn't validate.
+
+    Each entry of `diagnostics` has `file_path`, `message` and 0-based
+    `start_line`, `start_column`, `end_line` and `end_column` keys.
+    """
+
+    def __init__(self, message: str, diagnostics: List[Dict[str, Any]]):
+        super().__init__(message)
+        self.message = message
+        self.diagnostics = diagnostics
+
+    def __str__(self):
+        return self.message
+
+    def __repr__(self):
+        return f"BamlSchemaError(message={self.message}, diagnostics={self.diagnostics})"
@@ -76,6 +76,10 @@ class BamlStream(Generic[PartialOutputType, FinalOutputType]):
             if event.is_ok():
                 yield self.__partial_coerce(event)
 
+    def cancel(self) -> None:
+        """Stop the stream. Iteration ends and get_final_response raises BamlCancelledError."""
+        self.__ffi_stream.cancel()
+
     async def get_final_response(self):
         final = self.__drive_to_completion_in_bg()
         return self.__final_coerce((await asyncio.wrap_future(final)))
@@ -140,6 +144,10 @@ class BamlSyncStream(Generic[PartialOutputType, FinalOutputType]):
             if event.is_ok():
                 yield self.__partial_coerce(event)
 
+    def cancel(self) -> None:
+        """Stop the stream. Iteration ends and get_final_response raises BamlCancelledError."""
+        self.__ffi_stream.cancel()
+
     def get_final_response(self):
         self.__drive_to_completion_in_bg()
         if self.__task is not None:
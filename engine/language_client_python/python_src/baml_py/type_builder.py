@@ -6,13 +6,20 @@ from .baml_py import (
     ClassPropertyBuilder as _ClassPropertyBuilder,
     EnumValueBuilder,
     TypeBuilder as _TypeBuilder,
+    BamlRuntime,
 )
 
 
 class TypeBuilder:
-    def __init__(self, classes: typing.Set[str], enums: typing.Set[str]):
+    def __init__(
+        self,
+        classes: typing.Set[str],
+        enums: typing.Set[str],
+        runtime: typing.Optional[BamlRuntime] = None,
+    ):
         self.__classes = classes
         self.__enums = enums
+        self.__runtime = runtime
         self.__tb = _TypeBuilder()
 
     @property
@@ -52,6 +59,16 @@ class TypeBuilder:
     def union(self, types: typing.List[FieldType]):
         return self._tb.union(*types)
 
+    def add_baml(self, baml: str) -> None:
+        """Adds the classes, enums and type aliases declared in `baml`.
+
+        Raises a BamlSchemaError if the source doesn't validate against the
+        types of the BAML project.
+        """
+        if self.__runtime is None:
+            raise ValueError("add_baml needs a TypeBuilder created with a runtime.")
+        self._tb.add_baml(baml, self.__runtime)
+
     def add_class(self, name: str) -> "NewClassBuilder":
         if name in self.__classes:
             raise ValueError(f"Class with name {name} already exists.")
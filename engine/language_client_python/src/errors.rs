@@ -1,7 +1,9 @@
 use baml_runtime::{
     errors::ExposedError, internal::llm_client::LLMResponse, scope_diagnostics::ScopeStack,
+    DiagnosticsError,
 };
-use pyo3::types::PyModule;
+use pyo3::prelude::PyAnyMethods;
+use pyo3::types::{PyDict, PyDictMethods, PyModule};
 use pyo3::{create_exception, pymodule, Bound, PyErr, PyResult, Python};
 
 create_exception!(baml_py, BamlError, pyo3::exceptions::PyException);
@@ -10,6 +12,7 @@ create_exception!(baml_py, BamlError, pyo3::exceptions::PyException);
 create_exception!(baml_py, BamlInvalidArgumentError, BamlError);
 create_exception!(baml_py, BamlClientError, BamlError);
 create_exception!(baml_py, BamlClientHttpError, BamlClientError);
+create_exception!(baml_py, BamlCancelledError, BamlError);
 
 // Define the BamlValidationError exception with additional fields
 // can't use extends=PyException yet https://github.com/PyO3/pyo3/discussions/3838
@@ -25,6 +28,39 @@ fn raise_baml_validation_error(prompt: String, message: String, raw_output: Stri
     })
 }
 
+/// Raises a BamlSchemaError carrying each diagnostic as a dict, with 0-based
+/// lines and columns.
+fn raise_baml_schema_error(diagnostics: &DiagnosticsError) -> PyErr {
+    Python::with_gil(|py| {
+        let errors = diagnostics
+            .errors()
+            .iter()
+            .map(|e| {
+                let ((start_line, start_column), (end_line, end_column)) =
+                    e.span().line_and_column();
+                let error = PyDict::new_bound(py);
+                error.set_item("file_path", e.span().file.path())?;
+                error.set_item("start_line", start_line)?;
+                error.set_item("start_column", start_column)?;
+                error.set_item("end_line", end_line)?;
+                error.set_item("end_column", end_column)?;
+                error.set_item("message", e.message())?;
+                Ok(error)
+            })
+            .collect::<PyResult<Vec<_>>>();
+
+        let inst = errors.and_then(|errors| {
+            py.import_bound("baml_py.internal_monkeypatch")?
+                .getattr("BamlSchemaError")?
+                .call1((diagnostics.to_pretty_string(), errors))
+        });
+        match inst {
+            Ok(inst) => PyErr::from_value_bound(inst),
+            Err(e) => e,
+        }
+    })
+}
+
 /// Defines the errors module with the BamlValidationError exception.
 /// IIRC the name of this function is the name of the module that pyo3 generates (errors.py)
 #[pymodule]
@@ -47,11 +83,19 @@ pub fn errors(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
         "BamlClientHttpError",
         parent_module.py().get_type_bound::<BamlClientHttpError>(),
     )?;
+    parent_module.add(
+        "BamlCancelledError",
+        parent_module.py().get_type_bound::<BamlCancelledError>(),
+    )?;
 
     Ok(())
 }
 
 impl BamlError {
+    pub fn from_diagnostics(diagnostics: DiagnosticsError) -> PyErr {
+        raise_baml_schema_error(&diagnostics)
+    }
+
     pub fn from_anyhow(err: anyhow::Error) -> PyErr {
         if let Some(er) = err.downcast_ref::<ExposedError>() {
             match er {
@@ -79,12 +123,16 @@ impl BamlError {
                             failed.message
                         ))
                     }
+                    baml_runtime::internal::llm_client::ErrorCode::Cancelled => {
+                        PyErr::new::<BamlCancelledError, _>(failed.message.clone())
+                    }
                     baml_runtime::internal::llm_client::ErrorCode::Other(_)
                     | baml_runtime::internal::llm_client::ErrorCode::InvalidAuthentication
                     | baml_runtime::internal::llm_client::ErrorCode::NotSupported
                     | baml_runtime::internal::llm_client::ErrorCode::RateLimited
                     | baml_runtime::internal::llm_client::ErrorCode::ServerError
                     | baml_runtime::internal::llm_client::ErrorCode::ServiceUnavailable
+                    | baml_runtime::internal::llm_client::ErrorCode::Timeout
                     | baml_runtime::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                         PyErr::new::<BamlClientHttpError, _>(format!("{}", err))
                     }
@@ -9,7 +9,7 @@ use pyo3::{
     PyErr, PyObject, PyResult, Python, ToPyObject,
 };
 
-use crate::types::{BamlAudioPy, BamlImagePy};
+use crate::types::{BamlAudioPy, BamlImagePy, BamlPdfPy, BamlVideoPy};
 
 struct SerializationError {
     position: Vec<String>,
@@ -313,6 +313,12 @@ pub fn parse_py_type(
             } else if let Ok(b) = any.downcast_bound::<BamlAudioPy>(py) {
                 let b = b.borrow();
                 Ok(MappedPyType::BamlMedia(b.inner.clone()))
+            } else if let Ok(b) = any.downcast_bound::<BamlPdfPy>(py) {
+                let b = b.borrow();
+                Ok(MappedPyType::BamlMedia(b.inner.clone()))
+            } else if let Ok(b) = any.downcast_bound::<BamlVideoPy>(py) {
+                let b = b.borrow();
+                Ok(MappedPyType::BamlMedia(b.inner.clone()))
             } else {
                 if matches!(unknown_type_handler, UnknownTypeHandler::SerializeAsStr) {
                     // Call the __str__ method on the object
@@ -6,7 +6,7 @@ use crate::types::trace_stats::TraceStats;
 use crate::types::function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
 use crate::types::runtime_ctx_manager::RuntimeContextManager;
 use crate::types::type_builder::TypeBuilder;
-use crate::types::ClientRegistry;
+use crate::types::{CancellationToken, ClientRegistry, HTTPRequest, ResponseCache};
 use baml_runtime::runtime_interface::ExperimentalTracingInterface;
 use baml_runtime::BamlRuntime as CoreBamlRuntime;
 use pyo3::prelude::{pymethods, PyResult};
@@ -113,7 +113,7 @@ impl BamlRuntime {
             .into()
     }
 
-    #[pyo3(signature = (function_name, args, ctx, tb, cb))]
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, cancellation_token = None, bypass_cache = false))]
     fn call_function(
         &self,
         py: Python<'_>,
@@ -122,6 +122,8 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation_token: Option<&CancellationToken>,
+        bypass_cache: bool,
     ) -> PyResult<PyObject> {
         let Some(args) = parse_py_type(args.into_bound(py).to_object(py), false)? else {
             return Err(BamlInvalidArgumentError::new_err(
@@ -139,11 +141,20 @@ impl BamlRuntime {
         let ctx_mng = ctx.inner.clone();
         let tb = tb.map(|tb| tb.inner.clone());
         let cb = cb.map(|cb| cb.inner.clone());
+        let cancellation = cancellation_token.map(|t| t.inner.clone());
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let ctx_mng = ctx_mng;
             let (result, _) = baml_runtime
-                .call_function(function_name, &args_map, &ctx_mng, tb.as_ref(), cb.as_ref())
+                .call_function(
+                    function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    cancellation.as_ref(),
+                    bypass_cache,
+                )
                 .await;
 
             result
@@ -153,14 +164,17 @@ impl BamlRuntime {
         .map(|f| f.into())
     }
 
-    #[pyo3(signature = (function_name, args, ctx, tb, cb))]
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, cancellation_token = None, bypass_cache = false))]
     fn call_function_sync(
         &self,
+        py: Python<'_>,
         function_name: String,
         args: PyObject,
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation_token: Option<&CancellationToken>,
+        bypass_cache: bool,
     ) -> PyResult<FunctionResult> {
         let Some(args) = parse_py_type(args, false)? else {
             return Err(BamlInvalidArgumentError::new_err(
@@ -178,20 +192,27 @@ impl BamlRuntime {
         let tb = tb.map(|tb| tb.inner.clone());
         let cb = cb.map(|cb| cb.inner.clone());
 
-        let (result, _event_id) = self.inner.call_function_sync(
-            function_name,
-            &args_map,
-            &ctx_mng,
-            tb.as_ref(),
-            cb.as_ref(),
-        );
+        let cancellation = cancellation_token.map(|t| &t.inner);
+        // Release the GIL while blocking so the token can be cancelled from
+        // another Python thread.
+        let (result, _event_id) = py.allow_threads(|| {
+            self.inner.call_function_sync(
+                function_name,
+                &args_map,
+                &ctx_mng,
+                tb.as_ref(),
+                cb.as_ref(),
+                cancellation,
+                bypass_cache,
+            )
+        });
 
         result
             .map(FunctionResult::from)
             .map_err(BamlError::from_anyhow)
     }
 
-    #[pyo3(signature = (function_name, args, on_event, ctx, tb, cb))]
+    #[pyo3(signature = (function_name, args, on_event, ctx, tb, cb, bypass_cache = false))]
     fn stream_function(
         &self,
         py: Python<'_>,
@@ -201,6 +222,7 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        bypass_cache: bool,
     ) -> PyResult<FunctionResultStream> {
         let Some(args) = parse_py_type(args.into_bound(py).to_object(py), false)? else {
             return Err(BamlInvalidArgumentError::new_err(
@@ -221,6 +243,7 @@ impl BamlRuntime {
                 &ctx,
                 tb.map(|tb| tb.inner.clone()).as_ref(),
                 cb.map(|cb| cb.inner.clone()).as_ref(),
+                bypass_cache,
             )
             .map_err(BamlError::from_anyhow)?;
 
@@ -232,7 +255,7 @@ impl BamlRuntime {
         ))
     }
 
-    #[pyo3(signature = (function_name, args, on_event, ctx, tb, cb))]
+    #[pyo3(signature = (function_name, args, on_event, ctx, tb, cb, bypass_cache = false))]
     fn stream_function_sync(
         &self,
         py: Python<'_>,
@@ -242,6 +265,7 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        bypass_cache: bool,
     ) -> PyResult<SyncFunctionResultStream> {
         let Some(args) = parse_py_type(args.into_bound(py).to_object(py), false)? else {
             return Err(BamlInvalidArgumentError::new_err(
@@ -262,6 +286,7 @@ impl BamlRuntime {
                 &ctx,
                 tb.map(|tb| tb.inner.clone()).as_ref(),
                 cb.map(|cb| cb.inner.clone()).as_ref(),
+                bypass_cache,
             )
             .map_err(BamlError::from_anyhow)?;
 
@@ -273,6 +298,115 @@ impl BamlRuntime {
         ))
     }
 
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, stream = false))]
+    fn render_request(
+        &self,
+        py: Python<'_>,
+        function_name: String,
+        args: PyObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> PyResult<PyObject> {
+        let Some(args) = parse_py_type(args.into_bound(py).to_object(py), false)? else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args, perhaps you used a non-serializable type?",
+            ));
+        };
+        let Some(args_map) = args.as_map_owned() else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args. Expect kwargs",
+            ));
+        };
+
+        let baml_runtime = self.inner.clone();
+        let ctx_mng = ctx.inner.clone();
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            baml_runtime
+                .render_request(
+                    &function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    stream,
+                )
+                .await
+                .map(HTTPRequest::from)
+                .map_err(BamlError::from_anyhow)
+        })
+        .map(|f| f.into())
+    }
+
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, stream = false))]
+    fn render_request_sync(
+        &self,
+        function_name: String,
+        args: PyObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> PyResult<HTTPRequest> {
+        let Some(args) = parse_py_type(args, false)? else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args, perhaps you used a non-serializable type?",
+            ));
+        };
+        let Some(args_map) = args.as_map_owned() else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args as a map",
+            ));
+        };
+
+        self.inner
+            .render_request_sync(
+                &function_name,
+                &args_map,
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+                cb.map(|cb| &cb.inner),
+                stream,
+            )
+            .map(HTTPRequest::from)
+            .map_err(BamlError::from_anyhow)
+    }
+
+    #[pyo3(signature = (function_name, llm_response, ctx, tb, cb, allow_partials = false))]
+    fn parse_llm_response(
+        &self,
+        function_name: String,
+        llm_response: String,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        allow_partials: bool,
+    ) -> PyResult<FunctionResult> {
+        self.inner
+            .parse_llm_response(
+                &function_name,
+                llm_response,
+                allow_partials,
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+                cb.map(|cb| &cb.inner),
+            )
+            .map(FunctionResult::from)
+            .map_err(BamlError::from_anyhow)
+    }
+
+    /// Cache the LLM responses of every call made through this runtime.
+    /// Pass None to turn caching off again.
+    #[pyo3(signature = (cache))]
+    fn set_response_cache(&self, cache: Option<&ResponseCache>) {
+        self.inner
+            .set_response_cache(cache.map(|cache| cache.inner.clone()));
+    }
+
     #[pyo3()]
     fn flush(&self) -> PyResult<()> {
         self.inner.flush().map_err(BamlError::from_anyhow)
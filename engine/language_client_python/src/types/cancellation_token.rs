@@ -0,0 +1,23 @@
+use pyo3::prelude::pymethods;
+
+crate::lang_wrapper!(CancellationToken, baml_runtime::CancellationToken);
+
+#[pymethods]
+impl CancellationToken {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            inner: baml_runtime::CancellationToken::new(),
+        }
+    }
+
+    /// Stop every call this token was passed to. Calls fail with a
+    /// BamlCancelledError.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
@@ -11,7 +11,8 @@ crate::lang_wrapper!(
     baml_runtime::FunctionResultStream, thread_safe,
     on_event: Option<PyObject>,
     tb: Option<baml_runtime::type_builder::TypeBuilder>,
-    cb: Option<baml_runtime::client_registry::ClientRegistry>
+    cb: Option<baml_runtime::client_registry::ClientRegistry>,
+    cancellation: baml_runtime::CancellationToken
 );
 
 crate::lang_wrapper!(
@@ -19,7 +20,8 @@ crate::lang_wrapper!(
     baml_runtime::FunctionResultStream, sync_thread_safe,
     on_event: Option<PyObject>,
     tb: Option<baml_runtime::type_builder::TypeBuilder>,
-    cb: Option<baml_runtime::client_registry::ClientRegistry>
+    cb: Option<baml_runtime::client_registry::ClientRegistry>,
+    cancellation: baml_runtime::CancellationToken
 );
 
 impl FunctionResultStream {
@@ -30,6 +32,7 @@ impl FunctionResultStream {
         cb: Option<baml_runtime::client_registry::ClientRegistry>,
     ) -> Self {
         Self {
+            cancellation: inner.cancellation_token(),
             inner: std::sync::Arc::new(tokio::sync::Mutex::new(inner)),
             on_event: event,
             tb,
@@ -46,6 +49,7 @@ impl SyncFunctionResultStream {
         cb: Option<baml_runtime::client_registry::ClientRegistry>,
     ) -> Self {
         Self {
+            cancellation: inner.cancellation_token(),
             inner: std::sync::Arc::new(std::sync::Mutex::new(inner)),
             on_event: event,
             tb,
@@ -60,6 +64,11 @@ impl FunctionResultStream {
         format!("FunctionResultStream")
     }
 
+    /// Stop the stream. Safe to call while `done` is running.
+    fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
     /// Set the callback to be called when an event is received
     ///
     /// Callback will take an instance of FunctionResult
@@ -109,6 +118,11 @@ impl SyncFunctionResultStream {
         format!("SyncFunctionResultStream")
     }
 
+    /// Stop the stream. Safe to call while `done` is running.
+    fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
     /// Set the callback to be called when an event is received
     ///
     /// Callback will take an instance of FunctionResult
@@ -122,11 +136,11 @@ impl SyncFunctionResultStream {
         slf
     }
 
-    fn done(&self, ctx: &RuntimeContextManager) -> PyResult<FunctionResult> {
+    fn done(&self, py: Python<'_>, ctx: &RuntimeContextManager) -> PyResult<FunctionResult> {
         let inner = self.inner.clone();
 
         let on_event = self.on_event.as_ref().map(|cb| {
-            let cb = Python::with_gil(|py| cb.clone_ref(py));
+            let cb = cb.clone_ref(py);
             move |event| {
                 let partial = FunctionResult::from(event);
                 let res = Python::with_gil(|py| cb.call1(py, (partial,))).map(|_| ());
@@ -141,8 +155,12 @@ impl SyncFunctionResultStream {
         let cb = self.cb.as_ref().map(|cb| cb.clone());
 
         let ctx_mng = ctx_mng;
-        let mut locked = inner.lock().unwrap();
-        let (res, _) = locked.run_sync(on_event, &ctx_mng, tb.as_ref(), cb.as_ref());
+        // Release the GIL while blocking so `cancel` can be called from
+        // another Python thread.
+        let (res, _) = py.allow_threads(|| {
+            let mut locked = inner.lock().unwrap();
+            locked.run_sync(on_event, &ctx_mng, tb.as_ref(), cb.as_ref())
+        });
         res.map(FunctionResult::from)
             .map_err(BamlError::from_anyhow)
             .map(|f| f.into())
@@ -5,7 +5,7 @@ use pyo3::{Bound, IntoPy, PyAny, PyObject, Python};
 
 use crate::errors::BamlError;
 
-use super::{BamlAudioPy, BamlImagePy};
+use super::{BamlAudioPy, BamlImagePy, BamlPdfPy, BamlVideoPy};
 
 crate::lang_wrapper!(FunctionResult, baml_runtime::FunctionResult);
 
@@ -99,6 +99,10 @@ fn pythonize_strict(
             baml_types::BamlMediaType::Audio => {
                 Ok(BamlAudioPy::from(baml_media.clone()).into_py(py))
             }
+            baml_types::BamlMediaType::Pdf => Ok(BamlPdfPy::from(baml_media.clone()).into_py(py)),
+            baml_types::BamlMediaType::Video => {
+                Ok(BamlVideoPy::from(baml_media.clone()).into_py(py))
+            }
         },
         BamlValueWithMeta::Enum(enum_name, ref value, _) => {
             let enum_type = match enum_module.getattr(enum_name.as_str()) {
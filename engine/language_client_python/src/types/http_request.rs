@@ -0,0 +1,35 @@
+use pyo3::prelude::{pymethods, PyObject, PyResult, Python};
+use pythonize::pythonize;
+
+crate::lang_wrapper!(HTTPRequest, baml_runtime::HTTPRequest);
+
+#[pymethods]
+impl HTTPRequest {
+    #[getter]
+    pub fn url(&self) -> String {
+        self.inner.url.clone()
+    }
+
+    #[getter]
+    pub fn method(&self) -> String {
+        self.inner.method.clone()
+    }
+
+    #[getter]
+    pub fn headers(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize(py, &self.inner.headers)?)
+    }
+
+    /// The JSON body, as a dict.
+    #[getter]
+    pub fn body(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize(py, &self.inner.body)?)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HTTPRequest(method={:?}, url={:?}, headers={:?}, body={})",
+            self.inner.method, self.inner.url, self.inner.headers, self.inner.body
+        )
+    }
+}
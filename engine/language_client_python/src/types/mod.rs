@@ -2,21 +2,31 @@ mod lang_wrapper;
 
 pub use client_registry::ClientRegistry;
 pub(crate) mod audio;
+pub(crate) mod cancellation_token;
 pub(crate) mod client_registry;
 pub(crate) mod function_result_stream;
 pub(crate) mod function_results;
+pub(crate) mod http_request;
 pub(crate) mod image;
+pub(crate) mod response_cache;
 pub(super) mod media_repr;
+pub(crate) mod pdf;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod span;
 pub(crate) mod trace_stats;
 pub(crate) mod type_builder;
+pub(crate) mod video;
 
 pub use audio::BamlAudioPy;
+pub use cancellation_token::CancellationToken;
 pub use function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
 pub use function_results::FunctionResult;
+pub use http_request::HTTPRequest;
 pub use image::BamlImagePy;
+pub use pdf::BamlPdfPy;
+pub use response_cache::ResponseCache;
 
 pub use runtime_ctx_manager::RuntimeContextManager;
 pub use span::BamlSpan;
 pub use type_builder::*;
+pub use video::BamlVideoPy;
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use baml_runtime::{CacheFuture, ResponseCacheBackend};
+use pyo3::prelude::{pymethods, PyObject, Python};
+
+crate::lang_wrapper!(ResponseCache, baml_runtime::ResponseCache);
+
+#[pymethods]
+impl ResponseCache {
+    /// A cache that lives as long as the process.
+    #[staticmethod]
+    #[pyo3(signature = (ttl_secs = None))]
+    pub fn memory(ttl_secs: Option<u64>) -> Self {
+        baml_runtime::ResponseCache::memory()
+            .with_ttl(ttl_secs.map(Duration::from_secs))
+            .into()
+    }
+
+    /// A cache that stores one file per response in `path`.
+    #[staticmethod]
+    #[pyo3(signature = (path, ttl_secs = None))]
+    pub fn directory(path: String, ttl_secs: Option<u64>) -> Self {
+        baml_runtime::ResponseCache::directory(path)
+            .with_ttl(ttl_secs.map(Duration::from_secs))
+            .into()
+    }
+
+    /// A cache backed by user functions: `get(key) -> Optional[str]` and
+    /// `set(key, value) -> None`.
+    #[staticmethod]
+    #[pyo3(signature = (get, set, ttl_secs = None))]
+    pub fn callback(get: PyObject, set: PyObject, ttl_secs: Option<u64>) -> Self {
+        baml_runtime::ResponseCache::new(CallbackCache { get, set })
+            .with_ttl(ttl_secs.map(Duration::from_secs))
+            .into()
+    }
+}
+
+struct CallbackCache {
+    get: PyObject,
+    set: PyObject,
+}
+
+impl ResponseCacheBackend for CallbackCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>> {
+        let value =
+            Python::with_gil(|py| self.get.call1(py, (key,))?.extract::<Option<String>>(py));
+        Box::pin(async move { Ok(value?) })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()> {
+        let result = Python::with_gil(|py| self.set.call1(py, (key, value)).map(|_| ()));
+        Box::pin(async move { Ok(result?) })
+    }
+}
@@ -1,3 +1,5 @@
+use crate::errors::BamlError;
+use crate::runtime::BamlRuntime;
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
 use pyo3::{
@@ -45,6 +47,13 @@ impl TypeBuilder {
         }
     }
 
+    /// Adds the classes, enums and type aliases declared in `baml`.
+    pub fn add_baml(&self, baml: &str, rt: &BamlRuntime) -> PyResult<()> {
+        self.inner
+            .add_baml(baml, &rt.inner)
+            .map_err(BamlError::from_diagnostics)
+    }
+
     pub fn literal_string(&self, value: &str) -> FieldType {
         baml_types::FieldType::literal_string(value.to_string()).into()
     }
@@ -11,6 +11,7 @@ use crate::Result;
 #[magnus::wrap(class = "Baml::Ffi::FunctionResultStream", free_immediately, size)]
 pub struct FunctionResultStream {
     inner: RefCell<baml_runtime::FunctionResultStream>,
+    cancellation: baml_runtime::CancellationToken,
     t: Arc<tokio::runtime::Runtime>,
 }
 
@@ -20,6 +21,7 @@ impl FunctionResultStream {
         t: Arc<tokio::runtime::Runtime>,
     ) -> Self {
         Self {
+            cancellation: inner.cancellation_token(),
             inner: RefCell::new(inner),
             t,
         }
@@ -55,6 +57,12 @@ impl FunctionResultStream {
         }
     }
 
+    /// Stops the stream. Since `done` holds the GVL, this is meant to be called
+    /// from the block passed to `done`.
+    fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
     /// For usage in magnus::init
     ///
     /// TODO: use traits and macros to implement this
@@ -62,6 +70,7 @@ impl FunctionResultStream {
         let cls = module.define_class("FunctionResultStream", class::object())?;
 
         cls.define_method("done", method!(FunctionResultStream::done, 1))?;
+        cls.define_method("cancel", method!(FunctionResultStream::cancel, 0))?;
 
         Ok(())
     }
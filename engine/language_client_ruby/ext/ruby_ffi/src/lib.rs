@@ -1,6 +1,6 @@
 use baml_runtime::BamlRuntime;
 use baml_types::BamlValue;
-use magnus::{class, function, method, prelude::*, Error, RHash, Ruby};
+use magnus::{class, function, method, prelude::*, Error, RHash, Ruby, Value};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -129,6 +129,8 @@ impl BamlRuntimeFfi {
             &ctx.inner,
             type_registry.map(|t| &t.inner),
             client_registry.map(|c| c.inner.borrow_mut()).as_deref(),
+            None,
+            false,
         )) {
             (Ok(res), _) => Ok(FunctionResult::new(res)),
             (Err(e), _) => Err(Error::new(
@@ -170,6 +172,7 @@ impl BamlRuntimeFfi {
             &ctx.inner,
             type_registry.map(|t| &t.inner),
             client_registry.map(|c| c.inner.borrow_mut()).as_deref(),
+            false,
         ) {
             Ok(res) => Ok(FunctionResultStream::new(res, rb_self.t.clone())),
             Err(e) => Err(Error::new(
@@ -183,6 +186,74 @@ impl BamlRuntimeFfi {
 
         retval
     }
+
+    fn render_request(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        function_name: String,
+        args: RHash,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+        client_registry: Option<&types::client_registry::ClientRegistry>,
+        stream: bool,
+    ) -> Result<Value> {
+        let args = match ruby_to_json::RubyToJson::convert_hash_to_json(args) {
+            Ok(args) => args.into_iter().collect(),
+            Err(e) => {
+                return Err(Error::new(
+                    ruby.exception_syntax_error(),
+                    format!("error while parsing render_request args:\n{}", e),
+                ));
+            }
+        };
+
+        match rb_self.t.block_on(rb_self.inner.render_request(
+            &function_name,
+            &args,
+            &ctx.inner,
+            type_registry.map(|t| &t.inner),
+            client_registry.map(|c| c.inner.borrow_mut()).as_deref(),
+            stream,
+        )) {
+            Ok(request) => serde_magnus::serialize(&request),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "{:?}",
+                    e.context(format!("error while rendering the request for {function_name}"))
+                ),
+            )),
+        }
+    }
+
+    fn parse_llm_response(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        function_name: String,
+        llm_response: String,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+        client_registry: Option<&types::client_registry::ClientRegistry>,
+        allow_partials: bool,
+    ) -> Result<FunctionResult> {
+        match rb_self.inner.parse_llm_response(
+            &function_name,
+            llm_response,
+            allow_partials,
+            &ctx.inner,
+            type_registry.map(|t| &t.inner),
+            client_registry.map(|c| c.inner.borrow_mut()).as_deref(),
+        ) {
+            Ok(res) => Ok(FunctionResult::new(res)),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "{:?}",
+                    e.context(format!("error while parsing the response for {function_name}"))
+                ),
+            )),
+        }
+    }
 }
 
 fn invoke_runtime_cli(ruby: &Ruby, argv0: String, argv: Vec<String>) -> Result<()> {
@@ -253,6 +324,14 @@ fn init(ruby: &Ruby) -> Result<()> {
         "stream_function",
         method!(BamlRuntimeFfi::stream_function, 5),
     )?;
+    runtime_class.define_method(
+        "render_request",
+        method!(BamlRuntimeFfi::render_request, 6),
+    )?;
+    runtime_class.define_method(
+        "parse_llm_response",
+        method!(BamlRuntimeFfi::parse_llm_response, 6),
+    )?;
 
     FunctionResult::define_in_ruby(&module)?;
     FunctionResultStream::define_in_ruby(&module)?;
@@ -269,6 +348,8 @@ fn init(ruby: &Ruby) -> Result<()> {
     types::client_registry::ClientRegistry::define_in_ruby(&module)?;
     types::media::Audio::define_in_ruby(&module)?;
     types::media::Image::define_in_ruby(&module)?;
+    types::media::Pdf::define_in_ruby(&module)?;
+    types::media::Video::define_in_ruby(&module)?;
 
     // everything below this is for our own testing purposes
     module.define_module_function(
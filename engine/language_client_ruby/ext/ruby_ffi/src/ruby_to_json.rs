@@ -8,7 +8,7 @@ use std::result::Result;
 
 use crate::types::{
     self,
-    media::{Audio, Image},
+    media::{Audio, Image, Pdf, Video},
 };
 
 struct SerializationError {
@@ -231,6 +231,14 @@ impl<'rb> RubyToJson<'rb> {
             return self.to_type::<Image>(any, field_pos);
         }
 
+        if self.is_type::<Pdf>(any) {
+            return self.to_type::<Pdf>(any, field_pos);
+        }
+
+        if self.is_type::<Video>(any) {
+            return self.to_type::<Video>(any, field_pos);
+        }
+
         Err(vec![SerializationError {
             position: field_pos,
             message: format!(
@@ -2,8 +2,8 @@ use crate::Result;
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
 use magnus::{
-    class, function, method, scan_args::scan_args, try_convert::TryConvertOwned, Module, Object,
-    RModule, Value,
+    class, function, method, scan_args::scan_args, try_convert::TryConvertOwned, Error, Exception,
+    Module, Object, RClass, RModule, Ruby, Value,
 };
 
 #[magnus::wrap(class = "Baml::Ffi::TypeBuilder", free_immediately, size)]
@@ -53,6 +53,37 @@ impl TypeBuilder {
         }
     }
 
+    /// Adds the classes, enums and type aliases declared in `baml`, raising a
+    /// Baml::SchemaError if they don't validate.
+    fn add_baml(
+        ruby: &Ruby,
+        rb_self: &TypeBuilder,
+        baml: String,
+        runtime: &crate::BamlRuntimeFfi,
+    ) -> Result<()> {
+        let Err(diagnostics) = rb_self.inner.add_baml(&baml, &runtime.inner) else {
+            return Ok(());
+        };
+
+        let errors = ruby.ary_new();
+        for e in diagnostics.errors() {
+            let ((start_line, start_column), (end_line, end_column)) = e.span().line_and_column();
+            let error = ruby.hash_new();
+            error.aset(ruby.sym_new("file_path"), e.span().file.path())?;
+            error.aset(ruby.sym_new("start_line"), start_line)?;
+            error.aset(ruby.sym_new("start_column"), start_column)?;
+            error.aset(ruby.sym_new("end_line"), end_line)?;
+            error.aset(ruby.sym_new("end_column"), end_column)?;
+            error.aset(ruby.sym_new("message"), e.message())?;
+            errors.push(error)?;
+        }
+
+        let schema_error = ruby.eval::<RClass>("Baml::SchemaError")?;
+        let exception: Exception =
+            schema_error.funcall("new", (diagnostics.to_pretty_string(), errors))?;
+        Err(Error::from(exception))
+    }
+
     pub fn list(&self, inner: &FieldType) -> FieldType {
         inner.inner.lock().unwrap().clone().as_list().into()
     }
@@ -120,6 +151,7 @@ impl TypeBuilder {
         cls.define_method("enum", method!(TypeBuilder::r#enum, 1))?;
         // "class" is used by Kernel: https://ruby-doc.org/core-3.0.2/Kernel.html#method-i-class
         cls.define_method("class_", method!(TypeBuilder::class, 1))?;
+        cls.define_method("add_baml", method!(TypeBuilder::add_baml, 2))?;
         cls.define_method("list", method!(TypeBuilder::list, 1))?;
         cls.define_method("optional", method!(TypeBuilder::optional, 1))?;
         cls.define_method("string", method!(TypeBuilder::string, 0))?;
@@ -8,11 +8,26 @@ end
 require_relative "stream"
 require_relative "struct"
 require_relative "checked"
+require_relative "stream_state"
 
 module Baml
   ClientRegistry = Baml::Ffi::ClientRegistry
   Image = Baml::Ffi::Image
   Audio = Baml::Ffi::Audio
+  Pdf = Baml::Ffi::Pdf
+  Video = Baml::Ffi::Video
+
+  # Raised when BAML source passed to the runtime doesn't validate. Each
+  # diagnostic is a Hash with :file_path, :message and 0-based :start_line,
+  # :start_column, :end_line and :end_column.
+  class SchemaError < StandardError
+    attr_reader :diagnostics
+
+    def initialize(message, diagnostics)
+      super(message)
+      @diagnostics = diagnostics
+    end
+  end
 
   # Reexport Checked types.
   Checked = Baml::Checks::Checked
@@ -55,6 +55,13 @@ module Baml
     end
 
 
+    # Stops the stream. Call this from the block passed to `each`; the stream
+    # then ends and the pending call raises.
+    sig {void}
+    def cancel
+      @ffi_stream.cancel
+    end
+
     # Gets the final response from the stream.
     #
     # @return [FinalType] the parsed final response
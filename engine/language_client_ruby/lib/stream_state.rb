@@ -0,0 +1,20 @@
+require "sorbet-runtime"
+
+module Baml
+  # A field marked `@stream.with_state` in a partial result: the value so far,
+  # and whether it's still streaming in ("Incomplete") or done ("Complete").
+  class StreamState < T::Struct
+    extend T::Sig
+
+    extend T::Generic
+
+    Value = type_member
+
+    const :value, Value
+    const :state, String
+
+    def initialize(props)
+      super(value: props[:value], state: props[:state])
+    end
+  end
+end
@@ -1,4 +1,4 @@
-export { BamlRuntime, FunctionResult, FunctionResultStream, BamlImage as Image, ClientBuilder, BamlAudio as Audio, invoke_runtime_cli, ClientRegistry, BamlLogEvent, } from './native';
+export { BamlRuntime, FunctionResult, FunctionResultStream, BamlImage as Image, ClientBuilder, BamlAudio as Audio, BamlPdf as Pdf, BamlVideo as Video, invoke_runtime_cli, ClientRegistry, BamlLogEvent, CancellationToken, ResponseCache, HTTPRequest, } from './native';
 export { BamlStream } from './stream';
 export { BamlCtxManager } from './async_context_vars';
 export declare class BamlValidationError extends Error {
@@ -8,5 +8,19 @@ export declare class BamlValidationError extends Error {
     static from(error: Error): BamlValidationError | Error;
     toJSON(): string;
 }
+export interface BamlDiagnostic {
+    file_path: string;
+    /** 0-based */
+    start_line: number;
+    start_column: number;
+    end_line: number;
+    end_column: number;
+    message: string;
+}
+export declare class BamlSchemaError extends Error {
+    diagnostics: BamlDiagnostic[];
+    constructor(message: string, diagnostics: BamlDiagnostic[]);
+    static from(error: Error): BamlSchemaError | Error;
+}
 export declare function createBamlValidationError(error: Error): BamlValidationError | Error;
 //# sourceMappingURL=index.d.ts.map
\ No newline at end of file
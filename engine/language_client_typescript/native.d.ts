@@ -18,16 +18,33 @@ export declare class BamlImage {
   toJSON(): any
 }
 
+export declare class BamlPdf {
+  static fromUrl(url: string): BamlPdf
+  static fromBase64(mediaType: string, base64: string): BamlPdf
+  isUrl(): boolean
+  asUrl(): string
+  asBase64(): [string, string]
+  toJSON(): any
+}
+
 export declare class BamlRuntime {
   static fromDirectory(directory: string, envVars: Record<string, string>): BamlRuntime
   static fromFiles(rootPath: string, files: Record<string, string>, envVars: Record<string, string>): BamlRuntime
   reset(rootPath: string, files: Record<string, string>, envVars: Record<string, string>): void
   createContextManager(): RuntimeContextManager
-  callFunction(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null): Promise<FunctionResult>
-  callFunctionSync(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null): FunctionResult
-  streamFunction(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
-  streamFunctionSync(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
+  callFunction(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null, cancellationToken?: CancellationToken | undefined | null, bypassCache?: boolean | undefined | null): Promise<FunctionResult>
+  callFunctionSync(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null, cancellationToken?: CancellationToken | undefined | null, bypassCache?: boolean | undefined | null): FunctionResult
+  streamFunction(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null, bypassCache?: boolean | undefined | null): FunctionResultStream
+  streamFunctionSync(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null, bypassCache?: boolean | undefined | null): FunctionResultStream
   setLogEventCallback(func?: undefined | ((err: any, param: BamlLogEvent) => void)): void
+  renderRequest(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null, stream?: boolean | undefined | null): Promise<HTTPRequest>
+  renderRequestSync(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null, stream?: boolean | undefined | null): HTTPRequest
+  parseLlmResponse(functionName: string, llmResponse: string, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null, allowPartials?: boolean | undefined | null): FunctionResult
+  /**
+   * Cache the LLM responses of every call made through this runtime.
+   * Pass `undefined` to turn caching off again.
+   */
+  setResponseCache(cache?: ResponseCache | undefined | null): void
   flush(): void
   drainStats(): TraceStats
 }
@@ -37,6 +54,22 @@ export declare class BamlSpan {
   finish(result: any, ctx: RuntimeContextManager): any
 }
 
+export declare class BamlVideo {
+  static fromUrl(url: string): BamlVideo
+  static fromBase64(mediaType: string, base64: string): BamlVideo
+  isUrl(): boolean
+  asUrl(): string
+  asBase64(): [string, string]
+  toJSON(): any
+}
+
+export declare class CancellationToken {
+  constructor()
+  /** Stop every call this token was passed to. */
+  cancel(): void
+  isCancelled(): boolean
+}
+
 export declare class ClassBuilder {
   field(): FieldType
   property(name: string): ClassPropertyBuilder
@@ -78,9 +111,29 @@ export declare class FunctionResult {
 
 export declare class FunctionResultStream {
   onEvent(func: (err: any, param: FunctionResult) => void): void
+  /** Stop the stream. Safe to call while `done` is running. */
+  cancel(): void
   done(rctx: RuntimeContextManager): Promise<FunctionResult>
 }
 
+export declare class HTTPRequest {
+  get url(): string
+  get method(): string
+  get headers(): Record<string, string>
+  /** The JSON body, as an object. */
+  get body(): any
+  toJSON(): any
+}
+
+export declare class ResponseCache {
+  /** A cache that lives as long as the process. */
+  static memory(ttlSecs?: number | undefined | null): ResponseCache
+  /** A cache that stores one file per response in `path`. */
+  static directory(path: string, ttlSecs?: number | undefined | null): ResponseCache
+  /** A cache backed by user functions. Both must return promises. */
+  static callback(get: (err: any, key: string) => Promise<string | null | undefined>, set: (err: any, key: string, value: string) => Promise<void>, ttlSecs?: number | undefined | null): ResponseCache
+}
+
 export declare class RuntimeContextManager {
   upsertTags(tags: any): void
   deepClone(): RuntimeContextManager
@@ -101,6 +154,7 @@ export declare class TypeBuilder {
   constructor()
   getEnum(name: string): EnumBuilder
   getClass(name: string): ClassBuilder
+  addBaml(baml: string, rt: BamlRuntime): void
   list(inner: FieldType): FieldType
   optional(inner: FieldType): FieldType
   string(): FieldType
@@ -363,8 +363,11 @@ if (!nativeBinding) {
 
 module.exports.BamlAudio = nativeBinding.BamlAudio
 module.exports.BamlImage = nativeBinding.BamlImage
+module.exports.BamlPdf = nativeBinding.BamlPdf
 module.exports.BamlRuntime = nativeBinding.BamlRuntime
 module.exports.BamlSpan = nativeBinding.BamlSpan
+module.exports.BamlVideo = nativeBinding.BamlVideo
+module.exports.CancellationToken = nativeBinding.CancellationToken
 module.exports.ClassBuilder = nativeBinding.ClassBuilder
 module.exports.ClassPropertyBuilder = nativeBinding.ClassPropertyBuilder
 module.exports.ClientRegistry = nativeBinding.ClientRegistry
@@ -373,6 +376,8 @@ module.exports.EnumValueBuilder = nativeBinding.EnumValueBuilder
 module.exports.FieldType = nativeBinding.FieldType
 module.exports.FunctionResult = nativeBinding.FunctionResult
 module.exports.FunctionResultStream = nativeBinding.FunctionResultStream
+module.exports.HTTPRequest = nativeBinding.HTTPRequest
+module.exports.ResponseCache = nativeBinding.ResponseCache
 module.exports.RuntimeContextManager = nativeBinding.RuntimeContextManager
 module.exports.TraceStats = nativeBinding.TraceStats
 module.exports.TypeBuilder = nativeBinding.TypeBuilder
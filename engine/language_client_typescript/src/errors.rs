@@ -1,5 +1,6 @@
 use baml_runtime::{
     errors::ExposedError, internal::llm_client::LLMResponse, scope_diagnostics::ScopeStack,
+    DiagnosticsError,
 };
 
 // napi::Error::new(napi::Status::GenericFailure, e.to_string()))
@@ -37,12 +38,17 @@ pub fn from_anyhow_error(err: anyhow::Error) -> napi::Error {
                         failed.message
                     ),
                 ),
+                baml_runtime::internal::llm_client::ErrorCode::Cancelled => napi::Error::new(
+                    napi::Status::GenericFailure,
+                    format!("BamlError: BamlCancelledError: {}", failed.message),
+                ),
                 baml_runtime::internal::llm_client::ErrorCode::Other(_)
                 | baml_runtime::internal::llm_client::ErrorCode::InvalidAuthentication
                 | baml_runtime::internal::llm_client::ErrorCode::NotSupported
                 | baml_runtime::internal::llm_client::ErrorCode::RateLimited
                 | baml_runtime::internal::llm_client::ErrorCode::ServerError
                 | baml_runtime::internal::llm_client::ErrorCode::ServiceUnavailable
+                | baml_runtime::internal::llm_client::ErrorCode::Timeout
                 | baml_runtime::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                     napi::Error::new(
                         napi::Status::GenericFailure,
@@ -79,3 +85,28 @@ pub fn throw_baml_validation_error(prompt: &str, raw_output: &str, message: &str
     });
     napi::Error::new(napi::Status::GenericFailure, error_json.to_string())
 }
+
+/// Lines and columns are 0-based.
+pub fn throw_baml_schema_error(diagnostics: &DiagnosticsError) -> napi::Error {
+    let errors = diagnostics
+        .errors()
+        .iter()
+        .map(|e| {
+            let ((start_line, start_column), (end_line, end_column)) = e.span().line_and_column();
+            serde_json::json!({
+                "file_path": e.span().file.path(),
+                "start_line": start_line,
+                "start_column": start_column,
+                "end_line": end_line,
+                "end_column": end_column,
+                "message": e.message(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let error_json = serde_json::json!({
+        "type": "BamlSchemaError",
+        "message": format!("BamlSchemaError: {}", diagnostics.to_pretty_string()),
+        "diagnostics": errors,
+    });
+    napi::Error::new(napi::Status::InvalidArg, error_json.to_string())
+}
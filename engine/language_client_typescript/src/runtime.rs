@@ -1,8 +1,11 @@
 use crate::errors::{from_anyhow_error, invalid_argument_error};
 use crate::parse_ts_types;
+use crate::types::cancellation_token::CancellationToken;
 use crate::types::client_registry::ClientRegistry;
 use crate::types::function_result_stream::FunctionResultStream;
 use crate::types::function_results::FunctionResult;
+use crate::types::http_request::HTTPRequest;
+use crate::types::response_cache::ResponseCache;
 use crate::types::runtime_ctx_manager::RuntimeContextManager;
 use crate::types::trace_stats::TraceStats;
 use crate::types::type_builder::TypeBuilder;
@@ -99,6 +102,8 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation_token: Option<&CancellationToken>,
+        bypass_cache: Option<bool>,
     ) -> napi::Result<JsObject> {
         let args = parse_ts_types::js_object_to_baml_value(env, args)?;
 
@@ -114,10 +119,19 @@ impl BamlRuntime {
         let ctx_mng = ctx.inner.clone();
         let tb = tb.map(|tb| tb.inner.clone());
         let cb = cb.map(|cb| cb.inner.clone());
+        let cancellation = cancellation_token.map(|t| t.inner.clone());
 
         let fut = async move {
             let result = baml_runtime
-                .call_function(function_name, &args_map, &ctx_mng, tb.as_ref(), cb.as_ref())
+                .call_function(
+                    function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    cancellation.as_ref(),
+                    bypass_cache.unwrap_or(false),
+                )
                 .await;
 
             result
@@ -138,6 +152,8 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
+        cancellation_token: Option<&CancellationToken>,
+        bypass_cache: Option<bool>,
     ) -> napi::Result<FunctionResult> {
         let args = parse_ts_types::js_object_to_baml_value(env, args)?;
 
@@ -158,6 +174,8 @@ impl BamlRuntime {
             &ctx_mng,
             tb.as_ref(),
             cb.as_ref(),
+            cancellation_token.map(|t| &t.inner),
+            bypass_cache.unwrap_or(false),
         );
 
         result
@@ -177,6 +195,7 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         client_registry: Option<&ClientRegistry>,
+        bypass_cache: Option<bool>,
     ) -> napi::Result<FunctionResultStream> {
         let args: BamlValue = parse_ts_types::js_object_to_baml_value(env, args)?;
         if !args.is_map() {
@@ -198,6 +217,7 @@ impl BamlRuntime {
                 &ctx,
                 tb.as_ref(),
                 client_registry.as_ref(),
+                bypass_cache.unwrap_or(false),
             )
             .map_err(|e| from_anyhow_error(e))?;
 
@@ -221,6 +241,7 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         tb: Option<&TypeBuilder>,
         client_registry: Option<&ClientRegistry>,
+        bypass_cache: Option<bool>,
     ) -> napi::Result<FunctionResultStream> {
         let args: BamlValue = parse_ts_types::js_object_to_baml_value(env, args)?;
         if !args.is_map() {
@@ -242,6 +263,7 @@ impl BamlRuntime {
                 &ctx,
                 tb.as_ref(),
                 client_registry.as_ref(),
+                bypass_cache.unwrap_or(false),
             )
             .map_err(|e| from_anyhow_error(e))?;
 
@@ -345,6 +367,115 @@ impl BamlRuntime {
         env.get_undefined()
     }
 
+    /// Cache the LLM responses of every call made through this runtime.
+    /// Pass `undefined` to turn caching off again.
+    #[napi(ts_return_type = "Promise<HTTPRequest>")]
+    pub fn render_request(
+        &self,
+        env: Env,
+        function_name: String,
+        #[napi(ts_arg_type = "{ [string]: any }")] args: JsObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: Option<bool>,
+    ) -> napi::Result<JsObject> {
+        let args = parse_ts_types::js_object_to_baml_value(env, args)?;
+
+        if !args.is_map() {
+            return Err(invalid_argument_error(&format!(
+                "Expected a map of arguments, got: {}",
+                args.r#type()
+            )));
+        }
+        let args_map = args.as_map_owned().unwrap();
+
+        let baml_runtime = self.inner.clone();
+        let ctx_mng = ctx.inner.clone();
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+
+        let fut = async move {
+            baml_runtime
+                .render_request(
+                    &function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    stream.unwrap_or(false),
+                )
+                .await
+                .map(HTTPRequest::from)
+                .map_err(|e| from_anyhow_error(e))
+        };
+
+        env.execute_tokio_future(fut, |&mut _, data| Ok(data))
+    }
+
+    #[napi]
+    pub fn render_request_sync(
+        &self,
+        env: Env,
+        function_name: String,
+        #[napi(ts_arg_type = "{ [string]: any }")] args: JsObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: Option<bool>,
+    ) -> napi::Result<HTTPRequest> {
+        let args = parse_ts_types::js_object_to_baml_value(env, args)?;
+
+        if !args.is_map() {
+            return Err(invalid_argument_error(&format!(
+                "Expected a map of arguments, got: {}",
+                args.r#type()
+            )));
+        }
+        let args_map = args.as_map_owned().unwrap();
+
+        self.inner
+            .render_request_sync(
+                &function_name,
+                &args_map,
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+                cb.map(|cb| &cb.inner),
+                stream.unwrap_or(false),
+            )
+            .map(HTTPRequest::from)
+            .map_err(|e| from_anyhow_error(e))
+    }
+
+    #[napi]
+    pub fn parse_llm_response(
+        &self,
+        function_name: String,
+        llm_response: String,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        allow_partials: Option<bool>,
+    ) -> napi::Result<FunctionResult> {
+        self.inner
+            .parse_llm_response(
+                &function_name,
+                llm_response,
+                allow_partials.unwrap_or(false),
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+                cb.map(|cb| &cb.inner),
+            )
+            .map(FunctionResult::from)
+            .map_err(|e| from_anyhow_error(e))
+    }
+
+    #[napi]
+    pub fn set_response_cache(&self, cache: Option<&ResponseCache>) {
+        self.inner
+            .set_response_cache(cache.map(|cache| cache.inner.clone()));
+    }
+
     #[napi]
     pub fn flush(&mut self, _env: Env) -> napi::Result<()> {
         self.inner.flush().map_err(|e| from_anyhow_error(e))
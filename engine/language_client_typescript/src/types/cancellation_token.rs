@@ -0,0 +1,24 @@
+use napi_derive::napi;
+
+crate::lang_wrapper!(CancellationToken, baml_runtime::CancellationToken);
+
+#[napi]
+impl CancellationToken {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: baml_runtime::CancellationToken::new(),
+        }
+    }
+
+    /// Stop every call this token was passed to.
+    #[napi]
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    #[napi]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
@@ -17,7 +17,8 @@ crate::lang_wrapper!(
     thread_safe,
     callback: Option<napi::Ref<()>>,
     tb: Option<baml_runtime::type_builder::TypeBuilder>,
-    cb: Option<baml_runtime::client_registry::ClientRegistry>
+    cb: Option<baml_runtime::client_registry::ClientRegistry>,
+    cancellation: baml_runtime::CancellationToken
 );
 
 impl FunctionResultStream {
@@ -28,6 +29,7 @@ impl FunctionResultStream {
         cb: Option<baml_runtime::client_registry::ClientRegistry>,
     ) -> Self {
         Self {
+            cancellation: inner.cancellation_token(),
             inner: std::sync::Arc::new(tokio::sync::Mutex::new(inner)),
             callback: event,
             tb,
@@ -53,6 +55,12 @@ impl FunctionResultStream {
         env.get_undefined()
     }
 
+    /// Stop the stream. Safe to call while `done` is running.
+    #[napi]
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
     #[napi(ts_return_type = "Promise<FunctionResult>")]
     pub fn done(&self, env: Env, rctx: &RuntimeContextManager) -> napi::Result<JsObject> {
         let inner = self.inner.clone();
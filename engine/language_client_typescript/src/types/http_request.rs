@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use napi_derive::napi;
+
+crate::lang_wrapper!(HTTPRequest, baml_runtime::HTTPRequest);
+
+#[napi]
+impl HTTPRequest {
+    #[napi(getter)]
+    pub fn get_url(&self) -> String {
+        self.inner.url.clone()
+    }
+
+    #[napi(getter)]
+    pub fn get_method(&self) -> String {
+        self.inner.method.clone()
+    }
+
+    #[napi(getter)]
+    pub fn get_headers(&self) -> HashMap<String, String> {
+        self.inner
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// The JSON body, as an object.
+    #[napi(getter)]
+    pub fn get_body(&self) -> serde_json::Value {
+        self.inner.body.clone()
+    }
+
+    #[napi(js_name = "toJSON")]
+    pub fn to_json(&self) -> napi::Result<serde_json::Value> {
+        Ok(serde_json::to_value(&self.inner)?)
+    }
+}
@@ -1,11 +1,16 @@
 mod lang_wrappers;
 
 pub mod audio;
+pub(crate) mod cancellation_token;
 pub(crate) mod client_registry;
 pub(crate) mod function_result_stream;
 pub(crate) mod function_results;
+pub(crate) mod http_request;
+pub(crate) mod response_cache;
 pub mod image;
+pub mod pdf;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod span;
 pub(crate) mod trace_stats;
 pub(crate) mod type_builder;
+pub mod video;
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use baml_runtime::{CacheFuture, ResponseCacheBackend};
+use napi::bindgen_prelude::Promise;
+use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction};
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+
+crate::lang_wrapper!(ResponseCache, baml_runtime::ResponseCache);
+
+#[napi]
+impl ResponseCache {
+    /// A cache that lives as long as the process.
+    #[napi(factory)]
+    pub fn memory(ttl_secs: Option<u32>) -> Self {
+        baml_runtime::ResponseCache::memory()
+            .with_ttl(ttl(ttl_secs))
+            .into()
+    }
+
+    /// A cache that stores one file per response in `path`.
+    #[napi(factory)]
+    pub fn directory(path: String, ttl_secs: Option<u32>) -> Self {
+        baml_runtime::ResponseCache::directory(path)
+            .with_ttl(ttl(ttl_secs))
+            .into()
+    }
+
+    /// A cache backed by user functions. Both must return promises.
+    #[napi(factory)]
+    pub fn callback(
+        env: Env,
+        #[napi(ts_arg_type = "(err: any, key: string) => Promise<string | null | undefined>")]
+        get: JsFunction,
+        #[napi(ts_arg_type = "(err: any, key: string, value: string) => Promise<void>")]
+        set: JsFunction,
+        ttl_secs: Option<u32>,
+    ) -> napi::Result<Self> {
+        let mut get =
+            env.create_threadsafe_function(&get, 0, |ctx: ThreadSafeCallContext<String>| {
+                Ok(vec![ctx.value])
+            })?;
+        let mut set = env.create_threadsafe_function(
+            &set,
+            0,
+            |ctx: ThreadSafeCallContext<(String, String)>| Ok(vec![ctx.value.0, ctx.value.1]),
+        )?;
+        // The cache shouldn't keep node alive.
+        get.unref(&env)?;
+        set.unref(&env)?;
+
+        Ok(baml_runtime::ResponseCache::new(CallbackCache { get, set })
+            .with_ttl(ttl(ttl_secs))
+            .into())
+    }
+}
+
+fn ttl(ttl_secs: Option<u32>) -> Option<Duration> {
+    ttl_secs.map(|secs| Duration::from_secs(secs.into()))
+}
+
+struct CallbackCache {
+    get: ThreadsafeFunction<String>,
+    set: ThreadsafeFunction<(String, String)>,
+}
+
+impl ResponseCacheBackend for CallbackCache {
+    fn get<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let value = self
+                .get
+                .call_async::<Promise<Option<String>>>(Ok(key.to_string()))
+                .await?
+                .await?;
+            Ok(value)
+        })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> CacheFuture<'a, ()> {
+        Box::pin(async move {
+            self.set
+                .call_async::<Promise<()>>(Ok((key.to_string(), value)))
+                .await?
+                .await?;
+            Ok(())
+        })
+    }
+}
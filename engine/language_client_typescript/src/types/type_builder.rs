@@ -1,3 +1,5 @@
+use crate::errors::throw_baml_schema_error;
+use crate::runtime::BamlRuntime;
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
 use napi_derive::napi;
@@ -40,6 +42,13 @@ impl TypeBuilder {
         }
     }
 
+    #[napi]
+    pub fn add_baml(&self, baml: String, rt: &BamlRuntime) -> napi::Result<()> {
+        self.inner
+            .add_baml(&baml, &rt.inner)
+            .map_err(|e| throw_baml_schema_error(&e))
+    }
+
     #[napi]
     pub fn list(&self, inner: &FieldType) -> FieldType {
         inner.inner.lock().unwrap().clone().as_list().into()
@@ -10,6 +10,8 @@ export declare class BamlStream<PartialOutputType, FinalOutputType> {
     private driveToCompletion;
     private driveToCompletionInBg;
     [Symbol.asyncIterator](): AsyncIterableIterator<PartialOutputType>;
+    /** Stop the stream. The iterator ends and `getFinalResponse` rejects. */
+    cancel(): void;
     getFinalResponse(): Promise<FinalOutputType>;
 }
 //# sourceMappingURL=stream.d.ts.map
\ No newline at end of file
@@ -53,6 +53,10 @@ class BamlStream {
             }
         }
     }
+    /** Stop the stream. The iterator ends and `getFinalResponse` rejects. */
+    cancel() {
+        this.ffiStream.cancel();
+    }
     async getFinalResponse() {
         const final = await this.driveToCompletionInBg();
         return this.finalCoerce(final.parsed());
@@ -1,4 +1,4 @@
-import { ClassPropertyBuilder as _ClassPropertyBuilder, EnumValueBuilder, FieldType, TypeBuilder as _TypeBuilder } from './native';
+import { ClassPropertyBuilder as _ClassPropertyBuilder, EnumValueBuilder, FieldType, TypeBuilder as _TypeBuilder, BamlRuntime } from './native';
 type IsLiteral<T extends string> = string extends T ? false : true;
 type NameOf<T extends string> = IsLiteral<T> extends true ? T : 'DynamicType';
 type CheckNever<T, TypeName extends string, Value extends string> = [T] extends [never] ? `Error: Attempt to add value '${Value}' which is already a part of '${NameOf<TypeName>}'.` : T;
@@ -8,11 +8,20 @@ export declare class TypeBuilder {
     private tb;
     protected classes: Set<string>;
     protected enums: Set<string>;
-    constructor({ classes, enums }: {
+    private runtime?;
+    constructor({ classes, enums, runtime }: {
         classes: Set<string>;
         enums: Set<string>;
+        runtime?: BamlRuntime;
     });
     _tb(): _TypeBuilder;
+    /**
+     * Adds the classes, enums and type aliases declared in `baml`.
+     *
+     * Throws a BamlSchemaError if the source doesn't validate against the types
+     * of the BAML project.
+     */
+    addBaml(baml: string): void;
     null(): FieldType;
     string(): FieldType;
     literalString(value: string): FieldType;
@@ -2,18 +2,38 @@
 Object.defineProperty(exports, "__esModule", { value: true });
 exports.EnumBuilder = exports.ClassBuilder = exports.TypeBuilder = void 0;
 const native_1 = require("./native");
+const index_1 = require("./index");
 class TypeBuilder {
     tb;
     classes;
     enums;
-    constructor({ classes, enums }) {
+    runtime;
+    constructor({ classes, enums, runtime }) {
         this.classes = classes;
         this.enums = enums;
+        this.runtime = runtime;
         this.tb = new native_1.TypeBuilder();
     }
     _tb() {
         return this.tb;
     }
+    /**
+     * Adds the classes, enums and type aliases declared in `baml`.
+     *
+     * Throws a BamlSchemaError if the source doesn't validate against the types
+     * of the BAML project.
+     */
+    addBaml(baml) {
+        if (!this.runtime) {
+            throw new Error('addBaml needs a TypeBuilder created with a runtime.');
+        }
+        try {
+            this.tb.addBaml(baml, this.runtime);
+        }
+        catch (error) {
+            throw index_1.BamlSchemaError.from(error);
+        }
+    }
     null() {
         return this.tb.null();
     }
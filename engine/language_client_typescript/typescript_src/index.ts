@@ -5,9 +5,14 @@ export {
   BamlImage as Image,
   ClientBuilder,
   BamlAudio as Audio,
+  BamlPdf as Pdf,
+  BamlVideo as Video,
   invoke_runtime_cli,
   ClientRegistry,
   BamlLogEvent,
+  CancellationToken,
+  ResponseCache,
+  HTTPRequest,
 } from './native'
 export { BamlStream } from './stream'
 export { BamlCtxManager } from './async_context_vars'
@@ -61,6 +66,43 @@ export class BamlValidationError extends Error {
   }
 }
 
+export interface BamlDiagnostic {
+  file_path: string
+  /** 0-based */
+  start_line: number
+  start_column: number
+  end_line: number
+  end_column: number
+  message: string
+}
+
+export class BamlSchemaError extends Error {
+  diagnostics: BamlDiagnostic[]
+
+  constructor(message: string, diagnostics: BamlDiagnostic[]) {
+    super(message)
+    this.name = 'BamlSchemaError'
+    this.diagnostics = diagnostics
+
+    Object.setPrototypeOf(this, BamlSchemaError.prototype)
+  }
+
+  static from(error: Error): BamlSchemaError | Error {
+    if (error.message.includes('BamlSchemaError')) {
+      try {
+        const errorData = JSON.parse(error.message)
+        if (errorData.type === 'BamlSchemaError') {
+          return new BamlSchemaError(errorData.message || error.message, errorData.diagnostics || [])
+        }
+      } catch (parseError) {
+        // If JSON parsing fails, fall back to the original error
+      }
+    }
+
+    return error
+  }
+}
+
 // Helper function to safely create a BamlValidationError
 export function createBamlValidationError(error: Error): BamlValidationError | Error {
   return BamlValidationError.from(error)
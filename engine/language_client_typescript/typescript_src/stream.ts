@@ -58,6 +58,11 @@ export class BamlStream<PartialOutputType, FinalOutputType> {
     }
   }
 
+  /** Stop the stream. The iterator ends and `getFinalResponse` rejects. */
+  cancel(): void {
+    this.ffiStream.cancel()
+  }
+
   async getFinalResponse(): Promise<FinalOutputType> {
     const final = await this.driveToCompletionInBg()
 
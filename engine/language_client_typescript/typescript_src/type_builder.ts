@@ -5,7 +5,9 @@ import {
   EnumValueBuilder,
   FieldType,
   TypeBuilder as _TypeBuilder,
+  BamlRuntime,
 } from './native'
+import { BamlSchemaError } from './index'
 
 type IsLiteral<T extends string> = string extends T ? false : true
 type NameOf<T extends string> = IsLiteral<T> extends true ? T : 'DynamicType'
@@ -21,10 +23,12 @@ export class TypeBuilder {
   private tb: _TypeBuilder
   protected classes: Set<string>
   protected enums: Set<string>
+  private runtime?: BamlRuntime
 
-  constructor({ classes, enums }: { classes: Set<string>; enums: Set<string> }) {
+  constructor({ classes, enums, runtime }: { classes: Set<string>; enums: Set<string>; runtime?: BamlRuntime }) {
     this.classes = classes
     this.enums = enums
+    this.runtime = runtime
     this.tb = new _TypeBuilder()
   }
 
@@ -32,6 +36,23 @@ export class TypeBuilder {
     return this.tb
   }
 
+  /**
+   * Adds the classes, enums and type aliases declared in `baml`.
+   *
+   * Throws a BamlSchemaError if the source doesn't validate against the types
+   * of the BAML project.
+   */
+  addBaml(baml: string): void {
+    if (!this.runtime) {
+      throw new Error('addBaml needs a TypeBuilder created with a runtime.')
+    }
+    try {
+      this.tb.addBaml(baml, this.runtime)
+    } catch (error) {
+      throw BamlSchemaError.from(error as Error)
+    }
+  }
+
   null(): FieldType {
     return this.tb.null()
   }
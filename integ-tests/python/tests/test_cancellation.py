@@ -0,0 +1,95 @@
+import sys
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import pytest
+from baml_py import BamlRuntime, CancellationToken, errors
+
+# The sync calls below block the calling thread, so cancelling them only works
+# if they release the GIL for the cancelling thread to run.
+
+SLOW_RESPONSE_SECS = 30
+CANCEL_AFTER_SECS = 0.5
+
+
+class SlowHandler(BaseHTTPRequestHandler):
+    def do_POST(self):
+        time.sleep(SLOW_RESPONSE_SECS)
+
+    def log_message(self, format, *args):
+        pass
+
+
+@pytest.fixture
+def runtime():
+    server = ThreadingHTTPServer(("127.0.0.1", 0), SlowHandler)
+    server.daemon_threads = True
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    base_url = f"http://127.0.0.1:{server.server_address[1]}/v1"
+
+    yield BamlRuntime.from_files(
+        "baml_src",
+        {
+            "main.baml": f"""
+client<llm> Slow {{
+  provider openai-generic
+  options {{
+    base_url "{base_url}"
+    model "slow"
+  }}
+}}
+
+function Echo(input: string) -> string {{
+  client Slow
+  prompt #"{{{{ input }}}}"#
+}}
+"""
+        },
+        {},
+    )
+
+    server.shutdown()
+
+
+def cancel_later(cancel):
+    timer = threading.Timer(CANCEL_AFTER_SECS, cancel)
+    timer.start()
+    return timer
+
+
+def test_cancel_sync_call_from_another_thread(runtime: BamlRuntime):
+    token = CancellationToken()
+    timer = cancel_later(token.cancel)
+
+    start = time.monotonic()
+    result = runtime.call_function_sync(
+        "Echo",
+        {"input": "hi"},
+        runtime.create_context_manager(),
+        None,
+        None,
+        token,
+    )
+    elapsed = time.monotonic() - start
+    timer.join()
+
+    assert elapsed < SLOW_RESPONSE_SECS / 2
+    assert not result.is_ok()
+    module = sys.modules[__name__]
+    with pytest.raises(errors.BamlCancelledError):
+        result.cast_to(module, module)
+
+
+def test_cancel_sync_stream_from_another_thread(runtime: BamlRuntime):
+    ctx = runtime.create_context_manager()
+    stream = runtime.stream_function_sync("Echo", {"input": "hi"}, None, ctx, None, None)
+    timer = cancel_later(stream.cancel)
+
+    start = time.monotonic()
+    result = stream.done(ctx)
+    elapsed = time.monotonic() - start
+    timer.join()
+
+    assert elapsed < SLOW_RESPONSE_SECS / 2
+    assert not result.is_ok()
@@ -10,7 +10,7 @@ export const showTestsAtom = atom(false)
 export const showClientGraphAtom = atom(false)
 
 export type TestStatusType = 'queued' | 'running' | 'done' | 'error'
-export type DoneTestStatusType = 'passed' | 'llm_failed' | 'parse_failed' | 'error'
+export type DoneTestStatusType = 'passed' | 'llm_failed' | 'parse_failed' | 'expectation_failed' | 'error'
 export type TestState =
   | {
       status: 'queued'
@@ -44,6 +44,7 @@ export const statusCountAtom = atom({
     passed: 0,
     llm_failed: 0,
     parse_failed: 0,
+    expectation_failed: 0,
     error: 0,
   },
   error: 0,
@@ -92,6 +93,7 @@ export const useRunHooks = () => {
             passed: 0,
             llm_failed: 0,
             parse_failed: 0,
+            expectation_failed: 0,
             error: 0,
           },
           error: 0,
@@ -152,6 +154,8 @@ export const useRunHooks = () => {
                 response_status = 'llm_failed'
               } else if (status === 2) {
                 response_status = 'parse_failed'
+              } else if (status === 4) {
+                response_status = 'expectation_failed'
               } else {
                 response_status = 'error'
               }
@@ -57,6 +57,8 @@ const TestStatusMessage: React.FC<{ testStatus: DoneTestStatusType }> = ({ testS
       return <div className='text-vscode-testing-iconFailed'>LLM Failed</div>
     case 'parse_failed':
       return <div className='text-vscode-testing-iconFailed'>Parse Failed</div>
+    case 'expectation_failed':
+      return <div className='text-vscode-testing-iconFailed'>Expectation Failed</div>
     case 'error':
       return <div className='text-vscode-testing-iconFailed'>Unable to run</div>
   }
@@ -98,8 +100,10 @@ const TestStatusIcon: React.FC<{
   )
 }
 
-type FilterValues = 'queued' | 'running' | 'error' | 'llm_failed' | 'parse_failed' | 'passed'
-const filterAtom = atom(new Set<FilterValues>(['running', 'error', 'llm_failed', 'parse_failed', 'passed']))
+type FilterValues = 'queued' | 'running' | 'error' | 'llm_failed' | 'parse_failed' | 'expectation_failed' | 'passed'
+const filterAtom = atom(
+  new Set<FilterValues>(['running', 'error', 'llm_failed', 'parse_failed', 'expectation_failed', 'passed']),
+)
 
 const checkFilter = (filter: Set<FilterValues>, status: TestStatusType, test_status?: DoneTestStatusType) => {
   if (filter.size === 0) {
@@ -537,6 +541,12 @@ const TestStatusBanner: React.FC = () => {
         count={statusCounts.done.parse_failed}
         onClick={() => toggleFilter('parse_failed')}
       />
+      <FilterButton
+        selected={filter.has('expectation_failed')}
+        name='Expectation Failed'
+        count={statusCounts.done.expectation_failed}
+        onClick={() => toggleFilter('expectation_failed')}
+      />
       <FilterButton
         selected={filter.has('passed')}
         name='Passed'